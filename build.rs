@@ -49,7 +49,17 @@ fn shader_aliases() -> HashMap<&'static str, &'static str> {
     m
 }
 
-fn compile_shader(src: &str, dst: &str) {
+/// What happened to one shader in this build.
+enum ShaderBuild {
+    /// The `.spv` is newer than the GLSL and its includes.
+    UpToDate,
+    Compiled,
+    /// The GLSL changed but glslc could not be run: the committed `.spv`
+    /// is used as is and may not match the source.
+    Stale,
+}
+
+fn compile_shader(src: &str, dst: &str) -> ShaderBuild {
     let src_path = Path::new(src);
     let dst_path = Path::new(dst);
 
@@ -60,17 +70,15 @@ fn compile_shader(src: &str, dst: &str) {
             if let Ok(dst_time) = dst_meta.modified() {
                 if let Some(input_time) = newest_shader_input_time(src_path) {
                     if dst_time >= input_time {
-                        return;
+                        return ShaderBuild::UpToDate;
                     }
                 } else {
-                    return;
+                    return ShaderBuild::UpToDate;
                 }
             }
         }
     }
 
-    println!("cargo:warning=Compiling shader: {} -> {}", src, dst);
-
     let status = Command::new("glslc")
         .args([
             "-I",
@@ -83,12 +91,18 @@ fn compile_shader(src: &str, dst: &str) {
         .status();
 
     match status {
-        Ok(s) if s.success() => {}
-        Ok(s) => {
-            eprintln!("glslc failed for {} with exit code: {:?}", src, s.code());
+        Ok(s) if s.success() => {
+            println!("cargo:warning=Compiled shader: {} -> {}", src, dst);
+            ShaderBuild::Compiled
         }
+        // A shader that does not compile is a broken build, not a warning.
+        Ok(s) => panic!("glslc failed for {} with exit code: {:?}", src, s.code()),
         Err(e) => {
-            eprintln!("Could not run glslc (is Vulkan SDK installed?): {}", e);
+            println!(
+                "cargo:warning={} is newer than {} but glslc could not be run ({}); using the committed SPIR-V, which may be stale",
+                src, dst, e
+            );
+            ShaderBuild::Stale
         }
     }
 }
@@ -126,6 +140,7 @@ fn main() {
 
     let aliases = shader_aliases();
     let mut compiled = 0;
+    let mut stale = 0;
 
     for entry in walkdir::WalkDir::new(shaders_dir)
         .into_iter()
@@ -142,15 +157,24 @@ fn main() {
         let src = path.to_string_lossy().replace('\\', "/");
         let filename = path.file_name().unwrap().to_string_lossy().to_string();
 
-        if let Some(&alias_dst) = aliases.get(filename.as_str()) {
-            compile_shader(&src, alias_dst);
-            compiled += 1;
-        } else {
-            let dst = src.replace(&format!(".{}", ext_str), ".spv");
-            compile_shader(&src, &dst);
-            compiled += 1;
+        let dst = match aliases.get(filename.as_str()) {
+            Some(&alias_dst) => alias_dst.to_string(),
+            None => src.replace(&format!(".{}", ext_str), ".spv"),
+        };
+        match compile_shader(&src, &dst) {
+            ShaderBuild::UpToDate => {}
+            ShaderBuild::Compiled => compiled += 1,
+            ShaderBuild::Stale => stale += 1,
         }
     }
 
-    println!("cargo:warning=Compiled {} shaders", compiled);
+    if compiled > 0 {
+        println!("cargo:warning=Compiled {} shaders", compiled);
+    }
+    if stale > 0 {
+        println!(
+            "cargo:warning={} shaders were not rebuilt (glslc not found; install the Vulkan SDK)",
+            stale
+        );
+    }
 }
@@ -17,7 +17,7 @@
 // =============================================================================
 
 use reactor_vulkan::prelude::*;
-use reactor_vulkan::systems::scene::SceneObject;
+use reactor_vulkan::systems::scene::{SceneHandle, SceneObject};
 use reactor_vulkan::Vertex;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -55,7 +55,7 @@ struct BlenderLive {
     bridge_rx: Option<tokio::sync::mpsc::UnboundedReceiver<Message>>,
 
     /// Mapa: id de entidad (string Blender) → índice en ctx.scene.objects
-    entity_map: HashMap<String, usize>,
+    entity_map: HashMap<String, SceneHandle>,
 
     /// Mapa: id de luz (string Blender) → índice en ctx.lighting.lights
     light_map: HashMap<String, usize>,
@@ -452,9 +452,9 @@ impl BlenderLive {
 
         // ── CASO C: Sincronización de Geometría / Objetos MESH ──
         // Buscar si ya tenemos esta entidad mapeada
-        if let Some(&idx) = self.entity_map.get(&t.id) {
+        if let Some(&handle) = self.entity_map.get(&t.id) {
             // Aplicar la nueva matriz de transformación
-            if let Some(obj) = ctx.scene.get_mut(idx) {
+            if let Some(obj) = ctx.scene.get_mut(handle) {
                 let m = t.matrix;
                 obj.transform = Mat4::from_cols_array(&m);
 
@@ -811,9 +811,9 @@ impl BlenderLive {
                 }
             }
 
-            let idx = ctx.scene.objects.len();
-            ctx.scene.objects.push(obj);
-            self.entity_map.insert(t.id.clone(), idx);
+            let handle = ctx.scene.insert(obj);
+            self.entity_map.insert(t.id.clone(), handle);
+            let idx = ctx.scene.index_of_handle(handle).unwrap_or_default();
 
             let translation = transform.w_axis;
             println!(
@@ -110,7 +110,7 @@ impl ReactorApp for CubeDemo {
             }
         }
 
-        ctx.set_title(&self.fps.format_title(ctx, "🎲 REACTOR Cube Demo"));
+        self.fps.draw_overlay(ctx);
     }
 }
 
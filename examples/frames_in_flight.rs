@@ -0,0 +1,150 @@
+// =============================================================================
+// frames_in_flight.rs — CPU/GPU overlap benchmark
+// =============================================================================
+// A CPU-heavy scene (a few thousand cubes animated on the CPU every frame,
+// plus simulated gameplay work) rendered twice: first with one frame in
+// flight, where the CPU waits for the GPU to finish the previous frame before
+// recording the next one, then with three, where recording frame N+1 overlaps
+// the GPU rendering frame N. Average frame times for both runs are printed
+// and the example exits on its own.
+//
+//   cargo run --release --example frames_in_flight
+//
+// VSync is off so the swapchain does not hide the difference.
+// =============================================================================
+
+use reactor_vulkan::reactorapp::*;
+use reactor_vulkan::resources::primitives::Primitives;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cubes per side of the grid.
+const GRID: i32 = 48;
+/// Frames skipped after switching mode, then frames measured.
+const WARMUP_FRAMES: u32 = 60;
+const MEASURED_FRAMES: u32 = 600;
+/// Busy work per frame standing in for gameplay / physics.
+const SIMULATED_CPU_WORK: Duration = Duration::from_millis(4);
+
+struct Run {
+    frames_in_flight: usize,
+    frames: u32,
+    measured: Duration,
+}
+
+pub struct FramesInFlightBench {
+    cubes: Vec<usize>,
+    time: f32,
+    runs: Vec<Run>,
+    last_frame: Option<Instant>,
+}
+
+impl FramesInFlightBench {
+    pub fn new() -> Self {
+        Self {
+            cubes: Vec::new(),
+            time: 0.0,
+            runs: vec![
+                Run { frames_in_flight: 1, frames: 0, measured: Duration::ZERO },
+                Run { frames_in_flight: 3, frames: 0, measured: Duration::ZERO },
+            ],
+            last_frame: None,
+        }
+    }
+
+    fn report(&self) {
+        println!();
+        println!("frames in flight | avg frame time | fps");
+        for run in &self.runs {
+            let avg = run.measured / MEASURED_FRAMES;
+            println!("{:>16} | {:>11.2} ms | {:>5.0}", run.frames_in_flight, avg.as_secs_f64() * 1000.0, 1.0 / avg.as_secs_f64());
+        }
+        let serial = self.runs[0].measured.as_secs_f64();
+        let overlapped = self.runs[1].measured.as_secs_f64();
+        println!("speedup: {:.2}x", serial / overlapped);
+    }
+}
+
+impl Default for FramesInFlightBench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReactorApp for FramesInFlightBench {
+    fn config(&self) -> ReactorConfig {
+        ReactorConfig::new("REACTOR · Frames in flight benchmark")
+            .with_size(1280, 720)
+            .with_vsync(false)
+            .with_validation(false)
+            .with_frames_in_flight(1)
+    }
+
+    fn init(&mut self, ctx: &mut ReactorContext) {
+        let mut app = App::new(ctx);
+        app.camera().look_at(Vec3::new(0.0, 40.0, 70.0), Vec3::ZERO, 45.0);
+        app.lighting().default_three_point();
+
+        let (vertices, indices) = Primitives::cube();
+        let first = app
+            .mesh()
+            .vertices(&vertices)
+            .indices(&indices)
+            .use_cookbook_forward_material()
+            .spawn()
+            .expect("cube spawn");
+        let (mesh, material) = {
+            let object = ctx.scene.get(first).expect("spawned cube");
+            (Arc::clone(&object.mesh), Arc::clone(&object.material))
+        };
+
+        self.cubes.push(first);
+        for _ in 1..GRID * GRID {
+            let object = SceneObject::new(mesh.clone(), material.clone(), Mat4::IDENTITY);
+            self.cubes.push(ctx.scene.add(object));
+        }
+        println!("Benchmarking {} cubes, {} warm-up + {} measured frames per run...", self.cubes.len(), WARMUP_FRAMES, MEASURED_FRAMES);
+    }
+
+    fn update(&mut self, ctx: &mut ReactorContext) {
+        let now = Instant::now();
+        let frame_time = self.last_frame.map(|last| now - last);
+        self.last_frame = Some(now);
+
+        // Simulated CPU load: per-object animation plus fixed busy work.
+        self.time += 1.0 / 60.0;
+        for (i, &index) in self.cubes.iter().enumerate() {
+            let x = (i as i32 % GRID - GRID / 2) as f32 * 1.5;
+            let z = (i as i32 / GRID - GRID / 2) as f32 * 1.5;
+            let y = (self.time * 2.0 + x * 0.2 + z * 0.3).sin();
+            if let Some(object) = ctx.scene.get_mut(index) {
+                object.transform = Mat4::from_translation(Vec3::new(x, y, z)) * Mat4::from_rotation_y(self.time + i as f32);
+            }
+        }
+        let busy_until = Instant::now() + SIMULATED_CPU_WORK;
+        while Instant::now() < busy_until {
+            std::hint::spin_loop();
+        }
+
+        let Some(run) = self.runs.iter_mut().find(|run| run.frames < WARMUP_FRAMES + MEASURED_FRAMES) else {
+            if !ctx.reactor.exit_requested {
+                self.report();
+            }
+            ctx.reactor.exit_requested = true;
+            return;
+        };
+        if ctx.frames_in_flight() != run.frames_in_flight {
+            ctx.set_frames_in_flight(run.frames_in_flight).expect("valid frames in flight");
+        }
+        if let Some(frame_time) = frame_time {
+            if run.frames >= WARMUP_FRAMES {
+                run.measured += frame_time;
+            }
+        }
+        run.frames += 1;
+    }
+}
+
+fn main() {
+    reactor_vulkan::reactorapp::launch(FramesInFlightBench::new());
+}
@@ -79,7 +79,7 @@ impl ReactorApp for IblBakeDemo {
                 println!("\x1b[90m    layout(set=1, binding=0) uniform samplerCube u_ibl_irradiance;\x1b[0m");
                 println!("\x1b[90m    layout(set=1, binding=1) uniform samplerCube u_ibl_prefiltered;\x1b[0m");
                 println!("\x1b[90m    layout(set=1, binding=2) uniform sampler2D   u_ibl_brdf_lut;\x1b[0m");
-                println!("\x1b[90m    layout(set=1, binding=3) uniform IblParams {{ float max_mip; }};\x1b[0m");
+                println!("\x1b[90m    layout(set=1, binding=3) uniform IblParams {{ float max_mip; float intensity; }};\x1b[0m");
                 println!();
                 println!("\x1b[32m  ESC para salir.\x1b[0m");
                 // Conserva las texturas hasta el shutdown — el Drop libera los handles.
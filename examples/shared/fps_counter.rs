@@ -1,6 +1,7 @@
-//! Smoothed FPS counter + title formatter.
+//! Smoothed FPS counter + title/on-screen formatter.
 
 use reactor_vulkan::app::ReactorContext;
+use reactor_vulkan::Vec3;
 
 pub struct FpsCounter {
     smoothed: f32,
@@ -26,4 +27,10 @@ impl FpsCounter {
     pub fn format_title(&mut self, ctx: &ReactorContext, prefix: &str) -> String {
         format!("{} | FPS: {:.0}", prefix, self.update(ctx))
     }
+
+    /// Draws the counter in the top-left corner of the next frame.
+    pub fn draw_overlay(&mut self, ctx: &mut ReactorContext) {
+        let fps = self.update(ctx);
+        ctx.draw_text(8.0, 8.0, 16.0, Vec3::new(1.0, 1.0, 0.4), &format!("FPS {:.0}", fps));
+    }
 }
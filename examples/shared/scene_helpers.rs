@@ -40,6 +40,12 @@ pub fn add_object(
         roughness: 0.5,
         emission: glam::Vec4::ZERO,
         anisotropy: 0.0,
+        instances: Vec::new(),
+        joint_matrices: Vec::new(),
+        cast_shadows: true,
+        receive_shadows: true,
+        culled: false,
+        id: 0,
     };
     Ok(ctx.scene.add(object))
 }
@@ -73,6 +79,12 @@ pub fn add_object_with_texture(
         roughness: 0.5,
         emission: glam::Vec4::ZERO,
         anisotropy: 0.0,
+        instances: Vec::new(),
+        joint_matrices: Vec::new(),
+        cast_shadows: true,
+        receive_shadows: true,
+        culled: false,
+        id: 0,
     };
     Ok(ctx.scene.add(object))
 }
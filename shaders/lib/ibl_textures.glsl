@@ -7,9 +7,11 @@
 //   layout(set = N, binding = 0) uniform samplerCube  u_ibl_irradiance;
 //   layout(set = N, binding = 1) uniform samplerCube  u_ibl_prefiltered;
 //   layout(set = N, binding = 2) uniform sampler2D    u_ibl_brdf_lut;
-//   layout(set = N, binding = 3) uniform IblParams { float max_mip; } ibl_params;
+//   layout(set = N, binding = 3) uniform IblParams { float max_mip; float intensity; } ibl_params;
 //
 // `max_mip` = nivel máximo del prefiltered cubemap (típicamente 4 para 5 mips).
+// `intensity` escala el resultado de `ibl_eval_textured` (`Reactor::set_environment`);
+// sin entorno el motor enlaza cubos blancos y `intensity` es el ambiente escalar.
 // =============================================================================
 #ifndef REACTOR_LIB_IBL_TEXTURES
 #define REACTOR_LIB_IBL_TEXTURES
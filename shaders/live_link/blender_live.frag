@@ -34,7 +34,7 @@ layout(set = 0, binding = 3) uniform sampler2D u_roughness_map;
 layout(set = REACTOR_IBL_SET, binding = 0) uniform samplerCube u_ibl_irradiance;
 layout(set = REACTOR_IBL_SET, binding = 1) uniform samplerCube u_ibl_prefiltered;
 layout(set = REACTOR_IBL_SET, binding = 2) uniform sampler2D   u_ibl_brdf_lut;
-layout(set = REACTOR_IBL_SET, binding = 3) uniform IblParams { float max_mip; } ibl_params;
+layout(set = REACTOR_IBL_SET, binding = 3) uniform IblParams { float max_mip; float intensity; } ibl_params;
 
 // Set 2: Cascaded Shadow Maps
 layout(set = 2, binding = 0) uniform sampler2DArray u_shadow_map;
@@ -479,6 +479,7 @@ void main() {
     } else {
         ambient = ibl_eval_textured(N, V, albedo, metallic, roughness, f0, ao, ibl_params.max_mip);
     }
+    ambient *= ibl_params.intensity;
 
     // ── Rim light ──────────────────────────────────────────────────────────
     vec3 rim = light_rim(N, V, vec3(0.65, 0.78, 1.0) * 0.5, 4.0, metallic);
@@ -23,15 +23,16 @@ layout(push_constant) uniform Constants {
     vec4 camera_pos;
     vec4 light_pos;
     vec4 color;
+    vec4 emission;
+    mat3 normal_matrix; // inversa traspuesta de mat3(model) (ObjectPushConstants)
 } push;
 
 void main() {
     vec4 wp = push.model * vec4(position, 1.0);
     gl_Position = push.mvp * vec4(position, 1.0);
 
-    // Para escala uniforme `mat3(model)` basta. Si Blender empieza a enviar
-    // escalas no uniformes habrá que pasar la inversa traspuesta del modelo.
-    vWorldNormal = normalize(mat3(push.model) * normal);
+    // Inversa traspuesta calculada en CPU: correcta también con escala no uniforme.
+    vWorldNormal = normalize(push.normal_matrix * normal);
     vWorldPos    = wp.xyz;
     vUV          = uv;
     vColor       = push.color;
@@ -14,6 +14,10 @@ layout(push_constant) uniform Constants {
     vec4 light_pos;    // .xyz = light direction, .w = roughness
     vec4 color;        // .rgb = base color, .a = anisotropy
     vec4 emission;     // .rgb = emission color, .a = intensity
+    vec4 normal0;      // normal matrix, one column per vec4
+    vec4 normal1;
+    vec4 normal2;
+    vec4 extra;        // .x = first joint (skinned), .y = 1 → no shadows received
 } push;
 
 // ── Set 2: Cascaded Shadow Maps ──
@@ -28,6 +32,24 @@ layout(set = 2, binding = 1) uniform ShadowData {
     uint enabled;
 } shadowUBO;
 
+// ── Set 3: LightingSystem (matches LightUniformData, MAX_LIGHTS = 128) ──
+struct LightData {
+    vec4 position;     // .xyz = position, .w = range
+    vec4 direction;    // .xyz = direction, .w = spot angle (radians)
+    vec4 color;        // .rgb = color * intensity, .w = intensity
+    uint light_type;   // 0 = directional, 1 = point, 2 = spot
+    uint cast_shadows;
+    uvec2 _padding;
+};
+layout(set = 3, binding = 0) uniform LightBlock {
+    vec4 ambient_color;
+    uint light_count;
+    uint _pad0;
+    uint _pad1;
+    uint _pad2;
+    LightData lights[128];
+} lightUBO;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // AAA CINEMATIC RENDERING PIPELINE — REACTOR ENGINE (Phase 1)
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -107,7 +129,7 @@ float pcssPcfFilter(vec3 projCoords, int cascadeIdx, float filterRadius, float t
 }
 
 float sampleCSM(vec3 worldPos, vec3 worldNormal) {
-    if (shadowUBO.enabled == 0u) return 1.0;
+    if (shadowUBO.enabled == 0u || push.extra.y > 0.5) return 1.0;
 
     // Select cascade based on view-space depth
     vec4 viewPos = push.mvp * vec4(worldPos, 1.0);
@@ -138,7 +160,7 @@ float sampleCSM(vec3 worldPos, vec3 worldNormal) {
         return 1.0;
     }
 
-    float texelSize = 1.0 / 2048.0;
+    float texelSize = shadowUBO.pcf_radius; // 1 / shadow map resolution
     float rotation = pcssRotationAngle(gl_FragCoord.xy);
 
     // Step 1: Blocker search
@@ -478,6 +500,30 @@ void main() {
         totalDiffuse += bleed * lightColor * attenuation * 0.15 * sFactor;
     }
 
+    // ─── 6b. LIGHTINGSYSTEM LIGHTS (Lambert + range attenuation) ───────
+    uint lightCount = min(lightUBO.light_count, 128u);
+    for (uint i = 0u; i < lightCount; ++i) {
+        LightData light = lightUBO.lights[i];
+        vec3 L;
+        float attenuation = 1.0;
+        if (light.light_type == 0u) {
+            L = normalize(-light.direction.xyz);
+        } else {
+            vec3 toLight = light.position.xyz - fragPos;
+            float dist = length(toLight);
+            L = toLight / max(dist, 0.0001);
+            float falloff = clamp(1.0 - dist / max(light.position.w, 0.0001), 0.0, 1.0);
+            attenuation = falloff * falloff / (1.0 + 0.09 * dist + 0.032 * dist * dist);
+            if (light.light_type == 2u) {
+                float cosOuter = cos(light.direction.w);
+                float cosInner = cos(light.direction.w * 0.8);
+                float theta = dot(-L, normalize(light.direction.xyz));
+                attenuation *= clamp((theta - cosOuter) / max(cosInner - cosOuter, 0.0001), 0.0, 1.0);
+            }
+        }
+        totalDiffuse += max(dot(N, L), 0.0) * light.color.rgb * attenuation;
+    }
+
     // ─── 7. FLASHLIGHT ─────────────────────────────────────────────────
     vec3 flashlightPos = push.camera_pos.xyz;
     vec3 flashlightDir = normalize(vec3(0.0, -0.05, -1.0));
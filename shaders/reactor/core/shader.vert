@@ -11,11 +11,15 @@ layout(push_constant) uniform Constants {
     mat4 mvp;
     mat4 model;
     vec4 camera_pos;
+    vec4 light_pos;
+    vec4 color;
+    vec4 emission;
+    mat3 normal_matrix; // inversa traspuesta de mat3(model) (ObjectPushConstants)
 } push;
 
 void main() {
     gl_Position = push.mvp * vec4(position, 1.0);
-    fragNormal = normalize(mat3(push.model) * normal);
+    fragNormal = normalize(push.normal_matrix * normal);
     fragPos = vec3(push.model * vec4(position, 1.0));
     fragUV = uv;
 }
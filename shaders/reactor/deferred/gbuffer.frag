@@ -14,10 +14,10 @@ layout(location = 3) out vec4 outMotionDepthFlags;
 layout(push_constant) uniform Constants {
     mat4 mvp;
     mat4 model;
-    mat4 prev_mvp;
     vec4 camera_pos;
     vec4 light_pos;
-    vec4 color;
+    vec4 color;        // .rgb = base color
+    vec4 emission;     // .rgb = emission color, .a = intensity
 } push;
 
 vec2 encode_octahedral(vec3 n) {
@@ -32,7 +32,7 @@ vec2 encode_octahedral(vec3 n) {
 void main() {
     vec3 normal = normalize(vWorldNormal);
     vec3 albedo = max(vColor.rgb, vec3(0.0));
-    float alpha = clamp(vColor.a, 0.0, 1.0);
+    float alpha = 1.0; // .a del color es anisotropía, no opacidad
     float metallic = clamp(push.camera_pos.w, 0.0, 1.0);
     float roughness = clamp(push.light_pos.w, 0.04, 1.0);
     float ao = 1.0;
@@ -41,6 +41,6 @@ void main() {
 
     outAlbedoAo = vec4(albedo, ao);
     outNormalMaterial = vec4(encode_octahedral(normal), metallic, roughness);
-    outEmissiveMaterial = vec4(0.0, 0.0, 0.0, material_id);
+    outEmissiveMaterial = vec4(push.emission.rgb * push.emission.a, material_id);
     outMotionDepthFlags = vec4(vMotion, gl_FragCoord.z, flags);
 }
@@ -10,7 +10,7 @@ layout(location = 2) out vec3 vWorldPos;
 layout(location = 3) out vec4 vColor;
 layout(location = 4) out vec2 vMotion;
 
-layout(set = 0, binding = 4) uniform MotionData {
+layout(set = 0, binding = 0) uniform MotionData {
     mat4 prev_view_proj;
 } motion;
 
@@ -18,20 +18,25 @@ layout(push_constant) uniform Constants {
     mat4 mvp;
     mat4 model;
     vec4 camera_pos;
+    vec4 light_pos;
+    vec4 color;
+    vec4 emission;
+    mat3 normal_matrix; // inversa traspuesta de mat3(model) (ObjectPushConstants)
 } push;
 
 void main() {
     vec4 world_pos = push.model * vec4(position, 1.0);
     vec4 clip_pos = push.mvp * vec4(position, 1.0);
-    vec4 prev_clip_pos = motion.prev_view_proj * vec4(position, 1.0);
+    vec4 prev_clip_pos = motion.prev_view_proj * world_pos;
 
+    // Motion en espacio UV (lo que TAA resta a su coordenada de historia).
     vec2 ndc = clip_pos.xy / max(clip_pos.w, 1e-6);
     vec2 prev_ndc = prev_clip_pos.xy / max(prev_clip_pos.w, 1e-6);
-    vMotion = ndc - prev_ndc;
+    vMotion = (ndc - prev_ndc) * 0.5;
 
     gl_Position = clip_pos;
-    vWorldNormal = normalize(mat3(push.model) * normal);
+    vWorldNormal = normalize(push.normal_matrix * normal);
     vWorldPos = world_pos.xyz;
     vUV = uv;
-    vColor = vec4(1.0);
+    vColor = push.color;
 }
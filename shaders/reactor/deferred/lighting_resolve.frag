@@ -3,35 +3,26 @@
 // =============================================================================
 // REACTOR · shaders/deferred/lighting_resolve.frag — Deferred Lighting Pass
 // =============================================================================
-// Lee los 4 attachments del G-Buffer y resuelve la iluminación PBR completa:
-//   • Cook-Torrance GGX + Burley diffuse
-//   • IBL split-sum (irradiance + prefiltered + BRDF LUT)
-//   • CSM + PCSS directional shadows
-//   • Kulla-Conty energy compensation
-//   • Clear Coat / Cloth / Hair detection automática
+// Lee los 4 attachments del G-Buffer + depth y resuelve la iluminación:
+//   • Cook-Torrance GGX + Burley diffuse (pbr.glsl)
+//   • Todas las luces del LightingSystem (set 3), sin re-dibujar geometría
+//   • CSM con PCF 3x3 para las direccionales con cast_shadows
 //
-// Este shader produce el mismo resultado visual que el forward path pero
-// permite evaluar cientos de luces sin re-dibujar geometría.
+// Mismo layout de sets que los materiales forward: 0 = G-Buffer, 1 = padding,
+// 2 = sombras, 3 = luces. Salida en HDR lineal, como shader.frag.
 // =============================================================================
 
 #include "pbr.glsl"
-#include "tonemap.glsl"
 
-// ── G-Buffer inputs ──
+// ── G-Buffer inputs (Set 0) ──
 layout(set = 0, binding = 0) uniform sampler2D gbAlbedoAo;       // RGB = albedo, A = AO
 layout(set = 0, binding = 1) uniform sampler2D gbNormalMaterial;  // RG = octahedral normal, B = metallic, A = roughness
-layout(set = 0, binding = 2) uniform sampler2D gbEmissiveMat;     // RGB = emissive, A = material flags
-layout(set = 0, binding = 3) uniform sampler2D gbMotionDepth;     // RG = motion vectors, B = linear depth, A = flags
+layout(set = 0, binding = 2) uniform sampler2D gbEmissiveMat;     // RGB = emissive, A = material id
+layout(set = 0, binding = 3) uniform sampler2D gbMotionDepth;     // RG = motion vectors, B = depth, A = flags
 layout(set = 0, binding = 4) uniform sampler2D gbDepth;           // Depth buffer
 
-// ── IBL Textures (Set 1) ──
-layout(set = 1, binding = 0) uniform samplerCube u_ibl_irradiance;
-layout(set = 1, binding = 1) uniform samplerCube u_ibl_prefiltered;
-layout(set = 1, binding = 2) uniform sampler2D   u_ibl_brdf_lut;
-layout(set = 1, binding = 3) uniform IblParams { float max_mip; } ibl_params;
-
-// ── Shadow Maps (Set 2) ──
-layout(set = 2, binding = 0) uniform sampler2DArray u_shadow_map;
+// ── Shadow Maps (Set 2, matches ShadowUniformData) ──
+layout(set = 2, binding = 0) uniform sampler2DArrayShadow shadowMap;
 layout(set = 2, binding = 1) uniform ShadowData {
     mat4 cascade_view_proj[4];
     vec4 cascade_splits;
@@ -39,21 +30,33 @@ layout(set = 2, binding = 1) uniform ShadowData {
     float shadow_bias;
     float normal_bias;
     float pcf_radius;
-    uint shadow_enabled;
-    float pcss_light_size;
-    uint  pcss_samples;
-    uint  shadow_debug_mode;
-    uint  _padding;
-} shadow;
-
+    uint enabled;
+} shadowUBO;
+
+// ── Set 3: LightingSystem (matches LightUniformData, MAX_LIGHTS = 128) ──
+struct LightData {
+    vec4 position;     // .xyz = position, .w = range
+    vec4 direction;    // .xyz = direction, .w = spot angle (radians)
+    vec4 color;        // .rgb = color * intensity, .w = intensity
+    uint light_type;   // 0 = directional, 1 = point, 2 = spot
+    uint cast_shadows;
+    uvec2 _padding;
+};
+layout(set = 3, binding = 0) uniform LightBlock {
+    vec4 ambient_color;
+    uint light_count;
+    uint _pad0;
+    uint _pad1;
+    uint _pad2;
+    LightData lights[128];
+} lightUBO;
+
+// ── Push Constants (matches DeferredLightingPushConstants) ──
 layout(push_constant) uniform DeferredPushConstants {
-    mat4 view_proj_inv;   // Inversa de la matriz vista-proyección para reconstruir world pos
+    mat4 view_proj_inv;   // Inversa de la vista-proyección para reconstruir world pos
+    mat4 view;            // Vista de cámara (selección de cascada)
     vec4 camera_pos;      // xyz = posición de cámara en mundo
-    vec4 sun_color;       // xyz = color del sol, w = intensidad
-    float depth_near;
-    float depth_far;
-    float ambient_intensity;
-    float _pad;
+    vec4 clear_color;     // Fondo sin geometría (Reactor::clear_color)
 } push;
 
 layout(location = 0) in vec2 vUV;
@@ -76,20 +79,49 @@ vec3 reconstruct_world_pos(vec2 uv, float depth) {
     return world.xyz / world.w;
 }
 
+float sample_csm(vec3 P, vec3 N) {
+    if (shadowUBO.enabled == 0u) return 1.0;
+
+    float viewDepth = -(push.view * vec4(P, 1.0)).z;
+    int cascadeIdx = 3;
+    for (int i = 0; i < 4; i++) {
+        if (viewDepth < shadowUBO.cascade_splits[i]) {
+            cascadeIdx = i;
+            break;
+        }
+    }
+
+    vec4 lightSpacePos = shadowUBO.cascade_view_proj[cascadeIdx] * vec4(P + N * shadowUBO.normal_bias, 1.0);
+    vec3 projCoords = lightSpacePos.xyz / lightSpacePos.w;
+    projCoords.xy = projCoords.xy * 0.5 + 0.5;
+    if (any(lessThan(projCoords, vec3(0.0))) || any(greaterThan(projCoords, vec3(1.0)))) {
+        return 1.0;
+    }
+
+    float shadow = 0.0;
+    for (int x = -1; x <= 1; x++) {
+        for (int y = -1; y <= 1; y++) {
+            vec2 offset = vec2(x, y) * shadowUBO.pcf_radius;
+            shadow += texture(shadowMap, vec4(projCoords.xy + offset, float(cascadeIdx), projCoords.z - shadowUBO.shadow_bias));
+        }
+    }
+    return shadow / 9.0;
+}
+
 void main() {
-    // ── Leer G-Buffer ──
-    vec4 albedo_ao_sample     = texture(gbAlbedoAo, vUV);
-    vec4 normal_material      = texture(gbNormalMaterial, vUV);
-    vec4 emissive_mat_sample  = texture(gbEmissiveMat, vUV);
-    float depth               = texture(gbDepth, vUV).r;
-
-    // Descartar pixels del cielo (depth = 0 en reverse-Z o 1 en forward)
-    if (depth <= 0.0001 || depth >= 0.9999) {
-        outColor = vec4(0.0, 0.0, 0.0, 1.0);
+    float depth = texture(gbDepth, vUV).r;
+
+    // Sin geometría: mismo color de fondo que el clear del path forward.
+    if (depth >= 1.0) {
+        outColor = push.clear_color;
         return;
     }
 
-    // ── Decodificar propiedades ──
+    // ── Leer G-Buffer ──
+    vec4 albedo_ao_sample    = texture(gbAlbedoAo, vUV);
+    vec4 normal_material     = texture(gbNormalMaterial, vUV);
+    vec4 emissive_mat_sample = texture(gbEmissiveMat, vUV);
+
     vec3  albedo    = albedo_ao_sample.rgb;
     float ao        = albedo_ao_sample.a;
     vec3  N         = decode_octahedral(normal_material.rg);
@@ -98,48 +130,39 @@ void main() {
     vec3  emissive  = emissive_mat_sample.rgb;
     vec3  f0        = mix(REACTOR_F0_DIEL, albedo, metallic);
 
-    // ── Reconstruir posición y vista ──
     vec3 P = reconstruct_world_pos(vUV, depth);
     vec3 V = normalize(push.camera_pos.xyz - P);
-    float NoV = max(dot(N, V), 0.0);
 
-    // ── Iluminación directa: Sun con CSM ──
+    // ── Luces del LightingSystem ──
     vec3 lo = vec3(0.0);
-    
-    if (shadow.shadow_enabled != 0) {
-        vec3 L_sun = -normalize(shadow.light_direction.xyz);
-        vec3 sun_radiance = push.sun_color.rgb * push.sun_color.w;
-        
-        BrdfSample sun_brdf = brdf_eval(N, V, L_sun, albedo, metallic, roughness, f0);
-        lo += (sun_brdf.diffuse + sun_brdf.specular) * sun_radiance;
+    uint lightCount = min(lightUBO.light_count, 128u);
+    for (uint i = 0u; i < lightCount; ++i) {
+        LightData light = lightUBO.lights[i];
+        vec3 L;
+        float attenuation = 1.0;
+        if (light.light_type == 0u) {
+            L = normalize(-light.direction.xyz);
+            if (light.cast_shadows != 0u) {
+                attenuation = sample_csm(P, N);
+            }
+        } else {
+            vec3 toLight = light.position.xyz - P;
+            float dist = length(toLight);
+            float range = max(light.position.w, 0.0001);
+            if (dist >= range) continue;
+            L = toLight / max(dist, 0.0001);
+            float falloff = clamp(1.0 - dist / range, 0.0, 1.0);
+            attenuation = falloff * falloff / (1.0 + 0.09 * dist + 0.032 * dist * dist);
+            if (light.light_type == 2u) {
+                float cosOuter = cos(light.direction.w);
+                float cosInner = cos(light.direction.w * 0.8);
+                float theta = dot(-L, normalize(light.direction.xyz));
+                attenuation *= clamp((theta - cosOuter) / max(cosInner - cosOuter, 0.0001), 0.0, 1.0);
+            }
+        }
+        lo += brdf_shade(N, V, L, light.color.rgb * attenuation, albedo, metallic, roughness, f0);
     }
 
-    // ── IBL (diffuse irradiance + specular prefiltered) ──
-    vec3 R = reflect(-V, N);
-    
-    // Diffuse IBL
-    vec3 irradiance = texture(u_ibl_irradiance, N).rgb;
-    vec3 F_ibl = F_SchlickRoughness(NoV, f0, roughness);
-    vec3 kd_ibl = (1.0 - F_ibl) * (1.0 - metallic);
-    vec3 diffuse_ibl = kd_ibl * irradiance * albedo;
-
-    // Specular IBL (split-sum approximation)
-    float lod = roughness * ibl_params.max_mip;
-    vec3 prefiltered = textureLod(u_ibl_prefiltered, R, lod).rgb;
-    vec2 env_brdf = texture(u_ibl_brdf_lut, vec2(NoV, roughness)).rg;
-    vec3 specular_ibl = prefiltered * (F_ibl * env_brdf.x + env_brdf.y);
-
-    // Energy compensation (Kulla-Conty)
-    vec3 E_comp = energy_compensation(f0, env_brdf);
-    specular_ibl *= E_comp;
-
-    // Specular AO
-    float spec_ao = specular_AO(NoV, ao, roughness);
-
-    vec3 ambient = (diffuse_ibl * ao + specular_ibl * spec_ao) * push.ambient_intensity;
-
-    // ── Composición final ──
-    vec3 color = lo + ambient + emissive;
-
-    outColor = vec4(color, roughness);
+    vec3 ambient = lightUBO.ambient_color.rgb * albedo * ao;
+    outColor = vec4(lo + ambient + emissive, roughness);
 }
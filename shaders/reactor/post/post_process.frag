@@ -7,8 +7,10 @@ layout(binding = 0) uniform sampler2D screenTexture;
 layout(binding = 1) uniform sampler2D bloomTexture;
 layout(binding = 2) uniform sampler2D depthTexture;
 
-layout(binding = 3) readonly buffer ExposureBuffer {
+layout(binding = 3) buffer ExposureBuffer {
     float current_exposure;
+    // Bordes suavizados por el AA este frame (sólo con AA_FLAG_COUNT_EDGES).
+    uint aa_edge_count;
 };
 
 layout(binding = 4) uniform sampler2D lutTexture;
@@ -73,6 +75,16 @@ layout(push_constant) uniform PostProcessSettings {
 
     // Motion Blur (Feature F)
     float motion_blur_strength;
+
+    // Tonemapper (0 = AgX, 1 = Reinhard, 2 = ACES, 3 = Uncharted 2)
+    uint tonemapper;
+
+    // Anti-aliasing (FXAA / ADead-AA)
+    float aa_edge_threshold;
+    float aa_edge_threshold_min;
+    float aa_edge_width;
+    float aa_smoothness;
+    uint aa_flags;
 } settings;
 
 // Effect indices (matching PostProcessEffect enum)
@@ -99,6 +111,9 @@ layout(push_constant) uniform PostProcessSettings {
 #define EFFECT_AUTO_EXPOSURE      (1u << 23)
 #define EFFECT_MOTION_BLUR        (1u << 24)
 #define EFFECT_GTAO               (1u << 25)
+#define EFFECT_ADEAD_AA           (1u << 26)
+
+#define AA_FLAG_COUNT_EDGES       1u
 
 float luminance(vec3 color) {
     return dot(color, vec3(0.2126, 0.7152, 0.0722));
@@ -153,6 +168,24 @@ vec3 aces_tonemap(vec3 color) {
     return clamp((color * (a * color + b)) / (color * (c * color + d) + e), 0.0, 1.0);
 }
 
+vec3 reinhard_tonemap(vec3 color) {
+    return color / (1.0 + color);
+}
+
+// Uncharted 2 filmic (John Hable), white point at 11.2
+vec3 _uncharted2_curve(vec3 x) {
+    const float A = 0.15;
+    const float B = 0.50;
+    const float C = 0.10;
+    const float D = 0.20;
+    const float E = 0.02;
+    const float F = 0.30;
+    return ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F;
+}
+vec3 uncharted2_tonemap(vec3 color) {
+    return clamp(_uncharted2_curve(color * 2.0) / _uncharted2_curve(vec3(11.2)), 0.0, 1.0);
+}
+
 vec3 sample_screen(vec2 uv) {
     return texture(screenTexture, clamp(uv, vec2(0.001), vec2(0.999))).rgb;
 }
@@ -698,6 +731,52 @@ vec3 recover_highlights(vec3 color) {
     return mix(color, compressed * (1.0 + luma * 0.18), mask);
 }
 
+void count_aa_edge() {
+    if ((settings.aa_flags & AA_FLAG_COUNT_EDGES) != 0u) {
+        atomicAdd(aa_edge_count, 1u);
+    }
+}
+
+// ADead-AA: anti-aliasing por distancia al borde. El gradiente de luma (Sobel)
+// da la normal del borde y, junto con el salto de luma, la distancia con signo
+// del píxel al punto medio del borde, como un SDF. La parte del píxel que cae
+// al otro lado se mezcla con el vecino de ese lado.
+bool adead_edge_aa(vec2 uv, vec2 texelSize, inout vec3 color) {
+    float lumaM  = luminance(color);
+    float lumaN  = luminance(texture(screenTexture, uv + vec2( 0.0, -1.0) * texelSize).rgb);
+    float lumaS  = luminance(texture(screenTexture, uv + vec2( 0.0,  1.0) * texelSize).rgb);
+    float lumaE  = luminance(texture(screenTexture, uv + vec2( 1.0,  0.0) * texelSize).rgb);
+    float lumaW  = luminance(texture(screenTexture, uv + vec2(-1.0,  0.0) * texelSize).rgb);
+    float lumaNE = luminance(texture(screenTexture, uv + vec2( 1.0, -1.0) * texelSize).rgb);
+    float lumaNW = luminance(texture(screenTexture, uv + vec2(-1.0, -1.0) * texelSize).rgb);
+    float lumaSE = luminance(texture(screenTexture, uv + vec2( 1.0,  1.0) * texelSize).rgb);
+    float lumaSW = luminance(texture(screenTexture, uv + vec2(-1.0,  1.0) * texelSize).rgb);
+
+    float lumaMin = min(lumaM, min(min(lumaN, lumaS), min(lumaE, lumaW)));
+    float lumaMax = max(lumaM, max(max(lumaN, lumaS), max(lumaE, lumaW)));
+    if (lumaMax - lumaMin < max(settings.aa_edge_threshold_min, lumaMax * settings.aa_edge_threshold)) {
+        return false;
+    }
+
+    // Sobel / 8: variación de luma por píxel, apuntando hacia el lado claro.
+    vec2 gradient = vec2(
+        (lumaNE + 2.0 * lumaE + lumaSE) - (lumaNW + 2.0 * lumaW + lumaSW),
+        (lumaSW + 2.0 * lumaS + lumaSE) - (lumaNW + 2.0 * lumaN + lumaNE)) * 0.125;
+    float slope = length(gradient);
+    if (slope < 1e-4) {
+        return false;
+    }
+
+    // Distancia en píxeles al borde, positiva en el lado claro.
+    float dist = (lumaM - 0.5 * (lumaMin + lumaMax)) / slope;
+    float width = max(settings.aa_edge_width, 0.5);
+    float spill = clamp(0.5 - abs(dist) / width, 0.0, 0.5) * settings.aa_smoothness;
+    vec2 across = -sign(dist) * gradient / slope;
+    vec3 other = texture(screenTexture, uv + across * texelSize).rgb;
+    color = mix(color, other, clamp(spill, 0.0, 0.5));
+    return true;
+}
+
 float rect_mask(vec2 uv, vec2 center, vec2 halfSize, float softness) {
     vec2 d = abs(uv - center) - halfSize;
     float outside = length(max(d, 0.0));
@@ -902,9 +981,8 @@ void main() {
         float lumaRange = lumaMax - lumaMin;
         
         // Edge check
-        float edgeThreshold = 0.125;
-        float edgeThresholdMin = 0.0625;
-        if (lumaRange >= max(edgeThresholdMin, lumaMax * edgeThreshold)) {
+        if (lumaRange >= max(settings.aa_edge_threshold_min, lumaMax * settings.aa_edge_threshold)) {
+            count_aa_edge();
             float lumaNS = lumaNW + lumaNE - lumaSW - lumaSE;
             float lumaWE = lumaNW - lumaNE + lumaSW - lumaSE;
             
@@ -933,6 +1011,13 @@ void main() {
         }
     }
 
+    // 5b. ADead-AA (SDF edge resolve; FXAA wins if both bits are set)
+    if ((settings.effect_mask & EFFECT_ADEAD_AA) != 0 && (settings.effect_mask & EFFECT_FXAA) == 0) {
+        if (adead_edge_aa(uv, texelSize, color)) {
+            count_aa_edge();
+        }
+    }
+
     // 6. Vignette (Dark edges)
     if ((settings.effect_mask & EFFECT_VIGNETTE) != 0) {
         vec2 distVec = uv - 0.5;
@@ -1022,17 +1107,25 @@ void main() {
         color = 1.0 - color;
     }
 
-    // 16. Tone Mapping & Exposure (AgX — cinematic SDR, matches Blender output)
+    // 16. Tone Mapping & Exposure (AgX by default — cinematic SDR, matches Blender output)
     if ((settings.effect_mask & EFFECT_TONEMAP) != 0) {
         if ((settings.effect_mask & EFFECT_AUTO_EXPOSURE) != 0) {
             color *= current_exposure;
         } else {
             color *= settings.exposure;
         }
-        color = agx_tonemap(color);
-        // Subtle saturation recovery post-tonemap (AgX is deliberately neutral)
-        float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
-        color = mix(vec3(luma), color, 1.08);
+        if (settings.tonemapper == 1u) {
+            color = reinhard_tonemap(color);
+        } else if (settings.tonemapper == 2u) {
+            color = aces_tonemap(color);
+        } else if (settings.tonemapper == 3u) {
+            color = uncharted2_tonemap(color);
+        } else {
+            color = agx_tonemap(color);
+            // Subtle saturation recovery post-tonemap (AgX is deliberately neutral)
+            float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+            color = mix(vec3(luma), color, 1.08);
+        }
     }
 
     // 17. Gamma Correction
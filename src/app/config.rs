@@ -2,6 +2,7 @@
 pub enum RendererMode {
     #[default]
     Forward,
+    /// G-Buffer + iluminación a pantalla completa. Fuerza `msaa_samples = 1`.
     Deferred,
     RayTracing,
 }
@@ -19,6 +20,23 @@ pub struct ReactorConfig {
     pub renderer: RendererMode,
     pub physics_hz: u32,
     pub scene: Option<String>,
+    /// Carga `VK_LAYER_KHRONOS_validation` y captura sus mensajes
+    /// (`ReactorContext::validation_messages`). Por defecto sólo en debug.
+    pub validation: bool,
+    /// Frames que la CPU graba por delante de la GPU (1..=3). Con 1 cada
+    /// frame espera a que termine el anterior.
+    pub frames_in_flight: usize,
+    /// Reutiliza pipelines compilados en ejecuciones anteriores (cache en
+    /// disco). `REACTOR_PIPELINE_CACHE=0` lo desactiva sin recompilar.
+    pub pipeline_cache: bool,
+    /// GPU a usar cuando hay varias (índice o parte del nombre). Uno que no
+    /// existe o no sirve avisa y deja la elección automática. `REACTOR_GPU`
+    /// tiene prioridad.
+    pub gpu: crate::utils::gpu_detector::GpuPreference,
+    /// Carga `ctx.editor_settings` al arrancar y las guarda al salir
+    /// (`EditorSettings::default_path`). Apagado: se usan las de por defecto
+    /// y no se escribe nada.
+    pub editor_settings: bool,
 }
 
 impl ReactorConfig {
@@ -34,6 +52,11 @@ impl ReactorConfig {
     pub fn with_renderer(mut self, renderer: RendererMode) -> Self { self.renderer = renderer; self }
     pub fn with_physics_hz(mut self, hz: u32) -> Self { self.physics_hz = hz; self }
     pub fn with_scene(mut self, scene: &str) -> Self { self.scene = Some(scene.to_string()); self }
+    pub fn with_validation(mut self, validation: bool) -> Self { self.validation = validation; self }
+    pub fn with_frames_in_flight(mut self, frames: usize) -> Self { self.frames_in_flight = frames; self }
+    pub fn with_pipeline_cache(mut self, enabled: bool) -> Self { self.pipeline_cache = enabled; self }
+    pub fn with_gpu(mut self, gpu: crate::utils::gpu_detector::GpuPreference) -> Self { self.gpu = gpu; self }
+    pub fn with_editor_settings(mut self, enabled: bool) -> Self { self.editor_settings = enabled; self }
 }
 
 impl Default for ReactorConfig {
@@ -50,6 +73,11 @@ impl Default for ReactorConfig {
             renderer: RendererMode::default(),
             physics_hz: 0,
             scene: None,
+            validation: cfg!(debug_assertions),
+            frames_in_flight: 3,
+            pipeline_cache: true,
+            gpu: Default::default(),
+            editor_settings: false,
         }
     }
 }
@@ -15,33 +15,74 @@ pub struct ReactorContext {
     pub time: Time,
     pub config: ReactorConfig,
     pub camera: crate::scene::camera::Camera,
+    /// Estado de `camera_fly_update`; toma yaw/pitch de `camera` en su primer uso.
+    pub fly_camera: crate::systems::camera::FlyCameraController,
+    /// Estado de `camera_orbit_update`. `None` hasta su primer uso, que orbita
+    /// alrededor del punto a 10 unidades delante de la cámara.
+    pub orbit_camera: Option<crate::systems::camera::OrbitCameraController>,
     pub scene: crate::systems::scene::Scene,
     pub lighting: crate::systems::lighting::LightingSystem,
     pub physics: crate::systems::physics::PhysicsWorld,
+    /// Acciones y ejes con nombre (`action_down("jump")`, `axis_value("move_x")`).
+    pub actions: crate::systems::input::ActionMap,
     pub culling: crate::systems::frustum::CullingSystem,
     pub debug: crate::graphics::debug_renderer::DebugRenderer,
+    pub text: crate::graphics::text::TextRenderer,
     pub asset_manager: AssetManager,
     pub gltf_loader: GltfLoader,
     pub asset_db: AssetDatabase,
     pub asset_hot_reload: Option<AssetHotReloadManager>,
     pub asset_loader_queue: AssetLoaderQueue,
+    /// Modelos y texturas en streaming; el runner integra al empezar cada
+    /// frame con su presupuesto (`stream_model`).
+    pub streaming: crate::resources::LoadQueue,
     pub audio: crate::systems::audio::AudioSystem,
+    /// Clips ligados a objetos/entidades; el runner los avanza tras `update`.
+    pub animation: crate::systems::animation::AnimationSystem,
     pub event_bus: crate::systems::event_bus::EventBus,
+    /// Deshacer/rehacer de transforms de `reactor.world` (gizmos, inspector).
+    pub history: crate::systems::transform_history::TransformHistory,
+    /// Entidades de `reactor.world` seleccionadas (comandos de consola, gizmos).
+    pub selection: Vec<crate::scene::ecs::Entity>,
+    /// Preferencias de herramientas; persistentes con `ReactorConfig::editor_settings`.
+    pub editor_settings: crate::app::editor_settings::EditorSettings,
     pub(crate) hot_reload_rx: Option<
         tokio::sync::mpsc::UnboundedReceiver<crate::resources::asset_hot_reload::AssetReloadEvent>,
     >,
     pub(crate) blob_shadow_mesh: Option<Arc<crate::resources::mesh::Mesh>>,
     pub(crate) blob_shadow_material: Option<Arc<crate::resources::material::Material>>,
+    /// Sistemas de partículas por id (`particles_spawn*`). El runner los
+    /// avanza tras `update` y vuelca sus instancias a su objeto del scene.
+    pub(crate) particles: std::collections::BTreeMap<u32, ParticleEmitter>,
+    pub(crate) next_particles_id: u32,
+    pub(crate) particle_mesh: Option<Arc<crate::resources::mesh::Mesh>>,
+    pub(crate) particle_material: Option<Arc<crate::resources::material::Material>>,
+    /// Material de los modelos de `streaming`; se crea con el primero.
+    pub(crate) stream_material: Option<Arc<crate::resources::material::Material>>,
+    /// Ventanas secundarias por id (`open_window`). Antes que `reactor`:
+    /// sus swapchains se destruyen antes que el device.
+    pub(crate) windows: std::collections::BTreeMap<u32, SecondaryWindow>,
+    /// Pedidas con `open_window`; el runner las crea tras el frame.
+    pub(crate) pending_windows: Vec<WindowRequest>,
+    pub(crate) next_window_id: u32,
     pub reactor: Reactor,
-    pub(crate) fixed_accumulator: f32,
+    /// Acumulador de `fixed_update` (`physics_hz`), con tope de pasos por frame.
+    pub(crate) fixed_timestep: crate::platform::time::FixedTimestep,
+    /// Estado del editor guardado por `play_enter`; `None` fuera de play mode.
+    pub(crate) play: Option<crate::systems::play_session::PlaySession>,
 }
 
 impl Drop for ReactorContext {
     fn drop(&mut self) {
-        self.scene.objects.clear();
+        self.scene.clear();
         self.scene.lights.clear();
         self.blob_shadow_mesh = None;
         self.blob_shadow_material = None;
+        self.particles.clear();
+        self.particle_mesh = None;
+        self.particle_material = None;
+        self.stream_material = None;
+        self.windows.clear();
         self.asset_manager.clear();
         self.asset_hot_reload = None;
         self.hot_reload_rx = None;
@@ -50,6 +91,26 @@ impl Drop for ReactorContext {
 
 // ── Context-type structs ─────────────────────────────────────────────────────
 
+/// Ventana secundaria: su destino de render y su input (teclado y ratón de
+/// esa ventana; el gamepad sólo lo lee la principal).
+pub struct SecondaryWindow {
+    pub target: crate::reactor::RenderTargetWindow,
+    pub input: Input,
+}
+
+/// Un `ParticleSystem` y el objeto instanciado del scene que lo dibuja.
+pub(crate) struct ParticleEmitter {
+    pub system: crate::systems::particles::ParticleSystem,
+    pub handle: crate::systems::scene::SceneHandle,
+}
+
+pub(crate) struct WindowRequest {
+    pub id: u32,
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct GltfBounds {
     pub min: glam::Vec3,
@@ -97,10 +158,29 @@ pub struct AssetPipelineStats {
     pub hot_reload: Option<crate::resources::HotReloadStats>,
     pub db: crate::resources::AssetDbStats,
     pub gltf_cache: crate::resources::GltfCacheStats,
+    pub streaming: crate::resources::StreamStats,
 }
 
 impl ReactorContext {
     pub fn input(&self) -> &Input { &self.reactor.input }
+    pub fn key_released(&self, key: winit::keyboard::KeyCode) -> bool { self.reactor.input.is_key_just_released(key) }
+    pub fn mouse_scroll(&self) -> f32 { self.reactor.input.scroll_delta() }
+    pub fn mouse_wheel(&self) -> glam::Vec2 { self.reactor.input.wheel_delta() }
+    pub fn cursor_mode(&self) -> crate::platform::CursorMode { self.reactor.input.cursor_mode() }
+    /// Con `Locked` el cursor se oculta y `input().mouse_delta()` pasa a ser movimiento crudo.
+    pub fn set_cursor_mode(&mut self, mode: crate::platform::CursorMode) {
+        self.reactor.input.set_cursor_mode(mode);
+        if self.reactor.input.is_focused() { mode.apply(&self.window); }
+    }
+    pub fn gamepad_count(&self) -> usize { self.reactor.input.gamepad().pad_count() }
+    pub fn gamepad_connected(&self, pad: usize) -> bool { self.reactor.input.gamepad().is_pad_connected(pad) }
+    pub fn gamepad_button(&self, pad: usize, button: crate::platform::GamepadButton) -> bool { self.reactor.input.gamepad().pad_button_down(pad, button) }
+    pub fn gamepad_axis(&self, pad: usize, axis: crate::platform::GamepadAxis) -> f32 { self.reactor.input.gamepad().pad_axis(pad, axis) }
+    pub fn set_gamepad_deadzone(&mut self, deadzone: f32) { self.reactor.input.gamepad_mut().deadzone = deadzone.clamp(0.0, 0.95); }
+    pub fn action_down(&self, name: &str) -> bool { self.actions.action_down(&self.reactor.input, name) }
+    pub fn action_pressed(&self, name: &str) -> bool { self.actions.action_pressed(&self.reactor.input, name) }
+    pub fn action_released(&self, name: &str) -> bool { self.actions.action_released(&self.reactor.input, name) }
+    pub fn axis_value(&self, name: &str) -> f32 { self.actions.axis_value(&self.reactor.input, name) }
 
     pub fn aspect_ratio(&self) -> f32 {
         let size = self.window.inner_size();
@@ -109,34 +189,141 @@ impl ReactorContext {
     }
     pub fn window_size(&self) -> (u32, u32) { let s = self.window.inner_size(); (s.width, s.height) }
     pub fn set_title(&self, title: &str) { self.window.set_title(title); }
+    /// Pide un tamaño interior en píxeles físicos. El `Resized` que sigue
+    /// recrea el swapchain; si el sistema lo aplica al momento también.
+    pub fn set_window_size(&mut self, width: u32, height: u32) -> crate::core::error::ReactorResult<()> {
+        if width == 0 || height == 0 {
+            return Err(recorded(crate::core::error::ReactorError::invalid_parameter(format!("Window size must be non-zero, got {}x{}", width, height))));
+        }
+        if self.window.request_inner_size(winit::dpi::PhysicalSize::new(width, height)).is_some() { self.reactor.resized = true; }
+        Ok(())
+    }
+    /// Esquina superior izquierda de la ventana en píxeles de escritorio (ignorado en Wayland).
+    pub fn set_window_position(&self, x: i32, y: i32) { self.window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y)); }
+    pub fn set_fullscreen(&mut self, mode: crate::platform::FullscreenMode) {
+        mode.apply(&self.window);
+        self.reactor.resized = true;
+    }
+    pub fn fullscreen_mode(&self) -> crate::platform::FullscreenMode { crate::platform::FullscreenMode::of(&self.window) }
+    pub fn monitor_count(&self) -> usize { self.window.available_monitors().count() }
+    pub fn monitor_info(&self, index: usize) -> Option<crate::utils::MonitorInfo> {
+        self.window.available_monitors().nth(index).map(|monitor| crate::utils::ResolutionDetector::monitor_info(&monitor))
+    }
 
     pub fn create_mesh(&self, vertices: &[crate::resources::vertex::Vertex], indices: &[u32])
         -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
-        self.reactor.create_mesh(vertices, indices).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.create_mesh(vertices, indices).map_err(recorded)
+    }
+    pub fn create_mesh_async(&self, vertices: &[crate::resources::vertex::Vertex], indices: &[u32]) -> crate::resources::mesh_upload::MeshTicket {
+        self.reactor.create_mesh_async(vertices, indices)
+    }
+    pub fn poll_ticket(&self, ticket: crate::resources::mesh_upload::MeshTicket)
+        -> Option<crate::core::error::ReactorResult<crate::resources::mesh::Mesh>> {
+        self.reactor.poll_ticket(ticket)
+    }
+    pub fn create_dynamic_mesh(&self, vertices: &[crate::resources::vertex::Vertex], indices: &[u32])
+        -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
+        self.reactor.create_dynamic_mesh(vertices, indices).map_err(recorded)
+    }
+    /// Replaces the mesh of scene object `object` (see `Reactor::update_mesh`)
+    /// and refreshes its `world_bounds`. Call from `update`, between frames.
+    pub fn update_mesh(&mut self, object: impl crate::systems::scene::SceneKey, vertices: &[crate::resources::vertex::Vertex], indices: &[u32])
+        -> crate::core::error::ReactorResult<()> {
+        let Some(object) = self.scene.get_mut(object) else {
+            return Err(recorded(crate::core::error::ReactorError::invalid_parameter("update_mesh: no such scene object")));
+        };
+        self.reactor.update_mesh(&mut object.mesh, vertices, indices).map_err(recorded)?;
+        object.refresh_bounds();
+        Ok(())
     }
     pub fn create_material(&self, vert_code: &[u32], frag_code: &[u32])
         -> crate::core::error::ReactorResult<crate::resources::material::Material> {
-        self.reactor.create_material(vert_code, frag_code).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.create_material(vert_code, frag_code).map_err(recorded)
+    }
+    pub fn create_instanced_material(&self, frag_code: &[u32])
+        -> crate::core::error::ReactorResult<crate::resources::material::Material> {
+        self.reactor.create_instanced_material(frag_code).map_err(recorded)
+    }
+    pub fn create_compute_job(&self, spirv: &[u32], storage_buffers: u32, push_constant_size: Option<u32>)
+        -> crate::core::error::ReactorResult<crate::compute::ComputeJob> {
+        crate::compute::ComputeJob::new(&self.reactor.context, spirv, storage_buffers, push_constant_size).map_err(recorded)
+    }
+    pub fn create_compute_buffer(&self, size: u64, extra_usage: ash::vk::BufferUsageFlags)
+        -> crate::core::error::ReactorResult<crate::graphics::buffer::Buffer> {
+        crate::compute::ComputeJob::create_buffer(&self.reactor.context, self.reactor.allocator.clone(), size, extra_usage).map_err(recorded)
+    }
+    pub fn dispatch_compute(&self, job: &crate::compute::ComputeJob, group_count: [u32; 3], push_constants: &[u8])
+        -> crate::core::error::ReactorResult<()> {
+        job.dispatch(&self.reactor.context, group_count, push_constants).map_err(recorded)
     }
     pub fn load_texture(&self, path: &str)
         -> crate::core::error::ReactorResult<crate::resources::texture::Texture> {
-        self.reactor.load_texture(path).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.load_texture(path).map_err(recorded)
+    }
+    pub fn load_texture_with(&self, path: &str, options: &crate::resources::texture::TextureOptions)
+        -> crate::core::error::ReactorResult<crate::resources::texture::Texture> {
+        self.reactor.load_texture_with(path, options).map_err(recorded)
+    }
+    pub fn load_cubemap(&self, faces: [&str; 6], options: &crate::resources::texture::TextureOptions)
+        -> crate::core::error::ReactorResult<crate::resources::texture::Cubemap> {
+        self.reactor.load_cubemap(faces, options).map_err(recorded)
+    }
+    pub fn load_cubemap_equirect(&self, path: &str, face_size: u32, options: &crate::resources::texture::TextureOptions)
+        -> crate::core::error::ReactorResult<crate::resources::texture::Cubemap> {
+        self.reactor.load_cubemap_equirect(path, face_size, options).map_err(recorded)
+    }
+    pub fn set_environment(&mut self, cubemap: std::sync::Arc<crate::resources::texture::Cubemap>, intensity: f32)
+        -> crate::core::error::ReactorResult<()> {
+        self.reactor.set_environment(cubemap, intensity).map_err(recorded)
     }
+    pub fn set_environment_intensity(&mut self, intensity: f32) { self.reactor.set_environment_intensity(intensity); }
+    pub fn clear_environment(&mut self) { self.reactor.clear_environment(); }
+    pub fn set_ambient_fallback(&mut self, intensity: f32) { self.reactor.set_ambient_fallback(intensity); }
     pub fn load_texture_bytes(&self, bytes: &[u8])
         -> crate::core::error::ReactorResult<crate::resources::texture::Texture> {
-        self.reactor.load_texture_bytes(bytes).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.load_texture_bytes(bytes).map_err(recorded)
     }
     pub fn create_solid_texture(&self, r: u8, g: u8, b: u8, a: u8)
         -> crate::core::error::ReactorResult<crate::resources::texture::Texture> {
-        self.reactor.create_solid_texture(r, g, b, a).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.create_solid_texture(r, g, b, a).map_err(recorded)
     }
     pub fn create_textured_material(&self, vert_code: &[u32], frag_code: &[u32], texture: &crate::resources::texture::Texture)
         -> crate::core::error::ReactorResult<crate::resources::material::Material> {
-        self.reactor.create_textured_material(vert_code, frag_code, texture).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.reactor.create_textured_material(vert_code, frag_code, texture).map_err(recorded)
     }
     pub fn base_shader_cookbook(&self) -> crate::base_shader::BaseShaderCookbook {
         crate::base_shader::BaseShaderCookbook::default()
     }
+    pub fn post_process_settings(&mut self) -> &mut crate::graphics::post_process::PostProcessSettings { &mut self.reactor.post_process.settings }
+    pub fn set_post_process_preset(&mut self, preset: crate::graphics::post_process::PostProcessPreset) { self.reactor.post_process.apply_preset(preset); }
+    pub fn set_post_process_effect(&mut self, effect: crate::graphics::post_process::PostProcessEffect, enabled: bool) { self.reactor.post_process.set_effect(effect, enabled); }
+    /// Exposición en EV (`0` = sin cambio); la usan el post-proceso y el resolve HDR.
+    pub fn set_exposure(&mut self, ev: f32) { self.reactor.set_exposure(ev); }
+    pub fn set_tonemapper(&mut self, tonemapper: crate::graphics::post_process::Tonemapper) { self.reactor.set_tonemapper(tonemapper); }
+    /// Off / FXAA / ADead-AA; se cambia en caliente, sin recrear el swapchain.
+    pub fn set_aa_mode(&mut self, mode: crate::graphics::post_process::AAMode) { self.reactor.set_aa_mode(mode); }
+    pub fn aa_mode(&self) -> crate::graphics::post_process::AAMode { self.reactor.aa_mode() }
+    pub fn set_aa_preset(&mut self, preset: crate::graphics::post_process::AAQualityPreset) { self.reactor.set_aa_preset(preset); }
+    pub fn set_aa_stats_enabled(&mut self, enabled: bool) -> bool { self.reactor.set_aa_stats_enabled(enabled) }
+    pub fn aa_stats(&self) -> Option<crate::graphics::post_process::AAStats> { self.reactor.aa_stats() }
+    pub fn set_clear_color(&mut self, color: glam::Vec4) { self.reactor.set_clear_color(color); }
+    /// Rectángulo normalizado `[x, y, w, h]` de la ventana donde se dibuja la escena.
+    pub fn set_viewport_rect(&mut self, x: f32, y: f32, width: f32, height: f32) { self.reactor.set_viewport_rect(x, y, width, height); }
+    /// Activa/desactiva las sombras de la luz direccional y fija la resolución del shadow map.
+    pub fn shadows_enable(&mut self, enabled: bool, resolution: u32) -> crate::core::error::ReactorResult<()> {
+        self.reactor.set_shadows_enabled(enabled);
+        self.reactor.set_shadow_resolution(resolution).map_err(recorded)
+    }
+    pub fn shadows_enabled(&self) -> bool { self.reactor.shadows_enabled() }
+    pub fn set_shadow_bias(&mut self, bias: f32, normal_bias: f32) { self.reactor.set_shadow_bias(bias, normal_bias); }
+    pub fn renderer_mode(&self) -> crate::app::config::RendererMode { self.reactor.renderer_mode() }
+    pub fn set_renderer_mode(&mut self, mode: crate::app::config::RendererMode) -> crate::core::error::ReactorResult<()> { self.reactor.set_renderer_mode(mode).map_err(recorded) }
+    pub fn render_stats(&self) -> crate::reactor::RenderStats { self.reactor.render_stats() }
+    pub fn frames_in_flight(&self) -> usize { self.reactor.frames_in_flight() }
+    pub fn set_frames_in_flight(&mut self, count: usize) -> crate::core::error::ReactorResult<()> { self.reactor.set_frames_in_flight(count).map_err(recorded) }
+    pub fn validation_messages(&self) -> Vec<crate::core::validation::ValidationMessage> { crate::core::validation::validation_messages() }
+    pub fn clear_validation_messages(&self) { crate::core::validation::clear_validation_messages(); }
+    pub fn pipeline_cache_stats(&self) -> crate::core::pipeline_cache::PipelineCacheStats { self.reactor.pipeline_cache_stats() }
     pub fn apply_base_shader(&mut self, cookbook: &crate::base_shader::BaseShaderCookbook) {
         cookbook.apply_to_post_process(&mut self.reactor.post_process);
     }
@@ -154,15 +341,15 @@ impl ReactorContext {
         -> crate::core::error::ReactorResult<crate::resources::material::Material> {
         self.reactor.create_pbr_material(&cookbook.blender_live_pbr.vertex, &cookbook.blender_live_pbr.fragment,
             ibl_set_layout, albedo, normal, metallic, roughness)
-            .map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+            .map_err(recorded)
     }
 
     pub fn load_obj(&self, path: &str) -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
         use crate::resources::model::ObjData;
-        let obj = ObjData::load(path).map_err(|_e| crate::core::error::ReactorError::file_not_found(path))?;
-        if obj.vertices.is_empty() { return Err(crate::core::error::ReactorError::invalid_format("OBJ file contains no vertices")); }
-        println!("📦 Loaded OBJ: {} vertices, {} triangles", obj.vertex_count(), obj.triangle_count());
-        self.reactor.create_mesh(&obj.vertices, &obj.indices).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        let obj = ObjData::load(path).map_err(|_e| crate::core::error::ReactorError::file_not_found(path)).map_err(recorded)?;
+        if obj.vertices.is_empty() { return Err(recorded(crate::core::error::ReactorError::invalid_format("OBJ file contains no vertices"))); }
+        log::info!("📦 Loaded OBJ: {} vertices, {} triangles", obj.vertex_count(), obj.triangle_count());
+        self.reactor.create_mesh(&obj.vertices, &obj.indices).map_err(recorded)
     }
     pub fn load_obj_with_material(&mut self, path: &str, material: Arc<crate::resources::material::Material>)
         -> crate::core::error::ReactorResult<u32> {
@@ -172,45 +359,295 @@ impl ReactorContext {
         Ok(index)
     }
 
+    /// Carga un fichero de escena (`.obj`, `.gltf`, `.glb`) en `self.scene` y devuelve
+    /// cuántos objetos añadió. Las rutas relativas se buscan en el directorio de
+    /// trabajo y después junto al ejecutable.
+    pub fn load_scene_file(&mut self, path: &str) -> crate::core::error::ReactorResult<usize> {
+        let resolved = resolve_asset_path(path).ok_or_else(|| recorded(crate::core::error::ReactorError::file_not_found(path)))?;
+        let ext = resolved.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+        let before = self.scene.objects.len();
+        match ext.as_str() {
+            "obj" => {
+                let material = Arc::new(self.default_material()?);
+                self.load_obj_with_material(&resolved.to_string_lossy(), material)?;
+            }
+            "gltf" | "glb" => { self.spawn_gltf(&resolved, glam::Mat4::IDENTITY)?; }
+            _ => return Err(recorded(crate::core::error::ReactorError::invalid_format(format!("Unsupported scene format: {}", resolved.display())))),
+        }
+        self.editor_settings.add_recent_scene(path);
+        Ok(self.scene.objects.len() - before)
+    }
+
+    /// Serializa transforms, visibilidad, cámara y luces (formato v1, JSON). Ver `SceneSnapshot`.
+    pub fn serialize_scene(&self) -> crate::core::error::ReactorResult<Vec<u8>> {
+        crate::systems::scene_snapshot::SceneSnapshot::capture(&self.scene, &self.camera, &self.lighting).to_bytes().map_err(recorded)
+    }
+    /// Restaura un blob de `serialize_scene` por índice; los desajustes de conteo sólo generan un warning.
+    pub fn deserialize_scene(&mut self, data: &[u8]) -> crate::core::error::ReactorResult<()> {
+        let snapshot = crate::systems::scene_snapshot::SceneSnapshot::from_bytes(data).map_err(recorded)?;
+        snapshot.restore(&mut self.scene, &mut self.camera, &mut self.lighting);
+        Ok(())
+    }
+    /// Guarda transforms, cámara, luces y las entidades de `reactor.world` en un fichero `.reactor` (el formato de `serialize_scene`).
+    pub fn save_scene(&self, path: impl AsRef<std::path::Path>) -> crate::core::error::ReactorResult<()> {
+        crate::systems::scene_snapshot::SceneSnapshot::capture(&self.scene, &self.camera, &self.lighting).with_world(&self.reactor.world).save(path.as_ref()).map_err(recorded)?;
+        log::info!("Scene saved to {}", path.as_ref().display());
+        Ok(())
+    }
+    /// Restaura un `.reactor` de `save_scene` sobre la escena actual (por índice, como `deserialize_scene`).
+    pub fn open_scene(&mut self, path: impl AsRef<std::path::Path>) -> crate::core::error::ReactorResult<()> {
+        let snapshot = crate::systems::scene_snapshot::SceneSnapshot::load(path.as_ref()).map_err(recorded)?;
+        snapshot.restore(&mut self.scene, &mut self.camera, &mut self.lighting);
+        snapshot.restore_world(&mut self.reactor.world);
+        self.history.clear();
+        log::info!("Scene loaded from {} ({} objects)", path.as_ref().display(), snapshot.objects.len());
+        Ok(())
+    }
+    /// Guarda `root` y sus descendientes de `reactor.world` como prefab (`.prefab`).
+    pub fn save_prefab(&self, root: crate::scene::ecs::Entity, path: impl AsRef<std::path::Path>) -> crate::core::error::ReactorResult<()> {
+        crate::systems::prefab::Prefab::capture(&self.reactor.world, root).and_then(|prefab| prefab.save(path.as_ref())).map_err(recorded)
+    }
+    /// Instancia el prefab de `path` en `reactor.world` con la raíz en `position`; devuelve la nueva raíz.
+    pub fn instantiate_prefab(&mut self, path: impl AsRef<std::path::Path>, position: glam::Vec3) -> crate::core::error::ReactorResult<crate::scene::ecs::Entity> {
+        crate::systems::prefab::Prefab::instantiate_file(&mut self.reactor.world, path, position).map_err(recorded)
+    }
+    /// Entra en play mode: guarda escena, entidades (`Transform`, `RigidBody`,
+    /// `Parent`) y animaciones, y la simulación sigue sobre ellas.
+    pub fn play_enter(&mut self) -> crate::core::error::ReactorResult<()> {
+        if self.play.is_some() { return Ok(()); }
+        self.history.seal();
+        self.play = Some(crate::systems::play_session::PlaySession::begin(&self.scene, &self.camera, &self.lighting, &self.reactor.world, &self.animation, self.fixed_timestep.accumulated()));
+        Ok(())
+    }
+    /// Sale de play mode restaurando exactamente lo guardado en `play_enter`
+    /// (las entidades creadas jugando se destruyen).
+    pub fn play_exit(&mut self) -> crate::core::error::ReactorResult<()> {
+        if let Some(session) = self.play.take() {
+            let accumulated = session.end(&mut self.scene, &mut self.camera, &mut self.lighting, &mut self.reactor.world, &mut self.animation);
+            self.fixed_timestep.set_accumulated(accumulated);
+        }
+        Ok(())
+    }
+    pub fn is_playing(&self) -> bool { self.play.is_some() }
+    /// Congela física, `fixed_update` y animaciones; `update` y `render` siguen.
+    pub fn play_pause(&mut self) { if let Some(session) = &mut self.play { session.pause(); } }
+    pub fn play_resume(&mut self) { if let Some(session) = &mut self.play { session.resume(); } }
+    pub fn is_paused(&self) -> bool { self.play.as_ref().is_some_and(|session| session.is_paused()) }
+    /// En pausa, avanza un tick fijo de simulación en el próximo frame.
+    pub fn play_step(&mut self) { if let Some(session) = &mut self.play { session.step(); } }
+    /// Paso fijo de la simulación (`physics_hz`, 60 Hz si es 0).
+    pub fn fixed_delta(&self) -> f32 { match self.config.physics_hz { 0 => 1.0 / 60.0, hz => 1.0 / hz as f32 } }
+    /// Fracción de paso fijo pendiente en `[0, 1)`: cuánto interpolar en `render`
+    /// entre los dos últimos estados de `fixed_update`. 0 sin `physics_hz`.
+    pub fn fixed_alpha(&self) -> f32 { if self.config.physics_hz > 0 { self.fixed_timestep.alpha() } else { 0.0 } }
+    /// Tiempo de simulación de este frame: `dt`, o lo que deje pasar la pausa de play mode.
+    pub(crate) fn simulation_delta(&mut self, dt: f32) -> f32 {
+        let fixed_dt = self.fixed_delta();
+        match &mut self.play { Some(session) => session.simulation_delta(dt, fixed_dt), None => dt }
+    }
+    /// Deshace el último paso de `history`; devuelve su descripción. En play
+    /// mode se rechaza (el snapshot de `play_exit` lo pisaría).
+    pub fn undo(&mut self) -> Option<String> {
+        if self.is_playing() { log::warn!("Undo is disabled in play mode"); return None; }
+        self.history.undo(&mut self.reactor.world)
+    }
+    pub fn redo(&mut self) -> Option<String> {
+        if self.is_playing() { log::warn!("Redo is disabled in play mode"); return None; }
+        self.history.redo(&mut self.reactor.world)
+    }
+
+    pub fn set_culling_enabled(&mut self, enabled: bool) { self.culling.set_enabled(enabled); }
+    /// Objetos dibujados / descartados por frustum culling en el último frame.
+    pub fn culling_stats(&self) -> (usize, usize) { (self.culling.visible_count(), self.culling.culled_count()) }
+    /// Vuelca las entidades de `reactor.world` al scene (`MeshRenderer`) y a `lighting` (`Light`).
+    pub fn sync_ecs_to_scene(&mut self) {
+        self.scene.sync_from_world(&self.reactor.world);
+        self.lighting.sync_from_world(&self.reactor.world);
+    }
     pub fn render_scene(&mut self) {
-        self.camera.set_aspect_ratio(self.window.inner_size().width as f32, self.window.inner_size().height as f32);
+        self.sync_ecs_to_scene();
+        // Aspecto del rectángulo de la escena (`set_viewport_rect`), no de la ventana.
+        let viewport = self.reactor.viewport_extent();
+        self.camera.set_aspect_ratio(viewport.width as f32, viewport.height as f32);
         let vp = self.camera.view_projection_matrix();
+        self.scene.cull(&mut self.culling, vp);
         self.reactor.camera_pos = self.camera.position;
         self.reactor.camera_view = self.camera.view_matrix();
         self.reactor.camera_proj = self.camera.projection_matrix();
         self.reactor.camera_near = self.camera.near;
         self.reactor.camera_far = self.camera.far;
         self.reactor.post_process.update_time(self.time.elapsed());
-        if let Err(e) = self.reactor.draw_scene(&self.scene, &vp) { eprintln!("REACTOR draw error: {}", e); }
+        self.reactor.set_debug_lines(self.debug.get_lines());
+        self.reactor.set_text(self.text.vertices());
+        if let Err(e) = self.reactor.draw_scene_lit(&self.scene, &vp, &self.lighting) { log::error!("REACTOR draw error: {}", e); }
+        self.debug.clear();
+        self.text.clear();
     }
     pub fn draw_scene_with_vp(&mut self, view_projection: &glam::Mat4) {
+        self.sync_ecs_to_scene();
+        self.scene.cull(&mut self.culling, *view_projection);
         self.reactor.camera_pos = self.camera.position;
         self.reactor.camera_view = self.camera.view_matrix();
         self.reactor.camera_proj = self.camera.projection_matrix();
         self.reactor.camera_near = self.camera.near;
         self.reactor.camera_far = self.camera.far;
         self.reactor.post_process.update_time(self.time.elapsed());
-        if let Err(e) = self.reactor.draw_scene(&self.scene, view_projection) { eprintln!("REACTOR draw error: {}", e); }
+        self.reactor.set_debug_lines(self.debug.get_lines());
+        self.reactor.set_text(self.text.vertices());
+        if let Err(e) = self.reactor.draw_scene_lit(&self.scene, view_projection, &self.lighting) { log::error!("REACTOR draw error: {}", e); }
+        self.debug.clear();
+        self.text.clear();
     }
-    pub fn draw_scene(&mut self, scene: &crate::systems::scene::Scene, view_projection: &glam::Mat4) {
+    /// Dibuja una escena externa con la cámara y luces del contexto. La escena
+    /// se culla aquí contra `view_projection` (refresca `world_bounds` y
+    /// `culled`), igual que la propia en `draw_scene_with_vp`.
+    pub fn draw_scene(&mut self, scene: &mut crate::systems::scene::Scene, view_projection: &glam::Mat4) {
+        scene.cull(&mut self.culling, *view_projection);
         self.reactor.camera_pos = self.camera.position;
         self.reactor.camera_view = self.camera.view_matrix();
         self.reactor.camera_proj = self.camera.projection_matrix();
         self.reactor.camera_near = self.camera.near;
         self.reactor.camera_far = self.camera.far;
         self.reactor.post_process.update_time(self.time.elapsed());
-        if let Err(e) = self.reactor.draw_scene(scene, view_projection) { eprintln!("REACTOR draw error: {}", e); }
+        self.reactor.set_debug_lines(self.debug.get_lines());
+        self.reactor.set_text(self.text.vertices());
+        if let Err(e) = self.reactor.draw_scene_lit(scene, view_projection, &self.lighting) { log::error!("REACTOR draw error: {}", e); }
+        self.debug.clear();
+        self.text.clear();
+    }
+    /// Abre una ventana secundaria que comparte el device. Se crea al final
+    /// del frame actual; hasta entonces `draw_window` no dibuja nada.
+    pub fn open_window(&mut self, title: &str, width: u32, height: u32) -> u32 {
+        self.next_window_id += 1;
+        let id = self.next_window_id;
+        self.pending_windows.push(WindowRequest { id, title: title.to_string(), width: width.max(1), height: height.max(1) });
+        id
+    }
+    /// `true` mientras la ventana esté abierta o pendiente de crear.
+    pub fn window_open(&self, id: u32) -> bool { self.windows.contains_key(&id) || self.pending_windows.iter().any(|r| r.id == id) }
+    pub fn window(&self, id: u32) -> Option<&SecondaryWindow> { self.windows.get(&id) }
+    pub fn window_input(&self, id: u32) -> Option<&Input> { self.windows.get(&id).map(|w| &w.input) }
+    pub fn window_aspect_ratio(&self, id: u32) -> Option<f32> { self.windows.get(&id).map(|w| w.target.aspect_ratio()) }
+    /// Dibuja la escena del contexto en la ventana `id` (path forward, sin
+    /// post-proceso ni texto). Se puede llamar antes o después del
+    /// `draw_scene*` de la ventana principal.
+    pub fn draw_window(&mut self, id: u32, view_projection: &glam::Mat4) -> crate::core::error::ReactorResult<()> {
+        let Some(window) = self.windows.get_mut(&id) else {
+            if self.pending_windows.iter().any(|r| r.id == id) { return Ok(()); }
+            return Err(recorded(crate::core::error::ReactorError::invalid_parameter(format!("No window with id {}", id))));
+        };
+        self.scene.sync_from_world(&self.reactor.world);
+        self.lighting.sync_from_world(&self.reactor.world);
+        self.scene.cull(&mut self.culling, *view_projection);
+        self.reactor.set_lights(&self.lighting);
+        self.reactor.draw_scene_to(&mut window.target, &self.scene, view_projection).map_err(recorded)
+    }
+    /// Cierra la ventana `id`; el device y la ventana principal siguen.
+    pub fn close_window(&mut self, id: u32) {
+        self.windows.remove(&id);
+        self.pending_windows.retain(|r| r.id != id);
+    }
+    /// Dibuja la escena del contexto en `target` (cámara secundaria). Se llama
+    /// antes del `draw_scene*` que use su textura.
+    pub fn draw_to_target(&mut self, target: &mut crate::reactor::RenderTarget, view_projection: &glam::Mat4) -> crate::core::error::ReactorResult<()> {
+        self.scene.sync_from_world(&self.reactor.world);
+        self.lighting.sync_from_world(&self.reactor.world);
+        self.scene.cull(&mut self.culling, *view_projection);
+        self.reactor.set_lights(&self.lighting);
+        self.reactor.draw_scene_to_target(target, &self.scene, view_projection).map_err(recorded)
     }
     pub fn draw(&mut self, mesh: &crate::resources::mesh::Mesh, material: &crate::resources::material::Material, transform: &glam::Mat4) {
-        if let Err(e) = self.reactor.draw_frame(mesh, material, transform) { eprintln!("REACTOR draw error: {}", e); }
+        if let Err(e) = self.reactor.draw_frame(mesh, material, transform) { log::error!("REACTOR draw error: {}", e); }
     }
 
+    /// Texto en pantalla (píxeles, origen arriba a la izquierda) para el próximo `draw_scene*`.
+    pub fn draw_text(&mut self, x: f32, y: f32, size: f32, color: glam::Vec3, text: &str) { self.text.draw_text(glam::Vec2::new(x, y), size, color, text); }
+    /// Texto centrado sobre un punto del mundo, proyectado con la cámara actual.
+    pub fn draw_text_3d(&mut self, world_position: glam::Vec3, size: f32, color: glam::Vec3, text: &str) {
+        let (width, height) = self.window_size();
+        self.text.draw_text_3d(world_position, &self.camera.view_projection_matrix(), glam::Vec2::new(width as f32, height as f32), size, color, text);
+    }
+
+    /// Lote de sprites del próximo frame: `sprite_begin`, `sprite_draw`... y `sprite_end`.
+    pub fn sprite_begin(&mut self) { self.reactor.sprite_begin(); }
+    pub fn sprite_draw(&mut self, texture: &std::sync::Arc<crate::resources::texture::Texture>, sprite: crate::graphics::sprite_batch::Sprite) {
+        self.reactor.sprite_draw(texture, sprite);
+    }
+    pub fn sprite_end(&mut self) { self.reactor.sprite_end(); }
+    /// Cámara 2D de los sprites; `None` = píxeles con origen arriba a la izquierda.
+    pub fn set_sprite_camera(&mut self, camera: Option<crate::scene::camera::Camera2D>) { self.reactor.set_sprite_camera(camera); }
+
+    /// Decodifica un WAV/OGG/FLAC/MP3 y lo registra. Si falla, también queda en el último error.
+    pub fn audio_load(&mut self, path: &str) -> crate::core::error::ReactorResult<crate::systems::audio::AudioClipId> {
+        let result = if std::path::Path::new(path).exists() {
+            self.audio.load_clip(path).map_err(|e| crate::core::error::ReactorError::asset_load(format!("Failed to decode audio '{}': {}", path, e)))
+        } else {
+            Err(crate::core::error::ReactorError::file_not_found(path))
+        };
+        result.map_err(recorded)
+    }
+    pub fn audio_play(&mut self, clip: crate::systems::audio::AudioClipId, volume: f32) -> crate::systems::audio::AudioSourceId { self.audio.play_sfx(clip, None, volume) }
+    pub fn audio_play_at(&mut self, clip: crate::systems::audio::AudioClipId, position: glam::Vec3, volume: f32) -> crate::systems::audio::AudioSourceId { self.audio.play_sfx(clip, Some(position), volume) }
+    pub fn audio_stop(&mut self, source: crate::systems::audio::AudioSourceId) { self.audio.stop(source); }
+    pub fn audio_set_volume(&mut self, source: crate::systems::audio::AudioSourceId, volume: f32) -> bool { self.audio.set_source_volume(source, volume) }
+    pub fn audio_set_position(&mut self, source: crate::systems::audio::AudioSourceId, position: glam::Vec3) -> bool { self.audio.set_source_position(source, position) }
+    pub fn audio_set_listener(&mut self, position: glam::Vec3, forward: glam::Vec3, up: glam::Vec3) { self.audio.set_listener(position, forward, up); }
+    pub fn audio_set_master_volume(&mut self, volume: f32) { self.audio.set_master_volume(volume); }
+
     pub fn delta(&self) -> f32 { self.time.delta() }
     pub fn fps(&self) -> f32 { self.time.fps() }
+    pub fn gpu_name(&self) -> String { self.reactor.context.gpu_name() }
+    /// GPU en uso (nombre, fabricante, driver, VRAM, ray tracing).
+    pub fn gpu_info(&self) -> &crate::utils::gpu_detector::GPUInfo { self.reactor.context.gpu_info() }
+    /// Todas las GPUs encontradas al arrancar, por índice de enumeración.
+    pub fn available_gpus(&self) -> &[crate::utils::gpu_detector::GPUInfo] { self.reactor.context.available_gpus() }
     pub fn elapsed(&self) -> f32 { self.time.elapsed() }
 
+    /// Objetos con nombre e id estable (sobrevive a borrados de otros objetos).
+    pub fn add_object_named(&mut self, mesh: Arc<crate::resources::mesh::Mesh>, material: Arc<crate::resources::material::Material>, transform: glam::Mat4, name: &str) -> u64 { self.scene.add_named(mesh, material, transform, name) }
+    pub fn find_object(&self, name: &str) -> Option<u64> { self.scene.find_id_by_name(name) }
+    pub fn set_object_transform_by_id(&mut self, id: u64, transform: glam::Mat4) -> bool { self.scene.set_transform_by_id(id, transform) }
+    pub fn remove_object_by_id(&mut self, id: u64) -> bool { self.scene.remove_by_id(id).is_some() }
+
+    /// Guarda el próximo frame presentado como PNG (ver `Reactor::screenshot`).
+    pub fn screenshot(&mut self, path: &str) -> crate::core::error::ReactorResult<()> {
+        self.reactor.screenshot(path).map_err(recorded)
+    }
+    pub fn request_capture(&mut self) -> crate::core::error::ReactorResult<()> {
+        self.reactor.request_capture().map_err(recorded)
+    }
+    pub fn capture_frame(&mut self) -> crate::core::error::ReactorResult<image::RgbaImage> {
+        self.reactor.capture_frame().map_err(recorded)
+    }
+
+    /// Índice del objeto bajo el píxel `(x, y)` de la ventana, vía la cámara global.
+    pub fn pick_object(&self, x: f32, y: f32) -> Option<usize> { self.pick_object_ex(x, y).map(|hit| hit.index) }
+    pub fn pick_object_ex(&self, x: f32, y: f32) -> Option<crate::systems::scene::PickHit> {
+        let (width, height) = self.window_size();
+        if width == 0 || height == 0 { return None; }
+        let inv_vp = self.camera.view_projection_matrix().inverse();
+        let ray = crate::systems::physics::Ray::from_screen(x, y, width as f32, height as f32, inv_vp);
+        self.scene.pick(&ray)
+    }
     pub fn look_at(&mut self, eye: glam::Vec3, target: glam::Vec3) -> &mut Self { self.camera.aim_at(eye, target); self }
     pub fn move_camera_to(&mut self, position: glam::Vec3) -> &mut Self { self.camera.position = position; self }
+    pub fn set_camera_perspective(&mut self, fov_degrees: f32, near: f32, far: f32) -> &mut Self { self.camera.set_perspective(fov_degrees, near, far); self }
+    pub fn set_camera_orthographic(&mut self, size: f32, near: f32, far: f32) -> &mut Self { self.camera.set_orthographic(size, near, far); self }
+    pub fn set_camera_fov(&mut self, fov_degrees: f32) -> &mut Self { self.camera.set_fov(fov_degrees); self }
+    /// Cámara libre: `movement` en ejes de cámara (x derecha, y arriba, z
+    /// adelante) y `look` en píxeles, con suavizado independiente del framerate.
+    pub fn camera_fly_update(&mut self, dt: f32, movement: glam::Vec3, look: glam::Vec2) {
+        let input = crate::systems::camera::FlyCameraInput { movement, look, boost: false };
+        self.fly_camera.update(&mut self.camera, dt, input);
+    }
+    /// Cámara orbital: `orbit` y `pan` en píxeles, `zoom` en pasos de rueda (positivo acerca).
+    pub fn camera_orbit_update(&mut self, dt: f32, orbit: glam::Vec2, pan: glam::Vec2, zoom: f32) {
+        let camera = &mut self.camera;
+        let controller = self.orbit_camera.get_or_insert_with(|| {
+            crate::systems::camera::OrbitCameraController::from_camera(camera, camera.position + camera.forward() * 10.0)
+        });
+        controller.update(camera, dt, crate::systems::camera::OrbitCameraInput { orbit, pan, zoom });
+    }
 
     pub fn add_sun(&mut self) -> usize { self.lighting.add_light(crate::systems::lighting::Light::sun()) }
     pub fn add_directional_light(&mut self, direction: glam::Vec3, color: glam::Vec3, intensity: f32) -> usize {
@@ -222,12 +659,33 @@ impl ReactorContext {
     pub fn add_spot_light(&mut self, position: glam::Vec3, direction: glam::Vec3, color: glam::Vec3, intensity: f32, range: f32, angle_degrees: f32) -> usize {
         self.lighting.add_light(crate::systems::lighting::Light::spot(position, direction, color, intensity, range, angle_degrees))
     }
+    /// Edita luces globales sin reconstruir el `LightingSystem` (los índices son los de `add_*_light`).
+    pub fn set_light(&mut self, index: usize, light: crate::systems::lighting::Light) -> bool { self.lighting.set_light(index, light) }
+    pub fn light(&self, index: usize) -> Option<&crate::systems::lighting::Light> { self.lighting.get_light(index) }
+    pub fn remove_light(&mut self, index: usize) -> Option<crate::systems::lighting::Light> { self.lighting.remove_light(index) }
+    /// Edición por frame de luces globales por índice (p. ej. una luz que
+    /// orbita): no reconstruye la lista y falla si el índice no existe.
+    pub fn light_set_position(&mut self, index: usize, position: glam::Vec3) -> crate::core::error::ReactorResult<()> { self.edit_light(index, |light| light.position = position) }
+    pub fn light_set_color(&mut self, index: usize, color: glam::Vec3) -> crate::core::error::ReactorResult<()> { self.edit_light(index, |light| light.color = color) }
+    pub fn light_set_intensity(&mut self, index: usize, intensity: f32) -> crate::core::error::ReactorResult<()> { self.edit_light(index, |light| light.intensity = intensity) }
+    pub fn light_set_enabled(&mut self, index: usize, enabled: bool) -> crate::core::error::ReactorResult<()> { self.edit_light(index, |light| light.enabled = enabled) }
+    /// Borra la luz `index`; la última pasa a ocupar su índice y el resto no cambia.
+    pub fn light_remove(&mut self, index: usize) -> crate::core::error::ReactorResult<crate::systems::lighting::Light> {
+        let count = self.lighting.lights.len();
+        self.lighting.swap_remove_light(index).ok_or_else(|| recorded(no_light(index, count)))
+    }
+    fn edit_light(&mut self, index: usize, edit: impl FnOnce(&mut crate::systems::lighting::Light)) -> crate::core::error::ReactorResult<()> {
+        let count = self.lighting.lights.len();
+        let light = self.lighting.get_light_mut(index).ok_or_else(|| recorded(no_light(index, count)))?;
+        edit(light);
+        Ok(())
+    }
 
     pub fn spawn(&mut self, mesh: Arc<crate::resources::mesh::Mesh>, material: Arc<crate::resources::material::Material>, transform: glam::Mat4) -> usize {
         self.scene.add_object(mesh, material, transform)
     }
     pub fn set_transform(&mut self, index: usize, transform: glam::Mat4) {
-        if let Some(obj) = self.scene.objects.get_mut(index) { obj.transform = transform; }
+        if let Some(obj) = self.scene.objects.get_mut(index) { obj.set_transform(transform); }
     }
     pub fn get_transform(&self, index: usize) -> Option<glam::Mat4> {
         self.scene.objects.get(index).map(|obj| obj.transform)
@@ -237,8 +695,15 @@ impl ReactorContext {
         self.create_base_material(&self.base_shader_cookbook())
     }
     pub fn spawn_cube(&mut self, position: glam::Vec3) -> crate::core::error::ReactorResult<usize> {
-        let (v, i) = crate::resources::primitives::Primitives::cube();
-        self.spawn_primitive(&v, &i, glam::Mat4::from_translation(position))
+        self.spawn_cube_ex(position, 1.0, glam::Vec3::ONE)
+    }
+    /// Cubo con normales por cara de lado `size`, teñido con `color` (lineal, 0..1).
+    pub fn spawn_cube_ex(&mut self, position: glam::Vec3, size: f32, color: glam::Vec3) -> crate::core::error::ReactorResult<usize> {
+        if size <= 0.0 || !size.is_finite() { return Err(recorded(crate::core::error::ReactorError::invalid_parameter(format!("cube size must be positive, got {}", size)))); }
+        let (v, i) = crate::resources::primitives::Primitives::cube_sized(size);
+        let index = self.spawn_primitive(&v, &i, glam::Mat4::from_translation(position))?;
+        self.scene.objects[index].color = color.extend(1.0);
+        Ok(index)
     }
     pub fn spawn_sphere(&mut self, position: glam::Vec3, _radius: f32) -> crate::core::error::ReactorResult<usize> {
         let (v, i) = crate::resources::primitives::Primitives::sphere(32, 16);
@@ -250,15 +715,51 @@ impl ReactorContext {
         let xf = glam::Mat4::from_scale_rotation_translation(glam::Vec3::new(size, 1.0, size), glam::Quat::IDENTITY, position);
         self.spawn_primitive(&v, &i, xf)
     }
+    /// Crea (sin instanciar) una malla de esfera UV. `segments >= 3`, `rings >= 2`, radio positivo.
+    pub fn create_sphere_mesh(&self, radius: f32, segments: u32, rings: u32) -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
+        check_positive("sphere radius", radius)?; check_segments("sphere segments", segments, 3)?; check_segments("sphere rings", rings, 2)?;
+        let (mut v, i) = crate::resources::primitives::Primitives::sphere(segments, rings);
+        scale_vertices(&mut v, glam::Vec3::splat(radius * 2.0));
+        self.create_mesh(&v, &i)
+    }
+    /// Plano XZ de `width` × `depth`, subdividido `subdivisions` veces por lado.
+    pub fn create_plane_mesh(&self, width: f32, depth: f32, subdivisions: u32) -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
+        check_positive("plane width", width)?; check_positive("plane depth", depth)?; check_segments("plane subdivisions", subdivisions, 1)?;
+        let (mut v, i) = crate::resources::primitives::Primitives::plane(subdivisions);
+        scale_vertices(&mut v, glam::Vec3::new(width, 1.0, depth));
+        self.create_mesh(&v, &i)
+    }
+    pub fn create_cylinder_mesh(&self, radius: f32, height: f32, segments: u32) -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
+        check_positive("cylinder radius", radius)?; check_positive("cylinder height", height)?; check_segments("cylinder segments", segments, 3)?;
+        let (v, i) = crate::resources::primitives::Primitives::cylinder(segments, height, radius);
+        self.create_mesh(&v, &i)
+    }
+    /// Toro en el plano XZ: `major` = radio del anillo, `minor` = radio del tubo.
+    pub fn create_torus_mesh(&self, major: f32, minor: f32, segments: u32, rings: u32) -> crate::core::error::ReactorResult<crate::resources::mesh::Mesh> {
+        check_positive("torus major radius", major)?; check_positive("torus minor radius", minor)?;
+        check_segments("torus segments", segments, 3)?; check_segments("torus rings", rings, 3)?;
+        let (v, i) = crate::resources::primitives::Primitives::torus(segments, rings, major, minor);
+        self.create_mesh(&v, &i)
+    }
+    pub fn spawn_cylinder(&mut self, position: glam::Vec3, radius: f32, height: f32) -> crate::core::error::ReactorResult<usize> {
+        let mesh = Arc::new(self.create_cylinder_mesh(radius, height, 32)?);
+        let material = Arc::new(self.default_material()?);
+        Ok(self.scene.add_object(mesh, material, glam::Mat4::from_translation(position)))
+    }
+    pub fn spawn_torus(&mut self, position: glam::Vec3, major: f32, minor: f32) -> crate::core::error::ReactorResult<usize> {
+        let mesh = Arc::new(self.create_torus_mesh(major, minor, 32, 16)?);
+        let material = Arc::new(self.default_material()?);
+        Ok(self.scene.add_object(mesh, material, glam::Mat4::from_translation(position)))
+    }
     pub fn spawn_blob_shadow(&mut self, position: glam::Vec3, radius: f32) -> crate::core::error::ReactorResult<usize> {
         use crate::resources::primitives::Primitives;
         if self.blob_shadow_mesh.is_none() {
             let (v, i) = Primitives::sphere(12, 6);
-            let mesh = self.reactor.create_mesh(&v, &i).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?;
+            let mesh = self.reactor.create_mesh(&v, &i).map_err(recorded)?;
             self.blob_shadow_mesh = Some(Arc::new(mesh));
         }
         if self.blob_shadow_material.is_none() {
-            let dark_tex = self.reactor.create_solid_texture(8, 8, 10, 200).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?;
+            let dark_tex = self.reactor.create_solid_texture(8, 8, 10, 200).map_err(recorded)?;
             let mat = self.create_base_textured_material(&self.base_shader_cookbook(), &dark_tex)?.with_kept_texture(dark_tex);
             self.blob_shadow_material = Some(Arc::new(mat));
         }
@@ -271,22 +772,91 @@ impl ReactorContext {
     fn blob_xf(position: glam::Vec3, radius: f32) -> glam::Mat4 {
         glam::Mat4::from_scale_rotation_translation(glam::Vec3::new(radius, 0.02, radius), glam::Quat::IDENTITY, glam::Vec3::new(position.x, 0.02, position.z))
     }
+    /// Registra `system` y devuelve su id. Se dibuja como un único draw
+    /// instanciado (quad + material de partículas compartidos).
+    pub fn particles_spawn(&mut self, system: crate::systems::particles::ParticleSystem) -> crate::core::error::ReactorResult<u32> {
+        let handle = self.particle_object()?;
+        self.next_particles_id += 1;
+        self.particles.insert(self.next_particles_id, ParticleEmitter { system, handle });
+        Ok(self.next_particles_id)
+    }
+    pub fn particles_spawn_fire(&mut self, position: glam::Vec3) -> crate::core::error::ReactorResult<u32> { self.particles_spawn_at(crate::systems::particles::ParticleSystem::fire(), position) }
+    pub fn particles_spawn_smoke(&mut self, position: glam::Vec3) -> crate::core::error::ReactorResult<u32> { self.particles_spawn_at(crate::systems::particles::ParticleSystem::smoke(), position) }
+    /// La explosión no es cíclica: se borra sola cuando mueren sus partículas.
+    pub fn particles_spawn_explosion(&mut self, position: glam::Vec3) -> crate::core::error::ReactorResult<u32> { self.particles_spawn_at(crate::systems::particles::ParticleSystem::explosion(), position) }
+    fn particles_spawn_at(&mut self, mut system: crate::systems::particles::ParticleSystem, position: glam::Vec3) -> crate::core::error::ReactorResult<u32> {
+        system.position = position;
+        self.particles_spawn(system)
+    }
+    /// Mueve el emisor; las partículas ya emitidas en world space se quedan donde están.
+    pub fn particles_set_position(&mut self, id: u32, position: glam::Vec3) -> bool {
+        self.particles.get_mut(&id).map(|emitter| emitter.system.position = position).is_some()
+    }
+    pub fn particles(&self, id: u32) -> Option<&crate::systems::particles::ParticleSystem> { self.particles.get(&id).map(|emitter| &emitter.system) }
+    pub fn particles_mut(&mut self, id: u32) -> Option<&mut crate::systems::particles::ParticleSystem> { self.particles.get_mut(&id).map(|emitter| &mut emitter.system) }
+    pub fn particles_remove(&mut self, id: u32) -> bool {
+        let Some(emitter) = self.particles.remove(&id) else { return false };
+        self.scene.remove(emitter.handle);
+        true
+    }
+    /// Avanza los sistemas y reescribe las instancias de sus objetos. Los que
+    /// terminaron (`is_finished`) se borran con su objeto.
+    pub(crate) fn update_particles(&mut self, dt: f32) {
+        crate::profile_scope!("update_particles");
+        let ids: Vec<u32> = self.particles.keys().copied().collect();
+        for id in ids {
+            let emitter = self.particles.get_mut(&id).unwrap();
+            emitter.system.update(dt);
+            if emitter.system.is_finished() {
+                self.particles_remove(id);
+                continue;
+            }
+            // `scene.clear` o un `play_exit` pueden haberse llevado el objeto.
+            if !self.scene.contains(emitter.handle) {
+                match self.particle_object() {
+                    Ok(handle) => self.particles.get_mut(&id).unwrap().handle = handle,
+                    Err(e) => { log::warn!("Particle system {} not drawn: {}", id, e); continue; }
+                }
+            }
+            let emitter = &self.particles[&id];
+            if let Some(object) = self.scene.get_mut(emitter.handle) {
+                emitter.system.fill_instances(&mut object.instances);
+                object.refresh_bounds();
+            }
+        }
+    }
+    /// Objeto instanciado vacío con el quad y el material de partículas.
+    fn particle_object(&mut self) -> crate::core::error::ReactorResult<crate::systems::scene::SceneHandle> {
+        if self.particle_mesh.is_none() {
+            let (v, i) = crate::resources::primitives::Primitives::quad();
+            let mesh = self.reactor.create_mesh(&v, &i).map_err(recorded)?;
+            self.particle_mesh = Some(Arc::new(mesh));
+        }
+        if self.particle_material.is_none() {
+            let mat = self.reactor.create_particle_material().map_err(recorded)?;
+            self.particle_material = Some(Arc::new(mat));
+        }
+        let mut object = crate::systems::scene::SceneObject::new(self.particle_mesh.clone().unwrap(), self.particle_material.clone().unwrap(), glam::Mat4::IDENTITY).with_name("particles");
+        object.cast_shadows = false;
+        object.receive_shadows = false;
+        Ok(self.scene.insert(object))
+    }
     fn spawn_primitive(&mut self, vertices: &[crate::resources::vertex::Vertex], indices: &[u32], transform: glam::Mat4) -> crate::core::error::ReactorResult<usize> {
         let legacy: &[crate::resources::vertex::Vertex] = bytemuck::cast_slice(vertices);
-        let mesh = Arc::new(self.reactor.create_mesh(legacy, indices).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?);
+        let mesh = Arc::new(self.reactor.create_mesh(legacy, indices).map_err(recorded)?);
         let material = Arc::new(self.default_material()?);
         Ok(self.scene.add_object(mesh, material, transform))
     }
 
     pub fn spawn_colored_sphere(&mut self, position: glam::Vec3, radius: f32, r: u8, g: u8, b: u8, a: u8) -> crate::core::error::ReactorResult<usize> {
         let (v, i) = crate::resources::primitives::Primitives::sphere(16, 8);
-        let mesh = Arc::new(self.reactor.create_mesh(&v, &i).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?);
+        let mesh = Arc::new(self.reactor.create_mesh(&v, &i).map_err(recorded)?);
         let mat = Arc::new(self.create_colored_material(r, g, b, a)?);
         Ok(self.scene.add_object(mesh, mat, glam::Mat4::from_scale_rotation_translation(glam::Vec3::splat(radius.max(0.001)), glam::Quat::IDENTITY, position)))
     }
     pub fn spawn_textured_quad(&mut self, texture_path: &str, transform: glam::Mat4) -> crate::core::error::ReactorResult<usize> {
         let (v, i) = crate::resources::primitives::Primitives::quad();
-        let mesh = Arc::new(self.reactor.create_mesh(&v, &i).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?);
+        let mesh = Arc::new(self.reactor.create_mesh(&v, &i).map_err(recorded)?);
         let texture = self.load_texture(texture_path)?;
         let mat = Arc::new(self.create_base_textured_material(&self.base_shader_cookbook(), &texture)?.with_kept_texture(texture));
         Ok(self.scene.add_object(mesh, mat, transform))
@@ -297,13 +867,15 @@ impl ReactorContext {
     }
 
     pub fn load_gltf<P: AsRef<std::path::Path>>(&mut self, path: P) -> crate::core::error::ReactorResult<crate::resources::GltfModel> {
-        self.gltf_loader.load(path).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))
+        self.gltf_loader.load(path).map_err(recorded)
     }
     pub async fn load_gltf_async<P: AsRef<std::path::Path>>(&mut self, path: P) -> crate::core::error::ReactorResult<crate::resources::GltfModel> {
         let path_buf = path.as_ref().to_path_buf();
         let mut loader = self.gltf_loader.clone();
         tokio::task::spawn_blocking(move || loader.load(path_buf)).await
-            .map_err(|e| crate::core::error::ReactorError::internal(format!("Blocking task failed: {}", e)))?
+            .map_err(|e| crate::core::error::ReactorError::internal(format!("Blocking task failed: {}", e)))
+            .and_then(|result| result)
+            .map_err(recorded)
     }
     pub fn load_gltf_queued<P: AsRef<std::path::Path>>(&mut self, path: P, priority: crate::resources::LoadPriority)
         -> tokio::sync::oneshot::Receiver<crate::core::error::ReactorResult<Handle<crate::resources::GltfModel>>> {
@@ -311,18 +883,61 @@ impl ReactorContext {
         let id = AssetId::from_path(&path_buf);
         self.asset_loader_queue.enqueue_gltf(id, path_buf, priority)
     }
+    /// Carga un modelo (`.obj`, `.gltf`, `.glb`) sin bloquear: sus objetos
+    /// aparecen en `scene` en un frame posterior. Ver `resources::streaming`.
+    pub fn stream_model(&mut self, path: &str) -> crate::resources::StreamId {
+        let resolved = resolve_asset_path(path).unwrap_or_else(|| std::path::PathBuf::from(path));
+        self.streaming.load_model(resolved)
+    }
+    pub fn stream_status(&self, id: crate::resources::StreamId) -> Option<crate::resources::StreamStatus> { self.streaming.status(id) }
+    /// Milisegundos por frame para subir e integrar lo cargado en streaming.
+    pub fn set_stream_budget_ms(&mut self, budget_ms: f32) { self.streaming.set_budget_ms(budget_ms) }
+    /// Lo llama el runner al empezar el frame.
+    pub(crate) fn update_streaming(&mut self) {
+        if self.streaming.is_idle() { return; }
+        if self.stream_material.is_none() {
+            match self.default_material() {
+                Ok(material) => self.stream_material = Some(Arc::new(material)),
+                Err(e) => { record_last_error(&e); return; }
+            }
+        }
+        let Some(material) = self.stream_material.clone() else { return };
+        self.streaming.update(&self.reactor, &mut self.scene, &material);
+    }
+    /// Empieza a grabar los `profile_scope!` de todos los hilos.
+    pub fn profiler_start_capture(&self) { crate::utils::profiling::start_capture() }
+    /// Termina la captura y la escribe en `path` como JSON de chrome://tracing.
+    /// Devuelve cuántos eventos se escribieron.
+    pub fn profiler_stop_capture<P: AsRef<std::path::Path>>(&self, path: P) -> crate::core::error::ReactorResult<usize> {
+        crate::utils::profiling::stop_capture_to(path).map_err(recorded)
+    }
+    /// Añade a `debug` los gizmos de las luces del mundo ECS, resaltando las
+    /// de `selection`. No dibuja nada con `editor_settings.show_light_gizmos`
+    /// apagado. Se llama cada frame antes de `draw_scene*`.
+    pub fn draw_light_gizmos(&mut self) {
+        if self.editor_settings.show_light_gizmos {
+            crate::systems::light_gizmos::draw_light_gizmos(&mut self.debug, &self.reactor.world, &self.selection, self.camera.position);
+        }
+    }
+    /// Guarda `editor_settings` en `EditorSettings::default_path`. El runner
+    /// lo hace solo al salir si `ReactorConfig::editor_settings` está activo.
+    pub fn save_editor_settings(&self) -> crate::core::error::ReactorResult<()> {
+        let path = crate::app::editor_settings::EditorSettings::default_path()
+            .ok_or_else(|| recorded(crate::core::error::ReactorError::internal("No config directory for editor settings")))?;
+        self.editor_settings.save(&path).map_err(recorded)
+    }
     pub fn spawn_gltf<P: AsRef<std::path::Path>>(&mut self, path: P, transform: glam::Mat4) -> crate::core::error::ReactorResult<Vec<usize>> {
         let model = self.load_gltf(path)?;
         self.spawn_gltf_model(&model, transform)
     }
     pub fn gltf_bounds<P: AsRef<std::path::Path>>(&mut self, path: P) -> crate::core::error::ReactorResult<GltfBounds> {
         let model = self.load_gltf(path)?;
-        let (min, max) = model.bounds().ok_or_else(|| crate::core::error::ReactorError::asset_load("glTF model has no meshes"))?;
+        let (min, max) = model.bounds().ok_or_else(|| recorded(crate::core::error::ReactorError::asset_load("glTF model has no meshes")))?;
         Ok(GltfBounds { min, max, center: (min + max) * 0.5, size: max - min, height: max.y - min.y })
     }
     pub fn spawn_gltf_smart<P: AsRef<std::path::Path>>(&mut self, path: P, spawn: GltfSpawn) -> crate::core::error::ReactorResult<ModelSpawnInfo> {
         let model = self.load_gltf(path)?;
-        let (min, max) = model.bounds().ok_or_else(|| crate::core::error::ReactorError::asset_load("glTF model has no meshes"))?;
+        let (min, max) = model.bounds().ok_or_else(|| recorded(crate::core::error::ReactorError::asset_load("glTF model has no meshes")))?;
         let native_height = max.y - min.y;
         let native_center = (min + max) * 0.5;
         let scale = match spawn.target_height { Some(h) if native_height > 1e-6 => h / native_height, _ => 1.0 };
@@ -343,15 +958,68 @@ impl ReactorContext {
         Ok(ModelSpawnInfo { indices, applied_scale: scale, applied_rotation: rotation, native_height, world_height: native_height * scale, world_bounds_min: world_min, world_bounds_max: world_max })
     }
     pub fn spawn_gltf_model(&mut self, model: &crate::resources::GltfModel, parent_transform: glam::Mat4) -> crate::core::error::ReactorResult<Vec<usize>> {
+        self.spawn_gltf_model_skinned(model, parent_transform).map(|(indices, _)| indices)
+    }
+    /// Como `spawn_gltf`, pero devuelve el modelo skinned registrado en `animation`
+    /// (el del primer skin del archivo). Error si el glTF no tiene meshes skinned.
+    pub fn spawn_gltf_animated<P: AsRef<std::path::Path>>(&mut self, path: P, transform: glam::Mat4) -> crate::core::error::ReactorResult<crate::systems::animation::SkinnedModelId> {
+        let model = self.load_gltf(path)?;
+        let (_, models) = self.spawn_gltf_model_skinned(&model, transform)?;
+        models.first().copied().ok_or_else(|| recorded(crate::core::error::ReactorError::asset_load("glTF model has no skinned meshes")))
+    }
+    pub fn model_play_animation(&mut self, model: crate::systems::animation::SkinnedModelId, name: &str, looping: bool) -> bool { self.animation.play_model_animation(model, name, looping) }
+    pub fn model_stop_animation(&mut self, model: crate::systems::animation::SkinnedModelId) -> bool { self.animation.stop_model_animation(model) }
+    pub fn model_animations(&self, model: crate::systems::animation::SkinnedModelId) -> Vec<String> { self.animation.model_animation_names(model).into_iter().map(str::to_string).collect() }
+    /// Instancia el árbol de nodos; los meshes skinned se agrupan por skin y cada
+    /// skin se registra como un modelo en `animation`, en pose de reposo.
+    fn spawn_gltf_model_skinned(&mut self, model: &crate::resources::GltfModel, parent_transform: glam::Mat4) -> crate::core::error::ReactorResult<(Vec<usize>, Vec<crate::systems::animation::SkinnedModelId>)> {
         let mut indices = Vec::new();
-        self.spawn_gltf_node_recursive(&model.root_node, model, parent_transform, &mut indices)?;
-        Ok(indices)
+        let mut skinned_objects = Vec::new();
+        // Un material skinned por textura base del modelo: compilarlo por nodo repetía el shader y el pipeline.
+        let mut skinned_materials = std::collections::HashMap::new();
+        self.spawn_gltf_node_recursive(&model.root_node, model, parent_transform, &mut indices, &mut skinned_objects, &mut skinned_materials)?;
+        let mut models = Vec::new();
+        for skin_idx in 0..model.skins.len() {
+            let objects: Vec<u64> = skinned_objects.iter().filter(|(s, _)| *s == skin_idx).map(|(_, id)| *id).collect();
+            if objects.is_empty() { continue; }
+            let Some(skeleton) = crate::systems::skinning::Skeleton::from_model(model, skin_idx) else { continue };
+            models.push(self.animation.add_skinned_model(skeleton, model.animations.clone(), objects));
+        }
+        Ok((indices, models))
     }
-    fn spawn_gltf_node_recursive(&mut self, node: &crate::resources::GltfNode, model: &crate::resources::GltfModel, parent_transform: glam::Mat4, indices: &mut Vec<usize>) -> crate::core::error::ReactorResult<()> {
+    fn spawn_gltf_node_recursive(&mut self, node: &crate::resources::GltfNode, model: &crate::resources::GltfModel, parent_transform: glam::Mat4, indices: &mut Vec<usize>, skinned_objects: &mut Vec<(usize, u64)>, skinned_materials: &mut std::collections::HashMap<Option<usize>, Arc<crate::resources::material::Material>>) -> crate::core::error::ReactorResult<()> {
         let world_transform = parent_transform * node.transform;
         if let Some(mesh_idx) = node.mesh_index {
-            if let Some(mesh_data) = model.meshes.get(mesh_idx) {
-                let vulkan_mesh = crate::resources::mesh::Mesh::new(&self.reactor.context, &self.reactor.allocator, &mesh_data.vertices, &mesh_data.indices)?;
+            let skin = node.skin_index.and_then(|i| model.skins.get(i).map(|skin| (i, skin)));
+            if let (Some(mesh_data), Some((skin_idx, skin))) = (model.meshes.get(mesh_idx).filter(|m| m.is_skinned()), skin) {
+                // glTF ignora el transform del nodo de un mesh skinned: los joints ya lo llevan.
+                let mesh = crate::resources::mesh::Mesh::new_skinned(&self.reactor.context, &self.reactor.allocator, &mesh_data.skinned_vertices(), &mesh_data.indices).map_err(recorded)?;
+                // Misma textura que el camino rígido: la baseColorTexture del material o la primera del modelo.
+                let tex_to_use = mesh_data.material_index.and_then(|i| model.materials.get(i)).and_then(|m| m.base_color_texture_index)
+                    .or(Some(0))
+                    .filter(|&i| i < model.textures.len());
+                let material = match skinned_materials.get(&tex_to_use) {
+                    Some(material) => material.clone(),
+                    None => {
+                        let material = match tex_to_use.map(|i| &model.textures[i]) {
+                            Some(tex_data) => {
+                                let texture = crate::resources::texture::Texture::from_rgba(&self.reactor.context, self.reactor.allocator.clone(), &tex_data.pixels, tex_data.width, tex_data.height, true).map_err(recorded)?;
+                                self.reactor.create_default_skinned_textured_material(&texture).map_err(recorded)?.with_kept_texture(texture)
+                            }
+                            None => self.reactor.create_default_skinned_material().map_err(recorded)?,
+                        };
+                        skinned_materials.entry(tex_to_use).or_insert(Arc::new(material)).clone()
+                    }
+                };
+                let index = self.scene.add_object(Arc::new(mesh), material, parent_transform);
+                let base_color = mesh_data.material_index.and_then(|i| model.materials.get(i)).map_or([1.0; 4], |m| m.base_color);
+                let object = &mut self.scene.objects[index];
+                object.color = glam::Vec4::from_array(base_color);
+                object.joint_matrices = vec![glam::Mat4::IDENTITY; skin.joints.len()];
+                skinned_objects.push((skin_idx, object.id));
+                indices.push(index);
+            } else if let Some(mesh_data) = model.meshes.get(mesh_idx) {
+                let vulkan_mesh = crate::resources::mesh::Mesh::new(&self.reactor.context, &self.reactor.allocator, &mesh_data.vertices, &mesh_data.indices).map_err(recorded)?;
                 let mesh_arc = Arc::new(vulkan_mesh);
                 let material_arc = {
                     let mut tex_to_use = None;
@@ -361,9 +1029,9 @@ impl ReactorContext {
                     if tex_to_use.is_none() && !model.textures.is_empty() { tex_to_use = Some(0); }
                     match tex_to_use {
                         Some(tex_idx) if let Some(tex_data) = model.textures.get(tex_idx) => {
-                            let texture = crate::resources::texture::Texture::from_rgba(&self.reactor.context, self.reactor.allocator.clone(), &tex_data.pixels, tex_data.width, tex_data.height, true)?;
+                            let texture = crate::resources::texture::Texture::from_rgba(&self.reactor.context, self.reactor.allocator.clone(), &tex_data.pixels, tex_data.width, tex_data.height, true).map_err(recorded)?;
                             let cookbook = self.base_shader_cookbook();
-                            let mat = self.create_base_textured_material(&cookbook, &texture).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?.with_kept_texture(texture);
+                            let mat = self.create_base_textured_material(&cookbook, &texture).map_err(recorded)?.with_kept_texture(texture);
                             Arc::new(mat)
                         }
                         _ => Arc::new(self.default_material()?),
@@ -372,13 +1040,13 @@ impl ReactorContext {
                 indices.push(self.scene.add_object(mesh_arc, material_arc, world_transform));
             }
         }
-        for child in &node.children { self.spawn_gltf_node_recursive(child, model, world_transform, indices)?; }
+        for child in &node.children { self.spawn_gltf_node_recursive(child, model, world_transform, indices, skinned_objects, skinned_materials)?; }
         Ok(())
     }
     pub fn track_asset_for_reload<P: AsRef<std::path::Path>>(&mut self, path: P, asset_type: crate::resources::AssetType) -> crate::core::error::ReactorResult<AssetId> {
         let path = path.as_ref();
         let id = AssetId::from_path(path);
-        if let Some(ref mut hot_reload) = self.asset_hot_reload { hot_reload.track_asset(id, path, asset_type).map_err(|e| crate::core::error::ReactorError::internal(e.to_string()))?; }
+        if let Some(ref mut hot_reload) = self.asset_hot_reload { hot_reload.track_asset(id, path, asset_type).map_err(recorded)?; }
         Ok(id)
     }
     pub fn asset_stats(&self) -> AssetPipelineStats {
@@ -387,6 +1055,76 @@ impl ReactorContext {
             hot_reload: self.asset_hot_reload.as_ref().map(|hr| hr.stats()),
             db: self.asset_db.stats(),
             gltf_cache: self.gltf_loader.cache_stats(),
+            streaming: self.streaming.stats(),
         }
     }
+    /// Bytes y asignaciones propias del motor (buffers / imágenes), por tipo de memoria.
+    pub fn memory_stats(&self) -> crate::core::MemoryStats { crate::core::MemoryAllocator::stats() }
+    /// Presupuesto por heap; con `VK_EXT_memory_budget` trae el uso real reportado por el driver.
+    pub fn memory_budget(&self) -> crate::core::GpuMemoryBudget { self.reactor.context.get_vram_budget() }
+    /// VRAM en uso (MB): el dato del driver si hay `VK_EXT_memory_budget`, si no el contador propio.
+    pub fn vram_used_mb(&self) -> u64 {
+        let budget = self.memory_budget();
+        if budget.has_dynamic_budget { budget.total_vram_usage_mb() } else { self.memory_stats().device_local_mb() }
+    }
+}
+
+/// Busca `path` tal cual (absoluta o relativa al cwd) y, si no existe, junto al ejecutable.
+fn resolve_asset_path(path: &str) -> Option<std::path::PathBuf> {
+    let direct = std::path::PathBuf::from(path);
+    if direct.is_file() { return Some(direct); }
+    if direct.is_absolute() { return None; }
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let beside_exe = exe_dir.join(path);
+    beside_exe.is_file().then_some(beside_exe)
+}
+
+/// Refleja el error en el slot global de último error sin perder su código original.
+/// Si ya es el último (un método compuesto propagando el error de otro), no se
+/// vuelve a registrar ni a loguear.
+/// Los comandos integrados de `ConsoleRegistry` actúan sobre `reactor.world`;
+/// `play`, `stop` y `save` son los de play mode y `save_scene`.
+impl crate::systems::console::ConsoleHost for ReactorContext {
+    fn world(&mut self) -> &mut crate::scene::ecs::World { &mut self.reactor.world }
+    fn history(&mut self) -> &mut crate::systems::transform_history::TransformHistory { &mut self.history }
+    fn selection(&mut self) -> &mut Vec<crate::scene::ecs::Entity> { &mut self.selection }
+    fn play(&mut self) -> crate::systems::console::CommandResult {
+        self.play_enter().map(|_| "Play mode".to_string()).map_err(|e| format!("play: {}", e.message))
+    }
+    fn stop(&mut self) -> crate::systems::console::CommandResult {
+        self.play_exit().map(|_| "Edit mode".to_string()).map_err(|e| format!("stop: {}", e.message))
+    }
+    fn save(&mut self, path: &str) -> crate::systems::console::CommandResult {
+        self.save_scene(path).map(|_| format!("Saved {}", path)).map_err(|e| format!("save: {}", e.message))
+    }
+}
+
+pub(crate) fn record_last_error(e: &crate::core::error::ReactorError) {
+    use crate::core::error::{get_last_error_code, get_last_error_message, set_last_error};
+    if get_last_error_code() == e.code && get_last_error_message().as_deref() == Some(e.message.as_str()) { return; }
+    set_last_error(crate::core::error::ReactorError::new(e.code, e.message.clone()));
+}
+
+/// `record_last_error` para `map_err`: registra el error y lo devuelve.
+fn recorded(e: crate::core::error::ReactorError) -> crate::core::error::ReactorError {
+    record_last_error(&e);
+    e
+}
+
+fn check_positive(what: &str, value: f32) -> crate::core::error::ReactorResult<()> {
+    if value > 0.0 && value.is_finite() { Ok(()) }
+    else { Err(recorded(crate::core::error::ReactorError::invalid_parameter(format!("{} must be positive, got {}", what, value)))) }
+}
+
+fn no_light(index: usize, count: usize) -> crate::core::error::ReactorError {
+    crate::core::error::ReactorError::invalid_parameter(format!("No light at index {} ({} lights)", index, count))
+}
+
+fn check_segments(what: &str, value: u32, min: u32) -> crate::core::error::ReactorResult<()> {
+    if value >= min { Ok(()) }
+    else { Err(recorded(crate::core::error::ReactorError::invalid_parameter(format!("{} must be >= {}, got {}", what, min, value)))) }
+}
+
+fn scale_vertices(vertices: &mut [crate::resources::vertex::Vertex], scale: glam::Vec3) {
+    for v in vertices { v.position = (glam::Vec3::from_array(v.position) * scale).to_array(); }
 }
@@ -0,0 +1,177 @@
+// =============================================================================
+// EditorSettings — Preferencias de herramientas que sobreviven entre sesiones
+// =============================================================================
+// Layout de paneles, proyecto abierto, escenas recientes, snapping, grid,
+// gizmos de luces, velocidad de cámara y filtro de la consola. Con
+// `ReactorConfig::with_editor_settings` el runner las carga de
+// `<config dir>/reactor/editor_settings.json` antes de `init` y las guarda
+// tras `on_exit`; la app las lee y modifica en `ctx.editor_settings`.
+//
+// Un archivo ilegible o de otra versión se ignora con un warning (que llega a
+// la consola por la captura de logs) y se reescribe con los valores por
+// defecto, así el siguiente arranque ya no avisa.
+// =============================================================================
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::systems::gizmo::GizmoSnap;
+
+pub const EDITOR_SETTINGS_FILE: &str = "editor_settings.json";
+pub const EDITOR_SETTINGS_VERSION: u32 = 1;
+/// Entradas de File > Recent.
+pub const MAX_RECENT_SCENES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub version: u32,
+    /// Layout de paneles tal como lo serializa el editor (p. ej. un
+    /// `egui_dock::DockState`). `None` = el reparto por defecto.
+    pub dock_state: Option<serde_json::Value>,
+    pub project_root: Option<PathBuf>,
+    /// La más reciente primero, sin repetidas.
+    pub recent_scenes: Vec<PathBuf>,
+    pub snap: GizmoSnap,
+    pub show_grid: bool,
+    /// Dibuja los gizmos de las luces en el viewport (`ReactorContext::draw_light_gizmos`).
+    pub show_light_gizmos: bool,
+    /// Multiplica la velocidad de las cámaras del viewport.
+    pub camera_speed: f32,
+    /// Nivel mínimo que lista la consola (`error`, `warn`, `info`, ...).
+    pub console_level: String,
+    pub console_search: String,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            version: EDITOR_SETTINGS_VERSION,
+            dock_state: None,
+            project_root: None,
+            recent_scenes: Vec::new(),
+            snap: GizmoSnap::default(),
+            show_grid: true,
+            show_light_gizmos: true,
+            camera_speed: 1.0,
+            console_level: "info".to_string(),
+            console_search: String::new(),
+        }
+    }
+}
+
+impl EditorSettings {
+    /// `<platform config dir>/reactor/editor_settings.json`, e.g.
+    /// `%APPDATA%\reactor\config` on Windows or `~/.config/reactor` on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        directories::ProjectDirs::from("", "", "reactor").map(|dirs| dirs.config_dir().join(EDITOR_SETTINGS_FILE))
+    }
+
+    /// Lee `path`. Sin archivo devuelve los valores por defecto; uno corrupto
+    /// o de otra versión se avisa y se regenera.
+    pub fn load(path: &Path) -> Self {
+        let Ok(data) = std::fs::read(path) else { return Self::default() };
+        let problem = match serde_json::from_slice::<Self>(&data) {
+            Ok(settings) if settings.version == EDITOR_SETTINGS_VERSION => return settings,
+            Ok(settings) => format!("version {} (expected {})", settings.version, EDITOR_SETTINGS_VERSION),
+            Err(e) => e.to_string(),
+        };
+        log::warn!("Ignoring editor settings {}: {}; using defaults", path.display(), problem);
+        let settings = Self::default();
+        if let Err(e) = settings.save(path) {
+            log::warn!("Editor settings not regenerated: {}", e);
+        }
+        settings
+    }
+
+    pub fn save(&self, path: &Path) -> ReactorResult<()> {
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize editor settings: {}", e)))?;
+        let io_error = |e| ReactorError::with_source(ErrorCode::IoError, format!("Failed to write {}", path.display()), e);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        // Se escribe aparte y se renombra: un cierre a medias no deja el archivo truncado.
+        let partial = path.with_extension("json.tmp");
+        std::fs::write(&partial, data).map_err(io_error)?;
+        std::fs::rename(&partial, path).map_err(io_error)
+    }
+
+    /// Pone `path` al principio de las recientes.
+    pub fn add_recent_scene(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        self.recent_scenes.retain(|recent| *recent != path);
+        self.recent_scenes.insert(0, path);
+        self.recent_scenes.truncate(MAX_RECENT_SCENES);
+    }
+
+    /// "Reset Layout": el editor vuelve a su reparto de paneles por defecto.
+    pub fn reset_layout(&mut self) {
+        self.dock_state = None;
+    }
+
+    /// `console_level` como filtro; uno que no se reconoce cuenta como `Info`.
+    pub fn console_level_filter(&self) -> log::LevelFilter {
+        self.console_level.parse().unwrap_or(log::LevelFilter::Info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("reactor_editor_settings_{}_{}", name, std::process::id()))
+            .join(EDITOR_SETTINGS_FILE)
+    }
+
+    #[test]
+    fn settings_round_trip_through_the_file() {
+        let path = settings_path("round_trip");
+        let mut settings = EditorSettings {
+            dock_state: Some(serde_json::json!({ "surfaces": [{ "tabs": ["Viewport", "Inspector"] }] })),
+            project_root: Some(PathBuf::from("projects/demo")),
+            show_grid: false,
+            show_light_gizmos: false,
+            camera_speed: 2.5,
+            console_level: "warn".to_string(),
+            ..EditorSettings::default()
+        };
+        settings.snap.translate = 0.25;
+        settings.snap.rotate_degrees = 15.0;
+        for scene in ["a.gltf", "b.obj", "a.gltf"] {
+            settings.add_recent_scene(scene);
+        }
+        assert_eq!(settings.recent_scenes, [PathBuf::from("a.gltf"), PathBuf::from("b.obj")]);
+
+        settings.save(&path).unwrap();
+        let loaded = EditorSettings::load(&path);
+        assert_eq!(loaded, settings);
+        assert_eq!(loaded.console_level_filter(), log::LevelFilter::Warn);
+
+        let mut reset = loaded;
+        reset.reset_layout();
+        assert!(reset.dock_state.is_none());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn missing_corrupt_and_outdated_files_fall_back_to_defaults() {
+        let path = settings_path("fallback");
+        assert_eq!(EditorSettings::load(&path), EditorSettings::default());
+        assert!(!path.exists());
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"{ not json").unwrap();
+        assert_eq!(EditorSettings::load(&path), EditorSettings::default());
+        // Regenerado: ya se lee sin avisar.
+        assert_eq!(serde_json::from_slice::<EditorSettings>(&std::fs::read(&path).unwrap()).unwrap(), EditorSettings::default());
+
+        std::fs::write(&path, br#"{ "version": 99, "show_grid": false }"#).unwrap();
+        assert_eq!(EditorSettings::load(&path), EditorSettings::default());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
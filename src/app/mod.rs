@@ -1,13 +1,15 @@
 pub mod config;
 pub mod context;
+pub mod editor_settings;
 pub mod pause_config;
 pub mod quick;
 pub mod runner;
 
 pub use config::{ReactorConfig, RendererMode};
-pub use context::{AssetPipelineStats, GltfBounds, GltfSpawn, ModelSpawnInfo, ReactorContext};
-pub use quick::{call_init, call_update, quick, quick_with};
-pub use runner::run;
+pub use editor_settings::EditorSettings;
+pub use context::{AssetPipelineStats, GltfBounds, GltfSpawn, ModelSpawnInfo, ReactorContext, SecondaryWindow};
+pub use quick::{call_init, call_update, quick, quick_state, quick_with, QuickState};
+pub use runner::{run, try_run, PumpedApp};
 
 use winit::event::WindowEvent;
 
@@ -17,6 +19,11 @@ pub trait ReactorApp {
     fn init(&mut self, ctx: &mut ReactorContext);
     fn update(&mut self, ctx: &mut ReactorContext);
     fn render(&mut self, ctx: &mut ReactorContext) { ctx.render_scene(); }
+    /// Lógica a ritmo fijo (`ReactorConfig::physics_hz`; nunca se llama con 0).
+    /// Cada frame corre 0 o más pasos con el mismo `fixed_dt`, antes de
+    /// `update`; en cada paso `physics.simulate` va primero. Tras un tirón se
+    /// corren como mucho `DEFAULT_MAX_FIXED_STEPS` y el resto se descarta.
+    /// `ctx.fixed_alpha()` da la fracción pendiente para interpolar en `render`.
     fn fixed_update(&mut self, _ctx: &mut ReactorContext, _fixed_dt: f32) {}
     fn on_resize(&mut self, _ctx: &mut ReactorContext, _width: u32, _height: u32) {}
     fn on_exit(&mut self, _ctx: &mut ReactorContext) {}
@@ -27,6 +27,16 @@ pub(super) fn print(config: &PauseConfig, ctx: &ReactorContext) {
         on_off(ctx.reactor.post_process.enabled),
         pixel_display(ctx)
     );
+    let stats = ctx.render_stats();
+    println!(
+        "| Draws {:>5} | Tris {:>9} | Inst {:>7} | Binds {:>4}/{:<5} | Shadow {:>5} |",
+        stats.draw_calls,
+        stats.triangles,
+        stats.instances,
+        stats.pipeline_binds,
+        stats.descriptor_binds,
+        stats.shadow_draw_calls
+    );
     println!("+{}+", line);
 
     for (i, row) in rows.iter().enumerate() {
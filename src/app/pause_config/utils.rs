@@ -1,8 +1,7 @@
-use winit::window::Fullscreen;
-
 use crate::app::context::ReactorContext;
 use crate::core::PixelIntelligentProfile;
 use crate::graphics::post_process::{PostProcessEffect, PostProcessSettings};
+use crate::platform::FullscreenMode;
 
 pub(super) fn toggle_effect(settings: &mut PostProcessSettings, effect: PostProcessEffect) -> bool {
     if settings.is_effect_enabled(effect) {
@@ -37,18 +36,18 @@ pub(super) fn cycle_pixel_intelligent(ctx: &mut ReactorContext) -> bool {
 pub(super) fn toggle_vsync(ctx: &mut ReactorContext) -> bool {
     ctx.reactor.vsync = !ctx.reactor.vsync;
     if let Err(err) = ctx.reactor.recreate_swapchain() {
-        eprintln!("REACTOR PauseConfig: failed to toggle VSync: {err}");
+        log::warn!("REACTOR PauseConfig: failed to toggle VSync: {err}");
         ctx.reactor.resized = true;
     }
     true
 }
 
 pub(super) fn toggle_fullscreen(ctx: &mut ReactorContext) -> bool {
-    if ctx.window.fullscreen().is_some() {
-        ctx.window.set_fullscreen(None);
-    } else {
-        ctx.window.set_fullscreen(Some(Fullscreen::Borderless(None)));
-    }
+    let mode = match ctx.fullscreen_mode() {
+        FullscreenMode::Windowed => FullscreenMode::Borderless,
+        _ => FullscreenMode::Windowed,
+    };
+    ctx.set_fullscreen(mode);
     true
 }
 
@@ -37,6 +37,52 @@ where I: FnMut(&mut ReactorContext) + 'static, U: FnMut(&mut ReactorContext) + '
     run(QuickApp { config, init: Some(init), update });
 }
 
+type StateCallback<S> = Box<dyn FnMut(&mut S, &mut ReactorContext)>;
+
+/// Versión de `quick_with` con estado del juego: cada callback recibe `&mut S`
+/// junto al contexto, así init, update, resize y exit comparten datos sin
+/// globales ni `Rc<RefCell<_>>`.
+///
+/// Todos los callbacks se llaman en el hilo que ejecuta `run` (el del bucle de
+/// eventos), nunca desde otro; por eso `S` no necesita ser `Send`.
+///
+/// ```ignore
+/// struct Juego { objetos: Vec<usize> }
+///
+/// reactor::quick_state(ReactorConfig::new("Mi Juego"), Juego { objetos: Vec::new() })
+///     .on_init(|juego, ctx| juego.objetos.push(ctx.spawn_cube(Vec3::ZERO).unwrap()))
+///     .on_update(|juego, ctx| { /* ... */ })
+///     .run();
+/// ```
+pub struct QuickState<S: 'static> {
+    config: ReactorConfig,
+    state: S,
+    init: Option<StateCallback<S>>,
+    update: Option<StateCallback<S>>,
+    resize: Option<Box<dyn FnMut(&mut S, &mut ReactorContext, u32, u32)>>,
+    exit: Option<StateCallback<S>>,
+}
+
+pub fn quick_state<S: 'static>(config: ReactorConfig, state: S) -> QuickState<S> {
+    QuickState { config, state, init: None, update: None, resize: None, exit: None }
+}
+
+impl<S: 'static> QuickState<S> {
+    pub fn on_init(mut self, f: impl FnMut(&mut S, &mut ReactorContext) + 'static) -> Self { self.init = Some(Box::new(f)); self }
+    pub fn on_update(mut self, f: impl FnMut(&mut S, &mut ReactorContext) + 'static) -> Self { self.update = Some(Box::new(f)); self }
+    pub fn on_resize(mut self, f: impl FnMut(&mut S, &mut ReactorContext, u32, u32) + 'static) -> Self { self.resize = Some(Box::new(f)); self }
+    pub fn on_exit(mut self, f: impl FnMut(&mut S, &mut ReactorContext) + 'static) -> Self { self.exit = Some(Box::new(f)); self }
+    pub fn run(self) { run(self); }
+}
+
+impl<S: 'static> ReactorApp for QuickState<S> {
+    fn config(&self) -> ReactorConfig { self.config.clone() }
+    fn init(&mut self, ctx: &mut ReactorContext) { if let Some(f) = &mut self.init { f(&mut self.state, ctx); } }
+    fn update(&mut self, ctx: &mut ReactorContext) { if let Some(f) = &mut self.update { f(&mut self.state, ctx); } }
+    fn on_resize(&mut self, ctx: &mut ReactorContext, width: u32, height: u32) { if let Some(f) = &mut self.resize { f(&mut self.state, ctx, width, height); } }
+    fn on_exit(&mut self, ctx: &mut ReactorContext) { if let Some(f) = &mut self.exit { f(&mut self.state, ctx); } }
+}
+
 #[inline(always)]
 pub fn call_init<F>(mut f: F, ctx: &mut ReactorContext) where F: FnMut(&mut ReactorContext) { f(ctx); }
 
@@ -1,16 +1,19 @@
+use std::cell::RefCell;
 use std::sync::Arc;
 
 use winit::{
     application::ApplicationHandler,
     dpi::LogicalSize,
-    event::WindowEvent,
+    error::EventLoopError,
+    event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    window::{Window, WindowId},
+    window::{Fullscreen, Window, WindowId},
 };
 
 use crate::app::config::RendererMode;
 use crate::app::context::ReactorContext;
 use crate::app::ReactorApp;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::platform::time::Time;
 use crate::reactor::Reactor;
 use crate::resources::{
@@ -20,23 +23,58 @@ use crate::resources::{
 struct AppRunner<A: ReactorApp> {
     app: A,
     context: Option<ReactorContext>,
+    /// Un callback de la app hizo panic: no se vuelve a llamar a ninguno.
+    panicked: bool,
+}
+
+/// Llama a un callback de la app sin dejar que un panic atraviese winit (en
+/// algunas plataformas el bucle de eventos pasa por código nativo y el unwind
+/// es UB). El panic queda como último error (`ErrorCode::Unknown`) y el bucle
+/// termina ordenadamente.
+macro_rules! guarded {
+    ($runner:ident, $event_loop:expr, $what:literal, $call:expr) => {
+        if crate::core::error::catch_panic($what, || { $call; Ok(()) }).is_err() {
+            $runner.panicked = true;
+            $event_loop.exit();
+            return;
+        }
+    };
 }
 
 impl<A: ReactorApp> ApplicationHandler for AppRunner<A> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        if self.context.is_some() { return; }
-        let config = self.app.config();
+        if self.context.is_some() || self.panicked { return; }
+        let mut config = None;
+        guarded!(self, event_loop, "ReactorApp::config", config = Some(self.app.config()));
+        let Some(config) = config else { return };
         let window_attributes = Window::default_attributes()
             .with_title(&config.title)
-            .with_inner_size(LogicalSize::new(config.width, config.height));
+            .with_inner_size(LogicalSize::new(config.width, config.height))
+            .with_fullscreen(config.fullscreen.then_some(Fullscreen::Borderless(None)));
         let window = match event_loop.create_window(window_attributes) {
             Ok(w) => Arc::new(w),
-            Err(e) => { eprintln!("Failed to create window: {}", e); event_loop.exit(); return; }
+            Err(e) => { log::error!("Failed to create window: {}", e); event_loop.exit(); return; }
         };
-        let reactor = match Reactor::init(&window, config.msaa_samples, config.renderer == RendererMode::RayTracing, config.vsync) {
+        // El G-Buffer no es multisample: deferred fuerza MSAA 1x.
+        let msaa_samples = if config.renderer == RendererMode::Deferred && config.msaa_samples > 1 {
+            log::warn!("Deferred renderer: MSAA {}x ignored, using 1x", config.msaa_samples);
+            1
+        } else {
+            config.msaa_samples
+        };
+        crate::core::validation::set_validation_enabled(config.validation);
+        crate::core::pipeline_cache::set_pipeline_cache_enabled(config.pipeline_cache);
+        crate::utils::gpu_detector::set_gpu_preference(config.gpu.clone());
+        let mut reactor = match Reactor::init(&window, msaa_samples, config.renderer == RendererMode::RayTracing, config.vsync) {
             Ok(r) => r,
-            Err(e) => { eprintln!("Failed to initialize Reactor: {}", e); event_loop.exit(); return; }
+            Err(e) => { log::error!("Failed to initialize Reactor: {}", e); event_loop.exit(); return; }
         };
+        if let Err(e) = reactor.set_renderer_mode(config.renderer) {
+            log::warn!("Renderer {:?} unavailable, falling back to forward: {}", config.renderer, e);
+        }
+        if let Err(e) = reactor.set_frames_in_flight(config.frames_in_flight) {
+            log::warn!("{}; keeping {} frames in flight", e, reactor.frames_in_flight());
+        }
         crate::systems::console::init();
         crate::systems::console::ReactorBanner::print_init(
             &config.title,
@@ -55,6 +93,7 @@ impl<A: ReactorApp> ApplicationHandler for AppRunner<A> {
                 num_workers: 2, ..Default::default()
             }).unwrap()
         });
+        let streaming = crate::resources::LoadQueue::new().unwrap_or_else(|_| crate::resources::LoadQueue::with_workers(1).unwrap());
         let (hot_reload_tx, hot_reload_rx) = tokio::sync::mpsc::unbounded_channel();
         let asset_hot_reload = AssetHotReloadManager::new(crate::resources::HotReloadConfig::default(), hot_reload_tx).ok();
         let hot_reload_rx = if asset_hot_reload.is_some() { Some(hot_reload_rx) } else { None };
@@ -62,48 +101,97 @@ impl<A: ReactorApp> ApplicationHandler for AppRunner<A> {
             reactor, window,
             time: Time::new(), config: config.clone(),
             camera: crate::scene::camera::Camera::perspective(60.0, aspect, 0.1, 1000.0),
+            fly_camera: crate::systems::camera::FlyCameraController::default(), orbit_camera: None,
             scene: crate::systems::scene::Scene::new(),
             lighting: crate::systems::lighting::LightingSystem::new(),
             physics: crate::systems::physics::PhysicsWorld::new(),
+            actions: crate::systems::input::ActionMap::new(),
             culling: crate::systems::frustum::CullingSystem::new(),
             debug: crate::graphics::debug_renderer::DebugRenderer::new(),
-            asset_manager, gltf_loader, asset_db, asset_hot_reload, asset_loader_queue,
+            text: crate::graphics::text::TextRenderer::new(),
+            asset_manager, gltf_loader, asset_db, asset_hot_reload, asset_loader_queue, streaming,
             audio: crate::systems::audio::AudioSystem::new(),
+            animation: crate::systems::animation::AnimationSystem::new(),
             event_bus: crate::systems::event_bus::EventBus::new(),
+            history: crate::systems::transform_history::TransformHistory::new(),
+            selection: Vec::new(),
+            editor_settings: config.editor_settings.then(crate::app::editor_settings::EditorSettings::default_path).flatten()
+                .map(|path| crate::app::editor_settings::EditorSettings::load(&path)).unwrap_or_default(),
             hot_reload_rx,
             blob_shadow_mesh: None, blob_shadow_material: None,
-            fixed_accumulator: 0.0,
+            particles: std::collections::BTreeMap::new(), next_particles_id: 0, particle_mesh: None, particle_material: None, stream_material: None,
+            windows: std::collections::BTreeMap::new(), pending_windows: Vec::new(), next_window_id: 0,
+            fixed_timestep: crate::platform::time::FixedTimestep::new(config.physics_hz),
+            play: None,
         };
-        self.app.init(&mut ctx);
+        if let Some(scene_path) = config.scene.as_deref() {
+            match ctx.load_scene_file(scene_path) {
+                Ok(count) => log::info!("Loaded scene '{}' ({} objects)", scene_path, count),
+                Err(e) => {
+                    log::error!("Failed to load scene '{}': {}", scene_path, e);
+                    crate::app::context::record_last_error(&e);
+                }
+            }
+        }
         self.context = Some(ctx);
+        let Some(ctx) = &mut self.context else { return };
+        guarded!(self, event_loop, "ReactorApp::init", self.app.init(ctx));
+        open_pending_windows(ctx, event_loop);
     }
 
-    fn window_event(&mut self, event_loop: &ActiveEventLoop, _window_id: WindowId, event: WindowEvent) {
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId, event: WindowEvent) {
+        if self.panicked { return; }
         let Some(ctx) = &mut self.context else { return };
+        if window_id != ctx.window.id() {
+            secondary_window_event(ctx, window_id, &event);
+            return;
+        }
         ctx.reactor.handle_event(&event);
-        if self.app.on_event(ctx, &event) { return; }
+        let mut consumed = false;
+        guarded!(self, event_loop, "ReactorApp::on_event", consumed = self.app.on_event(ctx, &event));
+        if consumed { return; }
         match event {
             WindowEvent::CloseRequested => { event_loop.exit(); }
             WindowEvent::Resized(size) if size.width > 0 && size.height > 0 => {
-                self.app.on_resize(ctx, size.width, size.height);
+                if let Err(e) = ctx.reactor.recreate_swapchain_sized(size.width, size.height) {
+                    log::error!("REACTOR: swapchain recreation failed: {}", e);
+                }
+                let viewport = ctx.reactor.viewport_extent();
+                ctx.camera.set_aspect_ratio(viewport.width as f32, viewport.height as f32);
+                guarded!(self, event_loop, "ReactorApp::on_resize", self.app.on_resize(ctx, size.width, size.height));
+            }
+            WindowEvent::Focused(focused) => {
+                // Alt-tab suelta el grab; al volver se restaura el modo pedido.
+                let mode = if focused { ctx.reactor.input.cursor_mode() } else { crate::platform::CursorMode::Normal };
+                mode.apply(&ctx.window);
             }
             WindowEvent::RedrawRequested => {
                 if let Some(ref mut rx) = ctx.hot_reload_rx {
                     while let Ok(event) = rx.try_recv() { ctx.event_bus.emit(event); }
                 }
+                crate::core::profiler::begin_frame();
                 ctx.time.update();
+                ctx.update_streaming();
                 let dt = ctx.time.delta();
+                // En pausa de play mode la simulación no avanza (salvo `play_step`).
+                let sim_dt = ctx.simulation_delta(dt);
                 if ctx.config.physics_hz > 0 {
-                    let fixed_dt = 1.0 / ctx.config.physics_hz as f32;
-                    ctx.fixed_accumulator += dt;
-                    while ctx.fixed_accumulator >= fixed_dt {
-                        self.app.fixed_update(ctx, fixed_dt);
-                        ctx.fixed_accumulator -= fixed_dt;
+                    ctx.fixed_timestep.set_hz(ctx.config.physics_hz);
+                    let fixed_dt = ctx.fixed_delta();
+                    for _ in 0..ctx.fixed_timestep.update(std::time::Duration::from_secs_f32(sim_dt.max(0.0))) {
+                        ctx.physics.simulate(&mut ctx.reactor.world, fixed_dt);
+                        guarded!(self, event_loop, "ReactorApp::fixed_update", self.app.fixed_update(ctx, fixed_dt));
                     }
                 }
-                self.app.update(ctx);
-                self.app.render(ctx);
+                guarded!(self, event_loop, "ReactorApp::update", self.app.update(ctx));
+                ctx.audio.update(dt);
+                ctx.animation.update(sim_dt, &mut ctx.scene, &mut ctx.reactor.world);
+                ctx.update_particles(sim_dt);
+                guarded!(self, event_loop, "ReactorApp::render", self.app.render(ctx));
+                crate::core::profiler::end_frame();
                 ctx.reactor.input.begin_frame();
+                for window in ctx.windows.values_mut() { window.input.begin_frame(); }
+                open_pending_windows(ctx, event_loop);
                 if ctx.reactor.device_lost || ctx.reactor.exit_requested { event_loop.exit(); return; }
                 ctx.window.request_redraw();
             }
@@ -111,23 +199,258 @@ impl<A: ReactorApp> ApplicationHandler for AppRunner<A> {
         }
     }
 
+    fn device_event(&mut self, _event_loop: &ActiveEventLoop, _device_id: DeviceId, event: DeviceEvent) {
+        if let Some(ctx) = &mut self.context { ctx.reactor.input.process_device_event(&event); }
+    }
+
     fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
         if let Some(ctx) = &mut self.context {
             unsafe { let _ = ctx.reactor.context.device.device_wait_idle(); }
-            self.app.on_exit(ctx);
+            if !self.panicked {
+                let _ = crate::core::error::catch_panic("ReactorApp::on_exit", || { self.app.on_exit(ctx); Ok(()) });
+            }
+            if ctx.config.editor_settings {
+                if let Err(e) = ctx.save_editor_settings() { log::warn!("Editor settings not saved: {}", e); }
+            }
             unsafe { let _ = ctx.reactor.context.device.device_wait_idle(); }
         }
         self.context.take();
     }
 }
 
+/// Eventos de una ventana secundaria: van a su propio `Input`. Cerrarla sólo
+/// suelta su swapchain y su surface; la app sigue con la principal.
+fn secondary_window_event(ctx: &mut ReactorContext, window_id: WindowId, event: &WindowEvent) {
+    let Some((&id, window)) = ctx.windows.iter_mut().find(|(_, w)| w.target.window.id() == window_id) else { return };
+    window.input.process_event(event);
+    match event {
+        WindowEvent::CloseRequested => { ctx.windows.remove(&id); }
+        WindowEvent::Resized(size) => window.target.handle_resize(size.width, size.height),
+        _ => {}
+    }
+}
+
+/// Crea las ventanas pedidas con `open_window`. Si una falla se descarta (el
+/// error queda como último error) y `window_open` pasa a devolver `false`.
+fn open_pending_windows(ctx: &mut ReactorContext, event_loop: &ActiveEventLoop) {
+    for request in std::mem::take(&mut ctx.pending_windows) {
+        let attributes = Window::default_attributes()
+            .with_title(&request.title)
+            .with_inner_size(LogicalSize::new(request.width, request.height));
+        let window = event_loop.create_window(attributes)
+            .map_err(|e| ReactorError::new(ErrorCode::EventLoopError, format!("Failed to create window: {}", e)))
+            .and_then(|window| ctx.reactor.create_window_target(Arc::new(window)));
+        match window {
+            Ok(target) => {
+                let input = crate::platform::input::Input::without_gamepad();
+                ctx.windows.insert(request.id, crate::app::context::SecondaryWindow { target, input });
+            }
+            Err(e) => {
+                log::error!("REACTOR: window '{}' not opened: {}", request.title, e);
+                crate::app::context::record_last_error(&e);
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Bucle de eventos del proceso. winit no permite crear un segundo
+    /// `EventLoop`, así que se crea una vez y se reutiliza en cada `run`.
+    static EVENT_LOOP: RefCell<Option<EventLoop<()>>> = const { RefCell::new(None) };
+}
+
+/// Ejecuta la app hasta que se cierra la ventana. Ver `try_run`; aquí los
+/// errores sólo se muestran por consola.
 pub fn run<A: ReactorApp + 'static>(app: A) {
-    let _ = env_logger::try_init();
-    let rt = tokio::runtime::Builder::new_multi_thread()
-        .enable_all().build().expect("Failed to create Tokio runtime");
-    let _guard = rt.enter();
-    let event_loop = EventLoop::new().expect("Failed to create event loop");
-    event_loop.set_control_flow(ControlFlow::Poll);
-    let mut runner = AppRunner { app, context: None };
-    event_loop.run_app(&mut runner).expect("Event loop error");
+    if let Err(e) = try_run(app) {
+        log::error!("REACTOR: {}", e);
+    }
+}
+
+/// Ejecuta la app y devuelve cuando termina: al salir, la ventana, el
+/// `Reactor`, los recursos GPU y el estado de la app ya están destruidos.
+/// Es un bucle sobre `PumpedApp::poll_events`.
+///
+/// Se puede llamar varias veces en el mismo proceso (p. ej. un launcher que
+/// abre y cierra el renderer): el bucle de eventos se reutiliza. Las llamadas
+/// deben hacerse desde el hilo principal.
+///
+/// Errores:
+/// - `NotSupported`: llamado fuera del hilo principal.
+/// - `AlreadyInitialized`: la plataforma no permite reanudar el bucle (web,
+///   iOS) y ya se ejecutó una vez.
+/// - `EventLoopError`: winit no pudo crear o ejecutar el bucle.
+/// - `Unknown`: un callback de la app hizo panic (ver `catch_panic`).
+#[cfg(any(
+    target_os = "windows", target_os = "macos", target_os = "android", target_os = "linux",
+    target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd",
+))]
+pub fn try_run<A: ReactorApp + 'static>(app: A) -> ReactorResult<()> {
+    let mut app = PumpedApp::new(app)?;
+    while !app.poll_events_timeout(None)? {}
+    Ok(())
+}
+
+#[cfg(not(any(
+    target_os = "windows", target_os = "macos", target_os = "android", target_os = "linux",
+    target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd",
+)))]
+pub fn try_run<A: ReactorApp + 'static>(app: A) -> ReactorResult<()> {
+    crate::core::log_capture::init_log_capture();
+    let event_loop = acquire_event_loop()?;
+    let runtime = new_runtime()?;
+    let _guard = runtime.enter();
+    let mut runner = AppRunner { app, context: None, panicked: false };
+    // Sin pump/run-on-demand el bucle se consume: una segunda llamada termina
+    // en `AlreadyInitialized`.
+    event_loop.run_app(&mut runner).map_err(event_loop_error)?;
+    runner.context.take();
+    if runner.panicked {
+        return Err(panic_error());
+    }
+    Ok(())
+}
+
+/// La app dentro de un bucle de eventos que controla quien llama, para
+/// motores y editores con su propio main loop:
+///
+/// ```ignore
+/// let mut reactor = PumpedApp::new(MiJuego::default())?;
+/// loop {
+///     if reactor.poll_events()? { break; } // se pidió cerrar
+///     motor.tick();
+/// }
+/// ```
+///
+/// Cada `poll_events` despacha los eventos pendientes de ventana e input y
+/// dibuja un frame (`update` + `render` de la app). Al cerrarse, o al
+/// soltar el `PumpedApp`, la ventana y el `Reactor` se destruyen y el bucle
+/// de eventos queda libre para otro `PumpedApp` o `run`.
+///
+/// Sólo en el hilo principal: winit lo exige en macOS y Windows, y en X11 /
+/// Wayland por defecto. Desde otro hilo `new` devuelve `NotSupported`, igual
+/// que `poll_events` en plataformas sin `pump_events` (web, iOS).
+pub struct PumpedApp<A: ReactorApp> {
+    event_loop: Option<EventLoop<()>>,
+    runner: AppRunner<A>,
+    runtime: tokio::runtime::Runtime,
+    closed: bool,
+}
+
+impl<A: ReactorApp> PumpedApp<A> {
+    /// Toma el bucle de eventos; la ventana se crea en el primer `poll_events`.
+    pub fn new(app: A) -> ReactorResult<Self> {
+        crate::core::log_capture::init_log_capture();
+        let event_loop = acquire_event_loop()?;
+        event_loop.set_control_flow(ControlFlow::Poll);
+        Ok(Self {
+            event_loop: Some(event_loop),
+            runner: AppRunner { app, context: None, panicked: false },
+            runtime: new_runtime()?,
+            closed: false,
+        })
+    }
+
+    /// Despacha los eventos pendientes sin esperar. Devuelve `true` cuando la
+    /// app terminó (ventana cerrada, `exit_requested` o dispositivo perdido);
+    /// a partir de ahí no hace nada más. Un panic de la app es `Unknown`.
+    pub fn poll_events(&mut self) -> ReactorResult<bool> {
+        self.poll_events_timeout(Some(std::time::Duration::ZERO))
+    }
+
+    /// Como `poll_events`, esperando hasta `timeout` por eventos nuevos
+    /// (`None`: sin límite).
+    pub fn poll_events_timeout(&mut self, timeout: Option<std::time::Duration>) -> ReactorResult<bool> {
+        if self.closed { return Ok(true); }
+        let Some(event_loop) = self.event_loop.as_mut() else { return Ok(true) };
+        let _guard = self.runtime.enter();
+        let status = pump(event_loop, timeout, &mut self.runner)?;
+        if status {
+            self.closed = true;
+            self.runner.context.take();
+        }
+        if self.runner.panicked {
+            return Err(panic_error());
+        }
+        Ok(status)
+    }
+
+    /// Contexto del motor mientras la ventana existe.
+    pub fn context(&mut self) -> Option<&mut ReactorContext> { self.runner.context.as_mut() }
+    pub fn app(&mut self) -> &mut A { &mut self.runner.app }
+    /// Runtime de Tokio de la app, para llamar entre `poll_events` a métodos
+    /// del contexto que lanzan tareas (p. ej. `load_gltf_queued`).
+    pub fn runtime(&self) -> &tokio::runtime::Runtime { &self.runtime }
+}
+
+impl<A: ReactorApp> Drop for PumpedApp<A> {
+    fn drop(&mut self) {
+        if let Some(ctx) = &mut self.runner.context {
+            unsafe { let _ = ctx.reactor.context.device.device_wait_idle(); }
+            if !self.runner.panicked {
+                let _ = crate::core::error::catch_panic("ReactorApp::on_exit", || { self.runner.app.on_exit(ctx); Ok(()) });
+            }
+        }
+        self.runner.context.take();
+        if let Some(event_loop) = self.event_loop.take() {
+            EVENT_LOOP.with(|slot| *slot.borrow_mut() = Some(event_loop));
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "windows", target_os = "macos", target_os = "android", target_os = "linux",
+    target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd",
+))]
+fn pump<A: ReactorApp>(event_loop: &mut EventLoop<()>, timeout: Option<std::time::Duration>, runner: &mut AppRunner<A>) -> ReactorResult<bool> {
+    use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+    match event_loop.pump_app_events(timeout, runner) {
+        PumpStatus::Continue => Ok(false),
+        PumpStatus::Exit(0) => Ok(true),
+        PumpStatus::Exit(code) => Err(ReactorError::new(ErrorCode::EventLoopError, format!("Event loop exited with code {}", code))),
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows", target_os = "macos", target_os = "android", target_os = "linux",
+    target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd",
+)))]
+fn pump<A: ReactorApp>(_event_loop: &mut EventLoop<()>, _timeout: Option<std::time::Duration>, _runner: &mut AppRunner<A>) -> ReactorResult<bool> {
+    Err(ReactorError::new(ErrorCode::NotSupported, "This platform cannot pump the event loop; use run"))
+}
+
+/// El bucle de eventos de este hilo, creándolo la primera vez.
+fn acquire_event_loop() -> ReactorResult<EventLoop<()>> {
+    if let Some(event_loop) = EVENT_LOOP.with(|slot| slot.borrow_mut().take()) {
+        return Ok(event_loop);
+    }
+    // winit hace panic al crear el bucle fuera del hilo principal; aquí es un
+    // error en vez de un cuelgue o un abort.
+    match std::panic::catch_unwind(EventLoop::new) {
+        Ok(result) => result.map_err(event_loop_error),
+        Err(payload) => Err(ReactorError::new(
+            ErrorCode::NotSupported,
+            format!("The event loop must run on the main thread: {}", crate::core::error::panic_message(payload.as_ref())),
+        )),
+    }
+}
+
+fn new_runtime() -> ReactorResult<tokio::runtime::Runtime> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all().build()
+        .map_err(|e| ReactorError::with_source(ErrorCode::InternalError, "Failed to create Tokio runtime", e))
+}
+
+fn panic_error() -> ReactorError {
+    ReactorError::new(ErrorCode::Unknown, crate::core::error::get_last_error_message().unwrap_or_default())
+}
+
+fn event_loop_error(e: EventLoopError) -> ReactorError {
+    match e {
+        EventLoopError::RecreationAttempt => ReactorError::new(
+            ErrorCode::AlreadyInitialized,
+            "The event loop already exists: call run from the main thread, once per process on platforms without pump_events",
+        ),
+        e => ReactorError::new(ErrorCode::EventLoopError, format!("Event loop error: {}", e)),
+    }
 }
@@ -120,6 +120,7 @@ impl<'a> MeshBuilder<'a> {
             )
         };
 
+        let world_bounds = mesh.world_bounds(&self.transform);
         let object = SceneObject {
             mesh,
             material,
@@ -131,6 +132,16 @@ impl<'a> MeshBuilder<'a> {
             roughness: 0.5,
             emission: glam::Vec4::ZERO,
             anisotropy: 0.0,
+            instances: Vec::new(),
+            joint_matrices: Vec::new(),
+            cast_shadows: true,
+            receive_shadows: true,
+            culled: false,
+            id: 0,
+            node: None,
+            world_bounds,
+            lods: Vec::new(),
+            lod: 0,
         };
         Ok(self.ctx.scene.add(object))
     }
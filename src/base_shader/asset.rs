@@ -10,7 +10,7 @@ pub enum BaseShaderAsset {
     ShadowVert, ShadowFrag,
     PostProcessVert, PostProcessFrag,
     DecalFrag,
-    GBufferVert, GBufferFrag,
+    GBufferVert, GBufferFrag, DeferredLighting,
     BloomDownsample, BloomUpsample,
     DepthResolve, TaaResolve,
     Gtao, LightCull,
@@ -24,7 +24,7 @@ impl BaseShaderAsset {
         Self::BlenderLiveVert, Self::BlenderLiveFrag,
         Self::ShadowVert, Self::ShadowFrag,
         Self::PostProcessVert, Self::PostProcessFrag, Self::DecalFrag,
-        Self::GBufferVert, Self::GBufferFrag,
+        Self::GBufferVert, Self::GBufferFrag, Self::DeferredLighting,
         Self::BloomDownsample, Self::BloomUpsample,
         Self::DepthResolve, Self::TaaResolve,
         Self::Gtao, Self::LightCull,
@@ -40,6 +40,7 @@ impl BaseShaderAsset {
             Self::PostProcessVert => "post.fullscreen.vert", Self::PostProcessFrag => "post.fullscreen.frag",
             Self::DecalFrag => "post.decal.frag",
             Self::GBufferVert => "deferred.gbuffer.vert", Self::GBufferFrag => "deferred.gbuffer.frag",
+            Self::DeferredLighting => "deferred.lighting.frag",
             Self::BloomDownsample => "post.bloom_downsample.comp", Self::BloomUpsample => "post.bloom_upsample.comp",
             Self::DepthResolve => "post.depth_resolve.comp", Self::TaaResolve => "post.taa_resolve.comp",
             Self::Gtao => "post.gtao.comp", Self::LightCull => "compute.light_cull.comp",
@@ -54,7 +55,7 @@ impl BaseShaderAsset {
             Self::CoreVert | Self::TextureVert | Self::BlenderLiveVert | Self::ShadowVert
             | Self::PostProcessVert | Self::GBufferVert => BaseShaderStage::Vertex,
             Self::CoreFrag | Self::TextureFrag | Self::BlenderLiveFrag | Self::ShadowFrag
-            | Self::PostProcessFrag | Self::DecalFrag | Self::GBufferFrag => BaseShaderStage::Fragment,
+            | Self::PostProcessFrag | Self::DecalFrag | Self::GBufferFrag | Self::DeferredLighting => BaseShaderStage::Fragment,
             Self::BloomDownsample | Self::BloomUpsample | Self::DepthResolve | Self::TaaResolve
             | Self::Gtao | Self::LightCull | Self::IblEquirectToCube | Self::IblIrradiance
             | Self::IblPrefilter | Self::IblBrdfLut => BaseShaderStage::Compute,
@@ -67,7 +68,7 @@ impl BaseShaderAsset {
             Self::TextureVert | Self::TextureFrag => BaseShaderFamily::CoreTextured,
             Self::BlenderLiveVert | Self::BlenderLiveFrag => BaseShaderFamily::BlenderLivePbr,
             Self::ShadowVert | Self::ShadowFrag => BaseShaderFamily::ShadowDepth,
-            Self::GBufferVert | Self::GBufferFrag => BaseShaderFamily::Deferred,
+            Self::GBufferVert | Self::GBufferFrag | Self::DeferredLighting => BaseShaderFamily::Deferred,
             Self::PostProcessVert | Self::PostProcessFrag | Self::DecalFrag => BaseShaderFamily::PostFullscreen,
             Self::BloomDownsample | Self::BloomUpsample | Self::DepthResolve | Self::TaaResolve
             | Self::Gtao | Self::LightCull => BaseShaderFamily::PostCompute,
@@ -90,6 +91,7 @@ impl BaseShaderAsset {
             Self::DecalFrag => "Decal projection: projects textures onto G-Buffer depth geometry",
             Self::GBufferVert => "G-Buffer vert: world pos + normal + UV + vertex color",
             Self::GBufferFrag => "G-Buffer frag: escribe 4 attachments (albedo/AO, normal/material, emissive, motion/depth/flags)",
+            Self::DeferredLighting => "Deferred lighting: G-Buffer + depth → luces del LightingSystem + CSM PCF",
             Self::BloomDownsample => "Bloom 13-tap Karis downsample con threshold mip 0",
             Self::BloomUpsample => "Bloom 9-tap upsample con tent filter",
             Self::DepthResolve => "Depth MSAA → single-sample R32F (mín sample)",
@@ -112,6 +114,7 @@ impl BaseShaderAsset {
             Self::PostProcessVert => "shaders/post_process_vert.spv", Self::PostProcessFrag => "shaders/post_process_frag.spv",
             Self::DecalFrag => "shaders/post/decal.spv",
             Self::GBufferVert => "shaders/deferred/gbuffer_vert.spv", Self::GBufferFrag => "shaders/deferred/gbuffer_frag.spv",
+            Self::DeferredLighting => "shaders/deferred/lighting_resolve.spv",
             Self::BloomDownsample => "shaders/post/bloom_downsample.spv", Self::BloomUpsample => "shaders/post/bloom_upsample.spv",
             Self::DepthResolve => "shaders/post/depth_resolve.spv", Self::TaaResolve => "shaders/post/taa_resolve.spv",
             Self::Gtao => "shaders/post/gtao.spv", Self::LightCull => "shaders/compute/light_cull.spv",
@@ -129,6 +132,7 @@ impl BaseShaderAsset {
             Self::PostProcessVert => Some("shaders/post/post_process.vert"), Self::PostProcessFrag => Some("shaders/post/post_process.frag"),
             Self::DecalFrag => Some("shaders/post/decal.frag"),
             Self::GBufferVert => Some("shaders/deferred/gbuffer.vert"), Self::GBufferFrag => Some("shaders/deferred/gbuffer.frag"),
+            Self::DeferredLighting => Some("shaders/deferred/lighting_resolve.frag"),
             Self::BloomDownsample => Some("shaders/post/bloom_downsample.comp"), Self::BloomUpsample => Some("shaders/post/bloom_upsample.comp"),
             Self::DepthResolve => Some("shaders/post/depth_resolve.comp"), Self::TaaResolve => Some("shaders/post/taa_resolve.comp"),
             Self::Gtao => Some("shaders/post/gtao.comp"), Self::LightCull => Some("shaders/compute/light_cull.comp"),
@@ -147,6 +151,7 @@ impl BaseShaderAsset {
             Self::PostProcessVert => include_bytes!("../../shaders/post_process_vert.spv"), Self::PostProcessFrag => include_bytes!("../../shaders/post_process_frag.spv"),
             Self::DecalFrag => include_bytes!("../../shaders/post/decal.spv"),
             Self::GBufferVert => include_bytes!("../../shaders/deferred/gbuffer_vert.spv"), Self::GBufferFrag => include_bytes!("../../shaders/deferred/gbuffer_frag.spv"),
+            Self::DeferredLighting => include_bytes!("../../shaders/deferred/lighting_resolve.spv"),
             Self::BloomDownsample => include_bytes!("../../shaders/post/bloom_downsample.spv"), Self::BloomUpsample => include_bytes!("../../shaders/post/bloom_upsample.spv"),
             Self::DepthResolve => include_bytes!("../../shaders/post/depth_resolve.spv"), Self::TaaResolve => include_bytes!("../../shaders/post/taa_resolve.spv"),
             Self::Gtao => include_bytes!("../../shaders/post/gtao.spv"), Self::LightCull => include_bytes!("../../shaders/compute/light_cull.spv"),
@@ -138,6 +138,7 @@ impl BaseShaderCookbook {
 
     pub fn apply_to_post_process(&self, post_process: &mut PostProcessPipeline) {
         post_process.enabled = self.post_enabled; post_process.settings = self.post_settings;
+        post_process.settings.apply_aa_settings(&self.aa_settings);
     }
     pub fn apply_to_shadow_config(&self, target: &mut ShadowConfig) { *target = self.shadow_config.clone(); }
     pub fn apply_to_aa_settings(&self, target: &mut AASettings) { *target = self.aa_settings; }
@@ -193,7 +194,7 @@ impl BaseShaderCookbook {
         let layouts = mr.create_descriptor_set_layouts(&ctx.device)?;
         let pipeline = Pipeline::with_config_and_cache_multi_color(&ctx.device, render_pass,
             &vert_asset.words(), &frag_asset.words(), width, height, config, &layouts,
-            color_formats, depth_format, vk::PipelineCache::null())?;
+            color_formats, depth_format, ctx.pipeline_cache())?;
         Ok((pipeline, layouts))
     }
 }
@@ -0,0 +1,279 @@
+use crate::compute::pipeline::ComputePipeline;
+use crate::core::arc_handle::ArcDevice;
+use crate::core::context::VulkanContext;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::buffer::Buffer;
+use ash::vk;
+use gpu_allocator::vulkan::Allocator;
+use gpu_allocator::MemoryLocation;
+use std::sync::{Arc, Mutex};
+
+/// Self-contained compute pipeline for one-off GPU work: a `ComputePipeline`
+/// whose set 0 holds `storage_buffers` storage buffers (bindings `0..n`), plus
+/// the descriptor set they are written into.
+///
+/// `dispatch` records into a one-shot command buffer and blocks on a fence,
+/// so results can be read back with `Buffer::read` as soon as it returns.
+pub struct ComputeJob {
+    pub pipeline: ComputePipeline,
+    pub descriptor_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    descriptor_set: vk::DescriptorSet,
+    bound: Vec<bool>,
+    push_constant_size: u32,
+    device: ArcDevice,
+}
+
+impl ComputeJob {
+    pub fn new(
+        ctx: &VulkanContext,
+        shader_code: &[u32],
+        storage_buffers: u32,
+        push_constant_size: Option<u32>,
+    ) -> ReactorResult<Self> {
+        let device = ctx.ash_device();
+
+        let bindings: Vec<vk::DescriptorSetLayoutBinding> = (0..storage_buffers)
+            .map(|binding| {
+                vk::DescriptorSetLayoutBinding::default()
+                    .binding(binding)
+                    .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                    .descriptor_count(1)
+                    .stage_flags(vk::ShaderStageFlags::COMPUTE)
+            })
+            .collect();
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [vk::DescriptorPoolSize {
+            ty: vk::DescriptorType::STORAGE_BUFFER,
+            descriptor_count: storage_buffers.max(1),
+        }];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(1);
+        let descriptor_pool = match unsafe { device.create_descriptor_pool(&pool_info, None) } {
+            Ok(pool) => pool,
+            Err(e) => {
+                unsafe { device.destroy_descriptor_set_layout(descriptor_layout, None) };
+                return Err(e.into());
+            }
+        };
+
+        let set_layouts = [descriptor_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(descriptor_pool)
+            .set_layouts(&set_layouts);
+        let created = unsafe { device.allocate_descriptor_sets(&alloc_info) }
+            .map_err(ReactorError::from)
+            .and_then(|sets| {
+                let pipeline = ComputePipeline::new(ctx, shader_code, &set_layouts, push_constant_size)?;
+                Ok((sets[0], pipeline))
+            });
+        let (descriptor_set, pipeline) = match created {
+            Ok(created) => created,
+            Err(e) => {
+                unsafe {
+                    device.destroy_descriptor_pool(descriptor_pool, None);
+                    device.destroy_descriptor_set_layout(descriptor_layout, None);
+                }
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            pipeline,
+            descriptor_layout,
+            descriptor_pool,
+            descriptor_set,
+            bound: vec![false; storage_buffers as usize],
+            push_constant_size: push_constant_size.unwrap_or(0),
+            device: ctx.device.clone(),
+        })
+    }
+
+    /// Host-visible storage buffer suitable for `bind_buffer` and `Buffer::read`.
+    pub fn create_buffer(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        size: u64,
+        extra_usage: vk::BufferUsageFlags,
+    ) -> ReactorResult<Buffer> {
+        if size == 0 {
+            return Err(ReactorError::invalid_parameter("compute buffer size must be > 0"));
+        }
+        Buffer::new(
+            ctx,
+            allocator,
+            size,
+            vk::BufferUsageFlags::STORAGE_BUFFER | extra_usage,
+            MemoryLocation::CpuToGpu,
+        )
+    }
+
+    pub fn bind_buffer(&mut self, binding: u32, buffer: &Buffer) -> ReactorResult<()> {
+        let Some(slot) = self.bound.get_mut(binding as usize) else {
+            return Err(ReactorError::invalid_parameter(format!(
+                "binding {} out of range (job has {} storage buffers)",
+                binding,
+                self.bound.len()
+            )));
+        };
+
+        let buffer_info = vk::DescriptorBufferInfo::default()
+            .buffer(buffer.handle)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(self.descriptor_set)
+            .dst_binding(binding)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .buffer_info(std::slice::from_ref(&buffer_info));
+        unsafe { self.device.update_descriptor_sets(&[write], &[]) };
+        *slot = true;
+        Ok(())
+    }
+
+    /// Records the dispatch into a one-shot command buffer, submits it and
+    /// waits on a fence. Every binding must have a buffer bound.
+    ///
+    /// Uses the graphics queue: compute buffers are created with exclusive
+    /// sharing on that family, so no ownership transfer is needed.
+    pub fn dispatch(
+        &self,
+        ctx: &VulkanContext,
+        group_count: [u32; 3],
+        push_constants: &[u8],
+    ) -> ReactorResult<()> {
+        if let Some(missing) = self.bound.iter().position(|bound| !bound) {
+            return Err(ReactorError::invalid_parameter(format!(
+                "storage buffer binding {} has no buffer bound",
+                missing
+            )));
+        }
+        if push_constants.len() as u32 > self.push_constant_size {
+            return Err(ReactorError::invalid_parameter(format!(
+                "{} bytes of push constants exceed the declared {}",
+                push_constants.len(),
+                self.push_constant_size
+            )));
+        }
+        let limits = unsafe {
+            ctx.ash_instance()
+                .get_physical_device_properties(ctx.physical_device)
+                .limits
+        };
+        for (axis, (&count, &max)) in group_count
+            .iter()
+            .zip(limits.max_compute_work_group_count.iter())
+            .enumerate()
+        {
+            if count == 0 || count > max {
+                return Err(ReactorError::invalid_parameter(format!(
+                    "workgroup count {} on axis {} must be in 1..={}",
+                    count, axis, max
+                )));
+            }
+        }
+
+        let device = ctx.ash_device();
+        let pool_info = vk::CommandPoolCreateInfo::default()
+            .queue_family_index(ctx.queue_family_index)
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT);
+        let command_pool = unsafe { device.create_command_pool(&pool_info, None)? };
+        let fence = match unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None) } {
+            Ok(fence) => fence,
+            Err(e) => {
+                unsafe { device.destroy_command_pool(command_pool, None) };
+                return Err(e.into());
+            }
+        };
+
+        let result = self.record_and_submit(ctx, command_pool, fence, group_count, push_constants);
+
+        unsafe {
+            device.destroy_fence(fence, None);
+            device.destroy_command_pool(command_pool, None);
+        }
+        result
+    }
+
+    fn record_and_submit(
+        &self,
+        ctx: &VulkanContext,
+        command_pool: vk::CommandPool,
+        fence: vk::Fence,
+        group_count: [u32; 3],
+        push_constants: &[u8],
+    ) -> ReactorResult<()> {
+        let device = ctx.ash_device();
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(command_pool)
+            .command_buffer_count(1);
+        let command_buffer = unsafe { device.allocate_command_buffers(&alloc_info)?[0] };
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        unsafe {
+            device.begin_command_buffer(command_buffer, &begin_info)?;
+            self.pipeline.bind(command_buffer, device);
+            device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.layout,
+                0,
+                &[self.descriptor_set],
+                &[],
+            );
+            if !push_constants.is_empty() {
+                device.cmd_push_constants(
+                    command_buffer,
+                    self.pipeline.layout,
+                    vk::ShaderStageFlags::COMPUTE,
+                    0,
+                    push_constants,
+                );
+            }
+            device.cmd_dispatch(command_buffer, group_count[0], group_count[1], group_count[2]);
+
+            // Make shader writes visible to `Buffer::read` on the host.
+            let barrier = vk::MemoryBarrier::default()
+                .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+                .dst_access_mask(vk::AccessFlags::HOST_READ);
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+            device.end_command_buffer(command_buffer)?;
+
+            let command_buffers = [command_buffer];
+            let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+            device
+                .queue_submit(ctx.graphics_queue, &[submit_info], fence)
+                .map_err(|e| {
+                    ReactorError::with_source(ErrorCode::VulkanSynchronization, "compute submit failed", e)
+                })?;
+            device
+                .wait_for_fences(&[fence], true, u64::MAX)
+                .map_err(|e| {
+                    ReactorError::with_source(ErrorCode::VulkanSynchronization, "compute fence wait failed", e)
+                })?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ComputeJob {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_descriptor_pool(self.descriptor_pool, None);
+            self.device.destroy_descriptor_set_layout(self.descriptor_layout, None);
+        }
+    }
+}
@@ -2,10 +2,12 @@
 // Contains compute shader and pipeline abstractions
 
 pub mod dispatch;
+pub mod job;
 pub mod particles;
 pub mod pipeline;
 
 pub use dispatch::ComputeDispatch;
+pub use job::ComputeJob;
 pub use particles::{
     EmitShape, GPUParticle, GPUParticleEmitterConfig, GPUParticleSystem, ParticlePushConstants,
 };
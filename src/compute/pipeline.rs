@@ -1,5 +1,6 @@
 use crate::core::context::VulkanContext;
 use crate::core::error::ReactorResult;
+use crate::core::pipeline_cache::record_pipeline_feedback;
 use ash::vk;
 use std::ffi::CStr;
 
@@ -44,19 +45,25 @@ impl ComputePipeline {
 
         let layout = unsafe { device.create_pipeline_layout(&layout_info, None)? };
 
+        let mut feedback = vk::PipelineCreationFeedback::default();
+        let mut feedback_info =
+            vk::PipelineCreationFeedbackCreateInfo::default().pipeline_creation_feedback(&mut feedback);
         let pipeline_info = vk::ComputePipelineCreateInfo::default()
             .stage(stage_info)
-            .layout(layout);
+            .layout(layout)
+            .push_next(&mut feedback_info);
 
+        let pipeline_cache = ctx.pipeline_cache();
         let pipelines = unsafe {
             device
-                .create_compute_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
+                .create_compute_pipelines(pipeline_cache, &[pipeline_info], None)
                 .map_err(|(_, e)| e)?
         };
 
         unsafe {
             device.destroy_shader_module(shader_module, None);
         }
+        record_pipeline_feedback(pipeline_cache, &feedback);
 
         Ok(Self {
             pipeline: pipelines[0],
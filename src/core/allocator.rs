@@ -5,8 +5,8 @@
 // - Thread-safe access via Arc<Mutex<Allocator>>
 // - Integration with ReactorContext (via ArcInstance/ArcDevice)
 // - ReactorResult-based error handling (never Box<dyn std::error::Error + Send + Sync>)
-//
-// Future (Fase 1.2): Add per-pool allocation tracking and VRAM stats.
+// - Process-wide allocation accounting (`MemoryStats`): every allocate/free
+//   of a tracked buffer or image updates atomic byte/allocation counters.
 // =============================================================================
 
 use ash::vk;
@@ -14,11 +14,128 @@ use gpu_allocator::vulkan::{
     Allocation, AllocationCreateDesc, AllocationScheme, Allocator, AllocatorCreateDesc,
 };
 use gpu_allocator::MemoryLocation;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::core::context::VulkanContext;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 
+/// What a tracked allocation backs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationKind {
+    Buffer,
+    Image,
+}
+
+/// Snapshot of the engine's own GPU allocations.
+///
+/// Device-local memory types live in the device-local (VRAM) heap, so
+/// `device_local_bytes` includes host-visible VRAM (ReBAR). `host_visible_bytes`
+/// only counts system-memory types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub device_local_bytes: u64,
+    pub host_visible_bytes: u64,
+    pub buffer_bytes: u64,
+    pub image_bytes: u64,
+    pub buffer_allocations: u64,
+    pub image_allocations: u64,
+}
+
+impl MemoryStats {
+    pub fn total_bytes(&self) -> u64 {
+        self.buffer_bytes + self.image_bytes
+    }
+
+    pub fn total_allocations(&self) -> u64 {
+        self.buffer_allocations + self.image_allocations
+    }
+
+    pub fn device_local_mb(&self) -> u64 {
+        self.device_local_bytes / (1024 * 1024)
+    }
+}
+
+struct MemoryCounters {
+    device_local_bytes: AtomicU64,
+    host_visible_bytes: AtomicU64,
+    buffer_bytes: AtomicU64,
+    image_bytes: AtomicU64,
+    buffer_allocations: AtomicU64,
+    image_allocations: AtomicU64,
+}
+
+impl MemoryCounters {
+    const fn new() -> Self {
+        Self {
+            device_local_bytes: AtomicU64::new(0),
+            host_visible_bytes: AtomicU64::new(0),
+            buffer_bytes: AtomicU64::new(0),
+            image_bytes: AtomicU64::new(0),
+            buffer_allocations: AtomicU64::new(0),
+            image_allocations: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, kind: AllocationKind, size: u64, properties: vk::MemoryPropertyFlags, freed: bool) {
+        let apply = |counter: &AtomicU64, amount: u64| {
+            if freed {
+                // Saturating: a double free must not wrap the counter around.
+                let mut current = counter.load(Ordering::Relaxed);
+                while let Err(actual) = counter.compare_exchange_weak(
+                    current,
+                    current.saturating_sub(amount),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    current = actual;
+                }
+            } else {
+                counter.fetch_add(amount, Ordering::Relaxed);
+            }
+        };
+
+        if properties.contains(vk::MemoryPropertyFlags::DEVICE_LOCAL) {
+            apply(&self.device_local_bytes, size);
+        } else if properties.contains(vk::MemoryPropertyFlags::HOST_VISIBLE) {
+            apply(&self.host_visible_bytes, size);
+        }
+        match kind {
+            AllocationKind::Buffer => {
+                apply(&self.buffer_bytes, size);
+                apply(&self.buffer_allocations, 1);
+            }
+            AllocationKind::Image => {
+                apply(&self.image_bytes, size);
+                apply(&self.image_allocations, 1);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> MemoryStats {
+        MemoryStats {
+            device_local_bytes: self.device_local_bytes.load(Ordering::Relaxed),
+            host_visible_bytes: self.host_visible_bytes.load(Ordering::Relaxed),
+            buffer_bytes: self.buffer_bytes.load(Ordering::Relaxed),
+            image_bytes: self.image_bytes.load(Ordering::Relaxed),
+            buffer_allocations: self.buffer_allocations.load(Ordering::Relaxed),
+            image_allocations: self.image_allocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static MEMORY_COUNTERS: MemoryCounters = MemoryCounters::new();
+
+/// Count a successful allocation. Call right after `Allocator::allocate`.
+pub fn track_allocation(kind: AllocationKind, allocation: &Allocation) {
+    MEMORY_COUNTERS.record(kind, allocation.size(), allocation.memory_properties(), false);
+}
+
+/// Undo `track_allocation`. Call right before `Allocator::free`.
+pub fn track_free(kind: AllocationKind, allocation: &Allocation) {
+    MEMORY_COUNTERS.record(kind, allocation.size(), allocation.memory_properties(), true);
+}
+
 /// Thread-safe GPU memory allocator.
 ///
 /// Wraps `gpu-allocator::vulkan::Allocator` and provides safe access
@@ -55,6 +172,11 @@ impl MemoryAllocator {
         })
     }
 
+    /// Current totals for every tracked allocation in the process.
+    pub fn stats() -> MemoryStats {
+        MEMORY_COUNTERS.snapshot()
+    }
+
     /// Clone the inner Arc for sharing with subsystems.
     pub fn get(&self) -> Arc<Mutex<Allocator>> {
         self.allocator.clone()
@@ -86,6 +208,10 @@ impl MemoryAllocator {
                     e,
                 )
             })
+            .map(|allocation| {
+                track_allocation(AllocationKind::Buffer, &allocation);
+                allocation
+            })
     }
 
     /// Allocate memory for an image.
@@ -114,10 +240,15 @@ impl MemoryAllocator {
                     e,
                 )
             })
+            .map(|allocation| {
+                track_allocation(AllocationKind::Image, &allocation);
+                allocation
+            })
     }
 
     /// Free a previous allocation.
-    pub fn free(&self, allocation: Allocation) -> ReactorResult<()> {
+    pub fn free(&self, kind: AllocationKind, allocation: Allocation) -> ReactorResult<()> {
+        track_free(kind, &allocation);
         self.allocator
             .lock()
             .unwrap()
@@ -131,3 +262,35 @@ impl MemoryAllocator {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_return_to_zero_after_free() {
+        let counters = MemoryCounters::new();
+        let vram = vk::MemoryPropertyFlags::DEVICE_LOCAL;
+        let upload = vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+
+        counters.record(AllocationKind::Buffer, 1024, vram, false);
+        counters.record(AllocationKind::Image, 4096, vram, false);
+        counters.record(AllocationKind::Buffer, 256, upload, false);
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.device_local_bytes, 5120);
+        assert_eq!(stats.host_visible_bytes, 256);
+        assert_eq!(stats.buffer_bytes, 1280);
+        assert_eq!(stats.image_bytes, 4096);
+        assert_eq!(stats.total_allocations(), 3);
+
+        counters.record(AllocationKind::Buffer, 1024, vram, true);
+        counters.record(AllocationKind::Image, 4096, vram, true);
+        counters.record(AllocationKind::Buffer, 256, upload, true);
+        assert_eq!(counters.snapshot(), MemoryStats::default());
+
+        // An unmatched free saturates instead of wrapping.
+        counters.record(AllocationKind::Image, 64, vram, true);
+        assert_eq!(counters.snapshot(), MemoryStats::default());
+    }
+}
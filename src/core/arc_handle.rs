@@ -11,8 +11,9 @@
 // - No manual destroy calls anywhere else in the engine.
 // =============================================================================
 
+use crate::core::frame_timeline::DeletionQueue;
 use ash::vk;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 // =============================================================================
 // ArcInstance — Arc-wrapped VkInstance with RAII Drop
@@ -120,10 +121,23 @@ impl std::ops::Deref for ArcInstance {
 /// Inner data for an Arc-wrapped Vulkan logical device.
 struct DeviceInner {
     device: ash::Device,
+    /// Destructions deferred until the GPU is done with them.
+    deletion: Mutex<DeletionQueue>,
 }
 
 impl Drop for DeviceInner {
     fn drop(&mut self) {
+        // Anything still deferred goes before the device does.
+        let queue = self.deletion.get_mut().unwrap_or_else(|e| e.into_inner());
+        let retired = queue.take_all();
+        if !retired.is_empty() {
+            unsafe {
+                let _ = self.device.device_wait_idle();
+            }
+            for retire in retired {
+                retire(&self.device);
+            }
+        }
         // SAFETY: We only destroy the device after all its children
         // (swapchains, pipelines, buffers, etc.) have been dropped,
         // because those children hold their own Arc<Device> clones.
@@ -152,7 +166,7 @@ pub struct ArcDevice {
 impl ArcDevice {
     /// Wrap a freshly-created VkDevice.
     pub fn new(device: ash::Device) -> Self {
-        Self { inner: Arc::new(DeviceInner { device }) }
+        Self { inner: Arc::new(DeviceInner { device, deletion: Mutex::new(DeletionQueue::default()) }) }
     }
 
     /// Borrow the underlying `ash::Device`.
@@ -166,6 +180,27 @@ impl ArcDevice {
     pub fn ref_count(&self) -> usize {
         Arc::strong_count(&self.inner)
     }
+
+    /// Runs `destroy` once the GPU has finished every frame submitted so
+    /// far (see `FrameTimeline`), or right away when no frames are being
+    /// tracked. `destroy` must not capture this `ArcDevice`.
+    pub fn destroy_deferred(&self, destroy: impl FnOnce(&ash::Device) + Send + 'static) {
+        let immediate = self.deletion_queue().push(Box::new(destroy));
+        if let Some(destroy) = immediate {
+            destroy(self.get());
+        }
+    }
+
+    /// Reports (and asserts in debug builds) when `handle` is destroyed
+    /// while a frame in flight still uses it. Only active with in-flight
+    /// tracking on.
+    pub fn check_not_in_flight(&self, handle: impl vk::Handle, what: &str) {
+        self.deletion_queue().check_not_in_flight(handle.as_raw(), what);
+    }
+
+    pub(crate) fn deletion_queue(&self) -> MutexGuard<'_, DeletionQueue> {
+        self.inner.deletion.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl std::ops::Deref for ArcDevice {
@@ -191,6 +226,10 @@ impl Drop for SurfaceInner {
         // SAFETY: Surface must be destroyed before Instance, but after Swapchain.
         // Since ArcSurface holds a reference to the surface_loader (which
         // depends on Instance), and swapchains hold ArcSurface, order is correct.
+        // Headless contexts carry a null surface and never enabled VK_KHR_surface.
+        if self.surface == vk::SurfaceKHR::null() {
+            return;
+        }
         unsafe {
             self.surface_loader.destroy_surface(self.surface, None);
         }
@@ -1,7 +1,9 @@
 use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::memory_budget::{self, GpuMemoryBudget};
+use crate::core::pipeline_cache::{self, PipelineCacheStats};
 use crate::core::vrs::VrsCapabilities;
+use crate::utils::gpu_detector::GPUInfo;
 use ash::vk;
 
 use super::VulkanContext;
@@ -44,6 +46,12 @@ impl VulkanContext {
         self.surface.handle()
     }
 
+    /// True for contexts built with `new_headless` (no surface to present to).
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        self.surface.handle() == vk::SurfaceKHR::null()
+    }
+
     pub fn ref_counts(&self) -> (usize, usize, usize) {
         (
             self.instance.ref_count(),
@@ -52,6 +60,22 @@ impl VulkanContext {
         )
     }
 
+    /// `deviceName` of the selected physical device.
+    pub fn gpu_name(&self) -> String {
+        self.gpu_info().name.clone()
+    }
+
+    /// The physical device this context runs on.
+    pub fn gpu_info(&self) -> &GPUInfo {
+        self.gpus.selected()
+    }
+
+    /// Every physical device found, in enumeration order (the index
+    /// `GpuPreference::Index` refers to), suitable or not.
+    pub fn available_gpus(&self) -> &[GPUInfo] {
+        &self.gpus.gpus
+    }
+
     #[inline]
     pub fn debug_namer(&self) -> &DebugNamer {
         &self.debug_namer
@@ -70,11 +94,49 @@ impl VulkanContext {
         self.fragment_shading_rate.is_some()
     }
 
+    #[inline]
+    pub fn supports_ray_query(&self) -> bool {
+        self.ray_query
+    }
+
+    #[inline]
+    pub fn supports_timeline_semaphore(&self) -> bool {
+        self.timeline_semaphore
+    }
+
+    #[inline]
+    pub fn max_sampler_anisotropy(&self) -> Option<f32> {
+        self.max_sampler_anisotropy
+    }
+
+    #[inline]
+    pub fn supports_fragment_stores_and_atomics(&self) -> bool {
+        self.fragment_stores_and_atomics
+    }
+
     #[inline]
     pub fn vrs_capabilities(&self) -> &VrsCapabilities {
         &self.vrs_capabilities
     }
 
+    /// Cache to pass to every `vkCreate*Pipelines` call; null when disabled.
+    #[inline]
+    pub fn pipeline_cache(&self) -> vk::PipelineCache {
+        self.pipeline_cache
+            .as_ref()
+            .map_or(vk::PipelineCache::null(), |cache| cache.handle())
+    }
+
+    pub fn pipeline_cache_stats(&self) -> PipelineCacheStats {
+        match &self.pipeline_cache {
+            Some(cache) => cache.stats(),
+            None => {
+                let (hits, misses) = pipeline_cache::pipeline_cache_counters();
+                PipelineCacheStats { hits, misses, ..Default::default() }
+            }
+        }
+    }
+
     #[inline]
     pub fn has_async_compute(&self) -> bool {
         self.compute_queue.is_some()
@@ -2,12 +2,15 @@ use crate::core::arc_handle::{ArcDevice, ArcInstance, ArcSurface};
 use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::memory_budget;
+use crate::core::pipeline_cache::{self, CacheIdentity, PipelineCache};
+use crate::core::validation::{self, ValidationMessageKind, ValidationSeverity};
 use crate::core::vrs::{self, VrsCapabilities, VrsContext};
-use crate::utils::gpu_detector::GPUDetector;
+use crate::utils::gpu_detector::{GPUDetector, GpuSelection};
 use ash::vk;
 use ash::Entry;
 use raw_window_handle::HasWindowHandle;
 use std::ffi::{c_void, CStr};
+use std::sync::Arc;
 
 use super::VulkanContext;
 
@@ -17,39 +20,62 @@ unsafe extern "system" fn vulkan_debug_callback(
     p_callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT,
     _user_data: *mut c_void,
 ) -> vk::Bool32 {
-    let callback_data = *p_callback_data;
+    // Unwinding into the Vulkan loader is undefined behavior: a panic here
+    // becomes the last error instead.
+    let _ = crate::core::error::catch_panic("vulkan debug callback", || {
+        record_debug_message(message_severity, message_type, &*p_callback_data);
+        Ok(())
+    });
+    vk::FALSE
+}
+
+unsafe fn record_debug_message(
+    message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    message_type: vk::DebugUtilsMessageTypeFlagsEXT,
+    callback_data: &vk::DebugUtilsMessengerCallbackDataEXT,
+) {
     let message = if callback_data.p_message.is_null() {
         std::borrow::Cow::from("")
     } else {
         CStr::from_ptr(callback_data.p_message).to_string_lossy()
     };
+    let message_id_name = if callback_data.p_message_id_name.is_null() {
+        std::borrow::Cow::from("")
+    } else {
+        CStr::from_ptr(callback_data.p_message_id_name).to_string_lossy()
+    };
 
-    let type_str = match message_type {
-        vk::DebugUtilsMessageTypeFlagsEXT::GENERAL => "GENERAL",
-        vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION => "VALIDATION",
-        vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE => "PERFORMANCE",
-        _ => "UNKNOWN",
+    let (type_str, kind) = match message_type {
+        vk::DebugUtilsMessageTypeFlagsEXT::GENERAL => ("GENERAL", ValidationMessageKind::General),
+        vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION => ("VALIDATION", ValidationMessageKind::Validation),
+        vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE => ("PERFORMANCE", ValidationMessageKind::Performance),
+        _ => ("UNKNOWN", ValidationMessageKind::General),
     };
 
-    match message_severity {
-        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => {
-            log::error!("[Vulkan {}] {}", type_str, message);
-        }
+    let severity = match message_severity {
+        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => ValidationSeverity::Error,
         vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => {
             log::warn!("[Vulkan {}] {}", type_str, message);
+            ValidationSeverity::Warning
         }
         vk::DebugUtilsMessageSeverityFlagsEXT::INFO => {
             log::info!("[Vulkan {}] {}", type_str, message);
-        }
-        vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE => {
-            log::trace!("[Vulkan {}] {}", type_str, message);
+            ValidationSeverity::Info
         }
         _ => {
-            log::debug!("[Vulkan {}] {}", type_str, message);
+            log::trace!("[Vulkan {}] {}", type_str, message);
+            ValidationSeverity::Verbose
         }
-    }
+    };
 
-    vk::FALSE
+    // Errors are logged by `set_last_error` inside the capture.
+    validation::record_validation_message(
+        severity,
+        kind,
+        callback_data.message_id_number,
+        message_id_name.into_owned(),
+        message.into_owned(),
+    );
 }
 
 struct QueueFamilyInfo {
@@ -125,6 +151,73 @@ fn device_extension_supported(
 
 impl VulkanContext {
     pub fn new(window: &impl HasWindowHandle, enable_ray_tracing: bool) -> ReactorResult<Self> {
+        let arc_instance = Self::create_arc_instance(true)?;
+
+        let (surface, surface_loader) = Self::create_surface(&arc_instance, window)?;
+        let arc_surface = ArcSurface::new(surface, surface_loader);
+
+        let gpus = GPUDetector::detect(
+            arc_instance.get(),
+            arc_surface.loader(),
+            arc_surface.handle(),
+        )
+        .map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDeviceCreation, "GPU detection failed", e)
+        })?;
+
+        Self::from_parts(arc_instance, arc_surface, gpus, enable_ray_tracing)
+    }
+
+    /// A context for presenting to another window: same instance, device and
+    /// queues, with a new surface. Swapchains built from the returned
+    /// context target `window`; the device lives until every clone is gone,
+    /// so dropping it only destroys the surface.
+    pub fn for_window(&self, window: &impl HasWindowHandle) -> ReactorResult<Self> {
+        if self.is_headless() {
+            return Err(ReactorError::new(
+                ErrorCode::NotSupported,
+                "A headless context cannot present to windows",
+            ));
+        }
+        let (surface, surface_loader) = Self::create_surface(&self.instance, window)?;
+        let surface = ArcSurface::new(surface, surface_loader);
+        let supported = unsafe {
+            surface.loader().get_physical_device_surface_support(
+                self.physical_device,
+                self.queue_family_index,
+                surface.handle(),
+            )
+        }
+        .map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanSurfaceCreation, "Surface support query failed", e)
+        })?;
+        if !supported {
+            return Err(ReactorError::new(
+                ErrorCode::VulkanSurfaceCreation,
+                "The graphics queue cannot present to this window",
+            ));
+        }
+        Ok(Self { surface, ..self.clone() })
+    }
+
+    /// Context without a window or surface, for offscreen rendering (CI,
+    /// tests, capture tools). `surface_khr()` returns a null handle.
+    ///
+    /// Any device with a graphics queue qualifies, so software rasterizers
+    /// (lavapipe/llvmpipe) are picked when no GPU is present.
+    pub fn new_headless(enable_ray_tracing: bool) -> ReactorResult<Self> {
+        let arc_instance = Self::create_arc_instance(false)?;
+        let surface_loader = ash::khr::surface::Instance::new(arc_instance.entry(), arc_instance.get());
+        let arc_surface = ArcSurface::new(vk::SurfaceKHR::null(), surface_loader);
+
+        let gpus = GPUDetector::detect_headless(arc_instance.get()).map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDeviceCreation, "GPU detection failed", e)
+        })?;
+
+        Self::from_parts(arc_instance, arc_surface, gpus, enable_ray_tracing)
+    }
+
+    fn create_arc_instance(with_surface: bool) -> ReactorResult<ArcInstance> {
         let entry = unsafe {
             Entry::load().map_err(|e| {
                 ReactorError::with_source(
@@ -136,7 +229,7 @@ impl VulkanContext {
         };
 
         let (instance, debug_utils, debug_messenger) =
-            Self::create_instance(&entry).map_err(|e| {
+            Self::create_instance(&entry, with_surface).map_err(|e| {
                 ReactorError::with_source(
                     ErrorCode::VulkanInstanceCreation,
                     "Failed to create VkInstance",
@@ -144,19 +237,16 @@ impl VulkanContext {
                 )
             })?;
 
-        let arc_instance = ArcInstance::new(entry, instance, debug_utils, debug_messenger);
-
-        let (surface, surface_loader) = Self::create_surface(&arc_instance, window)?;
-        let arc_surface = ArcSurface::new(surface, surface_loader);
+        Ok(ArcInstance::new(entry, instance, debug_utils, debug_messenger))
+    }
 
-        let gpu_info = GPUDetector::detect(
-            arc_instance.get(),
-            arc_surface.loader(),
-            arc_surface.handle(),
-        )
-        .map_err(|e| {
-            ReactorError::with_source(ErrorCode::VulkanDeviceCreation, "GPU detection failed", e)
-        })?;
+    fn from_parts(
+        arc_instance: ArcInstance,
+        arc_surface: ArcSurface,
+        gpus: GpuSelection,
+        enable_ray_tracing: bool,
+    ) -> ReactorResult<Self> {
+        let gpu_info = gpus.selected();
         let pdevice = gpu_info.device;
         let queue_family_index = gpu_info.queue_family_index;
 
@@ -185,6 +275,26 @@ impl VulkanContext {
         let has_memory_budget =
             device_extension_supported(arc_instance.get(), pdevice, memory_budget_ext_name);
 
+        // Ray query lets raster shaders trace against the TLAS (RT shadows).
+        let ray_query = enable_ray_tracing
+            && device_extension_supported(arc_instance.get(), pdevice, ash::khr::ray_query::NAME);
+
+        // Core in Vulkan 1.2, but still a feature that has to be enabled.
+        let mut timeline_features = vk::PhysicalDeviceTimelineSemaphoreFeatures::default();
+        let (sampler_anisotropy, fragment_stores_and_atomics) = unsafe {
+            let mut features = vk::PhysicalDeviceFeatures2::default().push_next(&mut timeline_features);
+            arc_instance.get().get_physical_device_features2(pdevice, &mut features);
+            (
+                features.features.sampler_anisotropy == vk::TRUE,
+                features.features.fragment_stores_and_atomics == vk::TRUE,
+            )
+        };
+        let timeline_semaphore = timeline_features.timeline_semaphore == vk::TRUE;
+        let max_sampler_anisotropy = sampler_anisotropy.then(|| {
+            let limits = unsafe { arc_instance.get().get_physical_device_properties(pdevice) }.limits;
+            limits.max_sampler_anisotropy
+        });
+
         let vrs_capabilities =
             vrs::query_capabilities(arc_instance.entry(), arc_instance.get(), pdevice);
         let enable_fragment_shading_rate = vrs_capabilities.is_pipeline_ready();
@@ -203,8 +313,12 @@ impl VulkanContext {
             pdevice,
             &queue_info,
             enable_ray_tracing,
+            ray_query,
             has_memory_budget,
             enable_fragment_shading_rate,
+            timeline_semaphore,
+            sampler_anisotropy,
+            fragment_stores_and_atomics,
         )?;
         let arc_device = ArcDevice::new(device);
 
@@ -237,6 +351,20 @@ impl VulkanContext {
             log::info!("📊 VRAM budget monitoring active (VK_EXT_memory_budget)");
         }
 
+        let pipeline_cache = if pipeline_cache::pipeline_cache_enabled() {
+            let properties = unsafe { arc_instance.get().get_physical_device_properties(pdevice) };
+            let identity = CacheIdentity::from_properties(&properties);
+            let cache = PipelineCache::load_or_create(
+                arc_device.clone(),
+                &identity,
+                pipeline_cache::default_cache_path(),
+            )?;
+            Some(Arc::new(cache))
+        } else {
+            log::info!("Pipeline cache disabled");
+            None
+        };
+
         Ok(Self {
             instance: arc_instance,
             device: arc_device,
@@ -250,13 +378,20 @@ impl VulkanContext {
             transfer_queue_family_index: queue_info.transfer_index,
             debug_namer,
             has_memory_budget,
+            ray_query,
+            timeline_semaphore,
+            max_sampler_anisotropy,
+            fragment_stores_and_atomics,
             fragment_shading_rate,
             vrs_capabilities,
+            pipeline_cache,
+            gpus: Arc::new(gpus),
         })
     }
 
     fn create_instance(
         entry: &Entry,
+        with_surface: bool,
     ) -> Result<
         (
             ash::Instance,
@@ -265,14 +400,27 @@ impl VulkanContext {
         ),
         vk::Result,
     > {
+        let validation = validation::validation_enabled();
         let layer_names = [CStr::from_bytes_with_nul(b"VK_LAYER_KHRONOS_validation\0").unwrap()];
-        let layers_ptr: Vec<*const i8> = layer_names.iter().map(|r| r.as_ptr()).collect();
-
-        let extension_names = vec![
-            ash::khr::surface::NAME.as_ptr(),
-            ash::khr::win32_surface::NAME.as_ptr(),
-            ash::ext::debug_utils::NAME.as_ptr(),
-        ];
+        // Headless runs usually happen on CI machines without the SDK layers.
+        let available_layers = unsafe { entry.enumerate_instance_layer_properties()? };
+        let layers_ptr: Vec<*const i8> = layer_names
+            .iter()
+            .filter(|_| validation)
+            .filter(|name| {
+                with_surface
+                    || available_layers.iter().any(|layer| unsafe {
+                        CStr::from_ptr(layer.layer_name.as_ptr()) == **name
+                    })
+            })
+            .map(|r| r.as_ptr())
+            .collect();
+
+        let mut extension_names = vec![ash::ext::debug_utils::NAME.as_ptr()];
+        if with_surface {
+            extension_names.push(ash::khr::surface::NAME.as_ptr());
+            extension_names.push(ash::khr::win32_surface::NAME.as_ptr());
+        }
 
         let app_info = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_3);
 
@@ -282,11 +430,12 @@ impl VulkanContext {
             .enabled_extension_names(&extension_names);
 
         let instance = unsafe { entry.create_instance(&create_info, None)? };
+        let debug_utils = ash::ext::debug_utils::Instance::new(entry, &instance);
 
-        #[cfg(debug_assertions)]
-        let (debug_utils, debug_messenger) = {
-            let debug_utils = ash::ext::debug_utils::Instance::new(entry, &instance);
-
+        // Without the layer the messenger would only see loader messages.
+        let debug_messenger = if layers_ptr.is_empty() {
+            None
+        } else {
             let messenger_info = vk::DebugUtilsMessengerCreateInfoEXT::default()
                 .message_severity(
                     vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
@@ -300,21 +449,18 @@ impl VulkanContext {
                 )
                 .pfn_user_callback(Some(vulkan_debug_callback));
 
-            let messenger = unsafe {
-                debug_utils
-                    .create_debug_utils_messenger(&messenger_info, None)
-                    .expect("Failed to create debug messenger")
-            };
-
-            log::info!("🔍 Vulkan validation layers enabled");
-            (Some(debug_utils), Some(messenger))
-        };
-
-        #[cfg(not(debug_assertions))]
-        let (debug_utils, debug_messenger) = {
-            let debug_utils = ash::ext::debug_utils::Instance::new(entry, &instance);
-            (Some(debug_utils), None)
+            match unsafe { debug_utils.create_debug_utils_messenger(&messenger_info, None) } {
+                Ok(messenger) => {
+                    log::info!("🔍 Vulkan validation layers enabled");
+                    Some(messenger)
+                }
+                Err(e) => {
+                    log::warn!("Validation layer loaded but the debug messenger failed: {:?}", e);
+                    None
+                }
+            }
         };
+        let debug_utils = Some(debug_utils);
 
         Ok((instance, debug_utils, debug_messenger))
     }
@@ -366,13 +512,18 @@ impl VulkanContext {
         Ok((surface, surface_loader))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn create_device(
         instance: &ArcInstance,
         physical_device: vk::PhysicalDevice,
         queue_info: &QueueFamilyInfo,
         enable_ray_tracing: bool,
+        enable_ray_query: bool,
         has_memory_budget: bool,
         enable_fragment_shading_rate: bool,
+        enable_timeline_semaphore: bool,
+        enable_sampler_anisotropy: bool,
+        enable_fragment_stores_and_atomics: bool,
     ) -> ReactorResult<(ash::Device, vk::Queue, Option<vk::Queue>, Option<vk::Queue>)> {
         let mut device_extension_names: Vec<*const i8> = vec![
             ash::khr::swapchain::NAME.as_ptr(),
@@ -408,6 +559,10 @@ impl VulkanContext {
             );
         }
 
+        if enable_ray_query {
+            device_extension_names.push(ash::khr::ray_query::NAME.as_ptr());
+        }
+
         if enable_fragment_shading_rate {
             device_extension_names.push(ash::khr::fragment_shading_rate::NAME.as_ptr());
         }
@@ -441,9 +596,15 @@ impl VulkanContext {
             vk::PhysicalDeviceFragmentShadingRateFeaturesKHR::default()
                 .pipeline_fragment_shading_rate(true);
 
+        let enabled_features =
+            vk::PhysicalDeviceFeatures::default()
+                .sampler_anisotropy(enable_sampler_anisotropy)
+                .fragment_stores_and_atomics(enable_fragment_stores_and_atomics);
+
         let mut device_create_info = vk::DeviceCreateInfo::default()
             .queue_create_infos(&queue_create_infos)
             .enabled_extension_names(&device_extension_names)
+            .enabled_features(&enabled_features)
             .push_next(&mut dynamic_rendering_features);
 
         let mut buffer_device_address_features =
@@ -453,6 +614,8 @@ impl VulkanContext {
         let mut acceleration_structure_features =
             vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default()
                 .acceleration_structure(true);
+        let mut ray_query_features =
+            vk::PhysicalDeviceRayQueryFeaturesKHR::default().ray_query(true);
 
         if enable_ray_tracing {
             device_create_info = device_create_info
@@ -461,10 +624,20 @@ impl VulkanContext {
                 .push_next(&mut acceleration_structure_features);
         }
 
+        if enable_ray_query {
+            device_create_info = device_create_info.push_next(&mut ray_query_features);
+        }
+
         if enable_fragment_shading_rate {
             device_create_info = device_create_info.push_next(&mut fragment_shading_rate_features);
         }
 
+        let mut timeline_semaphore_features =
+            vk::PhysicalDeviceTimelineSemaphoreFeatures::default().timeline_semaphore(true);
+        if enable_timeline_semaphore {
+            device_create_info = device_create_info.push_next(&mut timeline_semaphore_features);
+        }
+
         let device = unsafe {
             instance
                 .get()
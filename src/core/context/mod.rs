@@ -5,8 +5,11 @@ pub use api::*;
 
 use crate::core::arc_handle::{ArcDevice, ArcInstance, ArcSurface};
 use crate::core::debug_utils::DebugNamer;
+use crate::core::pipeline_cache::PipelineCache;
 use crate::core::vrs::{VrsCapabilities, VrsContext};
+use crate::utils::gpu_detector::GpuSelection;
 use ash::vk;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct VulkanContext {
@@ -22,6 +25,22 @@ pub struct VulkanContext {
     pub transfer_queue_family_index: Option<u32>,
     pub debug_namer: DebugNamer,
     pub has_memory_budget: bool,
+    /// `VK_KHR_ray_query` enabled (only with ray tracing). Mesh buffers are
+    /// then created as acceleration structure build inputs.
+    pub ray_query: bool,
+    /// `timelineSemaphore` enabled: `FrameTimeline` signals a timeline
+    /// semaphore instead of deriving progress from the frame fences.
+    pub timeline_semaphore: bool,
+    /// `maxSamplerAnisotropy` when `samplerAnisotropy` is enabled; samplers
+    /// clamp their requested level to it.
+    pub max_sampler_anisotropy: Option<f32>,
+    /// `fragmentStoresAndAtomics` enabled: fragment shaders may write storage
+    /// buffers (the post pass counts AA edges with it).
+    pub fragment_stores_and_atomics: bool,
     pub fragment_shading_rate: Option<VrsContext>,
     pub vrs_capabilities: VrsCapabilities,
+    /// Shared by every pipeline; `None` when the cache is disabled.
+    pub pipeline_cache: Option<Arc<PipelineCache>>,
+    /// Devices found at creation and the one in use (`gpu_info`).
+    pub gpus: Arc<GpuSelection>,
 }
@@ -10,7 +10,21 @@
 // =============================================================================
 
 use ash::vk;
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::sync::Mutex;
+
+static OBJECT_NAME_COUNTERS: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+
+/// Next automatic label for `prefix`: `mesh_cube` → `mesh_cube_0`, `mesh_cube_1`, ...
+/// Each prefix counts on its own, for the whole process.
+pub fn next_object_name(prefix: &str) -> String {
+    let mut counters = OBJECT_NAME_COUNTERS.lock().unwrap_or_else(|e| e.into_inner());
+    let counter = counters.get_or_insert_with(HashMap::new).entry(prefix.to_string()).or_insert(0);
+    let name = format!("{prefix}_{counter}");
+    *counter += 1;
+    name
+}
 
 /// Wrapper around `ash::ext::debug_utils::Device` for naming Vulkan objects.
 ///
@@ -183,4 +197,44 @@ impl DebugNamer {
     pub fn name_queue(&self, queue: vk::Queue, name: &str) {
         self.set_name(queue, vk::ObjectType::QUEUE, name);
     }
+
+    // ─── Command buffer regions ──────────────────────────────────────────
+
+    /// Open a named region in `cmd` (a collapsible group in RenderDoc's event
+    /// browser). Must be closed with `end_label` in the same command buffer.
+    pub fn begin_label(&self, cmd: vk::CommandBuffer, name: &str, color: [f32; 4]) {
+        if let Some(ref loader) = self.loader {
+            let c_name = CString::new(name).unwrap_or_else(|_| CString::new("?").unwrap());
+            let label = vk::DebugUtilsLabelEXT::default().label_name(&c_name).color(color);
+            unsafe { loader.cmd_begin_debug_utils_label(cmd, &label) };
+        }
+    }
+
+    /// Close the region opened by the matching `begin_label`.
+    pub fn end_label(&self, cmd: vk::CommandBuffer) {
+        if let Some(ref loader) = self.loader {
+            unsafe { loader.cmd_end_debug_utils_label(cmd) };
+        }
+    }
+
+    /// Single marker at the current point of `cmd` (e.g. before a draw).
+    pub fn insert_label(&self, cmd: vk::CommandBuffer, name: &str, color: [f32; 4]) {
+        if let Some(ref loader) = self.loader {
+            let c_name = CString::new(name).unwrap_or_else(|_| CString::new("?").unwrap());
+            let label = vk::DebugUtilsLabelEXT::default().label_name(&c_name).color(color);
+            unsafe { loader.cmd_insert_debug_utils_label(cmd, &label) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn automatic_names_count_per_prefix() {
+        assert_eq!(next_object_name("test_auto_a"), "test_auto_a_0");
+        assert_eq!(next_object_name("test_auto_a"), "test_auto_a_1");
+        assert_eq!(next_object_name("test_auto_b"), "test_auto_b_0");
+    }
 }
@@ -301,44 +301,116 @@ pub type ReactorResult<T> = Result<T, ReactorError>;
 // Global Error State (for C ABI)
 // =============================================================================
 
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 
 /// Global last error for C ABI access
 static LAST_ERROR: Mutex<Option<ReactorError>> = Mutex::new(None);
 
+/// Lock the last error, recovering the slot if a thread panicked while
+/// holding it. Error reporting must keep working after a panic.
+fn last_error() -> MutexGuard<'static, Option<ReactorError>> {
+    LAST_ERROR.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 /// Set the last error (called internally when errors occur)
 pub fn set_last_error(error: ReactorError) {
     log::error!("{}", error);
-    *LAST_ERROR.lock().unwrap() = Some(error);
+    *last_error() = Some(error);
 }
 
 /// Get the last error code
 pub fn get_last_error_code() -> ErrorCode {
-    LAST_ERROR
-        .lock()
-        .unwrap()
-        .as_ref()
-        .map(|e| e.code)
-        .unwrap_or(ErrorCode::None)
+    last_error().as_ref().map(|e| e.code).unwrap_or(ErrorCode::None)
 }
 
 /// Get the last error message
 pub fn get_last_error_message() -> Option<String> {
-    LAST_ERROR
-        .lock()
-        .unwrap()
-        .as_ref()
-        .map(|e| e.message.clone())
+    last_error().as_ref().map(|e| e.message.clone())
 }
 
 /// Clear the last error
 pub fn clear_last_error() {
-    *LAST_ERROR.lock().unwrap() = None;
+    *last_error() = None;
 }
 
 /// Check if there's a pending error
 pub fn has_error() -> bool {
-    LAST_ERROR.lock().unwrap().is_some()
+    last_error().is_some()
+}
+
+/// Copy the last error message into `out` (see `copy_to_c_buffer`) and
+/// return the bytes written; 0 when there is no error.
+///
+/// The copy happens under the error lock, so unlike handing out a pointer
+/// into the global state, an error raised concurrently on another thread can
+/// never invalidate what the caller holds.
+pub fn last_error_message_into(out: &mut [u8]) -> usize {
+    match last_error().as_ref() {
+        Some(error) => copy_to_c_buffer(&error.message, out),
+        None => copy_to_c_buffer("", out),
+    }
+}
+
+/// Length in bytes of the last error message, without the terminator.
+/// A caller can allocate `len + 1` bytes to receive it whole.
+pub fn last_error_message_len() -> usize {
+    last_error().as_ref().map_or(0, |e| e.message.len())
+}
+
+/// Copy `text` into a caller-owned buffer as a NUL-terminated UTF-8 string.
+///
+/// Truncation rules:
+/// - At most `out.len() - 1` bytes of text are written, then a NUL.
+/// - Text is cut at a character boundary, so the result is always valid
+///   UTF-8; a multi-byte character that does not fit is dropped whole.
+/// - Interior NULs in `text` end the string early, as C would read it.
+/// - An empty `out` receives nothing.
+///
+/// Returns the bytes of text written (excluding the NUL). The result was
+/// truncated when this is less than `text.len()`.
+pub fn copy_to_c_buffer(text: &str, out: &mut [u8]) -> usize {
+    let Some(capacity) = out.len().checked_sub(1) else {
+        return 0;
+    };
+    let text = text.split('\0').next().unwrap_or("");
+    let mut len = text.len().min(capacity);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out[len] = 0;
+    len
+}
+
+// =============================================================================
+// Panic containment
+// =============================================================================
+
+/// Text of a panic payload: the `&str` or `String` passed to `panic!`, or a
+/// placeholder for other payload types.
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(text) = payload.downcast_ref::<&str>() {
+        text
+    } else if let Some(text) = payload.downcast_ref::<String>() {
+        text
+    } else {
+        "non-string panic payload"
+    }
+}
+
+/// Run `f`, turning a panic into an `ErrorCode::Unknown` error that is also
+/// stored as the last error. Use it wherever a panic must not unwind further:
+/// callbacks invoked by Vulkan or the windowing system, where unwinding
+/// across the foreign frames is undefined behavior.
+///
+/// `what` names the call in the error message. The closure is treated as
+/// unwind safe; state it touched may be half-updated after a panic.
+pub fn catch_panic<T>(what: &str, f: impl FnOnce() -> ReactorResult<T>) -> ReactorResult<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = format!("panic in {}: {}", what, panic_message(payload.as_ref()));
+        set_last_error(ReactorError::new(ErrorCode::Unknown, message.clone()));
+        Err(ReactorError::new(ErrorCode::Unknown, message))
+    })
 }
 
 // =============================================================================
@@ -483,6 +555,32 @@ mod tests {
         assert!(display.contains("Invalid shader"));
     }
 
+    #[test]
+    fn c_buffer_copy_truncates_at_char_boundaries() {
+        let mut out = [0xffu8; 8];
+        assert_eq!(copy_to_c_buffer("abc", &mut out), 3);
+        assert_eq!(&out[..4], b"abc\0");
+
+        // "ñ" is two bytes: it does not fit after "abcdef" in 7 bytes of text.
+        assert_eq!(copy_to_c_buffer("abcdefñ", &mut out), 6);
+        assert_eq!(&out[..7], b"abcdef\0");
+
+        assert_eq!(copy_to_c_buffer("a\0b", &mut out), 1);
+        assert_eq!(copy_to_c_buffer("abc", &mut out[..1]), 0);
+        assert_eq!(out[0], 0);
+        assert_eq!(copy_to_c_buffer("abc", &mut []), 0);
+    }
+
+    #[test]
+    fn panic_payloads_become_text() {
+        let owned = std::panic::catch_unwind(|| panic!("bad state {}", 7)).unwrap_err();
+        assert_eq!(panic_message(owned.as_ref()), "bad state 7");
+        let literal = std::panic::catch_unwind(|| panic!("no format")).unwrap_err();
+        assert_eq!(panic_message(literal.as_ref()), "no format");
+        let other = std::panic::catch_unwind(|| std::panic::panic_any(42u32)).unwrap_err();
+        assert_eq!(panic_message(other.as_ref()), "non-string panic payload");
+    }
+
     #[test]
     fn test_global_error_state() {
         clear_last_error();
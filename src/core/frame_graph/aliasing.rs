@@ -0,0 +1,208 @@
+//! Transient memory aliasing plan
+//!
+//! `FrameGraph::compile` works out which graph-owned images can share
+//! memory: two images interfere when the pass ranges that touch them
+//! overlap, when one of them is persistent, or when one is a depth target
+//! and the other a color target. Images are placed largest first into the
+//! first block none of whose members they interfere with, so each block is
+//! sized by its biggest member. Sizes here are estimates (width × height ×
+//! texel size); `execute` sizes the real `VkDeviceMemory` from the driver's
+//! requirements but keeps the same grouping.
+
+use std::collections::HashMap;
+
+use crate::core::frame_graph::graph::FrameGraph;
+use crate::core::frame_graph::types::*;
+
+/// First and last position in `order` of every resource the passes touch.
+pub(super) fn resource_lifetimes(
+    order: &[PassId],
+    passes: &HashMap<PassId, PassDesc>,
+) -> HashMap<ResourceId, (usize, usize)> {
+    let mut lifetimes: HashMap<ResourceId, (usize, usize)> = HashMap::new();
+    for (index, pass_id) in order.iter().enumerate() {
+        let Some(pass) = passes.get(pass_id) else { continue };
+        for &resource in pass.reads.iter().chain(&pass.writes) {
+            lifetimes
+                .entry(resource)
+                .and_modify(|(_, last)| *last = index)
+                .or_insert((index, index));
+        }
+    }
+    lifetimes
+}
+
+/// An image the graph will allocate.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct AliasCandidate {
+    pub id: ResourceId,
+    pub first: usize,
+    pub last: usize,
+    pub size: u64,
+    pub depth: bool,
+}
+
+impl AliasCandidate {
+    fn interferes(&self, other: &AliasCandidate) -> bool {
+        self.depth != other.depth || (self.first <= other.last && other.first <= self.last)
+    }
+}
+
+/// Images sharing one memory allocation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AliasBlock {
+    pub size: u64,
+    pub resources: Vec<ResourceId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct AliasPlan {
+    pub blocks: Vec<AliasBlock>,
+    pub block_of: HashMap<ResourceId, usize>,
+}
+
+impl AliasPlan {
+    /// Bytes the plan avoids allocating compared to one block per image.
+    pub fn bytes_saved(&self, candidates: &[AliasCandidate]) -> u64 {
+        let requested: u64 = candidates.iter().map(|c| c.size).sum();
+        let allocated: u64 = self.blocks.iter().map(|b| b.size).sum();
+        requested.saturating_sub(allocated)
+    }
+}
+
+/// Greedy colouring of the interference graph, largest images first.
+pub(super) fn plan_aliasing(candidates: &[AliasCandidate]) -> AliasPlan {
+    let mut order: Vec<&AliasCandidate> = candidates.iter().collect();
+    order.sort_by(|a, b| b.size.cmp(&a.size).then(a.id.0.cmp(&b.id.0)));
+
+    let by_id: HashMap<ResourceId, &AliasCandidate> = candidates.iter().map(|c| (c.id, c)).collect();
+    let mut plan = AliasPlan::default();
+    for candidate in order {
+        let block = plan.blocks.iter().position(|block| {
+            block.resources.iter().all(|id| !by_id[id].interferes(candidate))
+        });
+        let index = match block {
+            Some(index) => index,
+            None => {
+                plan.blocks.push(AliasBlock::default());
+                plan.blocks.len() - 1
+            }
+        };
+        let block = &mut plan.blocks[index];
+        block.size = block.size.max(candidate.size);
+        block.resources.push(candidate.id);
+        plan.block_of.insert(candidate.id, index);
+    }
+    plan
+}
+
+impl FrameGraph {
+    /// Builds `alias_plan` for the compiled order and fills the memory stats.
+    pub(super) fn plan_transient_memory(&mut self) {
+        let lifetimes = resource_lifetimes(self.execution_order(), &self.passes);
+        let mut candidates: Vec<AliasCandidate> = lifetimes
+            .iter()
+            .filter_map(|(&id, &(first, last))| {
+                let desc = self.resources.get(&id)?;
+                if matches!(desc.resource_type, ResourceType::Swapchain | ResourceType::Buffer) {
+                    return None;
+                }
+                // Persistent images keep their contents, so they never share memory.
+                let (first, last) = if desc.persistent { (0, usize::MAX) } else { (first, last) };
+                Some(AliasCandidate {
+                    id,
+                    first,
+                    last,
+                    size: desc.width as u64 * desc.height as u64 * desc.format.bytes_per_pixel(),
+                    depth: desc.is_depth(),
+                })
+            })
+            .collect();
+        candidates.sort_by_key(|c| c.id.0);
+
+        let plan = plan_aliasing(&candidates);
+        self.stats.aliased_bytes_saved = plan.bytes_saved(&candidates);
+        self.stats.allocation_count = plan.blocks.len() as u32;
+        self.alias_plan = plan;
+    }
+
+    /// Memory block `resource` was assigned by the last `compile`.
+    pub fn alias_block(&self, resource: ResourceId) -> Option<usize> {
+        self.alias_plan.block_of.get(&resource).copied()
+    }
+
+    pub fn alias_blocks(&self) -> &[AliasBlock] {
+        &self.alias_plan.blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::frame_graph::{create_deferred_graph, create_forward_graph};
+
+    fn candidate(id: u32, first: usize, last: usize, size: u64) -> AliasCandidate {
+        AliasCandidate { id: ResourceId(id), first, last, size, depth: false }
+    }
+
+    #[test]
+    fn disjoint_lifetimes_share_memory() {
+        // A lives in passes 0..=1, B in 1..=2 (overlaps A), C in 2..=3 (after A).
+        let candidates = [candidate(0, 0, 1, 1000), candidate(1, 1, 2, 512), candidate(2, 2, 3, 4096)];
+        let plan = plan_aliasing(&candidates);
+        assert_eq!(plan.blocks.len(), 2);
+        assert_eq!(plan.block_of[&ResourceId(0)], plan.block_of[&ResourceId(2)]);
+        assert_ne!(plan.block_of[&ResourceId(0)], plan.block_of[&ResourceId(1)]);
+        // The shared block fits the bigger image.
+        assert_eq!(plan.blocks[plan.block_of[&ResourceId(2)]].size, 4096);
+        assert_eq!(plan.bytes_saved(&candidates), 1000);
+    }
+
+    #[test]
+    fn depth_and_color_do_not_alias() {
+        let mut candidates = [candidate(0, 0, 0, 256), candidate(1, 1, 1, 256)];
+        candidates[1].depth = true;
+        let plan = plan_aliasing(&candidates);
+        assert_eq!(plan.blocks.len(), 2);
+    }
+
+    #[test]
+    fn sequential_fullscreen_targets_share_a_block() {
+        let mut graph = FrameGraph::new();
+        let a = graph.create_resource("A", ResourceType::RenderTarget, 1920, 1080, ResourceFormat::RGBA16F);
+        let b = graph.create_resource("B", ResourceType::RenderTarget, 1920, 1080, ResourceFormat::RGBA16F);
+        let c = graph.create_resource("C", ResourceType::RenderTarget, 1920, 1080, ResourceFormat::RGBA16F);
+        graph.pass("First").write(a).order(0).build();
+        graph.pass("Second").read(a).write(b).order(1).build();
+        graph.pass("Third").read(b).write(c).order(2).build();
+        graph.compile();
+
+        assert_eq!(graph.alias_block(a), graph.alias_block(c));
+        assert_ne!(graph.alias_block(a), graph.alias_block(b));
+        assert_eq!(graph.stats.allocation_count, 2);
+        assert_eq!(graph.stats.aliased_bytes_saved, 1920 * 1080 * 8);
+    }
+
+    #[test]
+    fn deferred_graph_reuses_gbuffer_memory_for_post_processing() {
+        let graph = create_deferred_graph(3840, 2160);
+        let normal = graph.resource_by_name("GBuffer1_Normal_Material").unwrap();
+        let post = graph.resource_by_name("PostProcess").unwrap();
+        assert_eq!(graph.alias_block(normal), graph.alias_block(post));
+        assert!(graph.stats.aliased_bytes_saved >= 3840 * 2160 * 8);
+        // Swapchain images are imported, never planned.
+        let final_output = graph.resource_by_name("Final").unwrap();
+        assert_eq!(graph.alias_block(final_output), None);
+    }
+
+    #[test]
+    fn forward_graph_lifetimes_cover_its_pass() {
+        let mut graph = create_forward_graph(64, 64);
+        graph.compile();
+        let lifetimes = resource_lifetimes(graph.execution_order(), &graph.passes);
+        let depth = graph.resource_by_name("Depth").unwrap();
+        assert_eq!(lifetimes[&depth], (0, 0));
+        assert!(graph.pass_by_name("Forward").is_some());
+        assert_eq!(graph.stats.allocation_count, 1);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::frame_graph::types::*;
 use crate::core::frame_graph::graph::FrameGraph;
+use crate::core::frame_graph::execute::{PassContext, PassExecutor};
 
 pub struct PassBuilder<'a> {
     graph: &'a mut FrameGraph,
@@ -8,11 +9,12 @@ pub struct PassBuilder<'a> {
     reads: Vec<ResourceId>,
     writes: Vec<ResourceId>,
     order: i32,
+    executor: Option<PassExecutor>,
 }
 
 impl<'a> PassBuilder<'a> {
     pub fn new(graph: &'a mut FrameGraph, pass_id: PassId, name: String) -> Self {
-        Self { graph, pass_id, name, reads: Vec::new(), writes: Vec::new(), order: 0 }
+        Self { graph, pass_id, name, reads: Vec::new(), writes: Vec::new(), order: 0, executor: None }
     }
 
     pub fn reads(mut self, resources: &[ResourceId]) -> Self {
@@ -40,6 +42,12 @@ impl<'a> PassBuilder<'a> {
         self
     }
 
+    /// Callback that records the pass when the graph is executed.
+    pub fn execute(mut self, f: impl Fn(&mut PassContext) + Send + Sync + 'static) -> Self {
+        self.executor = Some(PassExecutor::new(f));
+        self
+    }
+
     pub fn build(self) -> PassId {
         let pass = PassDesc {
             id: self.pass_id,
@@ -50,6 +58,9 @@ impl<'a> PassBuilder<'a> {
             order: self.order,
         };
         self.graph.passes.insert(self.pass_id, pass);
+        if let Some(executor) = self.executor {
+            self.graph.executors.insert(self.pass_id, executor);
+        }
         self.pass_id
     }
 }
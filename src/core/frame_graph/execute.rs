@@ -0,0 +1,461 @@
+//! FrameGraph execution
+//!
+//! `FrameGraph::execute` walks the compiled pass order, transitions every
+//! image a pass touches into the layout that access needs and then calls the
+//! pass's executor with a `PassContext`. Swapchain images and buffers are
+//! owned elsewhere and must be imported each frame; every other image the
+//! enabled passes use is created by the graph, sharing `VkDeviceMemory`
+//! as decided by the alias plan `compile` builds (see `aliasing.rs`).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::core::context::VulkanContext;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::frame_graph::graph::FrameGraph;
+use crate::core::frame_graph::types::*;
+
+/// Callback that records a pass's commands.
+#[derive(Clone)]
+pub struct PassExecutor(pub(super) Arc<dyn Fn(&mut PassContext) + Send + Sync>);
+
+impl PassExecutor {
+    pub fn new(f: impl Fn(&mut PassContext) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for PassExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PassExecutor")
+    }
+}
+
+/// Vulkan handles backing a graph resource.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResolvedResource {
+    pub image: vk::Image,
+    pub view: vk::ImageView,
+    pub buffer: vk::Buffer,
+    /// Layout the image is in outside of the pass that currently uses it.
+    pub layout: vk::ImageLayout,
+}
+
+/// What a pass executor sees: the command buffer being recorded and the
+/// handles of the graph's resources, already in the layout the pass needs.
+pub struct PassContext<'a> {
+    pub device: &'a ash::Device,
+    pub command_buffer: vk::CommandBuffer,
+    pub pass: PassId,
+    descs: &'a HashMap<ResourceId, ResourceDesc>,
+    resolved: &'a HashMap<ResourceId, ResolvedResource>,
+}
+
+impl PassContext<'_> {
+    pub fn image(&self, id: ResourceId) -> Option<vk::Image> {
+        self.resolved.get(&id).map(|r| r.image).filter(|i| *i != vk::Image::null())
+    }
+
+    pub fn view(&self, id: ResourceId) -> Option<vk::ImageView> {
+        self.resolved.get(&id).map(|r| r.view).filter(|v| *v != vk::ImageView::null())
+    }
+
+    pub fn buffer(&self, id: ResourceId) -> Option<vk::Buffer> {
+        self.resolved.get(&id).map(|r| r.buffer).filter(|b| *b != vk::Buffer::null())
+    }
+
+    /// Layout the resource is in for this pass.
+    pub fn layout(&self, id: ResourceId) -> vk::ImageLayout {
+        self.resolved.get(&id).map(|r| r.layout).unwrap_or(vk::ImageLayout::UNDEFINED)
+    }
+
+    pub fn extent(&self, id: ResourceId) -> vk::Extent2D {
+        self.descs
+            .get(&id)
+            .map(|d| vk::Extent2D { width: d.width, height: d.height })
+            .unwrap_or_default()
+    }
+
+    pub fn format(&self, id: ResourceId) -> vk::Format {
+        self.descs.get(&id).map(|d| vk_format(d.format)).unwrap_or(vk::Format::UNDEFINED)
+    }
+}
+
+/// Images and memory the graph created itself. Cloning a graph does not
+/// clone these: the copy allocates its own on its first `execute`.
+#[derive(Default)]
+pub(super) struct TransientImages {
+    pub(super) resources: HashMap<ResourceId, ResolvedResource>,
+    memories: Vec<vk::DeviceMemory>,
+    generation: u64,
+}
+
+impl Clone for TransientImages {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for TransientImages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransientImages")
+            .field("images", &self.resources.len())
+            .field("memories", &self.memories.len())
+            .finish()
+    }
+}
+
+impl TransientImages {
+    fn destroy(&mut self, device: &ash::Device) {
+        unsafe {
+            for resource in self.resources.values() {
+                device.destroy_image_view(resource.view, None);
+                device.destroy_image(resource.image, None);
+            }
+            for &memory in &self.memories {
+                device.free_memory(memory, None);
+            }
+        }
+        self.resources.clear();
+        self.memories.clear();
+    }
+}
+
+pub fn vk_format(format: ResourceFormat) -> vk::Format {
+    match format {
+        ResourceFormat::RGBA8 => vk::Format::R8G8B8A8_UNORM,
+        ResourceFormat::RGBA16F => vk::Format::R16G16B16A16_SFLOAT,
+        ResourceFormat::RGBA32F => vk::Format::R32G32B32A32_SFLOAT,
+        ResourceFormat::R8 => vk::Format::R8_UNORM,
+        ResourceFormat::R16F => vk::Format::R16_SFLOAT,
+        ResourceFormat::R32F => vk::Format::R32_SFLOAT,
+        ResourceFormat::Depth32F => vk::Format::D32_SFLOAT,
+        ResourceFormat::Depth24Stencil8 => vk::Format::D24_UNORM_S8_UINT,
+    }
+}
+
+/// Layout an image needs for `access` in a pass.
+pub(super) fn layout_for(desc: &ResourceDesc, access: AccessType) -> vk::ImageLayout {
+    match (access, desc.is_depth()) {
+        (AccessType::ReadWrite, _) => vk::ImageLayout::GENERAL,
+        (AccessType::Write, true) => vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        (AccessType::Write, false) => vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        (AccessType::Read, true) => vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
+        (AccessType::Read, false) => vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+    }
+}
+
+fn access_mask(layout: vk::ImageLayout) -> vk::AccessFlags {
+    match layout {
+        vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL => {
+            vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+        }
+        vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL => {
+            vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE
+        }
+        vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL | vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL => {
+            vk::AccessFlags::SHADER_READ
+        }
+        vk::ImageLayout::GENERAL => vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE,
+        _ => vk::AccessFlags::empty(),
+    }
+}
+
+impl FrameGraph {
+    /// Registers the callback that records `pass`.
+    pub fn set_pass_executor(&mut self, pass: PassId, f: impl Fn(&mut PassContext) + Send + Sync + 'static) {
+        self.executors.insert(pass, PassExecutor::new(f));
+    }
+
+    /// Binds an externally owned image (e.g. this frame's swapchain image)
+    /// to `resource`. `layout` is the layout it is in right now.
+    pub fn import_image(&mut self, resource: ResourceId, image: vk::Image, view: vk::ImageView, layout: vk::ImageLayout) {
+        self.imported.insert(resource, ResolvedResource { image, view, buffer: vk::Buffer::null(), layout });
+    }
+
+    /// Binds an externally owned buffer to `resource`. The graph never
+    /// allocates buffers.
+    pub fn import_buffer(&mut self, resource: ResourceId, buffer: vk::Buffer) {
+        self.imported.insert(resource, ResolvedResource { buffer, ..Default::default() });
+    }
+
+    /// Layout an imported image was left in by the last `execute`.
+    pub fn imported_layout(&self, resource: ResourceId) -> Option<vk::ImageLayout> {
+        self.imported.get(&resource).map(|r| r.layout)
+    }
+
+    /// Records every enabled pass into `command_buffer`, in compiled order.
+    /// Fails without recording anything while validation reports errors.
+    ///
+    /// Images the graph owns are (re)created when the graph was recompiled;
+    /// the device must be idle when that happens after a previous execute.
+    /// Contents of transient images do not survive between frames.
+    pub fn execute(&mut self, ctx: &VulkanContext, command_buffer: vk::CommandBuffer) -> ReactorResult<()> {
+        self.compile_validated().into_result()?;
+        self.allocate_images(ctx)?;
+
+        let mut resolved: HashMap<ResourceId, ResolvedResource> = self.transients.resources.clone();
+        for resource in resolved.values_mut() {
+            resource.layout = vk::ImageLayout::UNDEFINED;
+        }
+        resolved.extend(self.imported.iter().map(|(&id, &r)| (id, r)));
+        let mut written: HashMap<ResourceId, bool> = HashMap::new();
+        let device = ctx.ash_device();
+
+        for &pass_id in self.execution_order() {
+            let Some(pass) = self.passes.get(&pass_id) else { continue };
+
+            let mut image_barriers = Vec::new();
+            let mut buffer_hazard = false;
+            let accesses = pass.reads.iter().map(|&r| (r, pass.writes.contains(&r)))
+                .map(|(r, rw)| (r, if rw { AccessType::ReadWrite } else { AccessType::Read }))
+                .chain(pass.writes.iter().filter(|r| !pass.reads.contains(r)).map(|&r| (r, AccessType::Write)));
+            for (resource, access) in accesses {
+                let (Some(desc), Some(state)) = (self.resources.get(&resource), resolved.get_mut(&resource)) else {
+                    continue;
+                };
+                let after_write = written.insert(resource, access != AccessType::Read).unwrap_or(false);
+                if state.image == vk::Image::null() {
+                    buffer_hazard |= after_write;
+                    continue;
+                }
+                let new_layout = layout_for(desc, access);
+                if new_layout == state.layout && !after_write {
+                    continue;
+                }
+                let aspect = if desc.is_depth() { vk::ImageAspectFlags::DEPTH } else { vk::ImageAspectFlags::COLOR };
+                image_barriers.push(
+                    vk::ImageMemoryBarrier::default()
+                        .old_layout(state.layout)
+                        .new_layout(new_layout)
+                        .src_access_mask(access_mask(state.layout))
+                        .dst_access_mask(access_mask(new_layout))
+                        .image(state.image)
+                        .subresource_range(vk::ImageSubresourceRange {
+                            aspect_mask: aspect, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+                        }),
+                );
+                state.layout = new_layout;
+            }
+
+            // Buffers have no layout: a global barrier orders them after the last write.
+            let memory_barriers: Vec<vk::MemoryBarrier> = if buffer_hazard {
+                vec![vk::MemoryBarrier::default()
+                    .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+                    .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)]
+            } else {
+                Vec::new()
+            };
+            if !image_barriers.is_empty() || !memory_barriers.is_empty() {
+                unsafe {
+                    device.cmd_pipeline_barrier(command_buffer,
+                        vk::PipelineStageFlags::ALL_COMMANDS, vk::PipelineStageFlags::ALL_COMMANDS,
+                        vk::DependencyFlags::empty(), &memory_barriers, &[], &image_barriers);
+                }
+            }
+
+            if let Some(executor) = self.executors.get(&pass_id) {
+                let mut pass_ctx = PassContext {
+                    device,
+                    command_buffer,
+                    pass: pass_id,
+                    descs: &self.resources,
+                    resolved: &resolved,
+                };
+                (executor.0)(&mut pass_ctx);
+            }
+        }
+
+        // Swapchain images end ready to present.
+        let mut present_barriers = Vec::new();
+        for (id, state) in resolved.iter_mut() {
+            let is_swapchain = self.resources.get(id).is_some_and(|d| d.resource_type == ResourceType::Swapchain);
+            if is_swapchain && written.contains_key(id) && state.image != vk::Image::null() {
+                present_barriers.push(
+                    vk::ImageMemoryBarrier::default()
+                        .old_layout(state.layout)
+                        .new_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+                        .src_access_mask(access_mask(state.layout))
+                        .image(state.image)
+                        .subresource_range(vk::ImageSubresourceRange {
+                            aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+                        }),
+                );
+                state.layout = vk::ImageLayout::PRESENT_SRC_KHR;
+            }
+        }
+        if !present_barriers.is_empty() {
+            unsafe {
+                device.cmd_pipeline_barrier(command_buffer,
+                    vk::PipelineStageFlags::ALL_COMMANDS, vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                    vk::DependencyFlags::empty(), &[], &[], &present_barriers);
+            }
+        }
+
+        for (id, imported) in self.imported.iter_mut() {
+            if let Some(state) = resolved.get(id) {
+                imported.layout = state.layout;
+            }
+        }
+        Ok(())
+    }
+
+    /// Frees the images and memory the graph created. Call once the GPU is
+    /// done with every frame that executed this graph.
+    pub fn destroy_transients(&mut self, ctx: &VulkanContext) {
+        self.transients.destroy(ctx.ash_device());
+    }
+
+    /// Creates the graph's images and binds them following `alias_plan`: one
+    /// allocation per plan block, sized by its largest member. A member whose
+    /// memory types don't overlap the block's gets an allocation of its own.
+    fn allocate_images(&mut self, ctx: &VulkanContext) -> ReactorResult<()> {
+        if self.transients.generation == self.generation && !self.transients.memories.is_empty() {
+            return Ok(());
+        }
+        let device = ctx.ash_device();
+        self.transients.destroy(device);
+
+        let mut images: Vec<(ResourceId, vk::Image, vk::MemoryRequirements)> = Vec::new();
+        let destroy_images = |images: &[(ResourceId, vk::Image, vk::MemoryRequirements)]| unsafe {
+            for (_, image, _) in images {
+                device.destroy_image(*image, None);
+            }
+        };
+        for block in &self.alias_plan.blocks {
+            for &id in &block.resources {
+                let desc = &self.resources[&id];
+                if self.imported.contains_key(&id) {
+                    continue;
+                }
+                let usage = if desc.is_depth() {
+                    vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED
+                } else {
+                    vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC
+                };
+                let image_info = vk::ImageCreateInfo::default()
+                    .image_type(vk::ImageType::TYPE_2D)
+                    .format(vk_format(desc.format))
+                    .extent(vk::Extent3D { width: desc.width.max(1), height: desc.height.max(1), depth: 1 })
+                    .mip_levels(1)
+                    .array_layers(1)
+                    .samples(vk::SampleCountFlags::TYPE_1)
+                    .tiling(vk::ImageTiling::OPTIMAL)
+                    .usage(usage)
+                    .sharing_mode(vk::SharingMode::EXCLUSIVE)
+                    .initial_layout(vk::ImageLayout::UNDEFINED);
+                let image = match unsafe { device.create_image(&image_info, None) } {
+                    Ok(image) => image,
+                    Err(e) => {
+                        destroy_images(&images);
+                        return Err(ReactorError::with_source(ErrorCode::VulkanImageCreation,
+                            format!("Failed to create FrameGraph image '{}'", desc.name), e));
+                    }
+                };
+                let requirements = unsafe { device.get_image_memory_requirements(image) };
+                images.push((id, image, requirements));
+            }
+        }
+        let unplanned = self.execution_order().iter()
+            .filter_map(|pass| self.passes.get(pass))
+            .flat_map(|pass| pass.reads.iter().chain(&pass.writes))
+            .find(|id| !self.imported.contains_key(id) && !self.alias_plan.block_of.contains_key(id));
+        if let Some(desc) = unplanned.and_then(|id| self.resources.get(id)) {
+            destroy_images(&images);
+            return Err(ReactorError::new(
+                ErrorCode::NotInitialized,
+                format!("FrameGraph resource '{}' must be imported before execute", desc.name),
+            ));
+        }
+
+        // (plan block, size, memory type bits) of every allocation.
+        let mut allocations: Vec<(usize, u64, u32)> = Vec::new();
+        let mut allocation_of = Vec::with_capacity(images.len());
+        for (id, _, requirements) in &images {
+            let block = self.alias_plan.block_of[id];
+            let alignment = requirements.alignment.max(1);
+            let size = (requirements.size + alignment - 1) / alignment * alignment;
+            let shared = allocations.iter().position(|&(b, _, bits)| b == block && bits & requirements.memory_type_bits != 0);
+            match shared {
+                Some(index) => {
+                    let allocation = &mut allocations[index];
+                    allocation.1 = allocation.1.max(size);
+                    allocation.2 &= requirements.memory_type_bits;
+                    allocation_of.push(index);
+                }
+                None => {
+                    allocation_of.push(allocations.len());
+                    allocations.push((block, size, requirements.memory_type_bits));
+                }
+            }
+        }
+
+        let memory_props = unsafe { ctx.ash_instance().get_physical_device_memory_properties(ctx.physical_device) };
+        let mut memories = Vec::with_capacity(allocations.len());
+        for &(_, size, memory_type_bits) in &allocations {
+            let memory_type_index = (0..memory_props.memory_type_count).find(|&i| {
+                memory_type_bits & (1 << i) != 0
+                    && memory_props.memory_types[i as usize].property_flags.contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            });
+            let memory = memory_type_index
+                .ok_or_else(|| ReactorError::new(ErrorCode::VulkanMemoryAllocation, "No device-local memory type for FrameGraph images"))
+                .and_then(|index| {
+                    let alloc_info = vk::MemoryAllocateInfo::default().allocation_size(size).memory_type_index(index);
+                    unsafe { device.allocate_memory(&alloc_info, None) }.map_err(|e| {
+                        ReactorError::with_source(ErrorCode::VulkanMemoryAllocation, "Failed to allocate FrameGraph memory", e)
+                    })
+                });
+            match memory {
+                Ok(memory) => memories.push(memory),
+                Err(e) => {
+                    destroy_images(&images);
+                    unsafe {
+                        for memory in memories {
+                            device.free_memory(memory, None);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        self.transients.memories = memories;
+
+        for (index, &(id, image, _)) in images.iter().enumerate() {
+            let desc = &self.resources[&id];
+            let aspect = if desc.is_depth() { vk::ImageAspectFlags::DEPTH } else { vk::ImageAspectFlags::COLOR };
+            let view_info = vk::ImageViewCreateInfo::default()
+                .image(image)
+                .view_type(vk::ImageViewType::TYPE_2D)
+                .format(vk_format(desc.format))
+                .subresource_range(vk::ImageSubresourceRange {
+                    aspect_mask: aspect, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+                });
+            let view = unsafe {
+                device.bind_image_memory(image, self.transients.memories[allocation_of[index]], 0)
+                    .and_then(|_| device.create_image_view(&view_info, None))
+            };
+            let view = match view {
+                Ok(view) => view,
+                Err(e) => {
+                    destroy_images(&images[index..]);
+                    self.transients.destroy(device);
+                    return Err(ReactorError::with_source(ErrorCode::VulkanImageCreation,
+                        format!("Failed to bind FrameGraph image '{}'", desc.name), e));
+                }
+            };
+            self.transients.resources.insert(id, ResolvedResource { image, view, ..Default::default() });
+        }
+
+        self.transients.generation = self.generation;
+        log::debug!(
+            "FrameGraph: {} images in {} memory blocks ({} planned)",
+            self.transients.resources.len(),
+            self.transients.memories.len(),
+            self.alias_plan.blocks.len()
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::frame_graph::types::*;
+use crate::core::frame_graph::aliasing::AliasPlan;
 use crate::core::frame_graph::builder::PassBuilder;
+use crate::core::frame_graph::execute::{PassExecutor, ResolvedResource, TransientImages};
+use crate::core::frame_graph::validation::ValidationReport;
 
 #[derive(Clone, Debug, Default)]
 pub struct FrameGraph {
@@ -11,6 +15,13 @@ pub struct FrameGraph {
     next_resource_id: u32,
     next_pass_id: u32,
     compiled: bool,
+    /// Bumped by every `compile` that changes the alias plan; images created
+    /// for an older generation are recreated on the next `execute`.
+    pub(super) generation: u64,
+    pub(super) executors: HashMap<PassId, PassExecutor>,
+    pub(super) imported: HashMap<ResourceId, ResolvedResource>,
+    pub(super) transients: TransientImages,
+    pub(super) alias_plan: AliasPlan,
     pub stats: FrameGraphStats,
 }
 
@@ -68,18 +79,100 @@ impl FrameGraph {
         PassBuilder::new(self, id, name.to_string())
     }
 
+    /// Toggling a pass recompiles the order and barriers on the next
+    /// `compile`; setting the state it already has is free.
     pub fn set_pass_enabled(&mut self, pass: PassId, enabled: bool) {
         if let Some(p) = self.passes.get_mut(&pass) {
-            p.enabled = enabled;
-            self.compiled = false;
+            if p.enabled != enabled {
+                p.enabled = enabled;
+                self.compiled = false;
+            }
         }
     }
 
+    pub fn set_pass_enabled_by_name(&mut self, name: &str, enabled: bool) -> ReactorResult<PassId> {
+        let pass = self.pass_by_name(name).ok_or_else(|| {
+            ReactorError::new(ErrorCode::InvalidParameter, format!("FrameGraph has no pass named '{}'", name))
+        })?;
+        self.set_pass_enabled(pass, enabled);
+        Ok(pass)
+    }
+
+    pub fn is_pass_enabled(&self, pass: PassId) -> bool {
+        self.passes.get(&pass).is_some_and(|p| p.enabled)
+    }
+
+    /// Disables `pass` and, transitively, every enabled pass that reads a
+    /// resource only disabled passes write. Returns the passes it disabled,
+    /// `pass` first.
+    pub fn disable_pass_cascading(&mut self, pass: PassId) -> Vec<PassId> {
+        if !self.is_pass_enabled(pass) {
+            return Vec::new();
+        }
+        self.set_pass_enabled(pass, false);
+        let mut disabled = vec![pass];
+        loop {
+            let mut starved: Vec<PassId> = self.broken_edges().into_iter().map(|(reader, _, _)| reader).collect();
+            if starved.is_empty() {
+                return disabled;
+            }
+            starved.sort_by_key(|id| id.0);
+            starved.dedup();
+            for id in starved {
+                self.set_pass_enabled(id, false);
+                disabled.push(id);
+            }
+        }
+    }
+
+    /// `(reader, resource, writers)` for every enabled pass that reads a
+    /// resource whose writers are all disabled.
+    pub(super) fn broken_edges(&self) -> Vec<(PassId, ResourceId, Vec<PassId>)> {
+        let mut edges = Vec::new();
+        let mut readers: Vec<&PassDesc> = self.passes.values().filter(|p| p.enabled).collect();
+        readers.sort_by_key(|p| p.id.0);
+        for reader in readers {
+            for &resource in &reader.reads {
+                let mut writers: Vec<&PassDesc> = self
+                    .passes
+                    .values()
+                    .filter(|p| p.id != reader.id && p.writes.contains(&resource))
+                    .collect();
+                if writers.is_empty() || writers.iter().any(|p| p.enabled) {
+                    continue;
+                }
+                writers.sort_by_key(|p| p.id.0);
+                edges.push((reader.id, resource, writers.iter().map(|p| p.id).collect()));
+            }
+        }
+        edges
+    }
+
     pub fn compile(&mut self) {
+        if !self.compiled {
+            self.compile_validated();
+        }
+    }
+
+    /// Validates the graph and, if it changed since the last compile and has
+    /// no cycles, rebuilds the order, barriers and alias plan. Issues are
+    /// logged the first time they are found; the report is returned either way.
+    pub fn compile_validated(&mut self) -> ValidationReport {
+        let report = self.validation_report();
         if self.compiled {
-            return;
+            return report;
+        }
+        report.log();
+        if report.has_cycle() {
+            self.barriers.clear();
+            self.execution_order.clear();
+            return report;
         }
+        self.build();
+        report
+    }
 
+    fn build(&mut self) {
         self.barriers.clear();
         self.execution_order.clear();
 
@@ -117,7 +210,13 @@ impl FrameGraph {
         self.stats.transient_resources =
             self.resources.values().filter(|r| !r.persistent).count() as u32;
         self.stats.barriers_generated = self.barriers.len() as u32;
+        let previous_plan = std::mem::take(&mut self.alias_plan);
+        self.plan_transient_memory();
 
+        // Toggling a pass that leaves the memory layout alone keeps the images.
+        if self.alias_plan != previous_plan || self.generation == 0 {
+            self.generation += 1;
+        }
         self.compiled = true;
     }
 
@@ -211,6 +310,14 @@ impl FrameGraph {
         self.resources.get(&id)
     }
 
+    pub fn pass_by_name(&self, name: &str) -> Option<PassId> {
+        self.passes.values().find(|p| p.name == name).map(|p| p.id)
+    }
+
+    pub fn resource_by_name(&self, name: &str) -> Option<ResourceId> {
+        self.resources.values().find(|r| r.name == name).map(|r| r.id)
+    }
+
     pub fn print_debug(&self) {
         println!("╔══════════════════════════════════════════════════════════════════╗");
         println!("║                      FrameGraph Debug                            ║");
@@ -258,10 +365,82 @@ impl FrameGraph {
 
     pub fn reset(&mut self) {
         self.resources.retain(|_, r| r.persistent);
+        let resources = &self.resources;
+        self.imported.retain(|id, _| resources.contains_key(id));
         self.passes.clear();
+        self.executors.clear();
         self.barriers.clear();
         self.execution_order.clear();
         self.next_pass_id = 0;
         self.compiled = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shadow -> Lighting <- GBuffer, Lighting -> Post.
+    fn lit_graph() -> (FrameGraph, [PassId; 4]) {
+        let mut graph = FrameGraph::new();
+        let shadow = graph.create_resource("ShadowMap", ResourceType::DepthBuffer, 256, 256, ResourceFormat::Depth32F);
+        let gbuffer = graph.create_resource("GBuffer", ResourceType::RenderTarget, 64, 64, ResourceFormat::RGBA16F);
+        let hdr = graph.create_resource("HDR", ResourceType::RenderTarget, 64, 64, ResourceFormat::RGBA16F);
+        let out = graph.create_resource("Final", ResourceType::Swapchain, 64, 64, ResourceFormat::RGBA8);
+        let s = graph.pass("Shadow").write(shadow).order(0).build();
+        let g = graph.pass("GBuffer").write(gbuffer).order(1).build();
+        let l = graph.pass("Lighting").read(shadow).read(gbuffer).write(hdr).order(2).build();
+        let p = graph.pass("Post").read(hdr).write(out).order(3).build();
+        graph.compile();
+        (graph, [s, g, l, p])
+    }
+
+    #[test]
+    fn toggling_by_name_recompiles_the_order() {
+        let (mut graph, [_, _, lighting, post]) = lit_graph();
+        assert_eq!(graph.execution_order().len(), 4);
+
+        graph.set_pass_enabled_by_name("Post", false).unwrap();
+        graph.compile();
+        assert!(!graph.is_pass_enabled(post));
+        assert_eq!(graph.execution_order().last(), Some(&lighting));
+        assert_eq!(graph.stats.enabled_passes, 3);
+        assert!(graph.set_pass_enabled_by_name("Bloom", false).is_err());
+    }
+
+    #[test]
+    fn disabling_a_producer_fails_validation_with_the_broken_edge() {
+        let (mut graph, [shadow, ..]) = lit_graph();
+        assert!(graph.validate().is_ok());
+        graph.set_pass_enabled(shadow, false);
+        let err = graph.validate().unwrap_err().to_string();
+        assert!(err.contains("'Lighting' reads 'ShadowMap' written only by disabled Shadow"), "{}", err);
+        assert!(!err.contains("'Post'"));
+    }
+
+    #[test]
+    fn cascading_disable_turns_off_dependents() {
+        let (mut graph, [shadow, gbuffer, lighting, post]) = lit_graph();
+        assert_eq!(graph.disable_pass_cascading(shadow), vec![shadow, lighting, post]);
+        assert!(graph.is_pass_enabled(gbuffer));
+        assert!(graph.validate().is_ok());
+        graph.compile();
+        assert_eq!(graph.execution_order(), &[gbuffer]);
+        assert!(graph.disable_pass_cascading(shadow).is_empty());
+    }
+
+    #[test]
+    fn toggling_without_changing_memory_keeps_the_generation() {
+        let mut graph = FrameGraph::new();
+        let target = graph.create_resource("Target", ResourceType::RenderTarget, 64, 64, ResourceFormat::RGBA8);
+        graph.pass("Clear").write(target).order(0).build();
+        let overlay = graph.pass("Overlay").read(target).write(target).order(1).build();
+        graph.compile();
+        let generation = graph.generation;
+
+        graph.set_pass_enabled(overlay, false);
+        graph.compile();
+        assert_eq!(graph.execution_order().len(), 1);
+        assert_eq!(graph.generation, generation);
+    }
+}
@@ -1,11 +1,18 @@
+mod aliasing;
 mod builder;
+mod execute;
 mod graph;
 mod presets;
 mod types;
+mod validation;
 
+pub use aliasing::AliasBlock;
+pub use builder::PassBuilder;
+pub use execute::{vk_format, PassContext, PassExecutor, ResolvedResource};
 pub use graph::FrameGraph;
 pub use types::{
     AccessType, Barrier, FrameGraphStats, PassDesc, PassId, ResourceDesc, ResourceFormat,
     ResourceId, ResourceType,
 };
+pub use validation::{ValidationIssue, ValidationIssueKind, ValidationReport, ValidationSeverity};
 pub use presets::{create_deferred_graph, create_forward_graph};
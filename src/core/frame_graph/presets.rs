@@ -10,6 +10,7 @@ pub fn create_deferred_graph(width: u32, height: u32) -> FrameGraph {
     let emissive = graph.create_resource("GBuffer2_Emissive_Material", ResourceType::RenderTarget, width, height, ResourceFormat::RGBA16F);
     let motion_depth = graph.create_resource("GBuffer3_Motion_Depth_Flags", ResourceType::RenderTarget, width, height, ResourceFormat::RGBA16F);
     let lit = graph.create_resource("Lit", ResourceType::RenderTarget, width, height, ResourceFormat::RGBA16F);
+    let post = graph.create_resource("PostProcess", ResourceType::RenderTarget, width, height, ResourceFormat::RGBA16F);
     let final_output = graph.create_resource("Final", ResourceType::Swapchain, width, height, ResourceFormat::RGBA8);
 
     graph.pass("GBuffer")
@@ -21,12 +22,20 @@ pub fn create_deferred_graph(width: u32, height: u32) -> FrameGraph {
         .write(lit).order(1).build();
 
     graph.pass("PostProcess")
-        .read(lit).write(final_output).order(2).build();
+        .read(lit).write(post).order(2).build();
+
+    // Tonemap + composite into the swapchain. PostProcess starts after the
+    // G-buffer is last read, so the alias plan puts it in G-buffer memory.
+    graph.pass("Composite")
+        .read(post).write(final_output).order(3).build();
 
     graph.compile();
     graph
 }
 
+/// Single forward pass into the swapchain. To drive a frame, import the
+/// acquired image as `"Color"`, give `"Forward"` an executor and call
+/// `FrameGraph::execute`; the graph creates `"Depth"` itself.
 pub fn create_forward_graph(width: u32, height: u32) -> FrameGraph {
     let mut graph = FrameGraph::new();
 
@@ -38,6 +38,28 @@ pub enum ResourceFormat {
     Depth24Stencil8,
 }
 
+impl ResourceFormat {
+    pub fn bytes_per_pixel(self) -> u64 {
+        match self {
+            ResourceFormat::R8 => 1,
+            ResourceFormat::R16F => 2,
+            ResourceFormat::RGBA8 | ResourceFormat::R32F | ResourceFormat::Depth32F | ResourceFormat::Depth24Stencil8 => 4,
+            ResourceFormat::RGBA16F => 8,
+            ResourceFormat::RGBA32F => 16,
+        }
+    }
+
+    pub fn is_depth(self) -> bool {
+        matches!(self, ResourceFormat::Depth32F | ResourceFormat::Depth24Stencil8)
+    }
+}
+
+impl ResourceDesc {
+    pub fn is_depth(&self) -> bool {
+        self.resource_type == ResourceType::DepthBuffer || self.format.is_depth()
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AccessType {
     Read,
@@ -77,4 +99,8 @@ pub struct FrameGraphStats {
     pub total_resources: u32,
     pub transient_resources: u32,
     pub barriers_generated: u32,
+    /// Estimated bytes the alias plan saves over one allocation per image.
+    pub aliased_bytes_saved: u64,
+    /// Distinct memory allocations the graph makes for its own images.
+    pub allocation_count: u32,
 }
@@ -0,0 +1,451 @@
+//! FrameGraph validation
+//!
+//! `FrameGraph::compile_validated` checks the enabled passes before building
+//! the execution order: dependency cycles, reads of resources nothing
+//! writes, writes nothing reads, swapchain writers whose order is ambiguous
+//! and attachments whose format or size doesn't fit the way a pass uses
+//! them. A graph with a cycle is not compiled; every other issue is reported
+//! and `execute` refuses to run while any error remains.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::frame_graph::graph::FrameGraph;
+use crate::core::frame_graph::types::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    /// Passes that (transitively) read each other's output.
+    Cycle,
+    /// A pass reads a resource no enabled pass writes.
+    UnwrittenRead,
+    /// A pass writes a resource no pass reads afterwards.
+    UnreadWrite,
+    /// Several passes write the swapchain with nothing ordering them.
+    AmbiguousSwapchainWrite,
+    /// Depth format on a color resource or the other way around.
+    FormatMismatch,
+    /// A pass writes attachments of different sizes.
+    SizeMismatch,
+}
+
+impl ValidationIssueKind {
+    pub fn severity(self) -> ValidationSeverity {
+        match self {
+            ValidationIssueKind::UnreadWrite => ValidationSeverity::Warning,
+            _ => ValidationSeverity::Error,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    pub message: String,
+    /// Names of the passes involved, in the order the message uses them.
+    pub passes: Vec<String>,
+    /// Names of the resources involved.
+    pub resources: Vec<String>,
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> ValidationSeverity {
+        self.kind.severity()
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity() {
+            ValidationSeverity::Error => write!(f, "error: {}", self.message),
+            ValidationSeverity::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+/// Everything `FrameGraph::validation_report` found, errors first.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.issues.iter().any(|i| i.kind == ValidationIssueKind::Cycle)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|i| i.severity() == ValidationSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|i| i.severity() == ValidationSeverity::Warning)
+    }
+
+    /// `Err` listing every error; warnings don't fail.
+    pub fn into_result(self) -> ReactorResult<()> {
+        if !self.has_errors() {
+            return Ok(());
+        }
+        let errors: Vec<&str> = self.errors().map(|i| i.message.as_str()).collect();
+        Err(ReactorError::new(
+            ErrorCode::InvalidParameter,
+            format!("FrameGraph validation failed: {}", errors.join("; ")),
+        ))
+    }
+
+    pub(super) fn log(&self) {
+        for issue in &self.issues {
+            match issue.severity() {
+                ValidationSeverity::Error => log::error!("FrameGraph: {}", issue.message),
+                ValidationSeverity::Warning => log::warn!("FrameGraph: {}", issue.message),
+            }
+        }
+    }
+
+    fn push(&mut self, kind: ValidationIssueKind, message: String, passes: Vec<String>, resources: Vec<String>) {
+        self.issues.push(ValidationIssue { kind, message, passes, resources });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return writeln!(f, "FrameGraph: no issues");
+        }
+        let errors = self.errors().count();
+        writeln!(f, "FrameGraph: {} error(s), {} warning(s)", errors, self.issues.len() - errors)?;
+        for issue in &self.issues {
+            writeln!(f, "  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl FrameGraph {
+    /// Checks the enabled passes without compiling anything.
+    pub fn validation_report(&self) -> ValidationReport {
+        let mut passes: Vec<&PassDesc> = self.passes.values().filter(|p| p.enabled).collect();
+        passes.sort_by_key(|p| p.id.0);
+        // Passes each enabled pass waits for: the other writers of what it reads.
+        let depends_on: HashMap<PassId, Vec<PassId>> = passes
+            .iter()
+            .map(|pass| {
+                let writers = passes
+                    .iter()
+                    .filter(|other| other.id != pass.id && pass.reads.iter().any(|r| other.writes.contains(r)))
+                    .map(|other| other.id)
+                    .collect();
+                (pass.id, writers)
+            })
+            .collect();
+
+        let mut report = ValidationReport::default();
+        self.check_cycles(&passes, &depends_on, &mut report);
+        self.check_reads(&passes, &mut report);
+        self.check_swapchain_writers(&passes, &depends_on, &mut report);
+        self.check_attachments(&passes, &mut report);
+        self.check_writes(&passes, &mut report);
+        report
+    }
+
+    /// Fails when `validation_report` has errors. `execute` runs this first.
+    pub fn validate(&self) -> ReactorResult<()> {
+        self.validation_report().into_result()
+    }
+
+    fn pass_name(&self, id: PassId) -> String {
+        self.passes.get(&id).map(|p| p.name.clone()).unwrap_or_else(|| "?".to_string())
+    }
+
+    fn resource_name(&self, id: ResourceId) -> String {
+        self.resources.get(&id).map(|r| r.name.clone()).unwrap_or_else(|| "?".to_string())
+    }
+
+    fn check_cycles(&self, passes: &[&PassDesc], depends_on: &HashMap<PassId, Vec<PassId>>, report: &mut ValidationReport) {
+        fn visit(
+            pass: PassId,
+            depends_on: &HashMap<PassId, Vec<PassId>>,
+            done: &mut HashSet<PassId>,
+            stack: &mut Vec<PassId>,
+            cycles: &mut Vec<Vec<PassId>>,
+        ) {
+            if let Some(start) = stack.iter().position(|&p| p == pass) {
+                cycles.push(stack[start..].to_vec());
+                return;
+            }
+            if !done.insert(pass) {
+                return;
+            }
+            stack.push(pass);
+            for &dependency in &depends_on[&pass] {
+                visit(dependency, depends_on, done, stack, cycles);
+            }
+            stack.pop();
+        }
+
+        let mut done = HashSet::new();
+        let mut cycles = Vec::new();
+        for pass in passes {
+            visit(pass.id, depends_on, &mut done, &mut Vec::new(), &mut cycles);
+        }
+        for mut cycle in cycles {
+            // `stack` runs reader -> writer; report it in execution direction,
+            // starting from the oldest pass.
+            cycle.reverse();
+            let first = (0..cycle.len()).min_by_key(|&i| cycle[i].0).unwrap_or(0);
+            cycle.rotate_left(first);
+            let mut names: Vec<String> = cycle.iter().map(|&p| self.pass_name(p)).collect();
+            names.push(names[0].clone());
+            report.push(
+                ValidationIssueKind::Cycle,
+                format!("dependency cycle {}", names.join(" -> ")),
+                cycle.iter().map(|&p| self.pass_name(p)).collect(),
+                Vec::new(),
+            );
+        }
+    }
+
+    /// Reads need a writer unless the contents come from outside the frame:
+    /// imported images, buffers, the swapchain and persistent history.
+    fn check_reads(&self, passes: &[&PassDesc], report: &mut ValidationReport) {
+        let disabled_writers: HashMap<(PassId, ResourceId), Vec<PassId>> = self
+            .broken_edges()
+            .into_iter()
+            .map(|(reader, resource, writers)| ((reader, resource), writers))
+            .collect();
+        for pass in passes {
+            for &resource in &pass.reads {
+                let Some(desc) = self.resources.get(&resource) else { continue };
+                if let Some(writers) = disabled_writers.get(&(pass.id, resource)) {
+                    let writers: Vec<String> = writers.iter().map(|&p| self.pass_name(p)).collect();
+                    report.push(
+                        ValidationIssueKind::UnwrittenRead,
+                        format!("'{}' reads '{}' written only by disabled {}", pass.name, desc.name, writers.join(", ")),
+                        std::iter::once(pass.name.clone()).chain(writers).collect(),
+                        vec![desc.name.clone()],
+                    );
+                    continue;
+                }
+                let external = desc.persistent
+                    || self.imported.contains_key(&resource)
+                    || matches!(desc.resource_type, ResourceType::Swapchain | ResourceType::Buffer);
+                let written = self.passes.values().any(|p| p.id != pass.id && p.writes.contains(&resource));
+                if !external && !written {
+                    report.push(
+                        ValidationIssueKind::UnwrittenRead,
+                        format!("'{}' reads '{}' before any pass writes it", pass.name, desc.name),
+                        vec![pass.name.clone()],
+                        vec![desc.name.clone()],
+                    );
+                }
+            }
+        }
+    }
+
+    /// Two swapchain writers need a dependency or different `order`s,
+    /// otherwise which one lands on top is up to the sort.
+    fn check_swapchain_writers(
+        &self,
+        passes: &[&PassDesc],
+        depends_on: &HashMap<PassId, Vec<PassId>>,
+        report: &mut ValidationReport,
+    ) {
+        let reaches = |from: PassId, to: PassId| {
+            let mut pending = vec![from];
+            let mut seen = HashSet::new();
+            while let Some(pass) = pending.pop() {
+                if pass == to {
+                    return true;
+                }
+                if seen.insert(pass) {
+                    pending.extend(depends_on.get(&pass).into_iter().flatten().copied());
+                }
+            }
+            false
+        };
+        let mut swapchains: Vec<&ResourceDesc> =
+            self.resources.values().filter(|r| r.resource_type == ResourceType::Swapchain).collect();
+        swapchains.sort_by_key(|r| r.id.0);
+        for swapchain in swapchains {
+            let writers: Vec<&&PassDesc> = passes.iter().filter(|p| p.writes.contains(&swapchain.id)).collect();
+            for (i, a) in writers.iter().enumerate() {
+                for b in &writers[i + 1..] {
+                    if a.order == b.order && !reaches(a.id, b.id) && !reaches(b.id, a.id) {
+                        report.push(
+                            ValidationIssueKind::AmbiguousSwapchainWrite,
+                            format!(
+                                "'{}' and '{}' both write swapchain '{}' with order {} and no dependency between them",
+                                a.name, b.name, swapchain.name, a.order
+                            ),
+                            vec![a.name.clone(), b.name.clone()],
+                            vec![swapchain.name.clone()],
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Depth formats only on depth buffers, and every image a pass writes
+    /// shares one render area.
+    fn check_attachments(&self, passes: &[&PassDesc], report: &mut ValidationReport) {
+        let mut resources: Vec<&ResourceDesc> = self.resources.values().collect();
+        resources.sort_by_key(|r| r.id.0);
+        for desc in resources {
+            let used = passes.iter().any(|p| p.reads.contains(&desc.id) || p.writes.contains(&desc.id));
+            let depth_type = desc.resource_type == ResourceType::DepthBuffer;
+            if used && desc.resource_type != ResourceType::Buffer && depth_type != desc.format.is_depth() {
+                report.push(
+                    ValidationIssueKind::FormatMismatch,
+                    format!("'{}' is a {:?} with format {:?}", desc.name, desc.resource_type, desc.format),
+                    Vec::new(),
+                    vec![desc.name.clone()],
+                );
+            }
+        }
+
+        for pass in passes {
+            let attachments: Vec<&ResourceDesc> = pass
+                .writes
+                .iter()
+                .filter_map(|id| self.resources.get(id))
+                .filter(|d| d.resource_type != ResourceType::Buffer)
+                .collect();
+            let Some(first) = attachments.first() else { continue };
+            let mismatched: Vec<&&ResourceDesc> =
+                attachments.iter().filter(|d| (d.width, d.height) != (first.width, first.height)).collect();
+            if !mismatched.is_empty() {
+                let sizes: Vec<String> =
+                    attachments.iter().map(|d| format!("'{}' {}x{}", d.name, d.width, d.height)).collect();
+                report.push(
+                    ValidationIssueKind::SizeMismatch,
+                    format!("'{}' writes attachments of different sizes: {}", pass.name, sizes.join(", ")),
+                    vec![pass.name.clone()],
+                    attachments.iter().map(|d| d.name.clone()).collect(),
+                );
+            }
+        }
+    }
+
+    /// Writes nobody reads are wasted work. The swapchain, persistent and
+    /// imported images are read outside the graph, and a depth buffer is
+    /// consumed by the depth test of the pass that writes it.
+    fn check_writes(&self, passes: &[&PassDesc], report: &mut ValidationReport) {
+        for pass in passes {
+            for &resource in &pass.writes {
+                let Some(desc) = self.resources.get(&resource) else { continue };
+                let external = desc.persistent
+                    || self.imported.contains_key(&resource)
+                    || desc.is_depth()
+                    || matches!(desc.resource_type, ResourceType::Swapchain | ResourceType::Buffer);
+                let read = passes.iter().any(|p| p.reads.contains(&resource));
+                if !external && !read {
+                    report.push(
+                        ValidationIssueKind::UnreadWrite,
+                        format!("'{}' writes '{}' but no enabled pass reads it", pass.name, self.resource_name(resource)),
+                        vec![pass.name.clone()],
+                        vec![desc.name.clone()],
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::frame_graph::{create_deferred_graph, create_forward_graph};
+
+    fn target(graph: &mut FrameGraph, name: &str) -> ResourceId {
+        graph.create_resource(name, ResourceType::RenderTarget, 64, 64, ResourceFormat::RGBA8)
+    }
+
+    fn kinds(report: &ValidationReport) -> Vec<ValidationIssueKind> {
+        report.issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn presets_validate_cleanly() {
+        for graph in [create_forward_graph(1280, 720), create_deferred_graph(1920, 1080)] {
+            let report = graph.validation_report();
+            assert!(report.is_clean(), "{}", report);
+            assert!(graph.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn cycles_report_their_path_and_skip_compilation() {
+        let mut graph = FrameGraph::new();
+        let a = target(&mut graph, "A");
+        let b = target(&mut graph, "B");
+        graph.pass("Ping").read(b).write(a).build();
+        graph.pass("Pong").read(a).write(b).build();
+
+        let report = graph.compile_validated();
+        assert!(report.has_cycle());
+        let cycle = report.issues.iter().find(|i| i.kind == ValidationIssueKind::Cycle).unwrap();
+        assert_eq!(cycle.message, "dependency cycle Ping -> Pong -> Ping");
+        assert!(graph.execution_order().is_empty());
+        assert!(graph.validate().is_err());
+    }
+
+    #[test]
+    fn unwritten_reads_are_errors_and_unread_writes_warnings() {
+        let mut graph = FrameGraph::new();
+        let missing = target(&mut graph, "Missing");
+        let unused = target(&mut graph, "Unused");
+        let history = graph.create_persistent_resource("History", ResourceType::RenderTarget, 64, 64, ResourceFormat::RGBA8);
+        let out = graph.create_resource("Final", ResourceType::Swapchain, 64, 64, ResourceFormat::RGBA8);
+        graph.pass("Resolve").read(missing).read(history).write(unused).write(out).build();
+
+        let report = graph.compile_validated();
+        assert_eq!(kinds(&report), vec![ValidationIssueKind::UnwrittenRead, ValidationIssueKind::UnreadWrite]);
+        assert_eq!(report.errors().count(), 1);
+        assert_eq!(report.issues[0].resources, vec!["Missing".to_string()]);
+        assert_eq!(report.warnings().next().unwrap().passes, vec!["Resolve".to_string()]);
+        // Errors don't stop compilation, only execution.
+        assert_eq!(graph.execution_order().len(), 1);
+        assert!(report.to_string().contains("error: 'Resolve' reads 'Missing' before any pass writes it"));
+    }
+
+    #[test]
+    fn unordered_swapchain_writers_are_ambiguous() {
+        let mut graph = FrameGraph::new();
+        let out = graph.create_resource("Final", ResourceType::Swapchain, 64, 64, ResourceFormat::RGBA8);
+        graph.pass("Scene").write(out).order(0).build();
+        let ui = graph.pass("UI").write(out).order(0).build();
+        assert_eq!(kinds(&graph.validation_report()), vec![ValidationIssueKind::AmbiguousSwapchainWrite]);
+
+        graph.passes.get_mut(&ui).unwrap().order = 1;
+        assert!(graph.validation_report().is_clean());
+    }
+
+    #[test]
+    fn attachment_format_and_size_mismatches() {
+        let mut graph = FrameGraph::new();
+        let depth = graph.create_resource("Depth", ResourceType::DepthBuffer, 64, 64, ResourceFormat::RGBA8);
+        let color = graph.create_resource("Color", ResourceType::Swapchain, 32, 32, ResourceFormat::RGBA8);
+        graph.pass("Forward").write(depth).write(color).build();
+
+        let report = graph.validation_report();
+        assert_eq!(kinds(&report), vec![ValidationIssueKind::FormatMismatch, ValidationIssueKind::SizeMismatch]);
+        assert!(report.issues[1].message.contains("'Depth' 64x64, 'Color' 32x32"), "{}", report);
+    }
+}
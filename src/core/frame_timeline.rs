@@ -0,0 +1,306 @@
+// =============================================================================
+// REACTOR Frame Timeline + Deferred Destruction
+// =============================================================================
+// Every frame the `Reactor` submits signals the next value of one timeline
+// semaphore. Resources dropped while frames are in flight are queued on the
+// device, tagged with the value of the frame being recorded, and destroyed
+// once the GPU has passed it — no `device_wait_idle` needed to free them.
+//
+// Without timeline semaphore support the completed value is derived from
+// the per-slot fences the `Reactor` already waits on.
+// =============================================================================
+
+use std::collections::{HashMap, VecDeque};
+
+use ash::vk;
+use ash::vk::Handle;
+
+use crate::core::arc_handle::ArcDevice;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::VulkanContext;
+
+type Retired = Box<dyn FnOnce(&ash::Device) + Send>;
+
+/// Destructions waiting for the GPU, owned by the device (see
+/// `ArcDevice::destroy_deferred`). Inactive — everything is destroyed on
+/// the spot — while no `FrameTimeline` exists.
+#[derive(Default)]
+pub(crate) struct DeletionQueue {
+    active: bool,
+    /// Value the frame currently being recorded will signal.
+    pending: u64,
+    /// Last value the GPU is known to have reached.
+    completed: u64,
+    entries: VecDeque<(u64, Retired)>,
+    /// Raw handle -> last frame value that references it.
+    in_flight: HashMap<u64, u64>,
+    track_in_flight: bool,
+}
+
+impl DeletionQueue {
+    /// Runs `retire` now if the queue is inactive, otherwise hands it back
+    /// after queueing it behind the frame being recorded.
+    pub(crate) fn push(&mut self, retire: Retired) -> Option<Retired> {
+        if !self.active {
+            return Some(retire);
+        }
+        self.entries.push_back((self.pending, retire));
+        None
+    }
+
+    /// Removes every entry the GPU has passed.
+    fn take_ready(&mut self) -> Vec<Retired> {
+        let completed = self.completed;
+        self.in_flight.retain(|_, value| *value > completed);
+        let ready = self.entries.iter().take_while(|(value, _)| *value <= completed).count();
+        self.entries.drain(..ready).map(|(_, retire)| retire).collect()
+    }
+
+    /// Everything left, for when the device is idle.
+    pub(crate) fn take_all(&mut self) -> Vec<Retired> {
+        self.in_flight.clear();
+        self.entries.drain(..).map(|(_, retire)| retire).collect()
+    }
+
+    pub(crate) fn check_not_in_flight(&self, raw: u64, what: &str) {
+        if let Some(&value) = self.in_flight.get(&raw) {
+            if value > self.completed {
+                log::error!(
+                    "Destroying {} 0x{:x} still used by frame {} (GPU at {})",
+                    what, raw, value, self.completed
+                );
+                debug_assert!(false, "{} 0x{:x} destroyed while frame {} is in flight", what, raw, value);
+            }
+        }
+    }
+}
+
+/// Monotonic frame counter on the GPU timeline.
+pub struct FrameTimeline {
+    device: ArcDevice,
+    semaphore: Option<vk::Semaphore>,
+    submitted: u64,
+    completed: u64,
+    /// Value each frame slot signalled last (fence fallback).
+    slot_values: Vec<u64>,
+    track_in_flight: bool,
+}
+
+impl FrameTimeline {
+    /// Creates the timeline semaphore (when supported) and starts deferring
+    /// destruction on `ctx`'s device.
+    pub fn new(ctx: &VulkanContext, slots: usize) -> ReactorResult<Self> {
+        let semaphore = if ctx.supports_timeline_semaphore() {
+            let mut type_info = vk::SemaphoreTypeCreateInfo::default()
+                .semaphore_type(vk::SemaphoreType::TIMELINE)
+                .initial_value(0);
+            let info = vk::SemaphoreCreateInfo::default().push_next(&mut type_info);
+            let semaphore = unsafe { ctx.device.create_semaphore(&info, None) }.map_err(|e| {
+                ReactorError::with_source(ErrorCode::VulkanSynchronization, "Failed to create timeline semaphore", e)
+            })?;
+            ctx.debug_namer().name_semaphore(semaphore, "Semaphore: Frame Timeline");
+            Some(semaphore)
+        } else {
+            log::info!("Timeline semaphores unavailable; frame timeline follows the in-flight fences");
+            None
+        };
+        {
+            let mut queue = ctx.device.deletion_queue();
+            queue.active = true;
+            queue.pending = 1;
+            queue.completed = 0;
+            queue.track_in_flight = cfg!(debug_assertions);
+        }
+        Ok(Self {
+            device: ctx.device.clone(),
+            semaphore,
+            submitted: 0,
+            completed: 0,
+            slot_values: vec![0; slots],
+            track_in_flight: cfg!(debug_assertions),
+        })
+    }
+
+    /// Timeline semaphore to signal with `next_value`; `None` on the fence fallback.
+    pub fn semaphore(&self) -> Option<vk::Semaphore> {
+        self.semaphore
+    }
+
+    /// Value the next submitted frame signals.
+    pub fn next_value(&self) -> u64 {
+        self.submitted + 1
+    }
+
+    /// Value of the last submitted frame.
+    pub fn submitted_value(&self) -> u64 {
+        self.submitted
+    }
+
+    /// Last value the GPU is known to have reached.
+    pub fn completed_value(&self) -> u64 {
+        self.completed
+    }
+
+    /// Records that slot `slot` submitted `next_value`.
+    pub fn frame_submitted(&mut self, slot: usize) {
+        self.submitted += 1;
+        if let Some(value) = self.slot_values.get_mut(slot) {
+            *value = self.submitted;
+        }
+        self.device.deletion_queue().pending = self.submitted + 1;
+    }
+
+    /// Called after waiting slot `slot`'s fence: refreshes the completed
+    /// value and destroys everything the GPU has passed.
+    pub fn slot_finished(&mut self, slot: usize) {
+        let fenced = self.slot_values.get(slot).copied().unwrap_or(0);
+        let counter = self
+            .semaphore
+            .and_then(|semaphore| unsafe { self.device.get_semaphore_counter_value(semaphore) }.ok())
+            .unwrap_or(0);
+        self.completed = self.completed.max(fenced).max(counter);
+        self.collect();
+    }
+
+    /// Destroys the queued resources the GPU has passed. Returns how many.
+    pub fn collect(&mut self) -> usize {
+        let ready = {
+            let mut queue = self.device.deletion_queue();
+            queue.completed = queue.completed.max(self.completed);
+            queue.take_ready()
+        };
+        let count = ready.len();
+        for retire in ready {
+            retire(self.device.get());
+        }
+        count
+    }
+
+    /// Resources waiting for the GPU.
+    pub fn pending_destructions(&self) -> usize {
+        self.device.deletion_queue().entries.len()
+    }
+
+    /// Blocks until the GPU reaches `value` (timeline semaphore only; the
+    /// fence fallback waits for the whole device).
+    pub fn wait(&mut self, value: u64, timeout_ns: u64) -> ReactorResult<()> {
+        if value <= self.completed {
+            return Ok(());
+        }
+        match self.semaphore {
+            Some(semaphore) => {
+                let semaphores = [semaphore];
+                let values = [value];
+                let info = vk::SemaphoreWaitInfo::default().semaphores(&semaphores).values(&values);
+                unsafe { self.device.wait_semaphores(&info, timeout_ns) }.map_err(|e| {
+                    ReactorError::with_source(ErrorCode::VulkanSynchronization, "wait_semaphores failed", e)
+                })?;
+                self.completed = value;
+            }
+            None => {
+                unsafe { self.device.device_wait_idle() }.map_err(|e| {
+                    ReactorError::with_source(ErrorCode::VulkanSynchronization, "device_wait_idle failed", e)
+                })?;
+                self.completed = self.submitted;
+            }
+        }
+        self.collect();
+        Ok(())
+    }
+
+    /// Remembers that the frame being recorded references `handle`, so
+    /// destroying it immediately before that frame completes is reported.
+    pub fn mark_in_flight<H: Handle>(&self, handle: H) {
+        if !self.track_in_flight {
+            return;
+        }
+        let mut queue = self.device.deletion_queue();
+        let pending = queue.pending;
+        queue.in_flight.insert(handle.as_raw(), pending);
+    }
+
+    pub fn in_flight_tracking(&self) -> bool {
+        self.track_in_flight
+    }
+
+    /// Debug check on immediate destruction of in-flight handles. On by
+    /// default in debug builds.
+    pub fn set_in_flight_tracking(&mut self, enabled: bool) {
+        self.track_in_flight = enabled;
+        let mut queue = self.device.deletion_queue();
+        queue.track_in_flight = enabled;
+        if !enabled {
+            queue.in_flight.clear();
+        }
+    }
+}
+
+impl Drop for FrameTimeline {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.device.device_wait_idle();
+        }
+        let retired = {
+            let mut queue = self.device.deletion_queue();
+            queue.active = false;
+            queue.take_all()
+        };
+        for retire in retired {
+            retire(self.device.get());
+        }
+        if let Some(semaphore) = self.semaphore.take() {
+            unsafe { self.device.destroy_semaphore(semaphore, None) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn counter_retire(counter: &Arc<AtomicUsize>) -> Retired {
+        let counter = counter.clone();
+        Box::new(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn inactive_queue_hands_destruction_back() {
+        let mut queue = DeletionQueue::default();
+        let counter = Arc::new(AtomicUsize::new(0));
+        assert!(queue.push(counter_retire(&counter)).is_some());
+        assert!(queue.entries.is_empty());
+    }
+
+    #[test]
+    fn entries_wait_for_their_frame() {
+        let mut queue = DeletionQueue { active: true, pending: 3, completed: 1, ..Default::default() };
+        let counter = Arc::new(AtomicUsize::new(0));
+        assert!(queue.push(counter_retire(&counter)).is_none());
+        queue.pending = 4;
+        assert!(queue.push(counter_retire(&counter)).is_none());
+
+        queue.completed = 2;
+        assert!(queue.take_ready().is_empty());
+        queue.completed = 3;
+        assert_eq!(queue.take_ready().len(), 1);
+        assert_eq!(queue.entries.len(), 1);
+        assert_eq!(queue.take_all().len(), 1);
+    }
+
+    #[test]
+    fn in_flight_marks_expire_with_the_frame() {
+        let mut queue = DeletionQueue { active: true, pending: 5, completed: 4, track_in_flight: true, ..Default::default() };
+        queue.in_flight.insert(0xabc, 5);
+        queue.in_flight.insert(0xdef, 3);
+        queue.take_ready();
+        assert_eq!(queue.in_flight.len(), 1);
+        queue.completed = 5;
+        queue.check_not_in_flight(0xabc, "buffer");
+        queue.take_ready();
+        assert!(queue.in_flight.is_empty());
+    }
+}
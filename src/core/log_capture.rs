@@ -0,0 +1,243 @@
+// =============================================================================
+// REACTOR Log Capture
+// =============================================================================
+// Everything the engine reports goes through the `log` facade. The logger
+// installed by `init_log_capture` forwards to `env_logger` (stderr, `RUST_LOG`)
+// as before and also keeps structured records in a bounded ring buffer, so an
+// in-app console can list, filter and search them. Tools that want records as
+// they arrive call `subscribe_logs` and drain the receiver each frame.
+//
+// Vulkan messenger output is captured too: `record_debug_message` logs it
+// with `log::` before storing it in the validation capture.
+// =============================================================================
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Records kept before the oldest ones are dropped.
+pub const LOG_CAPTURE_CAPACITY: usize = 4096;
+
+/// Least severe level captured even when `RUST_LOG` asks for less on stderr.
+const CAPTURE_LEVEL: LevelFilter = LevelFilter::Info;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    pub level: Level,
+    /// Module path or explicit `target:` of the log call.
+    pub target: String,
+    pub message: String,
+    pub timestamp: SystemTime,
+}
+
+impl LogRecord {
+    /// `level` or more severe, and `search` (case-insensitive) in the
+    /// message or the target. An empty `search` matches everything.
+    pub fn matches(&self, level: LevelFilter, search: &str) -> bool {
+        if self.level > level {
+            return false;
+        }
+        if search.is_empty() {
+            return true;
+        }
+        let search = search.to_lowercase();
+        self.message.to_lowercase().contains(&search) || self.target.to_lowercase().contains(&search)
+    }
+}
+
+/// Bounded FIFO of log records.
+#[derive(Debug)]
+pub struct LogBuffer {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl LogBuffer {
+    pub const fn new(capacity: usize) -> Self {
+        Self { records: VecDeque::new(), capacity, dropped: 0 }
+    }
+
+    pub fn push(&mut self, record: LogRecord) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        while self.records.len() >= self.capacity {
+            self.records.pop_front();
+            self.dropped += 1;
+        }
+        self.records.push_back(record);
+    }
+
+    /// Buffered records, oldest first.
+    pub fn records(&self) -> impl Iterator<Item = &LogRecord> {
+        self.records.iter()
+    }
+
+    /// Records that pass `LogRecord::matches`, oldest first.
+    pub fn matching<'a>(&'a self, level: LevelFilter, search: &'a str) -> impl Iterator<Item = &'a LogRecord> {
+        self.records.iter().filter(move |r| r.matches(level, search))
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Records pushed out by newer ones since the last `clear`.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.dropped = 0;
+    }
+}
+
+static LOG_BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer::new(LOG_CAPTURE_CAPACITY));
+static LOG_SUBSCRIBERS: Mutex<Vec<Sender<LogRecord>>> = Mutex::new(Vec::new());
+
+/// Like the validation log, the buffer survives a panic while it is held.
+fn log_buffer() -> MutexGuard<'static, LogBuffer> {
+    LOG_BUFFER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn log_subscribers() -> MutexGuard<'static, Vec<Sender<LogRecord>>> {
+    LOG_SUBSCRIBERS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// `log::Log` that writes to stderr through `env_logger` and captures.
+struct CaptureLogger {
+    stderr: env_logger::Logger,
+    capture: LevelFilter,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.capture || self.stderr.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.stderr.matches(record) {
+            self.stderr.log(record);
+        }
+        if record.level() <= self.capture {
+            record_log(record.level(), record.target(), record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+    }
+}
+
+/// Install the capturing logger as the global `log` logger. Returns `false`
+/// if the application already installed another one; records are then not
+/// captured (only those passed to `record_log` directly).
+///
+/// The app runner calls this before creating the window.
+pub fn init_log_capture() -> bool {
+    let stderr = env_logger::Builder::from_default_env().build();
+    let capture = CAPTURE_LEVEL.max(stderr.filter());
+    if log::set_boxed_logger(Box::new(CaptureLogger { stderr, capture })).is_err() {
+        return false;
+    }
+    log::set_max_level(capture);
+    true
+}
+
+/// Store a record and hand it to every live subscriber.
+pub fn record_log(level: Level, target: &str, message: String) {
+    let record = LogRecord { level, target: target.to_string(), message, timestamp: SystemTime::now() };
+    log_subscribers().retain(|subscriber| subscriber.send(record.clone()).is_ok());
+    log_buffer().push(record);
+}
+
+/// Receiver of every record captured from now on. Dropping it unsubscribes.
+pub fn subscribe_logs() -> Receiver<LogRecord> {
+    let (sender, receiver) = mpsc::channel();
+    log_subscribers().push(sender);
+    receiver
+}
+
+/// Captured records, oldest first.
+pub fn captured_logs() -> Vec<LogRecord> {
+    log_buffer().records().cloned().collect()
+}
+
+/// Captured records at `level` or more severe containing `search`.
+pub fn captured_logs_matching(level: LevelFilter, search: &str) -> Vec<LogRecord> {
+    log_buffer().matching(level, search).cloned().collect()
+}
+
+/// Records lost because the ring buffer was full.
+pub fn dropped_log_records() -> u64 {
+    log_buffer().dropped()
+}
+
+pub fn clear_captured_logs() {
+    log_buffer().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: Level, target: &str, message: &str) -> LogRecord {
+        LogRecord { level, target: target.to_string(), message: message.to_string(), timestamp: SystemTime::now() }
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest_records() {
+        let mut buffer = LogBuffer::new(2);
+        buffer.push(record(Level::Info, "a", "first"));
+        buffer.push(record(Level::Info, "a", "second"));
+        buffer.push(record(Level::Info, "a", "third"));
+
+        let messages: Vec<_> = buffer.records().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, ["second", "third"]);
+        assert_eq!(buffer.dropped(), 1);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.dropped(), 0);
+    }
+
+    #[test]
+    fn filters_by_severity_and_search() {
+        let mut buffer = LogBuffer::new(8);
+        buffer.push(record(Level::Info, "reactor::resources", "Loaded OBJ: 24 vertices"));
+        buffer.push(record(Level::Warn, "reactor::app", "Swapchain recreation failed"));
+        buffer.push(record(Level::Error, "reactor::resources", "Failed to load cube.obj"));
+
+        let messages = |level, search| -> Vec<String> {
+            buffer.matching(level, search).map(|r| r.message.clone()).collect()
+        };
+        assert_eq!(messages(LevelFilter::Warn, "").len(), 2);
+        assert_eq!(messages(LevelFilter::Error, ""), ["Failed to load cube.obj"]);
+        assert_eq!(messages(LevelFilter::Trace, "OBJ"), ["Loaded OBJ: 24 vertices", "Failed to load cube.obj"]);
+        assert_eq!(messages(LevelFilter::Trace, "RESOURCES").len(), 2, "search covers the target");
+        assert!(messages(LevelFilter::Off, "").is_empty());
+    }
+
+    #[test]
+    fn subscribers_receive_new_records() {
+        let receiver = subscribe_logs();
+        record_log(Level::Warn, "log_capture::tests", "delivered".to_string());
+
+        let received: Vec<_> = receiver.try_iter().filter(|r| r.target == "log_capture::tests").collect();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].level, Level::Warn);
+        assert!(captured_logs_matching(LevelFilter::Warn, "log_capture::tests")
+            .iter()
+            .any(|r| r.message == "delivered"));
+    }
+}
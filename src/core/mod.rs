@@ -9,8 +9,12 @@ pub mod command;
 pub mod context;
 pub mod debug_utils;
 pub mod device;
+pub mod frame_timeline;
 pub mod memory_budget;
+pub mod pipeline_cache;
+pub mod render_scale;
 pub mod surface;
+pub mod validation;
 pub mod vrs;
 
 // Deterministic render graph
@@ -31,6 +35,9 @@ pub mod profiler;
 /// Structured logging (tracing-subscriber, env-filter via `REACTOR_LOG`).
 pub mod logging;
 
+/// `log` records kept in a bounded buffer for in-app consoles.
+pub mod log_capture;
+
 /// Parallel job system (rayon-backed, UE5 TaskGraph-style).
 /// Use `jobs::parallel_for`, `jobs::join`, `jobs::scope` for concurrency.
 pub mod jobs;
@@ -43,10 +50,11 @@ pub mod linear_allocator;
 // Re-exports
 // =============================================================================
 
-pub use allocator::MemoryAllocator;
+pub use allocator::{AllocationKind, MemoryAllocator, MemoryStats};
 pub use arc_handle::{ArcDevice, ArcInstance, ArcSurface};
 pub use command::CommandManager;
 pub use context::VulkanContext;
+pub use frame_timeline::FrameTimeline;
 pub use debug_utils::DebugNamer;
 pub use device::DeviceInfo;
 pub use error::{ErrorCode, ReactorError, ReactorResult};
@@ -58,16 +66,26 @@ pub use importance_map::{
     ImportanceMap, ImportanceMapConfig, ImportanceMapStats, ImportanceTileData, ImportanceType,
 };
 pub use memory_budget::{GpuMemoryBudget, HeapBudget};
+pub use pipeline_cache::{set_pipeline_cache_enabled, PipelineCache, PipelineCacheStats};
+pub use render_scale::{RenderScale, RenderScalePreset, RenderScaleStats};
+pub use validation::{
+    clear_validation_messages, set_validation_enabled, validation_messages, ValidationMessage,
+    ValidationMessageKind, ValidationSeverity,
+};
 pub use vrs::{
     PixelIntelligent, PixelIntelligentProfile, VrsCapabilities, VrsContext, VrsRate,
     VrsSupportedRate,
 };
 
 // Profiler
-pub use profiler::{begin_frame, get_frame_id, CpuTimer, PerfCounter};
+pub use profiler::{begin_frame, end_frame, get_frame_id, CpuTimer, PerfCounter};
 
 // Logging
 pub use logging::{init_logger, init_logger_with, LogLevel};
+pub use log_capture::{
+    captured_logs, captured_logs_matching, clear_captured_logs, init_log_capture, subscribe_logs,
+    LogBuffer, LogRecord,
+};
 
 // Jobs
 pub use jobs::{init_job_system, join, par_iter, par_iter_mut, parallel_for, scope};
@@ -0,0 +1,359 @@
+// =============================================================================
+// REACTOR Pipeline Cache
+// =============================================================================
+// One VkPipelineCache per VulkanContext, shared by every graphics, compute and
+// ray tracing pipeline the engine creates. Its contents are written to the
+// platform cache directory when the context goes away and fed back to the
+// driver on the next run, so shader compilation is mostly skipped at startup.
+//
+// On-disk layout (little endian):
+//
+//   magic "RPLC" | format version u32 | payload length u64 | xxh3 u64 | payload
+//
+// The payload is the blob from vkGetPipelineCacheData. Before handing it to
+// the driver its Vulkan header (vendor, device, pipelineCacheUUID) is checked
+// against the current GPU; files that fail any check are deleted and an empty
+// cache is used instead.
+//
+// Disable with `set_pipeline_cache_enabled(false)` before the context is
+// created (the app runner does it from `ReactorConfig`) or by setting
+// `REACTOR_PIPELINE_CACHE=0`.
+// =============================================================================
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use ash::vk;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::core::arc_handle::ArcDevice;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+
+const FILE_MAGIC: [u8; 4] = *b"RPLC";
+const FILE_VERSION: u32 = 1;
+const FILE_HEADER_LEN: usize = 4 + 4 + 8 + 8;
+/// `VkPipelineCacheHeaderVersionOne`: length, version, vendor, device, uuid.
+const VK_HEADER_LEN: usize = 16 + vk::UUID_SIZE;
+const CACHE_FILE_NAME: &str = "pipelines.vkcache";
+const ENV_SWITCH: &str = "REACTOR_PIPELINE_CACHE";
+
+static CACHE_ENABLED: AtomicBool = AtomicBool::new(true);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Use (or skip) the persistent cache for contexts created from now on.
+/// `REACTOR_PIPELINE_CACHE=0` / `off` overrides this to disabled.
+pub fn set_pipeline_cache_enabled(enabled: bool) {
+    CACHE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn pipeline_cache_enabled() -> bool {
+    let env_disabled = std::env::var(ENV_SWITCH)
+        .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "0" | "off" | "false" | "no"))
+        .unwrap_or(false);
+    CACHE_ENABLED.load(Ordering::Relaxed) && !env_disabled
+}
+
+/// Counters for pipelines created through the cache since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipelineCacheStats {
+    /// Pipelines the driver built from cached data.
+    pub hits: u64,
+    /// Pipelines compiled from scratch (or whose feedback was unavailable).
+    pub misses: u64,
+    /// Size of the blob loaded from disk at startup.
+    pub loaded_bytes: u64,
+    /// Current size of the driver cache, i.e. what the next save writes.
+    pub size_bytes: u64,
+}
+
+/// Global hit/miss counts; sizes are filled in by `PipelineCache::stats`.
+pub fn pipeline_cache_counters() -> (u64, u64) {
+    (CACHE_HITS.load(Ordering::Relaxed), CACHE_MISSES.load(Ordering::Relaxed))
+}
+
+/// Count one pipeline creation from its `VkPipelineCreationFeedback`.
+/// Nothing is recorded when no cache was passed to the create call.
+pub(crate) fn record_pipeline_feedback(cache: vk::PipelineCache, feedback: &vk::PipelineCreationFeedback) {
+    if cache == vk::PipelineCache::null() {
+        return;
+    }
+    let flags = feedback.flags;
+    if flags.contains(vk::PipelineCreationFeedbackFlags::VALID)
+        && flags.contains(vk::PipelineCreationFeedbackFlags::APPLICATION_PIPELINE_CACHE_HIT)
+    {
+        CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The GPU a cache blob must have been produced by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheIdentity {
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub uuid: [u8; vk::UUID_SIZE],
+}
+
+impl CacheIdentity {
+    pub fn from_properties(properties: &vk::PhysicalDeviceProperties) -> Self {
+        Self {
+            vendor_id: properties.vendor_id,
+            device_id: properties.device_id,
+            uuid: properties.pipeline_cache_uuid,
+        }
+    }
+}
+
+/// Why a cache file was thrown away.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheRejection {
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u32),
+    ChecksumMismatch,
+    BadVulkanHeader,
+    OtherDevice,
+}
+
+impl fmt::Display for CacheRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "file is truncated"),
+            Self::BadMagic => write!(f, "not a pipeline cache file"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported format version {}", v),
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+            Self::BadVulkanHeader => write!(f, "invalid Vulkan cache header"),
+            Self::OtherDevice => write!(f, "written by a different GPU or driver"),
+        }
+    }
+}
+
+/// Wrap a driver blob in the on-disk format.
+pub fn encode_cache_file(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(FILE_HEADER_LEN + payload.len());
+    out.extend_from_slice(&FILE_MAGIC);
+    out.extend_from_slice(&FILE_VERSION.to_le_bytes());
+    out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    out.extend_from_slice(&xxh3_64(payload).to_le_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Validate a cache file and return the driver blob inside it.
+pub fn decode_cache_file<'a>(data: &'a [u8], identity: &CacheIdentity) -> Result<&'a [u8], CacheRejection> {
+    if data.len() < FILE_HEADER_LEN {
+        return Err(CacheRejection::Truncated);
+    }
+    if data[0..4] != FILE_MAGIC {
+        return Err(CacheRejection::BadMagic);
+    }
+    let version = read_u32(&data[4..8]);
+    if version != FILE_VERSION {
+        return Err(CacheRejection::UnsupportedVersion(version));
+    }
+    let len = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let checksum = u64::from_le_bytes(data[16..24].try_into().unwrap());
+    let payload = &data[FILE_HEADER_LEN..];
+    if payload.len() as u64 != len {
+        return Err(CacheRejection::Truncated);
+    }
+    if xxh3_64(payload) != checksum {
+        return Err(CacheRejection::ChecksumMismatch);
+    }
+
+    if payload.len() < VK_HEADER_LEN {
+        return Err(CacheRejection::BadVulkanHeader);
+    }
+    let header_len = read_u32(&payload[0..4]) as usize;
+    let header_version = read_u32(&payload[4..8]);
+    if header_len < VK_HEADER_LEN
+        || header_len > payload.len()
+        || header_version != vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+    {
+        return Err(CacheRejection::BadVulkanHeader);
+    }
+    let vendor_id = read_u32(&payload[8..12]);
+    let device_id = read_u32(&payload[12..16]);
+    if vendor_id != identity.vendor_id
+        || device_id != identity.device_id
+        || payload[16..VK_HEADER_LEN] != identity.uuid
+    {
+        return Err(CacheRejection::OtherDevice);
+    }
+    Ok(payload)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().unwrap())
+}
+
+/// `<platform cache dir>/reactor/pipelines.vkcache`, e.g.
+/// `%LOCALAPPDATA%\reactor\cache` on Windows or `~/.cache/reactor` on Linux.
+pub fn default_cache_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "reactor").map(|dirs| dirs.cache_dir().join(CACHE_FILE_NAME))
+}
+
+/// Driver pipeline cache backed by a file. Saved and destroyed on drop.
+pub struct PipelineCache {
+    handle: vk::PipelineCache,
+    device: ArcDevice,
+    path: Option<PathBuf>,
+    loaded_bytes: u64,
+    save_lock: Mutex<()>,
+}
+
+impl PipelineCache {
+    /// Create the cache, seeding it from `path` when the file there is valid
+    /// for `identity`. Invalid files are deleted.
+    pub fn load_or_create(device: ArcDevice, identity: &CacheIdentity, path: Option<PathBuf>) -> ReactorResult<Self> {
+        let file = path.as_deref().and_then(|path| fs::read(path).ok());
+        let payload = match (&file, path.as_deref()) {
+            (Some(data), Some(path)) => match decode_cache_file(data, identity) {
+                Ok(payload) => payload,
+                Err(reason) => {
+                    log::warn!("Discarding pipeline cache {}: {}", path.display(), reason);
+                    let _ = fs::remove_file(path);
+                    &[]
+                }
+            },
+            _ => &[],
+        };
+
+        let info = vk::PipelineCacheCreateInfo::default().initial_data(payload);
+        let handle = match unsafe { device.get().create_pipeline_cache(&info, None) } {
+            Ok(handle) => handle,
+            // The driver can still refuse data that passed our checks.
+            Err(e) if !payload.is_empty() => {
+                log::warn!("Driver rejected the pipeline cache ({:?}); starting empty", e);
+                let info = vk::PipelineCacheCreateInfo::default();
+                unsafe { device.get().create_pipeline_cache(&info, None) }.map_err(|e| {
+                    ReactorError::with_source(ErrorCode::VulkanPipelineCreation, "vkCreatePipelineCache failed", e)
+                })?
+            }
+            Err(e) => {
+                return Err(ReactorError::with_source(
+                    ErrorCode::VulkanPipelineCreation,
+                    "vkCreatePipelineCache failed",
+                    e,
+                ))
+            }
+        };
+
+        if !payload.is_empty() {
+            log::info!("Pipeline cache loaded ({} KiB)", payload.len() / 1024);
+        }
+        Ok(Self { handle, device, path, loaded_bytes: payload.len() as u64, save_lock: Mutex::new(()) })
+    }
+
+    #[inline]
+    pub fn handle(&self) -> vk::PipelineCache {
+        self.handle
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn data(&self) -> ReactorResult<Vec<u8>> {
+        unsafe { self.device.get().get_pipeline_cache_data(self.handle) }.map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanPipelineCreation, "vkGetPipelineCacheData failed", e)
+        })
+    }
+
+    pub fn stats(&self) -> PipelineCacheStats {
+        let (hits, misses) = pipeline_cache_counters();
+        let size_bytes = self.data().map(|data| data.len() as u64).unwrap_or(0);
+        PipelineCacheStats { hits, misses, loaded_bytes: self.loaded_bytes, size_bytes }
+    }
+
+    /// Write the current contents to disk. Written to a temporary file first
+    /// so a crash mid-write never leaves a half file behind.
+    pub fn save(&self) -> ReactorResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let _guard = self.save_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let data = encode_cache_file(&self.data()?);
+        let io_err = |e| ReactorError::with_source(ErrorCode::IoError, "Failed to write the pipeline cache", e);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_err)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data).map_err(io_err)?;
+        fs::rename(&tmp, path).map_err(io_err)
+    }
+}
+
+impl Drop for PipelineCache {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            log::warn!("{}", e);
+        }
+        unsafe { self.device.get().destroy_pipeline_cache(self.handle, None) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> CacheIdentity {
+        CacheIdentity { vendor_id: 0x10de, device_id: 0x2684, uuid: [7; vk::UUID_SIZE] }
+    }
+
+    fn driver_blob(identity: &CacheIdentity) -> Vec<u8> {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&(VK_HEADER_LEN as u32).to_le_bytes());
+        blob.extend_from_slice(&1u32.to_le_bytes());
+        blob.extend_from_slice(&identity.vendor_id.to_le_bytes());
+        blob.extend_from_slice(&identity.device_id.to_le_bytes());
+        blob.extend_from_slice(&identity.uuid);
+        blob.extend_from_slice(b"driver private data");
+        blob
+    }
+
+    #[test]
+    fn roundtrip_returns_driver_blob() {
+        let blob = driver_blob(&identity());
+        let file = encode_cache_file(&blob);
+        assert_eq!(decode_cache_file(&file, &identity()), Ok(blob.as_slice()));
+    }
+
+    #[test]
+    fn truncated_and_corrupted_files_are_rejected() {
+        let file = encode_cache_file(&driver_blob(&identity()));
+        assert_eq!(decode_cache_file(&file[..10], &identity()), Err(CacheRejection::Truncated));
+        assert_eq!(decode_cache_file(&file[..file.len() - 1], &identity()), Err(CacheRejection::Truncated));
+
+        let mut flipped = file.clone();
+        *flipped.last_mut().unwrap() ^= 0xff;
+        assert_eq!(decode_cache_file(&flipped, &identity()), Err(CacheRejection::ChecksumMismatch));
+
+        let mut magic = file;
+        magic[0] = b'X';
+        assert_eq!(decode_cache_file(&magic, &identity()), Err(CacheRejection::BadMagic));
+    }
+
+    #[test]
+    fn cache_from_another_gpu_is_rejected() {
+        let file = encode_cache_file(&driver_blob(&identity()));
+        let other = CacheIdentity { uuid: [9; vk::UUID_SIZE], ..identity() };
+        assert_eq!(decode_cache_file(&file, &other), Err(CacheRejection::OtherDevice));
+        let other = CacheIdentity { device_id: 1, ..identity() };
+        assert_eq!(decode_cache_file(&file, &other), Err(CacheRejection::OtherDevice));
+    }
+
+    #[test]
+    fn malformed_vulkan_header_is_rejected() {
+        let mut blob = driver_blob(&identity());
+        blob[4] = 2;
+        let file = encode_cache_file(&blob);
+        assert_eq!(decode_cache_file(&file, &identity()), Err(CacheRejection::BadVulkanHeader));
+        assert_eq!(decode_cache_file(&encode_cache_file(b"short"), &identity()), Err(CacheRejection::BadVulkanHeader));
+    }
+}
@@ -19,10 +19,18 @@ thread_local! {
 }
 
 /// Advance the frame counter. Call once per frame at the start of `update()`.
+/// During a capture this also opens the frame's "frame" event.
 #[inline]
 pub fn begin_frame() {
     FRAME_COUNTER.with(|c| c.set(c.get() + 1));
     tracing::info_span!("frame", id = get_frame_id()).in_scope(|| {});
+    crate::utils::profiling::begin_frame();
+}
+
+/// Close the frame opened by `begin_frame`.
+#[inline]
+pub fn end_frame() {
+    crate::utils::profiling::end_frame();
 }
 
 /// Get current frame id (thread-local).
@@ -36,7 +44,8 @@ pub struct ProfileGuard {
     _span: tracing::span::EnteredSpan,
 }
 
-/// Profile a named scope. Emits a tracing span at `info` level.
+/// Profile a named scope. Recorded by `utils::profiling` while a capture
+/// runs; with fields it also emits a tracing span at `info` level.
 ///
 /// # Examples
 /// ```ignore
@@ -46,10 +55,13 @@ pub struct ProfileGuard {
 #[macro_export]
 macro_rules! profile_scope {
     ($name:expr) => {
-        let _profile_guard = tracing::info_span!($name).entered();
+        let _profile_guard = $crate::utils::profiling::Scope::new($name);
     };
     ($name:expr, $($fields:tt)*) => {
-        let _profile_guard = tracing::info_span!($name, $($fields)*).entered();
+        let _profile_guard = (
+            $crate::utils::profiling::Scope::new($name),
+            tracing::info_span!($name, $($fields)*).entered(),
+        );
     };
 }
 
@@ -0,0 +1,148 @@
+//! Dynamic render scale.
+//!
+//! With render scale on, the forward path draws the scene into the top-left
+//! `factor` × `factor` part of full-size scratch targets and blits it up to the
+//! post-process input before the post chain runs. The scratch targets are
+//! sized to the swapchain, so switching presets only changes the viewport:
+//! nothing is recreated.
+//!
+//! The estimated savings are the fraction of scene pixels not shaded, the
+//! same measure `PixelIntelligent::theoretical_pixel_work_reduction` reports
+//! for VRS.
+
+use ash::vk;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderScalePreset {
+    Balanced,
+    Performance,
+    Quality,
+    /// High refresh rate headsets: keeps frame time down at a moderate cost
+    /// in sharpness.
+    Vr,
+}
+
+impl RenderScalePreset {
+    pub const ALL: [Self; 4] = [Self::Balanced, Self::Performance, Self::Quality, Self::Vr];
+
+    /// Preset for a numeric id, in `ALL` order (0 = balanced, 3 = VR).
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Per-axis scale of the scene resolution.
+    pub fn factor(self) -> f32 {
+        match self {
+            Self::Quality => 0.85,
+            Self::Balanced => 0.75,
+            Self::Vr => 0.7,
+            Self::Performance => 0.6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderScaleStats {
+    pub enabled: bool,
+    pub preset: RenderScalePreset,
+    /// Whether the last frame actually rendered at `render_extent`.
+    pub applied: bool,
+    pub render_extent: vk::Extent2D,
+    pub output_extent: vk::Extent2D,
+    /// Fraction of scene pixels not shaded in the last frame (0 = none).
+    pub estimated_gpu_savings: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderScale {
+    pub enabled: bool,
+    pub preset: RenderScalePreset,
+    /// Set by the renderer each frame: the forward path with post-process and
+    /// without MSAA is the only one that can scale.
+    pub applied: bool,
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self { enabled: false, preset: RenderScalePreset::Balanced, applied: false }
+    }
+}
+
+impl RenderScale {
+    pub fn set_preset(&mut self, preset: RenderScalePreset) {
+        self.preset = preset;
+    }
+
+    /// Per-axis scale currently requested, 1.0 when disabled.
+    pub fn factor(&self) -> f32 {
+        if self.enabled { self.preset.factor() } else { 1.0 }
+    }
+
+    /// Scene resolution for an `output` of that size, never empty and never
+    /// larger than `output`.
+    pub fn render_extent(&self, output: vk::Extent2D) -> vk::Extent2D {
+        let scale = |size: u32| ((size as f32 * self.factor()).round() as u32).clamp(1.min(size), size);
+        vk::Extent2D { width: scale(output.width), height: scale(output.height) }
+    }
+
+    pub fn stats(&self, output: vk::Extent2D) -> RenderScaleStats {
+        let render_extent = if self.applied { self.render_extent(output) } else { output };
+        let output_pixels = output.width as u64 * output.height as u64;
+        let render_pixels = render_extent.width as u64 * render_extent.height as u64;
+        let estimated_gpu_savings = if output_pixels == 0 {
+            0.0
+        } else {
+            1.0 - render_pixels as f32 / output_pixels as f32
+        };
+        RenderScaleStats {
+            enabled: self.enabled,
+            preset: self.preset,
+            applied: self.applied,
+            render_extent,
+            output_extent: output,
+            estimated_gpu_savings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_HD: vk::Extent2D = vk::Extent2D { width: 1920, height: 1080 };
+
+    #[test]
+    fn presets_map_from_numeric_ids() {
+        assert_eq!(RenderScalePreset::from_index(0), Some(RenderScalePreset::Balanced));
+        assert_eq!(RenderScalePreset::from_index(1), Some(RenderScalePreset::Performance));
+        assert_eq!(RenderScalePreset::from_index(2), Some(RenderScalePreset::Quality));
+        assert_eq!(RenderScalePreset::from_index(3), Some(RenderScalePreset::Vr));
+        assert_eq!(RenderScalePreset::from_index(4), None);
+    }
+
+    #[test]
+    fn render_extent_follows_the_preset() {
+        let mut scale = RenderScale::default();
+        assert_eq!(scale.render_extent(FULL_HD), FULL_HD);
+
+        scale.enabled = true;
+        scale.set_preset(RenderScalePreset::Performance);
+        assert_eq!(scale.render_extent(FULL_HD), vk::Extent2D { width: 1152, height: 648 });
+        assert_eq!(scale.render_extent(vk::Extent2D { width: 1, height: 1 }), vk::Extent2D { width: 1, height: 1 });
+        assert_eq!(scale.render_extent(vk::Extent2D { width: 0, height: 0 }), vk::Extent2D { width: 0, height: 0 });
+    }
+
+    #[test]
+    fn savings_only_count_when_applied() {
+        let mut scale = RenderScale { enabled: true, preset: RenderScalePreset::Performance, applied: false };
+        assert_eq!(scale.stats(FULL_HD).estimated_gpu_savings, 0.0);
+        assert_eq!(scale.stats(FULL_HD).render_extent, FULL_HD);
+
+        scale.applied = true;
+        let stats = scale.stats(FULL_HD);
+        assert!((stats.estimated_gpu_savings - 0.64).abs() < 1e-3, "{}", stats.estimated_gpu_savings);
+        assert!(RenderScalePreset::ALL
+            .iter()
+            .all(|p| RenderScale { enabled: true, preset: *p, applied: true }.stats(FULL_HD).estimated_gpu_savings > 0.0));
+    }
+}
@@ -0,0 +1,209 @@
+// =============================================================================
+// REACTOR Validation Capture
+// =============================================================================
+// Messages from the VK_EXT_debug_utils messenger are kept in a bounded ring
+// buffer, tagged with the frame they were emitted in, so tools (an in-app
+// console, tests) can read them instead of scraping stderr. ERROR messages
+// also become the global last error.
+//
+// Whether the validation layer and messenger are set up is decided when the
+// VkInstance is created: call `set_validation_enabled` before
+// `VulkanContext::new` (the app runner does it from `ReactorConfig`).
+// =============================================================================
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::core::error::{set_last_error, ErrorCode, ReactorError};
+
+/// Messages kept before the oldest ones are dropped.
+pub const VALIDATION_LOG_CAPACITY: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValidationSeverity {
+    Verbose,
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationMessageKind {
+    General,
+    Validation,
+    Performance,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationMessage {
+    /// Value of the frame counter when the message arrived.
+    pub frame: u64,
+    pub severity: ValidationSeverity,
+    pub kind: ValidationMessageKind,
+    /// `messageIdNumber` from the layer (a hash of the VUID).
+    pub message_id: i32,
+    /// `pMessageIdName`, e.g. `VUID-vkCmdDraw-None-02699`.
+    pub message_id_name: String,
+    pub message: String,
+}
+
+/// Bounded FIFO of validation messages.
+#[derive(Debug)]
+pub struct ValidationLog {
+    messages: VecDeque<ValidationMessage>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl ValidationLog {
+    pub const fn new(capacity: usize) -> Self {
+        Self { messages: VecDeque::new(), capacity, dropped: 0 }
+    }
+
+    pub fn push(&mut self, message: ValidationMessage) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        while self.messages.len() >= self.capacity {
+            self.messages.pop_front();
+            self.dropped += 1;
+        }
+        self.messages.push_back(message);
+    }
+
+    /// Buffered messages, oldest first.
+    pub fn messages(&self) -> impl Iterator<Item = &ValidationMessage> {
+        self.messages.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Messages pushed out by newer ones since the last `clear`.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.dropped = 0;
+    }
+}
+
+static VALIDATION_ENABLED: AtomicBool = AtomicBool::new(cfg!(debug_assertions));
+static VALIDATION_FRAME: AtomicU64 = AtomicU64::new(0);
+static VALIDATION_LOG: Mutex<ValidationLog> = Mutex::new(ValidationLog::new(VALIDATION_LOG_CAPACITY));
+
+/// The log survives a thread panicking while holding it; the ring buffer is
+/// never left half-updated by `push` or `clear`.
+fn validation_log() -> MutexGuard<'static, ValidationLog> {
+    VALIDATION_LOG.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Request (or skip) `VK_LAYER_KHRONOS_validation` and the debug messenger
+/// for instances created from now on. Defaults to on in debug builds.
+pub fn set_validation_enabled(enabled: bool) {
+    VALIDATION_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn validation_enabled() -> bool {
+    VALIDATION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Advance the frame tag of captured messages. Called once per rendered frame.
+pub fn begin_validation_frame() {
+    VALIDATION_FRAME.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn validation_frame() -> u64 {
+    VALIDATION_FRAME.load(Ordering::Relaxed)
+}
+
+/// Captured messages, oldest first.
+pub fn validation_messages() -> Vec<ValidationMessage> {
+    validation_log().messages().cloned().collect()
+}
+
+/// Messages captured during `frame`.
+pub fn validation_messages_for_frame(frame: u64) -> Vec<ValidationMessage> {
+    validation_log().messages().filter(|m| m.frame == frame).cloned().collect()
+}
+
+/// Messages lost because the ring buffer was full.
+pub fn dropped_validation_messages() -> u64 {
+    validation_log().dropped()
+}
+
+pub fn clear_validation_messages() {
+    validation_log().clear();
+}
+
+/// Store a message from the messenger callback.
+pub(crate) fn record_validation_message(
+    severity: ValidationSeverity,
+    kind: ValidationMessageKind,
+    message_id: i32,
+    message_id_name: String,
+    message: String,
+) {
+    if severity == ValidationSeverity::Error {
+        set_last_error(ReactorError::new(ErrorCode::VulkanValidation, message.clone()));
+    }
+    let message = ValidationMessage { frame: validation_frame(), severity, kind, message_id, message_id_name, message };
+    validation_log().push(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(frame: u64, text: &str) -> ValidationMessage {
+        ValidationMessage {
+            frame,
+            severity: ValidationSeverity::Warning,
+            kind: ValidationMessageKind::Validation,
+            message_id: 0,
+            message_id_name: String::new(),
+            message: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest_messages() {
+        let mut log = ValidationLog::new(2);
+        log.push(message(1, "a"));
+        log.push(message(1, "b"));
+        log.push(message(2, "c"));
+
+        let texts: Vec<_> = log.messages().map(|m| m.message.as_str()).collect();
+        assert_eq!(texts, ["b", "c"]);
+        assert_eq!(log.dropped(), 1);
+
+        log.clear();
+        assert!(log.is_empty());
+        assert_eq!(log.dropped(), 0);
+    }
+
+    #[test]
+    fn captured_messages_carry_the_frame() {
+        // Warnings only: an error would race with the last-error tests.
+        let frame = validation_frame();
+        record_validation_message(
+            ValidationSeverity::Warning,
+            ValidationMessageKind::Performance,
+            42,
+            "VUID-test".into(),
+            "slow path".into(),
+        );
+        assert!(validation_messages_for_frame(frame)
+            .iter()
+            .any(|m| m.message_id == 42 && m.message_id_name == "VUID-test"));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::core::allocator::{track_allocation, track_free, AllocationKind};
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use ash::vk;
@@ -21,12 +23,37 @@ impl Buffer {
         size: u64,
         usage: vk::BufferUsageFlags,
         location: MemoryLocation,
+    ) -> ReactorResult<Self> {
+        Self::new_shared(ctx, allocator, size, usage, location, &[])
+    }
+
+    /// Like `new`, but usable from every family in `queue_families` without
+    /// ownership transfers (`CONCURRENT` sharing). Fewer than two distinct
+    /// families fall back to `EXCLUSIVE`.
+    pub fn new_shared(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        size: u64,
+        usage: vk::BufferUsageFlags,
+        location: MemoryLocation,
+        queue_families: &[u32],
     ) -> ReactorResult<Self> {
         let device = ctx.ash_device();
-        let create_info = vk::BufferCreateInfo::default()
-            .size(size)
-            .usage(usage)
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let mut families = queue_families.to_vec();
+        families.sort_unstable();
+        families.dedup();
+        let create_info = if families.len() > 1 {
+            vk::BufferCreateInfo::default()
+                .size(size)
+                .usage(usage)
+                .sharing_mode(vk::SharingMode::CONCURRENT)
+                .queue_family_indices(&families)
+        } else {
+            vk::BufferCreateInfo::default()
+                .size(size)
+                .usage(usage)
+                .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        };
 
         let handle = unsafe {
             device.create_buffer(&create_info, None).map_err(|e| {
@@ -56,6 +83,7 @@ impl Buffer {
                     e,
                 )
             })?;
+        track_allocation(AllocationKind::Buffer, &allocation);
 
         unsafe {
             device
@@ -163,6 +191,23 @@ impl Buffer {
         }
     }
 
+    /// Copies `out.len()` elements out of the mapped memory. Returns `false`
+    /// when the buffer is not host-visible or is smaller than `out`.
+    pub fn read<T: Copy>(&self, out: &mut [T]) -> bool {
+        if std::mem::size_of_val(out) as u64 > self.size {
+            return false;
+        }
+        match self.map::<T>() {
+            Some(ptr) => {
+                unsafe {
+                    out.as_mut_ptr().copy_from_nonoverlapping(ptr, out.len());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn write_slice(&self, data: &[u8]) {
         if let Some(ptr) = self.map::<u8>() {
             unsafe {
@@ -171,23 +216,99 @@ impl Buffer {
         }
     }
 
+    /// GPU address of the buffer. Needs `SHADER_DEVICE_ADDRESS` usage and the
+    /// `bufferDeviceAddress` feature (enabled with ray tracing).
+    pub fn device_address(&self) -> vk::DeviceAddress {
+        let info = vk::BufferDeviceAddressInfo::default().buffer(self.handle);
+        unsafe { self.device.get_buffer_device_address(&info) }
+    }
+
+    /// Destroys the buffer now. The caller guarantees the GPU is done with
+    /// it; dropping instead waits for the frames in flight.
     pub fn destroy(&mut self) {
         if self.handle != vk::Buffer::null() {
+            self.device.check_not_in_flight(self.handle, "buffer");
             unsafe {
                 self.device.destroy_buffer(self.handle, None);
             }
             if let Some(allocation) = self.allocation.take() {
+                track_free(AllocationKind::Buffer, &allocation);
                 if let Err(e) = self.allocator.lock().unwrap().free(allocation) {
-                    eprintln!("Failed to free buffer memory: {:?}", e);
+                    log::error!("Failed to free buffer memory: {:?}", e);
                 }
             }
             self.handle = vk::Buffer::null();
         }
     }
+
+    /// Label the buffer for RenderDoc / validation messages. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_buffer(self.handle, name);
+    }
+}
+
+/// One host-visible buffer per frame in flight, grown on demand to the next
+/// power of two. Meant for data rewritten every frame (instances, overlay
+/// vertices): the caller has already waited on the frame's fence, so its
+/// buffer is free to overwrite or replace.
+pub(crate) struct PerFrameBuffer {
+    usage: vk::BufferUsageFlags,
+    buffers: Vec<Option<Buffer>>,
+}
+
+impl PerFrameBuffer {
+    pub(crate) fn new(usage: vk::BufferUsageFlags) -> Self {
+        Self { usage, buffers: Vec::new() }
+    }
+
+    /// Buffer of `frame` holding at least `bytes`, created or replaced when
+    /// it is missing or too small. A replaced buffer is dropped, which defers
+    /// its destruction past the frames still in flight.
+    pub(crate) fn ensure(
+        &mut self,
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        frame: usize,
+        bytes: u64,
+    ) -> ReactorResult<&Buffer> {
+        if self.buffers.len() <= frame {
+            self.buffers.resize_with(frame + 1, || None);
+        }
+        let slot = &mut self.buffers[frame];
+        if slot.as_ref().map_or(true, |buffer| buffer.size < bytes) {
+            *slot = Some(Buffer::new(ctx, allocator, bytes.next_power_of_two(), self.usage, MemoryLocation::CpuToGpu)?);
+        }
+        Ok(slot.as_ref().unwrap())
+    }
+
+    /// Buffer of `frame`, if `ensure` created one.
+    pub(crate) fn get(&self, frame: usize) -> Option<&Buffer> {
+        self.buffers.get(frame)?.as_ref()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.buffers.clear();
+    }
 }
 
 impl Drop for Buffer {
     fn drop(&mut self) {
-        self.destroy();
+        if self.handle == vk::Buffer::null() {
+            return;
+        }
+        let handle = std::mem::replace(&mut self.handle, vk::Buffer::null());
+        let allocation = self.allocation.take();
+        let allocator = self.allocator.clone();
+        self.device.destroy_deferred(move |device| {
+            unsafe {
+                device.destroy_buffer(handle, None);
+            }
+            if let Some(allocation) = allocation {
+                track_free(AllocationKind::Buffer, &allocation);
+                if let Err(e) = allocator.lock().unwrap().free(allocation) {
+                    log::error!("Failed to free buffer memory: {:?}", e);
+                }
+            }
+        });
     }
 }
@@ -269,6 +269,37 @@ impl DebugRenderer {
         }
     }
 
+    /// Ground grid (y = 0) that looks infinite from an orbit camera: the
+    /// cell size follows `distance` in powers of ten, and the finer of the
+    /// two levels fades out as the camera pulls back, so lines cross-fade
+    /// instead of popping. Both levels are centered on `focus`.
+    pub fn adaptive_grid(&mut self, focus: Vec3, distance: f32, color: Vec4) {
+        const HALF_CELLS: i32 = 50;
+        let (cell, fade) = grid_lod(distance);
+        for (step, alpha, skip_every) in [(cell, 1.0 - fade, 10), (cell * 10.0, 1.0, 0)] {
+            if alpha <= 0.0 {
+                continue;
+            }
+            let color = Vec4::new(color.x, color.y, color.z, color.w * alpha);
+            let origin_x = (focus.x / step).round() as i32;
+            let origin_z = (focus.z / step).round() as i32;
+            let half = HALF_CELLS as f32 * step;
+            let (center_x, center_z) = (origin_x as f32 * step, origin_z as f32 * step);
+            // The coarse level already draws every tenth fine line.
+            let covered = |index: i32| skip_every > 0 && index.rem_euclid(skip_every) == 0;
+            for i in -HALF_CELLS..=HALF_CELLS {
+                if !covered(origin_z + i) {
+                    let z = center_z + i as f32 * step;
+                    self.line(Vec3::new(center_x - half, 0.0, z), Vec3::new(center_x + half, 0.0, z), color);
+                }
+                if !covered(origin_x + i) {
+                    let x = center_x + i as f32 * step;
+                    self.line(Vec3::new(x, 0.0, center_z - half), Vec3::new(x, 0.0, center_z + half), color);
+                }
+            }
+        }
+    }
+
     pub fn frustum(&mut self, inv_view_proj: Mat4, color: Vec4) {
         // NDC corners
         let ndc_corners = [
@@ -320,3 +351,29 @@ impl Default for DebugRenderer {
         Self::new()
     }
 }
+
+/// Cell size (a power of ten, about a tenth of `distance`) and how far the
+/// camera is towards the next coarser level, in `[0, 1)`.
+fn grid_lod(distance: f32) -> (f32, f32) {
+    let level = (distance.max(1e-3) / 10.0).log10();
+    (10f32.powf(level.floor()), level - level.floor())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_lod_is_continuous_across_levels() {
+        let (cell, fade) = grid_lod(10.0);
+        assert!((cell - 1.0).abs() < 1e-5 && fade.abs() < 1e-4);
+        let (cell, fade) = grid_lod(500.0);
+        assert!((cell - 10.0).abs() < 1e-4 && (fade - 0.699).abs() < 1e-3);
+
+        // Just below a level the fine lines are gone, so switching to the
+        // coarser cell changes nothing on screen.
+        let (below, fade) = grid_lod(99.99);
+        assert!(fade > 0.999);
+        assert!((grid_lod(100.0).0 - below * 10.0).abs() < 1e-3);
+    }
+}
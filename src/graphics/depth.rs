@@ -1,3 +1,4 @@
+use crate::core::allocator::{track_allocation, track_free, AllocationKind};
 use crate::core::error::ReactorResult;
 use crate::core::VulkanContext;
 use ash::vk;
@@ -46,6 +47,7 @@ impl DepthBuffer {
             linear: false,
             allocation_scheme: AllocationScheme::GpuAllocatorManaged,
         })?;
+        track_allocation(AllocationKind::Image, &allocation);
 
         unsafe {
             device.bind_image_memory(image, allocation.memory(), allocation.offset())?;
@@ -115,8 +117,9 @@ impl Drop for DepthBuffer {
             self.device.destroy_image(self.image, None);
         }
         if let Some(allocation) = self.allocation.take() {
+            track_free(AllocationKind::Image, &allocation);
             if let Err(e) = self.allocator.lock().unwrap().free(allocation) {
-                eprintln!("Failed to free depth buffer memory: {:?}", e);
+                log::error!("Failed to free depth buffer memory: {:?}", e);
             }
         }
     }
@@ -1,6 +1,9 @@
-use crate::core::error::ReactorResult;
+use crate::core::arc_handle::ArcDevice;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use ash::vk;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // Descriptor Set Layout
@@ -231,3 +234,360 @@ impl DescriptorSet {
         }
     }
 }
+
+// ============================================================================
+// Descriptor Allocator
+// ============================================================================
+
+/// Descriptors of one type a pool reserves per set it can hold.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolRatio {
+    pub descriptor_type: vk::DescriptorType,
+    pub per_set: u32,
+}
+
+/// Same mix as `DescriptorPool::standard`, plus storage images for compute.
+pub const DEFAULT_POOL_RATIOS: [PoolRatio; 4] = [
+    PoolRatio { descriptor_type: vk::DescriptorType::UNIFORM_BUFFER, per_set: 2 },
+    PoolRatio { descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER, per_set: 4 },
+    PoolRatio { descriptor_type: vk::DescriptorType::STORAGE_BUFFER, per_set: 2 },
+    PoolRatio { descriptor_type: vk::DescriptorType::STORAGE_IMAGE, per_set: 1 },
+];
+
+/// Sets in the first pool; each new pool doubles the previous one up to
+/// `MAX_POOL_SETS`.
+pub const FIRST_POOL_SETS: u32 = 64;
+pub const MAX_POOL_SETS: u32 = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PoolUsage {
+    capacity: u32,
+    live: u32,
+    /// The driver refused an allocation (out of memory or fragmented).
+    exhausted: bool,
+}
+
+/// Which persistent pool to allocate from, kept apart from the Vulkan calls.
+#[derive(Debug, Default)]
+struct PoolBook {
+    pools: Vec<PoolUsage>,
+}
+
+impl PoolBook {
+    /// First pool with room that has not refused an allocation.
+    fn candidate(&self) -> Option<usize> {
+        self.pools.iter().position(|p| !p.exhausted && p.live < p.capacity)
+    }
+
+    fn next_capacity(&self) -> u32 {
+        self.pools.last().map_or(FIRST_POOL_SETS, |p| (p.capacity * 2).min(MAX_POOL_SETS))
+    }
+
+    fn push(&mut self, capacity: u32) -> usize {
+        self.pools.push(PoolUsage { capacity, live: 0, exhausted: false });
+        self.pools.len() - 1
+    }
+
+    fn allocated(&mut self, index: usize) {
+        self.pools[index].live += 1;
+    }
+
+    /// Freeing a set gives its descriptors back, so the pool is worth trying again.
+    fn freed(&mut self, index: usize) {
+        let pool = &mut self.pools[index];
+        pool.live = pool.live.saturating_sub(1);
+        pool.exhausted = false;
+    }
+
+    fn mark_exhausted(&mut self, index: usize) {
+        self.pools[index].exhausted = true;
+    }
+
+    fn live(&self) -> u32 {
+        self.pools.iter().map(|p| p.live).sum()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct LayoutKey {
+    binding: u32,
+    descriptor_type: vk::DescriptorType,
+    count: u32,
+    stage_flags: vk::ShaderStageFlags,
+}
+
+#[derive(Default)]
+struct TransientPools {
+    pools: Vec<vk::DescriptorPool>,
+    /// Pools handed out since the last reset; the last one is being filled.
+    in_use: usize,
+}
+
+/// A set from the persistent pools, handed back when dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DescriptorAllocation {
+    pub set: vk::DescriptorSet,
+    pool: usize,
+}
+
+/// Grows descriptor pools on demand and hands out sets by layout.
+///
+/// Long-lived sets (materials) come from pools created with
+/// `FREE_DESCRIPTOR_SET`, so freeing a set makes room for the next one and
+/// creating and destroying materials does not grow the pool list. Per-frame
+/// sets come from separate pools that are reset in one call once the frame's
+/// fence has signaled. Layouts are cached by their bindings.
+///
+/// Shared as `Arc<Mutex<DescriptorAllocator>>`, the same way as the memory
+/// allocator. It keeps the device alive until every set it handed out is gone.
+pub struct DescriptorAllocator {
+    device: ArcDevice,
+    ratios: Vec<PoolRatio>,
+    pools: Vec<vk::DescriptorPool>,
+    book: PoolBook,
+    transient: Vec<TransientPools>,
+    layouts: HashMap<Vec<LayoutKey>, vk::DescriptorSetLayout>,
+}
+
+impl DescriptorAllocator {
+    pub fn new(ctx: &VulkanContext, frames: usize) -> Self {
+        Self::with_ratios(ctx, frames, &DEFAULT_POOL_RATIOS)
+    }
+
+    pub fn with_ratios(ctx: &VulkanContext, frames: usize, ratios: &[PoolRatio]) -> Self {
+        Self {
+            device: ctx.device.clone(),
+            ratios: ratios.to_vec(),
+            pools: Vec::new(),
+            book: PoolBook::default(),
+            transient: (0..frames).map(|_| TransientPools::default()).collect(),
+            layouts: HashMap::new(),
+        }
+    }
+
+    /// Layout for `bindings`, created on first use and shared afterwards.
+    /// Owned by the allocator: do not destroy it.
+    pub fn layout(&mut self, bindings: &[DescriptorBinding]) -> ReactorResult<vk::DescriptorSetLayout> {
+        let key: Vec<LayoutKey> = bindings
+            .iter()
+            .map(|b| LayoutKey { binding: b.binding, descriptor_type: b.descriptor_type, count: b.count, stage_flags: b.stage_flags })
+            .collect();
+        if let Some(&layout) = self.layouts.get(&key) {
+            return Ok(layout);
+        }
+
+        let vk_bindings: Vec<vk::DescriptorSetLayoutBinding> = bindings
+            .iter()
+            .map(|b| {
+                vk::DescriptorSetLayoutBinding::default()
+                    .binding(b.binding)
+                    .descriptor_type(b.descriptor_type)
+                    .descriptor_count(b.count)
+                    .stage_flags(b.stage_flags)
+            })
+            .collect();
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(&vk_bindings)
+            .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL);
+        let layout = unsafe { self.device.create_descriptor_set_layout(&layout_info, None) }.map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "create_descriptor_set_layout failed", e)
+        })?;
+        self.layouts.insert(key, layout);
+        Ok(layout)
+    }
+
+    /// Allocate a long-lived set. Give it back with `free`.
+    pub fn allocate(&mut self, layout: vk::DescriptorSetLayout) -> ReactorResult<DescriptorAllocation> {
+        while let Some(pool) = self.book.candidate() {
+            match self.try_allocate(self.pools[pool], layout) {
+                Ok(set) => {
+                    self.book.allocated(pool);
+                    return Ok(DescriptorAllocation { set, pool });
+                }
+                Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY) | Err(vk::Result::ERROR_FRAGMENTED_POOL) => {
+                    self.book.mark_exhausted(pool);
+                }
+                Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "allocate_descriptor_sets failed", e)),
+            }
+        }
+
+        let capacity = self.book.next_capacity();
+        let handle = self.create_pool(capacity, true)?;
+        self.pools.push(handle);
+        let pool = self.book.push(capacity);
+        let set = self.try_allocate(handle, layout).map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "allocate_descriptor_sets failed on a fresh pool", e)
+        })?;
+        self.book.allocated(pool);
+        Ok(DescriptorAllocation { set, pool })
+    }
+
+    /// Return a set from `allocate`. The GPU must be done with it.
+    pub fn free(&mut self, allocation: DescriptorAllocation) {
+        let Some(&pool) = self.pools.get(allocation.pool) else { return };
+        unsafe {
+            let _ = self.device.free_descriptor_sets(pool, &[allocation.set]);
+        }
+        self.book.freed(allocation.pool);
+    }
+
+    /// Allocate a set that only lives until `reset_transient(frame)`.
+    pub fn allocate_transient(&mut self, frame: usize, layout: vk::DescriptorSetLayout) -> ReactorResult<vk::DescriptorSet> {
+        if self.transient.len() <= frame {
+            self.transient.resize_with(frame + 1, TransientPools::default);
+        }
+        if self.transient[frame].in_use > 0 {
+            let pool = self.transient[frame].pools[self.transient[frame].in_use - 1];
+            match self.try_allocate(pool, layout) {
+                Ok(set) => return Ok(set),
+                Err(vk::Result::ERROR_OUT_OF_POOL_MEMORY) | Err(vk::Result::ERROR_FRAGMENTED_POOL) => {}
+                Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "allocate_descriptor_sets failed", e)),
+            }
+        }
+
+        // The current pool is full: move on to a reset one or create another.
+        if self.transient[frame].in_use == self.transient[frame].pools.len() {
+            let pool = self.create_pool(FIRST_POOL_SETS, false)?;
+            self.transient[frame].pools.push(pool);
+        }
+        let pool = self.transient[frame].pools[self.transient[frame].in_use];
+        self.transient[frame].in_use += 1;
+        self.try_allocate(pool, layout).map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "allocate_descriptor_sets failed on a fresh pool", e)
+        })
+    }
+
+    /// Recycle every transient set of `frame`. Call after its fence has signaled.
+    pub fn reset_transient(&mut self, frame: usize) {
+        let Some(transient) = self.transient.get_mut(frame) else { return };
+        for &pool in &transient.pools[..transient.in_use] {
+            unsafe {
+                let _ = self.device.reset_descriptor_pool(pool, vk::DescriptorPoolResetFlags::empty());
+            }
+        }
+        transient.in_use = 0;
+    }
+
+    /// Persistent pools created so far.
+    pub fn pool_count(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Long-lived sets currently handed out.
+    pub fn live_sets(&self) -> u32 {
+        self.book.live()
+    }
+
+    pub fn transient_pool_count(&self) -> usize {
+        self.transient.iter().map(|t| t.pools.len()).sum()
+    }
+
+    fn try_allocate(&self, pool: vk::DescriptorPool, layout: vk::DescriptorSetLayout) -> Result<vk::DescriptorSet, vk::Result> {
+        let layouts = [layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(pool)
+            .set_layouts(&layouts);
+        unsafe { self.device.allocate_descriptor_sets(&alloc_info) }.map(|sets| sets[0])
+    }
+
+    fn create_pool(&self, max_sets: u32, freeable: bool) -> ReactorResult<vk::DescriptorPool> {
+        let sizes: Vec<vk::DescriptorPoolSize> = self
+            .ratios
+            .iter()
+            .map(|r| vk::DescriptorPoolSize::default().ty(r.descriptor_type).descriptor_count(r.per_set * max_sets))
+            .collect();
+        let mut flags = vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND;
+        if freeable {
+            flags |= vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET;
+        }
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&sizes)
+            .max_sets(max_sets)
+            .flags(flags);
+        unsafe { self.device.create_descriptor_pool(&pool_info, None) }.map_err(|e| {
+            ReactorError::with_source(ErrorCode::VulkanDescriptorSet, "create_descriptor_pool failed", e)
+        })
+    }
+}
+
+impl Drop for DescriptorAllocator {
+    fn drop(&mut self) {
+        unsafe {
+            for &pool in self.pools.iter().chain(self.transient.iter().flat_map(|t| &t.pools)) {
+                self.device.destroy_descriptor_pool(pool, None);
+            }
+            for &layout in self.layouts.values() {
+                self.device.destroy_descriptor_set_layout(layout, None);
+            }
+        }
+    }
+}
+
+/// A long-lived set that returns itself to its `DescriptorAllocator` on drop.
+pub struct PooledDescriptorSet {
+    allocation: DescriptorAllocation,
+    allocator: Arc<Mutex<DescriptorAllocator>>,
+}
+
+impl PooledDescriptorSet {
+    pub fn new(allocator: &Arc<Mutex<DescriptorAllocator>>, layout: vk::DescriptorSetLayout) -> ReactorResult<Self> {
+        let allocation = allocator.lock().unwrap().allocate(layout)?;
+        Ok(Self { allocation, allocator: allocator.clone() })
+    }
+
+    pub fn handle(&self) -> vk::DescriptorSet {
+        self.allocation.set
+    }
+}
+
+impl Drop for PooledDescriptorSet {
+    fn drop(&mut self) {
+        if let Ok(mut allocator) = self.allocator.lock() {
+            allocator.free(self.allocation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pools_double_up_to_the_cap() {
+        let mut book = PoolBook::default();
+        assert_eq!(book.next_capacity(), FIRST_POOL_SETS);
+        book.push(FIRST_POOL_SETS);
+        assert_eq!(book.next_capacity(), FIRST_POOL_SETS * 2);
+        book.push(MAX_POOL_SETS);
+        assert_eq!(book.next_capacity(), MAX_POOL_SETS);
+    }
+
+    #[test]
+    fn freed_sets_are_reused_before_growing() {
+        let mut book = PoolBook::default();
+        let pool = book.push(2);
+        // Create and destroy far more sets than the pool holds.
+        for _ in 0..5000 {
+            assert_eq!(book.candidate(), Some(pool));
+            book.allocated(pool);
+            book.freed(pool);
+        }
+        assert_eq!(book.pools.len(), 1);
+        assert_eq!(book.live(), 0);
+    }
+
+    #[test]
+    fn exhausted_pools_are_skipped_until_a_set_is_freed() {
+        let mut book = PoolBook::default();
+        let first = book.push(4);
+        book.allocated(first);
+        book.mark_exhausted(first);
+        assert_eq!(book.candidate(), None);
+
+        let second = book.push(book.next_capacity());
+        assert_eq!(book.candidate(), Some(second));
+
+        book.freed(first);
+        assert_eq!(book.candidate(), Some(first));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::ReactorResult;
 use crate::core::VulkanContext;
 use ash::vk;
@@ -49,6 +50,11 @@ impl Framebuffer {
         }
         Self::new(ctx, render_pass, &attachments, width, height)
     }
+
+    /// Label the framebuffer. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_framebuffer(self.handle, name);
+    }
 }
 
 impl Drop for Framebuffer {
@@ -5,10 +5,12 @@ use crate::graphics::ibl::create::{create_2d_lut, create_cubemap};
 use crate::graphics::ibl::helpers::{
     allocate_set, begin_one_shot, combined_image_sampler_b, create_bake_descriptor_pool,
     create_cubemap_sampler, create_final_descriptor_layout, create_final_descriptor_pool,
-    create_one_shot_command_pool, create_2d_sampler, end_and_submit, storage_image_b,
-    transition_cube, transition_2d, update_set_combined, update_set_storage_image,
-    update_set_uniform_buffer,
+    create_one_shot_command_pool, create_2d_sampler, end_and_submit, end_and_submit_fenced,
+    storage_image_b, transition_cube, transition_2d, update_set_combined, update_set_storage_image,
+    update_set_uniform_buffer, IBL_PARAMS_SIZE,
 };
+use crate::graphics::ibl::image::IblImage;
+use crate::graphics::ibl::pending::PendingIbl;
 use crate::graphics::ibl::sky::{load_hdr_equirect, procedural_studio_sky};
 use crate::graphics::ibl::textures::IblTextures;
 use crate::graphics::ibl::upload::upload_equirect_hdr;
@@ -16,6 +18,7 @@ use crate::graphics::ibl::{
     IBL_BRDF_LUT_SIZE, IBL_IRRADIANCE_SIZE, IBL_PREFILTER_MIPS, IBL_PREFILTER_SIZE,
     IBL_RADIANCE_SIZE, SPV_BRDF_LUT, SPV_EQUIRECT_TO_CUBE, SPV_IRRADIANCE, SPV_PREFILTER,
 };
+use crate::resources::texture::Cubemap;
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use std::path::Path;
@@ -44,26 +47,15 @@ impl IblBaker {
         let equirect_img = upload_equirect_hdr(ctx, allocator.clone(), pool, pixels_rgba_f16, width, height)?;
         let radiance = create_cubemap(ctx, allocator.clone(), IBL_RADIANCE_SIZE, 1,
             vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC)?;
-        let irradiance = create_cubemap(ctx, allocator.clone(), IBL_IRRADIANCE_SIZE, 1,
-            vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)?;
-        let prefiltered = create_cubemap(ctx, allocator.clone(), IBL_PREFILTER_SIZE, IBL_PREFILTER_MIPS,
-            vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)?;
-        let brdf_lut = create_2d_lut(ctx, allocator.clone(), IBL_BRDF_LUT_SIZE,
-            vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)?;
+        let (irradiance, prefiltered) = create_filter_targets(ctx, allocator.clone())?;
+        let brdf_lut = create_brdf_lut(ctx, allocator.clone())?;
         let sampler_cube = create_cubemap_sampler(ctx, IBL_PREFILTER_MIPS as f32)?;
         let sampler_2d = create_2d_sampler(ctx)?;
         let p_equirect = ComputePass::new(ctx, SPV_EQUIRECT_TO_CUBE,
             &[combined_image_sampler_b(0), storage_image_b(1)],
             std::mem::size_of::<EquirectPC>() as u32)?;
-        let p_irradiance = ComputePass::new(ctx, SPV_IRRADIANCE,
-            &[combined_image_sampler_b(0), storage_image_b(1)],
-            std::mem::size_of::<IrradiancePC>() as u32)?;
-        let p_prefilter = ComputePass::new(ctx, SPV_PREFILTER,
-            &[combined_image_sampler_b(0), storage_image_b(1)],
-            std::mem::size_of::<PrefilterPC>() as u32)?;
-        let p_brdf = ComputePass::new(ctx, SPV_BRDF_LUT,
-            &[storage_image_b(0)],
-            std::mem::size_of::<BrdfLutPC>() as u32)?;
+        let filter = FilterPasses::new(ctx)?;
+        let p_brdf = brdf_lut_pass(ctx)?;
         let bake_desc_pool = create_bake_descriptor_pool(ctx, IBL_PREFILTER_MIPS + 3)?;
         let cmd = begin_one_shot(ctx, pool)?;
         transition_cube(ctx, cmd, radiance.image, radiance.mip_levels, radiance.layer_count,
@@ -82,15 +74,149 @@ impl IblBaker {
             vk::ImageLayout::GENERAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
             vk::AccessFlags::SHADER_WRITE, vk::AccessFlags::SHADER_READ,
             vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::COMPUTE_SHADER);
+        filter.record(ctx, cmd, bake_desc_pool, (radiance.view, sampler_cube, IBL_RADIANCE_SIZE), &irradiance, &prefiltered)?;
+        record_brdf_lut(ctx, cmd, bake_desc_pool, &p_brdf, &brdf_lut)?;
+        end_and_submit(ctx, pool, cmd)?;
+        unsafe {
+            device.destroy_descriptor_pool(bake_desc_pool, None);
+            device.destroy_command_pool(pool, None);
+        }
+        drop(radiance);
+        drop(equirect_img);
+        finish_textures(ctx, allocator, irradiance, prefiltered, Arc::new(brdf_lut), sampler_cube, sampler_2d, 1.0)
+    }
+
+    pub fn bake_procedural(
+        ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>,
+    ) -> ReactorResult<IblTextures> {
+        let (pixels, w, h) = procedural_studio_sky(1024, 512);
+        Self::bake_from_equirect_pixels(ctx, allocator, &pixels, w, h)
+    }
+
+    /// Filtra `source` (irradiance + prefiltered specular) y espera a la GPU.
+    pub fn bake_from_cubemap(
+        ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>, source: Arc<Cubemap>, intensity: f32,
+    ) -> ReactorResult<IblTextures> {
+        Self::begin_bake_from_cubemap(ctx, allocator, source, None, intensity)?.wait()
+    }
+
+    /// Graba y envía el filtrado de `source` sin esperar: el `PendingIbl`
+    /// devuelto se consulta cada frame hasta que la GPU termina. Con
+    /// `brdf_lut` reutiliza la LUT de un bake anterior en vez de recalcularla.
+    pub fn begin_bake_from_cubemap(
+        ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>, source: Arc<Cubemap>,
+        brdf_lut: Option<Arc<IblImage>>, intensity: f32,
+    ) -> ReactorResult<PendingIbl> {
+        let mut pending = PendingIbl::new(ctx, source.clone())?;
+        let (irradiance, prefiltered) = create_filter_targets(ctx, allocator.clone())?;
+        let filter = FilterPasses::new(ctx)?;
+        let cmd = begin_one_shot(ctx, pending.command_pool)?;
+        filter.record(ctx, cmd, pending.descriptor_pool, (source.view(), source.sampler_handle(), source.size), &irradiance, &prefiltered)?;
+        pending.passes.extend(filter.into_passes());
+
+        let brdf_lut = match brdf_lut {
+            Some(lut) => lut,
+            None => {
+                let lut = create_brdf_lut(ctx, allocator.clone())?;
+                let pass = brdf_lut_pass(ctx)?;
+                record_brdf_lut(ctx, cmd, pending.descriptor_pool, &pass, &lut)?;
+                pending.passes.push(pass);
+                Arc::new(lut)
+            }
+        };
+
+        let sampler_cube = create_cubemap_sampler(ctx, IBL_PREFILTER_MIPS as f32)?;
+        let sampler_2d = create_2d_sampler(ctx)?;
+        pending.textures = Some(finish_textures(ctx, allocator, irradiance, prefiltered, brdf_lut, sampler_cube, sampler_2d, intensity)?);
+        end_and_submit_fenced(ctx, cmd, pending.fence)?;
+        pending.submitted = true;
+        Ok(pending)
+    }
+
+    /// Entorno blanco uniforme de 1×1 por cara: con él `intensity` actúa
+    /// como luz ambiente escalar cuando no hay cubemap de entorno.
+    pub fn uniform(
+        ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>, intensity: f32,
+    ) -> ReactorResult<(IblTextures, Arc<IblImage>)> {
+        let device = ctx.ash_device();
+        let usage = vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED;
+        let irradiance = create_cubemap(ctx, allocator.clone(), 1, 1, usage)?;
+        let prefiltered = create_cubemap(ctx, allocator.clone(), 1, 1, usage)?;
+        let brdf_lut = create_brdf_lut(ctx, allocator.clone())?;
+        let p_brdf = brdf_lut_pass(ctx)?;
+        let pool = create_one_shot_command_pool(ctx)?;
+        let bake_desc_pool = create_bake_descriptor_pool(ctx, 1)?;
+        let cmd = begin_one_shot(ctx, pool)?;
+        for cube in [&irradiance, &prefiltered] {
+            transition_cube(ctx, cmd, cube.image, cube.mip_levels, cube.layer_count,
+                vk::ImageLayout::UNDEFINED, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::empty(), vk::AccessFlags::TRANSFER_WRITE,
+                vk::PipelineStageFlags::TOP_OF_PIPE, vk::PipelineStageFlags::TRANSFER);
+            let range = vk::ImageSubresourceRange::default()
+                .aspect_mask(vk::ImageAspectFlags::COLOR)
+                .base_mip_level(0).level_count(1)
+                .base_array_layer(0).layer_count(cube.layer_count);
+            unsafe {
+                device.cmd_clear_color_image(cmd, cube.image, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    &vk::ClearColorValue { float32: [1.0; 4] }, &[range]);
+            }
+            transition_cube(ctx, cmd, cube.image, cube.mip_levels, cube.layer_count,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE, vk::AccessFlags::SHADER_READ,
+                vk::PipelineStageFlags::TRANSFER, vk::PipelineStageFlags::FRAGMENT_SHADER);
+        }
+        record_brdf_lut(ctx, cmd, bake_desc_pool, &p_brdf, &brdf_lut)?;
+        end_and_submit(ctx, pool, cmd)?;
+        unsafe {
+            device.destroy_descriptor_pool(bake_desc_pool, None);
+            device.destroy_command_pool(pool, None);
+        }
+        let brdf_lut = Arc::new(brdf_lut);
+        let sampler_cube = create_cubemap_sampler(ctx, 0.0)?;
+        let sampler_2d = create_2d_sampler(ctx)?;
+        let textures = finish_textures(ctx, allocator, irradiance, prefiltered, brdf_lut.clone(), sampler_cube, sampler_2d, intensity)?;
+        Ok((textures, brdf_lut))
+    }
+}
+
+/// Pipelines de irradiance y prefiltered specular.
+struct FilterPasses {
+    irradiance: ComputePass,
+    prefilter: ComputePass,
+}
+
+impl FilterPasses {
+    fn new(ctx: &VulkanContext) -> ReactorResult<Self> {
+        let irradiance = ComputePass::new(ctx, SPV_IRRADIANCE,
+            &[combined_image_sampler_b(0), storage_image_b(1)],
+            std::mem::size_of::<IrradiancePC>() as u32)?;
+        let prefilter = ComputePass::new(ctx, SPV_PREFILTER,
+            &[combined_image_sampler_b(0), storage_image_b(1)],
+            std::mem::size_of::<PrefilterPC>() as u32)?;
+        Ok(Self { irradiance, prefilter })
+    }
+
+    fn into_passes(self) -> [ComputePass; 2] {
+        [self.irradiance, self.prefilter]
+    }
+
+    /// Graba irradiance y los mips del prefiltered desde `source` (vista
+    /// cube, sampler y tamaño de cara, ya en `SHADER_READ_ONLY_OPTIMAL`) y
+    /// deja ambos destinos listos para el fragment shader.
+    fn record(
+        &self, ctx: &VulkanContext, cmd: vk::CommandBuffer, desc_pool: vk::DescriptorPool,
+        source: (vk::ImageView, vk::Sampler, u32), irradiance: &IblImage, prefiltered: &IblImage,
+    ) -> ReactorResult<()> {
+        let (source_view, source_sampler, source_size) = source;
         transition_cube(ctx, cmd, irradiance.image, irradiance.mip_levels, irradiance.layer_count,
             vk::ImageLayout::UNDEFINED, vk::ImageLayout::GENERAL,
             vk::AccessFlags::empty(), vk::AccessFlags::SHADER_WRITE,
             vk::PipelineStageFlags::TOP_OF_PIPE, vk::PipelineStageFlags::COMPUTE_SHADER);
         {
-            let set = allocate_set(ctx, bake_desc_pool, p_irradiance.layout_set)?;
-            update_set_combined(ctx, set, 0, radiance.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+            let set = allocate_set(ctx, desc_pool, self.irradiance.layout_set)?;
+            update_set_combined(ctx, set, 0, source_view, source_sampler, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
             update_set_storage_image(ctx, set, 1, irradiance.mip_views[0]);
-            p_irradiance.dispatch(ctx, cmd, &[set], &IrradiancePC {
+            self.irradiance.dispatch(ctx, cmd, &[set], &IrradiancePC {
                 face_size: IBL_IRRADIANCE_SIZE as i32, num_faces: 6, _pad: [0.0; 2],
             }, IBL_IRRADIANCE_SIZE.div_ceil(8), IBL_IRRADIANCE_SIZE.div_ceil(8), 6);
         }
@@ -101,24 +227,13 @@ impl IblBaker {
         for mip in 0..IBL_PREFILTER_MIPS {
             let mip_size = IBL_PREFILTER_SIZE >> mip;
             let roughness = mip as f32 / (IBL_PREFILTER_MIPS - 1) as f32;
-            let set = allocate_set(ctx, bake_desc_pool, p_prefilter.layout_set)?;
-            update_set_combined(ctx, set, 0, radiance.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+            let set = allocate_set(ctx, desc_pool, self.prefilter.layout_set)?;
+            update_set_combined(ctx, set, 0, source_view, source_sampler, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
             update_set_storage_image(ctx, set, 1, prefiltered.mip_views[mip as usize]);
-            p_prefilter.dispatch(ctx, cmd, &[set], &PrefilterPC {
-                mip_size: mip_size as i32, num_faces: 6, roughness, src_face_size: IBL_RADIANCE_SIZE as i32,
+            self.prefilter.dispatch(ctx, cmd, &[set], &PrefilterPC {
+                mip_size: mip_size as i32, num_faces: 6, roughness, src_face_size: source_size as i32,
             }, mip_size.div_ceil(8).max(1), mip_size.div_ceil(8).max(1), 6);
         }
-        transition_2d(ctx, cmd, brdf_lut.image, 1,
-            vk::ImageLayout::UNDEFINED, vk::ImageLayout::GENERAL,
-            vk::AccessFlags::empty(), vk::AccessFlags::SHADER_WRITE,
-            vk::PipelineStageFlags::TOP_OF_PIPE, vk::PipelineStageFlags::COMPUTE_SHADER);
-        {
-            let set = allocate_set(ctx, bake_desc_pool, p_brdf.layout_set)?;
-            update_set_storage_image(ctx, set, 0, brdf_lut.view);
-            p_brdf.dispatch(ctx, cmd, &[set], &BrdfLutPC {
-                size: IBL_BRDF_LUT_SIZE as i32, _pad: 0, _pad2: [0.0; 2],
-            }, IBL_BRDF_LUT_SIZE / 8, IBL_BRDF_LUT_SIZE / 8, 1);
-        }
         transition_cube(ctx, cmd, irradiance.image, irradiance.mip_levels, irradiance.layer_count,
             vk::ImageLayout::GENERAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
             vk::AccessFlags::SHADER_WRITE, vk::AccessFlags::SHADER_READ,
@@ -127,39 +242,69 @@ impl IblBaker {
             vk::ImageLayout::GENERAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
             vk::AccessFlags::SHADER_WRITE, vk::AccessFlags::SHADER_READ,
             vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::FRAGMENT_SHADER);
-        transition_2d(ctx, cmd, brdf_lut.image, 1,
-            vk::ImageLayout::GENERAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
-            vk::AccessFlags::SHADER_WRITE, vk::AccessFlags::SHADER_READ,
-            vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::FRAGMENT_SHADER);
-        end_and_submit(ctx, pool, cmd)?;
-        unsafe {
-            device.destroy_descriptor_pool(bake_desc_pool, None);
-            device.destroy_command_pool(pool, None);
-        }
-        drop(radiance);
-        drop(equirect_img);
-        let final_layout = create_final_descriptor_layout(ctx)?;
-        let final_pool = create_final_descriptor_pool(ctx)?;
-        let final_set = allocate_set(ctx, final_pool, final_layout)?;
-        update_set_combined(ctx, final_set, 0, irradiance.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
-        update_set_combined(ctx, final_set, 1, prefiltered.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
-        update_set_combined(ctx, final_set, 2, brdf_lut.view, sampler_2d, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
-        let max_mip_level = (IBL_PREFILTER_MIPS - 1) as f32;
-        let (params_buf, params_alloc) = super::helpers::create_uniform_buffer(ctx, allocator.clone(), max_mip_level)?;
-        update_set_uniform_buffer(ctx, final_set, 3, params_buf, std::mem::size_of::<f32>() as u64);
-        Ok(IblTextures {
-            irradiance, prefiltered, brdf_lut,
-            sampler_cube, sampler_2d,
-            descriptor_pool: final_pool, descriptor_set_layout: final_layout, descriptor_set: final_set,
-            params_buffer: params_buf, params_allocation: Some(params_alloc), max_mip_level,
-            device: device.clone(), allocator,
-        })
+        Ok(())
     }
+}
 
-    pub fn bake_procedural(
-        ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>,
-    ) -> ReactorResult<IblTextures> {
-        let (pixels, w, h) = procedural_studio_sky(1024, 512);
-        Self::bake_from_equirect_pixels(ctx, allocator, &pixels, w, h)
-    }
+fn create_filter_targets(
+    ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>,
+) -> ReactorResult<(IblImage, IblImage)> {
+    let irradiance = create_cubemap(ctx, allocator.clone(), IBL_IRRADIANCE_SIZE, 1,
+        vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)?;
+    let prefiltered = create_cubemap(ctx, allocator, IBL_PREFILTER_SIZE, IBL_PREFILTER_MIPS,
+        vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)?;
+    Ok((irradiance, prefiltered))
+}
+
+fn create_brdf_lut(ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>) -> ReactorResult<IblImage> {
+    create_2d_lut(ctx, allocator, IBL_BRDF_LUT_SIZE, vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::SAMPLED)
+}
+
+fn brdf_lut_pass(ctx: &VulkanContext) -> ReactorResult<ComputePass> {
+    ComputePass::new(ctx, SPV_BRDF_LUT, &[storage_image_b(0)], std::mem::size_of::<BrdfLutPC>() as u32)
+}
+
+fn record_brdf_lut(
+    ctx: &VulkanContext, cmd: vk::CommandBuffer, desc_pool: vk::DescriptorPool,
+    pass: &ComputePass, brdf_lut: &IblImage,
+) -> ReactorResult<()> {
+    transition_2d(ctx, cmd, brdf_lut.image, 1,
+        vk::ImageLayout::UNDEFINED, vk::ImageLayout::GENERAL,
+        vk::AccessFlags::empty(), vk::AccessFlags::SHADER_WRITE,
+        vk::PipelineStageFlags::TOP_OF_PIPE, vk::PipelineStageFlags::COMPUTE_SHADER);
+    let set = allocate_set(ctx, desc_pool, pass.layout_set)?;
+    update_set_storage_image(ctx, set, 0, brdf_lut.view);
+    pass.dispatch(ctx, cmd, &[set], &BrdfLutPC {
+        size: IBL_BRDF_LUT_SIZE as i32, _pad: 0, _pad2: [0.0; 2],
+    }, IBL_BRDF_LUT_SIZE / 8, IBL_BRDF_LUT_SIZE / 8, 1);
+    transition_2d(ctx, cmd, brdf_lut.image, 1,
+        vk::ImageLayout::GENERAL, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        vk::AccessFlags::SHADER_WRITE, vk::AccessFlags::SHADER_READ,
+        vk::PipelineStageFlags::COMPUTE_SHADER, vk::PipelineStageFlags::FRAGMENT_SHADER);
+    Ok(())
+}
+
+/// Descriptor set final (set = 1 del fragment PBR) y UBO de parámetros.
+#[allow(clippy::too_many_arguments)]
+fn finish_textures(
+    ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>,
+    irradiance: IblImage, prefiltered: IblImage, brdf_lut: Arc<IblImage>,
+    sampler_cube: vk::Sampler, sampler_2d: vk::Sampler, intensity: f32,
+) -> ReactorResult<IblTextures> {
+    let final_layout = create_final_descriptor_layout(ctx)?;
+    let final_pool = create_final_descriptor_pool(ctx)?;
+    let final_set = allocate_set(ctx, final_pool, final_layout)?;
+    update_set_combined(ctx, final_set, 0, irradiance.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+    update_set_combined(ctx, final_set, 1, prefiltered.view, sampler_cube, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+    update_set_combined(ctx, final_set, 2, brdf_lut.view, sampler_2d, vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+    let max_mip_level = (prefiltered.mip_levels - 1) as f32;
+    let (params_buf, params_alloc) = super::helpers::create_uniform_buffer(ctx, allocator.clone(), max_mip_level, intensity)?;
+    update_set_uniform_buffer(ctx, final_set, 3, params_buf, IBL_PARAMS_SIZE);
+    Ok(IblTextures {
+        irradiance, prefiltered, brdf_lut,
+        sampler_cube, sampler_2d,
+        descriptor_pool: final_pool, descriptor_set_layout: final_layout, descriptor_set: final_set,
+        params_buffer: params_buf, params_allocation: Some(params_alloc), max_mip_level, intensity,
+        device: ctx.ash_device().clone(), allocator,
+    })
 }
@@ -43,7 +43,7 @@ impl ComputePass {
             .name(unsafe { std::ffi::CStr::from_bytes_with_nul_unchecked(b"main\0") });
         let pipelines = unsafe {
             device.create_compute_pipelines(
-                vk::PipelineCache::null(), &[vk::ComputePipelineCreateInfo::default().stage(stage).layout(layout)], None,
+                ctx.pipeline_cache(), &[vk::ComputePipelineCreateInfo::default().stage(stage).layout(layout)], None,
             ).map_err(|(_, e)| ReactorError::from(e))?
         };
         unsafe { device.destroy_shader_module(sm, None); }
@@ -120,11 +120,14 @@ pub fn update_set_uniform_buffer(
     unsafe { ctx.ash_device().update_descriptor_sets(&[w], &[]); }
 }
 
+/// `IblParams { float max_mip; float intensity; }` del fragment shader.
+pub const IBL_PARAMS_SIZE: u64 = 2 * std::mem::size_of::<f32>() as u64;
+
 pub fn create_uniform_buffer(
-    ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>, max_mip: f32,
+    ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>, max_mip: f32, intensity: f32,
 ) -> ReactorResult<(vk::Buffer, Allocation)> {
     let device = ctx.ash_device();
-    let size = std::mem::size_of::<f32>() as u64;
+    let size = IBL_PARAMS_SIZE;
     let info = vk::BufferCreateInfo::default().size(size)
         .usage(vk::BufferUsageFlags::UNIFORM_BUFFER).sharing_mode(vk::SharingMode::EXCLUSIVE);
     let buf = unsafe { device.create_buffer(&info, None).map_err(verr)? };
@@ -136,7 +139,9 @@ pub fn create_uniform_buffer(
             allocation_scheme: AllocationScheme::GpuAllocatorManaged,
         }).map_err(verr)?;
     unsafe { device.bind_buffer_memory(buf, alloc.memory(), alloc.offset()).map_err(verr)? };
-    alloc.mapped_slice_mut().expect("ubo no mapeado")[..4].copy_from_slice(&max_mip.to_le_bytes());
+    let mapped = alloc.mapped_slice_mut().expect("ubo no mapeado");
+    mapped[..4].copy_from_slice(&max_mip.to_le_bytes());
+    mapped[4..8].copy_from_slice(&intensity.to_le_bytes());
     Ok((buf, alloc))
 }
 
@@ -155,6 +160,15 @@ pub fn begin_one_shot(ctx: &VulkanContext, pool: vk::CommandPool) -> ReactorResu
     Ok(cb)
 }
 
+/// Como `end_and_submit` pero sin esperar: `fence` señaliza al terminar.
+pub fn end_and_submit_fenced(ctx: &VulkanContext, cb: vk::CommandBuffer, fence: vk::Fence) -> ReactorResult<()> {
+    let device = ctx.ash_device();
+    unsafe { device.end_command_buffer(cb).map_err(verr)?; }
+    let cbs = [cb];
+    let submit = vk::SubmitInfo::default().command_buffers(&cbs);
+    unsafe { device.queue_submit(ctx.graphics_queue, &[submit], fence).map_err(verr) }
+}
+
 pub fn end_and_submit(ctx: &VulkanContext, _pool: vk::CommandPool, cb: vk::CommandBuffer) -> ReactorResult<()> {
     let device = ctx.ash_device();
     unsafe { device.end_command_buffer(cb).map_err(verr)?; }
@@ -3,12 +3,14 @@ pub(crate) mod compute_pass;
 pub(crate) mod create;
 pub(crate) mod helpers;
 pub mod image;
+pub mod pending;
 pub(crate) mod sky;
 pub mod textures;
 pub(crate) mod upload;
 
 pub use baker::IblBaker;
 pub use image::IblImage;
+pub use pending::PendingIbl;
 pub use textures::IblTextures;
 
 use crate::core::error::{ErrorCode, ReactorError};
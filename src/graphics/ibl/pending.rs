@@ -0,0 +1,82 @@
+use crate::core::error::ReactorResult;
+use crate::core::VulkanContext;
+use crate::graphics::ibl::compute_pass::ComputePass;
+use crate::graphics::ibl::helpers::{create_bake_descriptor_pool, create_one_shot_command_pool};
+use crate::graphics::ibl::textures::IblTextures;
+use crate::graphics::ibl::{verr, IBL_PREFILTER_MIPS};
+use crate::resources::texture::Cubemap;
+use ash::vk;
+use std::sync::Arc;
+
+/// Bake de IBL enviado a la GPU y aún sin terminar. Sostiene todo lo que
+/// el command buffer usa (pools, pipelines y el cubemap origen) hasta que
+/// su fence señaliza; soltarlo antes espera a la GPU.
+pub struct PendingIbl {
+    pub(crate) textures: Option<IblTextures>,
+    pub(crate) fence: vk::Fence,
+    pub(crate) command_pool: vk::CommandPool,
+    pub(crate) descriptor_pool: vk::DescriptorPool,
+    pub(crate) passes: Vec<ComputePass>,
+    pub(crate) submitted: bool,
+    _source: Arc<Cubemap>,
+    device: ash::Device,
+}
+
+impl PendingIbl {
+    pub(crate) fn new(ctx: &VulkanContext, source: Arc<Cubemap>) -> ReactorResult<Self> {
+        let device = ctx.ash_device();
+        let fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None).map_err(verr)? };
+        let mut pending = Self {
+            textures: None,
+            fence,
+            command_pool: vk::CommandPool::null(),
+            descriptor_pool: vk::DescriptorPool::null(),
+            passes: Vec::new(),
+            submitted: false,
+            _source: source,
+            device: device.clone(),
+        };
+        // Asignados uno a uno: si el segundo falla, Drop libera el primero.
+        pending.command_pool = create_one_shot_command_pool(ctx)?;
+        pending.descriptor_pool = create_bake_descriptor_pool(ctx, IBL_PREFILTER_MIPS + 2)?;
+        Ok(pending)
+    }
+
+    /// `true` cuando la GPU terminó el bake (no bloquea).
+    pub fn is_ready(&self) -> bool {
+        self.submitted && unsafe { self.device.get_fence_status(self.fence) }.unwrap_or(false)
+    }
+
+    /// Saca las texturas si el bake ya terminó; `None` si sigue en la GPU
+    /// (o ya se sacaron) y hay que consultarlo de nuevo en otro frame.
+    pub fn try_finish(&mut self) -> Option<IblTextures> {
+        if !self.is_ready() {
+            return None;
+        }
+        self.textures.take()
+    }
+
+    /// Bloquea hasta que la GPU termina y devuelve las texturas.
+    pub fn wait(mut self) -> ReactorResult<IblTextures> {
+        unsafe { self.device.wait_for_fences(&[self.fence], true, u64::MAX).map_err(verr)? };
+        Ok(self.textures.take().expect("PendingIbl sin texturas"))
+    }
+}
+
+impl Drop for PendingIbl {
+    fn drop(&mut self) {
+        unsafe {
+            if self.submitted {
+                let _ = self.device.wait_for_fences(&[self.fence], true, u64::MAX);
+            }
+            self.passes.clear();
+            if self.descriptor_pool != vk::DescriptorPool::null() {
+                self.device.destroy_descriptor_pool(self.descriptor_pool, None);
+            }
+            if self.command_pool != vk::CommandPool::null() {
+                self.device.destroy_command_pool(self.command_pool, None);
+            }
+            self.device.destroy_fence(self.fence, None);
+        }
+    }
+}
@@ -6,7 +6,9 @@ use std::sync::{Arc, Mutex};
 pub struct IblTextures {
     pub irradiance: IblImage,
     pub prefiltered: IblImage,
-    pub brdf_lut: IblImage,
+    /// Depende solo del BRDF, no del entorno: se cocina una vez y lo
+    /// comparten todos los entornos.
+    pub brdf_lut: Arc<IblImage>,
     pub sampler_cube: vk::Sampler,
     pub sampler_2d: vk::Sampler,
     pub descriptor_pool: vk::DescriptorPool,
@@ -15,10 +17,27 @@ pub struct IblTextures {
     pub params_buffer: vk::Buffer,
     pub params_allocation: Option<Allocation>,
     pub max_mip_level: f32,
+    pub(crate) intensity: f32,
     pub(crate) device: ash::Device,
     pub(crate) allocator: Arc<Mutex<Allocator>>,
 }
 
+impl IblTextures {
+    /// Multiplicador de la luz ambiente (difusa + especular) que aporta el entorno.
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Escribe `intensity` en el UBO de parámetros; los frames en vuelo
+    /// pueden ver aún el valor anterior.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity;
+        if let Some(mapped) = self.params_allocation.as_mut().and_then(|a| a.mapped_slice_mut()) {
+            mapped[4..8].copy_from_slice(&intensity.to_le_bytes());
+        }
+    }
+}
+
 impl Drop for IblTextures {
     fn drop(&mut self) {
         unsafe {
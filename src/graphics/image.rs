@@ -1,4 +1,6 @@
+use crate::core::allocator::{track_allocation, track_free, AllocationKind};
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use ash::vk;
@@ -6,6 +8,12 @@ use gpu_allocator::vulkan::{Allocation, AllocationCreateDesc, AllocationScheme,
 use gpu_allocator::MemoryLocation;
 use std::sync::{Arc, Mutex};
 
+#[derive(Clone, Copy)]
+enum ImageShape {
+    Flat,
+    Cube,
+}
+
 pub struct Image {
     pub handle: vk::Image,
     pub view: vk::ImageView,
@@ -13,6 +21,8 @@ pub struct Image {
     pub format: vk::Format,
     pub extent: vk::Extent3D,
     pub mip_levels: u32,
+    /// 6 for cubemaps, 1 otherwise.
+    pub array_layers: u32,
     device: ArcDevice,
     allocator: Arc<Mutex<Allocator>>,
 }
@@ -27,15 +37,48 @@ impl Image {
         usage: vk::ImageUsageFlags,
         aspect: vk::ImageAspectFlags,
         mip_levels: u32,
+    ) -> ReactorResult<Self> {
+        Self::create(ctx, allocator, width, height, format, usage, aspect, mip_levels, ImageShape::Flat)
+    }
+
+    /// Cube-compatible image with six `size`×`size` layers (+X, -X, +Y, -Y,
+    /// +Z, -Z) and a `CUBE` view over every mip.
+    pub fn new_cube(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        size: u32,
+        format: vk::Format,
+        usage: vk::ImageUsageFlags,
+        mip_levels: u32,
+    ) -> ReactorResult<Self> {
+        Self::create(ctx, allocator, size, size, format, usage, vk::ImageAspectFlags::COLOR, mip_levels, ImageShape::Cube)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        width: u32,
+        height: u32,
+        format: vk::Format,
+        usage: vk::ImageUsageFlags,
+        aspect: vk::ImageAspectFlags,
+        mip_levels: u32,
+        shape: ImageShape,
     ) -> ReactorResult<Self> {
         let device = ctx.ash_device();
         let extent = vk::Extent3D { width, height, depth: 1 };
+        let (array_layers, flags, view_type) = match shape {
+            ImageShape::Flat => (1, vk::ImageCreateFlags::empty(), vk::ImageViewType::TYPE_2D),
+            ImageShape::Cube => (6, vk::ImageCreateFlags::CUBE_COMPATIBLE, vk::ImageViewType::CUBE),
+        };
 
         let image_info = vk::ImageCreateInfo::default()
+            .flags(flags)
             .image_type(vk::ImageType::TYPE_2D)
             .extent(extent)
             .mip_levels(mip_levels)
-            .array_layers(1)
+            .array_layers(array_layers)
             .format(format)
             .tiling(vk::ImageTiling::OPTIMAL)
             .initial_layout(vk::ImageLayout::UNDEFINED)
@@ -67,6 +110,7 @@ impl Image {
                     e,
                 )
             })?;
+        track_allocation(AllocationKind::Image, &allocation);
 
         unsafe {
             device
@@ -82,7 +126,7 @@ impl Image {
 
         let view_info = vk::ImageViewCreateInfo::default()
             .image(handle)
-            .view_type(vk::ImageViewType::TYPE_2D)
+            .view_type(view_type)
             .format(format)
             .subresource_range(
                 vk::ImageSubresourceRange::default()
@@ -90,7 +134,7 @@ impl Image {
                     .base_mip_level(0)
                     .level_count(mip_levels)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(array_layers),
             );
 
         let view = unsafe {
@@ -110,6 +154,7 @@ impl Image {
             format,
             extent,
             mip_levels,
+            array_layers,
             device: ctx.device.clone(),
             allocator,
         })
@@ -253,7 +298,7 @@ impl Image {
                     .base_mip_level(0)
                     .level_count(self.mip_levels)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(self.array_layers),
             )
             .src_access_mask(src_access)
             .dst_access_mask(dst_access);
@@ -270,18 +315,30 @@ impl Image {
             );
         }
     }
+
+    /// Label the image and its view. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_image(self.handle, name);
+        namer.name_image_view(self.view, &format!("{name} (view)"));
+    }
 }
 
 impl Drop for Image {
     fn drop(&mut self) {
-        unsafe {
-            self.device.destroy_image_view(self.view, None);
-            self.device.destroy_image(self.handle, None);
-        }
-        if let Some(allocation) = self.allocation.take() {
-            if let Err(e) = self.allocator.lock().unwrap().free(allocation) {
-                eprintln!("Failed to free image memory: {:?}", e);
+        let (handle, view) = (self.handle, self.view);
+        let allocation = self.allocation.take();
+        let allocator = self.allocator.clone();
+        self.device.destroy_deferred(move |device| {
+            unsafe {
+                device.destroy_image_view(view, None);
+                device.destroy_image(handle, None);
             }
-        }
+            if let Some(allocation) = allocation {
+                track_free(AllocationKind::Image, &allocation);
+                if let Err(e) = allocator.lock().unwrap().free(allocation) {
+                    log::error!("Failed to free image memory: {:?}", e);
+                }
+            }
+        });
     }
 }
@@ -13,37 +13,45 @@ pub mod ibl;
 pub mod image;
 pub mod msaa;
 pub mod pipeline;
+pub mod push_constants;
 pub mod post_process;
 pub mod render_pass;
 pub mod sampler;
 pub mod shadows;
+pub mod sprite_batch;
 pub mod swapchain;
 pub mod temporal;
+pub mod text;
 pub mod uniform_buffer;
 
 pub use buffer::Buffer;
 pub use debug_renderer::{DebugLine, DebugRenderer};
 pub use depth::DepthBuffer;
 pub use descriptors::{
-    DescriptorBinding, DescriptorPool, DescriptorSet, DescriptorSetLayout, PoolSize,
+    DescriptorAllocation, DescriptorAllocator, DescriptorBinding, DescriptorPool, DescriptorSet,
+    DescriptorSetLayout, PoolRatio, PoolSize, PooledDescriptorSet,
 };
 pub use framebuffer::{Framebuffer, FramebufferSet};
 pub use gbuffer::{GBuffer, GBufferAttachment};
 pub use hiz::HiZPyramid;
-pub use ibl::{IblBaker, IblImage, IblTextures};
+pub use ibl::{IblBaker, IblImage, IblTextures, PendingIbl};
 pub use image::Image;
 pub use msaa::MsaaTarget;
 pub use pipeline::{Pipeline, PipelineConfig};
+pub use push_constants::{ObjectPushConstants, OBJECT_PUSH_CONSTANT_SIZE};
 pub use post_process::{
-    PostProcessEffect, PostProcessPipeline, PostProcessPreset, PostProcessSettings,
+    AAMode, AAStats, PostProcessEffect, PostProcessPipeline, PostProcessPreset, PostProcessSettings,
+    Tonemapper,
 };
 pub use render_pass::{RenderPass, RenderPassConfig};
 pub use sampler::{FilterMode, Sampler, SamplerConfig, WrapMode};
-pub use shadows::{ShadowCascade, ShadowConfig, ShadowMap, ShadowUniformData};
+pub use shadows::{ShadowCacheKey, ShadowCascade, ShadowConfig, ShadowMap, ShadowUniformData};
+pub use sprite_batch::{Sprite, SpriteBatch, SpriteDraw};
 pub use swapchain::Swapchain;
 pub use temporal::TemporalHistory;
+pub use text::TextRenderer;
 pub use uniform_buffer::{
-    GlobalUniformData, LightData, LightUniformData, MaterialUniformData, UniformBuffer,
+    DynamicUniformRing, GlobalUniformData, LightData, LightUniformData, MaterialUniformData, UniformBuffer, UniformRingStats,
 };
 
 // ═══ FASE 2 — Pipeline gráfico moderno ═══
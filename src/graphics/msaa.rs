@@ -1,3 +1,4 @@
+use crate::core::allocator::{track_allocation, track_free, AllocationKind};
 use crate::core::error::ReactorResult;
 use crate::core::VulkanContext;
 use ash::vk;
@@ -48,6 +49,7 @@ impl MsaaTarget {
             linear: false,
             allocation_scheme: AllocationScheme::GpuAllocatorManaged,
         })?;
+        track_allocation(AllocationKind::Image, &allocation);
 
         unsafe {
             device.bind_image_memory(image, allocation.memory(), allocation.offset())?;
@@ -111,8 +113,9 @@ impl Drop for MsaaTarget {
             self.device.destroy_image(self.image, None);
         }
         if let Some(allocation) = self.allocation.take() {
+            track_free(AllocationKind::Image, &allocation);
             if let Err(e) = self.allocator.lock().unwrap().free(allocation) {
-                eprintln!("Failed to free MSAA target memory: {:?}", e);
+                log::error!("Failed to free MSAA target memory: {:?}", e);
             }
         }
     }
@@ -1,6 +1,8 @@
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
-use crate::resources::vertex::Vertex;
+use crate::core::pipeline_cache::record_pipeline_feedback;
+use crate::resources::vertex::{InstanceData, Vertex, VertexSkinned};
 use ash::vk;
 use std::ffi::CStr;
 
@@ -8,8 +10,24 @@ pub struct Pipeline {
     pub pipeline: vk::Pipeline,
     pub layout: vk::PipelineLayout,
     device: ArcDevice,
+    recipe: PipelineRecipe,
 }
 
+/// Parámetros con los que se creó el pipeline, para poder reconstruirlo con otros shaders.
+#[derive(Clone)]
+struct PipelineRecipe {
+    width: u32,
+    height: u32,
+    config: PipelineConfig,
+    descriptor_layouts: Vec<vk::DescriptorSetLayout>,
+    color_formats: Vec<vk::Format>,
+    depth_format: Option<vk::Format>,
+    pipeline_cache: vk::PipelineCache,
+    vert_spv: Vec<u32>,
+    frag_spv: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct PipelineConfig {
     pub cull_mode: vk::CullModeFlags,
     pub front_face: vk::FrontFace,
@@ -19,6 +37,20 @@ pub struct PipelineConfig {
     pub blend_enable: bool,
     pub samples: vk::SampleCountFlags,
     pub fragment_shading_rate: bool,
+    pub topology: vk::PrimitiveTopology,
+    /// Añade el stream `InstanceData` (binding 1: matriz en locations 3..=6,
+    /// color en 7).
+    pub instanced: bool,
+    /// Usa el layout `VertexSkinned` (joints en location 3, weights en 4) en
+    /// vez de `Vertex`. No se combina con `instanced`.
+    pub skinned: bool,
+    /// Alpha premultiplicado (`ONE`, `ONE_MINUS_SRC_ALPHA`) sin escribir
+    /// depth, aunque `depth_write` esté activo. El shader debe devolver el
+    /// color ya multiplicado por alpha.
+    pub transparent: bool,
+    /// `LESS` por defecto; el skybox usa `LESS_OR_EQUAL` para pasar en el
+    /// plano lejano sin escribir depth.
+    pub depth_compare: vk::CompareOp,
 }
 
 impl Default for PipelineConfig {
@@ -32,6 +64,11 @@ impl Default for PipelineConfig {
             blend_enable: false,
             samples: vk::SampleCountFlags::TYPE_1,
             fragment_shading_rate: false,
+            topology: vk::PrimitiveTopology::TRIANGLE_LIST,
+            instanced: false,
+            skinned: false,
+            transparent: false,
+            depth_compare: vk::CompareOp::LESS,
         }
     }
 }
@@ -165,6 +202,13 @@ impl Pipeline {
         depth_format: Option<vk::Format>,
         pipeline_cache: vk::PipelineCache,
     ) -> ReactorResult<Self> {
+        if config.skinned && config.instanced {
+            return Err(ReactorError::new(
+                ErrorCode::VulkanPipelineCreation,
+                "A pipeline cannot be both skinned and instanced",
+            ));
+        }
+
         let vert_shader_module = unsafe {
             let create_info = vk::ShaderModuleCreateInfo::default().code(vert_spv);
             device
@@ -203,15 +247,22 @@ impl Pipeline {
 
         let shader_stages = [vert_stage, frag_stage];
 
-        let binding_descriptions = [Vertex::binding_description()];
-        let attribute_descriptions = Vertex::attribute_descriptions();
+        let (mut binding_descriptions, mut attribute_descriptions) = if config.skinned {
+            (vec![VertexSkinned::binding_description()], VertexSkinned::attribute_descriptions().to_vec())
+        } else {
+            (vec![Vertex::binding_description()], Vertex::attribute_descriptions().to_vec())
+        };
+        if config.instanced {
+            binding_descriptions.push(InstanceData::binding_description());
+            attribute_descriptions.extend(InstanceData::attribute_descriptions(3));
+        }
 
         let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::default()
             .vertex_binding_descriptions(&binding_descriptions)
             .vertex_attribute_descriptions(&attribute_descriptions);
 
         let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::default()
-            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .topology(config.topology)
             .primitive_restart_enable(false);
 
         let viewport = vk::Viewport {
@@ -256,8 +307,8 @@ impl Pipeline {
 
         let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(config.depth_test)
-            .depth_write_enable(config.depth_write)
-            .depth_compare_op(vk::CompareOp::LESS)
+            .depth_write_enable(config.depth_write && !config.transparent)
+            .depth_compare_op(config.depth_compare)
             .depth_bounds_test_enable(false)
             .stencil_test_enable(false);
 
@@ -270,15 +321,25 @@ impl Pipeline {
         let attachments = active_color_formats
             .iter()
             .map(|_| {
-                vk::PipelineColorBlendAttachmentState::default()
+                let state = vk::PipelineColorBlendAttachmentState::default()
                     .color_write_mask(vk::ColorComponentFlags::RGBA)
-                    .blend_enable(config.blend_enable)
-                    .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
-                    .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
                     .color_blend_op(vk::BlendOp::ADD)
-                    .src_alpha_blend_factor(vk::BlendFactor::ONE)
-                    .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
-                    .alpha_blend_op(vk::BlendOp::ADD)
+                    .alpha_blend_op(vk::BlendOp::ADD);
+                if config.transparent {
+                    state
+                        .blend_enable(true)
+                        .src_color_blend_factor(vk::BlendFactor::ONE)
+                        .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                        .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                        .dst_alpha_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                } else {
+                    state
+                        .blend_enable(config.blend_enable)
+                        .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+                        .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                        .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                        .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+                }
             })
             .collect::<Vec<_>>();
         let color_blend_state = vk::PipelineColorBlendStateCreateInfo::default()
@@ -288,7 +349,7 @@ impl Pipeline {
         let push_constant_range = vk::PushConstantRange {
             stage_flags: vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
             offset: 0,
-            size: 256,
+            size: crate::graphics::push_constants::OBJECT_PUSH_CONSTANT_SIZE,
         };
 
         let push_constant_ranges = [push_constant_range];
@@ -335,7 +396,10 @@ impl Pipeline {
             create_info_builder = create_info_builder.push_next(&mut rendering_info);
         }
 
-        let create_info = create_info_builder;
+        let mut feedback = vk::PipelineCreationFeedback::default();
+        let mut feedback_info =
+            vk::PipelineCreationFeedbackCreateInfo::default().pipeline_creation_feedback(&mut feedback);
+        let create_info = create_info_builder.push_next(&mut feedback_info);
 
         let pipelines = unsafe {
             device
@@ -353,20 +417,100 @@ impl Pipeline {
             device.destroy_shader_module(vert_shader_module, None);
             device.destroy_shader_module(frag_shader_module, None);
         }
+        record_pipeline_feedback(pipeline_cache, &feedback);
 
         Ok(Self {
             pipeline: pipelines[0],
             layout,
             device: device.clone(),
+            recipe: PipelineRecipe {
+                width,
+                height,
+                config: *config,
+                descriptor_layouts: descriptor_layouts.to_vec(),
+                color_formats: color_formats.to_vec(),
+                depth_format,
+                pipeline_cache,
+                vert_spv: vert_spv.to_vec(),
+                frag_spv: frag_spv.to_vec(),
+            },
         })
     }
+
+    /// `true` si el pipeline consume el stream de instancias (binding 1).
+    pub fn is_instanced(&self) -> bool {
+        self.recipe.config.instanced
+    }
+
+    /// `true` si el pipeline espera vértices `VertexSkinned` y la paleta de joints.
+    pub fn is_skinned(&self) -> bool {
+        self.recipe.config.skinned
+    }
+
+    /// `true` si el pipeline mezcla con alpha premultiplicado (`PipelineConfig::transparent`).
+    pub fn is_transparent(&self) -> bool {
+        self.recipe.config.transparent
+    }
+
+    /// Construye un pipeline nuevo con los mismos shaders, layouts y formatos
+    /// que `self`, pero con `transparent` cambiado. `self` no se modifica.
+    pub fn rebuild_with_transparency(&self, transparent: bool) -> ReactorResult<Self> {
+        let r = &self.recipe;
+        let config = PipelineConfig { transparent, ..r.config };
+        Self::with_config_and_cache_multi_color(
+            &self.device,
+            None,
+            &r.vert_spv,
+            &r.frag_spv,
+            r.width,
+            r.height,
+            &config,
+            &r.descriptor_layouts,
+            &r.color_formats,
+            r.depth_format,
+            r.pipeline_cache,
+        )
+    }
+
+    /// Construye un pipeline nuevo con el mismo estado, layouts y formatos que
+    /// `self`, pero con otro par de shaders, compilado contra `pipeline_cache`
+    /// (normalmente `VulkanContext::pipeline_cache`). `self` no se modifica.
+    pub fn rebuild_with_shaders(
+        &self,
+        render_pass: Option<vk::RenderPass>,
+        vert_spv: &[u32],
+        frag_spv: &[u32],
+        pipeline_cache: vk::PipelineCache,
+    ) -> ReactorResult<Self> {
+        let r = &self.recipe;
+        Self::with_config_and_cache_multi_color(
+            &self.device,
+            render_pass,
+            vert_spv,
+            frag_spv,
+            r.width,
+            r.height,
+            &r.config,
+            &r.descriptor_layouts,
+            &r.color_formats,
+            r.depth_format,
+            pipeline_cache,
+        )
+    }
+
+    /// Label the pipeline and its layout. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_pipeline(self.pipeline, name);
+        namer.name_pipeline_layout(self.layout, &format!("{name} (layout)"));
+    }
 }
 
 impl Drop for Pipeline {
     fn drop(&mut self) {
-        unsafe {
-            self.device.destroy_pipeline(self.pipeline, None);
-            self.device.destroy_pipeline_layout(self.layout, None);
-        }
+        let (pipeline, layout) = (self.pipeline, self.layout);
+        self.device.destroy_deferred(move |device| unsafe {
+            device.destroy_pipeline(pipeline, None);
+            device.destroy_pipeline_layout(layout, None);
+        });
     }
 }
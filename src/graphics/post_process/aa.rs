@@ -0,0 +1,54 @@
+use ash::vk;
+use super::types::{AAMode, AAStats, AA_FLAG_COUNT_EDGES};
+use super::PostProcessPipeline;
+
+impl PostProcessPipeline {
+    /// Recoge lo que contó el frame que usó antes `frame_slot` (su fence ya se
+    /// esperó) y, con las estadísticas activas, pone a cero el contador de
+    /// `image_index` para el pase que se va a grabar. Devuelve los `aa_flags`
+    /// del push constant.
+    pub(crate) fn begin_aa_stats(&mut self, device: &ash::Device, command_buffer: vk::CommandBuffer,
+        frame_slot: usize, image_index: usize, pixels: u32) -> u32 {
+        if self.aa_stats_pending.len() <= frame_slot {
+            self.aa_stats_pending.resize(frame_slot + 1, None);
+        }
+        if let Some((image, mut stats)) = self.aa_stats_pending[frame_slot].take() {
+            let mut counters = [0u32; 2];
+            if self.exposure_buffers.get(image).is_some_and(|buffer| buffer.read(&mut counters)) {
+                stats.edges_detected = counters[1];
+                self.aa_stats = Some(stats);
+            }
+        }
+
+        let mode = self.settings.aa_mode();
+        if !self.aa_stats_enabled || mode == AAMode::Off {
+            return 0;
+        }
+        let Some(buffer) = self.exposure_buffers.get(image_index) else { return 0 };
+
+        let barrier = vk::BufferMemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED).dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(buffer.handle).offset(4).size(4);
+        unsafe {
+            device.cmd_fill_buffer(command_buffer, buffer.handle, 4, 4, 0);
+            device.cmd_pipeline_barrier(command_buffer, vk::PipelineStageFlags::TRANSFER, vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(), &[], &[barrier], &[]);
+        }
+        self.aa_stats_pending[frame_slot] = Some((image_index, AAStats { mode, edges_detected: 0, pixels_processed: pixels }));
+        AA_FLAG_COUNT_EDGES
+    }
+
+    /// Hace visible al host el contador escrito por el pase de post-proceso.
+    pub(crate) fn end_aa_stats(&self, device: &ash::Device, command_buffer: vk::CommandBuffer, image_index: usize) {
+        let Some(buffer) = self.exposure_buffers.get(image_index) else { return };
+        let barrier = vk::BufferMemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::SHADER_WRITE).dst_access_mask(vk::AccessFlags::HOST_READ)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED).dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(buffer.handle).offset(4).size(4);
+        unsafe {
+            device.cmd_pipeline_barrier(command_buffer, vk::PipelineStageFlags::FRAGMENT_SHADER, vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(), &[], &[barrier], &[]);
+        }
+    }
+}
@@ -17,6 +17,7 @@ impl PostProcessPipeline {
         height: u32,
         image_count: u32,
         swapchain_format: vk::Format,
+        scene_format: vk::Format,
         depth_view: vk::ImageView,
         sample_depth: bool,
     ) -> crate::core::error::ReactorResult<()> {
@@ -67,7 +68,7 @@ impl PostProcessPipeline {
         let dynamic_state_info = vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
         let mut rendering_info = vk::PipelineRenderingCreateInfo::default().color_attachment_formats(std::slice::from_ref(&swapchain_format));
         let pipeline_info = vk::GraphicsPipelineCreateInfo::default().stages(&shader_stages).vertex_input_state(&vertex_input).input_assembly_state(&input_assembly).viewport_state(&viewport_state).rasterization_state(&rasterization).multisample_state(&multisample).depth_stencil_state(&depth_stencil).color_blend_state(&color_blend).dynamic_state(&dynamic_state_info).layout(pipeline_layout).push_next(&mut rendering_info);
-        let pipelines = unsafe { device.create_graphics_pipelines(vk::PipelineCache::null(), &[pipeline_info], None).map_err(|(_, e)| e)? };
+        let pipelines = unsafe { device.create_graphics_pipelines(ctx.pipeline_cache(), &[pipeline_info], None).map_err(|(_, e)| e)? };
         let pipeline = pipelines[0];
         unsafe { device.destroy_shader_module(vert_module, None); device.destroy_shader_module(frag_module, None); }
 
@@ -88,7 +89,7 @@ impl PostProcessPipeline {
         self.descriptor_pool = Some(descriptor_pool);
         self.descriptor_sets = descriptor_sets;
 
-        self.recreate_offscreen_images(ctx, allocator.clone(), width, height, image_count, swapchain_format, depth_view, sample_depth)?;
+        self.recreate_offscreen_images(ctx, allocator.clone(), width, height, image_count, scene_format, depth_view, sample_depth)?;
         Ok(())
     }
 
@@ -133,8 +134,9 @@ impl PostProcessPipeline {
 
         self.exposure_buffers.clear();
         for _ in 0..image_count as usize {
-            let buf = Buffer::new(ctx, allocator.clone(), 4, vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::TRANSFER_DST, MemoryLocation::CpuToGpu)?;
-            buf.write(&[1.0f32]);
+            // Exposición actual + contador de bordes del AA.
+            let buf = Buffer::new(ctx, allocator.clone(), 8, vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::TRANSFER_DST, MemoryLocation::CpuToGpu)?;
+            buf.write(&[1.0f32, 0.0]);
             self.exposure_buffers.push(buf);
         }
 
@@ -146,11 +148,11 @@ impl PostProcessPipeline {
         self.init_taa(ctx, image_count)?;
 
         for i in 0..image_count as usize {
-            let img = Image::new(ctx, allocator.clone(), width, height, format, vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED, vk::ImageAspectFlags::COLOR, 1)?;
+            let img = Image::new(ctx, allocator.clone(), width, height, format, vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST, vk::ImageAspectFlags::COLOR, 1)?;
             let image_info = vk::DescriptorImageInfo::default().image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL).image_view(img.view).sampler(sampler);
             let depth_or_fallback_view = if sample_depth { depth_view } else { self.depth_resolved_images[i].view };
             let depth_info = vk::DescriptorImageInfo::default().image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL).image_view(depth_or_fallback_view).sampler(sampler);
-            let buffer_info = vk::DescriptorBufferInfo::default().buffer(self.exposure_buffers[i].handle).offset(0).range(4);
+            let buffer_info = vk::DescriptorBufferInfo::default().buffer(self.exposure_buffers[i].handle).offset(0).range(8);
             let lut_info = vk::DescriptorImageInfo::default().image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL).image_view(self.lut_texture.as_ref().unwrap().view()).sampler(self.lut_texture.as_ref().unwrap().sampler_handle());
 
             let writes = [
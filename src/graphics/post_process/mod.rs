@@ -3,12 +3,13 @@
 //! - `pipeline.rs` — `PostProcessPipeline` struct + constructors + Drop
 //! - `types.rs` — shared types (PostProcessSettings, PostProcessEffect, etc.)
 //! - `init.rs` — `init()` and `recreate_offscreen_images()` orchestrator
-//! - Each effect has its own file (bloom, taa, fog, lens_flare, gtao, etc.)
+//! - Each effect has its own file (aa, bloom, taa, fog, lens_flare, gtao, etc.)
 //! - `light_cull.rs` — `PointLightGpu` type + conversion helpers
 //! - `light_dispatch.rs` — light culling compute dispatch methods
 //! - `clouds.rs` — VolumetricClouds (separate, not part of PostProcessPipeline)
 //! - `ssgi_hiz.rs` — SsgiHiZ (separate)
 
+mod aa;
 mod auto_exposure;
 mod bloom;
 mod clouds;
@@ -30,6 +31,6 @@ pub use light_cull::{lights_to_gpu_buffer, PointLightGpu};
 pub use pipeline::PostProcessPipeline;
 pub use ssgi_hiz::SsgiHiZ;
 pub use types::{
-    AASettings, AAQualityPreset, AutoExposureParams, PostProcessEffect, PostProcessPreset,
-    PostProcessSettings,
+    AAMode, AASettings, AAQualityPreset, AAStats, AutoExposureParams, PostProcessEffect, PostProcessPreset,
+    PostProcessSettings, Tonemapper,
 };
@@ -4,7 +4,7 @@ use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use std::sync::{Arc, Mutex};
 
-use super::types::{PostProcessPreset, PostProcessSettings};
+use super::types::{AAStats, PostProcessEffect, PostProcessPreset, PostProcessSettings};
 
 /// Post-processing pipeline manager — owns all GPU resources for post-process effects.
 ///
@@ -49,6 +49,12 @@ pub struct PostProcessPipeline {
     pub last_time: f32,
     pub delta_time: f32,
 
+    // ── AA stats ──
+    pub aa_stats_enabled: bool,
+    pub aa_stats: Option<AAStats>,
+    /// Por frame slot: imagen cuyo contador de bordes grabó y lo que se midió.
+    pub aa_stats_pending: Vec<Option<(usize, AAStats)>>,
+
     // ── TAA ──
     pub taa_pipeline: Option<crate::compute::ComputePipeline>,
     pub taa_descriptor_layout: Option<vk::DescriptorSetLayout>,
@@ -108,6 +114,7 @@ impl PostProcessPipeline {
             bloom_upsample_sets: Vec::new(),
             auto_exposure_pipeline: None, exposure_buffers: Vec::new(),
             last_time: 0.0, delta_time: 0.0166,
+            aa_stats_enabled: false, aa_stats: None, aa_stats_pending: Vec::new(),
             taa_pipeline: None, taa_descriptor_layout: None, taa_descriptor_pool: None,
             taa_descriptor_sets: Vec::new(),
             fog_pipeline: None, fog_descriptor_layout: None, fog_descriptor_pool: None,
@@ -128,12 +135,7 @@ impl PostProcessPipeline {
 
     pub fn with_preset(preset: PostProcessPreset) -> Self {
         Self {
-            settings: match preset {
-                PostProcessPreset::None => PostProcessSettings::default(),
-                PostProcessPreset::Cinematic => PostProcessSettings::cinematic(),
-                PostProcessPreset::Vibrant => PostProcessSettings::vibrant(),
-                PostProcessPreset::Retro => PostProcessSettings::retro(),
-            },
+            settings: PostProcessSettings::from_preset(preset),
             enabled: true,
             pipeline: None, layout: None, descriptor_layout: None, descriptor_pool: None,
             descriptor_sets: Vec::new(), offscreen_images: Vec::new(), sampler: None,
@@ -147,6 +149,7 @@ impl PostProcessPipeline {
             bloom_upsample_sets: Vec::new(),
             auto_exposure_pipeline: None, exposure_buffers: Vec::new(),
             last_time: 0.0, delta_time: 0.0166,
+            aa_stats_enabled: false, aa_stats: None, aa_stats_pending: Vec::new(),
             taa_pipeline: None, taa_descriptor_layout: None, taa_descriptor_pool: None,
             taa_descriptor_sets: Vec::new(),
             fog_pipeline: None, fog_descriptor_layout: None, fog_descriptor_pool: None,
@@ -165,6 +168,22 @@ impl PostProcessPipeline {
         }
     }
 
+    /// Swaps in a preset's settings. Only push constants change, so no
+    /// pipeline or material has to be rebuilt; the frame time is preserved.
+    pub fn apply_preset(&mut self, preset: PostProcessPreset) {
+        let time = self.settings.time;
+        self.settings = PostProcessSettings::from_preset(preset);
+        self.settings.time = time;
+    }
+
+    pub fn set_effect(&mut self, effect: PostProcessEffect, enabled: bool) {
+        if enabled {
+            self.settings.enable_effect(effect);
+        } else {
+            self.settings.disable_effect(effect);
+        }
+    }
+
     pub fn update_time(&mut self, time: f32) {
         let raw_dt = time - self.last_time;
         self.delta_time = if raw_dt > 0.0 && raw_dt < 2.0 { raw_dt } else { 0.0166 };
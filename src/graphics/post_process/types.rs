@@ -34,6 +34,70 @@ pub enum PostProcessEffect {
     AutoExposure,
     MotionBlur,
     GTAO,
+    /// Anti-aliasing por distancia al borde (ver `AAMode::ADead`).
+    ADeadAA,
+}
+
+/// Curva que lleva el color HDR de la escena al rango del swapchain.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tonemapper {
+    /// Neutra y con buen manejo de colores saturados (la de siempre).
+    #[default]
+    AgX,
+    Reinhard,
+    /// Aproximación de Narkowicz del ACES filmic.
+    Aces,
+    /// Curva filmic de Uncharted 2 (Hable).
+    Uncharted2,
+}
+
+impl Tonemapper {
+    /// Valores desconocidos caen en `AgX`, igual que en los shaders.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::Reinhard,
+            2 => Self::Aces,
+            3 => Self::Uncharted2,
+            _ => Self::AgX,
+        }
+    }
+}
+
+/// Anti-aliasing del pase de post-proceso. Cambiarlo sólo toca los bits de
+/// `effect_mask`, así que no recrea pipelines ni swapchain.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AAMode {
+    Off,
+    /// FXAA: barato, suaviza a lo largo de la dirección del borde.
+    #[default]
+    Fxaa,
+    /// ADead-AA: estima con el gradiente de luma la distancia de cada píxel
+    /// al borde (como un SDF) y mezcla la parte del píxel que lo cruza.
+    ADead,
+}
+
+impl AAMode {
+    /// Valores desconocidos caen en `Off`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::Fxaa,
+            2 => Self::ADead,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// Lo que hizo el anti-aliasing en el último frame medido
+/// (`PostProcessPipeline::set_aa_stats_enabled`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AAStats {
+    pub mode: AAMode,
+    /// Píxeles que pasaron el umbral de borde y se suavizaron.
+    pub edges_detected: u32,
+    /// Píxeles que evaluó el filtro (todo el pase de post-proceso).
+    pub pixels_processed: u32,
 }
 
 /// Anti-Aliasing quality presets
@@ -120,6 +184,17 @@ impl AASettings {
         }
     }
 
+    pub fn from_preset(preset: AAQualityPreset) -> Self {
+        match preset {
+            AAQualityPreset::Off => Self { quality: AAQualityPreset::Off, ..Self::low() },
+            AAQualityPreset::Low => Self::low(),
+            AAQualityPreset::Medium => Self::medium(),
+            AAQualityPreset::High => Self::high(),
+            AAQualityPreset::Ultra => Self::ultra(),
+            AAQualityPreset::Cinematic => Self::cinematic(),
+        }
+    }
+
     pub fn cinematic() -> Self {
         Self {
             quality: AAQualityPreset::Cinematic,
@@ -177,9 +252,21 @@ pub struct PostProcessSettings {
     pub dof_focus_distance: f32,
     pub dof_aperture: f32,
     pub motion_blur_strength: f32,
+    /// `Tonemapper as u32`: curva que aplica `ToneMapping` tras la exposición.
+    pub tonemapper: u32,
+    /// Umbrales y ancho de borde de FXAA / ADead-AA (de `AASettings`).
+    pub aa_edge_threshold: f32,
+    pub aa_edge_threshold_min: f32,
+    pub aa_edge_width: f32,
+    pub aa_smoothness: f32,
+    /// `AA_FLAG_*` del shader; la pone el renderer en cada frame.
+    pub aa_flags: u32,
 }
 
-const _: () = assert!(mem::size_of::<PostProcessSettings>() == 144);
+const _: () = assert!(mem::size_of::<PostProcessSettings>() == 168);
+
+/// `aa_flags`: el shader cuenta los bordes suavizados.
+pub(crate) const AA_FLAG_COUNT_EDGES: u32 = 1;
 
 impl Default for PostProcessSettings {
     fn default() -> Self {
@@ -220,7 +307,14 @@ impl Default for PostProcessSettings {
             dof_focus_distance: 8.0,
             dof_aperture: 0.04,
             motion_blur_strength: 0.6,
+            tonemapper: Tonemapper::AgX as u32,
+            aa_edge_threshold: 0.0,
+            aa_edge_threshold_min: 0.0,
+            aa_edge_width: 0.0,
+            aa_smoothness: 0.0,
+            aa_flags: 0,
         };
+        settings.apply_aa_settings(&AASettings::default());
         settings.enable_effect(PostProcessEffect::ToneMapping);
         settings.enable_effect(PostProcessEffect::Vignette);
         settings.enable_effect(PostProcessEffect::FilmGrain);
@@ -250,6 +344,52 @@ impl PostProcessSettings {
         (self.effect_mask & (1 << (effect as u32))) != 0
     }
 
+    pub fn set_tonemapper(&mut self, tonemapper: Tonemapper) {
+        self.tonemapper = tonemapper as u32;
+    }
+
+    pub fn tonemapper(&self) -> Tonemapper {
+        Tonemapper::from_u32(self.tonemapper)
+    }
+
+    /// Activa un único modo de AA (apaga el otro).
+    pub fn set_aa_mode(&mut self, mode: AAMode) {
+        self.disable_effect(PostProcessEffect::FXAA);
+        self.disable_effect(PostProcessEffect::ADeadAA);
+        match mode {
+            AAMode::Off => {}
+            AAMode::Fxaa => self.enable_effect(PostProcessEffect::FXAA),
+            AAMode::ADead => self.enable_effect(PostProcessEffect::ADeadAA),
+        }
+    }
+
+    /// Con los dos bits puestos el shader usa FXAA.
+    pub fn aa_mode(&self) -> AAMode {
+        if self.is_effect_enabled(PostProcessEffect::FXAA) {
+            AAMode::Fxaa
+        } else if self.is_effect_enabled(PostProcessEffect::ADeadAA) {
+            AAMode::ADead
+        } else {
+            AAMode::Off
+        }
+    }
+
+    /// Copia umbrales y ancho de borde; el preset `Off` además apaga el AA.
+    pub fn apply_aa_settings(&mut self, aa: &AASettings) {
+        self.aa_edge_threshold = aa.edge_threshold;
+        self.aa_edge_threshold_min = aa.edge_threshold_min;
+        self.aa_edge_width = aa.edge_width;
+        self.aa_smoothness = aa.smoothness;
+        if aa.quality == AAQualityPreset::Off {
+            self.set_aa_mode(AAMode::Off);
+        }
+    }
+
+    /// Exposición en pasos de diafragma: `ev = 1` duplica la luz.
+    pub fn set_exposure_ev(&mut self, ev: f32) {
+        self.exposure = ev.exp2();
+    }
+
     pub fn cinematic() -> Self {
         let mut settings = Self::default();
         settings.enable_effect(PostProcessEffect::Vignette);
@@ -289,6 +429,20 @@ impl PostProcessSettings {
         settings
     }
 
+    pub fn from_preset(preset: PostProcessPreset) -> Self {
+        match preset {
+            PostProcessPreset::None => Self::default(),
+            PostProcessPreset::Cinematic => Self::cinematic(),
+            PostProcessPreset::Vibrant => Self::vibrant(),
+            PostProcessPreset::Retro => Self::retro(),
+        }
+    }
+
+    /// `true` when at least one effect is enabled; with none, the post pass is skipped.
+    pub fn any_effect_enabled(&self) -> bool {
+        self.effect_mask != 0
+    }
+
     pub fn retro() -> Self {
         let mut settings = Self::default();
         settings.enable_effect(PostProcessEffect::Sepia);
@@ -321,3 +475,29 @@ pub struct AutoExposureParams {
     pub max_exposure: f32,
     pub min_exposure: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aa_modes_are_exclusive_and_presets_only_touch_thresholds() {
+        let mut settings = PostProcessSettings::default();
+        assert_eq!(settings.aa_mode(), AAMode::Fxaa);
+        assert_eq!(settings.aa_edge_threshold, 0.125);
+
+        settings.set_aa_mode(AAMode::ADead);
+        assert_eq!(settings.aa_mode(), AAMode::ADead);
+        assert!(!settings.is_effect_enabled(PostProcessEffect::FXAA));
+
+        settings.apply_aa_settings(&AASettings::from_preset(AAQualityPreset::Ultra));
+        assert_eq!(settings.aa_mode(), AAMode::ADead);
+        assert_eq!(settings.aa_edge_width, AASettings::ultra().edge_width);
+
+        settings.apply_aa_settings(&AASettings::from_preset(AAQualityPreset::Off));
+        assert_eq!(settings.aa_mode(), AAMode::Off);
+        assert!(settings.is_effect_enabled(PostProcessEffect::ToneMapping));
+        assert_eq!(AAMode::from_u32(2), AAMode::ADead);
+        assert_eq!(AAMode::from_u32(9), AAMode::Off);
+    }
+}
@@ -0,0 +1,147 @@
+//! Per-object push constants for scene geometry
+//!
+//! `ObjectPushConstants` is the single source of truth for the 256-byte block
+//! that `render_geometry` pushes for every `SceneObject`. The first 192 bytes
+//! keep the layout the forward, textured and Live Link shaders were compiled
+//! against; the normal matrix lives in the tail so those shaders keep working
+//! unchanged while newer ones can read it.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat3, Mat4, Vec4};
+
+/// Size of the push constant range declared by every graphics pipeline.
+pub const OBJECT_PUSH_CONSTANT_SIZE: u32 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ObjectPushConstants {
+    pub mvp: [[f32; 4]; 4],
+    pub model: [[f32; 4]; 4],
+    pub camera_pos: [f32; 4], // .w = metallic
+    pub light_pos: [f32; 4],  // .w = roughness
+    pub color: [f32; 4],      // .w = anisotropy
+    pub emission: [f32; 4],   // .w = strength / SSS
+    /// Inverse-transpose of the model's upper 3x3, one column per vec4 (std430/std140 mat3 padding).
+    pub normal_matrix: [[f32; 4]; 3],
+    /// `.x` = index of the object's first joint matrix (skinned pipelines only),
+    /// `.y` = 1.0 when the object opts out of receiving shadows.
+    pub extra: [f32; 4],
+}
+
+const _: () = assert!(std::mem::size_of::<ObjectPushConstants>() == OBJECT_PUSH_CONSTANT_SIZE as usize);
+
+impl ObjectPushConstants {
+    pub fn new(mvp: Mat4, model: Mat4) -> Self {
+        Self {
+            mvp: mvp.to_cols_array_2d(),
+            model: model.to_cols_array_2d(),
+            camera_pos: [0.0; 4],
+            light_pos: [0.0; 4],
+            color: [1.0; 4],
+            emission: [0.0; 4],
+            normal_matrix: normal_matrix_columns(model),
+            extra: [0.0; 4],
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// Push block of the deferred lighting pass (`lighting_resolve.frag`). It is
+/// pushed through the same 256-byte range, so it must stay within it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DeferredLightingPushConstants {
+    /// Inverse of the view-projection the G-buffer was rendered with.
+    pub inv_view_proj: [[f32; 4]; 4],
+    /// Camera view matrix, used to pick the shadow cascade.
+    pub view: [[f32; 4]; 4],
+    pub camera_pos: [f32; 4],
+    /// Background where the G-buffer has no geometry (`Reactor::clear_color`).
+    pub clear_color: [f32; 4],
+}
+
+const _: () = assert!(std::mem::size_of::<DeferredLightingPushConstants>() <= OBJECT_PUSH_CONSTANT_SIZE as usize);
+
+impl DeferredLightingPushConstants {
+    pub fn new(view_proj: Mat4, view: Mat4, camera_pos: glam::Vec3) -> Self {
+        Self {
+            inv_view_proj: view_proj.inverse().to_cols_array_2d(),
+            view: view.to_cols_array_2d(),
+            camera_pos: [camera_pos.x, camera_pos.y, camera_pos.z, 1.0],
+            clear_color: [0.1, 0.1, 0.1, 1.0],
+        }
+    }
+
+    pub fn with_clear_color(mut self, color: glam::Vec4) -> Self {
+        self.clear_color = color.to_array();
+        self
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// Inverse-transpose of `model`'s upper 3x3. Falls back to the plain 3x3 when
+/// the matrix is singular (e.g. a zero scale on one axis).
+pub fn normal_matrix(model: Mat4) -> Mat3 {
+    let m = Mat3::from_mat4(model);
+    if m.determinant().abs() <= f32::EPSILON {
+        return m;
+    }
+    m.inverse().transpose()
+}
+
+fn normal_matrix_columns(model: Mat4) -> [[f32; 4]; 3] {
+    let n = normal_matrix(model);
+    [
+        Vec4::from((n.x_axis, 0.0)).to_array(),
+        Vec4::from((n.y_axis, 0.0)).to_array(),
+        Vec4::from((n.z_axis, 0.0)).to_array(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Quat, Vec3};
+
+    fn transformed_normal(model: Mat4, normal: Vec3) -> Vec3 {
+        (normal_matrix(model) * normal).normalize()
+    }
+
+    #[test]
+    fn normals_stay_perpendicular_under_non_uniform_scale() {
+        // Two objects with different non-uniform scales; the surface tangent
+        // goes through the model matrix, the normal through the normal matrix.
+        let objects = [
+            Mat4::from_scale_rotation_translation(Vec3::new(4.0, 1.0, 1.0), Quat::from_rotation_z(0.3), Vec3::X),
+            Mat4::from_scale_rotation_translation(Vec3::new(1.0, 0.25, 3.0), Quat::from_rotation_y(1.1), Vec3::NEG_Z),
+        ];
+        let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+        let tangent = Vec3::new(1.0, -1.0, 0.0).normalize();
+
+        for model in objects {
+            let n = transformed_normal(model, normal);
+            let t = model.transform_vector3(tangent).normalize();
+            assert!(n.dot(t).abs() < 1e-5, "normal not perpendicular: {}", n.dot(t));
+
+            // Transforming with the model matrix alone gets it wrong.
+            let naive = model.transform_vector3(normal).normalize();
+            assert!(naive.dot(t).abs() > 1e-2);
+        }
+    }
+
+    #[test]
+    fn packs_columns_with_padding() {
+        let model = Mat4::from_scale(Vec3::new(2.0, 4.0, 8.0));
+        let push = ObjectPushConstants::new(Mat4::IDENTITY, model);
+        assert_eq!(push.normal_matrix[0], [0.5, 0.0, 0.0, 0.0]);
+        assert_eq!(push.normal_matrix[1], [0.0, 0.25, 0.0, 0.0]);
+        assert_eq!(push.normal_matrix[2], [0.0, 0.0, 0.125, 0.0]);
+        assert_eq!(push.as_bytes().len(), OBJECT_PUSH_CONSTANT_SIZE as usize);
+    }
+}
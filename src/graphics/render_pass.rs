@@ -1,4 +1,5 @@
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use ash::vk;
@@ -160,6 +161,11 @@ impl RenderPass {
             },
         )
     }
+
+    /// Label the render pass. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_render_pass(self.handle, name);
+    }
 }
 
 impl Drop for RenderPass {
@@ -1,4 +1,5 @@
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use ash::vk;
@@ -8,14 +9,14 @@ pub struct Sampler {
     device: ArcDevice,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FilterMode {
     Nearest,
     Linear,
     Cubic,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WrapMode {
     Repeat,
     MirroredRepeat,
@@ -23,11 +24,14 @@ pub enum WrapMode {
     ClampToBorder,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SamplerConfig {
     pub mag_filter: FilterMode,
     pub min_filter: FilterMode,
     pub mipmap_mode: FilterMode,
     pub address_mode: WrapMode,
+    /// Requested anisotropy level, clamped to the device limit. Ignored when
+    /// the device has no `samplerAnisotropy`.
     pub anisotropy: Option<f32>,
     pub max_lod: f32,
 }
@@ -45,6 +49,23 @@ impl Default for SamplerConfig {
     }
 }
 
+impl SamplerConfig {
+    /// Trilinear, repeating, 8x anisotropic: what loaded textures use unless
+    /// told otherwise.
+    pub fn texture() -> Self {
+        Self { anisotropy: Some(8.0), ..Self::default() }
+    }
+}
+
+/// Anisotropy level actually used: `requested` clamped to `1..=max`, or none
+/// when either side is missing.
+fn clamp_anisotropy(requested: Option<f32>, max: Option<f32>) -> Option<f32> {
+    match (requested, max) {
+        (Some(level), Some(max)) if level > 1.0 => Some(level.min(max)),
+        _ => None,
+    }
+}
+
 impl Sampler {
     pub fn new(ctx: &VulkanContext, config: &SamplerConfig) -> ReactorResult<Self> {
         let mag_filter = match config.mag_filter {
@@ -69,7 +90,7 @@ impl Sampler {
             WrapMode::ClampToBorder => vk::SamplerAddressMode::CLAMP_TO_BORDER,
         };
 
-        let (anisotropy_enable, max_anisotropy) = match config.anisotropy {
+        let (anisotropy_enable, max_anisotropy) = match clamp_anisotropy(config.anisotropy, ctx.max_sampler_anisotropy) {
             Some(level) => (true, level),
             None => (false, 1.0),
         };
@@ -122,6 +143,11 @@ impl Sampler {
             },
         )
     }
+
+    /// Label the sampler. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        namer.name_sampler(self.handle, name);
+    }
 }
 
 impl Drop for Sampler {
@@ -131,3 +157,17 @@ impl Drop for Sampler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anisotropy_is_clamped_to_the_device() {
+        assert_eq!(clamp_anisotropy(Some(8.0), Some(16.0)), Some(8.0));
+        assert_eq!(clamp_anisotropy(Some(16.0), Some(4.0)), Some(4.0));
+        assert_eq!(clamp_anisotropy(Some(8.0), None), None);
+        assert_eq!(clamp_anisotropy(Some(1.0), Some(16.0)), None);
+        assert_eq!(clamp_anisotropy(None, Some(16.0)), None);
+    }
+}
@@ -11,15 +11,24 @@ use super::types::{CompiledShader, ShaderLanguage, ShaderReflection, ShaderStage
 
 pub struct ShaderCompiler {
     validator: Validator,
+    ray_query: bool,
 }
 
 impl ShaderCompiler {
     pub fn new() -> Self {
         Self {
             validator: Validator::new(ValidationFlags::all(), Capabilities::all()),
+            ray_query: false,
         }
     }
 
+    /// Lets shaders use `ray_query` (emits `RayQueryKHR`). The device must
+    /// have `VK_KHR_ray_query` enabled.
+    pub fn with_ray_query(mut self) -> Self {
+        self.ray_query = true;
+        self
+    }
+
     pub fn compile_file(
         &mut self,
         path: &Path,
@@ -165,6 +174,9 @@ impl ShaderCompiler {
 
         let mut caps = naga::FastHashSet::default();
         caps.insert(spv::Capability::Shader);
+        if self.ray_query {
+            caps.insert(spv::Capability::RayQueryKHR);
+        }
 
         let options = spv::Options {
             lang_version: (1, 3),
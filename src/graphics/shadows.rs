@@ -3,6 +3,8 @@
 // =============================================================================
 
 use glam::{Mat4, Vec3};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 /// Shadow map configuration
 #[derive(Clone, Debug)]
@@ -255,6 +257,45 @@ impl Default for ShadowMap {
     }
 }
 
+/// Everything the shadow pass output depends on. When a frame produces the
+/// same key as the last rendered pass, the shadow map is still valid and the
+/// pass can be skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShadowCacheKey {
+    light: u64,
+    casters: u64,
+}
+
+impl ShadowCacheKey {
+    /// `casters` yields each shadow caster's transform and a mesh identity
+    /// (e.g. the `Arc` pointer), in draw order.
+    pub fn new(
+        light_direction: Vec3,
+        camera_view: Mat4,
+        camera_proj: Mat4,
+        casters: impl IntoIterator<Item = (Mat4, usize)>,
+    ) -> Self {
+        let mut light = DefaultHasher::new();
+        hash_floats(&mut light, &light_direction.to_array());
+        hash_floats(&mut light, &camera_view.to_cols_array());
+        hash_floats(&mut light, &camera_proj.to_cols_array());
+
+        let mut hasher = DefaultHasher::new();
+        for (transform, mesh) in casters {
+            hash_floats(&mut hasher, &transform.to_cols_array());
+            mesh.hash(&mut hasher);
+        }
+
+        Self { light: light.finish(), casters: hasher.finish() }
+    }
+}
+
+fn hash_floats(hasher: &mut DefaultHasher, values: &[f32]) {
+    for v in values {
+        v.to_bits().hash(hasher);
+    }
+}
+
 /// Shadow uniform data for shaders
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
@@ -332,3 +373,22 @@ pub fn calculate_shadow_factor(
     // This is a placeholder that returns fully lit
     1.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(camera_x: f32, caster_x: f32) -> ShadowCacheKey {
+        let view = Mat4::look_at_rh(Vec3::new(camera_x, 2.0, 5.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(1.0, 1.5, 0.1, 100.0);
+        let casters = [(Mat4::from_translation(Vec3::new(caster_x, 0.0, 0.0)), 1usize), (Mat4::IDENTITY, 2)];
+        ShadowCacheKey::new(Vec3::NEG_Y, view, proj, casters)
+    }
+
+    #[test]
+    fn cache_key_only_changes_when_the_shadow_inputs_do() {
+        assert_eq!(key(0.0, 1.0), key(0.0, 1.0));
+        assert_ne!(key(0.0, 1.0), key(0.5, 1.0));
+        assert_ne!(key(0.0, 1.0), key(0.0, 1.5));
+    }
+}
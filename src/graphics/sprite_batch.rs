@@ -0,0 +1,231 @@
+//! Batched 2D sprites for HUDs and 2D games.
+//!
+//! `SpriteBatch` collects textured quads between `begin` and `end`, then
+//! sorts them by layer and texture so that every run of sprites sharing a
+//! texture becomes a single draw. The quads reuse `Vertex` the way
+//! `TextRenderer` does: `position.xy` is the corner, `position.z` carries the
+//! tint alpha, `color` the tint RGB and `uv` the texture coordinate.
+//!
+//! Without a `Camera2D` coordinates are pixels with the origin at the
+//! top-left corner of the window, like the text overlay.
+
+use crate::resources::texture::Texture;
+use crate::resources::vertex::Vertex;
+use glam::{Vec2, Vec4};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Vertices emitted per sprite (two triangles, no index buffer).
+pub const SPRITE_VERTICES: usize = 6;
+
+/// One textured quad.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprite {
+    /// Top-left corner before rotation.
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Radians, around the center of the quad.
+    pub rotation: f32,
+    /// `[u0, v0, u1, v1]` inside the texture (an atlas cell, or `[0, 0, 1, 1]`).
+    pub uv_rect: [f32; 4],
+    /// Multiplies the texel; alpha is blended.
+    pub tint: Vec4,
+    /// Lower layers are drawn first. Within a layer sprites are grouped by
+    /// texture, so overlapping sprites that must keep their order belong in
+    /// different layers.
+    pub layer: i32,
+}
+
+impl Sprite {
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self { position, size, ..Default::default() }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_uv_rect(mut self, u0: f32, v0: f32, u1: f32, v1: f32) -> Self {
+        self.uv_rect = [u0, v0, u1, v1];
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Vec4) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// The two triangles of this quad.
+    pub fn vertices(&self) -> [Vertex; SPRITE_VERTICES] {
+        let half = self.size * 0.5;
+        let center = self.position + half;
+        let (sin, cos) = self.rotation.sin_cos();
+        let [u0, v0, u1, v1] = self.uv_rect;
+        let corner = |x: f32, y: f32, u: f32, v: f32| {
+            let local = Vec2::new(x * half.x, y * half.y);
+            let p = center + Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+            Vertex {
+                position: [p.x, p.y, self.tint.w],
+                color: self.tint.truncate().to_array(),
+                uv: [u, v],
+            }
+        };
+        let top_left = corner(-1.0, -1.0, u0, v0);
+        let top_right = corner(1.0, -1.0, u1, v0);
+        let bottom_right = corner(1.0, 1.0, u1, v1);
+        let bottom_left = corner(-1.0, 1.0, u0, v1);
+        [top_left, bottom_left, bottom_right, top_left, bottom_right, top_right]
+    }
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            size: Vec2::ONE,
+            rotation: 0.0,
+            uv_rect: [0.0, 0.0, 1.0, 1.0],
+            tint: Vec4::ONE,
+            layer: 0,
+        }
+    }
+}
+
+/// A run of consecutive vertices drawn with one texture.
+pub struct SpriteDraw {
+    pub texture: Arc<Texture>,
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// Sprites queued between `begin` and `end`.
+#[derive(Default)]
+pub struct SpriteBatch {
+    queued: Vec<(i32, u32, Sprite)>,
+    textures: Vec<Arc<Texture>>,
+    texture_slots: HashMap<usize, u32>,
+    vertices: Vec<Vertex>,
+    draws: Vec<SpriteDraw>,
+}
+
+impl SpriteBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops everything queued or built so far.
+    pub fn begin(&mut self) {
+        self.queued.clear();
+        self.textures.clear();
+        self.texture_slots.clear();
+        self.vertices.clear();
+        self.draws.clear();
+    }
+
+    pub fn draw(&mut self, texture: &Arc<Texture>, sprite: Sprite) {
+        let slot = *self.texture_slots.entry(Arc::as_ptr(texture) as usize).or_insert_with(|| {
+            self.textures.push(texture.clone());
+            self.textures.len() as u32 - 1
+        });
+        self.queued.push((sprite.layer, slot, sprite));
+    }
+
+    /// Sorts the queued sprites and builds the vertex stream and draw list.
+    pub fn end(&mut self) {
+        let (vertices, runs) = build_runs(&mut self.queued);
+        self.vertices = vertices;
+        self.draws = runs
+            .into_iter()
+            .map(|(slot, first_vertex, vertex_count)| SpriteDraw {
+                texture: self.textures[slot as usize].clone(),
+                first_vertex,
+                vertex_count,
+            })
+            .collect();
+        self.queued.clear();
+    }
+
+    /// Vertices built by the last `end`.
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Draw ranges built by the last `end`, one per texture change.
+    pub fn draws(&self) -> &[SpriteDraw] {
+        &self.draws
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    /// Sprites queued since `begin` and not yet built.
+    pub fn queued_len(&self) -> usize {
+        self.queued.len()
+    }
+}
+
+/// Stable sort by (layer, texture slot) and merge into `(slot, first, count)`.
+fn build_runs(queued: &mut [(i32, u32, Sprite)]) -> (Vec<Vertex>, Vec<(u32, u32, u32)>) {
+    queued.sort_by_key(|&(layer, slot, _)| (layer, slot));
+    let mut vertices = Vec::with_capacity(queued.len() * SPRITE_VERTICES);
+    let mut runs: Vec<(u32, u32, u32)> = Vec::new();
+    for &(_, slot, ref sprite) in queued.iter() {
+        let first = vertices.len() as u32;
+        vertices.extend_from_slice(&sprite.vertices());
+        match runs.last_mut() {
+            Some(run) if run.0 == slot => run.2 += SPRITE_VERTICES as u32,
+            _ => runs.push((slot, first, SPRITE_VERTICES as u32)),
+        }
+    }
+    (vertices, runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrotated_quad_covers_its_rect_and_uv_cell() {
+        let sprite = Sprite::new(Vec2::new(10.0, 20.0), Vec2::new(4.0, 2.0))
+            .with_uv_rect(0.25, 0.5, 0.5, 0.75)
+            .with_tint(Vec4::new(1.0, 0.5, 0.25, 0.8));
+        let vertices = sprite.vertices();
+        let top_left = vertices[0];
+        let bottom_right = vertices[2];
+        assert_eq!(top_left.position, [10.0, 20.0, 0.8]);
+        assert_eq!(top_left.uv, [0.25, 0.5]);
+        assert_eq!(bottom_right.position, [14.0, 22.0, 0.8]);
+        assert_eq!(bottom_right.uv, [0.5, 0.75]);
+        assert_eq!(top_left.color, [1.0, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn rotation_turns_around_the_center() {
+        let sprite = Sprite::new(Vec2::ZERO, Vec2::new(2.0, 2.0)).with_rotation(std::f32::consts::FRAC_PI_2);
+        let top_left = sprite.vertices()[0].position;
+        // (-1, -1) relativo al centro (1, 1) gira a (1, -1).
+        assert!((top_left[0] - 2.0).abs() < 1e-5 && top_left[1].abs() < 1e-5, "{top_left:?}");
+    }
+
+    #[test]
+    fn sprites_sharing_a_texture_merge_into_one_run_per_layer() {
+        let sprite = Sprite::default();
+        let mut queued = vec![
+            (0, 1, sprite),
+            (0, 0, sprite),
+            (0, 1, sprite),
+            (1, 0, sprite),
+            (0, 0, sprite),
+        ];
+        let (vertices, runs) = build_runs(&mut queued);
+        assert_eq!(vertices.len(), 5 * SPRITE_VERTICES);
+        assert_eq!(runs, vec![(0, 0, 12), (1, 12, 12), (0, 24, 6)]);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
+use crate::graphics::image::Image;
 use ash::khr::swapchain;
 use ash::{vk, Device};
+use gpu_allocator::vulkan::Allocator;
+use std::sync::{Arc, Mutex};
 
 pub struct Swapchain {
     pub loader: swapchain::Device,
@@ -11,6 +14,10 @@ pub struct Swapchain {
     pub format: vk::Format,
     pub extent: vk::Extent2D,
     pub image_count: u32,
+    /// Images were created with `TRANSFER_SRC`, so frames can be copied out.
+    pub supports_readback: bool,
+    /// Backing images of an offscreen swapchain (`handle` is null).
+    offscreen: Vec<Image>,
 }
 
 impl Swapchain {
@@ -108,6 +115,16 @@ impl Swapchain {
             surface_capabilities.min_image_count + 1
         };
 
+        // TRANSFER_SRC lets screenshots copy the presented image; it is
+        // optional, so only request it where the surface allows it.
+        let supports_readback = surface_capabilities
+            .supported_usage_flags
+            .contains(vk::ImageUsageFlags::TRANSFER_SRC);
+        let mut image_usage = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        if supports_readback {
+            image_usage |= vk::ImageUsageFlags::TRANSFER_SRC;
+        }
+
         let create_info = vk::SwapchainCreateInfoKHR::default()
             .surface(surface)
             .min_image_count(image_count)
@@ -115,7 +132,7 @@ impl Swapchain {
             .image_color_space(format.color_space)
             .image_extent(extent)
             .image_array_layers(1)
-            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_usage(image_usage)
             .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
             .pre_transform(surface_capabilities.current_transform)
             .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
@@ -178,10 +195,81 @@ impl Swapchain {
             format: format.format,
             extent,
             image_count,
+            supports_readback,
+            offscreen: Vec::new(),
         })
     }
 
+    /// Offscreen stand-in for a swapchain: `image_count` color images the
+    /// renderer draws into exactly as it would into swapchain images. They
+    /// end each frame in `PRESENT_SRC_KHR` and can be copied out (usage
+    /// includes `TRANSFER_SRC`). There is no `vk::SwapchainKHR` to acquire
+    /// from or present to.
+    pub fn offscreen(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        width: u32,
+        height: u32,
+        image_count: u32,
+    ) -> ReactorResult<Self> {
+        // Same format the windowed path prefers, so pipelines are interchangeable.
+        let format = vk::Format::B8G8R8A8_SRGB;
+        let offscreen = (0..image_count)
+            .map(|_| {
+                Image::new(
+                    ctx,
+                    allocator.clone(),
+                    width,
+                    height,
+                    format,
+                    vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+                    vk::ImageAspectFlags::COLOR,
+                    1,
+                )
+            })
+            .collect::<ReactorResult<Vec<_>>>()?;
+
+        Ok(Self {
+            loader: swapchain::Device::new(ctx.ash_instance(), ctx.ash_device()),
+            handle: vk::SwapchainKHR::null(),
+            images: offscreen.iter().map(|image| image.handle).collect(),
+            image_views: offscreen.iter().map(|image| image.view).collect(),
+            format,
+            extent: vk::Extent2D { width, height },
+            image_count,
+            supports_readback: true,
+            offscreen,
+        })
+    }
+
+    /// Offscreen swapchain over one image owned elsewhere (a render target's
+    /// texture); `destroy` leaves it alone.
+    pub(crate) fn borrowed(ctx: &VulkanContext, image: &Image) -> Self {
+        Self {
+            loader: swapchain::Device::new(ctx.ash_instance(), ctx.ash_device()),
+            handle: vk::SwapchainKHR::null(),
+            images: vec![image.handle],
+            image_views: vec![image.view],
+            format: image.format,
+            extent: vk::Extent2D { width: image.extent.width, height: image.extent.height },
+            image_count: 1,
+            supports_readback: true,
+            offscreen: Vec::new(),
+        }
+    }
+
+    pub fn is_offscreen(&self) -> bool {
+        self.handle == vk::SwapchainKHR::null()
+    }
+
     pub fn destroy(&mut self, device: &Device) {
+        if self.is_offscreen() {
+            // Views and memory are owned by the `Image`s.
+            self.offscreen.clear();
+            self.images.clear();
+            self.image_views.clear();
+            return;
+        }
         unsafe {
             for &view in &self.image_views {
                 device.destroy_image_view(view, None);
@@ -0,0 +1,275 @@
+//! Bitmap-font text overlay.
+//!
+//! `TextRenderer` turns strings into screen-space quads using an embedded
+//! 8×8 monospace font (printable ASCII, public-domain `font8x8_basic`). The
+//! glyph bitmaps are baked into the overlay fragment shader, so the GPU side
+//! needs no texture or descriptor set: each vertex carries its glyph index in
+//! `position.z` and the glyph-local texel in `uv`.
+//!
+//! Coordinates are pixels with the origin at the top-left corner of the
+//! window; `size` is the glyph height in pixels.
+
+use crate::resources::vertex::Vertex;
+use glam::{Mat4, Vec2, Vec3};
+use std::fmt::Write;
+
+/// Glyph cell size in font texels.
+pub const GLYPH_SIZE: u32 = 8;
+
+/// First character in `FONT_8X8` (space).
+pub const FIRST_GLYPH: u8 = b' ';
+
+/// Row bitmaps for ASCII 0x20..=0x7F. Bit 0 of each byte is the leftmost texel.
+pub const FONT_8X8: [[u8; 8]; 96] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // '!'
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // '#'
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // '$'
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // '%'
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // '&'
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '''
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // '('
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // ')'
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ','
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // '.'
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // '/'
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // '0'
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // '1'
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // '2'
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // '3'
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // '4'
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // '5'
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // '6'
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // '7'
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // '8'
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ';'
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // '<'
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // '='
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // '>'
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // '?'
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // '@'
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // 'A'
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // 'B'
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // 'C'
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // 'D'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // 'E'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // 'F'
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // 'G'
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // 'H'
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'I'
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // 'J'
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // 'K'
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // 'L'
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // 'M'
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // 'N'
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // 'O'
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // 'P'
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // 'Q'
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // 'R'
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // 'S'
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'T'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // 'U'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'V'
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // 'W'
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // 'X'
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // 'Y'
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // 'Z'
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // '['
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // '\'
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ']'
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // 'a'
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // 'b'
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // 'c'
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // 'd'
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // 'e'
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // 'f'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'g'
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // 'h'
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'i'
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // 'j'
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // 'k'
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'l'
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // 'm'
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // 'n'
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // 'o'
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // 'p'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // 'q'
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // 'r'
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // 's'
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // 't'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // 'u'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'v'
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // 'w'
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // 'x'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'y'
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // 'z'
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // '{'
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // '|'
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // '}'
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // DEL
+];
+
+/// Glyph index for `c`; anything outside printable ASCII draws as `?`.
+pub fn glyph_index(c: char) -> u32 {
+    match c {
+        ' '..='~' => c as u32 - FIRST_GLYPH as u32,
+        _ => '?' as u32 - FIRST_GLYPH as u32,
+    }
+}
+
+/// `FONT_8X8` as a GLSL `const uint FONT[192]` (two words per glyph, rows
+/// 0-3 in the first word and 4-7 in the second, row 0 in the low byte).
+pub fn font_glsl_array() -> String {
+    let mut glsl = format!("const uint FONT[{}] = uint[](", FONT_8X8.len() * 2);
+    for (i, glyph) in FONT_8X8.iter().enumerate() {
+        let lo = u32::from_le_bytes([glyph[0], glyph[1], glyph[2], glyph[3]]);
+        let hi = u32::from_le_bytes([glyph[4], glyph[5], glyph[6], glyph[7]]);
+        let sep = if i + 1 == FONT_8X8.len() { "" } else { ", " };
+        let _ = write!(glsl, "0x{:08X}u, 0x{:08X}u{}", lo, hi, sep);
+    }
+    glsl.push_str(");\n");
+    glsl
+}
+
+/// Batches text into overlay quads for one frame.
+#[derive(Default)]
+pub struct TextRenderer {
+    vertices: Vec<Vertex>,
+}
+
+impl TextRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `text` with its top-left corner at `position` (pixels). `\n`
+    /// starts a new line below the first one.
+    pub fn draw_text(&mut self, position: Vec2, size: f32, color: Vec3, text: &str) {
+        let size = size.max(1.0);
+        let mut pen = position;
+        for c in text.chars() {
+            if c == '\n' {
+                pen = Vec2::new(position.x, pen.y + size);
+                continue;
+            }
+            if c != ' ' {
+                self.push_glyph(pen, size, color, glyph_index(c));
+            }
+            pen.x += size;
+        }
+    }
+
+    /// Queues `text` centered above `world_position` as seen through
+    /// `view_projection` on a `viewport`-sized target. Points behind the
+    /// camera are skipped.
+    pub fn draw_text_3d(
+        &mut self,
+        world_position: Vec3,
+        view_projection: &Mat4,
+        viewport: Vec2,
+        size: f32,
+        color: Vec3,
+        text: &str,
+    ) {
+        let Some(screen) = project_to_screen(world_position, view_projection, viewport) else {
+            return;
+        };
+        let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0) as f32 * size;
+        self.draw_text(screen - Vec2::new(width * 0.5, size), size, color, text);
+    }
+
+    /// Width and height in pixels that `text` occupies at `size`.
+    pub fn measure(text: &str, size: f32) -> Vec2 {
+        let columns = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let rows = text.lines().count().max(1);
+        Vec2::new(columns as f32, rows as f32) * size
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    fn push_glyph(&mut self, origin: Vec2, size: f32, color: Vec3, glyph: u32) {
+        let texels = GLYPH_SIZE as f32;
+        let corner = |dx: f32, dy: f32| {
+            Vertex::new(
+                Vec3::new(origin.x + dx * size, origin.y + dy * size, glyph as f32),
+                color,
+                Vec2::new(dx * texels, dy * texels),
+            )
+        };
+        let (tl, tr, bl, br) = (corner(0.0, 0.0), corner(1.0, 0.0), corner(0.0, 1.0), corner(1.0, 1.0));
+        self.vertices.extend_from_slice(&[tl, bl, tr, tr, bl, br]);
+    }
+}
+
+/// Pixel position (top-left origin) of `world_position`, or `None` behind the camera.
+pub fn project_to_screen(world_position: Vec3, view_projection: &Mat4, viewport: Vec2) -> Option<Vec2> {
+    let clip = *view_projection * world_position.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    Some(Vec2::new((ndc.x * 0.5 + 0.5) * viewport.x, (ndc.y * 0.5 + 0.5) * viewport.y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_visible_glyph_is_one_quad() {
+        let mut text = TextRenderer::new();
+        text.draw_text(Vec2::new(10.0, 20.0), 16.0, Vec3::ONE, "FPS 60");
+        // The space advances the pen but emits nothing.
+        assert_eq!(text.vertices().len(), 5 * 6);
+
+        let first = text.vertices()[0];
+        assert_eq!(first.position, [10.0, 20.0, glyph_index('F') as f32]);
+        let last_glyph_x = text.vertices()[4 * 6].position[0];
+        assert_eq!(last_glyph_x, 10.0 + 5.0 * 16.0);
+    }
+
+    #[test]
+    fn newline_returns_to_the_left_margin() {
+        let mut text = TextRenderer::new();
+        text.draw_text(Vec2::new(4.0, 0.0), 8.0, Vec3::ONE, "ab\nc");
+        let c = text.vertices()[2 * 6].position;
+        assert_eq!((c[0], c[1]), (4.0, 8.0));
+        assert_eq!(TextRenderer::measure("ab\nc", 8.0), Vec2::new(16.0, 16.0));
+    }
+
+    #[test]
+    fn unknown_characters_fall_back_to_question_mark() {
+        assert_eq!(glyph_index('é'), glyph_index('?'));
+        assert_eq!(glyph_index(' '), 0);
+        assert_eq!(FONT_8X8[glyph_index('A') as usize][0], 0x0C);
+    }
+
+    #[test]
+    fn points_behind_the_camera_are_not_projected() {
+        let vp = Mat4::perspective_rh(1.0, 1.0, 0.1, 100.0);
+        let viewport = Vec2::new(100.0, 100.0);
+        let center = project_to_screen(Vec3::new(0.0, 0.0, -5.0), &vp, viewport).unwrap();
+        assert!((center - Vec2::splat(50.0)).length() < 1e-3);
+        assert!(project_to_screen(Vec3::new(0.0, 0.0, 5.0), &vp, viewport).is_none());
+    }
+}
@@ -1,6 +1,7 @@
-use crate::core::error::ReactorResult;
+use crate::core::error::{ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use crate::graphics::buffer::Buffer;
+use crate::graphics::descriptors::{DescriptorAllocator, PooledDescriptorSet};
 use ash::vk;
 use bytemuck::{Pod, Zeroable};
 use gpu_allocator::vulkan::Allocator;
@@ -100,7 +101,7 @@ impl Default for LightData {
     }
 }
 
-pub const MAX_LIGHTS: usize = 16;
+pub const MAX_LIGHTS: usize = 128;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -151,6 +152,21 @@ impl Default for MaterialUniformData {
     }
 }
 
+impl MaterialUniformData {
+    /// Bakes a `PBRMaterial` description; `alpha` goes to `base_color.w`.
+    pub fn from_pbr(mat: &crate::resources::pbr_material::PBRMaterial) -> Self {
+        Self {
+            base_color: [mat.albedo.x, mat.albedo.y, mat.albedo.z, mat.alpha],
+            metallic: mat.metallic,
+            roughness: mat.roughness,
+            ao: mat.ao,
+            emissive_strength: mat.emissive_strength,
+            emissive_color: [mat.emissive.x, mat.emissive.y, mat.emissive.z, 1.0],
+            ..Self::default()
+        }
+    }
+}
+
 /// Uniform buffer wrapper with double/triple buffering support
 pub struct UniformBuffer<T: Pod + Zeroable> {
     buffers: Vec<Buffer>,
@@ -208,4 +224,234 @@ impl<T: Pod + Zeroable> UniformBuffer<T> {
     pub fn size(&self) -> u64 {
         std::mem::size_of::<T>() as u64
     }
+
+    /// One set per frame, each pointing at that frame's buffer through
+    /// `binding`, taken from the shared allocator.
+    pub fn descriptor_sets(
+        &self,
+        ctx: &VulkanContext,
+        descriptors: &Arc<Mutex<DescriptorAllocator>>,
+        layout: vk::DescriptorSetLayout,
+        binding: u32,
+    ) -> ReactorResult<Vec<PooledDescriptorSet>> {
+        let mut sets = Vec::with_capacity(self.buffers.len());
+        for buffer in &self.buffers {
+            let set = PooledDescriptorSet::new(descriptors, layout)?;
+            let buffer_info = [vk::DescriptorBufferInfo::default().buffer(buffer.handle).offset(0).range(self.size())];
+            let write = vk::WriteDescriptorSet::default()
+                .dst_set(set.handle())
+                .dst_binding(binding)
+                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                .buffer_info(&buffer_info);
+            unsafe { ctx.ash_device().update_descriptor_sets(&[write], &[]) };
+            sets.push(set);
+        }
+        Ok(sets)
+    }
+}
+
+/// Where one descriptor set per frame reads the ring: rewritten whenever that
+/// frame's buffer is replaced.
+struct RingBinding {
+    sets: Vec<vk::DescriptorSet>,
+    binding: u32,
+    range: u64,
+}
+
+/// Usage of a `DynamicUniformRing`, in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UniformRingStats {
+    /// Size of each frame's buffer.
+    pub capacity: u64,
+    /// Allocated so far in the current frame.
+    pub used: u64,
+    /// Most any frame has used.
+    pub high_water: u64,
+    /// Allocations refused because the frame ran out of space.
+    pub overflows: u64,
+}
+
+/// `offset` rounded up to `alignment` (a power of two).
+fn align_up(offset: u64, alignment: u64) -> u64 {
+    (offset + alignment - 1) & !(alignment - 1)
+}
+
+/// Per-frame uniform data bump-allocated out of one host-visible buffer per
+/// frame in flight. Descriptors use `UNIFORM_BUFFER_DYNAMIC`, so a draw only
+/// supplies the offset `push` returned.
+///
+/// A frame that runs out of space gets `None` for the rest of its
+/// allocations and the ring grows each frame's buffer the next time that
+/// frame begins, once its fence has been waited.
+pub struct DynamicUniformRing {
+    allocator: Arc<Mutex<Allocator>>,
+    buffers: Vec<Buffer>,
+    alignment: u64,
+    /// Capacity every buffer should have; buffers below it are replaced in
+    /// `begin_frame`.
+    target_capacity: u64,
+    current_frame: usize,
+    head: u64,
+    bindings: Vec<RingBinding>,
+    stats: UniformRingStats,
+}
+
+impl DynamicUniformRing {
+    pub fn new(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        frame_count: usize,
+        capacity: u64,
+    ) -> ReactorResult<Self> {
+        let limits = unsafe { ctx.ash_instance().get_physical_device_properties(ctx.physical_device) }.limits;
+        let alignment = limits.min_uniform_buffer_offset_alignment.max(1);
+        let capacity = align_up(capacity.max(alignment), alignment);
+        let mut buffers = Vec::with_capacity(frame_count);
+        for _ in 0..frame_count {
+            buffers.push(Buffer::new_uniform(ctx, allocator.clone(), capacity)?);
+        }
+        Ok(Self {
+            allocator,
+            buffers,
+            alignment,
+            target_capacity: capacity,
+            current_frame: 0,
+            head: 0,
+            bindings: Vec::new(),
+            stats: UniformRingStats { capacity, ..Default::default() },
+        })
+    }
+
+    /// Points `binding` of `sets[frame]` at frame `frame`'s buffer with
+    /// `range` bytes visible from each dynamic offset. Call while no frame
+    /// is in flight; later the ring keeps the sets up to date itself.
+    pub fn register_binding(
+        &mut self,
+        ctx: &VulkanContext,
+        sets: &[vk::DescriptorSet],
+        binding: u32,
+        range: u64,
+    ) -> ReactorResult<()> {
+        if sets.len() != self.buffers.len() {
+            return Err(ReactorError::invalid_parameter(format!(
+                "Uniform ring has {} frames but {} descriptor sets were given",
+                self.buffers.len(),
+                sets.len()
+            )));
+        }
+        self.target_capacity = self.target_capacity.max(align_up(range, self.alignment));
+        let view = RingBinding { sets: sets.to_vec(), binding, range };
+        for frame in 0..self.buffers.len() {
+            self.grow_frame(ctx, frame)?;
+            Self::write_binding(ctx, &view, frame, &self.buffers[frame]);
+        }
+        self.bindings.push(view);
+        Ok(())
+    }
+
+    /// Starts allocating for `frame`, whose previous submission must have
+    /// completed. Grows its buffer first if an earlier frame overflowed.
+    pub fn begin_frame(&mut self, ctx: &VulkanContext, frame: usize) -> ReactorResult<()> {
+        self.current_frame = frame % self.buffers.len();
+        self.head = 0;
+        self.stats.used = 0;
+        self.grow_frame(ctx, self.current_frame)
+    }
+
+    /// Copies `data` into the current frame and returns its dynamic offset,
+    /// or `None` when the frame is full.
+    pub fn push<T: Pod>(&mut self, data: &T) -> Option<u32> {
+        let bytes = bytemuck::bytes_of(data);
+        let offset = align_up(self.head, self.alignment);
+        let end = offset + bytes.len() as u64;
+        let buffer = &self.buffers[self.current_frame];
+        if end > buffer.size {
+            self.stats.overflows += 1;
+            // Grow by half again over what this frame wanted.
+            let wanted = end + end / 2;
+            self.target_capacity = self.target_capacity.max(align_up(wanted, self.alignment));
+            return None;
+        }
+        let ptr = buffer.map::<u8>()?;
+        unsafe {
+            ptr.add(offset as usize).copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+        }
+        self.head = end;
+        self.stats.used = end;
+        self.stats.high_water = self.stats.high_water.max(end);
+        Some(offset as u32)
+    }
+
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    pub fn stats(&self) -> UniformRingStats {
+        self.stats
+    }
+
+    fn grow_frame(&mut self, ctx: &VulkanContext, frame: usize) -> ReactorResult<()> {
+        if self.buffers[frame].size >= self.target_capacity {
+            return Ok(());
+        }
+        let buffer = Buffer::new_uniform(ctx, self.allocator.clone(), self.target_capacity)?;
+        for view in &self.bindings {
+            Self::write_binding(ctx, view, frame, &buffer);
+        }
+        log::debug!("Uniform ring frame {} grown to {} bytes", frame, self.target_capacity);
+        // The old buffer's destruction waits for the frames still using it.
+        self.buffers[frame] = buffer;
+        self.stats.capacity = self.target_capacity;
+        Ok(())
+    }
+
+    fn write_binding(ctx: &VulkanContext, view: &RingBinding, frame: usize, buffer: &Buffer) {
+        let buffer_info = [vk::DescriptorBufferInfo::default().buffer(buffer.handle).offset(0).range(view.range)];
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(view.sets[frame])
+            .dst_binding(view.binding)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
+            .buffer_info(&buffer_info);
+        unsafe { ctx.ash_device().update_descriptor_sets(&[write], &[]) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn align_up_rounds_to_the_next_multiple() {
+        assert_eq!(align_up(0, 256), 0);
+        assert_eq!(align_up(1, 256), 256);
+        assert_eq!(align_up(256, 256), 256);
+        assert_eq!(align_up(257, 64), 320);
+    }
+
+    #[test]
+    fn light_block_matches_its_std140_size() {
+        // ambient (16) + count/padding (16) + 128 lights of 64 bytes.
+        assert_eq!(std::mem::size_of::<LightData>(), 64);
+        assert_eq!(std::mem::size_of::<LightUniformData>(), 32 + 64 * MAX_LIGHTS);
+    }
+
+    /// `offset_of!` only exists since Rust 1.77: measure on a zeroed value.
+    macro_rules! field_offset {
+        ($ty:ty, $field:ident) => {{
+            let value: $ty = bytemuck::Zeroable::zeroed();
+            std::ptr::addr_of!(value.$field) as usize - std::ptr::addr_of!(value) as usize
+        }};
+    }
+
+    #[test]
+    fn shader_blocks_keep_their_offsets() {
+        assert_eq!(std::mem::size_of::<GlobalUniformData>(), 224);
+        assert_eq!(field_offset!(GlobalUniformData, camera_position), 192);
+        assert_eq!(field_offset!(GlobalUniformData, time), 208);
+        assert_eq!(std::mem::size_of::<MaterialUniformData>(), 64);
+        assert_eq!(field_offset!(MaterialUniformData, emissive_color), 32);
+        assert_eq!(field_offset!(MaterialUniformData, use_textures), 48);
+        assert_eq!(field_offset!(LightData, light_type), 48);
+        assert_eq!(field_offset!(LightUniformData, lights), 32);
+    }
 }
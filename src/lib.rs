@@ -51,10 +51,10 @@ pub mod utils;
 pub mod reactor;
 
 // Re-export the main run function
-pub use app::run;
+pub use app::{run, try_run, PumpedApp};
 
 // Re-export commonly used types at the crate root
-pub use app::{quick, quick_with, ReactorApp};
+pub use app::{quick, quick_state, quick_with, QuickState, ReactorApp};
 pub use app::{ReactorConfig, RendererMode};
 pub use app::pause_config::{
     PauseConfig, PauseConfigPage, PauseConfigResult, PauseConfiguracion, PauseConfiguration,
@@ -65,26 +65,30 @@ pub use base_shader::{
 pub use core::context::VulkanContext;
 pub use core::debug_utils::DebugNamer;
 pub use core::error::{ErrorCode, ReactorError, ReactorResult};
+pub use core::allocator::MemoryStats;
 pub use core::memory_budget::{GpuMemoryBudget, HeapBudget};
 pub use core::vrs::{
     PixelIntelligent, PixelIntelligentProfile, VrsCapabilities, VrsContext, VrsRate,
     VrsSupportedRate,
 };
-pub use reactor::Reactor;
+pub use reactor::{Reactor, RenderStats, RenderTarget, RenderTargetWindow};
 pub use resources::font::FontAsset;
 pub use resources::material::Material;
-pub use resources::mesh::Mesh;
+pub use resources::mesh::{Mesh, MeshUsage};
 pub use resources::decal::Decal;
-pub use resources::texture::Texture;
+pub use resources::texture::{Cubemap, Texture, TextureOptions};
 pub use resources::vertex::Vertex;
-pub use scene::camera::Camera;
+pub use graphics::sprite_batch::{Sprite, SpriteBatch};
+pub use scene::camera::{Camera, Camera2D, Projection};
+pub use scene::hierarchy::{ChildPolicy, Parent};
 pub use scene::transform::Transform;
 pub use systems::audio::AudioClip;
 
 // Re-export system types
-pub use systems::lighting::{Light, LightType, LightingSystem};
-pub use systems::physics::{PhysicsWorld, Ray, RigidBody, Sphere, AABB};
-pub use systems::scene::{Scene, SceneObject};
+pub use systems::input::{ActionMap, InputBinding};
+pub use systems::lighting::{Light, LightId, LightType, LightingSystem};
+pub use systems::physics::{Capsule, Collider, ColliderId, PhysicsMaterial, PhysicsWorld, Ray, RayHit, RigidBody, Sphere, AABB};
+pub use systems::scene::{MeshRenderer, Scene, SceneHandle, SceneObject};
 
 // High-level convenience API on top of ReactorContext
 pub mod app_helpers;
@@ -96,9 +100,18 @@ pub use utils::{CPUDetector, ResolutionDetector};
 // Re-export glam types for convenience
 pub use glam::{Mat3, Mat4, Quat, Vec2, Vec3, Vec4};
 
+/// Engine version, e.g. `"1.6.0"`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Engine name, version and build profile, e.g. `"REACTOR 1.6.0 (release)"`.
+pub fn engine_info() -> String {
+    let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+    format!("REACTOR {} ({})", VERSION, profile)
+}
+
 /// Prelude module - import everything you need with `use reactor_vulkan::prelude::*;`
 pub mod prelude {
-    pub use crate::platform::{Gamepad, GamepadAxis, GamepadButton};
+    pub use crate::platform::{CursorMode, FullscreenMode, Gamepad, GamepadAxis, GamepadButton, GamepadEvent};
     pub use crate::systems::audio::{
         AudioClipId, AudioListener, AudioSource, AudioSourceId, AudioSystem,
     };
@@ -106,6 +119,7 @@ pub mod prelude {
     pub use crate::systems::event_bus::{EventBus, Observer};
     pub use crate::{
         quick,
+        quick_state,
         quick_with,
 
         run,
@@ -136,6 +150,7 @@ pub mod prelude {
         Material,
         // Resources
         Mesh,
+        MeshRenderer,
         Decal,
         PauseConfig,
         PauseConfigPage,
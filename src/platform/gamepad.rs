@@ -5,6 +5,11 @@
 //! DualShock/DualSense (PlayStation), Switch Pro, y mandos genéricos vía
 //! HID en Windows, Linux y macOS.
 //!
+//! Cada mando ocupa un *slot* (0, 1, 2…) en orden de conexión. El slot se
+//! conserva al desconectarse, así que un mando que se vuelve a enchufar
+//! recupera su índice. Las consultas sin índice (`left_stick`,
+//! `is_button_down`…) leen el mando activo: el primero conectado.
+//!
 //! ## Uso típico
 //!
 //! ```ignore
@@ -25,12 +30,18 @@ use std::collections::{HashMap, HashSet};
 // dependencia directa en su Cargo.toml.
 pub use gilrs::{Axis as GamepadAxis, Button as GamepadButton};
 
-/// Subsistema de gamepad. Detecta conexión/desconexión en caliente y mantiene
-/// el estado del primer mando activo (suficiente para single-player).
-pub struct Gamepad {
-    gilrs: Option<gilrs::Gilrs>,
-    active: Option<gilrs::GamepadId>,
-    active_name: Option<String>,
+/// Cambio de conexión de un mando durante el último frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GamepadEvent {
+    Connected(usize),
+    Disconnected(usize),
+}
+
+/// Estado de un slot de mando.
+struct PadState {
+    id: gilrs::GamepadId,
+    name: String,
+    connected: bool,
     button_state: HashMap<GamepadButton, bool>,
     just_pressed: HashSet<GamepadButton>,
     just_released: HashSet<GamepadButton>,
@@ -38,6 +49,69 @@ pub struct Gamepad {
     right_stick: Vec2,
     left_trigger: f32,
     right_trigger: f32,
+}
+
+impl PadState {
+    fn new(id: gilrs::GamepadId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            connected: true,
+            button_state: HashMap::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+            left_stick: Vec2::ZERO,
+            right_stick: Vec2::ZERO,
+            left_trigger: 0.0,
+            right_trigger: 0.0,
+        }
+    }
+
+    fn reset_state(&mut self) {
+        self.button_state.clear();
+        self.left_stick = Vec2::ZERO;
+        self.right_stick = Vec2::ZERO;
+        self.left_trigger = 0.0;
+        self.right_trigger = 0.0;
+    }
+
+    fn apply(&mut self, event: gilrs::EventType) {
+        match event {
+            gilrs::EventType::ButtonPressed(btn, _) => {
+                if !*self.button_state.get(&btn).unwrap_or(&false) {
+                    self.just_pressed.insert(btn);
+                }
+                self.button_state.insert(btn, true);
+            }
+            gilrs::EventType::ButtonReleased(btn, _) => {
+                self.button_state.insert(btn, false);
+                self.just_released.insert(btn);
+            }
+            gilrs::EventType::AxisChanged(axis, value, _) => match axis {
+                GamepadAxis::LeftStickX => self.left_stick.x = value,
+                GamepadAxis::LeftStickY => self.left_stick.y = value,
+                GamepadAxis::RightStickX => self.right_stick.x = value,
+                GamepadAxis::RightStickY => self.right_stick.y = value,
+                _ => {}
+            },
+            gilrs::EventType::ButtonChanged(btn, value, _) => match btn {
+                GamepadButton::LeftTrigger2 => self.left_trigger = value,
+                GamepadButton::RightTrigger2 => self.right_trigger = value,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Subsistema de gamepad. Detecta conexión/desconexión en caliente y mantiene
+/// el estado de todos los mandos conectados.
+pub struct Gamepad {
+    gilrs: Option<gilrs::Gilrs>,
+    pads: Vec<PadState>,
+    /// Slot del mando activo (el que leen las consultas sin índice).
+    active: Option<usize>,
+    events: Vec<GamepadEvent>,
     /// Radio del *dead-zone* radial aplicado a los sticks (default 0.15).
     /// Valores con módulo menor se redondean a `Vec2::ZERO`.
     pub deadzone: f32,
@@ -56,11 +130,11 @@ impl Gamepad {
     pub fn new() -> Self {
         let gilrs = match gilrs::Gilrs::new() {
             Ok(g) => {
-                println!("🎮 Gamepad subsystem initialized (gilrs)");
+                log::info!("🎮 Gamepad subsystem initialized (gilrs)");
                 Some(g)
             }
             Err(e) => {
-                eprintln!(
+                log::warn!(
                     "⚠️ Gamepad subsystem failed to init: {} (continuando sin mando)",
                     e
                 );
@@ -68,38 +142,41 @@ impl Gamepad {
             }
         };
 
-        // Si ya hay un mando conectado al arrancar, lo activamos.
-        let (active, active_name) = match gilrs.as_ref() {
+        // Los mandos ya conectados al arrancar ocupan los primeros slots.
+        let pads: Vec<PadState> = match gilrs.as_ref() {
             Some(g) => g
                 .gamepads()
-                .next()
                 .map(|(id, pad)| {
-                    println!("🎮 Mando detectado: {} (id {:?})", pad.name(), id);
-                    (Some(id), Some(pad.name().to_string()))
+                    log::info!("🎮 Mando detectado: {} (id {:?})", pad.name(), id);
+                    PadState::new(id, pad.name().to_string())
                 })
-                .unwrap_or((None, None)),
-            None => (None, None),
+                .collect(),
+            None => Vec::new(),
         };
+        let active = (!pads.is_empty()).then_some(0);
 
         Self {
             gilrs,
+            pads,
             active,
-            active_name,
-            button_state: HashMap::new(),
-            just_pressed: HashSet::new(),
-            just_released: HashSet::new(),
-            left_stick: Vec2::ZERO,
-            right_stick: Vec2::ZERO,
-            left_trigger: 0.0,
-            right_trigger: 0.0,
+            events: Vec::new(),
             deadzone: 0.15,
         }
     }
 
+    /// Gamepad sin gilrs: siempre desconectado. Para inputs secundarios que
+    /// no deben robarle los eventos del mando al principal.
+    pub(crate) fn disconnected() -> Self {
+        Self { gilrs: None, pads: Vec::new(), active: None, events: Vec::new(), deadzone: 0.15 }
+    }
+
     /// Limpia los flags "just_pressed" / "just_released" — llamar al inicio de cada frame.
     pub(crate) fn begin_frame(&mut self) {
-        self.just_pressed.clear();
-        self.just_released.clear();
+        for pad in &mut self.pads {
+            pad.just_pressed.clear();
+            pad.just_released.clear();
+        }
+        self.events.clear();
     }
 
     /// Drena los eventos pendientes de gilrs. Debe llamarse una vez por frame
@@ -121,63 +198,63 @@ impl Gamepad {
         for (id, event) in events {
             match event {
                 gilrs::EventType::Connected => {
-                    if self.active.is_none() {
-                        self.active = Some(id);
-                        if let Some(pad) = self.gilrs.as_ref().and_then(|g| g.connected_gamepad(id))
-                        {
-                            let name = pad.name().to_string();
-                            println!("🎮 Mando conectado: {}", name);
-                            self.active_name = Some(name);
+                    let name = self
+                        .gilrs
+                        .as_ref()
+                        .and_then(|g| g.connected_gamepad(id))
+                        .map(|pad| pad.name().to_string())
+                        .unwrap_or_default();
+                    log::info!("🎮 Mando conectado: {}", name);
+                    let slot = match self.slot_of(id) {
+                        Some(slot) => {
+                            let pad = &mut self.pads[slot];
+                            pad.connected = true;
+                            pad.name = name;
+                            slot
                         }
+                        None => {
+                            self.pads.push(PadState::new(id, name));
+                            self.pads.len() - 1
+                        }
+                    };
+                    if self.active.is_none() {
+                        self.active = Some(slot);
                     }
+                    self.events.push(GamepadEvent::Connected(slot));
                 }
                 gilrs::EventType::Disconnected => {
-                    if self.active == Some(id) {
-                        println!("🎮 Mando desconectado");
-                        self.active = None;
-                        self.active_name = None;
-                        self.reset_state();
+                    let Some(slot) = self.slot_of(id) else { continue };
+                    log::info!("🎮 Mando desconectado");
+                    let pad = &mut self.pads[slot];
+                    pad.connected = false;
+                    pad.reset_state();
+                    if self.active == Some(slot) {
+                        self.active = self.pads.iter().position(|p| p.connected);
                     }
+                    self.events.push(GamepadEvent::Disconnected(slot));
                 }
-                _ if Some(id) != self.active => {
-                    // Eventos de mandos secundarios: ignorar (single-player).
-                }
-                gilrs::EventType::ButtonPressed(btn, _) => {
-                    if !*self.button_state.get(&btn).unwrap_or(&false) {
-                        self.just_pressed.insert(btn);
+                event => {
+                    if let Some(slot) = self.slot_of(id) {
+                        self.pads[slot].apply(event);
                     }
-                    self.button_state.insert(btn, true);
                 }
-                gilrs::EventType::ButtonReleased(btn, _) => {
-                    self.button_state.insert(btn, false);
-                    self.just_released.insert(btn);
-                }
-                gilrs::EventType::AxisChanged(axis, value, _) => match axis {
-                    GamepadAxis::LeftStickX => self.left_stick.x = value,
-                    GamepadAxis::LeftStickY => self.left_stick.y = value,
-                    GamepadAxis::RightStickX => self.right_stick.x = value,
-                    GamepadAxis::RightStickY => self.right_stick.y = value,
-                    _ => {}
-                },
-                gilrs::EventType::ButtonChanged(btn, value, _) => match btn {
-                    GamepadButton::LeftTrigger2 => self.left_trigger = value,
-                    GamepadButton::RightTrigger2 => self.right_trigger = value,
-                    _ => {}
-                },
-                _ => {}
             }
         }
     }
 
-    fn reset_state(&mut self) {
-        self.button_state.clear();
-        self.left_stick = Vec2::ZERO;
-        self.right_stick = Vec2::ZERO;
-        self.left_trigger = 0.0;
-        self.right_trigger = 0.0;
+    fn slot_of(&self, id: gilrs::GamepadId) -> Option<usize> {
+        self.pads.iter().position(|pad| pad.id == id)
     }
 
-    // ── Consultas ───────────────────────────────────────────────────────────
+    fn active_pad(&self) -> Option<&PadState> {
+        self.pad(self.active?)
+    }
+
+    fn pad(&self, slot: usize) -> Option<&PadState> {
+        self.pads.get(slot).filter(|pad| pad.connected)
+    }
+
+    // ── Consultas del mando activo ──────────────────────────────────────────
 
     /// `true` si hay al menos un mando conectado y activo.
     pub fn is_connected(&self) -> bool {
@@ -186,42 +263,99 @@ impl Gamepad {
 
     /// Nombre del mando activo (ej. `"Xbox Wireless Controller"`).
     pub fn name(&self) -> Option<&str> {
-        self.active_name.as_deref()
+        self.active_pad().map(|pad| pad.name.as_str())
     }
 
     /// `true` mientras el botón esté pulsado.
     pub fn is_button_down(&self, button: GamepadButton) -> bool {
-        *self.button_state.get(&button).unwrap_or(&false)
+        self.active.is_some_and(|slot| self.pad_button_down(slot, button))
     }
 
     /// `true` el frame en que el botón fue pulsado.
     pub fn is_button_just_pressed(&self, button: GamepadButton) -> bool {
-        self.just_pressed.contains(&button)
+        self.active.is_some_and(|slot| self.pad_button_just_pressed(slot, button))
     }
 
     /// `true` el frame en que el botón fue soltado.
     pub fn is_button_just_released(&self, button: GamepadButton) -> bool {
-        self.just_released.contains(&button)
+        self.active.is_some_and(|slot| self.pad_button_just_released(slot, button))
     }
 
     /// Posición del stick izquierdo (-1.0 .. 1.0 por eje), con deadzone radial.
     pub fn left_stick(&self) -> Vec2 {
-        self.apply_deadzone(self.left_stick)
+        self.active_pad().map_or(Vec2::ZERO, |pad| self.apply_deadzone(pad.left_stick))
     }
 
     /// Posición del stick derecho (-1.0 .. 1.0 por eje), con deadzone radial.
     pub fn right_stick(&self) -> Vec2 {
-        self.apply_deadzone(self.right_stick)
+        self.active_pad().map_or(Vec2::ZERO, |pad| self.apply_deadzone(pad.right_stick))
     }
 
     /// Valor analógico del gatillo izquierdo (0.0 .. 1.0).
     pub fn left_trigger(&self) -> f32 {
-        self.left_trigger
+        self.active_pad().map_or(0.0, |pad| pad.left_trigger)
     }
 
     /// Valor analógico del gatillo derecho (0.0 .. 1.0).
     pub fn right_trigger(&self) -> f32 {
-        self.right_trigger
+        self.active_pad().map_or(0.0, |pad| pad.right_trigger)
+    }
+
+    /// Eje del mando activo (ver `pad_axis`).
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.active.map_or(0.0, |slot| self.pad_axis(slot, axis))
+    }
+
+    // ── Consultas por slot ──────────────────────────────────────────────────
+
+    /// Número de slots (mandos vistos desde el arranque, conectados o no).
+    pub fn pad_count(&self) -> usize {
+        self.pads.len()
+    }
+
+    /// Número de mandos conectados ahora mismo.
+    pub fn connected_count(&self) -> usize {
+        self.pads.iter().filter(|pad| pad.connected).count()
+    }
+
+    pub fn is_pad_connected(&self, pad: usize) -> bool {
+        self.pad(pad).is_some()
+    }
+
+    pub fn pad_name(&self, pad: usize) -> Option<&str> {
+        self.pad(pad).map(|p| p.name.as_str())
+    }
+
+    pub fn pad_button_down(&self, pad: usize, button: GamepadButton) -> bool {
+        self.pad(pad).is_some_and(|p| *p.button_state.get(&button).unwrap_or(&false))
+    }
+
+    pub fn pad_button_just_pressed(&self, pad: usize, button: GamepadButton) -> bool {
+        self.pad(pad).is_some_and(|p| p.just_pressed.contains(&button))
+    }
+
+    pub fn pad_button_just_released(&self, pad: usize, button: GamepadButton) -> bool {
+        self.pad(pad).is_some_and(|p| p.just_released.contains(&button))
+    }
+
+    /// Valor de un eje del mando `pad`. Los sticks llevan el deadzone radial
+    /// aplicado; `LeftZ`/`RightZ` devuelven los gatillos (0.0 .. 1.0).
+    pub fn pad_axis(&self, pad: usize, axis: GamepadAxis) -> f32 {
+        let Some(p) = self.pad(pad) else { return 0.0 };
+        match axis {
+            GamepadAxis::LeftStickX => self.apply_deadzone(p.left_stick).x,
+            GamepadAxis::LeftStickY => self.apply_deadzone(p.left_stick).y,
+            GamepadAxis::RightStickX => self.apply_deadzone(p.right_stick).x,
+            GamepadAxis::RightStickY => self.apply_deadzone(p.right_stick).y,
+            GamepadAxis::LeftZ => p.left_trigger,
+            GamepadAxis::RightZ => p.right_trigger,
+            _ => 0.0,
+        }
+    }
+
+    /// Conexiones y desconexiones ocurridas en el último frame.
+    pub fn events(&self) -> &[GamepadEvent] {
+        &self.events
     }
 
     fn apply_deadzone(&self, stick: Vec2) -> Vec2 {
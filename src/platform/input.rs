@@ -1,18 +1,30 @@
 use glam::Vec2;
 use std::collections::HashSet;
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::{DeviceEvent, ElementState, MouseButton, WindowEvent};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
 use crate::platform::gamepad::Gamepad;
+use crate::platform::window::CursorMode;
+
+/// Píxeles de un touchpad que equivalen a una línea de rueda.
+const PIXELS_PER_LINE: f32 = 100.0;
 
 pub struct Input {
     pressed_keys: HashSet<KeyCode>,
     just_pressed_keys: HashSet<KeyCode>,
     just_released_keys: HashSet<KeyCode>,
     pressed_mouse_buttons: HashSet<MouseButton>,
+    just_pressed_mouse_buttons: HashSet<MouseButton>,
+    just_released_mouse_buttons: HashSet<MouseButton>,
     mouse_position: Vec2,
     mouse_delta: Vec2,
     scroll_delta: f32,
+    /// Rueda acumulada en ambos ejes (x = horizontal), en líneas.
+    wheel_delta: Vec2,
+    /// Modo de cursor pedido por el juego. Con `Locked` el delta del ratón sale
+    /// de `DeviceEvent::MouseMotion` en vez de la posición absoluta.
+    cursor_mode: CursorMode,
+    focused: bool,
     /// Subsistema de gamepad (Fase 5.5 — siempre presente, "desconectado"
     /// hasta que se enchufe un mando).
     gamepad: Gamepad,
@@ -26,23 +38,41 @@ impl Default for Input {
 
 impl Input {
     pub fn new() -> Self {
+        Self::with_gamepad(Gamepad::new())
+    }
+
+    /// Input de una ventana secundaria: teclado y ratón. El gamepad lo lee
+    /// sólo el input de la ventana principal.
+    pub fn without_gamepad() -> Self {
+        Self::with_gamepad(Gamepad::disconnected())
+    }
+
+    fn with_gamepad(gamepad: Gamepad) -> Self {
         Self {
             pressed_keys: HashSet::new(),
             just_pressed_keys: HashSet::new(),
             just_released_keys: HashSet::new(),
             pressed_mouse_buttons: HashSet::new(),
+            just_pressed_mouse_buttons: HashSet::new(),
+            just_released_mouse_buttons: HashSet::new(),
             mouse_position: Vec2::ZERO,
             mouse_delta: Vec2::ZERO,
             scroll_delta: 0.0,
-            gamepad: Gamepad::new(),
+            wheel_delta: Vec2::ZERO,
+            cursor_mode: CursorMode::Normal,
+            focused: true,
+            gamepad,
         }
     }
 
     pub fn begin_frame(&mut self) {
         self.just_pressed_keys.clear();
         self.just_released_keys.clear();
+        self.just_pressed_mouse_buttons.clear();
+        self.just_released_mouse_buttons.clear();
         self.mouse_delta = Vec2::ZERO;
         self.scroll_delta = 0.0;
+        self.wheel_delta = Vec2::ZERO;
         // Drenar eventos del gamepad y actualizar estado.
         self.gamepad.begin_frame();
         self.gamepad.poll();
@@ -58,43 +88,76 @@ impl Input {
                         ..
                     },
                 ..
-            } => match state {
-                ElementState::Pressed => {
-                    if !self.pressed_keys.contains(keycode) {
-                        self.just_pressed_keys.insert(*keycode);
-                    }
-                    self.pressed_keys.insert(*keycode);
-                }
-                ElementState::Released => {
-                    self.pressed_keys.remove(keycode);
-                    self.just_released_keys.insert(*keycode);
-                }
-            },
-            WindowEvent::MouseInput { state, button, .. } => match state {
-                ElementState::Pressed => {
-                    self.pressed_mouse_buttons.insert(*button);
-                }
-                ElementState::Released => {
-                    self.pressed_mouse_buttons.remove(button);
-                }
-            },
+            } => self.set_key(*keycode, *state == ElementState::Pressed),
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.set_mouse_button(*button, *state == ElementState::Pressed)
+            }
             WindowEvent::CursorMoved { position, .. } => {
                 let new_pos = Vec2::new(position.x as f32, position.y as f32);
-                self.mouse_delta = new_pos - self.mouse_position;
+                if !self.uses_raw_motion() {
+                    self.mouse_delta += new_pos - self.mouse_position;
+                }
                 self.mouse_position = new_pos;
             }
-            WindowEvent::MouseWheel { delta, .. } => match delta {
-                winit::event::MouseScrollDelta::LineDelta(_, y) => {
-                    self.scroll_delta = *y;
-                }
-                winit::event::MouseScrollDelta::PixelDelta(pos) => {
-                    self.scroll_delta = pos.y as f32 / 100.0;
+            WindowEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x, *y),
+                    winit::event::MouseScrollDelta::PixelDelta(pos) => {
+                        Vec2::new(pos.x as f32, pos.y as f32) / PIXELS_PER_LINE
+                    }
+                };
+                self.wheel_delta += lines;
+                self.scroll_delta += lines.y;
+            }
+            WindowEvent::Focused(focused) => {
+                self.focused = *focused;
+                if !focused {
+                    // Al perder el foco no llegan los `Released`: soltar todo.
+                    self.pressed_keys.clear();
+                    self.pressed_mouse_buttons.clear();
                 }
-            },
+            }
             _ => {}
         }
     }
 
+    /// Estado de una tecla con sus flancos del frame (los borra `begin_frame`).
+    pub(crate) fn set_key(&mut self, key: KeyCode, pressed: bool) {
+        if pressed {
+            if self.pressed_keys.insert(key) {
+                self.just_pressed_keys.insert(key);
+            }
+        } else {
+            self.pressed_keys.remove(&key);
+            self.just_released_keys.insert(key);
+        }
+    }
+
+    pub(crate) fn set_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            if self.pressed_mouse_buttons.insert(button) {
+                self.just_pressed_mouse_buttons.insert(button);
+            }
+        } else {
+            self.pressed_mouse_buttons.remove(&button);
+            self.just_released_mouse_buttons.insert(button);
+        }
+    }
+
+    /// Movimiento crudo del ratón (sin aceleración ni límites de pantalla).
+    /// Sólo se usa con el cursor bloqueado y la ventana enfocada.
+    pub fn process_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta: (dx, dy) } = event {
+            if self.uses_raw_motion() {
+                self.mouse_delta += Vec2::new(*dx as f32, *dy as f32);
+            }
+        }
+    }
+
+    fn uses_raw_motion(&self) -> bool {
+        self.cursor_mode == CursorMode::Locked && self.focused
+    }
+
     // Keyboard
     pub fn is_key_down(&self, key: KeyCode) -> bool {
         self.pressed_keys.contains(&key)
@@ -108,11 +171,52 @@ impl Input {
         self.just_released_keys.contains(&key)
     }
 
+    /// Shift izquierdo o derecho.
+    pub fn is_shift_down(&self) -> bool {
+        self.is_key_down(KeyCode::ShiftLeft) || self.is_key_down(KeyCode::ShiftRight)
+    }
+
+    /// Control izquierdo o derecho.
+    pub fn is_ctrl_down(&self) -> bool {
+        self.is_key_down(KeyCode::ControlLeft) || self.is_key_down(KeyCode::ControlRight)
+    }
+
+    /// Alt izquierdo o AltGr.
+    pub fn is_alt_down(&self) -> bool {
+        self.is_key_down(KeyCode::AltLeft) || self.is_key_down(KeyCode::AltRight)
+    }
+
+    /// `KeyCode` de la fila de números (0-9), no del teclado numérico.
+    pub fn digit_key(digit: u8) -> Option<KeyCode> {
+        const DIGITS: [KeyCode; 10] = [
+            KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+            KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+        ];
+        DIGITS.get(digit as usize).copied()
+    }
+
+    /// `KeyCode` de F1-F12 (`function_key(5)` = F5).
+    pub fn function_key(n: u8) -> Option<KeyCode> {
+        const FUNCTION: [KeyCode; 12] = [
+            KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+            KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+        ];
+        FUNCTION.get((n as usize).checked_sub(1)?).copied()
+    }
+
     // Mouse
     pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
         self.pressed_mouse_buttons.contains(&button)
     }
 
+    pub fn is_mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.just_pressed_mouse_buttons.contains(&button)
+    }
+
+    pub fn is_mouse_button_just_released(&self, button: MouseButton) -> bool {
+        self.just_released_mouse_buttons.contains(&button)
+    }
+
     pub fn mouse_position(&self) -> Vec2 {
         self.mouse_position
     }
@@ -121,10 +225,31 @@ impl Input {
         self.mouse_delta
     }
 
+    /// Rueda vertical acumulada en el frame (ver `wheel_delta`).
     pub fn scroll_delta(&self) -> f32 {
         self.scroll_delta
     }
 
+    /// Rueda acumulada en el frame en ambos ejes (x = horizontal, y = vertical),
+    /// en líneas tanto para ratones como para touchpads.
+    pub fn wheel_delta(&self) -> Vec2 {
+        self.wheel_delta
+    }
+
+    pub fn cursor_mode(&self) -> CursorMode {
+        self.cursor_mode
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sólo registra el modo; aplicarlo a la ventana es cosa de
+    /// `ReactorContext::set_cursor_mode`.
+    pub(crate) fn set_cursor_mode(&mut self, mode: CursorMode) {
+        self.cursor_mode = mode;
+    }
+
     // Movement helpers
     pub fn get_movement_vector(&self) -> Vec2 {
         let mut movement = Vec2::ZERO;
@@ -163,3 +288,19 @@ impl Input {
         &mut self.gamepad
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_helpers_cover_digits_and_function_keys() {
+        assert_eq!(Input::digit_key(0), Some(KeyCode::Digit0));
+        assert_eq!(Input::digit_key(9), Some(KeyCode::Digit9));
+        assert_eq!(Input::digit_key(10), None);
+        assert_eq!(Input::function_key(0), None);
+        assert_eq!(Input::function_key(5), Some(KeyCode::F5));
+        assert_eq!(Input::function_key(12), Some(KeyCode::F12));
+        assert_eq!(Input::function_key(13), None);
+    }
+}
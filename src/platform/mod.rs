@@ -7,7 +7,7 @@ pub mod input;
 pub mod time;
 pub mod window;
 
-pub use gamepad::{Gamepad, GamepadAxis, GamepadButton};
+pub use gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEvent};
 pub use input::Input;
 pub use time::Time;
-pub use window::ReactorWindow;
+pub use window::{CursorMode, FullscreenMode, ReactorWindow};
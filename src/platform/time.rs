@@ -78,21 +78,50 @@ impl Default for Time {
 pub struct FixedTimestep {
     accumulator: Duration,
     timestep: Duration,
+    max_steps: u32,
 }
 
+/// Steps `FixedTimestep::update` runs at most per call by default. After a
+/// hitch the rest of the backlog is dropped instead of simulated, so a slow
+/// frame cannot make the next one slower (spiral of death).
+pub const DEFAULT_MAX_FIXED_STEPS: u32 = 8;
+
 impl FixedTimestep {
     pub fn new(hz: u32) -> Self {
         Self {
             accumulator: Duration::ZERO,
-            timestep: Duration::from_secs_f64(1.0 / hz as f64),
+            timestep: Self::period(hz),
+            max_steps: DEFAULT_MAX_FIXED_STEPS,
         }
     }
 
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Changes the rate, keeping the accumulated time.
+    pub fn set_hz(&mut self, hz: u32) {
+        self.timestep = Self::period(hz);
+    }
+
+    fn period(hz: u32) -> Duration {
+        Duration::from_secs_f64(1.0 / hz.max(1) as f64)
+    }
+
+    /// Adds `delta` and returns how many steps to run now (at most
+    /// `max_steps`). What is left over is always less than one step.
     pub fn update(&mut self, delta: Duration) -> u32 {
         self.accumulator += delta;
         let mut steps = 0;
 
         while self.accumulator >= self.timestep {
+            if steps == self.max_steps {
+                // Drop the backlog, keep the phase.
+                let step = self.timestep.as_nanos();
+                self.accumulator = Duration::from_nanos((self.accumulator.as_nanos() % step) as u64);
+                break;
+            }
             self.accumulator -= self.timestep;
             steps += 1;
         }
@@ -104,7 +133,38 @@ impl FixedTimestep {
         self.timestep.as_secs_f32()
     }
 
+    /// Fraction of a step left in the accumulator, in `[0, 1)`: how far to
+    /// interpolate between the last two fixed states when rendering.
     pub fn alpha(&self) -> f32 {
         self.accumulator.as_secs_f32() / self.timestep.as_secs_f32()
     }
+
+    /// Time waiting for the next step, in seconds.
+    pub fn accumulated(&self) -> f32 {
+        self.accumulator.as_secs_f32()
+    }
+
+    pub fn set_accumulated(&mut self, seconds: f32) {
+        self.accumulator = Duration::from_secs_f32(seconds.max(0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_steps_keep_the_remainder_and_cap_the_backlog() {
+        let mut fixed = FixedTimestep::new(50);
+        assert_eq!(fixed.update(Duration::from_millis(30)), 1);
+        assert!((fixed.alpha() - 0.5).abs() < 1e-4);
+        assert_eq!(fixed.update(Duration::from_millis(10)), 1);
+        assert!(fixed.alpha() < 1e-4);
+
+        // A 1 s hitch runs 8 steps, not 50, and the next frame is back to normal.
+        assert_eq!(fixed.update(Duration::from_millis(1005)), DEFAULT_MAX_FIXED_STEPS);
+        assert!((fixed.accumulated() - 0.005).abs() < 1e-4);
+        assert_eq!(fixed.update(Duration::from_millis(20)), 1);
+        assert!((0.0..1.0).contains(&fixed.alpha()));
+    }
 }
@@ -2,10 +2,98 @@ use crate::core::error::ReactorResult;
 use std::sync::Arc;
 use winit::dpi::LogicalSize;
 use winit::event_loop::ActiveEventLoop;
-use winit::window::Window;
+use winit::window::{CursorGrabMode, Fullscreen, Window};
 
 use crate::app::ReactorConfig;
 
+/// How the OS cursor behaves over the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorMode {
+    /// Visible and free to leave the window.
+    #[default]
+    Normal,
+    /// Invisible while over the window, still free to leave it.
+    Hidden,
+    /// Invisible and grabbed; mouse deltas come from raw device motion, so
+    /// they keep flowing when the cursor would hit the screen edge.
+    Locked,
+}
+
+impl CursorMode {
+    /// Applies the mode to `window`. `Locked` falls back to `Confined` on
+    /// platforms without pointer locking (Windows, X11).
+    pub(crate) fn apply(self, window: &Window) {
+        let grab = match self {
+            CursorMode::Locked => window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined)),
+            _ => window.set_cursor_grab(CursorGrabMode::None),
+        };
+        if let Err(e) = grab {
+            log::warn!("Cursor grab not supported: {}", e);
+        }
+        window.set_cursor_visible(self == CursorMode::Normal);
+    }
+}
+
+/// Window presentation. The discriminants are stable for bindings.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FullscreenMode {
+    #[default]
+    Windowed = 0,
+    /// Covers the current monitor without changing its video mode.
+    Borderless = 1,
+    /// Takes over the current monitor at its largest video mode. Falls back
+    /// to `Borderless` where exclusive mode is unavailable.
+    Exclusive = 2,
+}
+
+impl FullscreenMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Windowed),
+            1 => Some(Self::Borderless),
+            2 => Some(Self::Exclusive),
+            _ => None,
+        }
+    }
+
+    /// Current mode of `window`.
+    pub fn of(window: &Window) -> Self {
+        match window.fullscreen() {
+            None => Self::Windowed,
+            Some(Fullscreen::Borderless(_)) => Self::Borderless,
+            Some(Fullscreen::Exclusive(_)) => Self::Exclusive,
+        }
+    }
+
+    /// Applies the mode to `window`; the resize that follows recreates the swapchain.
+    pub(crate) fn apply(self, window: &Window) {
+        let monitor = window.current_monitor();
+        let fullscreen = match self {
+            Self::Windowed => None,
+            Self::Borderless => Some(Fullscreen::Borderless(monitor)),
+            Self::Exclusive => {
+                let mode = monitor.as_ref().and_then(|monitor| {
+                    monitor.video_modes().max_by_key(|mode| {
+                        let size = mode.size();
+                        (size.width as u64 * size.height as u64, mode.refresh_rate_millihertz())
+                    })
+                });
+                match mode {
+                    Some(mode) => Some(Fullscreen::Exclusive(mode)),
+                    None => {
+                        log::warn!("Exclusive fullscreen not available, using borderless");
+                        Some(Fullscreen::Borderless(monitor))
+                    }
+                }
+            }
+        };
+        window.set_fullscreen(fullscreen);
+    }
+}
+
 /// Platform window abstraction wrapping winit
 pub struct ReactorWindow {
     pub(crate) inner: Arc<Window>,
@@ -60,6 +148,11 @@ impl ReactorWindow {
         self.inner.request_redraw();
     }
 
+    /// Set cursor visibility and grab
+    pub fn set_cursor_mode(&self, mode: CursorMode) {
+        mode.apply(&self.inner);
+    }
+
     /// Get DPI scale factor
     pub fn scale_factor(&self) -> f64 {
         self.inner.scale_factor()
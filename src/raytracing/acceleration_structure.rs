@@ -1,20 +1,349 @@
 // Ray Tracing Acceleration Structures
-// NOTE: This is a placeholder module. Full implementation requires specific ash version compatibility.
-// The RayTracingContext in the legacy module handles basic RT initialization.
+//
+// A `Blas` holds the triangles of one `Mesh`, a `Tlas` the instances that
+// point at them. Builds are recorded into a command buffer (no host builds);
+// the caller orders them with `AccelerationStructure::build_barrier` and
+// keeps any returned scratch buffer alive until that command buffer is done.
 
+use crate::core::context::VulkanContext;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::buffer::Buffer;
+use crate::raytracing::context::RayTracingContext;
+use crate::resources::mesh::Mesh;
 use ash::vk;
+use glam::Mat4;
+use gpu_allocator::vulkan::Allocator;
+use gpu_allocator::MemoryLocation;
+use std::sync::{Arc, Mutex};
 
-/// Placeholder for Bottom Level Acceleration Structure
-pub struct Blas {
+/// Acceleration structure handle plus the buffer that backs it.
+pub struct AccelerationStructure {
     pub handle: vk::AccelerationStructureKHR,
+    pub device_address: vk::DeviceAddress,
+    pub buffer: Buffer,
+    accel_fn: ash::khr::acceleration_structure::Device,
+}
+
+impl AccelerationStructure {
+    pub fn new(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        rt: &RayTracingContext,
+        ty: vk::AccelerationStructureTypeKHR,
+        size: u64,
+    ) -> ReactorResult<Self> {
+        let buffer = Buffer::new(
+            ctx,
+            allocator,
+            size,
+            vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR
+                | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
+            MemoryLocation::GpuOnly,
+        )?;
+        let create_info = vk::AccelerationStructureCreateInfoKHR::default()
+            .buffer(buffer.handle)
+            .size(size)
+            .ty(ty);
+        let handle = unsafe { rt.accel_fn.create_acceleration_structure(&create_info, None)? };
+        let address_info =
+            vk::AccelerationStructureDeviceAddressInfoKHR::default().acceleration_structure(handle);
+        let device_address =
+            unsafe { rt.accel_fn.get_acceleration_structure_device_address(&address_info) };
+
+        Ok(Self {
+            handle,
+            device_address,
+            buffer,
+            accel_fn: rt.accel_fn.clone(),
+        })
+    }
+
+    /// Orders acceleration structure work recorded before the barrier
+    /// (`src_stage`) with builds and traces recorded after it (`dst_stage`).
+    pub fn build_barrier(
+        device: &ash::Device,
+        command_buffer: vk::CommandBuffer,
+        src_stage: vk::PipelineStageFlags,
+        dst_stage: vk::PipelineStageFlags,
+    ) {
+        let barrier = vk::MemoryBarrier::default()
+            .src_access_mask(
+                vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR
+                    | vk::AccessFlags::ACCELERATION_STRUCTURE_READ_KHR,
+            )
+            .dst_access_mask(
+                vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR
+                    | vk::AccessFlags::ACCELERATION_STRUCTURE_READ_KHR,
+            );
+        unsafe {
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                src_stage,
+                dst_stage,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+        }
+    }
+}
+
+impl Drop for AccelerationStructure {
+    fn drop(&mut self) {
+        unsafe {
+            self.accel_fn
+                .destroy_acceleration_structure(self.handle, None);
+        }
+    }
+}
+
+/// Scratch memory for builds, aligned to `min_scratch_alignment`.
+pub struct ScratchBuffer {
+    pub buffer: Buffer,
+    pub address: vk::DeviceAddress,
+}
+
+impl ScratchBuffer {
+    pub fn new(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        rt: &RayTracingContext,
+        size: u64,
+    ) -> ReactorResult<Self> {
+        let alignment = rt.min_scratch_alignment;
+        let buffer = Buffer::new(
+            ctx,
+            allocator,
+            size + alignment,
+            vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
+            MemoryLocation::GpuOnly,
+        )?;
+        let address = align_up(buffer.device_address(), alignment);
+        Ok(Self { buffer, address })
+    }
+}
+
+fn align_up(value: u64, alignment: u64) -> u64 {
+    (value + alignment - 1) / alignment * alignment
+}
+
+/// Bottom level acceleration structure over the triangles of one mesh.
+pub struct Blas {
+    pub structure: AccelerationStructure,
+    pub triangle_count: u32,
+}
+
+impl Blas {
+    /// Creates the BLAS for `mesh` and records its build. The returned
+    /// scratch buffer must outlive `command_buffer`. Only the position
+    /// (first three floats of every vertex) is read, so any vertex layout
+    /// that starts with it works; skinned meshes use the rest pose.
+    pub fn build(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        rt: &RayTracingContext,
+        command_buffer: vk::CommandBuffer,
+        mesh: &Mesh,
+    ) -> ReactorResult<(Self, ScratchBuffer)> {
+        if mesh.vertex_count == 0 || mesh.index_count < 3 {
+            return Err(ReactorError::invalid_parameter("BLAS needs at least one triangle"));
+        }
+        let triangle_count = mesh.index_count / 3;
+        let stride = mesh.vertex_buffer.size / mesh.vertex_count as u64;
+
+        let triangles = vk::AccelerationStructureGeometryTrianglesDataKHR::default()
+            .vertex_format(vk::Format::R32G32B32_SFLOAT)
+            .vertex_data(vk::DeviceOrHostAddressConstKHR {
+                device_address: mesh.vertex_buffer.device_address(),
+            })
+            .vertex_stride(stride)
+            .max_vertex(mesh.vertex_count - 1)
+            .index_type(vk::IndexType::UINT32)
+            .index_data(vk::DeviceOrHostAddressConstKHR {
+                device_address: mesh.index_buffer.device_address(),
+            });
+        let geometry = vk::AccelerationStructureGeometryKHR::default()
+            .geometry_type(vk::GeometryTypeKHR::TRIANGLES)
+            .geometry(vk::AccelerationStructureGeometryDataKHR { triangles })
+            .flags(vk::GeometryFlagsKHR::OPAQUE);
+        let geometries = [geometry];
+        let mut build_info = vk::AccelerationStructureBuildGeometryInfoKHR::default()
+            .ty(vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL)
+            .flags(vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE)
+            .mode(vk::BuildAccelerationStructureModeKHR::BUILD)
+            .geometries(&geometries);
+
+        let mut sizes = vk::AccelerationStructureBuildSizesInfoKHR::default();
+        unsafe {
+            rt.accel_fn.get_acceleration_structure_build_sizes(
+                vk::AccelerationStructureBuildTypeKHR::DEVICE,
+                &build_info,
+                &[triangle_count],
+                &mut sizes,
+            );
+        }
+
+        let structure = AccelerationStructure::new(
+            ctx,
+            allocator.clone(),
+            rt,
+            vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL,
+            sizes.acceleration_structure_size,
+        )?;
+        let scratch = ScratchBuffer::new(ctx, allocator, rt, sizes.build_scratch_size)?;
+
+        build_info = build_info
+            .dst_acceleration_structure(structure.handle)
+            .scratch_data(vk::DeviceOrHostAddressKHR { device_address: scratch.address });
+        let range = vk::AccelerationStructureBuildRangeInfoKHR::default().primitive_count(triangle_count);
+        unsafe {
+            rt.accel_fn
+                .cmd_build_acceleration_structures(command_buffer, &[build_info], &[&[range]]);
+        }
+
+        Ok((Self { structure, triangle_count }, scratch))
+    }
 }
 
-/// Placeholder for Top Level Acceleration Structure  
+/// Top level acceleration structure with room for `capacity` instances.
+/// Built with `ALLOW_UPDATE`, so moving instances only needs a refit.
 pub struct Tlas {
-    pub handle: vk::AccelerationStructureKHR,
+    pub structure: AccelerationStructure,
+    pub capacity: u32,
+    pub instance_count: u32,
+    scratch: ScratchBuffer,
 }
 
-/// Placeholder for generic Acceleration Structure
-pub struct AccelerationStructure {
-    pub handle: vk::AccelerationStructureKHR,
+impl Tlas {
+    pub fn new(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        rt: &RayTracingContext,
+        capacity: u32,
+    ) -> ReactorResult<Self> {
+        let capacity = capacity.max(1);
+        let geometries = [Self::geometry(0)];
+        let build_info = Self::build_info(&geometries, vk::BuildAccelerationStructureModeKHR::BUILD);
+        let mut sizes = vk::AccelerationStructureBuildSizesInfoKHR::default();
+        unsafe {
+            rt.accel_fn.get_acceleration_structure_build_sizes(
+                vk::AccelerationStructureBuildTypeKHR::DEVICE,
+                &build_info,
+                &[capacity],
+                &mut sizes,
+            );
+        }
+
+        let structure = AccelerationStructure::new(
+            ctx,
+            allocator.clone(),
+            rt,
+            vk::AccelerationStructureTypeKHR::TOP_LEVEL,
+            sizes.acceleration_structure_size,
+        )?;
+        // The same scratch serves builds and refits; every use is ordered by
+        // the build barrier, so one buffer is enough across frames in flight.
+        let scratch_size = sizes.build_scratch_size.max(sizes.update_scratch_size);
+        let scratch = ScratchBuffer::new(ctx, allocator, rt, scratch_size)?;
+
+        Ok(Self {
+            structure,
+            capacity,
+            instance_count: 0,
+            scratch,
+        })
+    }
+
+    fn geometry(instances_address: vk::DeviceAddress) -> vk::AccelerationStructureGeometryKHR<'static> {
+        let instances = vk::AccelerationStructureGeometryInstancesDataKHR::default()
+            .array_of_pointers(false)
+            .data(vk::DeviceOrHostAddressConstKHR { device_address: instances_address });
+        vk::AccelerationStructureGeometryKHR::default()
+            .geometry_type(vk::GeometryTypeKHR::INSTANCES)
+            .geometry(vk::AccelerationStructureGeometryDataKHR { instances })
+    }
+
+    fn build_info<'a>(
+        geometries: &'a [vk::AccelerationStructureGeometryKHR<'a>],
+        mode: vk::BuildAccelerationStructureModeKHR,
+    ) -> vk::AccelerationStructureBuildGeometryInfoKHR<'a> {
+        vk::AccelerationStructureBuildGeometryInfoKHR::default()
+            .ty(vk::AccelerationStructureTypeKHR::TOP_LEVEL)
+            .flags(
+                vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE
+                    | vk::BuildAccelerationStructureFlagsKHR::ALLOW_UPDATE,
+            )
+            .mode(mode)
+            .geometries(geometries)
+    }
+
+    /// Records a build from `instance_count` instances at `instances_address`
+    /// (a buffer of `vk::AccelerationStructureInstanceKHR`). With `refit` the
+    /// structure is updated in place, which is only valid when the instances
+    /// and their BLASes match the last build and only transforms changed.
+    pub fn record_build(
+        &mut self,
+        rt: &RayTracingContext,
+        command_buffer: vk::CommandBuffer,
+        instances_address: vk::DeviceAddress,
+        instance_count: u32,
+        refit: bool,
+    ) -> ReactorResult<()> {
+        if instance_count > self.capacity {
+            return Err(ReactorError::new(
+                ErrorCode::InvalidParameter,
+                format!("{} TLAS instances exceed capacity {}", instance_count, self.capacity),
+            ));
+        }
+        let refit = refit && instance_count == self.instance_count;
+        let mode = if refit {
+            vk::BuildAccelerationStructureModeKHR::UPDATE
+        } else {
+            vk::BuildAccelerationStructureModeKHR::BUILD
+        };
+        let geometries = [Self::geometry(instances_address)];
+        let mut build_info = Self::build_info(&geometries, mode)
+            .dst_acceleration_structure(self.structure.handle)
+            .scratch_data(vk::DeviceOrHostAddressKHR { device_address: self.scratch.address });
+        if refit {
+            build_info = build_info.src_acceleration_structure(self.structure.handle);
+        }
+        let range = vk::AccelerationStructureBuildRangeInfoKHR::default().primitive_count(instance_count);
+        unsafe {
+            rt.accel_fn
+                .cmd_build_acceleration_structures(command_buffer, &[build_info], &[&[range]]);
+        }
+        self.instance_count = instance_count;
+        Ok(())
+    }
+}
+
+/// Row-major 3×4 matrix of an affine `Mat4`, as instances expect it.
+pub fn transform_matrix(transform: &Mat4) -> vk::TransformMatrixKHR {
+    let rows = transform.transpose().to_cols_array();
+    let mut matrix = [0.0; 12];
+    matrix.copy_from_slice(&rows[..12]);
+    vk::TransformMatrixKHR { matrix }
+}
+
+/// TLAS instance of `blas` placed at `transform`, visible to rays whose cull
+/// mask shares a bit with `mask`.
+pub fn instance(
+    blas: &Blas,
+    transform: &Mat4,
+    custom_index: u32,
+    mask: u8,
+) -> vk::AccelerationStructureInstanceKHR {
+    vk::AccelerationStructureInstanceKHR {
+        transform: transform_matrix(transform),
+        instance_custom_index_and_mask: vk::Packed24_8::new(custom_index, mask),
+        instance_shader_binding_table_record_offset_and_flags: vk::Packed24_8::new(
+            0,
+            vk::GeometryInstanceFlagsKHR::TRIANGLE_FACING_CULL_DISABLE.as_raw() as u8,
+        ),
+        acceleration_structure_reference: vk::AccelerationStructureReferenceKHR {
+            device_handle: blas.structure.device_address,
+        },
+    }
 }
@@ -10,6 +10,10 @@ pub struct RayTracingContext {
     pub shader_group_handle_alignment: u32,
     pub max_recursion_depth: u32,
     pub max_ray_dispatch_invocation_count: u32,
+    /// Required alignment of acceleration structure scratch addresses.
+    pub min_scratch_alignment: u64,
+    /// `VK_KHR_ray_query` is enabled: raster shaders can trace the TLAS.
+    pub ray_query: bool,
 }
 
 impl RayTracingContext {
@@ -21,23 +25,26 @@ impl RayTracingContext {
 
         // Get properties
         let mut pipeline_properties = vk::PhysicalDeviceRayTracingPipelinePropertiesKHR::default();
-        let mut properties =
-            vk::PhysicalDeviceProperties2::default().push_next(&mut pipeline_properties);
+        let mut accel_properties = vk::PhysicalDeviceAccelerationStructurePropertiesKHR::default();
+        let mut properties = vk::PhysicalDeviceProperties2::default()
+            .push_next(&mut pipeline_properties)
+            .push_next(&mut accel_properties);
 
         unsafe {
             ctx.instance
                 .get_physical_device_properties2(ctx.physical_device, &mut properties);
         }
 
-        println!("Ray Tracing Properties:");
-        println!(
+        log::info!("Ray Tracing Properties:");
+        log::info!(
             "  Max Recursion Depth: {}",
             pipeline_properties.max_ray_recursion_depth
         );
-        println!(
+        log::info!(
             "  Shader Group Handle Size: {}",
             pipeline_properties.shader_group_handle_size
         );
+        log::info!("  Ray Query: {}", ctx.ray_query);
 
         Ok(Self {
             pipeline_fn,
@@ -48,6 +55,10 @@ impl RayTracingContext {
             max_recursion_depth: pipeline_properties.max_ray_recursion_depth,
             max_ray_dispatch_invocation_count: pipeline_properties
                 .max_ray_dispatch_invocation_count,
+            min_scratch_alignment: accel_properties
+                .min_acceleration_structure_scratch_offset_alignment
+                .max(1) as u64,
+            ray_query: ctx.ray_query,
         })
     }
 
@@ -5,8 +5,10 @@ pub mod acceleration_structure;
 pub mod context;
 pub mod pipeline;
 pub mod shader_binding_table;
+pub mod tlas_tracker;
 
-pub use acceleration_structure::{AccelerationStructure, Blas, Tlas};
+pub use acceleration_structure::{AccelerationStructure, Blas, ScratchBuffer, Tlas};
 pub use context::RayTracingContext;
 pub use pipeline::RayTracingPipeline;
 pub use shader_binding_table::ShaderBindingTable;
+pub use tlas_tracker::{TlasTracker, TlasUpdate};
@@ -1,5 +1,6 @@
 use crate::core::context::VulkanContext;
 use crate::core::error::ReactorResult;
+use crate::core::pipeline_cache::record_pipeline_feedback;
 use crate::raytracing::context::RayTracingContext;
 use ash::vk;
 use std::ffi::CStr;
@@ -100,18 +101,23 @@ impl RayTracingPipeline {
         let layout = unsafe { device.create_pipeline_layout(&layout_info, None)? };
 
         // Create pipeline
+        let mut feedback = vk::PipelineCreationFeedback::default();
+        let mut feedback_info =
+            vk::PipelineCreationFeedbackCreateInfo::default().pipeline_creation_feedback(&mut feedback);
         let pipeline_info = vk::RayTracingPipelineCreateInfoKHR::default()
             .stages(&stage_infos)
             .groups(&vk_groups)
             .max_pipeline_ray_recursion_depth(max_recursion.min(rt_ctx.max_recursion_depth))
-            .layout(layout);
+            .layout(layout)
+            .push_next(&mut feedback_info);
 
+        let pipeline_cache = ctx.pipeline_cache();
         let pipelines = unsafe {
             rt_ctx
                 .pipeline_fn
                 .create_ray_tracing_pipelines(
                     vk::DeferredOperationKHR::null(),
-                    vk::PipelineCache::null(),
+                    pipeline_cache,
                     &[pipeline_info],
                     None,
                 )
@@ -124,6 +130,7 @@ impl RayTracingPipeline {
                 device.destroy_shader_module(module, None);
             }
         }
+        record_pipeline_feedback(pipeline_cache, &feedback);
 
         Ok(Self {
             pipeline: pipelines[0],
@@ -0,0 +1,111 @@
+// TLAS change tracking
+//
+// Compares the instance list of a frame with the one the TLAS was last built
+// from. Same BLASes in the same order with new transforms only need a refit;
+// anything else (instances added, removed, reordered or pointing at another
+// mesh) needs a full build. Refits degrade trace quality, so after
+// `MAX_TLAS_REFITS` in a row the next change rebuilds.
+
+use glam::Mat4;
+
+/// Consecutive refits before a change forces a full build.
+pub const MAX_TLAS_REFITS: u32 = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlasUpdate {
+    /// Nothing moved: the TLAS from the last frame is still valid.
+    Unchanged,
+    /// Only transforms changed: update the TLAS in place.
+    Refit,
+    /// The instance set changed: build from scratch.
+    Rebuild,
+}
+
+#[derive(Default)]
+pub struct TlasTracker {
+    blases: Vec<usize>,
+    transforms: Vec<Mat4>,
+    refits: u32,
+    built: bool,
+}
+
+impl TlasTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records this frame's instances, as `(BLAS key, transform)` in TLAS
+    /// order, and says what the TLAS needs.
+    pub fn update(&mut self, instances: &[(usize, Mat4)]) -> TlasUpdate {
+        let same_set = self.built
+            && self.blases.len() == instances.len()
+            && self.blases.iter().zip(instances).all(|(key, (other, _))| key == other);
+
+        if same_set {
+            if self.transforms.iter().zip(instances).all(|(a, (_, b))| a == b) {
+                return TlasUpdate::Unchanged;
+            }
+            self.store_transforms(instances);
+            if self.refits < MAX_TLAS_REFITS {
+                self.refits += 1;
+                return TlasUpdate::Refit;
+            }
+        } else {
+            self.blases.clear();
+            self.blases.extend(instances.iter().map(|(key, _)| *key));
+            self.store_transforms(instances);
+        }
+        self.refits = 0;
+        self.built = true;
+        TlasUpdate::Rebuild
+    }
+
+    /// Forces a full build on the next `update` (e.g. after recreating the TLAS).
+    pub fn invalidate(&mut self) {
+        self.built = false;
+    }
+
+    fn store_transforms(&mut self, instances: &[(usize, Mat4)]) {
+        self.transforms.clear();
+        self.transforms.extend(instances.iter().map(|(_, transform)| *transform));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn moving_instances_refits_and_new_instances_rebuild() {
+        let mut tracker = TlasTracker::new();
+        let a = (1, Mat4::IDENTITY);
+        let b = (2, Mat4::from_translation(Vec3::X));
+        assert_eq!(tracker.update(&[a, b]), TlasUpdate::Rebuild);
+        assert_eq!(tracker.update(&[a, b]), TlasUpdate::Unchanged);
+
+        let moved = (2, Mat4::from_translation(Vec3::Y));
+        assert_eq!(tracker.update(&[a, moved]), TlasUpdate::Refit);
+        assert_eq!(tracker.update(&[a, moved]), TlasUpdate::Unchanged);
+
+        assert_eq!(tracker.update(&[a]), TlasUpdate::Rebuild, "instance removed");
+        assert_eq!(tracker.update(&[a, moved]), TlasUpdate::Rebuild, "instance added");
+        assert_eq!(tracker.update(&[moved, a]), TlasUpdate::Rebuild, "reordered");
+        assert_eq!(tracker.update(&[(3, Mat4::IDENTITY), a]), TlasUpdate::Rebuild, "mesh swapped");
+    }
+
+    #[test]
+    fn invalidate_and_refit_limit_force_a_build() {
+        let mut tracker = TlasTracker::new();
+        let instance = |x: f32| [(7, Mat4::from_translation(Vec3::new(x, 0.0, 0.0)))];
+        assert_eq!(tracker.update(&instance(0.0)), TlasUpdate::Rebuild);
+        tracker.invalidate();
+        assert_eq!(tracker.update(&instance(0.0)), TlasUpdate::Rebuild);
+
+        for i in 1..=MAX_TLAS_REFITS {
+            assert_eq!(tracker.update(&instance(i as f32)), TlasUpdate::Refit);
+        }
+        assert_eq!(tracker.update(&instance(-1.0)), TlasUpdate::Rebuild);
+        assert_eq!(tracker.update(&instance(-2.0)), TlasUpdate::Refit);
+    }
+}
@@ -0,0 +1,289 @@
+//! Lectura de frames a CPU: `read_pixels` (headless) y capturas del
+//! swapchain (`screenshot` → PNG, `capture_frame` → `RgbaImage`).
+//!
+//! Una captura pedida se graba en el command buffer del siguiente frame,
+//! justo antes del present: barrier a `TRANSFER_SRC`, copia a un buffer
+//! `GpuToCpu` y vuelta a `PRESENT_SRC_KHR`. El buffer se lee al empezar el
+//! frame siguiente, esperando sólo el fence de ese frame (como mucho un frame
+//! de espera, nunca un `device_wait_idle`).
+
+use super::Reactor;
+use crate::core::error::{set_last_error, ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::buffer::Buffer;
+use ash::vk;
+use gpu_allocator::MemoryLocation;
+use std::path::{Path, PathBuf};
+
+/// Qué hacer con el frame capturado.
+pub(crate) enum CaptureTarget {
+    /// Se guarda para `capture_frame`.
+    Memory,
+    /// Se escribe como PNG.
+    Png(PathBuf),
+}
+
+/// Copia grabada en un frame cuyo fence aún no se ha esperado.
+pub(crate) struct InFlightCapture {
+    buffer: Buffer,
+    frame: usize,
+    extent: vk::Extent2D,
+    format: vk::Format,
+    target: CaptureTarget,
+}
+
+impl Reactor {
+    /// `true` si el Reactor se creó con `init_headless`.
+    pub fn is_headless(&self) -> bool {
+        self.swapchain.is_offscreen()
+    }
+
+    /// Píxeles RGBA8 (fila a fila, de arriba abajo) del último frame
+    /// renderizado en modo headless. Espera a que la GPU termine.
+    pub fn read_pixels(&mut self) -> ReactorResult<Vec<u8>> {
+        if !self.is_headless() {
+            return Err(ReactorError::new(ErrorCode::NotSupported, "read_pixels is only available in headless mode"));
+        }
+        let image_index = self
+            .last_rendered_image
+            .ok_or_else(|| ReactorError::new(ErrorCode::NotInitialized, "no frame has been rendered yet"))?;
+        self.context.wait_idle()?;
+        let image = self.swapchain.images[image_index as usize];
+        self.copy_image_rgba(image, vk::ImageLayout::PRESENT_SRC_KHR)
+    }
+
+    /// Guarda el próximo frame presentado como PNG en `path`. Aquí sólo se
+    /// comprueba que el directorio exista; el PNG se escribe al empezar el
+    /// frame siguiente en un temporal que luego se renombra a `path`, así que
+    /// nunca queda un fichero a medias. Los errores de entonces van a
+    /// `set_last_error`.
+    pub fn screenshot(&mut self, path: impl Into<PathBuf>) -> ReactorResult<()> {
+        self.check_capture_supported()?;
+        let path = path.into();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        if !dir.is_dir() || path.is_dir() {
+            return Err(ReactorError::new(
+                ErrorCode::IoError,
+                format!("cannot write screenshot to {}", path.display()),
+            ));
+        }
+        self.capture_request = Some(CaptureTarget::Png(path));
+        Ok(())
+    }
+
+    /// Pide capturar el próximo frame presentado para `capture_frame`.
+    pub fn request_capture(&mut self) -> ReactorResult<()> {
+        self.check_capture_supported()?;
+        self.capture_request = Some(CaptureTarget::Memory);
+        Ok(())
+    }
+
+    /// Frame capturado como imagen RGBA. En headless lee el último frame;
+    /// con ventana devuelve el pedido con `request_capture` (esperando su
+    /// fence si aún está en vuelo).
+    pub fn capture_frame(&mut self) -> ReactorResult<image::RgbaImage> {
+        if self.is_headless() {
+            let pixels = self.read_pixels()?;
+            let extent = self.swapchain.extent;
+            return image::RgbaImage::from_raw(extent.width, extent.height, pixels)
+                .ok_or_else(|| ReactorError::internal("readback size does not match the frame"));
+        }
+        if matches!(self.capture_in_flight, Some(InFlightCapture { target: CaptureTarget::Memory, .. })) {
+            self.resolve_capture();
+        }
+        self.captured_frame.take().ok_or_else(|| {
+            ReactorError::new(ErrorCode::NotInitialized, "no captured frame; call request_capture and render a frame first")
+        })
+    }
+
+    fn check_capture_supported(&self) -> ReactorResult<()> {
+        if !self.swapchain.supports_readback {
+            return Err(ReactorError::new(ErrorCode::NotSupported, "swapchain images cannot be used as a copy source"));
+        }
+        rgba_swizzle(self.swapchain.format).map(|_| ())
+    }
+
+    /// Si hay una captura pedida, graba su copia en `command_buffer`. La
+    /// imagen debe estar ya en `PRESENT_SRC_KHR` (y así se queda).
+    pub(crate) fn record_pending_capture(&mut self, command_buffer: vk::CommandBuffer, image: vk::Image) {
+        let Some(target) = self.capture_request.take() else { return };
+        let extent = self.swapchain.extent;
+        let buffer = match Buffer::new(
+            &self.context,
+            self.allocator.clone(),
+            extent.width as u64 * extent.height as u64 * 4,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            MemoryLocation::GpuToCpu,
+        ) {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                set_last_error(e);
+                return;
+            }
+        };
+        self.record_copy(command_buffer, image, vk::ImageLayout::PRESENT_SRC_KHR, &buffer);
+        self.capture_in_flight = Some(InFlightCapture {
+            buffer,
+            frame: self.current_frame,
+            extent,
+            format: self.swapchain.format,
+            target,
+        });
+    }
+
+    /// Termina la captura en vuelo: espera su fence, convierte a RGBA y la
+    /// guarda en memoria o en disco. Se llama al empezar cada frame.
+    pub(crate) fn resolve_capture(&mut self) {
+        let Some(capture) = self.capture_in_flight.take() else { return };
+        let fence = self.in_flight_fences[capture.frame];
+        if let Err(e) = unsafe { self.context.device.wait_for_fences(&[fence], true, u64::MAX) } {
+            set_last_error(ReactorError::with_source(ErrorCode::VulkanSynchronization, "wait_for_fences failed", e));
+            return;
+        }
+        let result = capture_to_image(&capture).and_then(|image| match capture.target {
+            CaptureTarget::Memory => {
+                self.captured_frame = Some(image);
+                Ok(())
+            }
+            CaptureTarget::Png(ref path) => write_png(&image, path),
+        });
+        if let Err(e) = result {
+            set_last_error(e);
+        }
+    }
+
+    /// Copia una imagen de color del tamaño del swapchain a CPU como RGBA8.
+    /// `layout` es el layout en el que está la imagen (y en el que se deja).
+    pub(crate) fn copy_image_rgba(&self, image: vk::Image, layout: vk::ImageLayout) -> ReactorResult<Vec<u8>> {
+        let extent = self.swapchain.extent;
+        let size = extent.width as u64 * extent.height as u64 * 4;
+        let staging = Buffer::new(
+            &self.context,
+            self.allocator.clone(),
+            size,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            MemoryLocation::GpuToCpu,
+        )?;
+
+        let device = &self.context.device;
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .command_pool(self.command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        let command_buffer = unsafe {
+            device.allocate_command_buffers(&alloc_info).map_err(|e| {
+                ReactorError::with_source(ErrorCode::VulkanCommandPool, "allocate_command_buffers failed", e)
+            })?[0]
+        };
+
+        let result = unsafe {
+            let begin_info = vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+            device.begin_command_buffer(command_buffer, &begin_info)
+                .and_then(|_| {
+                    self.record_copy(command_buffer, image, layout, &staging);
+                    device.end_command_buffer(command_buffer)
+                })
+                .and_then(|_| {
+                    let command_buffers = [command_buffer];
+                    let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+                    device.queue_submit(self.context.graphics_queue, &[submit_info], vk::Fence::null())
+                })
+                .and_then(|_| device.queue_wait_idle(self.context.graphics_queue))
+        };
+        unsafe { device.free_command_buffers(self.command_pool, &[command_buffer]) };
+        result.map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandBuffer, "image readback failed", e))?;
+
+        let mut pixels = vec![0u8; size as usize];
+        if !staging.read(&mut pixels) {
+            return Err(ReactorError::internal("readback buffer is not host-visible"));
+        }
+        to_rgba8(self.swapchain.format, &mut pixels)?;
+        Ok(pixels)
+    }
+
+    /// Graba `layout → TRANSFER_SRC`, la copia a `buffer` y la vuelta a `layout`.
+    fn record_copy(&self, command_buffer: vk::CommandBuffer, image: vk::Image, layout: vk::ImageLayout, buffer: &Buffer) {
+        let extent = self.swapchain.extent;
+        let range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+        };
+        let to_transfer = vk::ImageMemoryBarrier::default()
+            .old_layout(layout)
+            .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .image(image)
+            .subresource_range(range);
+        let restore = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .new_layout(layout)
+            .src_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .dst_access_mask(vk::AccessFlags::MEMORY_READ)
+            .image(image)
+            .subresource_range(range);
+        let region = vk::BufferImageCopy::default()
+            .image_subresource(vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR, mip_level: 0, base_array_layer: 0, layer_count: 1,
+            })
+            .image_extent(vk::Extent3D { width: extent.width, height: extent.height, depth: 1 });
+
+        unsafe {
+            let device = &self.context.device;
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(), &[], &[], &[to_transfer]);
+            device.cmd_copy_image_to_buffer(command_buffer, image, vk::ImageLayout::TRANSFER_SRC_OPTIMAL, buffer.handle, &[region]);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::TRANSFER, vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                vk::DependencyFlags::empty(), &[], &[], &[restore]);
+        }
+    }
+}
+
+fn capture_to_image(capture: &InFlightCapture) -> ReactorResult<image::RgbaImage> {
+    let mut pixels = vec![0u8; capture.extent.width as usize * capture.extent.height as usize * 4];
+    if !capture.buffer.read(&mut pixels) {
+        return Err(ReactorError::internal("readback buffer is not host-visible"));
+    }
+    to_rgba8(capture.format, &mut pixels)?;
+    image::RgbaImage::from_raw(capture.extent.width, capture.extent.height, pixels)
+        .ok_or_else(|| ReactorError::internal("readback size does not match the frame"))
+}
+
+/// Escribe `image` en un temporal junto a `path` y lo renombra encima.
+fn write_png(image: &image::RgbaImage, path: &Path) -> ReactorResult<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    let result = image
+        .save_with_format(&tmp, image::ImageFormat::Png)
+        .map_err(|e| ReactorError::with_source(ErrorCode::IoError, format!("failed to write screenshot {}", path.display()), e))
+        .and_then(|_| {
+            std::fs::rename(&tmp, path).map_err(|e| {
+                ReactorError::with_source(ErrorCode::IoError, format!("failed to write screenshot {}", path.display()), e)
+            })
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// `true` si el formato guarda los canales como BGRA (hay que intercambiar R y B).
+fn rgba_swizzle(format: vk::Format) -> ReactorResult<bool> {
+    match format {
+        vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => Ok(true),
+        vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => Ok(false),
+        other => Err(ReactorError::new(ErrorCode::NotSupported, format!("cannot read back {:?} images", other))),
+    }
+}
+
+/// Reordena in situ píxeles de 4 bytes del formato de la imagen a RGBA8.
+fn to_rgba8(format: vk::Format, pixels: &mut [u8]) -> ReactorResult<()> {
+    if rgba_swizzle(format)? {
+        pixels.chunks_exact_mut(4).for_each(|px| px.swap(0, 2));
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Selección del path de render: forward o deferred.
+//!
+//! En deferred, `draw_scene` escribe el G-Buffer con los objetos que sólo
+//! dependen de push constants (materiales sin texturas ni IBL, no
+//! instanciados ni skinned), resuelve la iluminación en una pasada a pantalla
+//! completa y después dibuja en forward el resto de objetos sobre el
+//! resultado, reutilizando el depth del G-Buffer.
+//!
+//! El G-Buffer no es multisample: el modo deferred exige `msaa_samples = 1`
+//! (el runner lo fuerza al leer `ReactorConfig::renderer`).
+
+use super::Reactor;
+use crate::app::config::RendererMode;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::systems::scene::SceneObject;
+use ash::vk;
+
+impl Reactor {
+    /// Cambia el path de `draw_scene`. El primer paso a `Deferred` crea sus
+    /// pipelines; si falla (o hay MSAA activo) el Reactor sigue en forward.
+    /// `RayTracing` se dibuja de momento con el path forward.
+    pub fn set_renderer_mode(&mut self, mode: RendererMode) -> ReactorResult<()> {
+        if mode == RendererMode::Deferred {
+            if self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
+                return Err(ReactorError::new(
+                    ErrorCode::InvalidParameter,
+                    format!("Deferred rendering needs MSAA 1x (current: {:?})", self.msaa_samples),
+                ));
+            }
+            if self.deferred.is_none() {
+                self.init_deferred()?;
+            }
+        }
+        self.renderer_mode = mode;
+        Ok(())
+    }
+
+    /// Path configurado con `set_renderer_mode`.
+    pub fn renderer_mode(&self) -> RendererMode {
+        self.renderer_mode
+    }
+
+    pub(crate) fn deferred_active(&self) -> bool {
+        self.renderer_mode == RendererMode::Deferred && self.deferred.is_some() && self.gbuffer.is_some()
+    }
+}
+
+/// `true` si el objeto puede ir al G-Buffer: su material sólo lee push
+/// constants (y como mucho su UBO, cuyo color ya va en `push.color`), que es
+/// justo lo que guarda `gbuffer.frag`. Los transparentes se mezclan en forward.
+pub(crate) fn renders_to_gbuffer(object: &SceneObject) -> bool {
+    let material = &object.material;
+    (material.descriptor_set.is_none() || material.uniform_only_set)
+        && !material.uses_ibl
+        && !material.is_transparent()
+        && !material.pipeline.is_instanced()
+        && !material.pipeline.is_skinned()
+}
@@ -0,0 +1,37 @@
+use crate::graphics::post_process::{AAMode, AAQualityPreset, AASettings, AAStats};
+use crate::reactor::Reactor;
+
+impl Reactor {
+    /// Anti-aliasing del pase de post-proceso. Sólo cambia push constants: ni
+    /// pipelines ni swapchain se recrean.
+    pub fn set_aa_mode(&mut self, mode: AAMode) {
+        self.post_process.settings.set_aa_mode(mode);
+    }
+
+    pub fn aa_mode(&self) -> AAMode {
+        self.post_process.settings.aa_mode()
+    }
+
+    /// Umbrales de borde de un preset de `AASettings`; `Off` apaga el AA.
+    pub fn set_aa_preset(&mut self, preset: AAQualityPreset) {
+        self.post_process.settings.apply_aa_settings(&AASettings::from_preset(preset));
+    }
+
+    /// Cuenta los bordes que suaviza el AA (un atomic por borde en el shader).
+    /// Devuelve `false` si el dispositivo no permite escribir desde fragment
+    /// shaders y por tanto no se pudo activar.
+    pub fn set_aa_stats_enabled(&mut self, enabled: bool) -> bool {
+        let enabled = enabled && self.context.supports_fragment_stores_and_atomics();
+        self.post_process.aa_stats_enabled = enabled;
+        if !enabled {
+            self.post_process.aa_stats = None;
+        }
+        enabled
+    }
+
+    /// Último frame medido, con un par de frames de retraso. `None` sin
+    /// estadísticas activas, con el AA apagado o sin post-proceso.
+    pub fn aa_stats(&self) -> Option<AAStats> {
+        self.post_process.aa_stats
+    }
+}
@@ -2,15 +2,18 @@ use crate::reactor::Reactor;
 use ash::vk;
 
 impl Reactor {
+    /// Enlaza el IBL (set = 1) y las sombras (set = 2) que pida el material.
+    /// Devuelve cuántos sets enlazó.
     pub unsafe fn bind_reactor_system_descriptors(
         &self,
         command_buffer: vk::CommandBuffer,
         pipeline_layout: vk::PipelineLayout,
         bind_ibl: bool,
         has_shadow_set: bool,
-    ) {
+    ) -> u32 {
+        let mut bound = 0;
         if bind_ibl {
-            if let Some(ref ibl) = self.ibl_textures {
+            if let Some(ibl) = self.ibl_textures.as_ref().or(self.ibl_fallback.as_ref()) {
                 self.context.device.cmd_bind_descriptor_sets(
                     command_buffer,
                     vk::PipelineBindPoint::GRAPHICS,
@@ -19,6 +22,7 @@ impl Reactor {
                     &[ibl.descriptor_set],
                     &[],
                 );
+                bound += 1;
             }
         }
 
@@ -31,6 +35,34 @@ impl Reactor {
                 &[self.shadow_descriptor_sets[self.current_frame]],
                 &[],
             );
+            bound += 1;
         }
+        bound
+    }
+
+    /// Enlaza el UBO de luces del frame actual en set = 3. Devuelve cuántos
+    /// sets enlazó (0 sin luces inicializadas).
+    ///
+    /// # Safety
+    /// `command_buffer` debe estar grabando, y `pipeline_layout` debe tener
+    /// en set = 3 el layout de luces del Reactor (un UBO dinámico en
+    /// binding 0).
+    pub unsafe fn bind_light_descriptors(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        pipeline_layout: vk::PipelineLayout,
+    ) -> u32 {
+        if let Some(&set) = self.light_descriptor_sets.get(self.current_frame) {
+            self.context.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                pipeline_layout,
+                3,
+                &[set],
+                &[self.light_uniform_offset],
+            );
+            return 1;
+        }
+        0
     }
 }
@@ -0,0 +1,54 @@
+use crate::core::error::ReactorResult;
+use crate::graphics::debug_renderer::DebugLine;
+use crate::reactor::Reactor;
+use crate::resources::vertex::Vertex;
+use ash::vk;
+
+impl Reactor {
+    /// Reemplaza las líneas a dibujar en el próximo `draw_scene`.
+    ///
+    /// Se consumen en ese frame; si no se vuelve a llamar, el frame siguiente
+    /// no dibuja ninguna.
+    pub fn set_debug_lines<'a>(&mut self, lines: impl IntoIterator<Item = &'a DebugLine>) {
+        self.debug_line_vertices.clear();
+        for line in lines {
+            let color = line.color.truncate();
+            let alpha = glam::Vec2::new(line.color.w, 0.0);
+            self.debug_line_vertices.push(Vertex::new(line.start, color, alpha));
+            self.debug_line_vertices.push(Vertex::new(line.end, color, alpha));
+        }
+    }
+
+    /// Graba las líneas pendientes dentro del render pass de geometría activo.
+    pub(super) fn record_debug_lines(
+        &mut self,
+        command_buffer: vk::CommandBuffer,
+        view_projection: &glam::Mat4,
+    ) -> ReactorResult<()> {
+        if self.debug_line_vertices.is_empty() || self.debug_line_pipeline.is_none() {
+            return Ok(());
+        }
+
+        let needed = std::mem::size_of_val(self.debug_line_vertices.as_slice()) as u64;
+        let buffer = self.debug_line_buffers.ensure(&self.context, self.allocator.clone(), self.current_frame, needed)?;
+        buffer.write(&self.debug_line_vertices);
+        let pipeline = self.debug_line_pipeline.as_ref().unwrap();
+        let vertex_count = self.debug_line_vertices.len() as u32;
+
+        unsafe {
+            let device = &self.context.device;
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            let constants = std::slice::from_raw_parts(
+                view_projection as *const glam::Mat4 as *const u8,
+                std::mem::size_of::<glam::Mat4>(),
+            );
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, constants);
+            device.cmd_bind_vertex_buffers(command_buffer, 0, &[buffer.handle], &[0]);
+            device.cmd_draw(command_buffer, vertex_count, 1, 0, 0);
+        }
+
+        self.debug_line_vertices.clear();
+        Ok(())
+    }
+}
@@ -80,18 +80,7 @@ impl Reactor {
                 pipeline.pipeline,
             );
 
-            let viewport = vk::Viewport {
-                x: 0.0,
-                y: 0.0,
-                width: self.swapchain.extent.width as f32,
-                height: self.swapchain.extent.height as f32,
-                min_depth: 0.0,
-                max_depth: 1.0,
-            };
-            let scissor = vk::Rect2D {
-                offset: vk::Offset2D { x: 0, y: 0 },
-                extent: self.swapchain.extent,
-            };
+            let (viewport, scissor) = self.scene_viewport(self.swapchain.extent);
             self.context.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
             self.context.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
 
@@ -100,7 +89,8 @@ impl Reactor {
             self.context.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
             self.context.device.cmd_bind_index_buffer(command_buffer, cube_mesh.index_buffer.handle, 0, vk::IndexType::UINT32);
 
-            let view_proj_inv = view_proj.inverse();
+            // `decal.frag` reconstruye desde las UV del target completo.
+            let view_proj_inv = (self.viewport_ndc(self.swapchain.extent) * *view_proj).inverse();
 
             for decal in &self.decals {
                 decal.update_depth_descriptor(
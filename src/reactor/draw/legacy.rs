@@ -1,4 +1,4 @@
-use crate::reactor::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::reactor::Reactor;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::resources::material::Material;
 use crate::resources::mesh::Mesh;
@@ -17,7 +17,9 @@ impl Reactor {
 
         if self.resized {
             self.recreate_swapchain()?;
-            self.resized = false;
+        }
+        if self.minimized {
+            return Ok(());
         }
 
         unsafe {
@@ -28,7 +30,7 @@ impl Reactor {
             ) {
                 Ok(_) => {}
                 Err(vk::Result::ERROR_DEVICE_LOST) => {
-                    eprintln!(
+                    log::error!(
                         "REACTOR FATAL: Dispositivo Vulkan perdido (wait_for_fences). El driver puede haber crasheado."
                     );
                     self.device_lost = true;
@@ -46,14 +48,14 @@ impl Reactor {
                 }
             }
         }
-
-        let (image_index, _) = unsafe {
-            match self.swapchain.loader.acquire_next_image(
-                self.swapchain.handle,
-                u64::MAX,
-                self.image_available_semaphores[self.current_frame],
-                vk::Fence::null(),
-            ) {
+        self.resolve_capture();
+        self.timeline.slot_finished(self.current_frame);
+        self.descriptor_allocator.lock().unwrap().reset_transient(self.current_frame);
+        self.mesh_uploader.pump();
+        self.frame_resources[self.current_frame].clear();
+
+        let (image_index, suboptimal) = unsafe {
+            match self.acquire_image() {
                 Ok(result) => {
                     self.context
                         .device
@@ -80,6 +82,10 @@ impl Reactor {
                 }
             }
         };
+        // SUBOPTIMAL: la imagen sigue siendo presentable; recreamos en el próximo frame.
+        if suboptimal {
+            self.resized = true;
+        }
 
         let command_buffer = self.command_buffers[self.current_frame];
         unsafe {
@@ -109,7 +115,8 @@ impl Reactor {
                     )
                 })?;
 
-            let swapchain_view = self.swapchain.image_views[image_index as usize];
+            // Con HDR se dibuja en la offscreen y el tonemap la lleva al swapchain.
+            let (target_view, target_image) = self.geometry_target(image_index, false);
             let msaa_enabled =
                 self.msaa_samples != vk::SampleCountFlags::TYPE_1 && self.msaa_image_view.is_some();
 
@@ -118,22 +125,18 @@ impl Reactor {
                     .image_view(self.msaa_image_view.unwrap())
                     .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                     .resolve_mode(vk::ResolveModeFlags::AVERAGE)
-                    .resolve_image_view(swapchain_view)
+                    .resolve_image_view(target_view)
                     .resolve_image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                     .load_op(vk::AttachmentLoadOp::CLEAR)
                     .store_op(vk::AttachmentStoreOp::DONT_CARE)
-                    .clear_value(vk::ClearValue {
-                        color: vk::ClearColorValue { float32: [0.1, 0.1, 0.1, 1.0] },
-                    })
+                    .clear_value(self.clear_color_value())
             } else {
                 vk::RenderingAttachmentInfo::default()
-                    .image_view(swapchain_view)
+                    .image_view(target_view)
                     .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                     .load_op(vk::AttachmentLoadOp::CLEAR)
                     .store_op(vk::AttachmentStoreOp::STORE)
-                    .clear_value(vk::ClearValue {
-                        color: vk::ClearColorValue { float32: [0.1, 0.1, 0.1, 1.0] },
-                    })
+                    .clear_value(self.clear_color_value())
             };
 
             let depth_attachment = vk::RenderingAttachmentInfo::default()
@@ -162,7 +165,7 @@ impl Reactor {
                 .new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                 .src_access_mask(vk::AccessFlags::empty())
                 .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
-                .image(swapchain_image)
+                .image(target_image)
                 .subresource_range(vk::ImageSubresourceRange {
                     aspect_mask: vk::ImageAspectFlags::COLOR,
                     base_mip_level: 0,
@@ -227,18 +230,7 @@ impl Reactor {
                 material.pipeline.pipeline,
             );
 
-            let viewport = vk::Viewport {
-                x: 0.0,
-                y: 0.0,
-                width: self.swapchain.extent.width as f32,
-                height: self.swapchain.extent.height as f32,
-                min_depth: 0.0,
-                max_depth: 1.0,
-            };
-            let scissor = vk::Rect2D {
-                offset: vk::Offset2D { x: 0, y: 0 },
-                extent: self.swapchain.extent,
-            };
+            let (viewport, scissor) = self.scene_viewport(self.swapchain.extent);
             self.context
                 .device
                 .cmd_set_viewport(command_buffer, 0, &[viewport]);
@@ -280,6 +272,10 @@ impl Reactor {
 
             self.context.device.cmd_end_rendering(command_buffer);
 
+            if self.hdr_enabled() {
+                self.record_tonemap(command_buffer, image_index);
+            }
+
             let image_barrier = vk::ImageMemoryBarrier::default()
                 .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                 .new_layout(vk::ImageLayout::PRESENT_SRC_KHR)
@@ -303,6 +299,7 @@ impl Reactor {
                 &[],
                 &[image_barrier],
             );
+            self.record_pending_capture(command_buffer, swapchain_image);
 
             self.context
                 .device
@@ -316,33 +313,15 @@ impl Reactor {
                 })?;
         }
 
+        if self.swapchain.is_offscreen() {
+            self.submit_offscreen(command_buffer, image_index)?;
+            self.advance_frame();
+            return Ok(());
+        }
+
         let wait_semaphores = [self.image_available_semaphores[self.current_frame]];
         let signal_semaphores = [self.render_finished_semaphores[image_index as usize]];
-        let command_buffers_submit = [command_buffer];
-        let wait_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
-
-        let submit_info = vk::SubmitInfo::default()
-            .wait_semaphores(&wait_semaphores)
-            .wait_dst_stage_mask(&wait_stages)
-            .command_buffers(&command_buffers_submit)
-            .signal_semaphores(&signal_semaphores);
-
-        unsafe {
-            self.context
-                .device
-                .queue_submit(
-                    self.context.graphics_queue,
-                    &[submit_info],
-                    self.in_flight_fences[self.current_frame],
-                )
-                .map_err(|e| {
-                    ReactorError::with_source(
-                        ErrorCode::VulkanSynchronization,
-                        "queue_submit failed",
-                        e,
-                    )
-                })?;
-        }
+        self.submit_frame(command_buffer, &wait_semaphores, &signal_semaphores)?;
 
         let swapchains = [self.swapchain.handle];
         let image_indices = [image_index];
@@ -357,10 +336,13 @@ impl Reactor {
                 .queue_present(self.context.graphics_queue, &present_info)
         };
 
-        self.current_frame = (self.current_frame + 1) % MAX_FRAMES_IN_FLIGHT;
+        self.advance_frame();
 
         match result {
-            Ok(_) => Ok(()),
+            Ok(suboptimal) => {
+                self.resized |= suboptimal;
+                Ok(())
+            }
             Err(vk::Result::ERROR_OUT_OF_DATE_KHR) | Err(vk::Result::SUBOPTIMAL_KHR) => {
                 self.recreate_swapchain()?;
                 Ok(())
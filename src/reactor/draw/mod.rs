@@ -1,7 +1,16 @@
 //! Comandos de dibujo — dividido en sub-módulos especializados.
 
+pub mod aa;
 pub mod bind;
+pub mod debug_lines;
 pub mod decals;
 pub mod legacy;
+pub mod rt_shadows;
 pub mod scene;
+pub mod sdf;
+pub mod skybox;
+pub mod sprites;
+pub mod text;
+pub mod tonemap;
+pub mod viewport;
 pub mod vrs;
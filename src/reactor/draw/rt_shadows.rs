@@ -0,0 +1,260 @@
+use crate::core::error::ReactorResult;
+use crate::raytracing::acceleration_structure::instance;
+use crate::raytracing::{AccelerationStructure, Blas, Tlas, TlasUpdate};
+use crate::reactor::init::RtRetired;
+use crate::reactor::Reactor;
+use crate::systems::scene::Scene;
+use ash::vk;
+use glam::Mat4;
+use std::collections::hash_map::Entry;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Cuánto oscurece la sombra trazada (alpha del pase).
+const RT_SHADOW_STRENGTH: f32 = 0.6;
+/// Separación mínima entre la superficie y el origen del rayo.
+const RT_SHADOW_BIAS: f32 = 0.01;
+const RT_SHADOW_MAX_DISTANCE: f32 = 1000.0;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct RtShadowPushConstants {
+    inv_view_proj: [f32; 16],
+    sun: [f32; 4],
+    params: [f32; 4],
+}
+
+impl Reactor {
+    /// Activa las sombras del sol por ray tracing (ray query contra un TLAS
+    /// de la escena), sumadas a las del shadow map. Sin ray tracing o sin
+    /// `VK_KHR_ray_query` devuelve `NotSupported` y no cambia nada.
+    pub fn set_rt_shadows_enabled(&mut self, enabled: bool) -> ReactorResult<()> {
+        if enabled && self.rt_shadows.is_none() {
+            self.init_rt_shadows()?;
+        }
+        self.rt_shadows_enabled = enabled;
+        Ok(())
+    }
+
+    pub fn rt_shadows_enabled(&self) -> bool {
+        self.rt_shadows_enabled
+    }
+
+    /// Actualiza BLAS/TLAS y oscurece los píxeles que no ven el sol. Va
+    /// fuera de cualquier rendering, con el target de geometría en
+    /// `COLOR_ATTACHMENT_OPTIMAL` y el depth en `DEPTH_STENCIL_ATTACHMENT_OPTIMAL`
+    /// (los deja igual). Sin MSAA: el depth se lee por píxel.
+    pub(super) fn record_rt_shadows(
+        &mut self,
+        scene: &Scene,
+        command_buffer: vk::CommandBuffer,
+        target: (vk::ImageView, vk::Image),
+        view_projection: &Mat4,
+    ) {
+        if !self.rt_shadows_enabled || self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
+            return;
+        }
+        match self.update_scene_tlas(scene, command_buffer) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                log::warn!("RT shadows skipped: {}", e);
+                if let Some(res) = self.rt_shadows.as_mut() {
+                    res.tracker.invalidate();
+                }
+                return;
+            }
+        }
+        self.draw_rt_shadow_pass(scene, command_buffer, target, view_projection);
+    }
+
+    /// Graba las builds pendientes. `false` si no hay nada que trazar.
+    fn update_scene_tlas(&mut self, scene: &Scene, command_buffer: vk::CommandBuffer) -> ReactorResult<bool> {
+        let (Some(rt), Some(res)) = (self.ray_tracing.as_ref(), self.rt_shadows.as_mut()) else {
+            return Ok(false);
+        };
+        let device = self.context.ash_device();
+        // El fence de este slot ya se esperó: lo retirado aquí ya no se usa.
+        res.retired[self.current_frame].clear();
+
+        // Objetos rígidos que proyectan sombra; los skinned usarían la pose de reposo.
+        let casters = scene.objects.iter().filter(|object| {
+            object.visible && object.cast_shadows && object.joint_matrices.is_empty() && object.mesh.index_count >= 3
+        });
+
+        let mut barrier_recorded = false;
+        let mut instances: Vec<(usize, Mat4)> = Vec::new();
+        for object in casters {
+            let key = Arc::as_ptr(&object.mesh) as usize;
+            if let Entry::Vacant(entry) = res.blases.entry(key) {
+                if !barrier_recorded {
+                    // Lecturas y builds de frames anteriores antes de escribir.
+                    AccelerationStructure::build_barrier(device, command_buffer,
+                        vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR,
+                        vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR);
+                    barrier_recorded = true;
+                }
+                match Blas::build(&self.context, self.allocator.clone(), rt, command_buffer, &object.mesh) {
+                    Ok((blas, scratch)) => {
+                        entry.insert((object.mesh.clone(), blas));
+                        res.retired[self.current_frame].push(RtRetired::Scratch(scratch));
+                    }
+                    Err(e) => {
+                        log::warn!("BLAS build failed, object casts no RT shadow: {}", e);
+                        continue;
+                    }
+                }
+            }
+            if object.instances.is_empty() {
+                instances.push((key, object.transform));
+            } else {
+                instances.extend(object.instances.iter()
+                    .map(|data| (key, object.transform * Mat4::from_cols_array_2d(&data.model_matrix))));
+            }
+        }
+
+        let mut update = res.tracker.update(&instances);
+        if update == TlasUpdate::Rebuild {
+            // Los BLAS de mallas que ya no están se retiran con el frame.
+            let live: HashSet<usize> = instances.iter().map(|(key, _)| *key).collect();
+            let stale: Vec<usize> = res.blases.keys().filter(|key| !live.contains(key)).copied().collect();
+            for key in stale {
+                if let Some((mesh, blas)) = res.blases.remove(&key) {
+                    res.retired[self.current_frame].push(RtRetired::Blas(mesh, blas));
+                }
+            }
+        }
+        if instances.is_empty() {
+            return Ok(false);
+        }
+
+        let count = instances.len() as u32;
+        if res.tlas.as_ref().map_or(true, |tlas| tlas.capacity < count) {
+            let tlas = Tlas::new(&self.context, self.allocator.clone(), rt, count.next_power_of_two())?;
+            if let Some(old) = res.tlas.replace(tlas) {
+                res.retired[self.current_frame].push(RtRetired::Tlas(old));
+            }
+            update = TlasUpdate::Rebuild;
+        }
+        if update == TlasUpdate::Unchanged {
+            return Ok(true);
+        }
+
+        let instance_size = std::mem::size_of::<vk::AccelerationStructureInstanceKHR>() as u64;
+        let instance_buffer =
+            res.instance_buffers.ensure(&self.context, self.allocator.clone(), self.current_frame, instance_size * count as u64)?;
+        let data: Vec<vk::AccelerationStructureInstanceKHR> = instances.iter().enumerate()
+            .map(|(index, (key, transform))| instance(&res.blases[key].1, transform, index as u32, 0xFF))
+            .collect();
+        instance_buffer.write(&data);
+
+        let src_stage = if barrier_recorded {
+            vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR
+        } else {
+            vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR
+        };
+        AccelerationStructure::build_barrier(device, command_buffer, src_stage, vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR);
+        let tlas = res.tlas.as_mut().unwrap();
+        tlas.record_build(rt, command_buffer, instance_buffer.device_address(), count, update == TlasUpdate::Refit)?;
+        AccelerationStructure::build_barrier(device, command_buffer,
+            vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR, vk::PipelineStageFlags::FRAGMENT_SHADER);
+        Ok(true)
+    }
+
+    fn draw_rt_shadow_pass(
+        &mut self,
+        scene: &Scene,
+        command_buffer: vk::CommandBuffer,
+        (target_view, target_image): (vk::ImageView, vk::Image),
+        view_projection: &Mat4,
+    ) {
+        let (Some(res), Some(depth_image), Some(depth_view)) = (self.rt_shadows.as_ref(), self.depth_image, self.depth_image_view) else {
+            return;
+        };
+        let Some(tlas) = res.tlas.as_ref() else { return };
+        let set = res.descriptor_sets[self.current_frame];
+        let device = &self.context.device;
+        let extent = self.swapchain.extent;
+
+        let handles = [tlas.structure.handle];
+        let mut tlas_write = vk::WriteDescriptorSetAccelerationStructureKHR::default().acceleration_structures(&handles);
+        let depth_info = vk::DescriptorImageInfo::default()
+            .image_view(depth_view)
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+        let writes = [
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(0)
+                .descriptor_type(vk::DescriptorType::ACCELERATION_STRUCTURE_KHR)
+                .descriptor_count(1)
+                .push_next(&mut tlas_write),
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .image_info(std::slice::from_ref(&depth_info)),
+        ];
+
+        let depth_range = vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::DEPTH, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 };
+        let color_range = vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::COLOR, ..depth_range };
+        let to_read = [
+            vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .image(depth_image).subresource_range(depth_range),
+            vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(target_image).subresource_range(color_range),
+        ];
+        let to_attachment = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL).new_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+            .src_access_mask(vk::AccessFlags::SHADER_READ)
+            .dst_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE | vk::AccessFlags::SHADER_READ)
+            .image(depth_image).subresource_range(depth_range);
+
+        let color_attachment = vk::RenderingAttachmentInfo::default()
+            .image_view(target_view)
+            .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE);
+        let area = vk::Rect2D { offset: vk::Offset2D::default(), extent };
+        let rendering_info = vk::RenderingInfo::default()
+            .render_area(area)
+            .layer_count(1)
+            .color_attachments(std::slice::from_ref(&color_attachment));
+        let viewport = vk::Viewport { x: 0.0, y: 0.0, width: extent.width as f32, height: extent.height as f32, min_depth: 0.0, max_depth: 1.0 };
+
+        let toward_sun = (-scene.sun_direction).normalize_or_zero();
+        let push = RtShadowPushConstants {
+            inv_view_proj: (self.viewport_ndc(extent) * *view_projection).inverse().to_cols_array(),
+            sun: [toward_sun.x, toward_sun.y, toward_sun.z, RT_SHADOW_STRENGTH],
+            params: [extent.width as f32, extent.height as f32, RT_SHADOW_BIAS, RT_SHADOW_MAX_DISTANCE],
+        };
+
+        unsafe {
+            device.update_descriptor_sets(&writes, &[]);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::LATE_FRAGMENT_TESTS | vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::DependencyFlags::empty(), &[], &[], &to_read);
+            device.cmd_begin_rendering(command_buffer, &rendering_info);
+            device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            device.cmd_set_scissor(command_buffer, 0, &[self.scene_viewport(extent).1]);
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, res.pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, res.pipeline.layout, 0, &[set], &[]);
+            device.cmd_push_constants(command_buffer, res.pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::bytes_of(&push));
+            device.cmd_draw(command_buffer, 3, 1, 0, 0);
+            device.cmd_end_rendering(command_buffer);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS | vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(), &[], &[], &[to_attachment]);
+        }
+        self.render_stats.pipeline_binds += 1;
+        self.render_stats.descriptor_binds += 1;
+        self.render_stats.count_draw(3, 1);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::graphics::push_constants::{DeferredLightingPushConstants, ObjectPushConstants};
+use crate::reactor::deferred::renders_to_gbuffer;
+use crate::reactor::Reactor;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+fn color_range() -> vk::ImageSubresourceRange {
+    vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 }
+}
+
+fn depth_range() -> vk::ImageSubresourceRange {
+    vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::DEPTH, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 }
+}
+
+impl Reactor {
+    /// Path deferred de `draw_scene`: G-Buffer → iluminación a pantalla
+    /// completa → forward de los objetos que no caben en el G-Buffer.
+    /// Deja el resultado en el mismo target que `render_geometry`.
+    pub(super) fn render_geometry_deferred(
+        &mut self,
+        scene: &Scene,
+        command_buffer: vk::CommandBuffer,
+        image_index: u32,
+        view_projection: &glam::Mat4,
+        use_post_process: bool,
+        taa_enabled: bool,
+    ) -> glam::Mat4 {
+        let local_vp = self.jittered_view_projection(view_projection, taa_enabled);
+        let (target_view, target_image) = self.geometry_target(image_index, use_post_process);
+        let (Some(deferred), Some(gbuffer)) = (self.deferred.as_ref(), self.gbuffer.as_ref()) else {
+            return local_vp;
+        };
+        let depth_view = self.depth_image_view.unwrap();
+        let depth_img = self.depth_image.unwrap();
+        let extent = self.swapchain.extent;
+        let render_area = vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent };
+        let full_viewport = vk::Viewport { x: 0.0, y: 0.0, width: extent.width as f32, height: extent.height as f32, min_depth: 0.0, max_depth: 1.0 };
+        // G-Buffer y forward van al rectángulo de la escena; la iluminación cubre
+        // todo el target y pinta `clear_color` fuera de él.
+        let (viewport, scissor) = self.scene_viewport(extent);
+        let viewport_ndc = self.viewport_ndc(extent);
+        let clear_value = self.clear_color_value();
+
+        deferred.motion_buffers[self.current_frame].write(&[self.prev_view_projection.to_cols_array_2d()]);
+
+        let mut start_barriers: Vec<vk::ImageMemoryBarrier> = gbuffer.images().iter()
+            .chain(std::iter::once(&target_image))
+            .map(|&image| vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::UNDEFINED).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::empty()).dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(image)
+                .subresource_range(color_range()))
+            .collect();
+        start_barriers.push(vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::UNDEFINED).new_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+            .src_access_mask(vk::AccessFlags::empty()).dst_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+            .image(depth_img)
+            .subresource_range(depth_range()));
+
+        let gbuffer_attachments = gbuffer.color_attachment_infos([vk::ClearColorValue { float32: [0.0; 4] }; 4]);
+        let gbuffer_depth = vk::RenderingAttachmentInfo::default()
+            .image_view(depth_view)
+            .image_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .clear_value(vk::ClearValue { depth_stencil: vk::ClearDepthStencilValue { depth: 1.0, stencil: 0 } });
+        let gbuffer_rendering = vk::RenderingInfo::default()
+            .render_area(render_area)
+            .layer_count(1)
+            .color_attachments(&gbuffer_attachments)
+            .depth_attachment(&gbuffer_depth);
+
+        // `deferred` y `gbuffer` tienen prestado `self`: se cuenta aparte.
+        let mut pass = crate::reactor::RenderStats::default();
+        unsafe {
+            let device = &self.context.device;
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::TOP_OF_PIPE,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+                vk::DependencyFlags::empty(), &[], &[], &start_barriers);
+
+            // ── 1. G-Buffer ──
+            device.cmd_begin_rendering(command_buffer, &gbuffer_rendering);
+            device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, deferred.gbuffer_pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS,
+                deferred.gbuffer_pipeline.layout, 0, &[deferred.motion_sets[self.current_frame]], &[]);
+            pass.pipeline_binds += 1;
+            pass.descriptor_binds += 1;
+
+            for object in &scene.objects {
+                if !object.visible || object.culled || !renders_to_gbuffer(object) { continue; }
+                if let Some(name) = &object.name {
+                    self.context.debug_namer().insert_label(command_buffer, name, [1.0; 4]);
+                }
+
+                let mut push = ObjectPushConstants::new(local_vp * object.transform, object.transform);
+                push.camera_pos = [self.camera_pos.x, self.camera_pos.y, self.camera_pos.z, object.metallic];
+                push.light_pos = [self.light_pos.x, self.light_pos.y, self.light_pos.z, object.roughness];
+                let tint = object.material.tint(object.color);
+                push.color = [tint.x, tint.y, tint.z, object.anisotropy];
+                push.emission = object.emission.to_array();
+                push.extra[1] = if object.receive_shadows { 0.0 } else { 1.0 };
+                device.cmd_push_constants(command_buffer, deferred.gbuffer_pipeline.layout,
+                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, push.as_bytes());
+
+                device.cmd_bind_vertex_buffers(command_buffer, 0, &[object.mesh.vertex_buffer.handle], &[0]);
+                device.cmd_bind_index_buffer(command_buffer, object.mesh.index_buffer.handle, 0, vk::IndexType::UINT32);
+                device.cmd_draw_indexed(command_buffer, object.mesh.index_count, 1, 0, 0, 0);
+                pass.gbuffer_objects += 1;
+                pass.count_draw(object.mesh.index_count, 1);
+            }
+            device.cmd_end_rendering(command_buffer);
+
+            // ── 2. G-Buffer y depth pasan a lectura ──
+            let mut read_barriers: Vec<vk::ImageMemoryBarrier> = gbuffer.images().iter()
+                .map(|&image| vk::ImageMemoryBarrier::default()
+                    .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ)
+                    .image(image)
+                    .subresource_range(color_range()))
+                .collect();
+            read_barriers.push(vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .image(depth_img)
+                .subresource_range(depth_range()));
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+                vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(), &[], &[], &read_barriers);
+
+            // ── 3. Iluminación a pantalla completa ──
+            let lighting_attachment = vk::RenderingAttachmentInfo::default()
+                .image_view(target_view)
+                .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .load_op(vk::AttachmentLoadOp::CLEAR)
+                .store_op(vk::AttachmentStoreOp::STORE)
+                .clear_value(clear_value);
+            let lighting_rendering = vk::RenderingInfo::default()
+                .render_area(render_area)
+                .layer_count(1)
+                .color_attachments(std::slice::from_ref(&lighting_attachment));
+            device.cmd_begin_rendering(command_buffer, &lighting_rendering);
+            device.cmd_set_viewport(command_buffer, 0, &[full_viewport]);
+            device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+            let layout = deferred.lighting_pipeline.layout;
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, deferred.lighting_pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, layout, 0, &[deferred.gbuffer_set], &[]);
+            pass.pipeline_binds += 1;
+            pass.descriptor_binds += 1
+                + self.bind_reactor_system_descriptors(command_buffer, layout, false, true)
+                + self.bind_light_descriptors(command_buffer, layout);
+            let push = DeferredLightingPushConstants::new(viewport_ndc * local_vp, self.camera_view, self.camera_pos)
+                .with_clear_color(self.clear_color);
+            device.cmd_push_constants(command_buffer, layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, push.as_bytes());
+            device.cmd_draw(command_buffer, 3, 1, 0, 0);
+            pass.count_draw(3, 1);
+            device.cmd_end_rendering(command_buffer);
+
+            // ── 4. El forward reutiliza el depth del G-Buffer ──
+            let color_barrier = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(target_image)
+                .subresource_range(color_range());
+            let depth_barrier = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL).new_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+                .image(depth_img)
+                .subresource_range(depth_range());
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+                vk::DependencyFlags::empty(), &[], &[], &[color_barrier, depth_barrier]);
+        }
+        // G-Buffer + iluminación.
+        pass.objects_drawn = pass.gbuffer_objects;
+        self.render_stats.add_pass(&pass);
+
+        let color_attachment = vk::RenderingAttachmentInfo::default()
+            .image_view(target_view)
+            .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE);
+        let depth_attachment = vk::RenderingAttachmentInfo::default()
+            .image_view(depth_view)
+            .image_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE);
+        let rendering_info = vk::RenderingInfo::default()
+            .render_area(render_area)
+            .layer_count(1)
+            .color_attachments(std::slice::from_ref(&color_attachment))
+            .depth_attachment(&depth_attachment);
+
+        // ── 5. Objetos forward (texturas, IBL, instancias, skinning) ──
+        unsafe {
+            self.context.device.cmd_begin_rendering(command_buffer, &rendering_info);
+            self.context.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            self.context.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+        }
+
+        self.draw_forward_objects(scene, command_buffer, &local_vp, true);
+
+        self.record_sdf_scene(command_buffer, &local_vp);
+
+        if let Err(e) = self.record_debug_lines(command_buffer, &local_vp) {
+            log::warn!("Debug lines skipped: {}", e);
+        }
+
+        unsafe {
+            self.context.device.cmd_end_rendering(command_buffer);
+        }
+
+        self.record_rt_shadows(scene, command_buffer, (target_view, target_image), &local_vp);
+
+        self.prev_view_projection = self.camera_proj * self.camera_view;
+
+        local_vp
+    }
+}
@@ -13,44 +13,15 @@ impl Reactor {
         use_post_process: bool,
         taa_enabled: bool,
     ) -> glam::Mat4 {
-        let mut local_vp = *view_projection;
-        if taa_enabled {
-            if let Some(ref history) = self.temporal_history {
-                let f_idx = history.frame_index;
-
-                fn halton(index: u32, base: u32) -> f32 {
-                    let mut result = 0.0;
-                    let mut f = 1.0 / base as f32;
-                    let mut i = index;
-                    while i > 0 {
-                        result += f * (i % base) as f32;
-                        f /= base as f32;
-                        i /= base;
-                    }
-                    result
-                }
-
-                let halton_x = halton((f_idx % 8) as u32 + 1, 2);
-                let halton_y = halton((f_idx % 8) as u32 + 1, 3);
-                let width = self.swapchain.extent.width as f32;
-                let height = self.swapchain.extent.height as f32;
-                let jitter_x = (halton_x - 0.5) * 2.0 / width;
-                let jitter_y = (halton_y - 0.5) * 2.0 / height;
-                self.camera_proj.z_axis.x += jitter_x;
-                self.camera_proj.z_axis.y += jitter_y;
-                local_vp = self.camera_proj * self.camera_view;
-            }
-        }
-
-        let target_view = if use_post_process {
-            self.post_process.offscreen_images[image_index as usize].view
-        } else {
-            self.swapchain.image_views[image_index as usize]
-        };
-        let target_image = if use_post_process {
-            self.post_process.offscreen_images[image_index as usize].handle
-        } else {
-            self.swapchain.images[image_index as usize]
+        let local_vp = self.jittered_view_projection(view_projection, taa_enabled);
+        let (target_view, target_image) = self.geometry_target(image_index, use_post_process);
+
+        // Con escala de render se dibuja en los targets propios y se escala al final.
+        let scaled_extent = self.scaled_render_extent(use_post_process);
+        let render_extent = scaled_extent.unwrap_or(self.swapchain.extent);
+        let (target_view, target_image, depth_view, depth_img) = match (scaled_extent, &self.render_scale_targets) {
+            (Some(_), Some((color, depth))) => (color.view, color.handle, depth.view, depth.handle),
+            _ => (target_view, target_image, self.depth_image_view.unwrap(), self.depth_image.unwrap()),
         };
 
         let msaa_enabled = self.msaa_samples != vk::SampleCountFlags::TYPE_1 && self.msaa_image_view.is_some();
@@ -64,31 +35,29 @@ impl Reactor {
                 .resolve_image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                 .load_op(vk::AttachmentLoadOp::CLEAR)
                 .store_op(vk::AttachmentStoreOp::DONT_CARE)
-                .clear_value(vk::ClearValue { color: vk::ClearColorValue { float32: [0.1, 0.1, 0.1, 1.0] } })
+                .clear_value(self.clear_color_value())
         } else {
             vk::RenderingAttachmentInfo::default()
                 .image_view(target_view)
                 .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
                 .load_op(vk::AttachmentLoadOp::CLEAR)
                 .store_op(vk::AttachmentStoreOp::STORE)
-                .clear_value(vk::ClearValue { color: vk::ClearColorValue { float32: [0.1, 0.1, 0.1, 1.0] } })
+                .clear_value(self.clear_color_value())
         };
 
         let depth_attachment = vk::RenderingAttachmentInfo::default()
-            .image_view(self.depth_image_view.unwrap())
+            .image_view(depth_view)
             .image_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
             .load_op(vk::AttachmentLoadOp::CLEAR)
             .store_op(vk::AttachmentStoreOp::STORE)
             .clear_value(vk::ClearValue { depth_stencil: vk::ClearDepthStencilValue { depth: 1.0, stencil: 0 } });
 
         let rendering_info = vk::RenderingInfo::default()
-            .render_area(vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent: self.swapchain.extent })
+            .render_area(vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent: render_extent })
             .layer_count(1)
             .color_attachments(std::slice::from_ref(&color_attachment))
             .depth_attachment(&depth_attachment);
 
-        let depth_img = self.depth_image.unwrap();
-
         let color_barrier = vk::ImageMemoryBarrier::default()
             .old_layout(vk::ImageLayout::UNDEFINED).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
             .src_access_mask(vk::AccessFlags::empty()).dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
@@ -129,35 +98,158 @@ impl Reactor {
 
             self.context.device.cmd_begin_rendering(command_buffer, &rendering_info);
 
-            let viewport = vk::Viewport { x: 0.0, y: 0.0, width: self.swapchain.extent.width as f32, height: self.swapchain.extent.height as f32, min_depth: 0.0, max_depth: 1.0 };
-            let scissor = vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent: self.swapchain.extent };
+            // El clear cubre todo el target; la escena, solo su rectángulo.
+            let (viewport, scissor) = self.scene_viewport(render_extent);
             self.context.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
             self.context.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
         }
 
-        let visible_objects = scene.objects.iter().filter(|object| object.visible).count();
+        self.draw_forward_objects(scene, command_buffer, &local_vp, false);
+
+        self.record_sdf_scene(command_buffer, &local_vp);
+
+        if let Err(e) = self.record_debug_lines(command_buffer, &local_vp) {
+            log::warn!("Debug lines skipped: {}", e);
+        }
+
+        unsafe {
+            self.context.device.cmd_end_rendering(command_buffer);
+        }
+
+        if let Some(extent) = scaled_extent {
+            self.upscale_render_scale_targets(command_buffer, image_index, extent);
+        }
+
+        let target = self.geometry_target(image_index, use_post_process);
+        self.record_rt_shadows(scene, command_buffer, target, &local_vp);
+
+        self.prev_view_projection = self.camera_proj * self.camera_view;
+
+        if use_post_process && !self.decals.is_empty() {
+            let _ = self.draw_screen_space_decals(command_buffer, image_index as usize, &local_vp);
+        }
+
+        local_vp
+    }
+
+    /// `view_projection` con el jitter sub-pixel de TAA aplicado a `camera_proj`.
+    pub(super) fn jittered_view_projection(&mut self, view_projection: &glam::Mat4, taa_enabled: bool) -> glam::Mat4 {
+        let mut local_vp = *view_projection;
+        if taa_enabled {
+            if let Some(ref history) = self.temporal_history {
+                let f_idx = history.frame_index;
+
+                fn halton(index: u32, base: u32) -> f32 {
+                    let mut result = 0.0;
+                    let mut f = 1.0 / base as f32;
+                    let mut i = index;
+                    while i > 0 {
+                        result += f * (i % base) as f32;
+                        f /= base as f32;
+                        i /= base;
+                    }
+                    result
+                }
+
+                let halton_x = halton((f_idx % 8) as u32 + 1, 2);
+                let halton_y = halton((f_idx % 8) as u32 + 1, 3);
+                let width = self.swapchain.extent.width as f32;
+                let height = self.swapchain.extent.height as f32;
+                let jitter_x = (halton_x - 0.5) * 2.0 / width;
+                let jitter_y = (halton_y - 0.5) * 2.0 / height;
+                self.camera_proj.z_axis.x += jitter_x;
+                self.camera_proj.z_axis.y += jitter_y;
+                local_vp = self.camera_proj * self.camera_view;
+            }
+        }
+
+        local_vp
+    }
+
+    /// Imagen donde acaba el color de la escena: la offscreen de post-proceso
+    /// (siempre con HDR, que necesita un tonemap) o el swapchain.
+    pub(crate) fn geometry_target(&self, image_index: u32, use_post_process: bool) -> (vk::ImageView, vk::Image) {
+        let offscreen = use_post_process || self.hdr_enabled();
+        let target_view = if offscreen {
+            self.post_process.offscreen_images[image_index as usize].view
+        } else {
+            self.swapchain.image_views[image_index as usize]
+        };
+        let target_image = if offscreen {
+            self.post_process.offscreen_images[image_index as usize].handle
+        } else {
+            self.swapchain.images[image_index as usize]
+        };
+        (target_view, target_image)
+    }
+
+    /// Dibuja los objetos visibles con sus propios materiales dentro del
+    /// rendering ya empezado, los transparentes al final (`Scene::draw_order`). Con `skip_gbuffer_objects` omite los que el
+    /// path deferred ya escribió en el G-Buffer.
+    pub(super) fn draw_forward_objects(&mut self, scene: &Scene, command_buffer: vk::CommandBuffer, local_vp: &glam::Mat4, skip_gbuffer_objects: bool) {
+        let visible_objects = scene.objects.iter().filter(|object| object.visible && !object.culled).count();
         self.apply_pixel_intelligent_vrs(command_buffer, visible_objects);
 
-        let frustum = crate::systems::frustum::Frustum::from_view_projection(local_vp);
+        let first_instances = match self.upload_instances(scene) {
+            Ok(offsets) => offsets,
+            Err(e) => {
+                log::warn!("Instance upload failed, instanced objects skipped: {}", e);
+                self.instance_data.clear();
+                vec![0; scene.objects.len()]
+            }
+        };
+        let instance_buffer = self.current_instance_buffer();
+        let first_joints = match self.upload_joint_matrices(scene) {
+            Ok(offsets) => offsets,
+            Err(e) => {
+                log::warn!("Joint upload failed, skinned objects skipped: {}", e);
+                self.joint_data.clear();
+                vec![0; scene.objects.len()]
+            }
+        };
+        let skin_descriptor_set = self.current_skin_descriptor_set();
+
         let mut active_pipeline = vk::Pipeline::null();
         let mut active_descriptor_set = vk::DescriptorSet::null();
+        let mut skybox_pending = scene.skybox().is_some();
 
         unsafe {
-            for object in &scene.objects {
-                if !object.visible { continue; }
-
-                let center = glam::Vec3::new(object.transform.w_axis.x, object.transform.w_axis.y, object.transform.w_axis.z);
-                let name = object.name.as_deref().unwrap_or("");
-
-                let radius = if name.contains("Floor") || name.contains("Wall") || name.contains("Techo") { 12.0 }
-                    else if name.contains("Pillar") { 4.0 }
-                    else if name.contains("zombie") || name.contains("Zombie") { 2.2 }
-                    else if name.contains("Shadow") || name.contains("shadow") { 1.8 }
-                    else if name.contains("Crosshair") || name.contains("GoScreen") || name.contains("VicScreen") { 100.0 }
-                    else { 1.5 };
+            // Opacos en orden de inserción y luego transparentes de atrás hacia delante.
+            for index in scene.draw_order(local_vp) {
+                let object = &scene.objects[index];
+                // En deferred estos ya se escribieron en el G-Buffer.
+                if skip_gbuffer_objects && crate::reactor::deferred::renders_to_gbuffer(object) { continue; }
+
+                // El cielo entra entre el último opaco y el primer transparente.
+                if skybox_pending && object.material.is_transparent() {
+                    self.record_skybox(scene, command_buffer, local_vp);
+                    skybox_pending = false;
+                    active_pipeline = vk::Pipeline::null();
+                }
 
-                let sphere = crate::systems::physics::Sphere::new(center, radius);
-                if !frustum.intersects_sphere(&sphere) { continue; }
+                // Un pipeline instanciado necesita instancias y viceversa. Sin
+                // instancias no hay nada que dibujar; instancias con un material
+                // sin instancing son un error del llamador y se avisa una vez.
+                let instanced = object.material.pipeline.is_instanced();
+                if instanced == object.instances.is_empty() {
+                    if !instanced && self.instancing_mismatch_warned.insert(object.id) {
+                        log::warn!(
+                            "Object {} has {} instances but its material is not instanced; it is not drawn",
+                            object.name.clone().unwrap_or_else(|| format!("#{}", object.id)), object.instances.len()
+                        );
+                    }
+                    continue;
+                }
+                if instanced && instance_buffer.is_none() { continue; }
+                // Igual con los pipelines skinned y la paleta de joints.
+                let skinned = object.material.pipeline.is_skinned();
+                if skinned == object.joint_matrices.is_empty() { continue; }
+                if skinned && skin_descriptor_set.is_none() { continue; }
+
+                // Nombre del objeto (`Scene::add_named`) visible en RenderDoc.
+                if let Some(name) = &object.name {
+                    self.context.debug_namer().insert_label(command_buffer, name, [1.0; 4]);
+                }
 
                 let pipeline_handle = object.material.pipeline.pipeline;
                 let descriptor_set_handle = object.material.descriptor_set.unwrap_or(vk::DescriptorSet::null());
@@ -166,11 +258,20 @@ impl Reactor {
                     self.context.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline_handle);
                     active_pipeline = pipeline_handle;
                     active_descriptor_set = vk::DescriptorSet::null();
+                    self.render_stats.pipeline_binds += 1;
 
                     if object.material.uses_ibl {
-                        self.bind_reactor_system_descriptors(command_buffer, object.material.pipeline.layout, true, object.material.has_shadow_set);
+                        self.render_stats.descriptor_binds += self.bind_reactor_system_descriptors(command_buffer, object.material.pipeline.layout, true, object.material.has_shadow_set);
                     } else if object.material.has_shadow_set {
-                        self.bind_reactor_system_descriptors(command_buffer, object.material.pipeline.layout, false, object.material.has_shadow_set);
+                        self.render_stats.descriptor_binds += self.bind_reactor_system_descriptors(command_buffer, object.material.pipeline.layout, false, object.material.has_shadow_set);
+                    }
+                    if object.material.has_light_set {
+                        self.render_stats.descriptor_binds += self.bind_light_descriptors(command_buffer, object.material.pipeline.layout);
+                    }
+                    if let Some(set) = skin_descriptor_set.filter(|_| skinned) {
+                        self.context.device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS,
+                            object.material.pipeline.layout, 4, &[set], &[]);
+                        self.render_stats.descriptor_binds += 1;
                     }
                 }
 
@@ -178,49 +279,38 @@ impl Reactor {
                     self.context.device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS,
                         object.material.pipeline.layout, 0, &[descriptor_set_handle], &[]);
                     active_descriptor_set = descriptor_set_handle;
+                    self.render_stats.descriptor_binds += 1;
                 }
 
-                let mvp = local_vp * object.transform;
-                let prev_mvp = self.prev_view_projection * object.transform;
-
-                #[repr(C)]
-                struct PushConstants {
-                    mvp: glam::Mat4,
-                    model: glam::Mat4,
-                    prev_mvp: glam::Mat4,
-                    camera_pos: glam::Vec4,
-                    light_pos: glam::Vec4,
-                    color: glam::Vec4,
-                    emission: glam::Vec4,
-                }
-                let push = PushConstants {
-                    mvp,
-                    model: object.transform,
-                    prev_mvp,
-                    camera_pos: glam::Vec4::new(self.camera_pos.x, self.camera_pos.y, self.camera_pos.z, object.metallic),
-                    light_pos: glam::Vec4::new(self.light_pos.x, self.light_pos.y, self.light_pos.z, object.roughness),
-                    color: glam::Vec4::new(object.color.x, object.color.y, object.color.z, object.anisotropy),
-                    emission: object.emission,
-                };
-                let constants_array = std::slice::from_raw_parts(&push as *const PushConstants as *const u8, std::mem::size_of::<PushConstants>());
+                let mut push = crate::graphics::push_constants::ObjectPushConstants::new(*local_vp * object.transform, object.transform);
+                push.camera_pos = [self.camera_pos.x, self.camera_pos.y, self.camera_pos.z, object.metallic];
+                push.light_pos = [self.light_pos.x, self.light_pos.y, self.light_pos.z, object.roughness];
+                let tint = object.material.tint(object.color);
+                push.color = [tint.x, tint.y, tint.z, object.anisotropy];
+                push.emission = object.emission.to_array();
+                push.extra[0] = first_joints[index] as f32;
+                push.extra[1] = if object.receive_shadows { 0.0 } else { 1.0 };
                 self.context.device.cmd_push_constants(command_buffer, object.material.pipeline.layout,
-                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, constants_array);
+                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, push.as_bytes());
 
                 let vertex_buffers = [object.mesh.vertex_buffer.handle];
                 self.context.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &[0]);
                 self.context.device.cmd_bind_index_buffer(command_buffer, object.mesh.index_buffer.handle, 0, vk::IndexType::UINT32);
-                self.context.device.cmd_draw_indexed(command_buffer, object.mesh.index_count, 1, 0, 0, 0);
+                let instance_count = if instanced { object.instances.len() as u32 } else { 1 };
+                if let Some(buffer) = instance_buffer.filter(|_| instanced) {
+                    self.context.device.cmd_bind_vertex_buffers(command_buffer, 1, &[buffer], &[0]);
+                    self.context.device.cmd_draw_indexed(command_buffer, object.mesh.index_count,
+                        instance_count, 0, 0, first_instances[index]);
+                } else {
+                    self.context.device.cmd_draw_indexed(command_buffer, object.mesh.index_count, 1, 0, 0, 0);
+                }
+                self.render_stats.objects_drawn += 1;
+                self.render_stats.count_draw(object.mesh.index_count, instance_count);
             }
-
-            self.context.device.cmd_end_rendering(command_buffer);
         }
 
-        self.prev_view_projection = self.camera_proj * self.camera_view;
-
-        if use_post_process && !self.decals.is_empty() {
-            let _ = self.draw_screen_space_decals(command_buffer, image_index as usize, &local_vp);
+        if skybox_pending {
+            self.record_skybox(scene, command_buffer, local_vp);
         }
-
-        local_vp
     }
 }
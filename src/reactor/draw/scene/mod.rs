@@ -3,57 +3,37 @@ use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::systems::scene::Scene;
 use ash::vk;
 
+mod deferred;
 mod geometry;
 mod postprocess;
+mod render_scale;
 mod shadow;
 mod sync;
+mod target;
+mod window;
+
+// Colores de las regiones de RenderDoc / Nsight.
+const LABEL_SHADOW: [f32; 4] = [0.35, 0.35, 0.8, 1.0];
+const LABEL_OPAQUE: [f32; 4] = [0.3, 0.8, 0.3, 1.0];
+const LABEL_POST: [f32; 4] = [0.9, 0.6, 0.2, 1.0];
 
 impl Reactor {
+    /// `draw_scene` subiendo antes el `LightingSystem` al UBO de luces.
+    pub fn draw_scene_lit(&mut self, scene: &Scene, view_projection: &glam::Mat4, lighting: &crate::systems::lighting::LightingSystem) -> ReactorResult<()> {
+        self.set_lights(lighting);
+        self.draw_scene(scene, view_projection)
+    }
+
     pub fn draw_scene(&mut self, scene: &Scene, view_projection: &glam::Mat4) -> ReactorResult<()> {
-        if self.device_lost {
+        crate::profile_scope!("draw_scene");
+        let Some((image_index, command_buffer)) = self.prepare_frame()? else {
             return Ok(());
-        }
-        if self.resized {
-            self.recreate_swapchain()?;
-            self.resized = false;
-        }
-
-        unsafe {
-            match self.context.device.wait_for_fences(
-                &[self.in_flight_fences[self.current_frame]], true, u64::MAX,
-            ) {
-                Ok(_) => {}
-                Err(vk::Result::ERROR_DEVICE_LOST) => {
-                    eprintln!("REACTOR FATAL: Dispositivo Vulkan perdido (wait_for_fences).");
-                    self.device_lost = true;
-                    return Err(ReactorError::new(ErrorCode::VulkanSynchronization, "Device lost"));
-                }
-                Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSynchronization, "wait_for_fences failed", e)),
-            }
-        }
-
-        let (image_index, _) = unsafe {
-            match self.swapchain.loader.acquire_next_image(
-                self.swapchain.handle, u64::MAX, self.image_available_semaphores[self.current_frame], vk::Fence::null(),
-            ) {
-                Ok(result) => {
-                    self.context.device.reset_fences(&[self.in_flight_fences[self.current_frame]])
-                        .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "reset_fences failed", e))?;
-                    result
-                }
-                Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
-                    self.recreate_swapchain()?;
-                    return Ok(());
-                }
-                Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSwapchain, "acquire_next_image failed", e)),
-            }
         };
+        self.retain_frame_resources(scene.objects.iter().map(|object| (object.mesh.clone(), object.material.clone())));
 
-        let command_buffer = self.command_buffers[self.current_frame];
-        unsafe {
-            self.context.device.reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())
-                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "reset_command_buffer failed", e))?;
-        }
+        self.upload_light_uniforms()?;
+        let deferred = self.deferred_active();
+        self.begin_render_stats(deferred);
 
         let begin_info = vk::CommandBufferBeginInfo::default();
 
@@ -63,9 +43,23 @@ impl Reactor {
             self.context.device.begin_command_buffer(command_buffer, &begin_info)
                 .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "begin_command_buffer failed", e))?;
 
+            let namer = self.context.debug_namer().clone();
+            namer.begin_label(command_buffer, "shadow", LABEL_SHADOW);
             self.render_shadow_cascades(scene, command_buffer);
+            namer.end_label(command_buffer);
+
+            namer.begin_label(command_buffer, "opaque", LABEL_OPAQUE);
+
+            let local_vp = if deferred {
+                // El path deferred no escala: ver `scaled_render_extent`.
+                self.render_scale.applied = false;
+                self.render_geometry_deferred(scene, command_buffer, image_index, view_projection, use_post_process, taa_enabled)
+            } else {
+                self.render_geometry(scene, command_buffer, image_index, view_projection, use_post_process, taa_enabled)
+            };
+            namer.end_label(command_buffer);
 
-            let local_vp = self.render_geometry(scene, command_buffer, image_index, view_projection, use_post_process, taa_enabled);
+            namer.begin_label(command_buffer, "post", LABEL_POST);
 
             if use_post_process && !self.decals.is_empty() {
                 self.draw_screen_space_decals(command_buffer, image_index as usize, &local_vp)?;
@@ -75,6 +69,7 @@ impl Reactor {
             let swapchain_image = self.swapchain.images[image_index as usize];
 
             self.render_post_process(scene, command_buffer, image_index, use_post_process, taa_enabled, &local_vp, swapchain_view, swapchain_image);
+            namer.end_label(command_buffer);
         }
 
         self.end_and_present(command_buffer, image_index)
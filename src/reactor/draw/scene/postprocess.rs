@@ -5,7 +5,10 @@ use ash::vk;
 
 impl Reactor {
     pub(super) fn update_post_descriptors(&mut self, image_index: u32) -> (bool, bool) {
-        let use_post_process = self.post_process.enabled && !self.post_process.offscreen_images.is_empty();
+        // Sin efectos activos no hay nada que componer: la geometría va directa al swapchain.
+        let use_post_process = self.post_process.enabled
+            && self.post_process.settings.any_effect_enabled()
+            && !self.post_process.offscreen_images.is_empty();
         let taa_enabled = self.post_process.enabled
             && self.post_process.settings.is_effect_enabled(PostProcessEffect::TAA)
             && self.temporal_history.is_some()
@@ -50,7 +53,12 @@ impl Reactor {
         swapchain_view: vk::ImageView,
         swapchain_image: vk::Image,
     ) {
-        if !use_post_process { return; }
+        if !use_post_process {
+            if self.hdr_enabled() {
+                self.record_tonemap(command_buffer, image_index);
+            }
+            return;
+        }
 
         let offscreen_barrier = vk::ImageMemoryBarrier::default()
             .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -120,7 +128,7 @@ impl Reactor {
                 self.camera_proj.inverse(), gpu_lights.len() as u32, depth_view);
         }
 
-        if self.post_process.bloom_downsample_pipeline.is_some() {
+        if self.post_process.bloom_downsample_pipeline.is_some() && self.post_process.settings.is_effect_enabled(PostProcessEffect::Bloom) {
             self.post_process.dispatch_bloom(self.context.ash_device(), command_buffer, image_index as usize,
                 self.swapchain.extent.width, self.swapchain.extent.height);
         }
@@ -152,12 +160,19 @@ impl Reactor {
             clouds.advance_time(self.post_process.delta_time);
         }
 
-        if self.post_process.lens_flare_pipeline.is_some() && self.post_process.settings.is_effect_enabled(PostProcessEffect::AnamorphicFlares) {
+        // El flare lee los mips del bloom: sin bloom estarían obsoletos.
+        if self.post_process.lens_flare_pipeline.is_some()
+            && self.post_process.settings.is_effect_enabled(PostProcessEffect::AnamorphicFlares)
+            && self.post_process.settings.is_effect_enabled(PostProcessEffect::Bloom) {
             self.post_process.dispatch_lens_flare(self.context.ash_device(), command_buffer, image_index as usize,
                 self.swapchain.extent.width, self.swapchain.extent.height,
                 self.post_process.last_time + self.post_process.delta_time);
         }
 
+        let pixels = self.swapchain.extent.width * self.swapchain.extent.height;
+        let aa_flags = self.post_process.begin_aa_stats(self.context.ash_device(), command_buffer,
+            self.current_frame, image_index as usize, pixels);
+
         let swapchain_barrier = vk::ImageMemoryBarrier::default()
             .old_layout(vk::ImageLayout::UNDEFINED).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
             .src_access_mask(vk::AccessFlags::empty()).dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
@@ -187,6 +202,7 @@ impl Reactor {
             post_settings.depth_far = self.camera_far.max(post_settings.depth_near + 0.001);
             post_settings.camera_proj_x = self.camera_proj.x_axis.x;
             post_settings.camera_proj_y = self.camera_proj.y_axis.y;
+            post_settings.aa_flags = aa_flags;
 
             let sun_dir_world = -scene.sun_direction;
             let sun_dir_view = self.camera_view.transform_vector3(sun_dir_world).normalize();
@@ -204,6 +220,9 @@ impl Reactor {
             self.context.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
             self.context.device.cmd_draw(command_buffer, 3, 1, 0, 0);
             self.context.device.cmd_end_rendering(command_buffer);
+            if aa_flags != 0 {
+                self.post_process.end_aa_stats(self.context.ash_device(), command_buffer, image_index as usize);
+            }
 
             if let Some(ref mut history) = self.temporal_history {
                 history.advance();
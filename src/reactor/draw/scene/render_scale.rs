@@ -0,0 +1,123 @@
+//! Escala de render dinámica del path forward.
+//!
+//! La escena se dibuja en la esquina superior izquierda de unos targets a
+//! tamaño completo (`render_scale_targets`) y se escala con un blit a la
+//! offscreen de post-proceso y al depth antes de la cadena de post. Como los
+//! targets no dependen del preset, cambiarlo solo cambia el viewport.
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::image::Image;
+use crate::reactor::Reactor;
+use ash::vk;
+
+impl Reactor {
+    /// Extent al que dibujar la escena este frame, o `None` a resolución
+    /// completa. Actualiza `render_scale.applied`.
+    pub(super) fn scaled_render_extent(&mut self, use_post_process: bool) -> Option<vk::Extent2D> {
+        self.render_scale.applied = false;
+        if !self.render_scale.enabled || !use_post_process || self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
+            return None;
+        }
+        let extent = self.render_scale.render_extent(self.swapchain.extent);
+        if extent == self.swapchain.extent {
+            return None;
+        }
+        if let Err(e) = self.ensure_render_scale_targets() {
+            log::warn!("Render scale disabled: {}", e);
+            self.render_scale.enabled = false;
+            return None;
+        }
+        self.render_scale.applied = true;
+        Some(extent)
+    }
+
+    fn ensure_render_scale_targets(&mut self) -> ReactorResult<()> {
+        let extent = self.swapchain.extent;
+        if let Some((color, _)) = &self.render_scale_targets {
+            if color.extent.width == extent.width && color.extent.height == extent.height {
+                return Ok(());
+            }
+        }
+        for format in [self.scene_color_format, self.depth_format] {
+            let props = unsafe {
+                self.context.instance.get_physical_device_format_properties(self.context.physical_device, format)
+            };
+            let blit = vk::FormatFeatureFlags::BLIT_SRC | vk::FormatFeatureFlags::BLIT_DST;
+            if !props.optimal_tiling_features.contains(blit) {
+                return Err(ReactorError::new(ErrorCode::NotSupported, format!("{:?} cannot be blitted", format)));
+            }
+        }
+
+        let color = Image::new(&self.context, self.allocator.clone(), extent.width, extent.height, self.scene_color_format,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC, vk::ImageAspectFlags::COLOR, 1)?;
+        let depth = Image::new(&self.context, self.allocator.clone(), extent.width, extent.height, self.depth_format,
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC, vk::ImageAspectFlags::DEPTH, 1)?;
+        let namer = self.context.debug_namer();
+        color.set_debug_name(namer, "Image: Render Scale Color");
+        depth.set_debug_name(namer, "Image: Render Scale Depth");
+        self.render_scale_targets = Some((color, depth));
+        Ok(())
+    }
+
+    /// Escala `extent` de los targets a la offscreen `image_index` y al depth,
+    /// dejándolos en los layouts de attachment que espera el resto del frame.
+    pub(super) fn upscale_render_scale_targets(&self, command_buffer: vk::CommandBuffer, image_index: u32, extent: vk::Extent2D) {
+        let Some((color, depth)) = &self.render_scale_targets else { return };
+        let offscreen = self.post_process.offscreen_images[image_index as usize].handle;
+        let depth_image = self.depth_image.unwrap();
+        let full = self.swapchain.extent;
+
+        let color_range = vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 };
+        let depth_range = vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::DEPTH, ..color_range };
+        let barrier = |image, range, old_layout, new_layout, src_access, dst_access| {
+            vk::ImageMemoryBarrier::default()
+                .old_layout(old_layout).new_layout(new_layout)
+                .src_access_mask(src_access).dst_access_mask(dst_access)
+                .image(image).subresource_range(range)
+        };
+
+        let to_transfer = [
+            barrier(color.handle, color_range, vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL, vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE, vk::AccessFlags::TRANSFER_READ),
+            barrier(depth.handle, depth_range, vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL, vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE, vk::AccessFlags::TRANSFER_READ),
+            barrier(offscreen, color_range, vk::ImageLayout::UNDEFINED, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::empty(), vk::AccessFlags::TRANSFER_WRITE),
+            barrier(depth_image, depth_range, vk::ImageLayout::UNDEFINED, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::empty(), vk::AccessFlags::TRANSFER_WRITE),
+        ];
+
+        let corner = |e: vk::Extent2D| vk::Offset3D { x: e.width as i32, y: e.height as i32, z: 1 };
+        let layers = |aspect_mask| vk::ImageSubresourceLayers { aspect_mask, mip_level: 0, base_array_layer: 0, layer_count: 1 };
+        let region = |aspect_mask| vk::ImageBlit::default()
+            .src_subresource(layers(aspect_mask)).src_offsets([vk::Offset3D::default(), corner(extent)])
+            .dst_subresource(layers(aspect_mask)).dst_offsets([vk::Offset3D::default(), corner(full)]);
+
+        let to_attachment = [
+            barrier(offscreen, color_range, vk::ImageLayout::TRANSFER_DST_OPTIMAL, vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE, vk::AccessFlags::COLOR_ATTACHMENT_WRITE | vk::AccessFlags::SHADER_READ),
+            barrier(depth_image, depth_range, vk::ImageLayout::TRANSFER_DST_OPTIMAL, vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE, vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | vk::AccessFlags::SHADER_READ),
+        ];
+
+        let device = &self.context.device;
+        unsafe {
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(), &[], &[], &to_transfer);
+            device.cmd_blit_image(command_buffer,
+                color.handle, vk::ImageLayout::TRANSFER_SRC_OPTIMAL, offscreen, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[region(vk::ImageAspectFlags::COLOR)], vk::Filter::LINEAR);
+            // Depth solo admite NEAREST.
+            device.cmd_blit_image(command_buffer,
+                depth.handle, vk::ImageLayout::TRANSFER_SRC_OPTIMAL, depth_image, vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[region(vk::ImageAspectFlags::DEPTH)], vk::Filter::NEAREST);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS
+                    | vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::DependencyFlags::empty(), &[], &[], &to_attachment);
+        }
+    }
+}
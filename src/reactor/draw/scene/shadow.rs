@@ -1,7 +1,21 @@
+use crate::graphics::shadows::ShadowCacheKey;
 use crate::reactor::Reactor;
-use crate::systems::scene::Scene;
+use crate::systems::scene::{Scene, SceneObject};
 use ash::vk;
 
+/// Objetos que se dibujan en el shadow map.
+///
+/// Los skinned quedan fuera: el pipeline de sombras lee el layout `Vertex`
+/// sin paleta de joints, así que un personaje animado no proyecta sombra
+/// hasta que haya una variante skinned del pase (se avisa una vez).
+fn casts_shadow(object: &SceneObject) -> bool {
+    if !object.visible || !object.cast_shadows || !object.joint_matrices.is_empty() {
+        return false;
+    }
+    let name = object.name.as_deref().unwrap_or("");
+    !(name.contains("Crosshair") || name.contains("GoScreen") || name.contains("VicScreen"))
+}
+
 impl Reactor {
     pub(super) fn render_shadow_cascades(&mut self, scene: &Scene, command_buffer: vk::CommandBuffer) {
         if self.shadow_map.is_none() || self.shadow_pipeline.is_none() {
@@ -26,6 +40,21 @@ impl Reactor {
             self.shadow_uniform_buffers[self.current_frame].write(&[shadow_uniform]);
         }
 
+        // Si nada de lo que ve la luz cambió, el shadow map anterior sigue valiendo.
+        // Desactivadas, basta con que la imagen se haya inicializado una vez.
+        let enabled = self.shadow_map.as_ref().is_some_and(|m| m.enabled);
+        if enabled && !self.skinned_shadow_warned
+            && scene.objects.iter().any(|o| o.visible && o.cast_shadows && !o.joint_matrices.is_empty())
+        {
+            log::warn!("Skinned objects cast no shadows: the shadow pass only supports static meshes");
+            self.skinned_shadow_warned = true;
+        }
+        let key = ShadowCacheKey::new(sun_dir, self.camera_view, self.camera_proj,
+            scene.objects.iter().filter(|o| enabled && casts_shadow(o)).map(|o| (o.transform, std::sync::Arc::as_ptr(&o.mesh) as usize)));
+        if self.shadow_cache_key.is_some_and(|last| !enabled || last == key) {
+            return;
+        }
+
         shadow_img = self.shadow_image.unwrap();
         shadow_pipe = self.shadow_pipeline.as_ref().unwrap();
 
@@ -69,10 +98,7 @@ impl Reactor {
             }
 
             let cascade = &self.shadow_map.as_ref().unwrap().cascades[layer as usize];
-            for object in &scene.objects {
-                if !object.visible { continue; }
-                let name = object.name.as_deref().unwrap_or("");
-                if name.contains("Crosshair") || name.contains("GoScreen") || name.contains("VicScreen") { continue; }
+            for object in scene.objects.iter().filter(|o| enabled && casts_shadow(o)) {
 
                 let light_mvp = cascade.view_proj * object.transform;
                 let push_bytes = unsafe {
@@ -87,6 +113,7 @@ impl Reactor {
                     self.context.device.cmd_bind_index_buffer(command_buffer, object.mesh.index_buffer.handle, 0, vk::IndexType::UINT32);
                     self.context.device.cmd_draw_indexed(command_buffer, object.mesh.index_count, 1, 0, 0, 0);
                 }
+                self.render_stats.count_shadow_draw(object.mesh.index_count, 1);
             }
 
             unsafe { self.context.device.cmd_end_rendering(command_buffer); }
@@ -107,5 +134,6 @@ impl Reactor {
                 vk::PipelineStageFlags::LATE_FRAGMENT_TESTS, vk::PipelineStageFlags::FRAGMENT_SHADER,
                 vk::DependencyFlags::empty(), &[], &[], &[shadow_end_barrier]);
         }
+        self.shadow_cache_key = Some(key);
     }
 }
@@ -1,35 +1,129 @@
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::reactor::Reactor;
+use crate::resources::material::Material;
+use crate::resources::mesh::Mesh;
 use ash::vk;
+use std::sync::Arc;
 
 impl Reactor {
-    pub(super) fn prepare_frame(&mut self) -> ReactorResult<(u32, vk::CommandBuffer)> {
-        if self.device_lost {
-            return Err(ReactorError::new(ErrorCode::VulkanSynchronization, "Device lost"));
+    /// `acquire_next_image`, o en modo headless la imagen offscreen del frame
+    /// en curso (su fence ya se esperó, así que está libre).
+    pub(crate) fn acquire_image(&self) -> Result<(u32, bool), vk::Result> {
+        if self.swapchain.is_offscreen() {
+            return Ok((self.current_frame as u32 % self.swapchain.image_count, false));
         }
-        if self.resized {
-            self.recreate_swapchain()?;
-            self.resized = false;
+        unsafe {
+            self.swapchain.loader.acquire_next_image(
+                self.swapchain.handle, u64::MAX, self.image_available_semaphores[self.current_frame], vk::Fence::null(),
+            )
+        }
+    }
+
+    /// Headless: envía el frame sin semáforos ni present y recuerda su imagen.
+    pub(crate) fn submit_offscreen(&mut self, command_buffer: vk::CommandBuffer, image_index: u32) -> ReactorResult<()> {
+        self.submit_frame(command_buffer, &[], &[])?;
+        self.last_rendered_image = Some(image_index);
+        Ok(())
+    }
+
+    /// Envía el command buffer del slot `current_frame` con su fence y
+    /// señaliza el siguiente valor del `FrameTimeline`. `wait` se espera en
+    /// la salida de color (imagen adquirida).
+    pub(crate) fn submit_frame(
+        &mut self,
+        command_buffer: vk::CommandBuffer,
+        wait: &[vk::Semaphore],
+        signal: &[vk::Semaphore],
+    ) -> ReactorResult<()> {
+        let wait_stages = vec![vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT; wait.len()];
+        let mut signal_semaphores = signal.to_vec();
+        // Los semáforos binarios ignoran su valor.
+        let mut signal_values = vec![0; signal.len()];
+        if let Some(timeline) = self.timeline.semaphore() {
+            signal_semaphores.push(timeline);
+            signal_values.push(self.timeline.next_value());
+        }
+        let mut timeline_info = vk::TimelineSemaphoreSubmitInfo::default().signal_semaphore_values(&signal_values);
+        let command_buffers = [command_buffer];
+        let mut submit_info = vk::SubmitInfo::default()
+            .wait_semaphores(wait)
+            .wait_dst_stage_mask(&wait_stages)
+            .command_buffers(&command_buffers)
+            .signal_semaphores(&signal_semaphores);
+        if self.timeline.semaphore().is_some() {
+            submit_info = submit_info.push_next(&mut timeline_info);
         }
+        unsafe {
+            self.context.device.queue_submit(self.context.graphics_queue, &[submit_info], self.in_flight_fences[self.current_frame])
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "queue_submit failed", e))?;
+        }
+        self.timeline.frame_submitted(self.current_frame);
+        Ok(())
+    }
 
+    /// Retiene meshes y materiales hasta que el slot `current_frame` termine
+    /// y, con el rastreo en vuelo activo, anota sus buffers y pipelines.
+    pub(crate) fn retain_frame_resources(&mut self, objects: impl IntoIterator<Item = (Arc<Mesh>, Arc<Material>)>) {
+        let slot = &mut self.frame_resources[self.current_frame];
+        let start = slot.len();
+        slot.extend(objects);
+        if !self.timeline.in_flight_tracking() {
+            return;
+        }
+        for (mesh, material) in &slot[start..] {
+            self.timeline.mark_in_flight(mesh.vertex_buffer.handle);
+            self.timeline.mark_in_flight(mesh.index_buffer.handle);
+            self.timeline.mark_in_flight(material.pipeline.pipeline);
+        }
+    }
+
+    /// Espera el fence del slot `current_frame` y libera lo que ese slot
+    /// retenía (sets transitorios, meshes y materiales del frame anterior).
+    pub(crate) fn wait_frame_slot(&mut self) -> ReactorResult<()> {
         unsafe {
             match self.context.device.wait_for_fences(
                 &[self.in_flight_fences[self.current_frame]], true, u64::MAX,
             ) {
                 Ok(_) => {}
                 Err(vk::Result::ERROR_DEVICE_LOST) => {
-                    eprintln!("REACTOR FATAL: Dispositivo Vulkan perdido (wait_for_fences).");
+                    log::error!("REACTOR FATAL: Dispositivo Vulkan perdido (wait_for_fences).");
                     self.device_lost = true;
                     return Err(ReactorError::new(ErrorCode::VulkanSynchronization, "Device lost"));
                 }
                 Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSynchronization, "wait_for_fences failed", e)),
             }
         }
+        self.resolve_capture();
+        // El fence del slot ya señalizó: sus sets transitorios están libres y
+        // lo diferido hasta su frame se puede destruir.
+        self.timeline.slot_finished(self.current_frame);
+        self.descriptor_allocator.lock().unwrap().reset_transient(self.current_frame);
+        self.mesh_uploader.pump();
+        self.frame_resources[self.current_frame].clear();
+        self.frame_skyboxes[self.current_frame] = None;
+        self.frame_sprite_textures[self.current_frame].clear();
+        self.poll_environment();
+        Ok(())
+    }
 
-        let (image_index, _) = unsafe {
-            match self.swapchain.loader.acquire_next_image(
-                self.swapchain.handle, u64::MAX, self.image_available_semaphores[self.current_frame], vk::Fence::null(),
-            ) {
+    /// Espera el fence del slot `current_frame`, adquiere imagen y resetea su
+    /// command buffer. `None` = este frame no se dibuja (minimizado, device
+    /// perdido o swapchain recreado).
+    pub(super) fn prepare_frame(&mut self) -> ReactorResult<Option<(u32, vk::CommandBuffer)>> {
+        if self.device_lost {
+            return Ok(None);
+        }
+        if self.resized {
+            self.recreate_swapchain()?;
+        }
+        if self.minimized {
+            return Ok(None);
+        }
+        crate::core::validation::begin_validation_frame();
+        self.wait_frame_slot()?;
+
+        let (image_index, suboptimal) = unsafe {
+            match self.acquire_image() {
                 Ok(result) => {
                     self.context.device.reset_fences(&[self.in_flight_fences[self.current_frame]])
                         .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "reset_fences failed", e))?;
@@ -37,21 +131,34 @@ impl Reactor {
                 }
                 Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
                     self.recreate_swapchain()?;
-                    return Err(ReactorError::new(ErrorCode::VulkanSwapchain, "Out of date"));
+                    return Ok(None);
                 }
                 Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSwapchain, "acquire_next_image failed", e)),
             }
         };
+        // SUBOPTIMAL: la imagen sigue siendo presentable; recreamos en el próximo frame.
+        if suboptimal {
+            self.resized = true;
+        }
 
         let command_buffer = self.command_buffers[self.current_frame];
         unsafe {
             self.context.device.reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())
                 .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "reset_command_buffer failed", e))?;
         }
-        Ok((image_index, command_buffer))
+        Ok(Some((image_index, command_buffer)))
+    }
+
+    /// Pasa al siguiente slot de frame en vuelo. Se llama tras el submit: la
+    /// CPU graba el frame N+1 mientras la GPU todavía ejecuta el N.
+    pub(crate) fn advance_frame(&mut self) {
+        self.current_frame = (self.current_frame + 1) % self.frames_in_flight;
     }
 
     pub(super) fn end_and_present(&mut self, command_buffer: vk::CommandBuffer, image_index: u32) -> ReactorResult<()> {
+        if let Err(e) = self.record_overlay(command_buffer, image_index) {
+            log::warn!("Overlay skipped: {}", e);
+        }
         let swapchain_image = self.swapchain.images[image_index as usize];
 
         let image_barrier = vk::ImageMemoryBarrier::default()
@@ -68,27 +175,24 @@ impl Reactor {
             self.context.device.cmd_pipeline_barrier(command_buffer,
                 vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, vk::PipelineStageFlags::BOTTOM_OF_PIPE,
                 vk::DependencyFlags::empty(), &[], &[], &[image_barrier]);
+        }
+        self.record_pending_capture(command_buffer, swapchain_image);
 
+        unsafe {
             self.context.device.end_command_buffer(command_buffer).map_err(|e| {
                 ReactorError::with_source(ErrorCode::VulkanCommandPool, "end_command_buffer failed", e)
             })?;
         }
 
+        if self.swapchain.is_offscreen() {
+            self.submit_offscreen(command_buffer, image_index)?;
+            self.advance_frame();
+            return Ok(());
+        }
+
         let wait_semaphores = [self.image_available_semaphores[self.current_frame]];
         let signal_semaphores = [self.render_finished_semaphores[image_index as usize]];
-        let command_buffers_submit = [command_buffer];
-        let wait_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
-
-        let submit_info = vk::SubmitInfo::default()
-            .wait_semaphores(&wait_semaphores)
-            .wait_dst_stage_mask(&wait_stages)
-            .command_buffers(&command_buffers_submit)
-            .signal_semaphores(&signal_semaphores);
-
-        unsafe {
-            self.context.device.queue_submit(self.context.graphics_queue, &[submit_info], self.in_flight_fences[self.current_frame])
-                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "queue_submit failed", e))?;
-        }
+        self.submit_frame(command_buffer, &wait_semaphores, &signal_semaphores)?;
 
         let swapchains = [self.swapchain.handle];
         let image_indices = [image_index];
@@ -98,8 +202,9 @@ impl Reactor {
             .image_indices(&image_indices);
 
         let result = unsafe { self.swapchain.loader.queue_present(self.context.graphics_queue, &present_info) };
+        self.advance_frame();
         match result {
-            Ok(_) => {}
+            Ok(suboptimal) => { self.resized |= suboptimal; }
             Err(vk::Result::ERROR_OUT_OF_DATE_KHR) | Err(vk::Result::SUBOPTIMAL_KHR) => { self.resized = true; }
             Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSwapchain, "queue_present failed", e)),
         }
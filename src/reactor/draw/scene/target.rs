@@ -0,0 +1,79 @@
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::reactor::render_target::RenderTarget;
+use crate::reactor::Reactor;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+use super::{LABEL_OPAQUE, LABEL_SHADOW};
+
+impl Reactor {
+    /// Dibuja `scene` vista con `view_projection` en `target` (ver
+    /// `create_render_target`), con el path forward y, con HDR, el tonemap.
+    /// Usa el siguiente slot de frame en vuelo, sin acquire ni present: se
+    /// llama antes del `draw_scene` del frame que sampléa la textura. La
+    /// imagen queda en `SHADER_READ_ONLY_OPTIMAL`.
+    pub fn draw_scene_to_target(&mut self, target: &mut RenderTarget, scene: &Scene, view_projection: &glam::Mat4) -> ReactorResult<()> {
+        if self.device_lost {
+            return Ok(());
+        }
+        self.wait_frame_slot()?;
+
+        let command_buffer = self.command_buffers[self.current_frame];
+        unsafe {
+            self.context.device.reset_fences(&[self.in_flight_fences[self.current_frame]])
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "reset_fences failed", e))?;
+            self.context.device.reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "reset_command_buffer failed", e))?;
+            self.context.device.begin_command_buffer(command_buffer, &vk::CommandBufferBeginInfo::default())
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "begin_command_buffer failed", e))?;
+        }
+        self.retain_frame_resources(scene.objects.iter().map(|object| (object.mesh.clone(), object.material.clone())));
+        self.upload_light_uniforms()?;
+
+        let namer = self.context.debug_namer().clone();
+        namer.begin_label(command_buffer, "shadow", LABEL_SHADOW);
+        self.render_shadow_cascades(scene, command_buffer);
+        namer.end_label(command_buffer);
+
+        // Como en `draw_scene_to`, pero el target ocupa entero: el
+        // rectángulo de viewport es el de la ventana principal.
+        let prev_view_projection = self.prev_view_projection;
+        let viewport_rect = std::mem::replace(&mut self.viewport_rect, [0.0, 0.0, 1.0, 1.0]);
+        self.swap_target_attachments(target);
+        namer.begin_label(command_buffer, "render target", LABEL_OPAQUE);
+        self.render_geometry(scene, command_buffer, 0, view_projection, false, false);
+        if self.hdr_enabled() {
+            self.record_tonemap(command_buffer, 0);
+        }
+        namer.end_label(command_buffer);
+        self.swap_target_attachments(target);
+        self.viewport_rect = viewport_rect;
+        self.prev_view_projection = prev_view_projection;
+
+        let read_barrier = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ)
+            .image(target.image())
+            .subresource_range(vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 });
+        unsafe {
+            self.context.device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, vk::PipelineStageFlags::FRAGMENT_SHADER,
+                vk::DependencyFlags::empty(), &[], &[], &[read_barrier]);
+            self.context.device.end_command_buffer(command_buffer)
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "end_command_buffer failed", e))?;
+        }
+
+        self.submit_frame(command_buffer, &[], &[])?;
+        self.advance_frame();
+        Ok(())
+    }
+
+    fn swap_target_attachments(&mut self, target: &mut RenderTarget) {
+        std::mem::swap(&mut self.swapchain, &mut target.swapchain);
+        std::mem::swap(&mut self.depth_image, &mut target.depth_image);
+        std::mem::swap(&mut self.depth_image_view, &mut target.depth_image_view);
+        std::mem::swap(&mut self.msaa_image, &mut target.msaa_image);
+        std::mem::swap(&mut self.msaa_image_view, &mut target.msaa_image_view);
+        std::mem::swap(&mut self.post_process.offscreen_images, &mut target.hdr_images);
+    }
+}
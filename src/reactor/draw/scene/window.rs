@@ -0,0 +1,113 @@
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::reactor::window_target::RenderTargetWindow;
+use crate::reactor::Reactor;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+use super::{LABEL_OPAQUE, LABEL_SHADOW};
+
+impl Reactor {
+    /// Dibuja `scene` en una ventana secundaria (ver `create_window_target`)
+    /// con el path forward, sin post-proceso (con HDR, sólo el tonemap). Usa el siguiente slot de frame
+    /// en vuelo, así que se puede intercalar con `draw_scene` en el mismo
+    /// frame de la app. Luces y sombras son las del `Reactor`.
+    pub fn draw_scene_to(&mut self, target: &mut RenderTargetWindow, scene: &Scene, view_projection: &glam::Mat4) -> ReactorResult<()> {
+        if self.device_lost {
+            return Ok(());
+        }
+        if target.resized {
+            target.recreate()?;
+        }
+        if target.minimized {
+            return Ok(());
+        }
+        self.wait_frame_slot()?;
+
+        let acquired = unsafe {
+            target.swapchain.loader.acquire_next_image(
+                target.swapchain.handle, u64::MAX, target.image_available_semaphores[self.current_frame], vk::Fence::null(),
+            )
+        };
+        let (image_index, suboptimal) = match acquired {
+            Ok(result) => result,
+            Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                target.resized = true;
+                return Ok(());
+            }
+            Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSwapchain, "acquire_next_image failed", e)),
+        };
+        target.resized |= suboptimal;
+
+        let command_buffer = self.command_buffers[self.current_frame];
+        unsafe {
+            self.context.device.reset_fences(&[self.in_flight_fences[self.current_frame]])
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "reset_fences failed", e))?;
+            self.context.device.reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "reset_command_buffer failed", e))?;
+            self.context.device.begin_command_buffer(command_buffer, &vk::CommandBufferBeginInfo::default())
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "begin_command_buffer failed", e))?;
+        }
+        self.retain_frame_resources(scene.objects.iter().map(|object| (object.mesh.clone(), object.material.clone())));
+        self.upload_light_uniforms()?;
+
+        let namer = self.context.debug_namer().clone();
+        namer.begin_label(command_buffer, "shadow", LABEL_SHADOW);
+        self.render_shadow_cascades(scene, command_buffer);
+        namer.end_label(command_buffer);
+
+        // La geometría forward lee swapchain, depth y MSAA de `self`: se
+        // prestan los de la ventana mientras se graba. El historial de TAA
+        // sigue siendo el de la ventana principal.
+        let prev_view_projection = self.prev_view_projection;
+        self.swap_window_attachments(target);
+        namer.begin_label(command_buffer, "opaque", LABEL_OPAQUE);
+        self.render_geometry(scene, command_buffer, image_index, view_projection, false, false);
+        if self.hdr_enabled() {
+            self.record_tonemap(command_buffer, image_index);
+        }
+        namer.end_label(command_buffer);
+        self.swap_window_attachments(target);
+        self.prev_view_projection = prev_view_projection;
+
+        let present_barrier = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::MEMORY_READ)
+            .image(target.swapchain.images[image_index as usize])
+            .subresource_range(vk::ImageSubresourceRange { aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1 });
+        unsafe {
+            self.context.device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                vk::DependencyFlags::empty(), &[], &[], &[present_barrier]);
+            self.context.device.end_command_buffer(command_buffer)
+                .map_err(|e| ReactorError::with_source(ErrorCode::VulkanCommandPool, "end_command_buffer failed", e))?;
+        }
+
+        let wait_semaphores = [target.image_available_semaphores[self.current_frame]];
+        let signal_semaphores = [target.render_finished_semaphores[image_index as usize]];
+        self.submit_frame(command_buffer, &wait_semaphores, &signal_semaphores)?;
+
+        let swapchains = [target.swapchain.handle];
+        let image_indices = [image_index];
+        let present_info = vk::PresentInfoKHR::default()
+            .wait_semaphores(&signal_semaphores)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        let result = unsafe { target.swapchain.loader.queue_present(self.context.graphics_queue, &present_info) };
+        self.advance_frame();
+        match result {
+            Ok(suboptimal) => target.resized |= suboptimal,
+            Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => target.resized = true,
+            Err(e) => return Err(ReactorError::with_source(ErrorCode::VulkanSwapchain, "queue_present failed", e)),
+        }
+        Ok(())
+    }
+
+    fn swap_window_attachments(&mut self, target: &mut RenderTargetWindow) {
+        std::mem::swap(&mut self.swapchain, &mut target.swapchain);
+        std::mem::swap(&mut self.depth_image, &mut target.depth_image);
+        std::mem::swap(&mut self.depth_image_view, &mut target.depth_image_view);
+        std::mem::swap(&mut self.msaa_image, &mut target.msaa_image);
+        std::mem::swap(&mut self.msaa_image_view, &mut target.msaa_image_view);
+        std::mem::swap(&mut self.post_process.offscreen_images, &mut target.hdr_images);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::reactor::Reactor;
+use crate::systems::sdf::{SdfScene, MAX_SDF_PRIMITIVES, SDF_HIT_EPSILON, SDF_MAX_STEPS};
+use ash::vk;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct SdfPushConstants {
+    view_proj: [f32; 16],
+    inv_view_proj: [f32; 16],
+    light_pos: [f32; 4],
+    params: [f32; 4],
+}
+
+impl Reactor {
+    /// Escena SDF que dibujan los `draw_scene` siguientes. A diferencia de
+    /// las líneas de depuración se mantiene hasta la próxima llamada; una
+    /// escena vacía deja de dibujarse.
+    pub fn set_sdf_scene(&mut self, scene: &SdfScene) {
+        if scene.len() > MAX_SDF_PRIMITIVES && !self.sdf_overflow_warned {
+            log::warn!(
+                "{} SDF primitives exceed MAX_SDF_PRIMITIVES ({}); extra primitives are ignored",
+                scene.len(),
+                MAX_SDF_PRIMITIVES
+            );
+            self.sdf_overflow_warned = true;
+        }
+        self.sdf_primitives = scene.to_gpu_data();
+    }
+
+    /// Pinta los pasos de raymarching por píxel (negro → rojo → blanco) en
+    /// vez del color de la superficie.
+    pub fn set_sdf_heatmap(&mut self, enabled: bool) {
+        self.sdf_heatmap = enabled;
+    }
+
+    /// Graba el raymarch a pantalla completa dentro del render pass de
+    /// geometría activo, después de los objetos opacos.
+    pub(super) fn record_sdf_scene(&mut self, command_buffer: vk::CommandBuffer, view_projection: &glam::Mat4) {
+        if self.sdf_primitives.is_empty() {
+            return;
+        }
+        let (Some(pipeline), Some(buffer), Some(&set)) = (
+            self.sdf_pipeline.as_ref(),
+            self.sdf_buffers.get(self.current_frame),
+            self.sdf_descriptor_sets.get(self.current_frame),
+        ) else {
+            return;
+        };
+
+        buffer.write(&self.sdf_primitives);
+        let heatmap = if self.sdf_heatmap { 1.0 } else { 0.0 };
+        let push = SdfPushConstants {
+            view_proj: view_projection.to_cols_array(),
+            inv_view_proj: view_projection.inverse().to_cols_array(),
+            light_pos: self.light_pos.to_array(),
+            params: [self.sdf_primitives.len() as f32, heatmap, SDF_MAX_STEPS as f32, SDF_HIT_EPSILON],
+        };
+
+        unsafe {
+            let device = &self.context.device;
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[set], &[]);
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::bytes_of(&push));
+            device.cmd_draw(command_buffer, 3, 1, 0, 0);
+        }
+        self.render_stats.pipeline_binds += 1;
+        self.render_stats.descriptor_binds += 1;
+        self.render_stats.count_draw(3, 1);
+    }
+}
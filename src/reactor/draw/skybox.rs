@@ -0,0 +1,51 @@
+use crate::reactor::Reactor;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+impl Reactor {
+    /// Graba el skybox de `scene` dentro del render pass de geometría activo.
+    /// Va después de los opacos y antes de los transparentes, que no escriben
+    /// depth y quedarían tapados.
+    pub(super) fn record_skybox(&mut self, scene: &Scene, command_buffer: vk::CommandBuffer, view_projection: &glam::Mat4) {
+        let (Some(cubemap), Some(pipeline), Some(&set)) = (
+            scene.skybox(),
+            self.skybox_pipeline.as_ref(),
+            self.skybox_descriptor_sets.get(self.current_frame),
+        ) else {
+            return;
+        };
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(cubemap.view());
+        let sampler_info = vk::DescriptorImageInfo::default().sampler(cubemap.sampler_handle());
+        let writes = [
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(0)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .image_info(std::slice::from_ref(&image_info)),
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .image_info(std::slice::from_ref(&sampler_info)),
+        ];
+
+        let inv_view_proj = view_projection.inverse().to_cols_array();
+        unsafe {
+            let device = &self.context.device;
+            device.update_descriptor_sets(&writes, &[]);
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[set], &[]);
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::bytes_of(&inv_view_proj));
+            device.cmd_draw(command_buffer, 3, 1, 0, 0);
+        }
+        // El cubemap vive hasta que el fence de este slot señalice.
+        self.frame_skyboxes[self.current_frame] = Some(cubemap.clone());
+        self.render_stats.pipeline_binds += 1;
+        self.render_stats.descriptor_binds += 1;
+        self.render_stats.count_draw(3, 1);
+    }
+}
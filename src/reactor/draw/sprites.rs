@@ -0,0 +1,124 @@
+use crate::core::error::ReactorResult;
+use crate::graphics::sprite_batch::Sprite;
+use crate::reactor::Reactor;
+use crate::resources::texture::Texture;
+use crate::scene::camera::Camera2D;
+use ash::vk;
+use std::sync::Arc;
+
+/// Sets por slot con los que nace su pool; crece al doble si no alcanzan.
+const INITIAL_SPRITE_SETS: u32 = 64;
+
+impl Reactor {
+    /// Empieza el lote de sprites del próximo frame y descarta el anterior.
+    pub fn sprite_begin(&mut self) {
+        self.sprite_batch.begin();
+    }
+
+    /// Añade un quad con `texture` al lote abierto.
+    pub fn sprite_draw(&mut self, texture: &Arc<Texture>, sprite: Sprite) {
+        self.sprite_batch.draw(texture, sprite);
+    }
+
+    /// Ordena el lote por capa y textura; se dibuja (y consume) en el
+    /// próximo frame, en el overlay junto al texto.
+    pub fn sprite_end(&mut self) {
+        self.sprite_batch.end();
+    }
+
+    /// Cámara de los sprites; `None` vuelve a coordenadas en píxeles.
+    pub fn set_sprite_camera(&mut self, camera: Option<Camera2D>) {
+        self.sprite_camera = camera;
+    }
+
+    /// Graba el lote dentro del render pass del overlay ya abierto. Un draw
+    /// por cada tramo de sprites que comparte textura.
+    pub(super) fn record_sprites(&mut self, command_buffer: vk::CommandBuffer, extent: vk::Extent2D) -> ReactorResult<()> {
+        if self.sprite_batch.is_empty() {
+            return Ok(());
+        }
+        let (Some(pipeline), Some(layout)) = (self.sprite_pipeline.as_ref(), self.sprite_descriptor_layout) else {
+            return Ok(());
+        };
+
+        let frame = self.current_frame;
+        let device = &self.context.device;
+        let vertices = self.sprite_batch.vertices();
+        let draws = self.sprite_batch.draws();
+
+        let needed = std::mem::size_of_val(vertices) as u64;
+        let buffer = self.sprite_buffers.ensure(&self.context, self.allocator.clone(), frame, needed)?;
+        buffer.write(vertices);
+
+        if self.sprite_descriptor_pools.len() <= frame {
+            self.sprite_descriptor_pools.resize(frame + 1, (vk::DescriptorPool::null(), 0));
+        }
+        let (pool, capacity) = &mut self.sprite_descriptor_pools[frame];
+        let set_count = draws.len() as u32;
+        unsafe {
+            if *capacity < set_count {
+                if *pool != vk::DescriptorPool::null() {
+                    device.destroy_descriptor_pool(*pool, None);
+                    *pool = vk::DescriptorPool::null();
+                    *capacity = 0;
+                }
+                let new_capacity = set_count.next_power_of_two().max(INITIAL_SPRITE_SETS);
+                let pool_sizes = [
+                    vk::DescriptorPoolSize::default().ty(vk::DescriptorType::SAMPLED_IMAGE).descriptor_count(new_capacity),
+                    vk::DescriptorPoolSize::default().ty(vk::DescriptorType::SAMPLER).descriptor_count(new_capacity),
+                ];
+                let pool_info = vk::DescriptorPoolCreateInfo::default().pool_sizes(&pool_sizes).max_sets(new_capacity);
+                *pool = device.create_descriptor_pool(&pool_info, None)?;
+                *capacity = new_capacity;
+            } else {
+                device.reset_descriptor_pool(*pool, vk::DescriptorPoolResetFlags::empty())?;
+            }
+        }
+
+        let layouts = vec![layout; draws.len()];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default().descriptor_pool(*pool).set_layouts(&layouts);
+        let sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        let camera = match &self.sprite_camera {
+            Some(camera) => camera.view_matrix(extent.width as f32, extent.height as f32),
+            None => glam::Mat4::orthographic_rh(0.0, extent.width as f32, 0.0, extent.height as f32, -1.0, 1.0),
+        };
+        unsafe {
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::cast_slice(&camera.to_cols_array()));
+            device.cmd_bind_vertex_buffers(command_buffer, 0, &[buffer.handle], &[0]);
+            for (draw, &set) in draws.iter().zip(&sets) {
+                let image_info = vk::DescriptorImageInfo::default()
+                    .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    .image_view(draw.texture.image.view);
+                let sampler_info = vk::DescriptorImageInfo::default().sampler(draw.texture.sampler.handle);
+                let writes = [
+                    vk::WriteDescriptorSet::default()
+                        .dst_set(set)
+                        .dst_binding(0)
+                        .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                        .image_info(std::slice::from_ref(&image_info)),
+                    vk::WriteDescriptorSet::default()
+                        .dst_set(set)
+                        .dst_binding(1)
+                        .descriptor_type(vk::DescriptorType::SAMPLER)
+                        .image_info(std::slice::from_ref(&sampler_info)),
+                ];
+                device.update_descriptor_sets(&writes, &[]);
+                device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[set], &[]);
+                device.cmd_draw(command_buffer, draw.vertex_count, 1, draw.first_vertex, 0);
+            }
+        }
+
+        // Las texturas viven hasta que el fence de este slot señalice.
+        self.frame_sprite_textures[frame].extend(draws.iter().map(|draw| draw.texture.clone()));
+        self.render_stats.pipeline_binds += 1;
+        self.render_stats.descriptor_binds += draws.len() as u32;
+        for draw in draws {
+            self.render_stats.count_draw(draw.vertex_count, 1);
+        }
+        self.sprite_batch.begin();
+        Ok(())
+    }
+}
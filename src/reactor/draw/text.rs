@@ -0,0 +1,89 @@
+use crate::core::error::ReactorResult;
+use crate::reactor::Reactor;
+use crate::resources::vertex::Vertex;
+use ash::vk;
+
+impl Reactor {
+    /// Reemplaza el texto a dibujar sobre el próximo frame (ver `TextRenderer`).
+    ///
+    /// Igual que las líneas de depuración, se consume en ese frame.
+    pub fn set_text(&mut self, vertices: &[Vertex]) {
+        self.text_vertices.clear();
+        self.text_vertices.extend_from_slice(vertices);
+    }
+
+    /// Abre el pase del overlay encima de la imagen de swapchain ya terminada
+    /// (después del post-proceso) y dibuja los sprites y, sobre ellos, el
+    /// texto pendiente.
+    pub(crate) fn record_overlay(&mut self, command_buffer: vk::CommandBuffer, image_index: u32) -> ReactorResult<()> {
+        let draw_text = !self.text_vertices.is_empty() && self.text_pipeline.is_some();
+        let draw_sprites = !self.sprite_batch.is_empty() && self.sprite_pipeline.is_some();
+        if !draw_text && !draw_sprites {
+            return Ok(());
+        }
+
+        let extent = self.swapchain.extent;
+        let subresource = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+        };
+
+        unsafe {
+            let device = &self.context.device;
+            // El pase anterior escribió la misma imagen: ordenar escritura → carga.
+            let barrier = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(self.swapchain.images[image_index as usize])
+                .subresource_range(subresource);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT, vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::DependencyFlags::empty(), &[], &[], &[barrier]);
+
+            let color_attachment = vk::RenderingAttachmentInfo::default()
+                .image_view(self.swapchain.image_views[image_index as usize])
+                .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .load_op(vk::AttachmentLoadOp::LOAD).store_op(vk::AttachmentStoreOp::STORE);
+            let rendering_info = vk::RenderingInfo::default()
+                .render_area(vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent })
+                .layer_count(1).color_attachments(std::slice::from_ref(&color_attachment));
+
+            device.cmd_begin_rendering(command_buffer, &rendering_info);
+            let viewport = vk::Viewport { x: 0.0, y: 0.0, width: extent.width as f32, height: extent.height as f32, min_depth: 0.0, max_depth: 1.0 };
+            device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            device.cmd_set_scissor(command_buffer, 0, &[vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent }]);
+        }
+
+        // El pase se cierra aunque un lote falle: la imagen debe quedar lista para present.
+        let mut result = Ok(());
+        if draw_sprites {
+            result = self.record_sprites(command_buffer, extent);
+        }
+        if draw_text {
+            result = result.and(self.record_text(command_buffer, extent));
+        }
+        unsafe { self.context.device.cmd_end_rendering(command_buffer) };
+        result
+    }
+
+    /// Dibuja el texto pendiente con proyección ortográfica en píxeles.
+    fn record_text(&mut self, command_buffer: vk::CommandBuffer, extent: vk::Extent2D) -> ReactorResult<()> {
+        let needed = std::mem::size_of_val(self.text_vertices.as_slice()) as u64;
+        let buffer = self.text_buffers.ensure(&self.context, self.allocator.clone(), self.current_frame, needed)?;
+        buffer.write(&self.text_vertices);
+        let pipeline = self.text_pipeline.as_ref().unwrap();
+        let vertex_count = self.text_vertices.len() as u32;
+        let ortho = glam::Mat4::orthographic_rh(0.0, extent.width as f32, 0.0, extent.height as f32, -1.0, 1.0);
+        unsafe {
+            let device = &self.context.device;
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::cast_slice(&ortho.to_cols_array()));
+            device.cmd_bind_vertex_buffers(command_buffer, 0, &[buffer.handle], &[0]);
+            device.cmd_draw(command_buffer, vertex_count, 1, 0, 0);
+        }
+
+        self.text_vertices.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::graphics::post_process::Tonemapper;
+use crate::reactor::Reactor;
+use ash::vk;
+
+impl Reactor {
+    /// `true` cuando la escena se dibuja en un target HDR (`scene_color_format`)
+    /// y hay que llevarla al swapchain con un tonemap.
+    pub fn hdr_enabled(&self) -> bool {
+        self.scene_color_format != self.swapchain.format
+    }
+
+    /// Exposición en pasos de diafragma (`0` = sin cambio, `1` = el doble de luz).
+    /// La ignora el efecto `AutoExposure` mientras está activo.
+    pub fn set_exposure(&mut self, ev: f32) {
+        self.post_process.settings.set_exposure_ev(ev);
+    }
+
+    /// Curva del tonemap, tanto en el post-proceso como en el resolve HDR.
+    pub fn set_tonemapper(&mut self, tonemapper: Tonemapper) {
+        self.post_process.settings.set_tonemapper(tonemapper);
+    }
+
+    pub fn tonemapper(&self) -> Tonemapper {
+        self.post_process.settings.tonemapper()
+    }
+
+    /// Lleva la offscreen HDR `image_index` (que sale del pase de geometría en
+    /// `COLOR_ATTACHMENT_OPTIMAL`) a la imagen del swapchain con exposición,
+    /// tonemap y gamma. La deja en `COLOR_ATTACHMENT_OPTIMAL`, como el pase
+    /// final del post-proceso.
+    pub(crate) fn record_tonemap(&mut self, command_buffer: vk::CommandBuffer, image_index: u32) {
+        let (Some(pipeline), Some(sampler)) = (self.tonemap_pipeline.as_ref(), self.tonemap_sampler) else {
+            return;
+        };
+        let Some(set) = self.tonemap_descriptor_sets.get(self.current_frame).copied() else {
+            return;
+        };
+        let scene = &self.post_process.offscreen_images[image_index as usize];
+        let swapchain_image = self.swapchain.images[image_index as usize];
+        let swapchain_view = self.swapchain.image_views[image_index as usize];
+        let extent = self.swapchain.extent;
+        let device = &self.context.device;
+
+        let subresource = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR, base_mip_level: 0, level_count: 1, base_array_layer: 0, layer_count: 1,
+        };
+        let barriers = [
+            vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL).new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE).dst_access_mask(vk::AccessFlags::SHADER_READ)
+                .image(scene.handle).subresource_range(subresource),
+            vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::UNDEFINED).new_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(vk::AccessFlags::empty()).dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .image(swapchain_image).subresource_range(subresource),
+        ];
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(scene.view);
+        let sampler_info = vk::DescriptorImageInfo::default().sampler(sampler);
+        let writes = [
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(0)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .image_info(std::slice::from_ref(&image_info)),
+            vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .image_info(std::slice::from_ref(&sampler_info)),
+        ];
+
+        // Un swapchain sRGB ya codifica la gamma al escribir.
+        let gamma = if is_srgb(self.swapchain.format) { 1.0 } else { self.post_process.settings.gamma.max(0.01) };
+        let push = [
+            self.post_process.settings.exposure.to_bits(),
+            gamma.to_bits(),
+            self.post_process.settings.tonemapper,
+        ];
+
+        unsafe {
+            device.update_descriptor_sets(&writes, &[]);
+            device.cmd_pipeline_barrier(command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::DependencyFlags::empty(), &[], &[], &barriers);
+
+            let color_attachment = vk::RenderingAttachmentInfo::default()
+                .image_view(swapchain_view)
+                .image_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .load_op(vk::AttachmentLoadOp::DONT_CARE).store_op(vk::AttachmentStoreOp::STORE);
+            let rendering_info = vk::RenderingInfo::default()
+                .render_area(vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent })
+                .layer_count(1).color_attachments(std::slice::from_ref(&color_attachment));
+
+            device.cmd_begin_rendering(command_buffer, &rendering_info);
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            device.cmd_bind_descriptor_sets(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline.layout, 0, &[set], &[]);
+            device.cmd_push_constants(command_buffer, pipeline.layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT, 0, bytemuck::cast_slice(&push));
+            let viewport = vk::Viewport { x: 0.0, y: 0.0, width: extent.width as f32, height: extent.height as f32, min_depth: 0.0, max_depth: 1.0 };
+            device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            device.cmd_set_scissor(command_buffer, 0, &[vk::Rect2D { offset: vk::Offset2D { x: 0, y: 0 }, extent }]);
+            device.cmd_draw(command_buffer, 3, 1, 0, 0);
+            device.cmd_end_rendering(command_buffer);
+        }
+    }
+}
+
+fn is_srgb(format: vk::Format) -> bool {
+    matches!(
+        format,
+        vk::Format::B8G8R8A8_SRGB | vk::Format::R8G8B8A8_SRGB | vk::Format::A8B8G8R8_SRGB_PACK32
+    )
+}
@@ -0,0 +1,132 @@
+use crate::reactor::Reactor;
+use ash::vk;
+
+impl Reactor {
+    /// Color de fondo de la escena. Va en el clear value del pase de
+    /// geometría (y en el fondo del lighting deferred): cambiarlo no recrea nada.
+    pub fn set_clear_color(&mut self, color: glam::Vec4) {
+        self.clear_color = color;
+    }
+
+    /// Dibuja la escena solo en `[x, y, w, h]` (normalizado, origen arriba a
+    /// la izquierda) del target: pantalla partida, letterboxing de editor...
+    /// Fuera del rectángulo queda `clear_color`. `(0, 0, 1, 1)` la devuelve a
+    /// pantalla completa.
+    pub fn set_viewport_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.viewport_rect = clamp_rect([x, y, width, height]);
+    }
+
+    pub fn viewport_rect(&self) -> [f32; 4] {
+        self.viewport_rect
+    }
+
+    /// Tamaño en píxeles del rectángulo de la escena en el swapchain; de aquí
+    /// sale el aspect ratio de la cámara.
+    pub fn viewport_extent(&self) -> vk::Extent2D {
+        viewport_pixels(self.viewport_rect, self.swapchain.extent).extent
+    }
+
+    pub(crate) fn clear_color_value(&self) -> vk::ClearValue {
+        vk::ClearValue { color: vk::ClearColorValue { float32: self.clear_color.to_array() } }
+    }
+
+    /// Viewport y scissor de la escena dentro de un target de `extent`.
+    pub(crate) fn scene_viewport(&self, extent: vk::Extent2D) -> (vk::Viewport, vk::Rect2D) {
+        let rect = viewport_pixels(self.viewport_rect, extent);
+        let viewport = vk::Viewport {
+            x: rect.offset.x as f32,
+            y: rect.offset.y as f32,
+            width: rect.extent.width as f32,
+            height: rect.extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+        (viewport, rect)
+    }
+
+    /// Lleva el clip space de la escena al del target completo. Los pases a
+    /// pantalla completa que reconstruyen posiciones desde el depth (lighting
+    /// deferred, decals, sombras RT) invierten `viewport_ndc * view_proj`.
+    pub(crate) fn viewport_ndc(&self, extent: vk::Extent2D) -> glam::Mat4 {
+        ndc_transform(viewport_pixels(self.viewport_rect, extent), extent)
+    }
+}
+
+/// Recorta el rectángulo a `[0, 1]` sin dejarlo vacío.
+fn clamp_rect([x, y, width, height]: [f32; 4]) -> [f32; 4] {
+    let x = x.clamp(0.0, 1.0);
+    let y = y.clamp(0.0, 1.0);
+    let width = width.min(1.0 - x).max(0.0);
+    let height = height.min(1.0 - y).max(0.0);
+    if width <= 0.0 || height <= 0.0 {
+        log::warn!("Empty viewport rect ({x}, {y}, {width}, {height}): using the full target");
+        return [0.0, 0.0, 1.0, 1.0];
+    }
+    [x, y, width, height]
+}
+
+/// Rectángulo normalizado a píxeles de `extent`, con al menos un píxel.
+fn viewport_pixels([x, y, width, height]: [f32; 4], extent: vk::Extent2D) -> vk::Rect2D {
+    let left = (x * extent.width as f32).round() as u32;
+    let top = (y * extent.height as f32).round() as u32;
+    let right = ((x + width) * extent.width as f32).round() as u32;
+    let bottom = ((y + height) * extent.height as f32).round() as u32;
+    let left = left.min(extent.width.saturating_sub(1));
+    let top = top.min(extent.height.saturating_sub(1));
+    vk::Rect2D {
+        offset: vk::Offset2D { x: left as i32, y: top as i32 },
+        extent: vk::Extent2D {
+            width: right.min(extent.width).saturating_sub(left).max(1),
+            height: bottom.min(extent.height).saturating_sub(top).max(1),
+        },
+    }
+}
+
+/// Escala y desplazamiento en NDC del rectángulo `rect` dentro de `extent`.
+fn ndc_transform(rect: vk::Rect2D, extent: vk::Extent2D) -> glam::Mat4 {
+    let width = extent.width.max(1) as f32;
+    let height = extent.height.max(1) as f32;
+    let scale_x = rect.extent.width as f32 / width;
+    let scale_y = rect.extent.height as f32 / height;
+    let offset_x = 2.0 * rect.offset.x as f32 / width + scale_x - 1.0;
+    let offset_y = 2.0 * rect.offset.y as f32 / height + scale_y - 1.0;
+    glam::Mat4::from_cols(
+        glam::Vec4::new(scale_x, 0.0, 0.0, 0.0),
+        glam::Vec4::new(0.0, scale_y, 0.0, 0.0),
+        glam::Vec4::Z,
+        glam::Vec4::new(offset_x, offset_y, 0.0, 1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_screen_halves_cover_the_target_without_overlap() {
+        let extent = vk::Extent2D { width: 1281, height: 720 };
+        let left = viewport_pixels([0.0, 0.0, 0.5, 1.0], extent);
+        let right = viewport_pixels([0.5, 0.0, 0.5, 1.0], extent);
+        assert_eq!(left.offset.x, 0);
+        assert_eq!(left.extent.width as i32, right.offset.x);
+        assert_eq!(left.extent.width + right.extent.width, extent.width);
+        assert_eq!(right.extent.height, 720);
+    }
+
+    #[test]
+    fn rect_is_clamped_to_the_target() {
+        assert_eq!(clamp_rect([0.75, -0.5, 0.5, 2.0]), [0.75, 0.0, 0.25, 1.0]);
+        assert_eq!(clamp_rect([1.0, 0.0, 0.5, 0.5]), [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn ndc_transform_maps_the_scene_corners_onto_its_rect() {
+        let extent = vk::Extent2D { width: 800, height: 600 };
+        let rect = viewport_pixels([0.5, 0.25, 0.5, 0.5], extent);
+        let ndc = ndc_transform(rect, extent);
+        let top_left = ndc * glam::Vec4::new(-2.0, -2.0, 0.5, 2.0);
+        let bottom_right = ndc.project_point3(glam::Vec3::new(1.0, 1.0, 0.5));
+        assert_eq!(top_left.truncate().truncate() / top_left.w, glam::Vec2::new(0.0, -0.5));
+        assert_eq!(bottom_right, glam::Vec3::new(1.0, 0.5, 0.5));
+    }
+}
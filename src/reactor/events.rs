@@ -9,7 +9,9 @@ impl Reactor {
     /// cuando corresponde.
     pub fn handle_event(&mut self, event: &WindowEvent) {
         self.input.process_event(event);
-        if let WindowEvent::Resized(_) = event {
+        if let WindowEvent::Resized(size) = event {
+            self.window_extent = vk::Extent2D { width: size.width, height: size.height };
+            self.minimized = size.width == 0 || size.height == 0;
             self.resized = true;
         }
     }
@@ -0,0 +1,90 @@
+//! Entorno IBL del fragment PBR (set = 1).
+//!
+//! `set_environment` graba el filtrado del cubemap (irradiance + prefiltered
+//! specular) y lo envía sin esperar; `wait_frame_slot` consulta su fence cada
+//! frame y lo activa al terminar, así que el render nunca se bloquea por el
+//! bake. La LUT del BRDF no depende del entorno: se calcula una vez en
+//! `init_ibl` y la comparten todos. Sin entorno se enlaza `ibl_fallback`, un
+//! cubo blanco cuya intensidad es la luz ambiente escalar.
+
+use super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::IblBaker;
+use crate::resources::texture::Cubemap;
+use ash::vk;
+use std::sync::Arc;
+
+/// Luz ambiente escalar mientras no hay entorno.
+pub const DEFAULT_AMBIENT_INTENSITY: f32 = 0.03;
+
+impl Reactor {
+    /// Crea el entorno de respaldo y la LUT del BRDF.
+    pub fn init_ibl(&mut self) -> ReactorResult<()> {
+        let (fallback, brdf_lut) = IblBaker::uniform(&self.context, self.allocator.clone(), DEFAULT_AMBIENT_INTENSITY)?;
+        self.ibl_fallback = Some(fallback);
+        self.ibl_brdf_lut = Some(brdf_lut);
+        log::info!("✅ IBL fallback initialized");
+        Ok(())
+    }
+
+    /// Empieza a filtrar `cubemap` como entorno de los materiales PBR. Hasta
+    /// que la GPU termina se sigue usando el entorno anterior; un bake aún
+    /// pendiente queda descartado.
+    pub fn set_environment(&mut self, cubemap: Arc<Cubemap>, intensity: f32) -> ReactorResult<()> {
+        self.pending_environment = None;
+        let pending = IblBaker::begin_bake_from_cubemap(
+            &self.context, self.allocator.clone(), cubemap, self.ibl_brdf_lut.clone(), intensity,
+        )?;
+        self.pending_environment = Some(pending);
+        Ok(())
+    }
+
+    /// Cambia la intensidad del entorno activo (y del pendiente, si lo hay).
+    pub fn set_environment_intensity(&mut self, intensity: f32) {
+        if let Some(ibl) = self.ibl_textures.as_mut() {
+            ibl.set_intensity(intensity);
+        }
+        if let Some(ibl) = self.pending_environment.as_mut().and_then(|p| p.textures.as_mut()) {
+            ibl.set_intensity(intensity);
+        }
+    }
+
+    /// Vuelve a la luz ambiente escalar.
+    pub fn clear_environment(&mut self) {
+        self.pending_environment = None;
+        if let Some(old) = self.ibl_textures.take() {
+            self.context.device.destroy_deferred(move |_| drop(old));
+        }
+    }
+
+    /// Intensidad de la luz ambiente cuando no hay entorno.
+    pub fn set_ambient_fallback(&mut self, intensity: f32) {
+        if let Some(fallback) = self.ibl_fallback.as_mut() {
+            fallback.set_intensity(intensity);
+        }
+    }
+
+    /// `true` mientras un `set_environment` sigue filtrándose en la GPU.
+    pub fn environment_pending(&self) -> bool {
+        self.pending_environment.is_some()
+    }
+
+    /// Layout del set = 1 que esperan `create_pbr_material` y
+    /// `create_ibl_material`; compatible con el de cualquier entorno.
+    pub fn ibl_descriptor_layout(&self) -> Option<vk::DescriptorSetLayout> {
+        self.ibl_fallback.as_ref().map(|ibl| ibl.descriptor_set_layout)
+    }
+
+    /// Activa el entorno pendiente si su bake terminó. El anterior se
+    /// destruye cuando los frames en vuelo que lo enlazaron acaban.
+    pub(crate) fn poll_environment(&mut self) {
+        let Some(textures) = self.pending_environment.as_mut().and_then(|p| p.try_finish()) else {
+            return;
+        };
+        self.pending_environment = None;
+        if let Some(old) = self.ibl_textures.replace(textures) {
+            self.context.device.destroy_deferred(move |_| drop(old));
+        }
+        log::info!("Environment IBL ready");
+    }
+}
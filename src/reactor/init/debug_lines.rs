@@ -0,0 +1,71 @@
+//! `Reactor::init_debug_lines` — line-list pipeline for `DebugRenderer`
+//!
+//! The shaders are tiny and compiled at runtime through naga, so the debug
+//! overlay does not depend on `glslc` being present at build time.
+
+use super::super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use ash::vk;
+
+const DEBUG_LINE_VERT: &str = r#"#version 450
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 color;
+layout(location = 2) in vec2 uv; // .x = alpha
+
+layout(location = 0) out vec4 fragColor;
+
+layout(push_constant) uniform Constants {
+    mat4 view_proj;
+} push;
+
+void main() {
+    gl_Position = push.view_proj * vec4(position, 1.0);
+    fragColor = vec4(color, uv.x);
+}
+"#;
+
+const DEBUG_LINE_FRAG: &str = r#"#version 450
+layout(location = 0) in vec4 fragColor;
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    outColor = fragColor;
+}
+"#;
+
+impl Reactor {
+    /// Crea el pipeline `LINE_LIST` usado para volcar las líneas de depuración.
+    pub fn init_debug_lines(&mut self) -> ReactorResult<()> {
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(DEBUG_LINE_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(DEBUG_LINE_FRAG, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_write: false,
+            blend_enable: true,
+            samples: self.msaa_samples,
+            topology: vk::PrimitiveTopology::LINE_LIST,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[],
+            self.scene_color_format,
+            Some(self.depth_format),
+            self.context.pipeline_cache(),
+        )?;
+
+        self.debug_line_pipeline = Some(pipeline);
+        log::info!("✅ Debug line pipeline initialized");
+        Ok(())
+    }
+}
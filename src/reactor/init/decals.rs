@@ -57,7 +57,7 @@ impl Reactor {
             vk::Format::R16G16B16A16_SFLOAT,
         ];
 
-        let decal_pipeline = crate::graphics::pipeline::Pipeline::with_config_multi_color(
+        let decal_pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache_multi_color(
             &self.context.device,
             None,
             &vert_words,
@@ -68,6 +68,7 @@ impl Reactor {
             &[decal_descriptor_layout],
             &color_formats,
             Some(self.depth_format),
+            self.context.pipeline_cache(),
         )?;
 
         self.decal_descriptor_layout = Some(decal_descriptor_layout);
@@ -0,0 +1,218 @@
+//! `Reactor::init_deferred` — pipelines and descriptors of the deferred path
+//!
+//! The geometry pass writes the existing `GBuffer` (4 MRT attachments) plus
+//! depth with `gbuffer.vert/frag`; set 0 holds a per-frame UBO with last
+//! frame's view-projection for motion vectors. The lighting pass draws a
+//! fullscreen triangle with `lighting_resolve.frag`, which samples the
+//! G-buffer and depth at set 0 and reuses the shadow (set 2) and light
+//! (set 3) sets of the forward materials. Created on demand by
+//! `set_renderer_mode(Deferred)`; the G-buffer set is rewritten whenever the
+//! swapchain (and with it the G-buffer and depth images) is recreated.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::base_shader::BaseShaderAsset;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::buffer::Buffer;
+use crate::graphics::pipeline::{Pipeline, PipelineConfig};
+use ash::vk;
+
+/// Vulkan objects owned by the deferred path.
+pub(crate) struct DeferredResources {
+    pub gbuffer_pipeline: Pipeline,
+    pub lighting_pipeline: Pipeline,
+    pub motion_layout: vk::DescriptorSetLayout,
+    pub gbuffer_layout: vk::DescriptorSetLayout,
+    pub empty_layout: vk::DescriptorSetLayout,
+    pub descriptor_pool: vk::DescriptorPool,
+    pub motion_sets: Vec<vk::DescriptorSet>,
+    pub motion_buffers: Vec<Buffer>,
+    pub gbuffer_set: vk::DescriptorSet,
+    pub sampler: vk::Sampler,
+}
+
+impl DeferredResources {
+    /// The caller must have waited for the device to go idle.
+    pub(crate) fn destroy(self, device: &ash::Device) {
+        let Self {
+            gbuffer_pipeline, lighting_pipeline, motion_buffers, sampler, descriptor_pool,
+            motion_layout, gbuffer_layout, empty_layout, ..
+        } = self;
+        drop((gbuffer_pipeline, lighting_pipeline, motion_buffers));
+        unsafe {
+            device.destroy_sampler(sampler, None);
+            device.destroy_descriptor_pool(descriptor_pool, None);
+            device.destroy_descriptor_set_layout(motion_layout, None);
+            device.destroy_descriptor_set_layout(gbuffer_layout, None);
+            device.destroy_descriptor_set_layout(empty_layout, None);
+        }
+    }
+}
+
+impl Reactor {
+    /// Crea pipelines, sets y buffers del path deferred. Necesita las luces
+    /// (set 3) y las sombras (set 2) ya inicializadas.
+    pub(crate) fn init_deferred(&mut self) -> ReactorResult<()> {
+        let (Some(light_layout), Some(shadow_layout)) = (self.light_descriptor_layout, self.shadow_descriptor_layout) else {
+            return Err(ReactorError::new(ErrorCode::NotInitialized, "Deferred rendering needs init_lights and init_shadows"));
+        };
+        let Some(gbuffer_formats) = self.gbuffer.as_ref().map(|gb| gb.formats()) else {
+            return Err(ReactorError::new(ErrorCode::NotInitialized, "Deferred rendering needs the G-Buffer"));
+        };
+        let device = self.context.ash_device();
+
+        let motion_bindings = [vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::VERTEX)];
+        let motion_layout = unsafe {
+            device.create_descriptor_set_layout(&vk::DescriptorSetLayoutCreateInfo::default().bindings(&motion_bindings), None)?
+        };
+
+        // G-Buffer (0..=3) + depth (4).
+        let gbuffer_bindings: Vec<_> = (0..5)
+            .map(|binding| {
+                vk::DescriptorSetLayoutBinding::default()
+                    .binding(binding)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .descriptor_count(1)
+                    .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            })
+            .collect();
+        let gbuffer_layout = unsafe {
+            device.create_descriptor_set_layout(&vk::DescriptorSetLayoutCreateInfo::default().bindings(&gbuffer_bindings), None)?
+        };
+        let empty_layout = unsafe { device.create_descriptor_set_layout(&vk::DescriptorSetLayoutCreateInfo::default(), None)? };
+
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::UNIFORM_BUFFER)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(5),
+        ];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32 + 1);
+        let descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+
+        let motion_layouts = vec![motion_layout; MAX_FRAMES_IN_FLIGHT];
+        let motion_sets = unsafe {
+            device.allocate_descriptor_sets(
+                &vk::DescriptorSetAllocateInfo::default().descriptor_pool(descriptor_pool).set_layouts(&motion_layouts),
+            )?
+        };
+        let gbuffer_set = unsafe {
+            device.allocate_descriptor_sets(
+                &vk::DescriptorSetAllocateInfo::default()
+                    .descriptor_pool(descriptor_pool)
+                    .set_layouts(std::slice::from_ref(&gbuffer_layout)),
+            )?[0]
+        };
+
+        let size = std::mem::size_of::<[[f32; 4]; 4]>() as u64;
+        let mut motion_buffers = Vec::with_capacity(MAX_FRAMES_IN_FLIGHT);
+        for &set in &motion_sets {
+            let buffer = Buffer::new_uniform(&self.context, self.allocator.clone(), size)?;
+            buffer.write(&[self.prev_view_projection.to_cols_array_2d()]);
+            let buffer_info = vk::DescriptorBufferInfo::default().buffer(buffer.handle).offset(0).range(size);
+            let write = vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(0)
+                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                .buffer_info(std::slice::from_ref(&buffer_info));
+            unsafe { device.update_descriptor_sets(&[write], &[]) };
+            motion_buffers.push(buffer);
+        }
+
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::NEAREST)
+            .min_filter(vk::Filter::NEAREST)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .max_lod(0.0);
+        let sampler = unsafe { device.create_sampler(&sampler_info, None)? };
+
+        let extent = self.swapchain.extent;
+        let gbuffer_pipeline = Pipeline::with_config_and_cache_multi_color(
+            &self.context.device,
+            None,
+            &BaseShaderAsset::GBufferVert.words(),
+            &BaseShaderAsset::GBufferFrag.words(),
+            extent.width,
+            extent.height,
+            &PipelineConfig::default(),
+            &[motion_layout],
+            &gbuffer_formats,
+            Some(self.depth_format),
+            self.context.pipeline_cache(),
+        )?;
+        let lighting_config = PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_test: false,
+            depth_write: false,
+            ..Default::default()
+        };
+        let lighting_pipeline = Pipeline::with_config_and_cache_multi_color(
+            &self.context.device,
+            None,
+            &BaseShaderAsset::PostProcessVert.words(),
+            &BaseShaderAsset::DeferredLighting.words(),
+            extent.width,
+            extent.height,
+            &lighting_config,
+            &[gbuffer_layout, empty_layout, shadow_layout, light_layout],
+            &[self.scene_color_format],
+            None,
+            self.context.pipeline_cache(),
+        )?;
+
+        self.deferred = Some(DeferredResources {
+            gbuffer_pipeline,
+            lighting_pipeline,
+            motion_layout,
+            gbuffer_layout,
+            empty_layout,
+            descriptor_pool,
+            motion_sets,
+            motion_buffers,
+            gbuffer_set,
+            sampler,
+        });
+        self.write_deferred_gbuffer_set();
+        log::info!("Deferred path ready: G-Buffer MRT + fullscreen lighting ({}x{})", extent.width, extent.height);
+        Ok(())
+    }
+
+    /// Enlaza los attachments actuales del G-Buffer y el depth al set de la
+    /// pasada de iluminación. Llamar después de recrear el swapchain.
+    pub(crate) fn write_deferred_gbuffer_set(&self) {
+        let (Some(deferred), Some(gbuffer), Some(depth_view)) = (self.deferred.as_ref(), self.gbuffer.as_ref(), self.depth_image_view) else {
+            return;
+        };
+        let mut image_infos = gbuffer.sampled_descriptor_infos(deferred.sampler).to_vec();
+        image_infos.push(
+            vk::DescriptorImageInfo::default()
+                .image_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+                .image_view(depth_view)
+                .sampler(deferred.sampler),
+        );
+        let writes: Vec<_> = image_infos
+            .iter()
+            .enumerate()
+            .map(|(binding, info)| {
+                vk::WriteDescriptorSet::default()
+                    .dst_set(deferred.gbuffer_set)
+                    .dst_binding(binding as u32)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(info))
+            })
+            .collect();
+        unsafe {
+            self.context.ash_device().update_descriptor_sets(&writes, &[]);
+        }
+    }
+}
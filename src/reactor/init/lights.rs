@@ -0,0 +1,59 @@
+//! `Reactor::init_lights` — per-frame `LightUniformData` UBO
+//!
+//! Creates one descriptor set per frame in flight, reading the light block
+//! out of the reactor's `DynamicUniformRing` through a dynamic offset.
+//! Materials built by `create_material` declare this layout at set 3, so the
+//! forward shader can read the `LightingSystem` uploaded by `set_lights`.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::ReactorResult;
+use crate::graphics::uniform_buffer::{DynamicUniformRing, LightUniformData};
+use ash::vk;
+
+/// Starting size of each frame's uniform ring; it grows if a frame overflows.
+const UNIFORM_RING_CAPACITY: u64 = 64 * 1024;
+
+impl Reactor {
+    /// Inicializa el descriptor set compartido de luces (set = 3).
+    pub fn init_lights(&mut self) -> ReactorResult<()> {
+        let device = self.context.ash_device();
+
+        let bindings = [vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let light_descriptor_layout =
+            unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
+            .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32)];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let light_descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+
+        let layouts = vec![light_descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(light_descriptor_pool)
+            .set_layouts(&layouts);
+        let light_descriptor_sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        let size = std::mem::size_of::<LightUniformData>() as u64;
+        let mut ring = DynamicUniformRing::new(
+            &self.context,
+            self.allocator.clone(),
+            MAX_FRAMES_IN_FLIGHT,
+            UNIFORM_RING_CAPACITY,
+        )?;
+        ring.register_binding(&self.context, &light_descriptor_sets, 0, size)?;
+
+        self.light_descriptor_layout = Some(light_descriptor_layout);
+        self.light_descriptor_pool = Some(light_descriptor_pool);
+        self.light_descriptor_sets = light_descriptor_sets;
+        self.uniform_ring = Some(ring);
+        Ok(())
+    }
+}
@@ -4,6 +4,12 @@
 //! the sub-initializer entry points split out by responsibility:
 //! - `shadows` — Cascaded Shadow Maps
 //! - `decals`  — Screen-Space Decals
+//! - `debug_lines` — DebugRenderer line-list pipeline
+//! - `lights`  — per-frame LightingSystem uniform buffer
+//! - `text`    — TextRenderer screen-space overlay pipeline
+//! - `skinning` — per-frame joint palette storage buffer
+//! - `rt_shadows` — ray query sun shadows over a scene TLAS (on demand)
+//! - `tonemap` — HDR scene format and its resolve to the swapchain
 //!
 //! Future sub-initializers (e.g. Hi-Z, light cull, volumetric clouds) live
 //! in their own files alongside this one.
@@ -12,18 +18,33 @@ use super::depth;
 use super::msaa;
 use super::{Reactor, MAX_FRAMES_IN_FLIGHT};
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
-use crate::core::VulkanContext;
+use crate::core::{FrameTimeline, VulkanContext};
+use crate::graphics::descriptors::DescriptorAllocator;
 use crate::graphics::swapchain::Swapchain;
 use crate::platform::input::Input;
 use crate::raytracing::RayTracingContext;
+use crate::resources::mesh_upload::MeshUploader;
 use crate::scene::ecs::World;
 use ash::vk;
 use gpu_allocator::vulkan::{Allocator, AllocatorCreateDesc};
 use std::sync::{Arc, Mutex};
 use winit::window::Window;
 
+mod debug_lines;
 mod decals;
+mod deferred;
+mod lights;
+mod rt_shadows;
+mod sdf;
 mod shadows;
+mod skinning;
+mod skybox;
+mod sprites;
+mod text;
+mod tonemap;
+
+pub(crate) use deferred::DeferredResources;
+pub(crate) use rt_shadows::{RtRetired, RtShadowResources};
 
 impl Reactor {
     pub fn init(
@@ -33,7 +54,29 @@ impl Reactor {
         vsync: bool,
     ) -> ReactorResult<Self> {
         let context = VulkanContext::new(window, enable_ray_tracing)?;
+        let inner_size = window.inner_size();
+        Self::init_with_context(context, inner_size.width, inner_size.height, requested_msaa, enable_ray_tracing, vsync)
+    }
 
+    /// Reactor sin ventana: renderiza a imágenes offscreen de `width`×`height`
+    /// (sin MSAA ni ray tracing). Los `draw_*` funcionan igual; el resultado
+    /// del último frame se lee con `read_pixels`.
+    pub fn init_headless(width: u32, height: u32) -> ReactorResult<Self> {
+        if width == 0 || height == 0 {
+            return Err(ReactorError::invalid_parameter(format!("headless size {}x{}", width, height)));
+        }
+        let context = VulkanContext::new_headless(false)?;
+        Self::init_with_context(context, width, height, 1, false, false)
+    }
+
+    fn init_with_context(
+        context: VulkanContext,
+        width: u32,
+        height: u32,
+        requested_msaa: u32,
+        enable_ray_tracing: bool,
+        vsync: bool,
+    ) -> ReactorResult<Self> {
         let allocator = Allocator::new(&AllocatorCreateDesc {
             instance: context.ash_instance().clone(),
             device: context.ash_device().clone(),
@@ -50,13 +93,18 @@ impl Reactor {
             )
         })?;
         let allocator = Arc::new(Mutex::new(allocator));
+        let descriptor_allocator = Arc::new(Mutex::new(DescriptorAllocator::new(&context, MAX_FRAMES_IN_FLIGHT)));
+        let mesh_uploader = MeshUploader::new(&context, allocator.clone())?;
 
-        let inner_size = window.inner_size();
-        let swapchain = Swapchain::new(&context, inner_size.width, inner_size.height, vsync)?;
-
-        context
-            .debug_namer()
-            .name_swapchain(swapchain.handle, "Swapchain: Main Window");
+        let swapchain = if context.is_headless() {
+            Swapchain::offscreen(&context, allocator.clone(), width, height, MAX_FRAMES_IN_FLIGHT as u32)?
+        } else {
+            let swapchain = Swapchain::new(&context, width, height, vsync)?;
+            context
+                .debug_namer()
+                .name_swapchain(swapchain.handle, "Swapchain: Main Window");
+            swapchain
+        };
 
         for (i, view) in swapchain.image_views.iter().enumerate() {
             context
@@ -64,11 +112,13 @@ impl Reactor {
                 .name_image_view(*view, &format!("ImageView: Swapchain[{}]", i));
         }
 
+        let scene_color_format = tonemap::scene_color_format(&context, swapchain.format);
+
         let msaa_samples = msaa::msaa_from_u32(requested_msaa, &context);
         if msaa_samples == vk::SampleCountFlags::TYPE_1 {
-            println!("🔷 MSAA: disabled (1 sample)");
+            log::info!("🔷 MSAA: disabled (1 sample)");
         } else {
-            println!("🔷 MSAA: {:?} enabled for anti-aliasing", msaa_samples);
+            log::info!("🔷 MSAA: {:?} enabled for anti-aliasing", msaa_samples);
         }
 
         let (msaa_image, msaa_image_view, msaa_memory) =
@@ -77,7 +127,7 @@ impl Reactor {
                     &context,
                     swapchain.extent.width,
                     swapchain.extent.height,
-                    swapchain.format,
+                    scene_color_format,
                     msaa_samples,
                 )?;
                 context
@@ -102,7 +152,7 @@ impl Reactor {
             depth_format,
             msaa_samples,
         )?;
-        println!("🔹 Depth buffer created: {:?}", depth_format);
+        log::info!("🔹 Depth buffer created: {:?}", depth_format);
 
         context
             .debug_namer()
@@ -212,14 +262,16 @@ impl Reactor {
             }
         }
 
+        let timeline = FrameTimeline::new(&context, MAX_FRAMES_IN_FLIGHT)?;
+
         let ray_tracing = if enable_ray_tracing {
             match RayTracingContext::new(&context) {
                 Ok(rt) => {
-                    println!("Ray Tracing initialized successfully!");
+                    log::info!("Ray Tracing initialized successfully!");
                     Some(rt)
                 }
                 Err(e) => {
-                    println!("Ray Tracing not supported or failed to init: {}", e);
+                    log::warn!("Ray Tracing not supported or failed to init: {}", e);
                     None
                 }
             }
@@ -265,6 +317,7 @@ impl Reactor {
             swapchain.extent.height,
             swapchain.images.len() as u32,
             swapchain.format,
+            scene_color_format,
             depth_image_view,
             msaa_samples == vk::SampleCountFlags::TYPE_1,
         )?;
@@ -359,16 +412,24 @@ impl Reactor {
             context,
             swapchain,
             allocator,
+            descriptor_allocator,
+            mesh_uploader,
             command_pool,
             command_buffers,
             image_available_semaphores,
             render_finished_semaphores,
             in_flight_fences,
             current_frame: 0,
+            frames_in_flight: MAX_FRAMES_IN_FLIGHT,
+            frame_resources: (0..MAX_FRAMES_IN_FLIGHT).map(|_| Vec::new()).collect(),
+            retired_meshes: Vec::new(),
+            timeline,
             input: Input::new(),
             world: World::new(),
             ray_tracing,
             resized: false,
+            minimized: false,
+            window_extent: vk::Extent2D { width, height },
             device_lost: false,
             exit_requested: false,
             vsync,
@@ -379,6 +440,8 @@ impl Reactor {
             prev_view_projection: glam::Mat4::IDENTITY,
             camera_near: 0.1,
             camera_far: 1000.0,
+            clear_color: glam::Vec4::new(0.1, 0.1, 0.1, 1.0),
+            viewport_rect: [0.0, 0.0, 1.0, 1.0],
             post_process,
             gbuffer: Some(gbuffer),
             temporal_history: Some(temporal_history),
@@ -387,6 +450,8 @@ impl Reactor {
             volumetric_clouds: Some(volumetric_clouds),
             clouds_sampler: Some(linear_sampler),
             pixel_intelligent: crate::core::PixelIntelligent::default(),
+            render_scale: crate::core::RenderScale::default(),
+            render_scale_targets: None,
             msaa_samples,
             msaa_image,
             msaa_image_view,
@@ -395,7 +460,16 @@ impl Reactor {
             depth_image_view: Some(depth_image_view),
             depth_memory: Some(depth_memory),
             depth_format,
+            scene_color_format,
+            tonemap_pipeline: None,
+            tonemap_descriptor_layout: None,
+            tonemap_descriptor_pool: None,
+            tonemap_descriptor_sets: Vec::new(),
+            tonemap_sampler: None,
             ibl_textures: None,
+            ibl_fallback: None,
+            ibl_brdf_lut: None,
+            pending_environment: None,
             shadow_map: None,
             shadow_image: None,
             shadow_image_views: Vec::new(),
@@ -407,14 +481,91 @@ impl Reactor {
             shadow_descriptor_pool: None,
             shadow_descriptor_sets: Vec::new(),
             shadow_uniform_buffers: Vec::new(),
+            shadow_cache_key: None,
             decals: Vec::new(),
             decal_pipeline: None,
             decal_descriptor_layout: None,
             decal_cube_mesh: None,
+            debug_line_pipeline: None,
+            debug_line_buffers: crate::graphics::buffer::PerFrameBuffer::new(vk::BufferUsageFlags::VERTEX_BUFFER),
+            debug_line_vertices: Vec::new(),
+            sdf_pipeline: None,
+            sdf_descriptor_layout: None,
+            sdf_descriptor_pool: None,
+            sdf_descriptor_sets: Vec::new(),
+            sdf_buffers: Vec::new(),
+            sdf_primitives: Vec::new(),
+            sdf_heatmap: false,
+            sdf_overflow_warned: false,
+            skybox_pipeline: None,
+            skybox_descriptor_layout: None,
+            skybox_descriptor_pool: None,
+            skybox_descriptor_sets: Vec::new(),
+            frame_skyboxes: vec![None; MAX_FRAMES_IN_FLIGHT],
+            text_pipeline: None,
+            text_buffers: crate::graphics::buffer::PerFrameBuffer::new(vk::BufferUsageFlags::VERTEX_BUFFER),
+            text_vertices: Vec::new(),
+            sprite_pipeline: None,
+            sprite_descriptor_layout: None,
+            sprite_descriptor_pools: Vec::new(),
+            sprite_buffers: crate::graphics::buffer::PerFrameBuffer::new(vk::BufferUsageFlags::VERTEX_BUFFER),
+            sprite_batch: crate::graphics::sprite_batch::SpriteBatch::new(),
+            sprite_camera: None,
+            frame_sprite_textures: vec![Vec::new(); MAX_FRAMES_IN_FLIGHT],
+            instance_buffers: crate::graphics::buffer::PerFrameBuffer::new(vk::BufferUsageFlags::VERTEX_BUFFER),
+            instance_data: Vec::new(),
+            instancing_mismatch_warned: std::collections::HashSet::new(),
+            skin_descriptor_layout: None,
+            skin_descriptor_pool: None,
+            skin_descriptor_sets: Vec::new(),
+            joint_buffers: Vec::new(),
+            joint_data: Vec::new(),
+            skinned_shadow_warned: false,
+            light_descriptor_layout: None,
+            light_descriptor_pool: None,
+            light_descriptor_sets: Vec::new(),
+            light_uniform: crate::graphics::uniform_buffer::LightUniformData::default(),
+            light_uniform_offset: 0,
+            uniform_ring: None,
+            light_overflow_warned: false,
+            renderer_mode: crate::app::config::RendererMode::Forward,
+            deferred: None,
+            rt_shadows: None,
+            rt_shadows_enabled: false,
+            render_stats: crate::reactor::RenderStats::default(),
+            last_draw_at: None,
+            last_rendered_image: None,
+            capture_request: None,
+            capture_in_flight: None,
+            captured_frame: None,
         };
 
+        // Sin el resolve, una escena HDR nunca llegaría al swapchain.
+        reactor.init_tonemap()?;
         reactor.init_decals()?;
         reactor.init_shadows()?;
+        reactor.init_lights()?;
+        if let Err(e) = reactor.init_debug_lines() {
+            log::warn!("Debug line pipeline unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_text_overlay() {
+            log::warn!("Text overlay pipeline unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_sprites() {
+            log::warn!("Sprite pipeline unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_skinning() {
+            log::warn!("Skinned meshes unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_sdf() {
+            log::warn!("SDF raymarch pipeline unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_skybox() {
+            log::warn!("Skybox pipeline unavailable: {}", e);
+        }
+        if let Err(e) = reactor.init_ibl() {
+            log::warn!("IBL fallback unavailable: {}", e);
+        }
         Ok(reactor)
     }
 }
@@ -0,0 +1,222 @@
+//! `Reactor::init_rt_shadows` — ray-traced sun shadows (ray query)
+//!
+//! A BLAS is built per unique mesh and a TLAS from the shadow casters of the
+//! scene; `raytracing::TlasTracker` decides each frame between reusing it,
+//! refitting it (only transforms moved) or rebuilding it (objects added or
+//! removed). All builds are recorded into the frame's command buffer, so
+//! scene edits never stall the CPU. After the opaque pass a fullscreen
+//! triangle rebuilds every pixel's world position from depth, traces one ray
+//! toward the sun and darkens occluded pixels through alpha blending, on top
+//! of the shadow maps. Set 0 holds the TLAS (binding 0) and the depth image
+//! (binding 1); there is one set per frame in flight, rewritten every frame
+//! because both can be recreated. Needs `VK_KHR_ray_query`; created on
+//! demand by `set_rt_shadows_enabled(true)`.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::buffer::PerFrameBuffer;
+use crate::graphics::pipeline::{Pipeline, PipelineConfig};
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::raytracing::{Blas, ScratchBuffer, Tlas, TlasTracker};
+use crate::resources::mesh::Mesh;
+use ash::vk;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const RT_SHADOW_VERT: &str = r#"
+@vertex
+fn main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+"#;
+
+const RT_SHADOW_FRAG: &str = r#"
+// VK_RAY_FLAGS_TERMINATE_ON_FIRST_HIT_BIT_KHR: any occluder will do.
+const SHADOW_RAY_FLAGS: u32 = 4u;
+const NO_INTERSECTION: u32 = 0u;
+
+struct Constants {
+    inv_view_proj: mat4x4<f32>,
+    // xyz: direction toward the sun, w: shadow strength
+    sun: vec4<f32>,
+    // xy: target size in pixels, z: minimum ray start offset, w: ray length
+    params: vec4<f32>,
+}
+
+var<push_constant> push: Constants;
+
+@group(0) @binding(0) var scene_tlas: acceleration_structure;
+@group(0) @binding(1) var scene_depth: texture_depth_2d;
+
+@fragment
+fn main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let depth = textureLoad(scene_depth, vec2<i32>(frag_coord.xy), 0);
+    if depth >= 1.0 {
+        discard;
+    }
+    let ndc = frag_coord.xy / push.params.xy * 2.0 - 1.0;
+    let world = push.inv_view_proj * vec4<f32>(ndc, depth, 1.0);
+    let position = world.xyz / world.w;
+    // Depth loses precision with distance: push the ray start out with it.
+    let near_point = push.inv_view_proj * vec4<f32>(ndc, 0.0, 1.0);
+    let t_min = max(push.params.z, distance(position, near_point.xyz / near_point.w) * 0.002);
+
+    var query: ray_query;
+    rayQueryInitialize(&query, scene_tlas, RayDesc(SHADOW_RAY_FLAGS, 0xFFu, t_min, push.params.w, position, push.sun.xyz));
+    while rayQueryProceed(&query) {}
+    if rayQueryGetCommittedIntersection(&query).kind == NO_INTERSECTION {
+        discard;
+    }
+    return vec4<f32>(0.0, 0.0, 0.0, push.sun.w);
+}
+"#;
+
+/// Something the GPU may still read from an earlier frame.
+pub(crate) enum RtRetired {
+    Blas(Arc<Mesh>, Blas),
+    Scratch(ScratchBuffer),
+    Tlas(Tlas),
+}
+
+/// Vulkan objects owned by the ray-traced shadow pass.
+pub(crate) struct RtShadowResources {
+    pub pipeline: Pipeline,
+    pub descriptor_layout: vk::DescriptorSetLayout,
+    pub descriptor_pool: vk::DescriptorPool,
+    pub descriptor_sets: Vec<vk::DescriptorSet>,
+    /// `vk::AccelerationStructureInstanceKHR` per frame in flight.
+    pub instance_buffers: PerFrameBuffer,
+    pub tlas: Option<Tlas>,
+    /// BLAS per mesh, keyed by `Arc::as_ptr`. Holding the `Arc` keeps the
+    /// key from being reused by another mesh.
+    pub blases: HashMap<usize, (Arc<Mesh>, Blas)>,
+    pub tracker: TlasTracker,
+    /// Per frame slot; dropped once that slot's fence has been waited.
+    pub retired: Vec<Vec<RtRetired>>,
+}
+
+impl RtShadowResources {
+    /// The caller must have waited for the device to go idle.
+    pub(crate) fn destroy(self, device: &ash::Device) {
+        let Self { pipeline, descriptor_layout, descriptor_pool, instance_buffers, tlas, blases, retired, .. } = self;
+        drop((pipeline, instance_buffers, tlas, blases, retired));
+        unsafe {
+            device.destroy_descriptor_pool(descriptor_pool, None);
+            device.destroy_descriptor_set_layout(descriptor_layout, None);
+        }
+    }
+}
+
+impl Reactor {
+    /// Crea el pipeline de sombras por ray query y sus descriptor sets. Falla
+    /// con `NotSupported` si no hay ray tracing o `VK_KHR_ray_query`.
+    pub(crate) fn init_rt_shadows(&mut self) -> ReactorResult<()> {
+        if !self.ray_tracing.as_ref().is_some_and(|rt| rt.ray_query) {
+            return Err(ReactorError::new(ErrorCode::NotSupported, "RT shadows need ray tracing with VK_KHR_ray_query"));
+        }
+        let device = self.context.ash_device();
+
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::ACCELERATION_STRUCTURE_KHR)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::ACCELERATION_STRUCTURE_KHR)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+        ];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let descriptor_pool = match unsafe { device.create_descriptor_pool(&pool_info, None) } {
+            Ok(pool) => pool,
+            Err(e) => {
+                unsafe { device.destroy_descriptor_set_layout(descriptor_layout, None) };
+                return Err(e.into());
+            }
+        };
+
+        let built = self.create_rt_shadow_pipeline(descriptor_layout, descriptor_pool);
+        let (pipeline, descriptor_sets) = match built {
+            Ok(built) => built,
+            Err(e) => {
+                unsafe {
+                    device.destroy_descriptor_pool(descriptor_pool, None);
+                    device.destroy_descriptor_set_layout(descriptor_layout, None);
+                }
+                return Err(e);
+            }
+        };
+
+        self.rt_shadows = Some(RtShadowResources {
+            pipeline,
+            descriptor_layout,
+            descriptor_pool,
+            descriptor_sets,
+            instance_buffers: PerFrameBuffer::new(
+                vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
+                    | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
+            ),
+            tlas: None,
+            blases: HashMap::new(),
+            tracker: TlasTracker::new(),
+            retired: (0..MAX_FRAMES_IN_FLIGHT).map(|_| Vec::new()).collect(),
+        });
+        log::info!("✅ Ray-traced shadows initialized (ray query)");
+        Ok(())
+    }
+
+    fn create_rt_shadow_pipeline(
+        &self,
+        descriptor_layout: vk::DescriptorSetLayout,
+        descriptor_pool: vk::DescriptorPool,
+    ) -> ReactorResult<(Pipeline, Vec<vk::DescriptorSet>)> {
+        let layouts = vec![descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(descriptor_pool)
+            .set_layouts(&layouts);
+        let descriptor_sets = unsafe { self.context.device.allocate_descriptor_sets(&alloc_info)? };
+
+        let mut compiler = ShaderCompiler::new().with_ray_query();
+        let vert = compiler.compile_source(RT_SHADOW_VERT, ShaderLanguage::Wgsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(RT_SHADOW_FRAG, ShaderLanguage::Wgsl, ShaderStage::Fragment, "main")?;
+
+        // Sin depth attachment: el depth se lee como textura.
+        let config = PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_test: false,
+            depth_write: false,
+            blend_enable: true,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[descriptor_layout],
+            self.scene_color_format,
+            None,
+            self.context.pipeline_cache(),
+        )?;
+        Ok((pipeline, descriptor_sets))
+    }
+}
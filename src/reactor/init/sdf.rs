@@ -0,0 +1,225 @@
+//! `Reactor::init_sdf` — fullscreen raymarch pipeline for `SdfScene`
+//!
+//! The primitives live in one storage buffer per frame in flight (set = 0).
+//! The fragment shader rebuilds the view ray from the inverse view-projection,
+//! sphere-traces the field and writes `gl_FragDepth`, so the regular depth test
+//! sorts SDF surfaces against meshes. Compiled at runtime through naga like the
+//! debug line shaders.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::systems::sdf::{SdfPrimitiveGpu, MAX_SDF_PRIMITIVES};
+use ash::vk;
+
+const SDF_VERT: &str = r#"#version 450
+layout(location = 0) out vec2 ndc;
+
+void main() {
+    vec2 position = vec2(float((gl_VertexIndex << 1) & 2), float(gl_VertexIndex & 2)) * 2.0 - 1.0;
+    ndc = position;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
+"#;
+
+const SDF_FRAG: &str = r#"#version 450
+layout(location = 0) in vec2 ndc;
+layout(location = 0) out vec4 outColor;
+
+struct SdfPrimitive {
+    mat4 world_to_local;
+    vec4 params;
+    vec4 color;
+    vec4 blend;
+};
+
+layout(std430, set = 0, binding = 0) readonly buffer SdfPrimitives {
+    SdfPrimitive primitives[MAX_SDF_PRIMITIVES];
+};
+
+layout(push_constant) uniform Constants {
+    mat4 view_proj;
+    mat4 inv_view_proj;
+    vec4 light_pos;
+    // x: primitive count, y: heatmap, z: max steps, w: hit epsilon
+    vec4 params;
+} push;
+
+float shape_distance(SdfPrimitive prim, vec3 p) {
+    int shape = int(prim.params.w + 0.5);
+    if (shape == 0) {
+        return length(p) - prim.params.x;
+    } else if (shape == 1) {
+        vec3 q = abs(p) - prim.params.xyz;
+        return length(max(q, vec3(0.0))) + min(max(q.x, max(q.y, q.z)), 0.0);
+    }
+    vec2 q = vec2(length(p.xz) - prim.params.x, p.y);
+    return length(q) - prim.params.y;
+}
+
+vec4 scene(vec3 p) {
+    float d = 1e30;
+    vec3 color = vec3(0.0);
+    int count = int(push.params.x);
+    for (int i = 0; i < count; i++) {
+        SdfPrimitive prim = primitives[i];
+        vec3 local = (prim.world_to_local * vec4(p, 1.0)).xyz;
+        float b = shape_distance(prim, local) * prim.blend.y;
+        int op = int(prim.color.w + 0.5);
+        if (op == 0) {
+            if (b < d) { d = b; color = prim.color.rgb; }
+        } else if (op == 1) {
+            d = max(d, -b);
+        } else if (op == 2) {
+            if (b > d) { d = b; color = prim.color.rgb; }
+        } else {
+            float k = prim.blend.x;
+            float h = clamp(0.5 + 0.5 * (b - d) / k, 0.0, 1.0);
+            d = mix(b, d, h) - k * h * (1.0 - h);
+            color = mix(prim.color.rgb, color, h);
+        }
+    }
+    return vec4(color, d);
+}
+
+vec3 scene_normal(vec3 p) {
+    vec2 e = vec2(0.001, 0.0);
+    return normalize(vec3(
+        scene(p + e.xyy).w - scene(p - e.xyy).w,
+        scene(p + e.yxy).w - scene(p - e.yxy).w,
+        scene(p + e.yyx).w - scene(p - e.yyx).w));
+}
+
+vec3 heat(float t) {
+    return clamp(vec3(t * 3.0, t * 3.0 - 1.0, t * 3.0 - 2.0), 0.0, 1.0);
+}
+
+void main() {
+    vec4 near_point = push.inv_view_proj * vec4(ndc, 0.0, 1.0);
+    vec4 far_point = push.inv_view_proj * vec4(ndc, 1.0, 1.0);
+    vec3 origin = near_point.xyz / near_point.w;
+    vec3 ray = far_point.xyz / far_point.w - origin;
+    float max_distance = length(ray);
+    vec3 direction = ray / max_distance;
+
+    int max_steps = int(push.params.z);
+    float t = 0.0;
+    int steps = 0;
+    bool hit = false;
+    vec4 hit_sample = vec4(0.0);
+    for (int i = 0; i < max_steps; i++) {
+        steps = i + 1;
+        hit_sample = scene(origin + direction * t);
+        if (hit_sample.w < push.params.w) { hit = true; break; }
+        t += hit_sample.w;
+        if (t > max_distance) { break; }
+    }
+
+    bool heatmap = push.params.y > 0.5;
+    if (!hit && !heatmap) {
+        discard;
+    }
+
+    vec3 p = origin + direction * t;
+    if (hit) {
+        vec4 clip = push.view_proj * vec4(p, 1.0);
+        gl_FragDepth = clip.z / clip.w;
+    } else {
+        gl_FragDepth = 0.99999;
+    }
+
+    if (heatmap) {
+        outColor = vec4(heat(float(steps) / float(max_steps)), 1.0);
+        return;
+    }
+    vec3 normal = scene_normal(p);
+    vec3 to_light = normalize(push.light_pos.xyz - p);
+    float diffuse = max(dot(normal, to_light), 0.0);
+    outColor = vec4(hit_sample.rgb * (0.15 + 0.85 * diffuse), 1.0);
+}
+"#;
+
+impl Reactor {
+    /// Crea el pipeline de raymarching SDF y sus storage buffers por frame.
+    pub fn init_sdf(&mut self) -> ReactorResult<()> {
+        let device = self.context.ash_device();
+
+        let bindings = [vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let sdf_descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32)];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let sdf_descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+        // A partir de aquí los handles son del Reactor: Drop los libera si algo falla.
+        self.sdf_descriptor_layout = Some(sdf_descriptor_layout);
+        self.sdf_descriptor_pool = Some(sdf_descriptor_pool);
+
+        let layouts = vec![sdf_descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(sdf_descriptor_pool)
+            .set_layouts(&layouts);
+        let sdf_descriptor_sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        let size = (std::mem::size_of::<SdfPrimitiveGpu>() * MAX_SDF_PRIMITIVES) as u64;
+        let mut sdf_buffers = Vec::with_capacity(MAX_FRAMES_IN_FLIGHT);
+        for &set in &sdf_descriptor_sets {
+            let buffer = crate::graphics::buffer::Buffer::new(
+                &self.context,
+                self.allocator.clone(),
+                size,
+                vk::BufferUsageFlags::STORAGE_BUFFER,
+                gpu_allocator::MemoryLocation::CpuToGpu,
+            )?;
+            let buffer_info = vk::DescriptorBufferInfo::default().buffer(buffer.handle).offset(0).range(size);
+            let write = vk::WriteDescriptorSet::default()
+                .dst_set(set)
+                .dst_binding(0)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(std::slice::from_ref(&buffer_info));
+            unsafe {
+                device.update_descriptor_sets(&[write], &[]);
+            }
+            sdf_buffers.push(buffer);
+        }
+
+        let frag_source = SDF_FRAG.replace("MAX_SDF_PRIMITIVES", &MAX_SDF_PRIMITIVES.to_string());
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(SDF_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(&frag_source, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            samples: self.msaa_samples,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[sdf_descriptor_layout],
+            self.scene_color_format,
+            Some(self.depth_format),
+            self.context.pipeline_cache(),
+        )?;
+
+        self.sdf_descriptor_sets = sdf_descriptor_sets;
+        self.sdf_buffers = sdf_buffers;
+        self.sdf_pipeline = Some(pipeline);
+        log::info!("✅ SDF raymarch pipeline initialized");
+        Ok(())
+    }
+}
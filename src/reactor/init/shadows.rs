@@ -18,81 +18,9 @@ impl Reactor {
         let width = shadow_map.config.resolution;
         let height = shadow_map.config.resolution;
         let cascade_count = shadow_map.config.cascade_count;
-        let format = vk::Format::D32_SFLOAT;
         let device = self.context.ash_device();
-
-        let image_info = vk::ImageCreateInfo::default()
-            .image_type(vk::ImageType::TYPE_2D)
-            .extent(vk::Extent3D { width, height, depth: 1 })
-            .mip_levels(1)
-            .array_layers(cascade_count)
-            .format(format)
-            .tiling(vk::ImageTiling::OPTIMAL)
-            .initial_layout(vk::ImageLayout::UNDEFINED)
-            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
-            .sharing_mode(vk::SharingMode::EXCLUSIVE)
-            .samples(vk::SampleCountFlags::TYPE_1);
-
-        let shadow_image = unsafe { device.create_image(&image_info, None)? };
-        let requirements = unsafe { device.get_image_memory_requirements(shadow_image) };
-
-        let memory_props = unsafe {
-            self.context
-                .instance
-                .get_physical_device_memory_properties(self.context.physical_device)
-        };
-        let memory_type_index = (0..memory_props.memory_type_count)
-            .find(|&i| {
-                let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
-                let memory_type = memory_props.memory_types[i as usize];
-                suitable
-                    && memory_type
-                        .property_flags
-                        .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
-            })
-            .ok_or_else(|| {
-                ReactorError::new(
-                    ErrorCode::VulkanMemoryAllocation,
-                    "Failed to find memory type for shadow map",
-                )
-            })?;
-
-        let alloc_info = vk::MemoryAllocateInfo::default()
-            .allocation_size(requirements.size)
-            .memory_type_index(memory_type_index);
-
-        let shadow_memory = unsafe { device.allocate_memory(&alloc_info, None)? };
-        unsafe { device.bind_image_memory(shadow_image, shadow_memory, 0)? };
-
-        let array_view_info = vk::ImageViewCreateInfo::default()
-            .image(shadow_image)
-            .view_type(vk::ImageViewType::TYPE_2D_ARRAY)
-            .format(format)
-            .subresource_range(vk::ImageSubresourceRange {
-                aspect_mask: vk::ImageAspectFlags::DEPTH,
-                base_mip_level: 0,
-                level_count: 1,
-                base_array_layer: 0,
-                layer_count: cascade_count,
-            });
-        let shadow_array_view = unsafe { device.create_image_view(&array_view_info, None)? };
-
-        let mut shadow_image_views = Vec::with_capacity(cascade_count as usize);
-        for layer in 0..cascade_count {
-            let view_info = vk::ImageViewCreateInfo::default()
-                .image(shadow_image)
-                .view_type(vk::ImageViewType::TYPE_2D)
-                .format(format)
-                .subresource_range(vk::ImageSubresourceRange {
-                    aspect_mask: vk::ImageAspectFlags::DEPTH,
-                    base_mip_level: 0,
-                    level_count: 1,
-                    base_array_layer: layer,
-                    layer_count: 1,
-                });
-            let view = unsafe { device.create_image_view(&view_info, None)? };
-            shadow_image_views.push(view);
-        }
+        let (shadow_image, shadow_memory, shadow_array_view, shadow_image_views) =
+            self.create_shadow_targets(width, cascade_count)?;
 
         let sampler_info = vk::SamplerCreateInfo::default()
             .mag_filter(vk::Filter::LINEAR)
@@ -214,7 +142,7 @@ impl Reactor {
             ..Default::default()
         };
 
-        let shadow_pipeline = crate::graphics::pipeline::Pipeline::with_config(
+        let shadow_pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
             &self.context.device,
             None,
             &shadow_vert_spv,
@@ -225,6 +153,7 @@ impl Reactor {
             &[shadow_descriptor_layout],
             vk::Format::UNDEFINED,
             Some(vk::Format::D32_SFLOAT),
+            self.context.pipeline_cache(),
         )?;
 
         self.shadow_map = Some(shadow_map);
@@ -239,11 +168,97 @@ impl Reactor {
         self.shadow_descriptor_sets = shadow_descriptor_sets;
         self.shadow_uniform_buffers = shadow_uniform_buffers;
 
-        println!(
+        log::info!(
             "✅ CSM Shadow Maps initialized: {} cascades @ {}x{}",
             cascade_count, width, height
         );
 
         Ok(())
     }
+
+    /// Crea la imagen de profundidad del CSM (una capa por cascada) con su
+    /// memoria, la vista array que muestrea el shader y una vista por capa.
+    pub(crate) fn create_shadow_targets(
+        &self,
+        resolution: u32,
+        cascade_count: u32,
+    ) -> ReactorResult<(vk::Image, vk::DeviceMemory, vk::ImageView, Vec<vk::ImageView>)> {
+        let device = self.context.ash_device();
+        let format = vk::Format::D32_SFLOAT;
+
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D { width: resolution, height: resolution, depth: 1 })
+            .mip_levels(1)
+            .array_layers(cascade_count)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let shadow_image = unsafe { device.create_image(&image_info, None)? };
+        let requirements = unsafe { device.get_image_memory_requirements(shadow_image) };
+
+        let memory_props = unsafe {
+            self.context
+                .instance
+                .get_physical_device_memory_properties(self.context.physical_device)
+        };
+        let memory_type_index = (0..memory_props.memory_type_count)
+            .find(|&i| {
+                let suitable = (requirements.memory_type_bits & (1 << i)) != 0;
+                let memory_type = memory_props.memory_types[i as usize];
+                suitable
+                    && memory_type
+                        .property_flags
+                        .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            })
+            .ok_or_else(|| {
+                ReactorError::new(
+                    ErrorCode::VulkanMemoryAllocation,
+                    "Failed to find memory type for shadow map",
+                )
+            })?;
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type_index);
+
+        let shadow_memory = unsafe { device.allocate_memory(&alloc_info, None)? };
+        unsafe { device.bind_image_memory(shadow_image, shadow_memory, 0)? };
+
+        let array_view_info = vk::ImageViewCreateInfo::default()
+            .image(shadow_image)
+            .view_type(vk::ImageViewType::TYPE_2D_ARRAY)
+            .format(format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::DEPTH,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: cascade_count,
+            });
+        let shadow_array_view = unsafe { device.create_image_view(&array_view_info, None)? };
+
+        let mut shadow_image_views = Vec::with_capacity(cascade_count as usize);
+        for layer in 0..cascade_count {
+            let view_info = vk::ImageViewCreateInfo::default()
+                .image(shadow_image)
+                .view_type(vk::ImageViewType::TYPE_2D)
+                .format(format)
+                .subresource_range(vk::ImageSubresourceRange {
+                    aspect_mask: vk::ImageAspectFlags::DEPTH,
+                    base_mip_level: 0,
+                    level_count: 1,
+                    base_array_layer: layer,
+                    layer_count: 1,
+                });
+            let view = unsafe { device.create_image_view(&view_info, None)? };
+            shadow_image_views.push(view);
+        }
+
+        Ok((shadow_image, shadow_memory, shadow_array_view, shadow_image_views))
+    }
 }
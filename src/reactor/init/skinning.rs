@@ -0,0 +1,79 @@
+//! `Reactor::init_skinning` — per-frame joint palette storage buffer
+//!
+//! One `CpuToGpu` storage buffer and one descriptor set per frame in flight.
+//! Skinned materials declare this layout at set 4; `upload_joint_matrices`
+//! concatenates the palettes of every skinned object into the current
+//! frame's buffer, growing it (and rewriting its set) when needed.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::ReactorResult;
+use crate::graphics::buffer::Buffer;
+use ash::vk;
+use gpu_allocator::MemoryLocation;
+
+/// Joint matrices each frame buffer starts with.
+const INITIAL_JOINT_CAPACITY: u64 = 256;
+
+impl Reactor {
+    /// Inicializa el descriptor set de paletas de joints (set = 4).
+    pub fn init_skinning(&mut self) -> ReactorResult<()> {
+        let device = self.context.ash_device();
+
+        let bindings = [vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::VERTEX)];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let skin_descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32)];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let skin_descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+
+        let layouts = vec![skin_descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(skin_descriptor_pool)
+            .set_layouts(&layouts);
+        let skin_descriptor_sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        self.skin_descriptor_layout = Some(skin_descriptor_layout);
+        self.skin_descriptor_pool = Some(skin_descriptor_pool);
+        self.skin_descriptor_sets = skin_descriptor_sets;
+        self.joint_buffers.clear();
+        for frame in 0..MAX_FRAMES_IN_FLIGHT {
+            let buffer = self.create_joint_buffer(frame, INITIAL_JOINT_CAPACITY * 64)?;
+            self.joint_buffers.push(buffer);
+        }
+        Ok(())
+    }
+
+    /// Crea el buffer de joints del frame y lo enlaza a su descriptor set.
+    pub(crate) fn create_joint_buffer(&self, frame: usize, size: u64) -> ReactorResult<Buffer> {
+        let buffer = Buffer::new(
+            &self.context,
+            self.allocator.clone(),
+            size,
+            vk::BufferUsageFlags::STORAGE_BUFFER,
+            MemoryLocation::CpuToGpu,
+        )?;
+        let buffer_info = vk::DescriptorBufferInfo::default()
+            .buffer(buffer.handle)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(self.skin_descriptor_sets[frame])
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+            .buffer_info(std::slice::from_ref(&buffer_info));
+        unsafe {
+            self.context.ash_device().update_descriptor_sets(&[write], &[]);
+        }
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,121 @@
+//! `Reactor::init_skybox` — fullscreen cubemap background for `Scene::set_skybox`
+//!
+//! One triangle on the far plane (z = 1) drawn with `LESS_OR_EQUAL` and no
+//! depth writes, after the opaque objects: it only survives where the depth
+//! buffer still holds the clear value. The view ray comes from the inverse
+//! view-projection, so the camera position never moves the sky. Each frame in
+//! flight owns a set (set = 0) that is rewritten with the scene's cubemap;
+//! naga takes no combined image samplers, so view and sampler are separate
+//! bindings, and the shaders are WGSL like the RT shadow pass.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use ash::vk;
+
+const SKYBOX_VERT: &str = r#"
+struct Output {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+@vertex
+fn main(@builtin(vertex_index) index: u32) -> Output {
+    let ndc = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    return Output(vec4<f32>(ndc, 1.0, 1.0), ndc);
+}
+"#;
+
+const SKYBOX_FRAG: &str = r#"
+struct Constants {
+    inv_view_proj: mat4x4<f32>,
+}
+
+var<push_constant> push: Constants;
+
+@group(0) @binding(0) var sky: texture_cube<f32>;
+@group(0) @binding(1) var sky_sampler: sampler;
+
+@fragment
+fn main(@location(0) ndc: vec2<f32>) -> @location(0) vec4<f32> {
+    let near_point = push.inv_view_proj * vec4<f32>(ndc, 0.0, 1.0);
+    let far_point = push.inv_view_proj * vec4<f32>(ndc, 1.0, 1.0);
+    let direction = far_point.xyz / far_point.w - near_point.xyz / near_point.w;
+    return vec4<f32>(textureSample(sky, sky_sampler, normalize(direction)).rgb, 1.0);
+}
+"#;
+
+impl Reactor {
+    /// Crea el pipeline del skybox y un descriptor set por frame en vuelo.
+    pub fn init_skybox(&mut self) -> ReactorResult<()> {
+        let device = self.context.ash_device();
+
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let skybox_descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::SAMPLER)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+        ];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let skybox_descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+        // A partir de aquí los handles son del Reactor: Drop los libera si algo falla.
+        self.skybox_descriptor_layout = Some(skybox_descriptor_layout);
+        self.skybox_descriptor_pool = Some(skybox_descriptor_pool);
+
+        let layouts = vec![skybox_descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(skybox_descriptor_pool)
+            .set_layouts(&layouts);
+        let skybox_descriptor_sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(SKYBOX_VERT, ShaderLanguage::Wgsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(SKYBOX_FRAG, ShaderLanguage::Wgsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_write: false,
+            depth_compare: vk::CompareOp::LESS_OR_EQUAL,
+            samples: self.msaa_samples,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[skybox_descriptor_layout],
+            self.scene_color_format,
+            Some(self.depth_format),
+            self.context.pipeline_cache(),
+        )?;
+
+        self.skybox_descriptor_sets = skybox_descriptor_sets;
+        self.skybox_pipeline = Some(pipeline);
+        log::info!("✅ Skybox pipeline initialized");
+        Ok(())
+    }
+}
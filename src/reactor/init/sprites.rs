@@ -0,0 +1,94 @@
+//! `Reactor::init_sprites` — textured quads for `SpriteBatch`
+//!
+//! Drawn in the same overlay pass as the text, so no depth and one sample.
+//! The vertex layout is plain `Vertex` (tint alpha in `position.z`). Each
+//! draw range binds its texture through a set (set = 0) from the slot's own
+//! pool; naga takes no combined image samplers, so view and sampler are
+//! separate bindings and the shaders are WGSL like the skybox.
+
+use super::super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use ash::vk;
+
+const SPRITE_VERT: &str = r#"
+struct Constants {
+    view_proj: mat4x4<f32>,
+}
+
+var<push_constant> push: Constants;
+
+struct Output {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+@vertex
+fn main(@location(0) position: vec3<f32>, @location(1) color: vec3<f32>, @location(2) uv: vec2<f32>) -> Output {
+    return Output(push.view_proj * vec4<f32>(position.xy, 0.0, 1.0), vec4<f32>(color, position.z), uv);
+}
+"#;
+
+const SPRITE_FRAG: &str = r#"
+@group(0) @binding(0) var sprite_texture: texture_2d<f32>;
+@group(0) @binding(1) var sprite_sampler: sampler;
+
+@fragment
+fn main(@location(0) color: vec4<f32>, @location(1) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    return textureSample(sprite_texture, sprite_sampler, uv) * color;
+}
+"#;
+
+impl Reactor {
+    /// Crea el pipeline de sprites (sin depth, 1 muestra, sobre la swapchain).
+    /// Los pools de descriptores se crean al grabar cada slot.
+    pub fn init_sprites(&mut self) -> ReactorResult<()> {
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let sprite_descriptor_layout = unsafe { self.context.device.create_descriptor_set_layout(&layout_info, None)? };
+        // A partir de aquí el layout es del Reactor: Drop lo libera si algo falla.
+        self.sprite_descriptor_layout = Some(sprite_descriptor_layout);
+
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(SPRITE_VERT, ShaderLanguage::Wgsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(SPRITE_FRAG, ShaderLanguage::Wgsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_test: false,
+            depth_write: false,
+            blend_enable: true,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[sprite_descriptor_layout],
+            self.swapchain.format,
+            None,
+            self.context.pipeline_cache(),
+        )?;
+
+        self.sprite_pipeline = Some(pipeline);
+        log::info!("✅ Sprite pipeline initialized");
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! `Reactor::init_text_overlay` — screen-space pipeline for `TextRenderer`
+//!
+//! Like the debug lines, the shaders are compiled at runtime through naga.
+//! The 8×8 font is spliced into the fragment shader as a constant array, so
+//! the overlay needs no texture upload and no descriptor set.
+
+use super::super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::graphics::text::font_glsl_array;
+use ash::vk;
+
+const TEXT_VERT: &str = r#"#version 450
+layout(location = 0) in vec3 position; // .xy = pixels, .z = glyph index
+layout(location = 1) in vec3 color;
+layout(location = 2) in vec2 uv;       // glyph texel (0..8)
+
+layout(location = 0) out vec3 fragColor;
+layout(location = 1) out vec2 fragTexel;
+layout(location = 2) flat out uint fragGlyph;
+
+layout(push_constant) uniform Constants {
+    mat4 ortho;
+} push;
+
+void main() {
+    gl_Position = push.ortho * vec4(position.xy, 0.0, 1.0);
+    fragColor = color;
+    fragTexel = uv;
+    fragGlyph = uint(position.z + 0.5);
+}
+"#;
+
+const TEXT_FRAG_HEAD: &str = r#"#version 450
+layout(location = 0) in vec3 fragColor;
+layout(location = 1) in vec2 fragTexel;
+layout(location = 2) flat in uint fragGlyph;
+layout(location = 0) out vec4 outColor;
+"#;
+
+const TEXT_FRAG_MAIN: &str = r#"
+void main() {
+    uvec2 texel = min(uvec2(fragTexel), uvec2(7u));
+    uint word = FONT[fragGlyph * 2u + texel.y / 4u];
+    uint row = (word >> ((texel.y % 4u) * 8u)) & 0xFFu;
+    if (((row >> texel.x) & 1u) == 0u) {
+        discard;
+    }
+    outColor = vec4(fragColor, 1.0);
+}
+"#;
+
+impl Reactor {
+    /// Crea el pipeline del overlay de texto (sin depth, 1 muestra, sobre la swapchain).
+    pub fn init_text_overlay(&mut self) -> ReactorResult<()> {
+        let frag_source = format!("{}{}{}", TEXT_FRAG_HEAD, font_glsl_array(), TEXT_FRAG_MAIN);
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(TEXT_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(&frag_source, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_test: false,
+            depth_write: false,
+            blend_enable: true,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[],
+            self.swapchain.format,
+            None,
+            self.context.pipeline_cache(),
+        )?;
+
+        self.text_pipeline = Some(pipeline);
+        log::info!("✅ Text overlay pipeline initialized");
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! `Reactor::init_tonemap` — HDR scene color resolved to the swapchain
+//!
+//! The scene (forward, deferred, skybox, SDF, particles, decals) renders into
+//! `scene_color_format`, RGBA16F whenever the GPU can blend and sample it, so
+//! lighting above 1.0 survives until the end of the frame. The post-process
+//! chain already tonemaps in its final pass; this pipeline covers the paths
+//! that skip it (post-process off, `draw_frame`, `draw_scene_to`): one
+//! fullscreen triangle that applies exposure, the selected `Tonemapper` and
+//! gamma. MSAA resolves into the HDR image before either of them runs. Each
+//! frame in flight owns a set (set = 0) rewritten with that frame's image;
+//! naga takes no combined image samplers, so view and sampler are separate
+//! bindings and the shaders are WGSL like the skybox.
+
+use super::super::{Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::ReactorResult;
+use crate::core::VulkanContext;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use ash::vk;
+
+/// Formato de color de la escena cuando la GPU lo admite.
+pub(crate) const HDR_SCENE_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+const TONEMAP_VERT: &str = r#"
+struct Output {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn main(@builtin(vertex_index) index: u32) -> Output {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return Output(vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0), uv);
+}
+"#;
+
+const TONEMAP_FRAG: &str = r#"
+struct Constants {
+    exposure: f32,
+    gamma: f32,
+    tonemapper: u32,
+}
+
+var<push_constant> push: Constants;
+
+@group(0) @binding(0) var scene_color: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+
+fn agx_contrast(x: vec3<f32>) -> vec3<f32> {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    return 15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2 + 0.1191 * x - 0.00232;
+}
+
+fn agx(color: vec3<f32>) -> vec3<f32> {
+    let min_ev = -12.47393;
+    let max_ev = 4.026069;
+    let agx_mat = mat3x3<f32>(
+        vec3<f32>(0.842479062253094, 0.0423282422610123, 0.0423756549057051),
+        vec3<f32>(0.0784335999999992, 0.878468636469772, 0.0784336),
+        vec3<f32>(0.0792237451477643, 0.0791661274605434, 0.879142973793104),
+    );
+    var c = clamp(log2(max(agx_mat * color, vec3<f32>(1e-10))), vec3<f32>(min_ev), vec3<f32>(max_ev));
+    c = clamp(agx_contrast((c - min_ev) / (max_ev - min_ev)), vec3<f32>(0.0), vec3<f32>(1.0));
+    let luma = dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+    return mix(vec3<f32>(luma), c, 1.08);
+}
+
+fn aces(c: vec3<f32>) -> vec3<f32> {
+    return clamp((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn uncharted2_curve(x: vec3<f32>) -> vec3<f32> {
+    let a = 0.15;
+    let b = 0.50;
+    let c = 0.10;
+    let d = 0.20;
+    let e = 0.02;
+    let f = 0.30;
+    return ((x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f)) - e / f;
+}
+
+fn uncharted2(color: vec3<f32>) -> vec3<f32> {
+    return clamp(uncharted2_curve(color * 2.0) / uncharted2_curve(vec3<f32>(11.2)), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let hdr = max(textureSample(scene_color, scene_sampler, uv).rgb * push.exposure, vec3<f32>(0.0));
+    var color: vec3<f32>;
+    switch push.tonemapper {
+        case 1u: { color = hdr / (1.0 + hdr); }
+        case 2u: { color = aces(hdr); }
+        case 3u: { color = uncharted2(hdr); }
+        default: { color = agx(hdr); }
+    }
+    return vec4<f32>(pow(color, vec3<f32>(1.0 / push.gamma)), 1.0);
+}
+"#;
+
+/// `HDR_SCENE_FORMAT` si se puede usar como attachment con blending y
+/// muestrear; si no, el del swapchain (y la escena vuelve a recortar en 1.0).
+pub(crate) fn scene_color_format(context: &VulkanContext, swapchain_format: vk::Format) -> vk::Format {
+    let props = unsafe {
+        context
+            .instance
+            .get_physical_device_format_properties(context.physical_device, HDR_SCENE_FORMAT)
+    };
+    let needed = vk::FormatFeatureFlags::COLOR_ATTACHMENT
+        | vk::FormatFeatureFlags::COLOR_ATTACHMENT_BLEND
+        | vk::FormatFeatureFlags::SAMPLED_IMAGE;
+    if props.optimal_tiling_features.contains(needed) {
+        HDR_SCENE_FORMAT
+    } else {
+        log::warn!("{:?} unsupported as a render target: scene renders in {:?} (no HDR)", HDR_SCENE_FORMAT, swapchain_format);
+        swapchain_format
+    }
+}
+
+impl Reactor {
+    /// Crea el pipeline de resolve HDR → swapchain y un descriptor set por
+    /// frame en vuelo. Sin HDR (la escena ya usa el formato del swapchain)
+    /// no hace nada.
+    pub fn init_tonemap(&mut self) -> ReactorResult<()> {
+        if !self.hdr_enabled() {
+            return Ok(());
+        }
+        let device = self.context.ash_device();
+
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT),
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+        let tonemap_descriptor_layout = unsafe { device.create_descriptor_set_layout(&layout_info, None)? };
+        // A partir de aquí los handles son del Reactor: Drop los libera si algo falla.
+        self.tonemap_descriptor_layout = Some(tonemap_descriptor_layout);
+
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::SAMPLED_IMAGE)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::SAMPLER)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+        ];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_FRAMES_IN_FLIGHT as u32);
+        let tonemap_descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None)? };
+        self.tonemap_descriptor_pool = Some(tonemap_descriptor_pool);
+
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::NEAREST)
+            .min_filter(vk::Filter::NEAREST)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .max_lod(0.0);
+        self.tonemap_sampler = Some(unsafe { device.create_sampler(&sampler_info, None)? });
+
+        let layouts = vec![tonemap_descriptor_layout; MAX_FRAMES_IN_FLIGHT];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(tonemap_descriptor_pool)
+            .set_layouts(&layouts);
+        let tonemap_descriptor_sets = unsafe { device.allocate_descriptor_sets(&alloc_info)? };
+
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(TONEMAP_VERT, ShaderLanguage::Wgsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(TONEMAP_FRAG, ShaderLanguage::Wgsl, ShaderStage::Fragment, "main")?;
+
+        let config = crate::graphics::pipeline::PipelineConfig {
+            cull_mode: vk::CullModeFlags::NONE,
+            depth_test: false,
+            depth_write: false,
+            ..Default::default()
+        };
+
+        let pipeline = crate::graphics::pipeline::Pipeline::with_config_and_cache(
+            &self.context.device,
+            None,
+            &vert.spirv,
+            &frag.spirv,
+            self.swapchain.extent.width,
+            self.swapchain.extent.height,
+            &config,
+            &[tonemap_descriptor_layout],
+            self.swapchain.format,
+            None,
+            self.context.pipeline_cache(),
+        )?;
+
+        self.tonemap_descriptor_sets = tonemap_descriptor_sets;
+        self.tonemap_pipeline = Some(pipeline);
+        log::info!("✅ HDR tonemap resolve initialized ({:?} → {:?})", self.scene_color_format, self.swapchain.format);
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Instancing: `SceneObject::instances` → buffer de instancias (binding 1).
+//!
+//! Cada frame en vuelo tiene su propio buffer `CpuToGpu` que crece a la
+//! siguiente potencia de dos. Las instancias de todos los objetos se
+//! concatenan y cada objeto dibuja su tramo vía `first_instance`, así que el
+//! buffer se enlaza una sola vez por frame.
+
+use super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::resources::material::Material;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+/// Vertex shader de instancing: la matriz de la instancia se compone con la
+/// del objeto (`push.model`). La normal usa la matriz de cofactores, que es
+/// la inversa traspuesta salvo escala y evita `inverse()` en el shader.
+const INSTANCED_VERT: &str = r#"#version 450
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 normal;
+layout(location = 2) in vec2 uv;
+layout(location = 3) in vec4 instance_col0;
+layout(location = 4) in vec4 instance_col1;
+layout(location = 5) in vec4 instance_col2;
+layout(location = 6) in vec4 instance_col3;
+
+layout(location = 0) out vec3 fragNormal;
+layout(location = 1) out vec2 fragUV;
+layout(location = 2) out vec3 fragPos;
+
+layout(push_constant) uniform Constants {
+    mat4 mvp;
+    mat4 model;
+} push;
+
+void main() {
+    mat4 instance = mat4(instance_col0, instance_col1, instance_col2, instance_col3);
+    mat4 world = push.model * instance;
+    vec3 c0 = world[0].xyz;
+    vec3 c1 = world[1].xyz;
+    vec3 c2 = world[2].xyz;
+    mat3 cofactor = mat3(cross(c1, c2), cross(c2, c0), cross(c0, c1));
+    float det_sign = dot(c0, cross(c1, c2)) < 0.0 ? -1.0 : 1.0;
+
+    gl_Position = push.mvp * instance * vec4(position, 1.0);
+    fragNormal = normalize(cofactor * normal * det_sign);
+    fragUV = uv;
+    fragPos = vec3(world * vec4(position, 1.0));
+}
+"#;
+
+impl Reactor {
+    /// Crea un material para objetos instanciados (`Scene::add_instanced_object`)
+    /// con el vertex shader de instancing incluido y el fragment indicado.
+    pub fn create_instanced_material(&self, frag_code: &[u32]) -> ReactorResult<Material> {
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(INSTANCED_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        self.build_simple_material(&vert.spirv, frag_code, true, false, false, None)
+    }
+
+    /// Sube las instancias de los objetos visibles al buffer del frame en
+    /// curso. Devuelve, por índice de objeto, el `first_instance` de su tramo.
+    pub(crate) fn upload_instances(&mut self, scene: &Scene) -> ReactorResult<Vec<u32>> {
+        self.instance_data.clear();
+        let mut first_instances = Vec::with_capacity(scene.objects.len());
+        for object in &scene.objects {
+            first_instances.push(self.instance_data.len() as u32);
+            if object.visible {
+                self.instance_data.extend_from_slice(&object.instances);
+            }
+        }
+        if self.instance_data.is_empty() {
+            return Ok(first_instances);
+        }
+
+        let needed = std::mem::size_of_val(self.instance_data.as_slice()) as u64;
+        self.instance_buffers
+            .ensure(&self.context, self.allocator.clone(), self.current_frame, needed)?
+            .write(&self.instance_data);
+        Ok(first_instances)
+    }
+
+    /// Buffer de instancias del frame en curso, si este frame subió alguna.
+    pub(crate) fn current_instance_buffer(&self) -> Option<vk::Buffer> {
+        if self.instance_data.is_empty() {
+            return None;
+        }
+        self.instance_buffers.get(self.current_frame).map(|b| b.handle)
+    }
+}
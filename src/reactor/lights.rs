@@ -0,0 +1,40 @@
+//! Luces: `LightingSystem` → `LightUniformData` (set = 3).
+//!
+//! `set_lights` sólo copia los datos en CPU; la escritura al ring uniforme del
+//! frame en curso se hace dentro de `draw_scene`, después de esperar su fence.
+
+use super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::uniform_buffer::MAX_LIGHTS;
+use crate::systems::lighting::LightingSystem;
+
+impl Reactor {
+    /// Prepara las luces que verá el próximo `draw_scene`. Las luces activas
+    /// por encima de `MAX_LIGHTS` se descartan (avisando una sola vez).
+    pub fn set_lights(&mut self, lighting: &LightingSystem) {
+        let active = lighting.light_count();
+        if active > MAX_LIGHTS && !self.light_overflow_warned {
+            log::warn!(
+                "{} active lights exceed MAX_LIGHTS ({}); extra lights are ignored",
+                active,
+                MAX_LIGHTS
+            );
+            self.light_overflow_warned = true;
+        }
+        self.light_uniform = lighting.to_gpu_data();
+    }
+
+    /// Reinicia el ring de `current_frame` (ya esperado su fence) y copia en
+    /// él las luces preparadas. Si el frame se queda sin espacio se reutiliza
+    /// el offset anterior y el ring crece al volver a este frame.
+    pub(crate) fn upload_light_uniforms(&mut self) -> ReactorResult<()> {
+        let Some(ring) = self.uniform_ring.as_mut() else {
+            return Ok(());
+        };
+        ring.begin_frame(&self.context, self.current_frame)?;
+        if let Some(offset) = ring.push(&self.light_uniform) {
+            self.light_uniform_offset = offset;
+        }
+        Ok(())
+    }
+}
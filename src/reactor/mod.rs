@@ -15,35 +15,67 @@
 //! ├── swapchain_recreate.rs — recreate_swapchain
 //! ├── resources.rs          — create_mesh / load_texture / materials
 //! ├── events.rs             — handle_event + queries
+//! ├── lights.rs             — set_lights (LightingSystem → UBO)
+//! ├── instancing.rs         — buffer de instancias + material instanciado
+//! ├── particles.rs          — material de partículas (billboard instanciado)
+//! ├── deferred.rs           — set_renderer_mode (forward / deferred)
+//! ├── stats.rs              — RenderStats del último draw_scene
+//! ├── capture.rs            — read_pixels, screenshot, capture_frame
+//! ├── window_target.rs      — RenderTargetWindow (ventanas secundarias)
+//! ├── render_target.rs      — RenderTarget (render a textura)
 //! └── draw/
 //!     ├── mod.rs            — submodule declarations
 //!     ├── vrs.rs            — apply_pixel_intelligent_vrs
-//!     ├── scene.rs          — draw_scene (escena completa)
+//!     ├── scene/render_scale.rs — escala de render dinámica (forward)
+//!     ├── scene.rs          — draw_scene (escena completa), draw_scene_to, draw_scene_to_target
 //!     ├── legacy.rs         — draw_frame (single-mesh, legado)
 //!     ├── decals.rs         — draw_screen_space_decals
+//!     ├── sdf.rs            — raymarch de SdfScene (pantalla completa)
+//!     ├── rt_shadows.rs     — sombras por ray query (BLAS/TLAS de la escena)
+//!     ├── text.rs           — overlay de texto (TextRenderer)
 //!     └── bind.rs           — bind_reactor_system_descriptors
 //! ```
 //!
 //! El usuario sigue viendo **un solo tipo `Reactor`** (monolito en la API),
 //! pero internamente cada responsabilidad vive en su archivo (modular).
 
-use crate::core::{PixelIntelligent, PixelIntelligentProfile, VrsRate, VulkanContext};
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::{FrameTimeline, PixelIntelligent, PixelIntelligentProfile, RenderScale, RenderScalePreset, RenderScaleStats, VrsRate, VulkanContext};
+use crate::graphics::descriptors::DescriptorAllocator;
 use crate::graphics::swapchain::Swapchain;
 use crate::platform::input::Input;
 use crate::raytracing::RayTracingContext;
+use crate::resources::material::Material;
+use crate::resources::mesh::Mesh;
+use crate::resources::mesh_upload::MeshUploader;
 use crate::scene::ecs::World;
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use std::sync::{Arc, Mutex};
 
+mod capture;
+mod deferred;
 mod depth;
 mod draw;
 mod events;
+mod ibl;
 mod init;
+mod instancing;
+mod lights;
 mod msaa;
+mod particles;
 mod render_pass;
+mod render_target;
 mod resources;
+mod shadows;
+mod skinning;
+mod stats;
 mod swapchain_recreate;
+mod window_target;
+
+pub use render_target::RenderTarget;
+pub use stats::RenderStats;
+pub use window_target::RenderTargetWindow;
 
 /// Número máximo de frames en vuelo simultáneamente.
 ///
@@ -57,6 +89,10 @@ pub struct Reactor {
     // ── Swapchain / GPU ──
     pub swapchain: Swapchain,
     pub allocator: Arc<Mutex<Allocator>>,
+    /// Pools de descriptor sets compartidos por materiales y uniform buffers.
+    pub descriptor_allocator: Arc<Mutex<DescriptorAllocator>>,
+    /// Hilo de subida de meshes (`create_mesh_async` / `poll_ticket`).
+    pub mesh_uploader: MeshUploader,
 
     // ── Command pool y buffers ──
     pub command_pool: vk::CommandPool,
@@ -66,7 +102,22 @@ pub struct Reactor {
     pub image_available_semaphores: Vec<vk::Semaphore>,
     pub render_finished_semaphores: Vec<vk::Semaphore>,
     pub in_flight_fences: Vec<vk::Fence>,
+    /// Slot del frame en curso. Al empezar el frame su fence ya se esperó, así
+    /// que lo indexado por él (buffers, descriptor sets) se puede reescribir.
     pub current_frame: usize,
+    /// Slots de `MAX_FRAMES_IN_FLIGHT` en uso (1 = CPU y GPU en serie).
+    pub(crate) frames_in_flight: usize,
+    /// Meshes y materiales dibujados por cada slot. Se sueltan cuando el fence
+    /// del slot señaliza, así que soltar el último `Arc` de un recurso mientras
+    /// la GPU aún lo usa es seguro.
+    pub(crate) frame_resources: Vec<Vec<(Arc<Mesh>, Arc<Material>)>>,
+    /// Meshes sustituidos por `update_mesh`. Cuando ningún slot los retiene
+    /// ya, sus buffers se reutilizan para la siguiente actualización.
+    pub(crate) retired_meshes: Vec<Arc<Mesh>>,
+    /// Valor de timeline de cada frame enviado. Mientras exista, soltar un
+    /// `Buffer`, `Image` o `Pipeline` difiere su destrucción hasta que la GPU
+    /// pase el frame en curso, sin `device_wait_idle`.
+    pub(crate) timeline: FrameTimeline,
 
     // ── Subsistemas ──
     pub input: Input,
@@ -75,6 +126,9 @@ pub struct Reactor {
 
     // ── Flags de estado del frame ──
     pub resized: bool,
+    /// Ventana a 0×0: los `draw_*` no renderizan hasta que vuelva a tener tamaño.
+    pub minimized: bool,
+    pub(crate) window_extent: vk::Extent2D,
     pub device_lost: bool,
     pub exit_requested: bool,
     pub vsync: bool,
@@ -85,6 +139,11 @@ pub struct Reactor {
     pub prev_view_projection: glam::Mat4,
     pub camera_near: f32,
     pub camera_far: f32,
+    /// Color con el que se limpia la escena antes de dibujarla (`set_clear_color`).
+    pub clear_color: glam::Vec4,
+    /// Parte del target donde cae la escena, normalizada `[x, y, w, h]`
+    /// (`set_viewport_rect`). El resto queda con `clear_color`.
+    pub(crate) viewport_rect: [f32; 4],
     pub post_process: crate::graphics::post_process::PostProcessPipeline,
     pub gbuffer: Option<crate::graphics::GBuffer>,
     pub temporal_history: Option<crate::graphics::TemporalHistory>,
@@ -93,6 +152,9 @@ pub struct Reactor {
     pub volumetric_clouds: Option<crate::graphics::post_process::VolumetricClouds>,
     pub clouds_sampler: Option<vk::Sampler>,
     pub pixel_intelligent: PixelIntelligent,
+    pub render_scale: RenderScale,
+    /// Color y depth a tamaño del swapchain donde se dibuja la escena escalada.
+    pub(crate) render_scale_targets: Option<(crate::graphics::image::Image, crate::graphics::image::Image)>,
 
     // ── Contexto Vulkan (al final: se libera al final por orden de Drop) ──
     pub context: VulkanContext,
@@ -109,8 +171,22 @@ pub struct Reactor {
     pub depth_memory: Option<vk::DeviceMemory>,
     pub depth_format: vk::Format,
 
+    // ── HDR (escena en `scene_color_format` → tonemap al swapchain) ──
+    /// Formato de color de todos los pipelines de la escena y de sus targets
+    /// (offscreen, MSAA, escala de render). Igual al del swapchain sin HDR.
+    pub scene_color_format: vk::Format,
+    pub tonemap_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) tonemap_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    pub(crate) tonemap_descriptor_pool: Option<vk::DescriptorPool>,
+    pub(crate) tonemap_descriptor_sets: Vec<vk::DescriptorSet>,
+    pub(crate) tonemap_sampler: Option<vk::Sampler>,
+
     // ── IBL (Image-Based Lighting) ──
     pub ibl_textures: Option<crate::graphics::IblTextures>,
+    /// Cubo blanco enlazado mientras no hay entorno (luz ambiente escalar).
+    pub(crate) ibl_fallback: Option<crate::graphics::IblTextures>,
+    pub(crate) ibl_brdf_lut: Option<Arc<crate::graphics::IblImage>>,
+    pub(crate) pending_environment: Option<crate::graphics::PendingIbl>,
 
     // ── Cascaded Shadow Maps (CSM) ──
     pub shadow_map: Option<crate::graphics::shadows::ShadowMap>,
@@ -124,12 +200,100 @@ pub struct Reactor {
     pub shadow_descriptor_pool: Option<vk::DescriptorPool>,
     pub shadow_descriptor_sets: Vec<vk::DescriptorSet>,
     pub shadow_uniform_buffers: Vec<crate::graphics::buffer::Buffer>,
+    /// Entradas del último shadow pass; `None` = hay que volver a dibujarlo.
+    pub(crate) shadow_cache_key: Option<crate::graphics::shadows::ShadowCacheKey>,
 
     // ── Screen-Space Decals ──
     pub decals: Vec<crate::resources::decal::Decal>,
     pub decal_pipeline: Option<crate::graphics::pipeline::Pipeline>,
     pub decal_descriptor_layout: Option<vk::DescriptorSetLayout>,
     pub decal_cube_mesh: Option<crate::resources::mesh::Mesh>,
+
+    // ── Debug lines (DebugRenderer → GPU) ──
+    pub debug_line_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) debug_line_buffers: crate::graphics::buffer::PerFrameBuffer,
+    pub(crate) debug_line_vertices: Vec<crate::resources::vertex::Vertex>,
+
+    // ── SDF (SdfScene → SSBO, raymarch a pantalla completa) ──
+    pub sdf_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) sdf_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    pub(crate) sdf_descriptor_pool: Option<vk::DescriptorPool>,
+    pub(crate) sdf_descriptor_sets: Vec<vk::DescriptorSet>,
+    pub(crate) sdf_buffers: Vec<crate::graphics::buffer::Buffer>,
+    pub(crate) sdf_primitives: Vec<crate::systems::sdf::SdfPrimitiveGpu>,
+    pub(crate) sdf_heatmap: bool,
+    pub(crate) sdf_overflow_warned: bool,
+
+    // ── Skybox (Scene::set_skybox → triángulo en el plano lejano) ──
+    pub skybox_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) skybox_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    pub(crate) skybox_descriptor_pool: Option<vk::DescriptorPool>,
+    pub(crate) skybox_descriptor_sets: Vec<vk::DescriptorSet>,
+    /// Cubemap dibujado por cada slot, retenido hasta que su fence señalice.
+    pub(crate) frame_skyboxes: Vec<Option<Arc<crate::resources::texture::Cubemap>>>,
+
+    // ── Texto (TextRenderer → overlay sobre la swapchain) ──
+    pub text_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) text_buffers: crate::graphics::buffer::PerFrameBuffer,
+    pub(crate) text_vertices: Vec<crate::resources::vertex::Vertex>,
+
+    // ── Sprites (SpriteBatch → mismo overlay que el texto) ──
+    pub sprite_pipeline: Option<crate::graphics::pipeline::Pipeline>,
+    pub(crate) sprite_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    /// Pool por slot (y cuántos sets caben), reiniciado al grabar su frame.
+    pub(crate) sprite_descriptor_pools: Vec<(vk::DescriptorPool, u32)>,
+    pub(crate) sprite_buffers: crate::graphics::buffer::PerFrameBuffer,
+    pub(crate) sprite_batch: crate::graphics::sprite_batch::SpriteBatch,
+    /// `None` = píxeles con origen arriba a la izquierda, como el texto.
+    pub sprite_camera: Option<crate::scene::camera::Camera2D>,
+    /// Texturas dibujadas por cada slot, retenidas hasta que su fence señalice.
+    pub(crate) frame_sprite_textures: Vec<Vec<Arc<crate::resources::texture::Texture>>>,
+
+    // ── Instancing (SceneObject::instances → binding 1) ──
+    pub(crate) instance_buffers: crate::graphics::buffer::PerFrameBuffer,
+    pub(crate) instance_data: Vec<crate::resources::vertex::InstanceData>,
+    /// Objetos (por `id`) con instancias y material sin instancing, ya avisados.
+    pub(crate) instancing_mismatch_warned: std::collections::HashSet<u64>,
+
+    // ── Skinning (SceneObject::joint_matrices → SSBO, set = 4) ──
+    pub skin_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    pub(crate) skin_descriptor_pool: Option<vk::DescriptorPool>,
+    pub(crate) skin_descriptor_sets: Vec<vk::DescriptorSet>,
+    pub(crate) joint_buffers: Vec<crate::graphics::buffer::Buffer>,
+    pub(crate) joint_data: Vec<[f32; 16]>,
+    /// Ya se avisó de que los objetos skinned no proyectan sombra.
+    pub(crate) skinned_shadow_warned: bool,
+
+    // ── Luces (LightingSystem → UBO, set = 3) ──
+    pub light_descriptor_layout: Option<vk::DescriptorSetLayout>,
+    pub light_descriptor_pool: Option<vk::DescriptorPool>,
+    pub light_descriptor_sets: Vec<vk::DescriptorSet>,
+    pub(crate) light_uniform: crate::graphics::uniform_buffer::LightUniformData,
+    /// Offset del bloque de luces de este frame dentro de `uniform_ring`.
+    pub(crate) light_uniform_offset: u32,
+    /// Datos uniformes por frame (luces por ahora), con offsets dinámicos.
+    pub(crate) uniform_ring: Option<crate::graphics::uniform_buffer::DynamicUniformRing>,
+    pub(crate) light_overflow_warned: bool,
+
+    // ── Deferred (G-Buffer MRT + pasada de iluminación) ──
+    pub(crate) renderer_mode: crate::app::config::RendererMode,
+    pub(crate) deferred: Option<init::DeferredResources>,
+
+    // ── Sombras por ray tracing (BLAS/TLAS de la escena + ray query) ──
+    pub(crate) rt_shadows: Option<init::RtShadowResources>,
+    pub(crate) rt_shadows_enabled: bool,
+    pub(crate) render_stats: RenderStats,
+    /// Inicio del `draw_scene` anterior, para `RenderStats::frame_time`.
+    pub(crate) last_draw_at: Option<std::time::Instant>,
+
+    // ── Headless (swapchain offscreen) ──
+    /// Imagen del último frame enviado en modo headless (la que lee `read_pixels`).
+    pub(crate) last_rendered_image: Option<u32>,
+
+    // ── Capturas (screenshot / capture_frame) ──
+    pub(crate) capture_request: Option<capture::CaptureTarget>,
+    pub(crate) capture_in_flight: Option<capture::InFlightCapture>,
+    pub(crate) captured_frame: Option<image::RgbaImage>,
 }
 
 impl Reactor {
@@ -144,6 +308,59 @@ impl Reactor {
     pub fn pixel_intelligent_enabled(&self) -> bool {
         self.pixel_intelligent.enabled && self.context.supports_fragment_shading_rate()
     }
+
+    /// Activa la escala de render dinámica (`core::render_scale`). Solo el
+    /// path forward con post-proceso y sin MSAA la aplica.
+    pub fn set_render_scale_enabled(&mut self, enabled: bool) {
+        self.render_scale.enabled = enabled;
+    }
+
+    /// Cambia el preset en caliente: no recrea el swapchain ni los targets.
+    pub fn set_render_scale_preset(&mut self, preset: RenderScalePreset) {
+        self.render_scale.set_preset(preset);
+    }
+
+    /// Resolución del último frame y ahorro estimado de trabajo por píxel.
+    pub fn render_scale_stats(&self) -> RenderScaleStats {
+        self.render_scale.stats(self.swapchain.extent)
+    }
+
+    /// Frames que la CPU puede grabar por delante de la GPU.
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames_in_flight
+    }
+
+    /// Cambia los frames en vuelo (1..=`MAX_FRAMES_IN_FLIGHT`). Los recursos
+    /// por frame ya existen para todos los slots, así que no hace falta
+    /// esperar a la GPU: cada slot sigue esperando su propio fence.
+    pub fn set_frames_in_flight(&mut self, count: usize) -> ReactorResult<()> {
+        if count == 0 || count > MAX_FRAMES_IN_FLIGHT {
+            return Err(ReactorError::new(
+                ErrorCode::InvalidParameter,
+                format!("Frames in flight must be in 1..={} (got {})", MAX_FRAMES_IN_FLIGHT, count),
+            ));
+        }
+        self.frames_in_flight = count;
+        self.current_frame %= count;
+        Ok(())
+    }
+
+    /// Timeline de frames: valores enviados/completados y destrucciones pendientes.
+    pub fn frame_timeline(&self) -> &FrameTimeline {
+        &self.timeline
+    }
+
+    /// Activa la comprobación de recursos destruidos en el acto (p. ej.
+    /// `Buffer::destroy`) mientras un frame en vuelo los usa: se registra el
+    /// error y en debug salta un `debug_assert!`. Activa por defecto en debug.
+    pub fn set_in_flight_tracking(&mut self, enabled: bool) {
+        self.timeline.set_in_flight_tracking(enabled);
+    }
+
+    /// Aciertos/fallos del pipeline cache persistente y su tamaño en bytes.
+    pub fn pipeline_cache_stats(&self) -> crate::core::pipeline_cache::PipelineCacheStats {
+        self.context.pipeline_cache_stats()
+    }
 }
 
 impl Drop for Reactor {
@@ -152,8 +369,15 @@ impl Drop for Reactor {
             // Esperar a que la GPU termine cualquier trabajo pendiente.
             let _ = self.context.device.device_wait_idle();
 
+            if let Some(deferred) = self.deferred.take() {
+                deferred.destroy(self.context.ash_device());
+            }
+            if let Some(rt_shadows) = self.rt_shadows.take() {
+                rt_shadows.destroy(self.context.ash_device());
+            }
             self.gbuffer = None;
             self.temporal_history = None;
+            self.render_scale_targets = None;
             self.hiz_pyramid = None;
             self.ssgi_hiz = None;
             self.volumetric_clouds = None;
@@ -166,6 +390,69 @@ impl Drop for Reactor {
                 self.context.device.destroy_descriptor_set_layout(layout, None);
             }
             self.decal_pipeline = None;
+            self.debug_line_buffers.clear();
+            self.debug_line_pipeline = None;
+            self.sdf_pipeline = None;
+            self.skybox_pipeline = None;
+            self.frame_skyboxes.clear();
+            self.pending_environment = None;
+            self.ibl_textures = None;
+            self.ibl_fallback = None;
+            self.ibl_brdf_lut = None;
+            if let Some(pool) = self.skybox_descriptor_pool.take() {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.skybox_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
+            self.sdf_buffers.clear();
+            if let Some(pool) = self.sdf_descriptor_pool.take() {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.sdf_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
+            self.text_buffers.clear();
+            self.text_pipeline = None;
+            self.sprite_buffers.clear();
+            self.sprite_pipeline = None;
+            self.frame_sprite_textures.clear();
+            for (pool, _) in self.sprite_descriptor_pools.drain(..) {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.sprite_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
+            self.tonemap_pipeline = None;
+            if let Some(pool) = self.tonemap_descriptor_pool.take() {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.tonemap_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
+            if let Some(sampler) = self.tonemap_sampler.take() {
+                self.context.device.destroy_sampler(sampler, None);
+            }
+            self.instance_buffers.clear();
+            self.capture_in_flight = None;
+
+            // ── Skinning ──
+            self.joint_buffers.clear();
+            if let Some(pool) = self.skin_descriptor_pool.take() {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.skin_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
+
+            // ── Luces ──
+            self.uniform_ring = None;
+            if let Some(pool) = self.light_descriptor_pool.take() {
+                self.context.device.destroy_descriptor_pool(pool, None);
+            }
+            if let Some(layout) = self.light_descriptor_layout.take() {
+                self.context.device.destroy_descriptor_set_layout(layout, None);
+            }
 
             // ── Shadows ──
             if let Some(pool) = self.shadow_descriptor_pool.take() {
@@ -0,0 +1,90 @@
+//! Partículas: `ParticleSystem::fill_instances` → un draw instanciado por sistema.
+//!
+//! Cada sistema es un único `SceneObject` con un quad compartido y el
+//! material de partículas; sus instancias (`InstanceData`, con color) se
+//! reescriben cada frame, así que 10k partículas cuestan un draw call.
+
+use super::Reactor;
+use crate::core::error::ReactorResult;
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::resources::material::{material_uniform_binding, Material, MaterialBuilder};
+
+/// Billboard: la escala y el giro de la instancia están en el plano del quad,
+/// que se orienta con los ejes de cámara sacados de las filas x/y de
+/// `push.mvp`. El objeto se dibuja con `transform` identidad, así que
+/// `push.mvp` es la view-projection.
+const PARTICLE_VERT: &str = r#"#version 450
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 normal;
+layout(location = 2) in vec2 uv;
+layout(location = 3) in vec4 instance_col0;
+layout(location = 4) in vec4 instance_col1;
+layout(location = 5) in vec4 instance_col2;
+layout(location = 6) in vec4 instance_col3;
+layout(location = 7) in vec4 instance_color;
+
+layout(location = 0) out vec4 fragColor;
+layout(location = 1) out vec2 fragUV;
+
+layout(push_constant) uniform Constants {
+    mat4 mvp;
+    mat4 model;
+} push;
+
+void main() {
+    vec3 right = normalize(vec3(push.mvp[0][0], push.mvp[1][0], push.mvp[2][0]));
+    vec3 up = normalize(vec3(push.mvp[0][1], push.mvp[1][1], push.mvp[2][1]));
+    vec2 corner = mat2(instance_col0.xy, instance_col1.xy) * position.xy;
+    vec3 world = instance_col3.xyz + right * corner.x + up * corner.y;
+
+    gl_Position = push.mvp * vec4(world, 1.0);
+    fragColor = instance_color;
+    fragUV = uv;
+}
+"#;
+
+/// Disco suave con el color de la instancia. Alpha 0 a la salida: con la
+/// mezcla premultiplicada (`ONE`, `ONE_MINUS_SRC_ALPHA`) eso es aditivo.
+const PARTICLE_FRAG: &str = r#"#version 450
+layout(location = 0) in vec4 fragColor;
+layout(location = 1) in vec2 fragUV;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    float falloff = clamp(1.0 - length(fragUV - 0.5) * 2.0, 0.0, 1.0);
+    float alpha = fragColor.a * falloff * falloff;
+    outColor = vec4(fragColor.rgb * alpha, 0.0);
+}
+"#;
+
+impl Reactor {
+    /// Material de partículas: billboard instanciado, color por instancia y
+    /// mezcla aditiva sin escribir depth. Va con `Primitives::quad`.
+    pub fn create_particle_material(&self) -> ReactorResult<Material> {
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(PARTICLE_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(PARTICLE_FRAG, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")?;
+
+        let material_layout = self.descriptor_allocator.lock().unwrap().layout(&[material_uniform_binding()])?;
+        let mat = MaterialBuilder::new(vert.spirv, frag.spirv)
+            .msaa(self.msaa_samples)
+            .fragment_shading_rate(self.context.supports_fragment_shading_rate())
+            .descriptor_layout(material_layout) // set = 0 (UBO del material)
+            .instanced()
+            .transparent()
+            .no_cull()
+            .build(
+                &self.context,
+                None,
+                self.swapchain.extent.width,
+                self.swapchain.extent.height,
+                self.scene_color_format,
+                Some(self.depth_format),
+            )?;
+        let mat = self.with_material_uniforms(mat, material_layout)?;
+
+        self.label_material(&mat);
+        Ok(mat)
+    }
+}
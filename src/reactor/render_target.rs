@@ -0,0 +1,130 @@
+//! Render a textura: cámaras secundarias (minimapa, retrovisor, monitor de
+//! seguridad) que dibujan la escena en una `Texture` en vez de en pantalla.
+//!
+//! Cada `RenderTarget` tiene su color (la textura), depth, MSAA y color HDR
+//! con la resolución pedida; device, pipelines y recursos de la escena son los
+//! del `Reactor`, como en `RenderTargetWindow`. Se dibuja con
+//! `Reactor::draw_scene_to_target` y después `texture()` se usa como
+//! cualquier otra textura (`create_textured_material`).
+//!
+//! La textura tiene el formato del swapchain principal, porque el tonemap y
+//! los pipelines ya están creados para él. Mismas limitaciones que las
+//! ventanas secundarias: path forward, sin post-proceso ni texto.
+
+use super::{depth, msaa, Reactor};
+use crate::core::error::{ReactorError, ReactorResult};
+use crate::core::VulkanContext;
+use crate::graphics::image::Image;
+use crate::graphics::swapchain::Swapchain;
+use crate::resources::texture::Texture;
+use ash::vk;
+use std::sync::Arc;
+
+pub struct RenderTarget {
+    /// Se sampléa desde los materiales; el draw escribe en su imagen.
+    texture: Arc<Texture>,
+    /// Vista de `texture` como swapchain de una imagen, para prestarla al
+    /// pase de geometría.
+    pub(crate) swapchain: Swapchain,
+    pub(crate) msaa_image: Option<vk::Image>,
+    pub(crate) msaa_image_view: Option<vk::ImageView>,
+    pub(crate) msaa_memory: Option<vk::DeviceMemory>,
+    pub(crate) depth_image: Option<vk::Image>,
+    pub(crate) depth_image_view: Option<vk::ImageView>,
+    pub(crate) depth_memory: Option<vk::DeviceMemory>,
+    /// Color de la escena antes del tonemap (vacío sin HDR).
+    pub(crate) hdr_images: Vec<Image>,
+    context: VulkanContext,
+}
+
+impl Reactor {
+    /// Crea un target de `width` x `height` para `draw_scene_to_target`.
+    pub fn create_render_target(&self, width: u32, height: u32) -> ReactorResult<RenderTarget> {
+        if width == 0 || height == 0 {
+            return Err(ReactorError::invalid_parameter(format!(
+                "Render target size must be non-zero, got {}x{}",
+                width, height
+            )));
+        }
+        let context = self.context.clone();
+        let texture = Texture::render_target(&context, self.allocator.clone(), width, height, self.swapchain.format)?;
+        let swapchain = Swapchain::borrowed(&context, &texture.image);
+
+        let (msaa_image, msaa_image_view, msaa_memory) = if self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
+            let (image, view, memory) =
+                msaa::create_msaa_resources(&context, width, height, self.scene_color_format, self.msaa_samples)?;
+            (Some(image), Some(view), Some(memory))
+        } else {
+            (None, None, None)
+        };
+        let mut target = RenderTarget {
+            texture: Arc::new(texture),
+            swapchain,
+            msaa_image,
+            msaa_image_view,
+            msaa_memory,
+            depth_image: None,
+            depth_image_view: None,
+            depth_memory: None,
+            hdr_images: Vec::new(),
+            context,
+        };
+        let (image, view, memory) =
+            depth::create_depth_resources(&target.context, width, height, self.depth_format, self.msaa_samples)?;
+        target.depth_image = Some(image);
+        target.depth_image_view = Some(view);
+        target.depth_memory = Some(memory);
+        if self.hdr_enabled() {
+            target.hdr_images.push(Image::new(
+                &target.context,
+                self.allocator.clone(),
+                width,
+                height,
+                self.scene_color_format,
+                vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED,
+                vk::ImageAspectFlags::COLOR,
+                1,
+            )?);
+        }
+        Ok(target)
+    }
+}
+
+impl RenderTarget {
+    /// Resultado del último draw. Los materiales creados con ella guardan su
+    /// view y su sampler: el target debe vivir mientras se usen.
+    pub fn texture(&self) -> &Arc<Texture> {
+        &self.texture
+    }
+
+    pub fn extent(&self) -> vk::Extent2D {
+        self.swapchain.extent
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.swapchain.aspect_ratio()
+    }
+
+    pub(crate) fn image(&self) -> vk::Image {
+        self.texture.image.handle
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.context.device.device_wait_idle();
+        }
+        self.hdr_images.clear();
+        let device = self.context.ash_device();
+        unsafe {
+            if let Some(view) = self.depth_image_view.take() { device.destroy_image_view(view, None); }
+            if let Some(image) = self.depth_image.take() { device.destroy_image(image, None); }
+            if let Some(memory) = self.depth_memory.take() { device.free_memory(memory, None); }
+            if let Some(view) = self.msaa_image_view.take() { device.destroy_image_view(view, None); }
+            if let Some(image) = self.msaa_image.take() { device.destroy_image(image, None); }
+            if let Some(memory) = self.msaa_memory.take() { device.free_memory(memory, None); }
+        }
+        self.swapchain.destroy(self.context.ash_device());
+    }
+}
@@ -5,25 +5,124 @@
 
 use super::Reactor;
 use crate::core::error::ReactorResult;
-use crate::resources::material::Material;
-use crate::resources::mesh::Mesh;
-use crate::resources::texture::Texture;
+use crate::graphics::descriptors::{DescriptorBinding, PooledDescriptorSet};
+use crate::graphics::uniform_buffer::MaterialUniformData;
+use crate::resources::material::{material_uniform_binding, Material};
+use crate::resources::mesh::{Mesh, MeshUsage};
+use crate::resources::mesh_upload::MeshTicket;
+use crate::resources::pbr_material::{AlphaMode, PBRMaterial};
+use crate::resources::texture::{Cubemap, Texture, TextureOptions};
 use crate::resources::vertex::Vertex;
+use std::sync::Arc;
+
+/// Meshes retirados sin uso que `update_mesh` conserva para reutilizar.
+const MAX_IDLE_RETIRED_MESHES: usize = 8;
 
 impl Reactor {
     /// Crea un mesh GPU a partir de vértices e índices.
     pub fn create_mesh(&self, vertices: &[Vertex], indices: &[u32]) -> ReactorResult<Mesh> {
-        Mesh::new(&self.context, &self.allocator, vertices, indices)
+        let mesh = Mesh::new(&self.context, &self.allocator, vertices, indices)?;
+        mesh.set_auto_debug_name(&self.context, "mesh");
+        Ok(mesh)
+    }
+
+    /// Mesh en memoria visible desde CPU (`MeshUsage::Dynamic`), pensado para
+    /// reescribirse con `update_mesh` cada pocos frames.
+    pub fn create_dynamic_mesh(&self, vertices: &[Vertex], indices: &[u32]) -> ReactorResult<Mesh> {
+        let mesh = Mesh::new_dynamic(&self.context, &self.allocator, vertices, indices)?;
+        mesh.set_auto_debug_name(&self.context, "mesh_dynamic");
+        Ok(mesh)
+    }
+
+    /// Reemplaza vértices e índices de `mesh` sin `device_wait_idle`.
+    ///
+    /// Si algún frame en vuelo (u otro `Arc`) sigue usando `mesh`, los datos
+    /// se escriben en un mesh retirado que ya nadie retiene (o en uno nuevo)
+    /// y `mesh` pasa a apuntar a él; el anterior queda retirado hasta que su
+    /// frame termine. Sólo se actualiza este `Arc`: los demás que compartían
+    /// el mesh siguen viendo los datos viejos.
+    pub fn update_mesh(&mut self, mesh: &mut Arc<Mesh>, vertices: &[Vertex], indices: &[u32]) -> ReactorResult<()> {
+        if let Some(owned) = Arc::get_mut(mesh) {
+            owned.update_vertices(&self.context, &self.allocator, vertices)?;
+            return owned.update_indices(&self.context, &self.allocator, indices);
+        }
+
+        let usage = mesh.usage;
+        let idle = self.retired_meshes.iter().position(|retired| {
+            Arc::strong_count(retired) == 1 && retired.usage == usage && retired.fits(vertices, indices)
+        });
+        let next = match idle {
+            Some(index) => {
+                let mut retired = self.retired_meshes.swap_remove(index);
+                let owned = Arc::get_mut(&mut retired).expect("idle retired mesh is unique");
+                owned.update_vertices(&self.context, &self.allocator, vertices)?;
+                owned.update_indices(&self.context, &self.allocator, indices)?;
+                retired
+            }
+            None => Arc::new(match usage {
+                MeshUsage::Static => self.create_mesh(vertices, indices)?,
+                MeshUsage::Dynamic => self.create_dynamic_mesh(vertices, indices)?,
+            }),
+        };
+        self.retired_meshes.push(std::mem::replace(mesh, next));
+
+        // Los retirados que nadie retiene por encima del límite se liberan.
+        let mut idle_kept = 0;
+        self.retired_meshes.retain(|retired| {
+            if Arc::strong_count(retired) > 1 {
+                return true;
+            }
+            idle_kept += 1;
+            idle_kept <= MAX_IDLE_RETIRED_MESHES
+        });
+        Ok(())
+    }
+
+    /// Igual que `create_mesh`, pero las copias a GPU se graban en un hilo
+    /// aparte y no bloquean el frame. Recoge el mesh con `poll_ticket`.
+    pub fn create_mesh_async(&self, vertices: &[Vertex], indices: &[u32]) -> MeshTicket {
+        self.mesh_uploader.create_mesh(vertices, indices)
+    }
+
+    /// `Some` cuando la subida de `ticket` terminó (con el mesh o su error);
+    /// `None` mientras sigue en curso. Cada ticket se canjea una sola vez.
+    pub fn poll_ticket(&self, ticket: MeshTicket) -> Option<ReactorResult<Mesh>> {
+        self.mesh_uploader.poll(ticket)
     }
 
     /// Carga una textura desde fichero (PNG/JPG/BMP/HDR/…).
     pub fn load_texture(&self, path: &str) -> ReactorResult<Texture> {
-        Texture::from_file(&self.context, self.allocator.clone(), path, true)
+        let texture = Texture::from_file(&self.context, self.allocator.clone(), path, true)?;
+        self.label_texture(&texture, path);
+        Ok(texture)
+    }
+
+    /// Carga una textura eligiendo filtrado, wrap, anisotropía, sRGB y mips.
+    pub fn load_texture_with(&self, path: &str, options: &TextureOptions) -> ReactorResult<Texture> {
+        let texture = Texture::from_file_with(&self.context, self.allocator.clone(), path, options)?;
+        self.label_texture(&texture, path);
+        Ok(texture)
+    }
+
+    /// Carga un cubemap de seis caras cuadradas en orden +X, -X, +Y, -Y, +Z, -Z.
+    pub fn load_cubemap(&self, faces: [&str; 6], options: &TextureOptions) -> ReactorResult<Cubemap> {
+        let cubemap = Cubemap::from_files(&self.context, self.allocator.clone(), faces, options)?;
+        self.label_cubemap(&cubemap, faces[0]);
+        Ok(cubemap)
+    }
+
+    /// Carga un panorama equirectangular y lo convierte en caras de `face_size` píxeles.
+    pub fn load_cubemap_equirect(&self, path: &str, face_size: u32, options: &TextureOptions) -> ReactorResult<Cubemap> {
+        let cubemap = Cubemap::from_equirect_file(&self.context, self.allocator.clone(), path, face_size, options)?;
+        self.label_cubemap(&cubemap, path);
+        Ok(cubemap)
     }
 
     /// Carga una textura lineal desde fichero (normalmente mapas de datos PBR).
     pub fn load_texture_linear(&self, path: &str) -> ReactorResult<Texture> {
-        Texture::from_file_linear(&self.context, self.allocator.clone(), path, true)
+        let texture = Texture::from_file_linear(&self.context, self.allocator.clone(), path, true)?;
+        self.label_texture(&texture, path);
+        Ok(texture)
     }
 
     /// Carga una textura desde bytes embebidos.
@@ -36,50 +135,159 @@ impl Reactor {
         Texture::solid_color(&self.context, self.allocator.clone(), r, g, b, a)
     }
 
-    /// Crea un material sin texturas usando *Dynamic Rendering*.
+    /// Crea un material sin texturas usando *Dynamic Rendering*. Sus
+    /// parámetros (`Material::set_params`) llegan al shader por el UBO de
+    /// set 0, binding `MATERIAL_UNIFORM_BINDING`.
     pub fn create_material(&self, vert_code: &[u32], frag_code: &[u32]) -> ReactorResult<Material> {
+        self.build_simple_material(vert_code, frag_code, false, false, false, None)
+    }
+
+    /// Igual que `create_material`, con parámetros iniciales.
+    pub fn create_material_with_params(
+        &self,
+        vert_code: &[u32],
+        frag_code: &[u32],
+        params: MaterialUniformData,
+    ) -> ReactorResult<Material> {
+        let mat = self.build_simple_material(vert_code, frag_code, false, false, false, None)?;
+        mat.set_params(params);
+        Ok(mat)
+    }
+
+    /// Convierte una descripción `PBRMaterial` en un material real: color,
+    /// metallic, roughness, AO y emisivo van a su UBO. Con
+    /// `AlphaMode::Blend` el material es transparente.
+    pub fn create_material_pbr(&self, vert_code: &[u32], frag_code: &[u32], pbr: &PBRMaterial) -> ReactorResult<Material> {
+        let transparent = pbr.alpha_mode == AlphaMode::Blend;
+        let mat = self.build_simple_material(vert_code, frag_code, false, false, transparent, None)?;
+        mat.set_params(MaterialUniformData::from_pbr(pbr));
+        Ok(mat)
+    }
+
+    /// Igual que `create_material`, pero el pipeline consume el stream de
+    /// `InstanceData` (binding 1) para objetos con `SceneObject::instances`,
+    /// o vértices `VertexSkinned` y la paleta de joints (set = 4) si `skinned`.
+    /// Con `transparent` mezcla con alpha premultiplicado. Con `base_color` el
+    /// set 0 añade la textura (binding 0) y su sampler (binding 1) por separado.
+    pub(crate) fn build_simple_material(
+        &self,
+        vert_code: &[u32],
+        frag_code: &[u32],
+        instanced: bool,
+        skinned: bool,
+        transparent: bool,
+        base_color: Option<&Texture>,
+    ) -> ReactorResult<Material> {
         use crate::resources::material::MaterialBuilder;
 
-        let empty_layout = unsafe {
-            self.context
-                .device
-                .create_descriptor_set_layout(
-                    &ash::vk::DescriptorSetLayoutCreateInfo::default(),
-                    None,
-                )
-                .map_err(|e| {
-                    crate::core::error::ReactorError::with_source(
-                        crate::core::error::ErrorCode::VulkanPipelineCreation,
-                        "Failed to create empty descriptor set layout for simple material",
-                        e,
-                    )
-                })?
+        let skin_layout = match (skinned, self.skin_descriptor_layout) {
+            (false, _) => None,
+            (true, Some(layout)) => Some(layout),
+            (true, None) => {
+                return Err(crate::core::error::ReactorError::new(
+                    crate::core::error::ErrorCode::NotInitialized,
+                    "Skinned materials need init_skinning",
+                ))
+            }
+        };
+
+        let (empty_layout, material_layout) = {
+            let mut descriptors = self.descriptor_allocator.lock().unwrap();
+            let fragment_binding = |binding, descriptor_type| DescriptorBinding {
+                binding,
+                descriptor_type,
+                count: 1,
+                stage_flags: ash::vk::ShaderStageFlags::FRAGMENT,
+            };
+            let material_layout = if base_color.is_some() {
+                descriptors.layout(&[
+                    fragment_binding(0, ash::vk::DescriptorType::SAMPLED_IMAGE),
+                    fragment_binding(1, ash::vk::DescriptorType::SAMPLER),
+                    material_uniform_binding(),
+                ])?
+            } else {
+                descriptors.layout(&[material_uniform_binding()])?
+            };
+            (descriptors.layout(&[])?, material_layout)
         };
 
         let mut builder = MaterialBuilder::new(vert_code.to_vec(), frag_code.to_vec())
             .msaa(self.msaa_samples)
             .fragment_shading_rate(self.context.supports_fragment_shading_rate())
-            .descriptor_layout(empty_layout); // set = 0
+            .descriptor_layout(material_layout); // set = 0 (UBO del material)
+        if instanced {
+            builder = builder.instanced();
+        }
+        if transparent {
+            builder = builder.transparent();
+        }
 
         if let Some(shadow_layout) = self.shadow_descriptor_layout {
             builder = builder
                 .has_shadow_set(true)
                 .descriptor_layout(empty_layout)   // set = 1 (dummy padding so shadow lands at set 2)
                 .descriptor_layout(shadow_layout); // set = 2 (Sombras)
+        } else if self.light_descriptor_layout.is_some() || skinned {
+            builder = builder
+                .descriptor_layout(empty_layout)  // set = 1 (padding)
+                .descriptor_layout(empty_layout); // set = 2 (padding)
+        }
+
+        if let Some(light_layout) = self.light_descriptor_layout {
+            builder = builder
+                .has_light_set(true)
+                .descriptor_layout(light_layout); // set = 3 (Luces)
+        } else if skinned {
+            builder = builder.descriptor_layout(empty_layout); // set = 3 (padding)
+        }
+
+        if let Some(skin_layout) = skin_layout {
+            builder = builder
+                .skinned()
+                .descriptor_layout(skin_layout); // set = 4 (Joints)
         }
 
-        let mut mat = builder.build(
+        let mat = builder.build(
             &self.context,
             None,
             self.swapchain.extent.width,
             self.swapchain.extent.height,
-            self.swapchain.format,
+            self.scene_color_format,
             Some(self.depth_format),
         )?;
+        let mut mat = self.with_material_uniforms(mat, material_layout)?;
+        if let (Some(texture), Some(set)) = (base_color, mat.descriptor_set) {
+            let image_info = ash::vk::DescriptorImageInfo::default()
+                .image_layout(ash::vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .image_view(texture.view());
+            let sampler_info = ash::vk::DescriptorImageInfo::default().sampler(texture.sampler_handle());
+            let writes = [
+                ash::vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(0)
+                    .descriptor_type(ash::vk::DescriptorType::SAMPLED_IMAGE)
+                    .image_info(std::slice::from_ref(&image_info)),
+                ash::vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(1)
+                    .descriptor_type(ash::vk::DescriptorType::SAMPLER)
+                    .image_info(std::slice::from_ref(&sampler_info)),
+            ];
+            unsafe { self.context.device.update_descriptor_sets(&writes, &[]) };
+            mat.uniform_only_set = false;
+        }
 
-        mat.descriptor_layout = Some(empty_layout);
-        mat.device = Some(self.context.device.clone());
+        self.label_material(&mat);
+        Ok(mat)
+    }
 
+    /// Asigna a `mat` un set 0 propio con `layout` y le conecta el UBO.
+    pub(super) fn with_material_uniforms(&self, mat: Material, layout: ash::vk::DescriptorSetLayout) -> ReactorResult<Material> {
+        let mut mat = mat.with_pooled_set(PooledDescriptorSet::new(&self.descriptor_allocator, layout)?);
+        if let Some(set) = mat.descriptor_set {
+            mat.attach_uniforms(&self.context, self.allocator.clone(), set)?;
+        }
+        mat.uniform_only_set = true;
         Ok(mat)
     }
 
@@ -93,46 +301,17 @@ impl Reactor {
         use crate::resources::material::MaterialBuilder;
         use ash::vk;
 
-        let sampler_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(0)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let bindings = [sampler_binding];
-        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
-            .bindings(&bindings)
-            .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL);
-
-        let descriptor_layout = unsafe {
-            self.context
-                .device
-                .create_descriptor_set_layout(&layout_info, None)?
-        };
-
-        let pool_size = vk::DescriptorPoolSize::default()
-            .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1);
-
-        let pool_sizes = [pool_size];
-        let pool_info = vk::DescriptorPoolCreateInfo::default()
-            .pool_sizes(&pool_sizes)
-            .max_sets(1)
-            .flags(vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND);
-
-        let descriptor_pool = unsafe {
-            self.context.device.create_descriptor_pool(&pool_info, None)?
-        };
-
-        let layouts = [descriptor_layout];
-        let alloc_info = vk::DescriptorSetAllocateInfo::default()
-            .descriptor_pool(descriptor_pool)
-            .set_layouts(&layouts);
-
-        let descriptor_sets = unsafe {
-            self.context.device.allocate_descriptor_sets(&alloc_info)?
-        };
-        let descriptor_set = descriptor_sets[0];
+        let descriptor_layout = self.descriptor_allocator.lock().unwrap().layout(&[
+            DescriptorBinding {
+                binding: 0,
+                descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                count: 1,
+                stage_flags: vk::ShaderStageFlags::FRAGMENT,
+            },
+            material_uniform_binding(),
+        ])?;
+        let pooled_set = PooledDescriptorSet::new(&self.descriptor_allocator, descriptor_layout)?;
+        let descriptor_set = pooled_set.handle();
 
         let image_info = vk::DescriptorImageInfo::default()
             .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
@@ -169,15 +348,15 @@ impl Reactor {
                 None,
                 self.swapchain.extent.width,
                 self.swapchain.extent.height,
-                self.swapchain.format,
+                self.scene_color_format,
                 Some(self.depth_format),
             )?;
 
         mat.descriptor_set = Some(descriptor_set);
-        mat.descriptor_pool = Some(descriptor_pool);
-        mat.descriptor_layout = Some(descriptor_layout);
-        mat.device = Some(self.context.device.clone());
+        mat.pooled_set = Some(pooled_set);
+        mat.attach_uniforms(&self.context, self.allocator.clone(), descriptor_set)?;
 
+        self.label_material(&mat);
         Ok(mat)
     }
 
@@ -194,28 +373,13 @@ impl Reactor {
     ) -> ReactorResult<Material> {
         use crate::resources::material::MaterialBuilder;
 
-        // Crear un descriptor set layout vacío para set=0 (el material no tiene
-        // texturas propias todavía — el color llega por push constants).
-        let empty_layout = unsafe {
-            self.context
-                .device
-                .create_descriptor_set_layout(
-                    &ash::vk::DescriptorSetLayoutCreateInfo::default(),
-                    None,
-                )
-                .map_err(|e| {
-                    crate::core::error::ReactorError::with_source(
-                        crate::core::error::ErrorCode::VulkanPipelineCreation,
-                        "Failed to create empty descriptor set layout for IBL material",
-                        e,
-                    )
-                })?
-        };
+        // Set 0 solo con el UBO del material: no tiene texturas propias.
+        let material_layout = self.descriptor_allocator.lock().unwrap().layout(&[material_uniform_binding()])?;
 
         let mut builder = MaterialBuilder::new(vert_code.to_vec(), frag_code.to_vec())
             .msaa(self.msaa_samples)
             .fragment_shading_rate(self.context.supports_fragment_shading_rate())
-            .descriptor_layout(empty_layout)    // set = 0 (vacío)
+            .descriptor_layout(material_layout) // set = 0 (UBO del material)
             .descriptor_layout(ibl_set_layout); // set = 1 (IBL textures)
 
         if let Some(shadow_layout) = self.shadow_descriptor_layout {
@@ -224,21 +388,19 @@ impl Reactor {
                 .descriptor_layout(shadow_layout); // set = 2 (Sombras)
         }
 
-        let mut mat = builder
+        let mat = builder
             .uses_ibl(true)
             .build(
                 &self.context,
                 None,
                 self.swapchain.extent.width,
                 self.swapchain.extent.height,
-                self.swapchain.format,
+                self.scene_color_format,
                 Some(self.depth_format),
             )?;
+        let mat = self.with_material_uniforms(mat, material_layout)?;
 
-        // Almacenar el layout vacío para limpieza posterior.
-        mat.descriptor_layout = Some(empty_layout);
-        mat.device = Some(self.context.device.clone());
-
+        self.label_material(&mat);
         Ok(mat)
     }
 
@@ -256,95 +418,23 @@ impl Reactor {
         use crate::resources::material::MaterialBuilder;
         use ash::vk;
 
-        // 1. Crear el descriptor set layout para Set 0 (albedo + normal + metallic + roughness)
-        let albedo_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(0)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let normal_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(1)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let metallic_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(2)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let roughness_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(3)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let bindings = [
-            albedo_binding,
-            normal_binding,
-            metallic_binding,
-            roughness_binding,
-        ];
-        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
-            .bindings(&bindings)
-            .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL);
-
-        let descriptor_layout = unsafe {
-            self.context
-                .device
-                .create_descriptor_set_layout(&layout_info, None)
-                .map_err(|e| {
-                    crate::core::error::ReactorError::with_source(
-                        crate::core::error::ErrorCode::VulkanPipelineCreation,
-                        "Failed to create descriptor set layout for PBR material",
-                        e,
-                    )
-                })?
-        };
-
-        // 2. Crear descriptor pool
-        let pool_size = vk::DescriptorPoolSize::default()
-            .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(4);
-
-        let pool_info = vk::DescriptorPoolCreateInfo::default()
-            .pool_sizes(std::slice::from_ref(&pool_size))
-            .max_sets(1)
-            .flags(vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND);
-
-        let descriptor_pool = unsafe {
-            self.context
-                .device
-                .create_descriptor_pool(&pool_info, None)
-                .map_err(|e| {
-                    crate::core::error::ReactorError::with_source(
-                        crate::core::error::ErrorCode::VulkanPipelineCreation,
-                        "Failed to create descriptor pool for PBR material",
-                        e,
-                    )
-                })?
-        };
-
-        // 3. Alloc descriptor set
-        let alloc_info = vk::DescriptorSetAllocateInfo::default()
-            .descriptor_pool(descriptor_pool)
-            .set_layouts(std::slice::from_ref(&descriptor_layout));
-
-        let descriptor_sets = unsafe {
-            self.context
-                .device
-                .allocate_descriptor_sets(&alloc_info)
-                .map_err(|e| {
-                    crate::core::error::ReactorError::with_source(
-                        crate::core::error::ErrorCode::VulkanPipelineCreation,
-                        "Failed to allocate descriptor set for PBR material",
-                        e,
-                    )
-                })?
+        // 1. Layout de Set 0 (albedo + normal + metallic + roughness + UBO) y su set,
+        //    ambos del allocator compartido.
+        let texture_binding = |binding| DescriptorBinding {
+            binding,
+            descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            count: 1,
+            stage_flags: vk::ShaderStageFlags::FRAGMENT,
         };
-        let descriptor_set = descriptor_sets[0];
+        let descriptor_layout = self.descriptor_allocator.lock().unwrap().layout(&[
+            texture_binding(0),
+            texture_binding(1),
+            texture_binding(2),
+            texture_binding(3),
+            material_uniform_binding(),
+        ])?;
+        let pooled_set = PooledDescriptorSet::new(&self.descriptor_allocator, descriptor_layout)?;
+        let descriptor_set = pooled_set.handle();
 
         // 4. Update descriptor set
         let albedo_info = vk::DescriptorImageInfo::default()
@@ -422,15 +512,41 @@ impl Reactor {
                 None,
                 self.swapchain.extent.width,
                 self.swapchain.extent.height,
-                self.swapchain.format,
+                self.scene_color_format,
                 Some(self.depth_format),
             )?;
 
         mat.descriptor_set = Some(descriptor_set);
-        mat.descriptor_pool = Some(descriptor_pool);
-        mat.descriptor_layout = Some(descriptor_layout);
-        mat.device = Some(self.context.device.clone());
+        mat.pooled_set = Some(pooled_set);
+        mat.attach_uniforms(&self.context, self.allocator.clone(), descriptor_set)?;
+        mat.set_params(MaterialUniformData { use_textures: 0b1111, ..mat.params() });
 
+        self.label_material(&mat);
         Ok(mat)
     }
+
+    /// Nombre `texture_<fichero>` para RenderDoc.
+    fn label_texture(&self, texture: &Texture, path: &str) {
+        let namer = self.context.debug_namer();
+        if namer.is_active() {
+            let file = std::path::Path::new(path).file_name().and_then(|f| f.to_str()).unwrap_or(path);
+            texture.set_debug_name(namer, &format!("texture_{file}"));
+        }
+    }
+
+    fn label_cubemap(&self, cubemap: &Cubemap, path: &str) {
+        let namer = self.context.debug_namer();
+        if namer.is_active() {
+            let file = std::path::Path::new(path).file_name().and_then(|f| f.to_str()).unwrap_or(path);
+            cubemap.set_debug_name(namer, &format!("cubemap_{file}"));
+        }
+    }
+
+    /// Nombre automático `material_N` para RenderDoc.
+    pub(super) fn label_material(&self, material: &Material) {
+        let namer = self.context.debug_namer();
+        if namer.is_active() {
+            material.set_debug_name(namer, &crate::core::debug_utils::next_object_name("material"));
+        }
+    }
 }
@@ -0,0 +1,117 @@
+//! Sombras: ajustes en tiempo de ejecución del CSM de la luz direccional.
+//!
+//! Activar/desactivar y cambiar el bias sólo tocan `ShadowMap`; el UBO del
+//! frame se reescribe en el shadow pass. Cambiar la resolución recrea la
+//! imagen de profundidad y vuelve a enlazarla en todos los descriptor sets.
+
+use super::Reactor;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use ash::vk;
+
+impl Reactor {
+    /// Activa o desactiva las sombras. Desactivadas, el shader deja de
+    /// muestrearlas y el shadow pass no vuelve a dibujarse.
+    pub fn set_shadows_enabled(&mut self, enabled: bool) {
+        if let Some(shadow_map) = self.shadow_map.as_mut() {
+            if shadow_map.enabled != enabled {
+                shadow_map.enabled = enabled;
+                self.shadow_cache_key = None;
+            }
+        }
+    }
+
+    /// `true` si el CSM está inicializado y activo.
+    pub fn shadows_enabled(&self) -> bool {
+        self.shadow_map.as_ref().is_some_and(|s| s.enabled)
+    }
+
+    /// Cambia el bias de profundidad y el normal bias del CSM.
+    pub fn set_shadow_bias(&mut self, bias: f32, normal_bias: f32) {
+        if let Some(shadow_map) = self.shadow_map.as_mut() {
+            shadow_map.config.bias = bias.max(0.0);
+            shadow_map.config.normal_bias = normal_bias.max(0.0);
+        }
+    }
+
+    /// Resolución (por cascada) del shadow map, o 0 si no hay sombras.
+    pub fn shadow_resolution(&self) -> u32 {
+        self.shadow_map.as_ref().map_or(0, |s| s.config.resolution)
+    }
+
+    /// Recrea el shadow map con otra resolución. Espera a que la GPU termine
+    /// porque la imagen anterior puede estar en uso por frames en vuelo.
+    pub fn set_shadow_resolution(&mut self, resolution: u32) -> ReactorResult<()> {
+        let Some(shadow_map) = self.shadow_map.as_ref() else {
+            return Err(ReactorError::new(ErrorCode::NotInitialized, "Shadow maps were not initialized"));
+        };
+        let max = unsafe {
+            self.context
+                .ash_instance()
+                .get_physical_device_properties(self.context.physical_device)
+                .limits
+                .max_image_dimension2_d
+        };
+        if resolution == 0 || resolution > max {
+            return Err(ReactorError::new(
+                ErrorCode::InvalidParameter,
+                format!("Shadow resolution {} is outside 1..={}", resolution, max),
+            ));
+        }
+        if resolution == shadow_map.config.resolution {
+            return Ok(());
+        }
+        let cascade_count = shadow_map.config.cascade_count;
+        let Some(sampler) = self.shadow_sampler else {
+            return Err(ReactorError::new(ErrorCode::NotInitialized, "Shadow sampler was not initialized"));
+        };
+
+        let (image, memory, array_view, views) = self.create_shadow_targets(resolution, cascade_count)?;
+        self.context.wait_idle()?;
+        let device = self.context.ash_device();
+        unsafe {
+            if let Some(view) = self.shadow_array_view.take() {
+                device.destroy_image_view(view, None);
+            }
+            for view in self.shadow_image_views.drain(..) {
+                device.destroy_image_view(view, None);
+            }
+            if let Some(image) = self.shadow_image.take() {
+                device.destroy_image(image, None);
+            }
+            if let Some(memory) = self.shadow_memory.take() {
+                device.free_memory(memory, None);
+            }
+        }
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(array_view)
+            .sampler(sampler);
+        let writes: Vec<_> = self
+            .shadow_descriptor_sets
+            .iter()
+            .map(|&set| {
+                vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(0)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&image_info))
+            })
+            .collect();
+        unsafe {
+            device.update_descriptor_sets(&writes, &[]);
+        }
+
+        self.shadow_image = Some(image);
+        self.shadow_memory = Some(memory);
+        self.shadow_array_view = Some(array_view);
+        self.shadow_image_views = views;
+        if let Some(shadow_map) = self.shadow_map.as_mut() {
+            shadow_map.config.resolution = resolution;
+        }
+        // La imagen nueva está en UNDEFINED: el próximo shadow pass debe dibujarse.
+        self.shadow_cache_key = None;
+        Ok(())
+    }
+}
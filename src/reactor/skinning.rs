@@ -0,0 +1,169 @@
+//! Skinning: `SceneObject::joint_matrices` → storage buffer (set = 4).
+//!
+//! Igual que el instancing, las paletas de todos los objetos skinned se
+//! concatenan en el buffer del frame en curso; cada objeto recibe el índice
+//! de su primer joint en `ObjectPushConstants::extra.x` y el vertex shader
+//! suma ese offset a sus `JOINTS_0`.
+
+use super::Reactor;
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use crate::resources::material::Material;
+use crate::resources::texture::Texture;
+use crate::systems::scene::Scene;
+use ash::vk;
+
+/// Vertex shader de skinning: mezcla hasta cuatro matrices de la paleta y
+/// luego aplica el objeto como el shader base.
+const SKINNED_VERT: &str = r#"#version 450
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 normal;
+layout(location = 2) in vec2 uv;
+layout(location = 3) in uvec4 joints;
+layout(location = 4) in vec4 weights;
+
+layout(location = 0) out vec3 fragNormal;
+layout(location = 1) out vec2 fragUV;
+layout(location = 2) out vec3 fragPos;
+
+layout(std430, set = 4, binding = 0) readonly buffer JointMatrices {
+    mat4 matrices[];
+} joint_palette;
+
+layout(push_constant) uniform Constants {
+    mat4 mvp;
+    mat4 model;
+    vec4 camera_pos;
+    vec4 light_pos;
+    vec4 color;
+    vec4 emission;
+    vec4 normal0;
+    vec4 normal1;
+    vec4 normal2;
+    vec4 extra;
+} push;
+
+void main() {
+    uint base = uint(push.extra.x);
+    mat4 skin = weights.x * joint_palette.matrices[base + joints.x]
+              + weights.y * joint_palette.matrices[base + joints.y]
+              + weights.z * joint_palette.matrices[base + joints.z]
+              + weights.w * joint_palette.matrices[base + joints.w];
+    vec4 local_pos = skin * vec4(position, 1.0);
+    mat3 skin_normal = mat3(skin[0].xyz, skin[1].xyz, skin[2].xyz);
+    mat3 normal_matrix = mat3(push.normal0.xyz, push.normal1.xyz, push.normal2.xyz);
+
+    gl_Position = push.mvp * local_pos;
+    fragNormal = normalize(normal_matrix * (skin_normal * normal));
+    fragUV = uv;
+    fragPos = vec3(push.model * local_pos);
+}
+"#;
+
+/// Fragment por defecto para modelos glTF skinned: Lambert con `push.color`.
+const SKINNED_FRAG: &str = r#"#version 450
+layout(location = 0) in vec3 fragNormal;
+layout(location = 1) in vec2 fragUV;
+layout(location = 2) in vec3 fragPos;
+
+layout(location = 0) out vec4 outColor;
+
+layout(push_constant) uniform Constants {
+    mat4 mvp;
+    mat4 model;
+    vec4 camera_pos;
+    vec4 light_pos;
+    vec4 color;
+} push;
+
+void main() {
+    vec3 n = normalize(fragNormal);
+    vec3 l = normalize(push.light_pos.xyz - fragPos);
+    float diffuse = max(dot(n, l), 0.0);
+    outColor = vec4(push.color.rgb * (0.25 + 0.75 * diffuse), 1.0);
+}
+"#;
+
+/// Fragment skinned con textura base: WGSL porque naga no acepta samplers
+/// combinados en GLSL; textura y sampler van separados en el set 0.
+const SKINNED_TEXTURED_FRAG: &str = r#"
+struct Constants {
+    mvp: mat4x4<f32>,
+    model: mat4x4<f32>,
+    camera_pos: vec4<f32>,
+    light_pos: vec4<f32>,
+    color: vec4<f32>,
+}
+var<push_constant> push: Constants;
+
+@group(0) @binding(0) var base_color_texture: texture_2d<f32>;
+@group(0) @binding(1) var base_color_sampler: sampler;
+
+@fragment
+fn main(@location(0) normal: vec3<f32>, @location(1) uv: vec2<f32>, @location(2) position: vec3<f32>) -> @location(0) vec4<f32> {
+    let albedo = textureSample(base_color_texture, base_color_sampler, uv) * push.color;
+    let diffuse = max(dot(normalize(normal), normalize(push.light_pos.xyz - position)), 0.0);
+    return vec4<f32>(albedo.rgb * (0.25 + 0.75 * diffuse), 1.0);
+}
+"#;
+
+impl Reactor {
+    /// Crea un material para meshes `VertexSkinned` con el vertex shader de
+    /// skinning incluido y el fragment indicado.
+    pub fn create_skinned_material(&self, frag_code: &[u32]) -> ReactorResult<Material> {
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(SKINNED_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        self.build_simple_material(&vert.spirv, frag_code, false, true, false, None)
+    }
+
+    /// Material skinned con un fragment Lambert simple (color del objeto).
+    pub fn create_default_skinned_material(&self) -> ReactorResult<Material> {
+        let mut compiler = ShaderCompiler::new();
+        let frag = compiler.compile_source(SKINNED_FRAG, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")?;
+        self.create_skinned_material(&frag.spirv)
+    }
+
+    /// Como `create_default_skinned_material`, modulando `push.color` con la
+    /// textura base del modelo (la de glTF `baseColorTexture`).
+    pub fn create_default_skinned_textured_material(&self, texture: &Texture) -> ReactorResult<Material> {
+        let mut compiler = ShaderCompiler::new();
+        let vert = compiler.compile_source(SKINNED_VERT, ShaderLanguage::Glsl, ShaderStage::Vertex, "main")?;
+        let frag = compiler.compile_source(SKINNED_TEXTURED_FRAG, ShaderLanguage::Wgsl, ShaderStage::Fragment, "main")?;
+        self.build_simple_material(&vert.spirv, &frag.spirv, false, true, false, Some(texture))
+    }
+
+    /// Sube las paletas de los objetos skinned visibles al buffer del frame en
+    /// curso. Devuelve, por índice de objeto, el índice de su primer joint.
+    pub(crate) fn upload_joint_matrices(&mut self, scene: &Scene) -> ReactorResult<Vec<u32>> {
+        self.joint_data.clear();
+        let mut first_joints = Vec::with_capacity(scene.objects.len());
+        for object in &scene.objects {
+            first_joints.push(self.joint_data.len() as u32);
+            if object.visible {
+                self.joint_data.extend(object.joint_matrices.iter().map(|m| m.to_cols_array()));
+            }
+        }
+        if self.joint_data.is_empty() {
+            return Ok(first_joints);
+        }
+
+        let frame = self.current_frame;
+        if frame >= self.joint_buffers.len() {
+            return Err(ReactorError::new(ErrorCode::NotInitialized, "Skinning was not initialized"));
+        }
+        let needed = std::mem::size_of_val(self.joint_data.as_slice()) as u64;
+        if self.joint_buffers[frame].size < needed {
+            self.joint_buffers[frame] = self.create_joint_buffer(frame, needed.next_power_of_two())?;
+        }
+        self.joint_buffers[frame].write(&self.joint_data);
+        Ok(first_joints)
+    }
+
+    /// Descriptor set de joints del frame en curso, si este frame subió alguno.
+    pub(crate) fn current_skin_descriptor_set(&self) -> Option<vk::DescriptorSet> {
+        if self.joint_data.is_empty() {
+            return None;
+        }
+        self.skin_descriptor_sets.get(self.current_frame).copied()
+    }
+}
@@ -0,0 +1,116 @@
+//! Estadísticas del último `draw_scene`.
+
+use super::Reactor;
+use crate::app::config::RendererMode;
+
+/// Resumen del último frame dibujado por `draw_scene`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Path con el que se dibujó el frame.
+    pub mode: RendererMode,
+    pub msaa_samples: u32,
+    /// Objetos dibujados (G-Buffer + forward).
+    pub objects_drawn: u32,
+    /// De ellos, los escritos en el G-Buffer (0 en forward).
+    pub gbuffer_objects: u32,
+    /// Draws grabados en los pases de la escena (sin contar sombras).
+    pub draw_calls: u32,
+    /// Instancias de esos draws (1 por draw no instanciado).
+    pub instances: u32,
+    /// Triángulos enviados: `index_count / 3 × instancias` por draw.
+    pub triangles: u64,
+    /// Estimación de invocaciones del vertex shader (`index_count ×
+    /// instancias`, sin cache post-transform).
+    pub vertices: u64,
+    /// Cambios de pipeline y `vkCmdBindDescriptorSets` grabados.
+    pub pipeline_binds: u32,
+    pub descriptor_binds: u32,
+    /// Draws y triángulos de las cascadas de sombra, aparte.
+    pub shadow_draw_calls: u32,
+    pub shadow_triangles: u64,
+    /// Luces subidas al UBO de luces.
+    pub lights: u32,
+    /// Bytes del ring uniforme usados en este frame.
+    pub uniform_bytes: u64,
+    /// Máximo de bytes que ha usado cualquier frame del ring.
+    pub uniform_high_water: u64,
+    /// Tamaño actual del ring por frame.
+    pub uniform_capacity: u64,
+    /// Segundos desde el `draw_scene` anterior (0 en el primero).
+    pub frame_time: f32,
+}
+
+impl RenderStats {
+    /// Frames por segundo según `frame_time` (0 si aún no hay dos frames).
+    pub fn fps(&self) -> f32 {
+        if self.frame_time > 0.0 { 1.0 / self.frame_time } else { 0.0 }
+    }
+
+    /// Cuenta un draw de `vertex_count` índices (o vértices) en triángulos.
+    pub(crate) fn count_draw(&mut self, vertex_count: u32, instance_count: u32) {
+        self.draw_calls += 1;
+        self.instances += instance_count;
+        self.vertices += vertex_count as u64 * instance_count as u64;
+        self.triangles += (vertex_count / 3) as u64 * instance_count as u64;
+    }
+
+    /// Suma los contadores de draws de un pase grabado aparte.
+    pub(crate) fn add_pass(&mut self, pass: &RenderStats) {
+        self.objects_drawn += pass.objects_drawn;
+        self.gbuffer_objects += pass.gbuffer_objects;
+        self.draw_calls += pass.draw_calls;
+        self.instances += pass.instances;
+        self.triangles += pass.triangles;
+        self.vertices += pass.vertices;
+        self.pipeline_binds += pass.pipeline_binds;
+        self.descriptor_binds += pass.descriptor_binds;
+    }
+
+    pub(crate) fn count_shadow_draw(&mut self, vertex_count: u32, instance_count: u32) {
+        self.shadow_draw_calls += 1;
+        self.shadow_triangles += (vertex_count / 3) as u64 * instance_count as u64;
+    }
+}
+
+impl Reactor {
+    pub fn render_stats(&self) -> RenderStats {
+        self.render_stats
+    }
+
+    /// Reinicia los contadores al empezar a grabar un frame.
+    pub(crate) fn begin_render_stats(&mut self, deferred: bool) {
+        let now = std::time::Instant::now();
+        let frame_time = self.last_draw_at.map_or(0.0, |last| now.duration_since(last).as_secs_f32());
+        self.last_draw_at = Some(now);
+        let ring = self.uniform_ring.as_ref().map(|ring| ring.stats()).unwrap_or_default();
+        self.render_stats = RenderStats {
+            mode: match self.renderer_mode {
+                RendererMode::Deferred if !deferred => RendererMode::Forward,
+                mode => mode,
+            },
+            msaa_samples: self.msaa_samples.as_raw(),
+            lights: self.light_uniform.light_count,
+            uniform_bytes: ring.used,
+            uniform_high_water: ring.high_water,
+            uniform_capacity: ring.capacity,
+            frame_time,
+            ..Default::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instanced_draws_multiply_triangles_and_vertices() {
+        let mut stats = RenderStats::default();
+        stats.count_draw(36, 1);
+        stats.count_draw(6, 10_000);
+        assert_eq!(stats.draw_calls, 2);
+        assert_eq!(stats.instances, 10_001);
+        assert_eq!(stats.triangles, 12 + 20_000);
+        assert_eq!(stats.vertices, 36 + 60_000);
+    }
+}
@@ -1,7 +1,9 @@
 //! Recreación del swapchain ante resize / out-of-date.
 //!
 //! Libera y reconstruye también los attachments MSAA y depth, manteniendo
-//! la coherencia de extents con el nuevo tamaño de la superficie.
+//! la coherencia de extents con el nuevo tamaño de la superficie. En modo
+//! headless reconstruye las imágenes offscreen al tamaño pedido (p. ej. un
+//! viewport de editor que cambia de tamaño).
 
 use super::{depth, msaa, Reactor};
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
@@ -12,6 +14,30 @@ impl Reactor {
     /// Espera GPU, destruye los recursos dependientes del tamaño y los
     /// reconstruye contra el nuevo extent reportado por la surface.
     pub fn recreate_swapchain(&mut self) -> ReactorResult<()> {
+        self.recreate_swapchain_sized(self.window_extent.width, self.window_extent.height)
+    }
+
+    /// Como `recreate_swapchain`, pero con el tamaño de ventana explícito
+    /// (se usa cuando la surface no fija el extent, p. ej. Wayland).
+    ///
+    /// Con tamaño 0 (ventana minimizada) no se recrea nada: `minimized` queda
+    /// activo, `resized` sigue pendiente y los `draw_*` se saltan el frame.
+    ///
+    /// En headless no hay surface: las imágenes offscreen se recrean con
+    /// `width`×`height` si cambió el tamaño, y `read_pixels` no tiene frame
+    /// hasta el siguiente `draw_*`.
+    pub fn recreate_swapchain_sized(&mut self, width: u32, height: u32) -> ReactorResult<()> {
+        let offscreen = self.swapchain.is_offscreen();
+        if offscreen && self.swapchain.extent == (vk::Extent2D { width, height }) {
+            self.resized = false;
+            return Ok(());
+        }
+        self.window_extent = vk::Extent2D { width, height };
+        if width == 0 || height == 0 {
+            self.minimized = true;
+            return Ok(());
+        }
+
         unsafe {
             self.context.device.device_wait_idle().map_err(|e| {
                 ReactorError::with_source(
@@ -22,22 +48,28 @@ impl Reactor {
             })?;
         }
 
-        let capabilities = unsafe {
-            self.context
-                .surface_loader()
-                .get_physical_device_surface_capabilities(
-                    self.context.physical_device,
-                    self.context.surface_khr(),
-                )?
-        };
+        if !offscreen {
+            let capabilities = unsafe {
+                self.context
+                    .surface_loader()
+                    .get_physical_device_surface_capabilities(
+                        self.context.physical_device,
+                        self.context.surface_khr(),
+                    )?
+            };
 
-        // Ventana minimizada → no recreamos hasta que vuelva a tener tamaño.
-        if capabilities.current_extent.width == 0 || capabilities.current_extent.height == 0 {
-            return Ok(());
+            // Ventana minimizada → no recreamos hasta que vuelva a tener tamaño.
+            if capabilities.current_extent.width == 0 || capabilities.current_extent.height == 0 {
+                self.minimized = true;
+                return Ok(());
+            }
         }
+        self.minimized = false;
 
         self.gbuffer = None;
         self.temporal_history = None;
+        // Se recrean al nuevo tamaño en el siguiente frame escalado.
+        self.render_scale_targets = None;
 
         // ── Destruir depth previo ──
         if let Some(view) = self.depth_image_view.take() {
@@ -62,13 +94,14 @@ impl Reactor {
         }
 
         // ── Swapchain ──
+        let image_count = self.swapchain.image_count;
         self.swapchain.destroy(self.context.ash_device());
-        self.swapchain = Swapchain::new(
-            &self.context,
-            capabilities.current_extent.width,
-            capabilities.current_extent.height,
-            self.vsync,
-        )?;
+        self.swapchain = if offscreen {
+            self.last_rendered_image = None;
+            Swapchain::offscreen(&self.context, self.allocator.clone(), width, height, image_count)?
+        } else {
+            Swapchain::new(&self.context, width, height, self.vsync)?
+        };
 
         // ── Recrear MSAA si estaba habilitado ──
         if self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
@@ -76,7 +109,7 @@ impl Reactor {
                 &self.context,
                 self.swapchain.extent.width,
                 self.swapchain.extent.height,
-                self.swapchain.format,
+                self.scene_color_format,
                 self.msaa_samples,
             )?;
             self.msaa_image = Some(img);
@@ -103,11 +136,21 @@ impl Reactor {
             self.swapchain.extent.width,
             self.swapchain.extent.height,
             self.swapchain.images.len() as u32,
-            self.swapchain.format,
+            self.scene_color_format,
             self.depth_image_view.unwrap(),
             self.msaa_samples == vk::SampleCountFlags::TYPE_1,
         )?;
 
+        // ── Recrear el pipeline de líneas de depuración ──
+        // Se creó con las muestras y el formato de escena de entonces; sin
+        // rehacerlo dejaría de ser compatible con el render pass actual.
+        if self.debug_line_pipeline.is_some() {
+            if let Err(e) = self.init_debug_lines() {
+                self.debug_line_pipeline = None;
+                log::warn!("Debug line pipeline unavailable after recreate: {}", e);
+            }
+        }
+
         let gbuffer = crate::graphics::GBuffer::new(
             &self.context,
             self.allocator.clone(),
@@ -122,6 +165,8 @@ impl Reactor {
             gbuffer.storage_writes_supported
         );
         self.gbuffer = Some(gbuffer);
+        // La pasada de iluminación deferred muestrea G-Buffer y depth nuevos.
+        self.write_deferred_gbuffer_set();
 
         let temporal_history = crate::graphics::TemporalHistory::new(
             &self.context,
@@ -137,6 +182,7 @@ impl Reactor {
             temporal_history.storage_writes_supported
         );
         self.temporal_history = Some(temporal_history);
+        self.resized = false;
 
         Ok(())
     }
@@ -0,0 +1,215 @@
+//! Ventanas secundarias que comparten el device del `Reactor`.
+//!
+//! Cada `RenderTargetWindow` tiene su surface, swapchain, depth, MSAA, color
+//! HDR y semáforos; el device, las colas, los pipelines y los recursos de la escena
+//! son los del `Reactor`. Se dibuja con `Reactor::draw_scene_to`, que usa un
+//! slot de frame en vuelo igual que `draw_scene`. Cerrar la ventana (soltar el
+//! target) sólo destruye lo suyo.
+//!
+//! Limitaciones: sólo el path forward, sin post-proceso ni overlay de texto
+//! (sus targets tienen el tamaño de la ventana principal).
+
+use super::{depth, msaa, Reactor, MAX_FRAMES_IN_FLIGHT};
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::VulkanContext;
+use crate::graphics::image::Image;
+use crate::graphics::swapchain::Swapchain;
+use ash::vk;
+use gpu_allocator::vulkan::Allocator;
+use std::sync::{Arc, Mutex};
+use winit::window::Window;
+
+pub struct RenderTargetWindow {
+    pub swapchain: Swapchain,
+    pub(crate) msaa_image: Option<vk::Image>,
+    pub(crate) msaa_image_view: Option<vk::ImageView>,
+    pub(crate) msaa_memory: Option<vk::DeviceMemory>,
+    pub(crate) depth_image: Option<vk::Image>,
+    pub(crate) depth_image_view: Option<vk::ImageView>,
+    pub(crate) depth_memory: Option<vk::DeviceMemory>,
+    /// Color de la escena antes del tonemap, uno por imagen del swapchain
+    /// (vacío sin HDR).
+    pub(crate) hdr_images: Vec<Image>,
+    /// Uno por slot de frame en vuelo.
+    pub(crate) image_available_semaphores: Vec<vk::Semaphore>,
+    /// Uno por imagen del swapchain.
+    pub(crate) render_finished_semaphores: Vec<vk::Semaphore>,
+    pub resized: bool,
+    pub minimized: bool,
+    pub(crate) window_extent: vk::Extent2D,
+    vsync: bool,
+    msaa_samples: vk::SampleCountFlags,
+    depth_format: vk::Format,
+    scene_color_format: vk::Format,
+    allocator: Arc<Mutex<Allocator>>,
+    /// Contexto con la surface de esta ventana (la suelta al destruirse).
+    pub(crate) context: VulkanContext,
+    /// Al final: la ventana debe sobrevivir a su surface.
+    pub window: Arc<Window>,
+}
+
+impl Reactor {
+    /// Prepara `window` como destino de `draw_scene_to`. El swapchain usa el
+    /// mismo formato que la ventana principal, porque los pipelines ya están
+    /// creados para ese formato.
+    pub fn create_window_target(&self, window: Arc<Window>) -> ReactorResult<RenderTargetWindow> {
+        let context = self.context.for_window(&*window)?;
+        let size = window.inner_size();
+        let swapchain = Swapchain::new(&context, size.width.max(1), size.height.max(1), self.vsync)?;
+        let mut target = RenderTargetWindow {
+            swapchain,
+            msaa_image: None,
+            msaa_image_view: None,
+            msaa_memory: None,
+            depth_image: None,
+            depth_image_view: None,
+            depth_memory: None,
+            hdr_images: Vec::new(),
+            image_available_semaphores: Vec::new(),
+            render_finished_semaphores: Vec::new(),
+            resized: false,
+            minimized: size.width == 0 || size.height == 0,
+            window_extent: vk::Extent2D { width: size.width, height: size.height },
+            vsync: self.vsync,
+            msaa_samples: self.msaa_samples,
+            depth_format: self.depth_format,
+            scene_color_format: self.scene_color_format,
+            allocator: self.allocator.clone(),
+            context,
+            window,
+        };
+        if target.swapchain.format != self.swapchain.format {
+            return Err(ReactorError::new(
+                ErrorCode::NotSupported,
+                format!(
+                    "Window surface format {:?} differs from the main window's {:?}",
+                    target.swapchain.format, self.swapchain.format
+                ),
+            ));
+        }
+        target.create_attachments()?;
+        target.create_semaphores()?;
+        Ok(target)
+    }
+}
+
+impl RenderTargetWindow {
+    /// Marca el resize; se aplica en el próximo `draw_scene_to`.
+    pub fn handle_resize(&mut self, width: u32, height: u32) {
+        self.window_extent = vk::Extent2D { width, height };
+        self.minimized = width == 0 || height == 0;
+        self.resized = true;
+    }
+
+    pub fn extent(&self) -> vk::Extent2D {
+        self.swapchain.extent
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.swapchain.aspect_ratio()
+    }
+
+    /// Recrea swapchain y attachments con el tamaño actual de la ventana.
+    /// Espera a que el device quede libre: las imágenes viejas pueden estar
+    /// en uso por un frame en vuelo.
+    pub(crate) fn recreate(&mut self) -> ReactorResult<()> {
+        let vk::Extent2D { width, height } = self.window_extent;
+        if width == 0 || height == 0 {
+            self.minimized = true;
+            return Ok(());
+        }
+        unsafe { self.context.device.device_wait_idle() }
+            .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "device_wait_idle failed", e))?;
+        self.destroy_attachments();
+        self.destroy_render_finished();
+        self.swapchain.destroy(self.context.ash_device());
+        self.swapchain = Swapchain::new(&self.context, width, height, self.vsync)?;
+        self.create_attachments()?;
+        self.create_semaphores()?;
+        self.minimized = false;
+        self.resized = false;
+        Ok(())
+    }
+
+    fn create_attachments(&mut self) -> ReactorResult<()> {
+        let vk::Extent2D { width, height } = self.swapchain.extent;
+        if self.msaa_samples != vk::SampleCountFlags::TYPE_1 {
+            let (image, view, memory) =
+                msaa::create_msaa_resources(&self.context, width, height, self.scene_color_format, self.msaa_samples)?;
+            self.msaa_image = Some(image);
+            self.msaa_image_view = Some(view);
+            self.msaa_memory = Some(memory);
+        }
+        let (image, view, memory) =
+            depth::create_depth_resources(&self.context, width, height, self.depth_format, self.msaa_samples)?;
+        self.depth_image = Some(image);
+        self.depth_image_view = Some(view);
+        self.depth_memory = Some(memory);
+        if self.scene_color_format != self.swapchain.format {
+            for _ in 0..self.swapchain.images.len() {
+                self.hdr_images.push(Image::new(
+                    &self.context,
+                    self.allocator.clone(),
+                    width,
+                    height,
+                    self.scene_color_format,
+                    vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED,
+                    vk::ImageAspectFlags::COLOR,
+                    1,
+                )?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Completa los semáforos que falten: los de imagen disponible sólo se
+    /// crean una vez, los de render terminado siguen al número de imágenes.
+    fn create_semaphores(&mut self) -> ReactorResult<()> {
+        let device = self.context.ash_device();
+        let info = vk::SemaphoreCreateInfo::default();
+        let create = || unsafe { device.create_semaphore(&info, None) }
+            .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "Failed to create semaphore", e));
+        while self.image_available_semaphores.len() < MAX_FRAMES_IN_FLIGHT {
+            self.image_available_semaphores.push(create()?);
+        }
+        while self.render_finished_semaphores.len() < self.swapchain.images.len() {
+            self.render_finished_semaphores.push(create()?);
+        }
+        Ok(())
+    }
+
+    fn destroy_attachments(&mut self) {
+        self.hdr_images.clear();
+        let device = self.context.ash_device();
+        unsafe {
+            if let Some(view) = self.depth_image_view.take() { device.destroy_image_view(view, None); }
+            if let Some(image) = self.depth_image.take() { device.destroy_image(image, None); }
+            if let Some(memory) = self.depth_memory.take() { device.free_memory(memory, None); }
+            if let Some(view) = self.msaa_image_view.take() { device.destroy_image_view(view, None); }
+            if let Some(image) = self.msaa_image.take() { device.destroy_image(image, None); }
+            if let Some(memory) = self.msaa_memory.take() { device.free_memory(memory, None); }
+        }
+    }
+
+    fn destroy_render_finished(&mut self) {
+        let device = self.context.ash_device();
+        for semaphore in self.render_finished_semaphores.drain(..) {
+            unsafe { device.destroy_semaphore(semaphore, None) };
+        }
+    }
+}
+
+impl Drop for RenderTargetWindow {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.context.device.device_wait_idle();
+        }
+        self.destroy_attachments();
+        self.destroy_render_finished();
+        let device = self.context.ash_device();
+        for semaphore in self.image_available_semaphores.drain(..) {
+            unsafe { device.destroy_semaphore(semaphore, None) };
+        }
+        self.swapchain.destroy(self.context.ash_device());
+    }
+}
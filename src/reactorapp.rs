@@ -44,10 +44,11 @@ pub use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 pub use crate::base_shader::{BaseShaderCookbook, BaseShaderPair, BaseShaderStage};
 pub use crate::resources::decal::Decal;
 pub use crate::resources::material::Material;
-pub use crate::resources::mesh::Mesh;
-pub use crate::resources::texture::Texture;
+pub use crate::resources::mesh::{Mesh, MeshUsage};
+pub use crate::resources::texture::{Cubemap, Texture, TextureOptions};
 pub use crate::resources::vertex::Vertex;
-pub use crate::scene::camera::Camera;
+pub use crate::graphics::sprite_batch::Sprite;
+pub use crate::scene::camera::{Camera, Camera2D};
 pub use crate::scene::transform::Transform;
 pub use crate::systems::lighting::{Light, LightType, LightingSystem};
 pub use crate::systems::scene::{Scene, SceneObject};
@@ -69,7 +69,7 @@ impl AssetCooker {
         }
 
         let path_str = relative_path.to_string_lossy().replace('\\', "/");
-        println!("🍳 Cooking asset: {} (Type: {:?})", path_str, asset_type);
+        log::info!("🍳 Cooking asset: {} (Type: {:?})", path_str, asset_type);
 
         let content = fs::read(path).map_err(|e| {
             ReactorError::new(
@@ -181,7 +181,7 @@ impl AssetCooker {
                                 format!("Failed to write patched model: {}", e),
                             )
                         })?;
-                        println!("🩹 Patched glTF extensions in {}", path_str);
+                        log::info!("🩹 Patched glTF extensions in {}", path_str);
                     }
                 }
 
@@ -179,7 +179,7 @@ impl AssetHotReloadManager {
 
                 match res {
                     Ok(event) => Self::handle_filesystem_event(&event, &config, &tx, &shared),
-                    Err(e) => eprintln!("[AssetHotReload] Watcher error: {}", e),
+                    Err(e) => log::error!("[AssetHotReload] Watcher error: {}", e),
                 }
             })
             .map_err(|e| ReactorError::asset_load(format!("Failed to create watcher: {}", e)))?;
@@ -194,7 +194,7 @@ impl AssetHotReloadManager {
                         e
                     ))
                 })?;
-                println!("[AssetHotReload] Watching: {}", dir.display());
+                log::info!("[AssetHotReload] Watching: {}", dir.display());
             }
         }
 
@@ -165,7 +165,7 @@ impl AssetLoaderQueue {
         loop {
             if shutdown.load(Ordering::Relaxed) {
                 if config.log_stats {
-                    println!("[LoaderQueue#{}] Shutting down", worker_id);
+                    log::info!("[LoaderQueue#{}] Shutting down", worker_id);
                 }
                 break;
             }
@@ -405,7 +405,7 @@ impl AssetLoaderQueue {
 
         if self.config.log_stats {
             let stats = self.stats.lock().unwrap();
-            println!(
+            log::info!(
                 "[LoaderQueue] Final stats: completed={}, failed={}, avg_time={:.2}ms",
                 stats.completed, stats.failed, stats.avg_load_time_ms
             );
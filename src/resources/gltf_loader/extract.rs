@@ -1,6 +1,6 @@
 use gltf::buffer::Data as GltfBufferData;
 use gltf::image::Data as GltfImageData;
-use glam::{Mat4, Vec2, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3};
 use crate::core::error::{ReactorError, ReactorResult};
 use crate::resources::gltf_loader::types::*;
 use crate::resources::vertex::Vertex;
@@ -93,6 +93,9 @@ pub(super) fn extract_mesh(
 ) -> ReactorResult<GltfMeshData> {
     let mut vertices = Vec::new();
     let mut indices = Vec::new();
+    let mut joints = Vec::new();
+    let mut weights = Vec::new();
+    let mut skinned = false;
     let mut material_index = None;
 
     for primitive in mesh.primitives() {
@@ -122,6 +125,20 @@ pub(super) fn extract_mesh(
             ));
         }
 
+        // Primitives without skin data get a single full-weight joint 0 so the
+        // mesh keeps one vertex layout.
+        match (reader.read_joints(0), reader.read_weights(0)) {
+            (Some(j), Some(w)) => {
+                skinned = true;
+                joints.extend(j.into_u16());
+                weights.extend(w.into_f32().map(normalize_weights));
+            }
+            _ => {
+                joints.extend(std::iter::repeat([0u16; 4]).take(positions.len()));
+                weights.extend(std::iter::repeat([1.0, 0.0, 0.0, 0.0]).take(positions.len()));
+            }
+        }
+
         if let Some(idx_reader) = reader.read_indices() {
             indices.extend(idx_reader.into_u32().map(|i| i + base_vertex));
         } else {
@@ -136,15 +153,29 @@ pub(super) fn extract_mesh(
     if vertices.is_empty() {
         return Err(ReactorError::asset_load("Mesh has no vertices"));
     }
+    if !skinned || joints.len() != vertices.len() {
+        joints.clear();
+        weights.clear();
+    }
 
     Ok(GltfMeshData {
         vertices,
         indices,
         name: mesh.name().unwrap_or("unnamed").to_string(),
         material_index,
+        joints,
+        weights,
     })
 }
 
+fn normalize_weights(w: [f32; 4]) -> [f32; 4] {
+    let sum: f32 = w.iter().sum();
+    if sum <= f32::EPSILON {
+        return [1.0, 0.0, 0.0, 0.0];
+    }
+    w.map(|x| x / sum)
+}
+
 pub(super) fn build_node_hierarchy(gltf: &gltf::Document) -> ReactorResult<GltfNode> {
     let scene = gltf
         .default_scene()
@@ -161,6 +192,8 @@ pub(super) fn build_node_hierarchy(gltf: &gltf::Document) -> ReactorResult<GltfN
         transform: Mat4::IDENTITY,
         mesh_index: None,
         material_index: None,
+        node_index: None,
+        skin_index: None,
         children,
     })
 }
@@ -184,21 +217,102 @@ fn build_node(node: &gltf::Node) -> ReactorResult<GltfNode> {
         transform,
         mesh_index,
         material_index,
+        node_index: Some(node.index()),
+        skin_index: node.skin().map(|s| s.index()),
         children,
     })
 }
 
-pub(super) fn extract_animations(gltf: &gltf::Document) -> Vec<GltfAnimation> {
+pub(super) fn extract_nodes(gltf: &gltf::Document) -> Vec<GltfNodeTransform> {
+    let mut nodes: Vec<GltfNodeTransform> = gltf
+        .nodes()
+        .map(|node| {
+            let (translation, rotation, scale) = node.transform().decomposed();
+            GltfNodeTransform {
+                name: node.name().unwrap_or("unnamed").to_string(),
+                parent: None,
+                translation: Vec3::from(translation),
+                rotation: Quat::from_array(rotation),
+                scale: Vec3::from(scale),
+            }
+        })
+        .collect();
+
+    for node in gltf.nodes() {
+        for child in node.children() {
+            nodes[child.index()].parent = Some(node.index());
+        }
+    }
+    nodes
+}
+
+pub(super) fn extract_skins(gltf: &gltf::Document, buffers: &[GltfBufferData]) -> Vec<GltfSkin> {
+    gltf.skins()
+        .map(|skin| {
+            let joints: Vec<usize> = skin.joints().map(|j| j.index()).collect();
+            let reader = skin.reader(|buffer| Some(&buffers[buffer.index()]));
+            let inverse_bind_matrices = reader
+                .read_inverse_bind_matrices()
+                .map(|ibm| ibm.map(|m| Mat4::from_cols_array_2d(&m)).collect())
+                .unwrap_or_else(|| vec![Mat4::IDENTITY; joints.len()]);
+            GltfSkin {
+                name: skin.name().unwrap_or("unnamed").to_string(),
+                joints,
+                inverse_bind_matrices,
+            }
+        })
+        .collect()
+}
+
+/// Reads every channel with its own sampler (samplers shared between
+/// channels in the file are duplicated), flattening the outputs to `f32`.
+pub(super) fn extract_animations(
+    gltf: &gltf::Document,
+    buffers: &[GltfBufferData],
+) -> Vec<GltfAnimation> {
+    use gltf::animation::util::ReadOutputs;
+
     let mut animations = Vec::new();
 
     for anim in gltf.animations() {
         let name = anim.name().unwrap_or("unnamed").to_string();
+        let mut channels = Vec::new();
+        let mut samplers = Vec::new();
+        let mut duration = 0.0f32;
+
+        for channel in anim.channels() {
+            let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
+            let Some(inputs) = reader.read_inputs() else { continue };
+            let input: Vec<f32> = inputs.collect();
+
+            let (path, output): (AnimationPath, Vec<f32>) = match reader.read_outputs() {
+                Some(ReadOutputs::Translations(t)) => (AnimationPath::Translation, t.flatten().collect()),
+                Some(ReadOutputs::Rotations(r)) => (AnimationPath::Rotation, r.into_f32().flatten().collect()),
+                Some(ReadOutputs::Scales(s)) => (AnimationPath::Scale, s.flatten().collect()),
+                Some(ReadOutputs::MorphTargetWeights(w)) => (AnimationPath::Weights, w.into_f32().collect()),
+                None => continue,
+            };
+
+            let interpolation = match channel.sampler().interpolation() {
+                gltf::animation::Interpolation::Linear => AnimationInterpolation::Linear,
+                gltf::animation::Interpolation::Step => AnimationInterpolation::Step,
+                gltf::animation::Interpolation::CubicSpline => AnimationInterpolation::CubicSpline,
+            };
+
+            duration = duration.max(input.last().copied().unwrap_or(0.0));
+            channels.push(AnimationChannel {
+                node_index: channel.target().node().index(),
+                sampler_index: samplers.len(),
+                path,
+            });
+            samplers.push(AnimationSampler { input, output, interpolation });
+        }
 
         animations.push(GltfAnimation {
             name,
-            duration: 0.0,
-            channels: Vec::new(),
-            samplers: Vec::new(),
+            duration,
+            channels,
+            samplers,
         });
     }
 
@@ -110,7 +110,9 @@ impl GltfLoader {
 
         let root_node = extract::build_node_hierarchy(gltf)?;
 
-        let animations = extract::extract_animations(gltf);
+        let animations = extract::extract_animations(gltf, buffers);
+        let nodes = extract::extract_nodes(gltf);
+        let skins = extract::extract_skins(gltf, buffers);
 
         let model = GltfModel {
             meshes,
@@ -118,6 +120,8 @@ impl GltfLoader {
             textures,
             root_node,
             animations,
+            nodes,
+            skins,
             source_path: path.to_path_buf(),
         };
 
@@ -7,7 +7,7 @@ pub use loader::GltfLoader;
 pub use types::{
     AnimationChannel, AnimationInterpolation, AnimationPath, AnimationSampler,
     GltfAlphaMode, GltfAnimation, GltfCacheStats, GltfMaterialData, GltfMeshData, GltfModel,
-    GltfNode, GltfTextureData,
+    GltfNode, GltfNodeTransform, GltfSkin, GltfTextureData,
 };
 
 use std::path::Path;
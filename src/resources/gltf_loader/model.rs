@@ -1,5 +1,30 @@
 use crate::core::error::ReactorResult;
 use crate::resources::gltf_loader::types::*;
+use crate::resources::vertex::VertexSkinned;
+
+impl GltfMeshData {
+    pub fn is_skinned(&self) -> bool {
+        !self.joints.is_empty() && self.joints.len() == self.vertices.len()
+    }
+
+    /// Vertices in the `VertexSkinned` layout; empty for rigid meshes.
+    pub fn skinned_vertices(&self) -> Vec<VertexSkinned> {
+        if !self.is_skinned() {
+            return Vec::new();
+        }
+        self.vertices
+            .iter()
+            .zip(self.joints.iter().zip(&self.weights))
+            .map(|(v, (j, w))| VertexSkinned {
+                position: v.position,
+                normal: v.color,
+                uv: v.uv,
+                joints: j.map(u32::from),
+                weights: *w,
+            })
+            .collect()
+    }
+}
 
 impl GltfModel {
     pub fn upload_first_mesh(
@@ -103,6 +128,10 @@ impl GltfModel {
         }
     }
 
+    pub fn find_animation(&self, name: &str) -> Option<usize> {
+        self.animations.iter().position(|a| a.name == name)
+    }
+
     pub fn height(&self) -> f32 {
         self.bounds().map(|(mn, mx)| mx.y - mn.y).unwrap_or(0.0)
     }
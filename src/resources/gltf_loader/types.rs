@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use glam::Mat4;
+use glam::{Mat4, Quat, Vec3};
 use crate::resources::vertex::Vertex;
 
 #[derive(Clone, Debug)]
@@ -8,6 +8,10 @@ pub struct GltfMeshData {
     pub indices: Vec<u32>,
     pub name: String,
     pub material_index: Option<usize>,
+    /// Per-vertex `JOINTS_0`, indexing the skin's `joints`. Empty for rigid meshes.
+    pub joints: Vec<[u16; 4]>,
+    /// Per-vertex `WEIGHTS_0`, normalized to sum 1. Empty for rigid meshes.
+    pub weights: Vec<[f32; 4]>,
 }
 
 #[derive(Clone, Debug)]
@@ -48,6 +52,9 @@ pub struct GltfModel {
     pub textures: Vec<GltfTextureData>,
     pub root_node: GltfNode,
     pub animations: Vec<GltfAnimation>,
+    /// Every node of the document, indexed by glTF node index.
+    pub nodes: Vec<GltfNodeTransform>,
+    pub skins: Vec<GltfSkin>,
     pub source_path: PathBuf,
 }
 
@@ -57,9 +64,32 @@ pub struct GltfNode {
     pub transform: Mat4,
     pub mesh_index: Option<usize>,
     pub material_index: Option<usize>,
+    /// glTF node index (`None` for the synthetic root).
+    pub node_index: Option<usize>,
+    pub skin_index: Option<usize>,
     pub children: Vec<GltfNode>,
 }
 
+/// Rest pose of a node plus its parent, so a skeleton can be posed without
+/// walking the `GltfNode` tree.
+#[derive(Clone, Debug)]
+pub struct GltfNodeTransform {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+#[derive(Clone, Debug)]
+pub struct GltfSkin {
+    pub name: String,
+    /// Node index of each joint; `JOINTS_0` values index this list.
+    pub joints: Vec<usize>,
+    /// One per joint (identity when the file omits them).
+    pub inverse_bind_matrices: Vec<Mat4>,
+}
+
 #[derive(Clone, Debug)]
 pub struct GltfAnimation {
     pub name: String,
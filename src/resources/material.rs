@@ -1,19 +1,48 @@
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::ReactorResult;
 use crate::core::VulkanContext;
+use crate::graphics::buffer::Buffer;
+use crate::graphics::descriptors::{DescriptorAllocator, DescriptorBinding, PooledDescriptorSet};
 use crate::graphics::pipeline::{Pipeline, PipelineConfig};
+use crate::graphics::uniform_buffer::MaterialUniformData;
 use crate::resources::texture::Texture;
 use ash::vk;
-use std::sync::Arc;
+use gpu_allocator::vulkan::Allocator;
+use std::sync::{Arc, Mutex};
+
+/// Set del UBO de parámetros del material (`MaterialUniformData`).
+pub const MATERIAL_UNIFORM_SET: u32 = 0;
+/// Binding del UBO dentro de `MATERIAL_UNIFORM_SET`, después de las cuatro
+/// texturas de los materiales PBR (bindings 0-3).
+pub const MATERIAL_UNIFORM_BINDING: u32 = 4;
+
+/// Binding del UBO de parámetros para el layout de set 0.
+pub fn material_uniform_binding() -> DescriptorBinding {
+    DescriptorBinding {
+        binding: MATERIAL_UNIFORM_BINDING,
+        descriptor_type: vk::DescriptorType::UNIFORM_BUFFER,
+        count: 1,
+        stage_flags: vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+    }
+}
 
 pub struct Material {
     pub pipeline: Arc<Pipeline>,
     pub descriptor_set: Option<vk::DescriptorSet>,
     pub descriptor_pool: Option<vk::DescriptorPool>,
     pub descriptor_layout: Option<vk::DescriptorSetLayout>,
+    /// Set 0 taken from the shared `DescriptorAllocator`; returned on drop.
+    pub(crate) pooled_set: Option<PooledDescriptorSet>,
     pub kept_textures: Vec<crate::resources::texture::Texture>,
     pub uses_ibl: bool,
     pub has_shadow_set: bool,
+    pub has_light_set: bool,
+    /// UBO con `params`; `None` si el material no tiene set 0 propio.
+    pub(crate) uniform_buffer: Option<Buffer>,
+    /// Set 0 solo con el UBO, sin texturas.
+    pub(crate) uniform_only_set: bool,
+    params: Mutex<MaterialUniformData>,
     pub(crate) device: Option<ArcDevice>,
 }
 
@@ -59,15 +88,18 @@ impl Material {
         color_format: vk::Format,
         depth_format: Option<vk::Format>,
     ) -> ReactorResult<Self> {
-        let pipeline = Pipeline::new(
+        let pipeline = Pipeline::with_config_and_cache(
             &ctx.device,
             render_pass,
             vert_code,
             frag_code,
             width,
             height,
+            &PipelineConfig::default(),
+            &[],
             color_format,
             depth_format,
+            ctx.pipeline_cache(),
         )?;
 
         Ok(Self {
@@ -75,9 +107,14 @@ impl Material {
             descriptor_set: None,
             descriptor_pool: None,
             descriptor_layout: None,
+            pooled_set: None,
             kept_textures: Vec::new(),
             uses_ibl: false,
             has_shadow_set: false,
+            has_light_set: false,
+            uniform_buffer: None,
+            uniform_only_set: false,
+            params: Mutex::new(MaterialUniformData::default()),
             device: None,
         })
     }
@@ -94,7 +131,7 @@ impl Material {
         color_format: vk::Format,
         depth_format: Option<vk::Format>,
     ) -> ReactorResult<Self> {
-        let pipeline = Pipeline::with_config(
+        let pipeline = Pipeline::with_config_and_cache(
             &ctx.device,
             render_pass,
             vert_code,
@@ -105,6 +142,7 @@ impl Material {
             descriptor_layouts,
             color_format,
             depth_format,
+            ctx.pipeline_cache(),
         )?;
 
         Ok(Self {
@@ -112,16 +150,24 @@ impl Material {
             descriptor_set: None,
             descriptor_pool: None,
             descriptor_layout: None,
+            pooled_set: None,
             kept_textures: Vec::new(),
             uses_ibl: false,
             has_shadow_set: false,
+            has_light_set: false,
+            uniform_buffer: None,
+            uniform_only_set: false,
+            params: Mutex::new(MaterialUniformData::default()),
             device: None,
         })
     }
 
-    /// Create a textured material with a diffuse texture
+    /// Create a textured material with a diffuse texture. The layout and
+    /// set come from the shared `descriptors` allocator.
+    #[allow(clippy::too_many_arguments)]
     pub fn with_texture(
         ctx: &VulkanContext,
+        descriptors: &Arc<Mutex<DescriptorAllocator>>,
         render_pass: Option<vk::RenderPass>,
         vert_code: &[u32],
         frag_code: &[u32],
@@ -132,44 +178,14 @@ impl Material {
         color_format: vk::Format,
         depth_format: Option<vk::Format>,
     ) -> ReactorResult<Self> {
-        // Create descriptor set layout for texture sampler
-        let sampler_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(0)
-            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
-
-        let bindings = [sampler_binding];
-        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
-            .bindings(&bindings)
-            .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL);
-
-        let descriptor_layout = unsafe {
-            ctx.device
-                .create_descriptor_set_layout(&layout_info, None)?
-        };
-
-        // Create descriptor pool
-        let pool_size = vk::DescriptorPoolSize::default()
-            .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1);
-
-        let pool_sizes = [pool_size];
-        let pool_info = vk::DescriptorPoolCreateInfo::default()
-            .pool_sizes(&pool_sizes)
-            .max_sets(1)
-            .flags(vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND);
-
-        let descriptor_pool = unsafe { ctx.device.create_descriptor_pool(&pool_info, None)? };
-
-        // Allocate descriptor set
-        let layouts = [descriptor_layout];
-        let alloc_info = vk::DescriptorSetAllocateInfo::default()
-            .descriptor_pool(descriptor_pool)
-            .set_layouts(&layouts);
-
-        let descriptor_sets = unsafe { ctx.device.allocate_descriptor_sets(&alloc_info)? };
-        let descriptor_set = descriptor_sets[0];
+        let descriptor_layout = descriptors.lock().unwrap().layout(&[DescriptorBinding {
+            binding: 0,
+            descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+            count: 1,
+            stage_flags: vk::ShaderStageFlags::FRAGMENT,
+        }])?;
+        let pooled_set = PooledDescriptorSet::new(descriptors, descriptor_layout)?;
+        let descriptor_set = pooled_set.handle();
 
         // Update descriptor set with texture
         let image_info = vk::DescriptorImageInfo::default()
@@ -196,7 +212,7 @@ impl Material {
             ..PipelineConfig::default()
         };
 
-        let pipeline = Pipeline::with_config(
+        let pipeline = Pipeline::with_config_and_cache(
             &ctx.device,
             render_pass,
             vert_code,
@@ -207,20 +223,33 @@ impl Material {
             &[descriptor_layout],
             color_format,
             depth_format,
+            ctx.pipeline_cache(),
         )?;
 
         Ok(Self {
             pipeline: Arc::new(pipeline),
             descriptor_set: Some(descriptor_set),
-            descriptor_pool: Some(descriptor_pool),
-            descriptor_layout: Some(descriptor_layout),
+            descriptor_pool: None,
+            descriptor_layout: None,
+            pooled_set: Some(pooled_set),
             kept_textures: Vec::new(),
             uses_ibl: false,
             has_shadow_set: false,
-            device: Some(ctx.device.clone()),
+            has_light_set: false,
+            uniform_buffer: None,
+            uniform_only_set: false,
+            params: Mutex::new(MaterialUniformData::default()),
+            device: None,
         })
     }
 
+    /// Use a set from the shared allocator as set 0.
+    pub fn with_pooled_set(mut self, set: PooledDescriptorSet) -> Self {
+        self.descriptor_set = Some(set.handle());
+        self.pooled_set = Some(set);
+        self
+    }
+
     pub fn with_kept_texture(mut self, texture: crate::resources::texture::Texture) -> Self {
         self.kept_textures.push(texture);
         self
@@ -231,6 +260,103 @@ impl Material {
         self
     }
 
+    /// Crea el UBO de parámetros, lo escribe en `MATERIAL_UNIFORM_BINDING` de
+    /// `set` (cuyo layout debe incluir `material_uniform_binding`) y sube los
+    /// parámetros actuales.
+    pub(crate) fn attach_uniforms(
+        &mut self,
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        set: vk::DescriptorSet,
+    ) -> ReactorResult<()> {
+        let size = std::mem::size_of::<MaterialUniformData>() as u64;
+        let buffer = Buffer::new_uniform(ctx, allocator, size)?;
+        let buffer_info = [vk::DescriptorBufferInfo::default().buffer(buffer.handle).offset(0).range(size)];
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(set)
+            .dst_binding(MATERIAL_UNIFORM_BINDING)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .buffer_info(&buffer_info);
+        unsafe { ctx.device.update_descriptor_sets(&[write], &[]) };
+
+        buffer.write(std::slice::from_ref(&*self.params.lock().unwrap()));
+        self.uniform_buffer = Some(buffer);
+        Ok(())
+    }
+
+    /// Parámetros actuales (color base, metallic, roughness, emisivo).
+    pub fn params(&self) -> MaterialUniformData {
+        *self.params.lock().unwrap()
+    }
+
+    /// Reemplaza los parámetros y los sube al UBO, sin recrear el pipeline.
+    /// Los frames grabados después los ven; uno todavía en vuelo puede leer
+    /// ya los nuevos valores.
+    pub fn set_params(&self, params: MaterialUniformData) {
+        let mut current = self.params.lock().unwrap();
+        *current = params;
+        if let Some(buffer) = &self.uniform_buffer {
+            buffer.write(std::slice::from_ref(&*current));
+        }
+    }
+
+    /// Cambia solo el color base (RGBA lineal).
+    pub fn set_color(&self, color: [f32; 4]) {
+        self.set_params(MaterialUniformData { base_color: color, ..self.params() });
+    }
+
+    /// `color` multiplicado por el color base. Los shaders integrados no
+    /// leen el UBO: reciben este valor en `push.color`.
+    pub fn tint(&self, color: glam::Vec4) -> glam::Vec4 {
+        color * glam::Vec4::from_array(self.params.lock().unwrap().base_color)
+    }
+
+    /// `true` si los parámetros llegan al shader por el UBO de set 0.
+    pub fn has_uniforms(&self) -> bool {
+        self.uniform_buffer.is_some()
+    }
+
+    /// Recompila el pipeline con nuevos SPIR-V manteniendo layout, estado y formatos.
+    ///
+    /// No espera a la GPU: el pipeline viejo se destruye cuando el último
+    /// frame en vuelo que lo usa termina (`FrameTimeline`). Si la creación
+    /// falla se conserva el pipeline anterior y el error queda también en el
+    /// last-error global.
+    pub fn reload_shaders(
+        &mut self,
+        ctx: &VulkanContext,
+        render_pass: Option<vk::RenderPass>,
+        vert_spv: &[u32],
+        frag_spv: &[u32],
+    ) -> ReactorResult<()> {
+        match self.pipeline.rebuild_with_shaders(render_pass, vert_spv, frag_spv, ctx.pipeline_cache()) {
+            Ok(pipeline) => {
+                self.pipeline = Arc::new(pipeline);
+                Ok(())
+            }
+            Err(e) => {
+                crate::core::error::set_last_error(crate::core::error::ReactorError::new(e.code, e.message.clone()));
+                Err(e)
+            }
+        }
+    }
+
+    /// `true` si se dibuja en la pasada transparente, ordenado de atrás hacia delante.
+    pub fn is_transparent(&self) -> bool {
+        self.pipeline.is_transparent()
+    }
+
+    /// Activa o quita la mezcla con alpha premultiplicado (sin escribir
+    /// depth). Reconstruye el pipeline con los mismos shaders; el viejo se
+    /// destruye diferido como en `reload_shaders`.
+    pub fn set_transparent(&mut self, _ctx: &VulkanContext, transparent: bool) -> ReactorResult<()> {
+        if self.is_transparent() == transparent {
+            return Ok(());
+        }
+        self.pipeline = Arc::new(self.pipeline.rebuild_with_transparency(transparent)?);
+        Ok(())
+    }
+
     pub fn bind(&self, device: &ash::Device, command_buffer: vk::CommandBuffer) {
         unsafe {
             device.cmd_bind_pipeline(
@@ -251,6 +377,14 @@ impl Material {
             }
         }
     }
+
+    /// Label the pipeline and descriptor set. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        self.pipeline.set_debug_name(namer, name);
+        if let Some(set) = self.descriptor_set {
+            namer.name_descriptor_set(set, &format!("{name} (set 0)"));
+        }
+    }
 }
 
 impl Drop for Material {
@@ -276,6 +410,9 @@ pub struct MaterialBuilder {
     pub descriptor_layouts: Vec<vk::DescriptorSetLayout>,
     pub uses_ibl: bool,
     pub has_shadow_set: bool,
+    pub has_light_set: bool,
+    /// Valores iniciales del UBO del material.
+    pub params: MaterialUniformData,
 }
 
 impl MaterialBuilder {
@@ -287,6 +424,8 @@ impl MaterialBuilder {
             descriptor_layouts: Vec::new(),
             uses_ibl: false,
             has_shadow_set: false,
+            has_light_set: false,
+            params: MaterialUniformData::default(),
         }
     }
 
@@ -311,11 +450,27 @@ impl MaterialBuilder {
         self
     }
 
+    pub fn instanced(mut self) -> Self {
+        self.config.instanced = true;
+        self
+    }
+
+    pub fn skinned(mut self) -> Self {
+        self.config.skinned = true;
+        self
+    }
+
     pub fn blend(mut self) -> Self {
         self.config.blend_enable = true;
         self
     }
 
+    /// Alpha premultiplicado sin escribir depth (`PipelineConfig::transparent`).
+    pub fn transparent(mut self) -> Self {
+        self.config.transparent = true;
+        self
+    }
+
     pub fn msaa(mut self, samples: vk::SampleCountFlags) -> Self {
         self.config.samples = samples;
         self
@@ -341,6 +496,37 @@ impl MaterialBuilder {
         self
     }
 
+    pub fn has_light_set(mut self, v: bool) -> Self {
+        self.has_light_set = v;
+        self
+    }
+
+    pub fn params(mut self, params: MaterialUniformData) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn base_color(mut self, color: [f32; 4]) -> Self {
+        self.params.base_color = color;
+        self
+    }
+
+    pub fn metallic(mut self, metallic: f32) -> Self {
+        self.params.metallic = metallic;
+        self
+    }
+
+    pub fn roughness(mut self, roughness: f32) -> Self {
+        self.params.roughness = roughness;
+        self
+    }
+
+    pub fn emissive(mut self, color: [f32; 3], strength: f32) -> Self {
+        self.params.emissive_color = [color[0], color[1], color[2], 1.0];
+        self.params.emissive_strength = strength;
+        self
+    }
+
     pub fn build(
         self,
         ctx: &VulkanContext,
@@ -364,6 +550,8 @@ impl MaterialBuilder {
         )?;
         mat.uses_ibl = self.uses_ibl;
         mat.has_shadow_set = self.has_shadow_set;
+        mat.has_light_set = self.has_light_set;
+        *mat.params.get_mut().unwrap() = self.params;
         Ok(mat)
     }
 }
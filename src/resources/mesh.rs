@@ -1,26 +1,361 @@
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::ReactorResult;
 use crate::core::VulkanContext;
 use crate::graphics::buffer::Buffer;
-use crate::resources::vertex::Vertex;
+use crate::resources::vertex::{Vertex, VertexSkinned};
+use crate::systems::physics::AABB;
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use gpu_allocator::MemoryLocation;
 use std::sync::{Arc, Mutex};
 
+/// A mesh whose GPU buffers exist but still hold no data, plus the staging
+/// buffers to copy from. Staging buffers must outlive the copy.
+pub(crate) struct StagedMesh {
+    pub mesh: Mesh,
+    staging_vertex: Buffer,
+    staging_index: Buffer,
+}
+
+impl StagedMesh {
+    /// Record the staging → GPU copies into `command_buffer`.
+    pub fn record_copy(&self, device: &ash::Device, command_buffer: vk::CommandBuffer) {
+        let vertex_region = vk::BufferCopy { src_offset: 0, dst_offset: 0, size: self.mesh.vertex_buffer.size };
+        let index_region = vk::BufferCopy { src_offset: 0, dst_offset: 0, size: self.mesh.index_buffer.size };
+        unsafe {
+            device.cmd_copy_buffer(
+                command_buffer,
+                self.staging_vertex.handle,
+                self.mesh.vertex_buffer.handle,
+                &[vertex_region],
+            );
+            device.cmd_copy_buffer(
+                command_buffer,
+                self.staging_index.handle,
+                self.mesh.index_buffer.handle,
+                &[index_region],
+            );
+        }
+    }
+}
+
+/// How a mesh's buffers are placed, chosen at creation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MeshUsage {
+    /// Device-local buffers filled through a staging copy. Updates block on
+    /// the graphics queue.
+    #[default]
+    Static,
+    /// Host-visible buffers written in place, for geometry re-uploaded every
+    /// few frames (terrain editing, cloth, morphs).
+    Dynamic,
+}
+
 pub struct Mesh {
     pub vertex_buffer: Buffer,
     pub index_buffer: Buffer,
     pub vertex_count: u32,
     pub index_count: u32,
+    /// Local-space bounds of the vertex positions, computed at creation and
+    /// by `update_vertices`.
+    pub bounds: AABB,
+    pub usage: MeshUsage,
 }
 
 impl Mesh {
+    /// Caja local de las posiciones de los vértices.
+    pub fn bounds(&self) -> AABB {
+        self.bounds
+    }
+
+    /// Caja en mundo de la malla colocada con `transform`: contiene las ocho
+    /// esquinas transformadas de `bounds` (ver `AABB::transformed_by`).
+    pub fn world_bounds(&self, transform: &glam::Mat4) -> AABB {
+        self.bounds.transformed_by(transform)
+    }
+
     pub fn new(
         ctx: &VulkanContext,
         allocator: &Arc<Mutex<Allocator>>,
         vertices: &[Vertex],
         indices: &[u32],
     ) -> ReactorResult<Self> {
+        let bounds = AABB::from_points(vertices.iter().map(|v| glam::Vec3::from_array(v.position)));
+        Self::upload(ctx, allocator, vertices, indices, bounds)
+    }
+
+    /// Mesh in host-visible memory (`MeshUsage::Dynamic`), meant to be
+    /// rewritten with `update_vertices` / `update_indices`.
+    pub fn new_dynamic(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        vertices: &[Vertex],
+        indices: &[u32],
+    ) -> ReactorResult<Self> {
+        let vertex_buffer = Self::create_buffer(
+            ctx,
+            allocator,
+            MeshUsage::Dynamic,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+            std::mem::size_of_val(vertices) as u64,
+            &[],
+        )?;
+        let index_buffer = Self::create_buffer(
+            ctx,
+            allocator,
+            MeshUsage::Dynamic,
+            vk::BufferUsageFlags::INDEX_BUFFER,
+            std::mem::size_of_val(indices) as u64,
+            &[],
+        )?;
+        vertex_buffer.write(vertices);
+        index_buffer.write(indices);
+        Ok(Self {
+            vertex_buffer,
+            index_buffer,
+            vertex_count: vertices.len() as u32,
+            index_count: indices.len() as u32,
+            bounds: Self::vertex_bounds(vertices),
+            usage: MeshUsage::Dynamic,
+        })
+    }
+
+    /// Replaces the vertex data, reusing the vertex buffer when `vertices`
+    /// fits and growing it otherwise (the old buffer is freed once the frames
+    /// in flight are done with it). No frame in flight may be drawing this
+    /// mesh; `Reactor::update_mesh` takes care of that for shared meshes.
+    pub fn update_vertices(
+        &mut self,
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        vertices: &[Vertex],
+    ) -> ReactorResult<()> {
+        Self::update_buffer(
+            ctx,
+            allocator,
+            self.usage,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+            &mut self.vertex_buffer,
+            vertices,
+        )?;
+        self.vertex_count = vertices.len() as u32;
+        self.bounds = Self::vertex_bounds(vertices);
+        Ok(())
+    }
+
+    /// Replaces the index data; same rules as `update_vertices`.
+    pub fn update_indices(
+        &mut self,
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        indices: &[u32],
+    ) -> ReactorResult<()> {
+        Self::update_buffer(
+            ctx,
+            allocator,
+            self.usage,
+            vk::BufferUsageFlags::INDEX_BUFFER,
+            &mut self.index_buffer,
+            indices,
+        )?;
+        self.index_count = indices.len() as u32;
+        Ok(())
+    }
+
+    /// Whether `vertices` and `indices` fit the current buffers unchanged.
+    pub fn fits(&self, vertices: &[Vertex], indices: &[u32]) -> bool {
+        std::mem::size_of_val(vertices) as u64 <= self.vertex_buffer.size
+            && std::mem::size_of_val(indices) as u64 <= self.index_buffer.size
+    }
+
+    /// Decimated copy of a triangle list with about `ratio` (0..=1) of its
+    /// triangles, for building coarser LODs (`SceneObject::add_lod`).
+    ///
+    /// Vertex clustering: vertices are snapped to a grid over the bounds and
+    /// merged per cell (position, color and UV averaged); triangles that
+    /// collapse or repeat are dropped. The grid is the finest one that stays
+    /// within the budget. Cheap and shape-preserving at a distance, but it
+    /// welds UV seams and hard edges, so it is not for close-up levels.
+    pub fn simplify(vertices: &[Vertex], indices: &[u32], ratio: f32) -> (Vec<Vertex>, Vec<u32>) {
+        if ratio >= 1.0 || vertices.is_empty() {
+            return (vertices.to_vec(), indices.to_vec());
+        }
+        let budget = ((indices.len() / 3) as f32 * ratio.max(0.0)) as usize;
+        let bounds = Self::vertex_bounds(vertices);
+        let (mut low, mut high) = (1, 1024);
+        let mut best = Self::cluster_vertices(vertices, indices, &bounds, low);
+        while low < high {
+            let resolution = (low + high + 1) / 2;
+            let candidate = Self::cluster_vertices(vertices, indices, &bounds, resolution);
+            if candidate.1.len() / 3 <= budget {
+                best = candidate;
+                low = resolution;
+            } else {
+                high = resolution - 1;
+            }
+        }
+        best
+    }
+
+    /// One `simplify` pass with `resolution` cells along each axis.
+    fn cluster_vertices(vertices: &[Vertex], indices: &[u32], bounds: &AABB, resolution: u32) -> (Vec<Vertex>, Vec<u32>) {
+        let cell_size = (bounds.size() / resolution as f32).max(glam::Vec3::splat(f32::EPSILON));
+        let last_cell = glam::UVec3::splat(resolution - 1);
+        let mut cells = std::collections::HashMap::new();
+        // Suma de posición, color y UV más el número de vértices de cada celda.
+        let mut sums: Vec<(glam::Vec3, glam::Vec3, glam::Vec2, f32)> = Vec::new();
+        let remap: Vec<u32> = vertices
+            .iter()
+            .map(|vertex| {
+                let position = glam::Vec3::from_array(vertex.position);
+                let cell = ((position - bounds.min) / cell_size).as_uvec3().min(last_cell);
+                let cluster = *cells.entry(cell).or_insert_with(|| {
+                    sums.push((glam::Vec3::ZERO, glam::Vec3::ZERO, glam::Vec2::ZERO, 0.0));
+                    sums.len() as u32 - 1
+                });
+                let sum = &mut sums[cluster as usize];
+                sum.0 += position;
+                sum.1 += glam::Vec3::from_array(vertex.color);
+                sum.2 += glam::Vec2::from_array(vertex.uv);
+                sum.3 += 1.0;
+                cluster
+            })
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        let mut simplified_indices = Vec::new();
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|corner| remap[triangle[corner] as usize]);
+            if a == b || b == c || a == c {
+                continue;
+            }
+            // El mismo triángulo rotado cuenta como repetido; el winding se conserva.
+            let key = match a.min(b).min(c) {
+                min if min == a => [a, b, c],
+                min if min == b => [b, c, a],
+                _ => [c, a, b],
+            };
+            if seen.insert(key) {
+                simplified_indices.extend_from_slice(&[a, b, c]);
+            }
+        }
+
+        let simplified_vertices = sums
+            .into_iter()
+            .map(|(position, color, uv, count)| Vertex::new(position / count, color / count, uv / count))
+            .collect();
+        (simplified_vertices, simplified_indices)
+    }
+
+    fn vertex_bounds(vertices: &[Vertex]) -> AABB {
+        AABB::from_points(vertices.iter().map(|v| glam::Vec3::from_array(v.position)))
+            .unwrap_or(AABB::new(glam::Vec3::ZERO, glam::Vec3::ZERO))
+    }
+
+    fn update_buffer<T: Copy>(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        usage: MeshUsage,
+        kind: vk::BufferUsageFlags,
+        buffer: &mut Buffer,
+        data: &[T],
+    ) -> ReactorResult<()> {
+        let size = std::mem::size_of_val(data) as u64;
+        if size == 0 {
+            return Ok(());
+        }
+        if size > buffer.size {
+            // Dropping the old buffer goes through the deferred deletion queue.
+            *buffer = Self::create_buffer(ctx, allocator, usage, kind, size, &[])?;
+        }
+        match usage {
+            MeshUsage::Dynamic => buffer.write(data),
+            MeshUsage::Static => {
+                let staging = Buffer::new_staging(ctx, allocator.clone(), size)?;
+                staging.write(data);
+                Self::copy_buffer(ctx, staging.handle, buffer.handle, size)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Vertex or index buffer (`kind`) for a mesh of the given usage.
+    fn create_buffer(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        usage: MeshUsage,
+        kind: vk::BufferUsageFlags,
+        size: u64,
+        queue_families: &[u32],
+    ) -> ReactorResult<Buffer> {
+        let blas_input = if ctx.ray_query {
+            vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
+        } else {
+            vk::BufferUsageFlags::empty()
+        };
+        let location = match usage {
+            MeshUsage::Static => MemoryLocation::GpuOnly,
+            MeshUsage::Dynamic => MemoryLocation::CpuToGpu,
+        };
+        Buffer::new_shared(
+            ctx,
+            allocator.clone(),
+            size,
+            kind | vk::BufferUsageFlags::TRANSFER_DST
+                | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS
+                | blas_input,
+            location,
+            queue_families,
+        )
+    }
+
+    /// Mesh from `VertexSkinned` vertices, for `skinned` pipelines. Bounds
+    /// cover the rest pose only.
+    pub fn new_skinned(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        vertices: &[VertexSkinned],
+        indices: &[u32],
+    ) -> ReactorResult<Self> {
+        let bounds = AABB::from_points(vertices.iter().map(|v| glam::Vec3::from_array(v.position)));
+        Self::upload(ctx, allocator, vertices, indices, bounds)
+    }
+
+    fn upload<V: Copy>(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        vertices: &[V],
+        indices: &[u32],
+        bounds: Option<AABB>,
+    ) -> ReactorResult<Self> {
+        let staged = Self::stage(ctx, allocator, vertices, indices, bounds, &[])?;
+        let mesh = &staged.mesh;
+        Self::copy_buffer(
+            ctx,
+            staged.staging_vertex.handle,
+            mesh.vertex_buffer.handle,
+            mesh.vertex_buffer.size,
+        )?;
+        Self::copy_buffer(
+            ctx,
+            staged.staging_index.handle,
+            mesh.index_buffer.handle,
+            mesh.index_buffer.size,
+        )?;
+        Ok(staged.mesh)
+    }
+
+    /// Fill staging buffers and create the GPU buffers, without copying yet.
+    /// `queue_families` lists the families that will touch the GPU buffers
+    /// (see `Buffer::new_shared`).
+    pub(crate) fn stage<V: Copy>(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        vertices: &[V],
+        indices: &[u32],
+        bounds: Option<AABB>,
+        queue_families: &[u32],
+    ) -> ReactorResult<StagedMesh> {
         let vertex_size = std::mem::size_of_val(vertices) as u64;
         let index_size = std::mem::size_of_val(indices) as u64;
 
@@ -46,40 +381,34 @@ impl Mesh {
         staging_index.write(indices);
 
         // Create GPU Buffers
-        let vertex_buffer = Buffer::new(
+        let vertex_buffer = Self::create_buffer(
             ctx,
-            allocator.clone(),
+            allocator,
+            MeshUsage::Static,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
             vertex_size,
-            vk::BufferUsageFlags::VERTEX_BUFFER
-                | vk::BufferUsageFlags::TRANSFER_DST
-                | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
-            MemoryLocation::GpuOnly,
+            queue_families,
         )?;
-
-        let index_buffer = Buffer::new(
+        let index_buffer = Self::create_buffer(
             ctx,
-            allocator.clone(),
+            allocator,
+            MeshUsage::Static,
+            vk::BufferUsageFlags::INDEX_BUFFER,
             index_size,
-            vk::BufferUsageFlags::INDEX_BUFFER
-                | vk::BufferUsageFlags::TRANSFER_DST
-                | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
-            MemoryLocation::GpuOnly,
-        )?;
-
-        // Copy from Staging to GPU
-        Self::copy_buffer(
-            ctx,
-            staging_vertex.handle,
-            vertex_buffer.handle,
-            vertex_size,
+            queue_families,
         )?;
-        Self::copy_buffer(ctx, staging_index.handle, index_buffer.handle, index_size)?;
 
-        Ok(Self {
-            vertex_buffer,
-            index_buffer,
-            vertex_count: vertices.len() as u32,
-            index_count: indices.len() as u32,
+        Ok(StagedMesh {
+            mesh: Self {
+                vertex_buffer,
+                index_buffer,
+                vertex_count: vertices.len() as u32,
+                index_count: indices.len() as u32,
+                bounds: bounds.unwrap_or(AABB::new(glam::Vec3::ZERO, glam::Vec3::ZERO)),
+                usage: MeshUsage::Static,
+            },
+            staging_vertex,
+            staging_index,
         })
     }
 
@@ -154,66 +483,39 @@ impl Mesh {
             device.cmd_draw_indexed(command_buffer, self.index_count, instance_count, 0, 0, 0);
         }
     }
+
+    /// Label the vertex and index buffers. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        self.vertex_buffer.set_debug_name(namer, &format!("{name} (vertices)"));
+        self.index_buffer.set_debug_name(namer, &format!("{name} (indices)"));
+    }
+
+    /// `set_debug_name` with the next `{prefix}_N` label, only when the
+    /// context can name objects.
+    pub(crate) fn set_auto_debug_name(&self, ctx: &VulkanContext, prefix: &str) {
+        let namer = ctx.debug_namer();
+        if namer.is_active() {
+            self.set_debug_name(namer, &crate::core::debug_utils::next_object_name(prefix));
+        }
+    }
 }
 
 // Primitive mesh generators
 impl Mesh {
+    /// Cubo unitario con 24 vértices: normales y UVs por cara, winding CCW.
     pub fn cube(ctx: &VulkanContext, allocator: &Arc<Mutex<Allocator>>) -> ReactorResult<Self> {
-        let vertices = [
-            // Front
-            Vertex::new(
-                glam::Vec3::new(-0.5, -0.5, 0.5),
-                glam::Vec3::new(0.0, 0.0, 1.0),
-                glam::Vec2::new(0.0, 0.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(0.5, -0.5, 0.5),
-                glam::Vec3::new(0.0, 0.0, 1.0),
-                glam::Vec2::new(1.0, 0.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(0.5, 0.5, 0.5),
-                glam::Vec3::new(0.0, 0.0, 1.0),
-                glam::Vec2::new(1.0, 1.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(-0.5, 0.5, 0.5),
-                glam::Vec3::new(0.0, 0.0, 1.0),
-                glam::Vec2::new(0.0, 1.0),
-            ),
-            // Back
-            Vertex::new(
-                glam::Vec3::new(0.5, -0.5, -0.5),
-                glam::Vec3::new(0.0, 0.0, -1.0),
-                glam::Vec2::new(0.0, 0.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(-0.5, -0.5, -0.5),
-                glam::Vec3::new(0.0, 0.0, -1.0),
-                glam::Vec2::new(1.0, 0.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(-0.5, 0.5, -0.5),
-                glam::Vec3::new(0.0, 0.0, -1.0),
-                glam::Vec2::new(1.0, 1.0),
-            ),
-            Vertex::new(
-                glam::Vec3::new(0.5, 0.5, -0.5),
-                glam::Vec3::new(0.0, 0.0, -1.0),
-                glam::Vec2::new(0.0, 1.0),
-            ),
-        ];
-
-        let indices = [
-            0, 1, 2, 2, 3, 0, // Front
-            4, 5, 6, 6, 7, 4, // Back
-            5, 0, 3, 3, 6, 5, // Left
-            1, 4, 7, 7, 2, 1, // Right
-            3, 2, 7, 7, 6, 3, // Top
-            5, 4, 1, 1, 0, 5, // Bottom
-        ];
+        Self::cube_sized(ctx, allocator, 1.0)
+    }
 
-        Self::new(ctx, allocator, &vertices, &indices)
+    pub fn cube_sized(
+        ctx: &VulkanContext,
+        allocator: &Arc<Mutex<Allocator>>,
+        size: f32,
+    ) -> ReactorResult<Self> {
+        let (vertices, indices) = crate::resources::primitives::Primitives::cube_sized(size);
+        let mesh = Self::new(ctx, allocator, &vertices, &indices)?;
+        mesh.set_auto_debug_name(ctx, "mesh_cube");
+        Ok(mesh)
     }
 
     pub fn quad(ctx: &VulkanContext, allocator: &Arc<Mutex<Allocator>>) -> ReactorResult<Self> {
@@ -242,6 +544,31 @@ impl Mesh {
 
         let indices = [0, 1, 2, 2, 3, 0];
 
-        Self::new(ctx, allocator, &vertices, &indices)
+        let mesh = Self::new(ctx, allocator, &vertices, &indices)?;
+        mesh.set_auto_debug_name(ctx, "mesh_quad");
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::primitives::Primitives;
+
+    #[test]
+    fn simplify_stays_within_budget_and_keeps_the_shape() {
+        let (vertices, indices) = Primitives::sphere(48, 32);
+        let triangles = indices.len() / 3;
+
+        let (coarse_vertices, coarse_indices) = Mesh::simplify(&vertices, &indices, 0.25);
+        let coarse_triangles = coarse_indices.len() / 3;
+        assert!(coarse_triangles <= triangles / 4, "{coarse_triangles} of {triangles}");
+        assert!(coarse_triangles > triangles / 20, "{coarse_triangles} of {triangles}");
+        assert!(coarse_indices.iter().all(|&index| (index as usize) < coarse_vertices.len()));
+        let size = Mesh::vertex_bounds(&coarse_vertices).size();
+        assert!(size.abs_diff_eq(Mesh::vertex_bounds(&vertices).size(), 0.2), "{size}");
+
+        let (same_vertices, same_indices) = Mesh::simplify(&vertices, &indices, 1.0);
+        assert_eq!((same_vertices.len(), same_indices), (vertices.len(), indices));
     }
 }
@@ -0,0 +1,267 @@
+// =============================================================================
+// MeshUploader — mesh creation off the main thread
+// =============================================================================
+// `Reactor::create_mesh` blocks on `queue_wait_idle` for every mesh, so a
+// level with hundreds of meshes stalls rendering for seconds. The uploader
+// moves that work to a worker thread: it fills the staging buffers, records
+// the copies in its own command pool and hands back a `MeshTicket`. A fence
+// per upload signals completion; `poll` returns the finished `Mesh`.
+//
+// Queues:
+// - With a dedicated transfer queue the worker submits there itself. Nothing
+//   else in the engine submits to that queue.
+// - Without one, recorded uploads wait until the next `poll` on the main
+//   thread and are submitted to the graphics queue there, since the render
+//   loop owns that queue.
+//
+// Ownership: when the transfer family differs from the graphics family the
+// GPU buffers are created with `CONCURRENT` sharing across both, so no
+// release/acquire barriers are needed before drawing. A mesh is only
+// returned after its fence has signaled, so the copy is complete by the time
+// the graphics queue can see it.
+// =============================================================================
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use ash::vk;
+use gpu_allocator::vulkan::Allocator;
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::core::VulkanContext;
+use crate::resources::mesh::{Mesh, StagedMesh};
+use crate::resources::vertex::Vertex;
+use crate::systems::physics::AABB;
+
+/// Handle to a mesh being uploaded. Redeem it with `MeshUploader::poll`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MeshTicket(u64);
+
+impl MeshTicket {
+    pub fn id(self) -> u64 {
+        self.0
+    }
+
+    pub fn from_id(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+struct UploadJob {
+    ticket: u64,
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+}
+
+/// Upload recorded by the worker, waiting for submission or for its fence.
+struct PendingUpload {
+    ticket: u64,
+    staged: StagedMesh,
+    command_buffer: vk::CommandBuffer,
+    fence: vk::Fence,
+}
+
+struct UploadState {
+    /// Worker-owned pool; locked for allocating, recording and freeing.
+    command_pool: vk::CommandPool,
+    /// Recorded but not submitted (no dedicated transfer queue).
+    to_submit: Vec<PendingUpload>,
+    in_flight: Vec<PendingUpload>,
+    finished: HashMap<u64, ReactorResult<Mesh>>,
+}
+
+pub struct MeshUploader {
+    ctx: VulkanContext,
+    jobs: Option<mpsc::Sender<UploadJob>>,
+    state: Arc<Mutex<UploadState>>,
+    worker: Option<thread::JoinHandle<()>>,
+    next_ticket: AtomicU64,
+}
+
+impl MeshUploader {
+    pub fn new(ctx: &VulkanContext, allocator: Arc<Mutex<Allocator>>) -> ReactorResult<Self> {
+        let dedicated_queue = ctx.transfer_queue;
+        let family = ctx.transfer_family();
+        let pool_info = vk::CommandPoolCreateInfo::default()
+            .queue_family_index(family)
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT | vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
+        let command_pool = unsafe { ctx.device.create_command_pool(&pool_info, None)? };
+
+        let state = Arc::new(Mutex::new(UploadState {
+            command_pool,
+            to_submit: Vec::new(),
+            in_flight: Vec::new(),
+            finished: HashMap::new(),
+        }));
+        let (jobs, receiver) = mpsc::channel::<UploadJob>();
+
+        let worker_ctx = ctx.clone();
+        let worker_state = state.clone();
+        let queue_families = [ctx.queue_family_index, family];
+        let worker = thread::Builder::new()
+            .name("reactor-mesh-upload".into())
+            .spawn(move || {
+                for job in receiver {
+                    let ticket = job.ticket;
+                    let result = record_upload(&worker_ctx, &allocator, &worker_state, job, &queue_families)
+                        .and_then(|upload| match dedicated_queue {
+                            Some(queue) => submit(&worker_ctx, queue, &upload).map(|_| upload),
+                            None => Ok(upload),
+                        });
+                    let mut state = worker_state.lock().unwrap();
+                    match result {
+                        Ok(upload) if dedicated_queue.is_some() => state.in_flight.push(upload),
+                        Ok(upload) => state.to_submit.push(upload),
+                        Err(e) => {
+                            state.finished.insert(ticket, Err(e));
+                        }
+                    }
+                }
+            })
+            .map_err(|e| ReactorError::with_source(ErrorCode::IoError, "Failed to spawn the mesh upload thread", e))?;
+
+        Ok(Self {
+            ctx: ctx.clone(),
+            jobs: Some(jobs),
+            state,
+            worker: Some(worker),
+            next_ticket: AtomicU64::new(1),
+        })
+    }
+
+    /// Queue a mesh for upload. The data is copied; the call does not touch
+    /// the GPU.
+    pub fn create_mesh(&self, vertices: &[Vertex], indices: &[u32]) -> MeshTicket {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        let job = UploadJob { ticket, vertices: vertices.to_vec(), indices: indices.to_vec() };
+        if let Some(jobs) = &self.jobs {
+            if jobs.send(job).is_err() {
+                let error = ReactorError::new(ErrorCode::InternalError, "The mesh upload thread has stopped");
+                self.state.lock().unwrap().finished.insert(ticket, Err(error));
+            }
+        }
+        MeshTicket(ticket)
+    }
+
+    /// Submit pending uploads and retire finished ones. Call from the thread
+    /// that renders (it may submit to the graphics queue).
+    pub fn pump(&self) {
+        let device = self.ctx.ash_device();
+        let mut state = self.state.lock().unwrap();
+
+        let to_submit = std::mem::take(&mut state.to_submit);
+        for upload in to_submit {
+            match submit(&self.ctx, self.ctx.graphics_queue, &upload) {
+                Ok(()) => state.in_flight.push(upload),
+                Err(e) => {
+                    free_upload(device, state.command_pool, &upload);
+                    state.finished.insert(upload.ticket, Err(e));
+                }
+            }
+        }
+
+        let in_flight = std::mem::take(&mut state.in_flight);
+        for upload in in_flight {
+            match unsafe { device.get_fence_status(upload.fence) } {
+                Ok(false) => state.in_flight.push(upload),
+                status => {
+                    free_upload(device, state.command_pool, &upload);
+                    let result = status
+                        .map(|_| upload.staged.mesh)
+                        .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "Mesh upload failed", e));
+                    state.finished.insert(upload.ticket, result);
+                }
+            }
+        }
+    }
+
+    /// The mesh for `ticket` once its copy has finished on the GPU, or `None`
+    /// while it is still in progress. Each ticket yields its mesh once.
+    pub fn poll(&self, ticket: MeshTicket) -> Option<ReactorResult<Mesh>> {
+        self.pump();
+        self.state.lock().unwrap().finished.remove(&ticket.0)
+    }
+
+    /// True once `poll` would return a result for `ticket`.
+    pub fn is_ready(&self, ticket: MeshTicket) -> bool {
+        self.pump();
+        self.state.lock().unwrap().finished.contains_key(&ticket.0)
+    }
+
+    /// Recorded uploads whose copy has not been retired by `pump` yet. Jobs
+    /// the worker has not picked up are not counted.
+    pub fn in_flight(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.to_submit.len() + state.in_flight.len()
+    }
+}
+
+fn record_upload(
+    ctx: &VulkanContext,
+    allocator: &Arc<Mutex<Allocator>>,
+    state: &Mutex<UploadState>,
+    job: UploadJob,
+    queue_families: &[u32],
+) -> ReactorResult<PendingUpload> {
+    let bounds = AABB::from_points(job.vertices.iter().map(|v| glam::Vec3::from_array(v.position)));
+    let staged = Mesh::stage(ctx, allocator, &job.vertices, &job.indices, bounds, queue_families)?;
+    staged.mesh.set_auto_debug_name(ctx, "mesh_async");
+
+    let device = ctx.ash_device();
+    let fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None)? };
+    let state = state.lock().unwrap();
+    let alloc_info = vk::CommandBufferAllocateInfo::default()
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_pool(state.command_pool)
+        .command_buffer_count(1);
+    let command_buffer = unsafe { device.allocate_command_buffers(&alloc_info)?[0] };
+    let begin_info = vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+    unsafe {
+        device.begin_command_buffer(command_buffer, &begin_info)?;
+        staged.record_copy(device, command_buffer);
+        device.end_command_buffer(command_buffer)?;
+    }
+    Ok(PendingUpload { ticket: job.ticket, staged, command_buffer, fence })
+}
+
+fn submit(ctx: &VulkanContext, queue: vk::Queue, upload: &PendingUpload) -> ReactorResult<()> {
+    let command_buffers = [upload.command_buffer];
+    let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+    unsafe { ctx.device.queue_submit(queue, &[submit_info], upload.fence) }
+        .map_err(|e| ReactorError::with_source(ErrorCode::VulkanSynchronization, "Mesh upload submit failed", e))
+}
+
+fn free_upload(device: &ash::Device, command_pool: vk::CommandPool, upload: &PendingUpload) {
+    unsafe {
+        device.free_command_buffers(command_pool, &[upload.command_buffer]);
+        device.destroy_fence(upload.fence, None);
+    }
+}
+
+impl Drop for MeshUploader {
+    fn drop(&mut self) {
+        // Closing the channel ends the worker once queued jobs are recorded.
+        self.jobs.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        let device = self.ctx.ash_device();
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let fences: Vec<vk::Fence> = state.in_flight.iter().map(|upload| upload.fence).collect();
+        if !fences.is_empty() {
+            unsafe {
+                let _ = device.wait_for_fences(&fences, true, u64::MAX);
+            }
+        }
+        let pool = state.command_pool;
+        for upload in state.to_submit.drain(..).chain(state.in_flight.drain(..)) {
+            free_upload(device, pool, &upload);
+        }
+        state.finished.clear();
+        unsafe { device.destroy_command_pool(pool, None) };
+    }
+}
@@ -23,9 +23,12 @@ pub mod gltf_loader;
 pub mod handle;
 pub mod material;
 pub mod mesh;
+pub mod mesh_upload;
 pub mod model;
 pub mod pbr_material;
 pub mod primitives;
+pub mod project_index;
+pub mod streaming;
 pub mod texture;
 pub mod vertex;
 
@@ -38,10 +41,11 @@ pub use asset_cooker::AssetCooker;
 pub use asset_database::{AssetDatabase, AssetDbStats, AssetMetadata, AssetType};
 pub use asset_hot_reload::{AssetHotReloadManager, HotReloadConfig, HotReloadStats};
 pub use asset_loader_queue::{AssetLoaderQueue, LoadPriority, LoaderQueueConfig, LoaderStats};
+pub use project_index::{AssetInfo, ProjectAsset, ProjectIndex};
 pub use font::FontAsset;
 pub use gltf_loader::{
-    GltfCacheStats, GltfLoader, GltfMaterialData, GltfMeshData, GltfModel, GltfNode,
-    GltfTextureData,
+    GltfAnimation, GltfCacheStats, GltfLoader, GltfMaterialData, GltfMeshData, GltfModel, GltfNode,
+    GltfSkin, GltfTextureData,
 };
 
 // Legacy / actualmente funcional
@@ -49,10 +53,12 @@ pub mod decal;
 pub use decal::Decal;
 pub use asset_manager::{AssetHandle, AssetManager, AssetState, AssetStats};
 pub use material::{Material, MaterialBuilder};
-pub use mesh::Mesh;
+pub use mesh::{Mesh, MeshUsage};
+pub use mesh_upload::{MeshTicket, MeshUploader};
 pub use model::{GltfData, Model, ModelBatch, ObjData};
-pub use pbr_material::{IBLEnvironment, IBLUniformData, PBRMaterial, PBRTextures, PBRUniformData};
+pub use pbr_material::{AlphaMode, IBLEnvironment, IBLUniformData, PBRMaterial, PBRTextures, PBRUniformData};
 pub use primitives::Primitives;
-pub use texture::Texture;
-pub use vertex::{InstanceData, Vertex, VertexPBR};
+pub use streaming::{LoadQueue, StreamId, StreamStats, StreamStatus, DEFAULT_STREAM_BUDGET_MS};
+pub use texture::{Cubemap, Texture, TextureOptions};
+pub use vertex::{InstanceData, Vertex, VertexPBR, VertexSkinned};
 
@@ -4,6 +4,18 @@
 
 use glam::Vec3;
 
+/// How the material's alpha is used (glTF `alphaMode`)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlphaMode {
+    #[default]
+    Opaque = 0,
+    /// Cut out below `alpha_cutoff`
+    Mask = 1,
+    /// Alpha blended, drawn in the transparent pass
+    Blend = 2,
+}
+
 /// PBR material properties
 #[derive(Clone, Debug)]
 pub struct PBRMaterial {
@@ -16,6 +28,7 @@ pub struct PBRMaterial {
     pub normal_scale: f32,
     pub alpha: f32,
     pub alpha_cutoff: f32,
+    pub alpha_mode: AlphaMode,
     pub double_sided: bool,
 }
 
@@ -31,6 +44,7 @@ impl Default for PBRMaterial {
             normal_scale: 1.0,
             alpha: 1.0,
             alpha_cutoff: 0.5,
+            alpha_mode: AlphaMode::Opaque,
             double_sided: false,
         }
     }
@@ -78,6 +92,7 @@ impl PBRMaterial {
             metallic: 0.0,
             roughness: 0.0,
             alpha: 0.3,
+            alpha_mode: AlphaMode::Blend,
             ..Default::default()
         }
     }
@@ -123,6 +138,11 @@ impl PBRMaterial {
         self.alpha = alpha.clamp(0.0, 1.0);
         self
     }
+
+    pub fn with_alpha_mode(mut self, mode: AlphaMode) -> Self {
+        self.alpha_mode = mode;
+        self
+    }
 }
 
 /// PBR uniform data for shaders
@@ -148,6 +148,15 @@ impl Primitives {
         (vertices, indices)
     }
 
+    /// Generate a cube of edge length `size`, same layout as [`Primitives::cube`]
+    pub fn cube_sized(size: f32) -> (Vec<Vertex>, Vec<u32>) {
+        let (mut vertices, indices) = Self::cube();
+        for v in &mut vertices {
+            v.position = (Vec3::from_array(v.position) * size).to_array();
+        }
+        (vertices, indices)
+    }
+
     /// Generate a UV sphere
     pub fn sphere(segments: u32, rings: u32) -> (Vec<Vertex>, Vec<u32>) {
         let mut vertices = Vec::new();
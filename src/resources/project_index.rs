@@ -0,0 +1,335 @@
+// =============================================================================
+// ProjectIndex — Índice de los assets de una carpeta de proyecto
+// =============================================================================
+// Lo que necesita un explorador de assets:
+// - Escaneo recursivo en un hilo aparte; los resultados llegan por canal y se
+//   recogen con `poll`, así una carpeta enorme no bloquea el frame
+// - Metadata leída con los loaders del engine (vértices de un OBJ, meshes de
+//   un glTF, dimensiones de una imagen) y cacheada en `.reactor-cache`: un
+//   re-escaneo sólo abre los archivos cuyo tamaño o fecha cambió
+// - Refresco incremental con `refresh` / `apply_event`, alimentado por el
+//   `AssetHotReloadManager` vigilando la raíz del proyecto
+//
+// Para instanciar un mesh del índice: `ReactorContext::load_scene_file`
+// (material por defecto para OBJ, materiales propios para glTF).
+// =============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::resources::asset_database::AssetType;
+use crate::resources::asset_hot_reload::AssetReloadEvent;
+
+/// Archivo de caché, en la raíz del proyecto.
+pub const PROJECT_CACHE_FILE: &str = ".reactor-cache";
+const PROJECT_CACHE_VERSION: u32 = 1;
+
+/// Extensiones que entran en el índice.
+pub const PROJECT_EXTENSIONS: &[&str] = &["obj", "gltf", "glb", "png", "jpg", "jpeg", "spv"];
+
+/// Metadata específica del tipo, para mostrar sin cargar el asset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AssetInfo {
+    Mesh { vertices: usize, triangles: usize },
+    Model { meshes: usize, nodes: usize },
+    Texture { width: u32, height: u32 },
+    Shader { words: usize },
+    /// El loader no pudo leerlo; el error se muestra en vez de ocultar el archivo.
+    Invalid { error: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectAsset {
+    /// Relativo a la raíz del proyecto.
+    pub path: PathBuf,
+    pub asset_type: AssetType,
+    pub file_size: u64,
+    /// Nanosegundos desde epoch; con `file_size`, la clave de la caché.
+    pub modified: u64,
+    pub info: AssetInfo,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProjectCache {
+    version: u32,
+    assets: Vec<ProjectAsset>,
+}
+
+pub struct ProjectIndex {
+    root: PathBuf,
+    assets: BTreeMap<PathBuf, ProjectAsset>,
+    scan: Option<Receiver<ProjectAsset>>,
+}
+
+impl ProjectIndex {
+    /// Abre `root` y empieza a escanearlo en segundo plano. Los assets van
+    /// apareciendo en `assets()` a medida que se llama a `poll`.
+    pub fn open(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let (tx, rx) = mpsc::channel();
+        let worker_root = root.clone();
+        std::thread::spawn(move || {
+            let cache = load_cache(&worker_root);
+            let mut scanned = Vec::new();
+            for path in walk(&worker_root) {
+                let Some(asset) = describe(&worker_root, &path, &cache) else { continue };
+                scanned.push(asset.clone());
+                if tx.send(asset).is_err() {
+                    return; // el índice se soltó: no hace falta terminar
+                }
+            }
+            if let Err(e) = save_cache(&worker_root, scanned) {
+                log::warn!("Project cache not written: {}", e);
+            }
+        });
+        Self { root, assets: BTreeMap::new(), scan: Some(rx) }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Recoge lo que el escaneo encontró desde la última llamada. Devuelve
+    /// cuántos assets llegaron.
+    pub fn poll(&mut self) -> usize {
+        let Some(scan) = &self.scan else { return 0 };
+        let mut received = 0;
+        loop {
+            match scan.try_recv() {
+                Ok(asset) => {
+                    self.assets.insert(asset.path.clone(), asset);
+                    received += 1;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.scan = None;
+                    break;
+                }
+            }
+        }
+        received
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some()
+    }
+
+    /// Ordenados por ruta.
+    pub fn assets(&self) -> impl Iterator<Item = &ProjectAsset> {
+        self.assets.values()
+    }
+
+    pub fn assets_of_type(&self, asset_type: AssetType) -> impl Iterator<Item = &ProjectAsset> {
+        self.assets.values().filter(move |asset| asset.asset_type == asset_type)
+    }
+
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&ProjectAsset> {
+        self.assets.get(path.as_ref())
+    }
+
+    /// Ruta absoluta (para los loaders) de un asset del índice.
+    pub fn absolute_path(&self, asset: &ProjectAsset) -> PathBuf {
+        self.root.join(&asset.path)
+    }
+
+    /// Vuelve a leer un archivo (absoluto o relativo a la raíz): lo añade,
+    /// lo actualiza o lo quita si ya no existe.
+    pub fn refresh(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let absolute = if path.is_absolute() { path.to_path_buf() } else { self.root.join(path) };
+        let Ok(relative) = absolute.strip_prefix(&self.root).map(Path::to_path_buf) else { return };
+        match describe(&self.root, &absolute, &HashMap::new()) {
+            Some(asset) if absolute.is_file() => { self.assets.insert(relative, asset); }
+            _ => { self.assets.remove(&relative); }
+        }
+    }
+
+    /// Aplica un evento del watcher (`AssetHotReloadManager` con la raíz en
+    /// `watch_dirs`).
+    pub fn apply_event(&mut self, event: &AssetReloadEvent) {
+        match event {
+            AssetReloadEvent::Reloaded { path, .. }
+            | AssetReloadEvent::AssetDiscovered { path, .. }
+            | AssetReloadEvent::AssetRemoved { path, .. } => self.refresh(path),
+            AssetReloadEvent::ReloadFailed { .. } => {}
+        }
+    }
+
+    /// Escribe la caché con el estado actual (p. ej. tras varios `refresh`).
+    pub fn save_cache(&self) -> ReactorResult<()> {
+        save_cache(&self.root, self.assets.values().cloned().collect())
+    }
+}
+
+/// Archivos con extensión de `PROJECT_EXTENSIONS`, saltando carpetas ocultas
+/// y `target`.
+fn walk(root: &Path) -> impl Iterator<Item = PathBuf> {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || {
+                let name = entry.file_name().to_string_lossy();
+                !name.starts_with('.') && name != "target"
+            }
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| extension(path).is_some_and(|ext| PROJECT_EXTENSIONS.contains(&ext.as_str())))
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase)
+}
+
+/// Entrada del índice para `path`: de la caché si tamaño y fecha coinciden,
+/// si no leyendo el archivo. `None` si no existe o no es de un tipo indexado.
+fn describe(root: &Path, path: &Path, cache: &HashMap<PathBuf, ProjectAsset>) -> Option<ProjectAsset> {
+    let ext = extension(path).filter(|ext| PROJECT_EXTENSIONS.contains(&ext.as_str()))?;
+    let relative = path.strip_prefix(root).ok()?.to_path_buf();
+    let meta = std::fs::metadata(path).ok()?;
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos() as u64);
+    if let Some(cached) = cache.get(&relative) {
+        if cached.file_size == meta.len() && cached.modified == modified {
+            return Some(cached.clone());
+        }
+    }
+    Some(ProjectAsset {
+        path: relative,
+        asset_type: AssetType::from_extension(&ext),
+        file_size: meta.len(),
+        modified,
+        info: read_info(path, &ext, meta.len()),
+    })
+}
+
+fn read_info(path: &Path, ext: &str, file_size: u64) -> AssetInfo {
+    let invalid = |error: String| AssetInfo::Invalid { error };
+    match ext {
+        "obj" => match crate::resources::model::ObjData::load(path) {
+            Ok(obj) => AssetInfo::Mesh { vertices: obj.vertex_count(), triangles: obj.triangle_count() },
+            Err(e) => invalid(e.to_string()),
+        },
+        // Sólo el JSON: los buffers no hacen falta para contar.
+        "gltf" | "glb" => match gltf::Gltf::open(path) {
+            Ok(gltf) => AssetInfo::Model { meshes: gltf.meshes().len(), nodes: gltf.nodes().len() },
+            Err(e) => invalid(e.to_string()),
+        },
+        // Sólo la cabecera.
+        "png" | "jpg" | "jpeg" => match image::image_dimensions(path) {
+            Ok((width, height)) => AssetInfo::Texture { width, height },
+            Err(e) => invalid(e.to_string()),
+        },
+        "spv" if file_size % 4 == 0 => AssetInfo::Shader { words: (file_size / 4) as usize },
+        "spv" => invalid(format!("SPIR-V size {} is not a multiple of 4", file_size)),
+        _ => invalid(format!("Unsupported extension .{}", ext)),
+    }
+}
+
+/// Caché anterior por ruta relativa; vacía si no hay o no se puede leer.
+fn load_cache(root: &Path) -> HashMap<PathBuf, ProjectAsset> {
+    let Ok(data) = std::fs::read(root.join(PROJECT_CACHE_FILE)) else { return HashMap::new() };
+    match serde_json::from_slice::<ProjectCache>(&data) {
+        Ok(cache) if cache.version == PROJECT_CACHE_VERSION => {
+            cache.assets.into_iter().map(|asset| (asset.path.clone(), asset)).collect()
+        }
+        Ok(_) => HashMap::new(),
+        Err(e) => {
+            log::warn!("Ignoring unreadable {}: {}", PROJECT_CACHE_FILE, e);
+            HashMap::new()
+        }
+    }
+}
+
+fn save_cache(root: &Path, assets: Vec<ProjectAsset>) -> ReactorResult<()> {
+    let cache = ProjectCache { version: PROJECT_CACHE_VERSION, assets };
+    let data = serde_json::to_vec(&cache)
+        .map_err(|e| ReactorError::internal(format!("Failed to serialize project cache: {}", e)))?;
+    let path = root.join(PROJECT_CACHE_FILE);
+    std::fs::write(&path, data)
+        .map_err(|e| ReactorError::with_source(ErrorCode::IoError, format!("Failed to write {}", path.display()), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("reactor_project_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("models")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("models/tri.obj"), "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        image::RgbaImage::new(3, 2).save(root.join("albedo.png")).unwrap();
+        std::fs::write(root.join("shader.spv"), [0u8; 12]).unwrap();
+        std::fs::write(root.join("notes.txt"), "not an asset").unwrap();
+        std::fs::write(root.join(".git/hidden.png"), "skipped").unwrap();
+        root
+    }
+
+    fn scan(root: &Path) -> ProjectIndex {
+        let mut index = ProjectIndex::open(root);
+        while index.is_scanning() {
+            index.poll();
+            std::thread::yield_now();
+        }
+        index
+    }
+
+    #[test]
+    fn scans_supported_files_with_loader_metadata() {
+        let root = project("scan");
+        let index = scan(&root);
+        let paths: Vec<_> = index.assets().map(|a| a.path.clone()).collect();
+        assert_eq!(paths, [PathBuf::from("albedo.png"), PathBuf::from("models/tri.obj"), PathBuf::from("shader.spv")]);
+        assert_eq!(index.get("albedo.png").unwrap().info, AssetInfo::Texture { width: 3, height: 2 });
+        assert_eq!(index.get("shader.spv").unwrap().info, AssetInfo::Shader { words: 3 });
+        assert!(matches!(index.get("models/tri.obj").unwrap().info, AssetInfo::Mesh { triangles: 1, .. }));
+        assert_eq!(index.assets_of_type(AssetType::Texture).count(), 1);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rescans_reuse_the_cache_for_unchanged_files() {
+        let root = project("cache");
+        scan(&root);
+        // Marca la entrada cacheada: si el re-escaneo la reutiliza, no relee el archivo.
+        let mut cache: ProjectCache = serde_json::from_slice(&std::fs::read(root.join(PROJECT_CACHE_FILE)).unwrap()).unwrap();
+        for asset in &mut cache.assets {
+            asset.info = AssetInfo::Invalid { error: "from cache".into() };
+        }
+        std::fs::write(root.join(PROJECT_CACHE_FILE), serde_json::to_vec(&cache).unwrap()).unwrap();
+        std::fs::write(root.join("shader.spv"), [0u8; 16]).unwrap();
+
+        let index = scan(&root);
+        assert_eq!(index.get("albedo.png").unwrap().info, AssetInfo::Invalid { error: "from cache".into() });
+        assert_eq!(index.get("shader.spv").unwrap().info, AssetInfo::Shader { words: 4 });
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn refresh_adds_updates_and_removes() {
+        let root = project("refresh");
+        let mut index = scan(&root);
+        image::RgbaImage::new(8, 8).save(root.join("normal.png")).unwrap();
+        index.refresh(root.join("normal.png"));
+        assert_eq!(index.get("normal.png").unwrap().info, AssetInfo::Texture { width: 8, height: 8 });
+
+        std::fs::remove_file(root.join("models/tri.obj")).unwrap();
+        index.refresh("models/tri.obj");
+        assert!(index.get("models/tri.obj").is_none());
+
+        index.refresh(root.join("notes.txt"));
+        assert!(index.get("notes.txt").is_none());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,511 @@
+// =============================================================================
+// LoadQueue — streaming de modelos y texturas con presupuesto por frame
+// =============================================================================
+// Cargar un nivel grande con `load_scene_file` bloquea el frame durante
+// segundos. La cola reparte el trabajo en tres etapas:
+//
+// 1. Lectura y parseo (OBJ, glTF, imágenes) en un pool de hilos.
+// 2. Subida a GPU: los meshes van por `MeshUploader` (la cola de transfer si
+//    existe); las texturas se suben con `Texture::from_rgba`, que bloquea, así
+//    que cuentan dentro del presupuesto.
+// 3. Integración en el `Scene` desde `update`, al empezar el frame, hasta
+//    gastar `budget_ms` (siempre al menos un paso, para que todo avance).
+//
+// Cancelar sólo vale mientras no haya empezado la subida a GPU. Los modelos
+// se añaden como objetos estáticos con un único material (el color base de
+// cada material glTF va en `SceneObject::color`); para materiales completos,
+// skinning y animaciones está `ReactorContext::spawn_gltf`.
+// =============================================================================
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use glam::{Mat4, Vec4};
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::reactor::Reactor;
+use crate::resources::gltf_loader::{GltfLoader, GltfModel, GltfNode};
+use crate::resources::material::Material;
+use crate::resources::mesh::Mesh;
+use crate::resources::mesh_upload::MeshTicket;
+use crate::resources::model::ObjData;
+use crate::resources::texture::{open_image, Texture};
+use crate::resources::vertex::Vertex;
+use crate::systems::scene::{Scene, SceneHandle, SceneObject};
+
+/// Presupuesto de integración por defecto (ms por frame).
+pub const DEFAULT_STREAM_BUDGET_MS: f32 = 2.0;
+
+/// Id de una petición de `LoadQueue`. Nunca se reutiliza.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamId(u32);
+
+impl StreamId {
+    pub fn id(self) -> u32 {
+        self.0
+    }
+
+    pub fn from_id(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamStatus {
+    /// Esperando un hilo libre.
+    Queued,
+    /// Leyendo, parseando o subiendo a GPU.
+    Loading,
+    /// Integrado: objetos en la escena o textura lista.
+    Ready,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StreamStats {
+    pub queued: usize,
+    pub loading: usize,
+    pub ready: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    /// Bytes de vértices, índices y píxeles enviados a GPU en el último `update`.
+    pub bytes_uploaded: u64,
+    /// Tiempo gastado en el último `update`.
+    pub integration_ms: f32,
+}
+
+impl StreamStats {
+    /// Peticiones sin terminar (en cola o cargando).
+    pub fn pending(&self) -> usize {
+        self.queued + self.loading
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AssetKind {
+    Model,
+    Texture,
+}
+
+#[derive(Default)]
+struct JobFlags {
+    started: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+struct Job {
+    id: StreamId,
+    path: PathBuf,
+    kind: AssetKind,
+    flags: Arc<JobFlags>,
+}
+
+struct ParsedMesh {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    transform: Mat4,
+    color: Vec4,
+}
+
+enum Parsed {
+    Model(Vec<ParsedMesh>),
+    Texture { pixels: Vec<u8>, width: u32, height: u32 },
+}
+
+struct UploadingMesh {
+    ticket: MeshTicket,
+    transform: Mat4,
+    color: Vec4,
+    mesh: Option<Mesh>,
+}
+
+enum Stage {
+    /// En el pool de hilos (`JobFlags::started` distingue cola y parseo).
+    Pending,
+    Parsed(Parsed),
+    Uploading(Vec<UploadingMesh>),
+    Objects(Vec<SceneHandle>),
+    Texture(Arc<Texture>),
+    Failed(ReactorError),
+    Cancelled,
+}
+
+struct Request {
+    flags: Arc<JobFlags>,
+    stage: Stage,
+}
+
+pub struct LoadQueue {
+    jobs: Option<mpsc::Sender<Job>>,
+    parsed: mpsc::Receiver<(StreamId, ReactorResult<Parsed>)>,
+    workers: Vec<thread::JoinHandle<()>>,
+    /// Ordenadas por id: se integran en el orden en que se pidieron.
+    requests: BTreeMap<StreamId, Request>,
+    next_id: u32,
+    budget_ms: f32,
+    bytes_uploaded: u64,
+    integration_ms: f32,
+}
+
+impl LoadQueue {
+    /// Cola con un hilo por núcleo libre (entre 1 y 4).
+    pub fn new() -> ReactorResult<Self> {
+        let cores = thread::available_parallelism().map_or(2, |n| n.get());
+        Self::with_workers(cores.saturating_sub(1).clamp(1, 4))
+    }
+
+    pub fn with_workers(count: usize) -> ReactorResult<Self> {
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+        let (results, parsed) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let workers = (0..count.max(1))
+            .map(|index| {
+                let jobs = job_receiver.clone();
+                let results = results.clone();
+                thread::Builder::new()
+                    .name(format!("reactor-stream-{}", index))
+                    .spawn(move || worker_loop(&jobs, &results))
+                    .map_err(|e| ReactorError::with_source(ErrorCode::IoError, "Failed to spawn a streaming thread", e))
+            })
+            .collect::<ReactorResult<Vec<_>>>()?;
+        Ok(Self {
+            jobs: Some(jobs),
+            parsed,
+            workers,
+            requests: BTreeMap::new(),
+            next_id: 1,
+            budget_ms: DEFAULT_STREAM_BUDGET_MS,
+            bytes_uploaded: 0,
+            integration_ms: 0.0,
+        })
+    }
+
+    /// Pide un modelo (`.obj`, `.gltf`, `.glb`); sus objetos aparecen en la
+    /// escena en algún `update` posterior.
+    pub fn load_model(&mut self, path: impl AsRef<Path>) -> StreamId {
+        self.enqueue(path.as_ref(), AssetKind::Model)
+    }
+
+    /// Pide una textura; cuando está `Ready`, `texture` la devuelve.
+    pub fn load_texture(&mut self, path: impl AsRef<Path>) -> StreamId {
+        self.enqueue(path.as_ref(), AssetKind::Texture)
+    }
+
+    fn enqueue(&mut self, path: &Path, kind: AssetKind) -> StreamId {
+        let id = StreamId(self.next_id);
+        self.next_id += 1;
+        let flags = Arc::new(JobFlags::default());
+        let job = Job { id, path: path.to_path_buf(), kind, flags: flags.clone() };
+        let stage = match self.jobs.as_ref().map(|jobs| jobs.send(job)) {
+            Some(Ok(())) => Stage::Pending,
+            _ => Stage::Failed(ReactorError::internal("The streaming threads have stopped")),
+        };
+        self.requests.insert(id, Request { flags, stage });
+        id
+    }
+
+    /// `None` si `id` no es de esta cola.
+    pub fn status(&self, id: StreamId) -> Option<StreamStatus> {
+        let request = self.requests.get(&id)?;
+        Some(match request.stage {
+            Stage::Pending if !request.flags.started.load(Ordering::Relaxed) => StreamStatus::Queued,
+            Stage::Pending | Stage::Parsed(_) | Stage::Uploading(_) => StreamStatus::Loading,
+            Stage::Objects(_) | Stage::Texture(_) => StreamStatus::Ready,
+            Stage::Failed(_) => StreamStatus::Failed,
+            Stage::Cancelled => StreamStatus::Cancelled,
+        })
+    }
+
+    /// Error de una petición `Failed`.
+    pub fn error(&self, id: StreamId) -> Option<&ReactorError> {
+        match &self.requests.get(&id)?.stage {
+            Stage::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Objetos que añadió un modelo `Ready`.
+    pub fn objects(&self, id: StreamId) -> Option<&[SceneHandle]> {
+        match &self.requests.get(&id)?.stage {
+            Stage::Objects(handles) => Some(handles),
+            _ => None,
+        }
+    }
+
+    /// Textura de una petición `Ready`.
+    pub fn texture(&self, id: StreamId) -> Option<Arc<Texture>> {
+        match &self.requests.get(&id)?.stage {
+            Stage::Texture(texture) => Some(texture.clone()),
+            _ => None,
+        }
+    }
+
+    /// Cancela una petición que aún no empezó a subir a GPU. `false` si ya
+    /// subía, había terminado o no existe.
+    pub fn cancel(&mut self, id: StreamId) -> bool {
+        let Some(request) = self.requests.get_mut(&id) else { return false };
+        if !matches!(request.stage, Stage::Pending | Stage::Parsed(_)) {
+            return false;
+        }
+        request.flags.cancelled.store(true, Ordering::Relaxed);
+        request.stage = Stage::Cancelled;
+        true
+    }
+
+    pub fn budget_ms(&self) -> f32 {
+        self.budget_ms
+    }
+
+    /// Tiempo máximo que `update` dedica a subir e integrar por frame.
+    pub fn set_budget_ms(&mut self, budget_ms: f32) {
+        self.budget_ms = budget_ms.max(0.0);
+    }
+
+    /// `true` si no queda nada en curso.
+    pub fn is_idle(&self) -> bool {
+        self.requests.values().all(|request| {
+            matches!(request.stage, Stage::Objects(_) | Stage::Texture(_) | Stage::Failed(_) | Stage::Cancelled)
+        })
+    }
+
+    pub fn stats(&self) -> StreamStats {
+        let mut stats = StreamStats {
+            bytes_uploaded: self.bytes_uploaded,
+            integration_ms: self.integration_ms,
+            ..StreamStats::default()
+        };
+        for &id in self.requests.keys() {
+            match self.status(id) {
+                Some(StreamStatus::Queued) => stats.queued += 1,
+                Some(StreamStatus::Loading) => stats.loading += 1,
+                Some(StreamStatus::Ready) => stats.ready += 1,
+                Some(StreamStatus::Failed) => stats.failed += 1,
+                Some(StreamStatus::Cancelled) | None => stats.cancelled += 1,
+            }
+        }
+        stats
+    }
+
+    /// Avanza las peticiones en orden: empieza subidas, recoge meshes
+    /// terminados y añade los modelos completos a `scene` con `material`.
+    /// Para al pasar `budget_ms`. Se llama al empezar el frame.
+    pub fn update(&mut self, reactor: &Reactor, scene: &mut Scene, material: &Arc<Material>) {
+        crate::profile_scope!("stream_integration");
+        let start = Instant::now();
+        let budget = Duration::from_secs_f32(self.budget_ms / 1000.0);
+        self.collect_parsed();
+        self.bytes_uploaded = 0;
+        let mut worked = false;
+        for request in self.requests.values_mut() {
+            if worked && start.elapsed() >= budget {
+                break;
+            }
+            let stage = std::mem::replace(&mut request.stage, Stage::Cancelled);
+            request.stage = match stage {
+                Stage::Parsed(parsed) => {
+                    worked = true;
+                    start_upload(reactor, parsed, &mut self.bytes_uploaded)
+                }
+                Stage::Uploading(meshes) => finish_upload(reactor, scene, material, meshes, &mut worked),
+                other => other,
+            };
+        }
+        self.integration_ms = start.elapsed().as_secs_f32() * 1000.0;
+    }
+
+    /// Pasa a `Parsed` (o `Failed`) lo que terminaron los hilos.
+    fn collect_parsed(&mut self) {
+        while let Ok((id, result)) = self.parsed.try_recv() {
+            let Some(request) = self.requests.get_mut(&id) else { continue };
+            if matches!(request.stage, Stage::Pending) {
+                request.stage = match result {
+                    Ok(parsed) => Stage::Parsed(parsed),
+                    Err(e) => {
+                        log::warn!("Streaming request {} failed: {}", id.0, e);
+                        Stage::Failed(e)
+                    }
+                };
+            }
+        }
+    }
+}
+
+impl Drop for LoadQueue {
+    fn drop(&mut self) {
+        // Cerrar el canal termina cada hilo al acabar su trabajo actual.
+        for request in self.requests.values() {
+            request.flags.cancelled.store(true, Ordering::Relaxed);
+        }
+        self.jobs.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker_loop(jobs: &Mutex<mpsc::Receiver<Job>>, results: &mpsc::Sender<(StreamId, ReactorResult<Parsed>)>) {
+    loop {
+        let job = jobs.lock().unwrap().recv();
+        let Ok(job) = job else { return };
+        if job.flags.cancelled.load(Ordering::Relaxed) {
+            continue;
+        }
+        job.flags.started.store(true, Ordering::Relaxed);
+        let result = match job.kind {
+            AssetKind::Model => parse_model(&job.path),
+            AssetKind::Texture => parse_texture(&job.path),
+        };
+        if job.flags.cancelled.load(Ordering::Relaxed) {
+            continue;
+        }
+        if results.send((job.id, result)).is_err() {
+            return;
+        }
+    }
+}
+
+fn parse_model(path: &Path) -> ReactorResult<Parsed> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+    let meshes = match extension.as_str() {
+        "obj" => {
+            let obj = ObjData::load(path)?;
+            vec![ParsedMesh { vertices: obj.vertices, indices: obj.indices, transform: Mat4::IDENTITY, color: Vec4::ONE }]
+        }
+        "gltf" | "glb" => {
+            let model = GltfLoader::new(".").load(path)?;
+            let mut meshes = Vec::new();
+            collect_gltf_meshes(&model, &model.root_node, Mat4::IDENTITY, &mut meshes);
+            meshes
+        }
+        _ => return Err(ReactorError::invalid_format(format!("Unsupported model format: {}", path.display()))),
+    };
+    if meshes.iter().all(|mesh| mesh.indices.is_empty()) {
+        return Err(ReactorError::invalid_format(format!("Model has no geometry: {}", path.display())));
+    }
+    Ok(Parsed::Model(meshes))
+}
+
+fn collect_gltf_meshes(model: &GltfModel, node: &GltfNode, parent: Mat4, meshes: &mut Vec<ParsedMesh>) {
+    let transform = parent * node.transform;
+    if let Some(data) = node.mesh_index.and_then(|index| model.meshes.get(index)) {
+        let color = data.material_index.and_then(|index| model.materials.get(index)).map_or([1.0; 4], |m| m.base_color);
+        meshes.push(ParsedMesh {
+            vertices: data.vertices.clone(),
+            indices: data.indices.clone(),
+            transform,
+            color: Vec4::from_array(color),
+        });
+    }
+    for child in &node.children {
+        collect_gltf_meshes(model, child, transform, meshes);
+    }
+}
+
+fn parse_texture(path: &Path) -> ReactorResult<Parsed> {
+    let rgba = open_image(path, "texture")?.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    Ok(Parsed::Texture { pixels: rgba.into_raw(), width, height })
+}
+
+fn start_upload(reactor: &Reactor, parsed: Parsed, bytes: &mut u64) -> Stage {
+    match parsed {
+        Parsed::Model(meshes) => Stage::Uploading(
+            meshes
+                .into_iter()
+                .filter(|mesh| !mesh.indices.is_empty())
+                .map(|mesh| {
+                    *bytes += (std::mem::size_of_val(mesh.vertices.as_slice()) + std::mem::size_of_val(mesh.indices.as_slice())) as u64;
+                    UploadingMesh {
+                        ticket: reactor.create_mesh_async(&mesh.vertices, &mesh.indices),
+                        transform: mesh.transform,
+                        color: mesh.color,
+                        mesh: None,
+                    }
+                })
+                .collect(),
+        ),
+        Parsed::Texture { pixels, width, height } => {
+            *bytes += pixels.len() as u64;
+            match Texture::from_rgba(&reactor.context, reactor.allocator.clone(), &pixels, width, height, true) {
+                Ok(texture) => Stage::Texture(Arc::new(texture)),
+                Err(e) => Stage::Failed(e),
+            }
+        }
+    }
+}
+
+/// Recoge los meshes terminados; con todos, añade los objetos a la escena.
+fn finish_upload(
+    reactor: &Reactor,
+    scene: &mut Scene,
+    material: &Arc<Material>,
+    mut meshes: Vec<UploadingMesh>,
+    worked: &mut bool,
+) -> Stage {
+    for uploading in meshes.iter_mut().filter(|uploading| uploading.mesh.is_none()) {
+        match reactor.poll_ticket(uploading.ticket) {
+            Some(Ok(mesh)) => uploading.mesh = Some(mesh),
+            Some(Err(e)) => return Stage::Failed(e),
+            None => {}
+        }
+    }
+    if meshes.iter().any(|uploading| uploading.mesh.is_none()) {
+        return Stage::Uploading(meshes);
+    }
+    *worked = true;
+    let handles = meshes
+        .into_iter()
+        .filter_map(|uploading| {
+            let mut object = SceneObject::new(Arc::new(uploading.mesh?), material.clone(), uploading.transform);
+            object.color = uploading.color;
+            Some(scene.insert(object))
+        })
+        .collect();
+    Stage::Objects(handles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_until_parsed(queue: &mut LoadQueue, id: StreamId) {
+        let start = Instant::now();
+        while matches!(queue.requests[&id].stage, Stage::Pending) && start.elapsed() < Duration::from_secs(10) {
+            thread::sleep(Duration::from_millis(1));
+            queue.collect_parsed();
+        }
+    }
+
+    #[test]
+    fn parsed_requests_can_be_cancelled_and_bad_ones_fail() {
+        let path = std::env::temp_dir().join(format!("reactor_stream_{}.obj", std::process::id()));
+        std::fs::write(&path, "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        let mut queue = LoadQueue::with_workers(2).unwrap();
+        let model = queue.load_model(&path);
+        let missing = queue.load_model(path.with_extension("missing.obj"));
+        let unsupported = queue.load_model(path.with_extension("fbx"));
+        wait_until_parsed(&mut queue, model);
+        wait_until_parsed(&mut queue, missing);
+        wait_until_parsed(&mut queue, unsupported);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(queue.status(model), Some(StreamStatus::Loading));
+        assert_eq!(queue.status(missing), Some(StreamStatus::Failed));
+        assert_eq!(queue.error(unsupported).unwrap().code, ErrorCode::InvalidFormat);
+        assert_eq!(queue.stats().pending(), 1);
+        assert!(!queue.is_idle());
+
+        assert!(queue.cancel(model));
+        assert!(!queue.cancel(model) && !queue.cancel(missing));
+        assert_eq!(queue.status(model), Some(StreamStatus::Cancelled));
+        assert_eq!(queue.status(StreamId::from_id(99)), None);
+        assert!(queue.is_idle());
+        let stats = queue.stats();
+        assert_eq!((stats.failed, stats.cancelled, stats.pending()), (2, 1, 0));
+    }
+}
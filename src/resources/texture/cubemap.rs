@@ -0,0 +1,248 @@
+use super::{mip_level_count, open_image, supports_linear_blit, upload, TextureOptions};
+use crate::core::debug_utils::DebugNamer;
+use crate::core::error::{ReactorError, ReactorResult};
+use crate::core::VulkanContext;
+use crate::graphics::buffer::Buffer;
+use crate::graphics::image::Image;
+use crate::graphics::sampler::{Sampler, SamplerConfig, WrapMode};
+use ash::vk;
+use glam::Vec3;
+use gpu_allocator::vulkan::Allocator;
+use gpu_allocator::MemoryLocation;
+use std::f32::consts::PI;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Faces per cubemap, in Vulkan layer order: +X, -X, +Y, -Y, +Z, -Z.
+pub const CUBE_FACES: usize = 6;
+
+/// Six square layers sampled through a `CUBE` view. The image keeps its own
+/// mip chain so it can be prefiltered for IBL later.
+pub struct Cubemap {
+    pub image: Image,
+    pub sampler: Sampler,
+    /// Width and height of each face.
+    pub size: u32,
+}
+
+impl Cubemap {
+    /// Loads six square faces of the same size, in layer order
+    /// (+X, -X, +Y, -Y, +Z, -Z).
+    pub fn from_files<P: AsRef<Path>>(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        faces: [P; CUBE_FACES],
+        options: &TextureOptions,
+    ) -> ReactorResult<Self> {
+        let mut size = 0;
+        let mut data = Vec::new();
+        for (index, path) in faces.iter().enumerate() {
+            let path = path.as_ref();
+            let rgba = open_image(path, "cubemap face")?.to_rgba8();
+            let (width, height) = rgba.dimensions();
+            if width != height {
+                return Err(ReactorError::invalid_format(format!(
+                    "Cubemap face {} is {}x{}, faces must be square",
+                    path.display(), width, height
+                )));
+            }
+            if index == 0 {
+                size = width;
+            } else if width != size {
+                return Err(ReactorError::invalid_format(format!(
+                    "Cubemap face {} is {}px, expected {}px like the first face",
+                    path.display(), width, size
+                )));
+            }
+            data.extend_from_slice(rgba.as_raw());
+        }
+        Self::from_rgba_faces(ctx, allocator, &data, size, options)
+    }
+
+    /// Loads a latitude/longitude panorama and resamples it into faces of
+    /// `face_size` pixels on the CPU.
+    pub fn from_equirect_file<P: AsRef<Path>>(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        path: P,
+        face_size: u32,
+        options: &TextureOptions,
+    ) -> ReactorResult<Self> {
+        let rgba = open_image(path.as_ref(), "equirect panorama")?.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let data = equirect_to_faces(rgba.as_raw(), width, height, face_size);
+        Self::from_rgba_faces(ctx, allocator, &data, face_size, options)
+    }
+
+    /// Uploads six tightly packed RGBA8 faces of `size`×`size`, one after
+    /// the other in layer order.
+    pub fn from_rgba_faces(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        data: &[u8],
+        size: u32,
+        options: &TextureOptions,
+    ) -> ReactorResult<Self> {
+        let face_bytes = (size * size * 4) as usize;
+        if size == 0 || data.len() != face_bytes * CUBE_FACES {
+            return Err(ReactorError::invalid_parameter(format!(
+                "Cubemap data is {} bytes, expected six {}x{} RGBA faces",
+                data.len(), size, size
+            )));
+        }
+
+        let format = if options.srgb { vk::Format::R8G8B8A8_SRGB } else { vk::Format::R8G8B8A8_UNORM };
+        let mut mip_levels = if options.generate_mipmaps { mip_level_count(size, size) } else { 1 };
+        if mip_levels > 1 && !supports_linear_blit(ctx, format) {
+            log::warn!("{:?} cannot be blitted with linear filtering; uploading cubemap without mips", format);
+            mip_levels = 1;
+        }
+
+        let image = Image::new_cube(
+            ctx,
+            allocator.clone(),
+            size,
+            format,
+            vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::SAMPLED,
+            mip_levels,
+        )?;
+
+        let staging = Buffer::new(
+            ctx,
+            allocator,
+            data.len() as u64,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            MemoryLocation::CpuToGpu,
+        )?;
+        staging.write(data);
+
+        let layers = CUBE_FACES as u32;
+        upload::copy_buffer_to_image(ctx, staging.handle, image.handle, size, size, layers)?;
+        if mip_levels > 1 {
+            upload::generate_mipmaps(ctx, image.handle, size, size, mip_levels, layers)?;
+        } else {
+            upload::transition_to_shader_read(ctx, image.handle, mip_levels, layers)?;
+        }
+
+        // Clamp: con REPEAT el filtrado en el borde de una cara mezclaría el
+        // borde opuesto de la misma cara y se vería la costura.
+        let sampler = Sampler::new(
+            ctx,
+            &SamplerConfig { address_mode: WrapMode::ClampToEdge, max_lod: mip_levels as f32, ..options.sampler },
+        )?;
+
+        Ok(Self { image, sampler, size })
+    }
+
+    /// Mip levels in the image (1 when uploaded without mips).
+    pub fn mip_count(&self) -> u32 {
+        self.image.mip_levels
+    }
+
+    pub fn view(&self) -> vk::ImageView {
+        self.image.view
+    }
+
+    pub fn sampler_handle(&self) -> vk::Sampler {
+        self.sampler.handle
+    }
+
+    /// Label the image, view and sampler. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        self.image.set_debug_name(namer, name);
+        self.sampler.set_debug_name(namer, &format!("{name} (sampler)"));
+    }
+}
+
+/// World direction through `(s, t)` ∈ [0, 1]² of `face`, following the
+/// Vulkan cube face orientation (same table as `equirect_to_cube.comp`).
+pub fn cube_face_direction(face: usize, s: f32, t: f32) -> Vec3 {
+    let (a, b) = (s * 2.0 - 1.0, t * 2.0 - 1.0);
+    let direction = match face {
+        0 => Vec3::new(1.0, -b, -a),
+        1 => Vec3::new(-1.0, -b, a),
+        2 => Vec3::new(a, 1.0, b),
+        3 => Vec3::new(a, -1.0, -b),
+        4 => Vec3::new(a, -b, 1.0),
+        _ => Vec3::new(-a, -b, -1.0),
+    };
+    direction.normalize()
+}
+
+/// Resamples an RGBA8 panorama (`width`×`height`, top row looking up) into
+/// six packed faces of `face_size`, with bilinear filtering that wraps
+/// around the horizontal seam.
+pub fn equirect_to_faces(pixels: &[u8], width: u32, height: u32, face_size: u32) -> Vec<u8> {
+    let mut faces = Vec::with_capacity((face_size * face_size * 4) as usize * CUBE_FACES);
+    for face in 0..CUBE_FACES {
+        for y in 0..face_size {
+            for x in 0..face_size {
+                let s = (x as f32 + 0.5) / face_size as f32;
+                let t = (y as f32 + 0.5) / face_size as f32;
+                let direction = cube_face_direction(face, s, t);
+                let u = direction.z.atan2(direction.x) / (2.0 * PI) + 0.5;
+                let v = 0.5 - direction.y.clamp(-1.0, 1.0).asin() / PI;
+                faces.extend_from_slice(&sample_bilinear(pixels, width, height, u, v));
+            }
+        }
+    }
+    faces
+}
+
+fn sample_bilinear(pixels: &[u8], width: u32, height: u32, u: f32, v: f32) -> [u8; 4] {
+    let x = u * width as f32 - 0.5;
+    let y = (v * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let column = |x: f32| (x as i64).rem_euclid(width as i64) as usize;
+    let row = |y: f32| (y as usize).min(height as usize - 1);
+    let texel = |x: usize, y: usize, c: usize| pixels[(y * width as usize + x) * 4 + c] as f32;
+
+    let (left, right) = (column(x0), column(x0 + 1.0));
+    let (top, bottom) = (row(y0), row(y0 + 1.0));
+    let mut out = [0u8; 4];
+    for (c, value) in out.iter_mut().enumerate() {
+        let upper = texel(left, top, c) * (1.0 - fx) + texel(right, top, c) * fx;
+        let lower = texel(left, bottom, c) * (1.0 - fx) + texel(right, bottom, c) * fx;
+        *value = (upper * (1.0 - fy) + lower * fy).round() as u8;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn face_centers_point_along_their_axis() {
+        let axes = [Vec3::X, -Vec3::X, Vec3::Y, -Vec3::Y, Vec3::Z, -Vec3::Z];
+        for (face, axis) in axes.iter().enumerate() {
+            assert!(cube_face_direction(face, 0.5, 0.5).abs_diff_eq(*axis, 1e-6), "face {face}");
+        }
+    }
+
+    #[test]
+    fn neighbouring_faces_share_their_edges() {
+        // +X izquierda == +Z derecha, +Y abajo == +Z arriba, -Y arriba == +Z abajo.
+        let pairs = [((0, 0.0, 0.3), (4, 1.0, 0.3)), ((2, 0.3, 1.0), (4, 0.3, 0.0)), ((3, 0.3, 0.0), (4, 0.3, 1.0))];
+        for ((fa, sa, ta), (fb, sb, tb)) in pairs {
+            let a = cube_face_direction(fa, sa, ta);
+            let b = cube_face_direction(fb, sb, tb);
+            assert!(a.abs_diff_eq(b, 1e-6), "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn equirect_top_row_lands_on_the_up_face() {
+        // Mitad superior blanca, inferior negra.
+        let (width, height) = (16, 8);
+        let pixels: Vec<u8> = (0..height)
+            .flat_map(|y| std::iter::repeat(if y < height / 2 { 255 } else { 0 }).take(width as usize * 4))
+            .collect();
+        let faces = equirect_to_faces(&pixels, width, height, 4);
+        let face_bytes = 4 * 4 * 4;
+        assert_eq!(faces.len(), face_bytes * CUBE_FACES);
+        assert!(faces[2 * face_bytes..3 * face_bytes].iter().all(|&c| c == 255));
+        assert!(faces[3 * face_bytes..4 * face_bytes].iter().all(|&c| c == 0));
+    }
+}
@@ -1,17 +1,43 @@
 use crate::core::arc_handle::ArcDevice;
+use crate::core::debug_utils::DebugNamer;
 use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
 use crate::core::VulkanContext;
 use crate::graphics::buffer::Buffer;
 use crate::graphics::image::Image;
-use crate::graphics::sampler::Sampler;
+use crate::graphics::sampler::{Sampler, SamplerConfig, WrapMode};
 use ash::vk;
 use gpu_allocator::vulkan::Allocator;
 use gpu_allocator::MemoryLocation;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+pub mod cubemap;
 mod upload;
 
+pub use cubemap::Cubemap;
+
+/// How `Texture::from_file_with` uploads and samples an image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextureOptions {
+    pub sampler: SamplerConfig,
+    /// Color data (`R8G8B8A8_SRGB`); `false` for normal, roughness and other
+    /// data maps (`R8G8B8A8_UNORM`).
+    pub srgb: bool,
+    /// Build the full mip chain with blits at upload time.
+    pub generate_mipmaps: bool,
+}
+
+impl Default for TextureOptions {
+    fn default() -> Self {
+        Self { sampler: SamplerConfig::texture(), srgb: true, generate_mipmaps: true }
+    }
+}
+
+/// Levels in a full mip chain down to 1×1.
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
 pub struct Texture {
     pub image: Image,
     pub sampler: Sampler,
@@ -28,14 +54,7 @@ impl Texture {
         path: P,
         generate_mipmaps: bool,
     ) -> ReactorResult<Self> {
-        let path_ref = path.as_ref();
-        let img = image::open(path_ref).map_err(|e| {
-            ReactorError::with_source(
-                ErrorCode::TextureLoadFailed,
-                format!("Failed to open texture: {}", path_ref.display()),
-                e,
-            )
-        })?;
+        let img = open_image(path.as_ref(), "texture")?;
         let rgba = img.to_rgba8();
         let (width, height) = rgba.dimensions();
         let data = rgba.into_raw();
@@ -43,20 +62,28 @@ impl Texture {
         Self::from_rgba(ctx, allocator, &data, width, height, generate_mipmaps)
     }
 
+    /// Loads an image with explicit filtering, wrapping, color space and
+    /// mip generation.
+    pub fn from_file_with<P: AsRef<Path>>(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        path: P,
+        options: &TextureOptions,
+    ) -> ReactorResult<Self> {
+        let img = open_image(path.as_ref(), "texture")?;
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let format = if options.srgb { vk::Format::R8G8B8A8_SRGB } else { vk::Format::R8G8B8A8_UNORM };
+        Self::upload_rgba(ctx, allocator, &rgba.into_raw(), width, height, format, options.generate_mipmaps, &options.sampler)
+    }
+
     pub fn from_file_linear<P: AsRef<Path>>(
         ctx: &VulkanContext,
         allocator: Arc<Mutex<Allocator>>,
         path: P,
         generate_mipmaps: bool,
     ) -> ReactorResult<Self> {
-        let path_ref = path.as_ref();
-        let img = image::open(path_ref).map_err(|e| {
-            ReactorError::with_source(
-                ErrorCode::TextureLoadFailed,
-                format!("Failed to open linear texture: {}", path_ref.display()),
-                e,
-            )
-        })?;
+        let img = open_image(path.as_ref(), "linear texture")?;
         let rgba = img.to_rgba8();
         let (width, height) = rgba.dimensions();
         let data = rgba.into_raw();
@@ -80,7 +107,7 @@ impl Texture {
     ) -> ReactorResult<Self> {
         let img = image::load_from_memory(bytes).map_err(|e| {
             ReactorError::with_source(
-                ErrorCode::TextureLoadFailed,
+                decode_error_code(&e),
                 "Failed to load texture from bytes",
                 e,
             )
@@ -175,6 +202,40 @@ impl Texture {
         )
     }
 
+    /// Texture the GPU renders into (`Reactor::create_render_target`): color
+    /// attachment and sampled, no mips, clamped. Starts in
+    /// `SHADER_READ_ONLY_OPTIMAL`, so it can be bound before the first draw.
+    pub fn render_target(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        width: u32,
+        height: u32,
+        format: vk::Format,
+    ) -> ReactorResult<Self> {
+        let image = Image::new(
+            ctx,
+            allocator,
+            width,
+            height,
+            format,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC,
+            vk::ImageAspectFlags::COLOR,
+            1,
+        )?;
+        upload::transition_from_to_shader_read(ctx, image.handle, vk::ImageLayout::UNDEFINED, 1, 1)?;
+        let sampler = Sampler::new(
+            ctx,
+            &SamplerConfig { address_mode: WrapMode::ClampToEdge, max_lod: 1.0, ..SamplerConfig::default() },
+        )?;
+        Ok(Self {
+            image,
+            sampler,
+            width,
+            height,
+            device: ctx.device.clone(),
+        })
+    }
+
     pub fn from_rgba(
         ctx: &VulkanContext,
         allocator: Arc<Mutex<Allocator>>,
@@ -203,11 +264,25 @@ impl Texture {
         generate_mipmaps: bool,
         format: vk::Format,
     ) -> ReactorResult<Self> {
-        let mip_levels = if generate_mipmaps {
-            ((width.max(height) as f32).log2().floor() as u32) + 1
-        } else {
-            1
-        };
+        Self::upload_rgba(ctx, allocator, data, width, height, format, generate_mipmaps, &SamplerConfig::texture())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn upload_rgba(
+        ctx: &VulkanContext,
+        allocator: Arc<Mutex<Allocator>>,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        format: vk::Format,
+        generate_mipmaps: bool,
+        sampler: &SamplerConfig,
+    ) -> ReactorResult<Self> {
+        let mut mip_levels = if generate_mipmaps { mip_level_count(width, height) } else { 1 };
+        if mip_levels > 1 && !supports_linear_blit(ctx, format) {
+            log::warn!("{:?} cannot be blitted with linear filtering; uploading without mips", format);
+            mip_levels = 1;
+        }
 
         let image = Image::new_texture_with_format(
             ctx,
@@ -229,15 +304,15 @@ impl Texture {
 
         staging.write(data);
 
-        upload::copy_buffer_to_image(ctx, staging.handle, image.handle, width, height)?;
+        upload::copy_buffer_to_image(ctx, staging.handle, image.handle, width, height, 1)?;
 
-        if generate_mipmaps && mip_levels > 1 {
-            upload::generate_mipmaps(ctx, image.handle, width, height, mip_levels)?;
+        if mip_levels > 1 {
+            upload::generate_mipmaps(ctx, image.handle, width, height, mip_levels, 1)?;
         } else {
-            upload::transition_to_shader_read(ctx, image.handle, mip_levels)?;
+            upload::transition_to_shader_read(ctx, image.handle, mip_levels, 1)?;
         }
 
-        let sampler = Sampler::linear(ctx)?;
+        let sampler = Sampler::new(ctx, &SamplerConfig { max_lod: mip_levels as f32, ..*sampler })?;
 
         Ok(Self {
             image,
@@ -248,6 +323,11 @@ impl Texture {
         })
     }
 
+    /// Mip levels in the image (1 when uploaded without mips).
+    pub fn mip_count(&self) -> u32 {
+        self.image.mip_levels
+    }
+
     pub fn view(&self) -> vk::ImageView {
         self.image.view
     }
@@ -255,4 +335,57 @@ impl Texture {
     pub fn sampler_handle(&self) -> vk::Sampler {
         self.sampler.handle
     }
+
+    /// Label the image, view and sampler. No-op without debug utils.
+    pub fn set_debug_name(&self, namer: &DebugNamer, name: &str) {
+        self.image.set_debug_name(namer, name);
+        self.sampler.set_debug_name(namer, &format!("{name} (sampler)"));
+    }
+}
+
+/// Abre y decodifica una imagen distinguiendo fichero inexistente, formato no
+/// soportado y fallo de decodificación, para que el llamador reciba un código útil.
+pub(crate) fn open_image(path: &Path, what: &str) -> ReactorResult<image::DynamicImage> {
+    if !path.is_file() {
+        return Err(ReactorError::file_not_found(path.display().to_string()));
+    }
+    image::open(path).map_err(|e| {
+        ReactorError::with_source(
+            decode_error_code(&e),
+            format!("Failed to open {}: {}", what, path.display()),
+            e,
+        )
+    })
+}
+
+/// Whether `vkCmdBlitImage` with `LINEAR` works on optimal-tiling `format`.
+fn supports_linear_blit(ctx: &VulkanContext, format: vk::Format) -> bool {
+    let properties = unsafe {
+        ctx.ash_instance().get_physical_device_format_properties(ctx.physical_device, format)
+    };
+    properties.optimal_tiling_features.contains(
+        vk::FormatFeatureFlags::BLIT_SRC
+            | vk::FormatFeatureFlags::BLIT_DST
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR,
+    )
+}
+
+fn decode_error_code(err: &image::ImageError) -> ErrorCode {
+    match err {
+        image::ImageError::Unsupported(_) => ErrorCode::InvalidFormat,
+        _ => ErrorCode::TextureLoadFailed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mip_chain_reaches_one_pixel() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(1024, 512), 11);
+        assert_eq!(mip_level_count(300, 17), 9);
+    }
 }
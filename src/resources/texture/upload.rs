@@ -2,12 +2,16 @@ use crate::core::VulkanContext;
 use crate::core::error::ReactorResult;
 use ash::vk;
 
+// Los `layers` se copian y reducen a la vez: 1 para texturas, 6 para cubemaps
+// (caras contiguas en el staging buffer).
+
 pub(super) fn copy_buffer_to_image(
     ctx: &VulkanContext,
     buffer: vk::Buffer,
     image: vk::Image,
     width: u32,
     height: u32,
+    layers: u32,
 ) -> ReactorResult<()> {
     let pool_info = vk::CommandPoolCreateInfo::default()
         .queue_family_index(ctx.queue_family_index)
@@ -39,7 +43,7 @@ pub(super) fn copy_buffer_to_image(
                     .base_mip_level(0)
                     .level_count(1)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(layers),
             )
             .src_access_mask(vk::AccessFlags::empty())
             .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
@@ -63,7 +67,7 @@ pub(super) fn copy_buffer_to_image(
                     .aspect_mask(vk::ImageAspectFlags::COLOR)
                     .mip_level(0)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(layers),
             )
             .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
             .image_extent(vk::Extent3D { width, height, depth: 1 });
@@ -93,6 +97,17 @@ pub(super) fn transition_to_shader_read(
     ctx: &VulkanContext,
     image: vk::Image,
     mip_levels: u32,
+    layers: u32,
+) -> ReactorResult<()> {
+    transition_from_to_shader_read(ctx, image, vk::ImageLayout::TRANSFER_DST_OPTIMAL, mip_levels, layers)
+}
+
+pub(super) fn transition_from_to_shader_read(
+    ctx: &VulkanContext,
+    image: vk::Image,
+    old_layout: vk::ImageLayout,
+    mip_levels: u32,
+    layers: u32,
 ) -> ReactorResult<()> {
     let pool_info = vk::CommandPoolCreateInfo::default()
         .queue_family_index(ctx.queue_family_index)
@@ -113,7 +128,7 @@ pub(super) fn transition_to_shader_read(
         ctx.device.begin_command_buffer(command_buffer, &begin_info)?;
 
         let barrier = vk::ImageMemoryBarrier::default()
-            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .old_layout(old_layout)
             .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
             .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
             .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
@@ -124,7 +139,7 @@ pub(super) fn transition_to_shader_read(
                     .base_mip_level(0)
                     .level_count(mip_levels)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(layers),
             )
             .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
             .dst_access_mask(vk::AccessFlags::SHADER_READ);
@@ -158,6 +173,7 @@ pub(super) fn generate_mipmaps(
     width: u32,
     height: u32,
     mip_levels: u32,
+    layers: u32,
 ) -> ReactorResult<()> {
     let pool_info = vk::CommandPoolCreateInfo::default()
         .queue_family_index(ctx.queue_family_index)
@@ -190,7 +206,7 @@ pub(super) fn generate_mipmaps(
                         .base_mip_level(1)
                         .level_count(mip_levels - 1)
                         .base_array_layer(0)
-                        .layer_count(1),
+                        .layer_count(layers),
                 )
                 .src_access_mask(vk::AccessFlags::empty())
                 .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
@@ -222,7 +238,7 @@ pub(super) fn generate_mipmaps(
                         .base_mip_level(i - 1)
                         .level_count(1)
                         .base_array_layer(0)
-                        .layer_count(1),
+                        .layer_count(layers),
                 )
                 .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
                 .dst_access_mask(vk::AccessFlags::TRANSFER_READ);
@@ -247,7 +263,7 @@ pub(super) fn generate_mipmaps(
                         .aspect_mask(vk::ImageAspectFlags::COLOR)
                         .mip_level(i - 1)
                         .base_array_layer(0)
-                        .layer_count(1),
+                        .layer_count(layers),
                 )
                 .dst_offsets([
                     vk::Offset3D { x: 0, y: 0, z: 0 },
@@ -262,7 +278,7 @@ pub(super) fn generate_mipmaps(
                         .aspect_mask(vk::ImageAspectFlags::COLOR)
                         .mip_level(i)
                         .base_array_layer(0)
-                        .layer_count(1),
+                        .layer_count(layers),
                 );
 
             ctx.device.cmd_blit_image(
@@ -287,7 +303,7 @@ pub(super) fn generate_mipmaps(
                         .base_mip_level(i - 1)
                         .level_count(1)
                         .base_array_layer(0)
-                        .layer_count(1),
+                        .layer_count(layers),
                 )
                 .src_access_mask(vk::AccessFlags::TRANSFER_READ)
                 .dst_access_mask(vk::AccessFlags::SHADER_READ);
@@ -318,7 +334,7 @@ pub(super) fn generate_mipmaps(
                     .base_mip_level(mip_levels - 1)
                     .level_count(1)
                     .base_array_layer(0)
-                    .layer_count(1),
+                    .layer_count(layers),
             )
             .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
             .dst_access_mask(vk::AccessFlags::SHADER_READ);
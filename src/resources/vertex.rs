@@ -3,6 +3,9 @@ use bytemuck::{Pod, Zeroable};
 use glam::{Vec2, Vec3};
 use std::mem;
 
+/// 32 bytes: position (location 0, offset 0), color (location 1, offset 12)
+/// and uv (location 2, offset 24). `with_normal` stores the normal in the
+/// color slot, which is how the forward shader reads it.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Vertex {
@@ -112,20 +115,90 @@ impl VertexPBR {
     }
 }
 
+// Skinned vertex: same first three attributes as `Vertex`, plus up to four
+// joint influences indexing the skin's joint matrix palette
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct VertexSkinned {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub uv: [f32; 2],
+    pub joints: [u32; 4],
+    pub weights: [f32; 4],
+}
+
+impl VertexSkinned {
+    pub fn new(position: Vec3, normal: Vec3, uv: Vec2, joints: [u32; 4], weights: [f32; 4]) -> Self {
+        Self {
+            position: position.to_array(),
+            normal: normal.to_array(),
+            uv: uv.to_array(),
+            joints,
+            weights,
+        }
+    }
+
+    pub fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::default()
+            .binding(0)
+            .stride(mem::size_of::<Self>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+    }
+
+    pub fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 5] {
+        let joints_offset = mem::size_of::<[f32; 3]>() * 2 + mem::size_of::<[f32; 2]>();
+        [
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(0)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(0),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(1)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(mem::size_of::<[f32; 3]>() as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(2)
+                .format(vk::Format::R32G32_SFLOAT)
+                .offset((mem::size_of::<[f32; 3]>() * 2) as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(3)
+                .format(vk::Format::R32G32B32A32_UINT)
+                .offset(joints_offset as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(4)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset((joints_offset + mem::size_of::<[u32; 4]>()) as u32),
+        ]
+    }
+}
+
 // Instance data for instanced rendering
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct InstanceData {
     pub model_matrix: [[f32; 4]; 4],
+    /// Color por instancia (location `start_location + 4`). Blanco por defecto.
+    pub color: [f32; 4],
 }
 
 impl InstanceData {
     pub fn new(transform: glam::Mat4) -> Self {
         Self {
             model_matrix: transform.to_cols_array_2d(),
+            color: [1.0; 4],
         }
     }
 
+    pub fn with_color(mut self, color: glam::Vec4) -> Self {
+        self.color = color.to_array();
+        self
+    }
+
     pub fn binding_description() -> vk::VertexInputBindingDescription {
         vk::VertexInputBindingDescription::default()
             .binding(1)
@@ -133,7 +206,7 @@ impl InstanceData {
             .input_rate(vk::VertexInputRate::INSTANCE)
     }
 
-    pub fn attribute_descriptions(start_location: u32) -> [vk::VertexInputAttributeDescription; 4] {
+    pub fn attribute_descriptions(start_location: u32) -> [vk::VertexInputAttributeDescription; 5] {
         [
             vk::VertexInputAttributeDescription::default()
                 .binding(1)
@@ -155,6 +228,78 @@ impl InstanceData {
                 .location(start_location + 3)
                 .format(vk::Format::R32G32B32A32_SFLOAT)
                 .offset((mem::size_of::<[f32; 4]>() * 3) as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(start_location + 4)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(mem::size_of::<[[f32; 4]; 4]>() as u32),
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{align_of, size_of};
+
+    /// `offset_of!` sólo existe desde Rust 1.77: se mide sobre un valor a cero.
+    macro_rules! field_offset {
+        ($ty:ty, $field:ident) => {{
+            let value: $ty = bytemuck::Zeroable::zeroed();
+            std::ptr::addr_of!(value.$field) as usize - std::ptr::addr_of!(value) as usize
+        }};
+    }
+
+    fn offsets(attributes: &[vk::VertexInputAttributeDescription]) -> Vec<u32> {
+        attributes.iter().map(|a| a.offset).collect()
+    }
+
+    // Los shaders y los ficheros cocinados dependen de estos layouts: un
+    // cambio de orden o de tamaño tiene que romper aquí primero.
+    #[test]
+    fn vertex_layouts_are_pinned() {
+        assert_eq!((size_of::<Vertex>(), align_of::<Vertex>()), (32, 4));
+        assert_eq!(field_offset!(Vertex, color), 12);
+        assert_eq!(field_offset!(Vertex, uv), 24);
+
+        assert_eq!((size_of::<VertexPBR>(), align_of::<VertexPBR>()), (48, 4));
+        assert_eq!(field_offset!(VertexPBR, tangent), 32);
+
+        assert_eq!((size_of::<VertexSkinned>(), align_of::<VertexSkinned>()), (64, 4));
+        assert_eq!(field_offset!(VertexSkinned, joints), 32);
+        assert_eq!(field_offset!(VertexSkinned, weights), 48);
+
+        assert_eq!((size_of::<InstanceData>(), align_of::<InstanceData>()), (80, 4));
+        assert_eq!(field_offset!(InstanceData, color), 64);
+    }
+
+    #[test]
+    fn attribute_offsets_follow_the_fields() {
+        assert_eq!(
+            offsets(&Vertex::attribute_descriptions()),
+            [field_offset!(Vertex, position), field_offset!(Vertex, color), field_offset!(Vertex, uv)].map(|o| o as u32)
+        );
+        assert_eq!(
+            offsets(&VertexPBR::attribute_descriptions()),
+            [
+                field_offset!(VertexPBR, position),
+                field_offset!(VertexPBR, normal),
+                field_offset!(VertexPBR, uv),
+                field_offset!(VertexPBR, tangent),
+            ]
+            .map(|o| o as u32)
+        );
+        assert_eq!(
+            offsets(&VertexSkinned::attribute_descriptions()),
+            [
+                field_offset!(VertexSkinned, position),
+                field_offset!(VertexSkinned, normal),
+                field_offset!(VertexSkinned, uv),
+                field_offset!(VertexSkinned, joints),
+                field_offset!(VertexSkinned, weights),
+            ]
+            .map(|o| o as u32)
+        );
+        assert_eq!(offsets(&InstanceData::attribute_descriptions(5))[4], field_offset!(InstanceData, color) as u32);
+    }
+}
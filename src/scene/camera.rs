@@ -1,5 +1,19 @@
+use crate::systems::physics::Ray;
 use glam::{Mat4, Quat, Vec3};
 
+/// Tipo de proyección de `Camera`. `aspect_ratio` se aplica en todos los modos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Usa `fov` (radianes, vertical).
+    Perspective,
+    /// `size` = alto visible en unidades de mundo; el ancho sale de `aspect_ratio`.
+    Orthographic { size: f32 },
+    /// 1 unidad de mundo = `pixels_per_unit` píxeles. El alto visible sigue al
+    /// viewport en cada `set_aspect_ratio`, así que un resize no reescala nada.
+    PixelOrthographic { pixels_per_unit: f32, viewport_height: f32 },
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct Camera {
     pub position: Vec3,
     pub rotation: Quat,
@@ -7,6 +21,7 @@ pub struct Camera {
     pub near: f32,
     pub far: f32,
     pub aspect_ratio: f32,
+    pub projection: Projection,
 }
 
 impl Camera {
@@ -18,6 +33,7 @@ impl Camera {
             near: 0.1,
             far: 1000.0,
             aspect_ratio: 16.0 / 9.0,
+            projection: Projection::Perspective,
         }
     }
 
@@ -29,9 +45,55 @@ impl Camera {
             near,
             far,
             aspect_ratio,
+            projection: Projection::Perspective,
+        }
+    }
+
+    /// Cámara ortográfica que ve `size` unidades de alto.
+    pub fn orthographic(size: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        Self {
+            projection: Projection::Orthographic { size },
+            ..Self::perspective(45.0, aspect_ratio, near, far)
         }
     }
 
+    /// Cámara ortográfica 2D con escala fija en píxeles (ver `Projection::PixelOrthographic`).
+    pub fn pixel_orthographic(pixels_per_unit: f32, width: f32, height: f32, near: f32, far: f32) -> Self {
+        let mut camera = Self {
+            projection: Projection::PixelOrthographic { pixels_per_unit, viewport_height: height },
+            ..Self::perspective(45.0, 1.0, near, far)
+        };
+        camera.set_aspect_ratio(width, height);
+        camera
+    }
+
+    /// Pasa a perspectiva con `fov_degrees` de FOV vertical.
+    pub fn set_perspective(&mut self, fov_degrees: f32, near: f32, far: f32) -> &mut Self {
+        self.projection = Projection::Perspective;
+        self.fov = fov_degrees.to_radians();
+        self.near = near;
+        self.far = far;
+        self
+    }
+
+    /// Pasa a ortográfica viendo `size` unidades de alto.
+    pub fn set_orthographic(&mut self, size: f32, near: f32, far: f32) -> &mut Self {
+        self.projection = Projection::Orthographic { size };
+        self.near = near;
+        self.far = far;
+        self
+    }
+
+    /// Cambia el FOV vertical (grados). Sólo afecta al modo perspectiva.
+    pub fn set_fov(&mut self, fov_degrees: f32) -> &mut Self {
+        self.fov = fov_degrees.to_radians();
+        self
+    }
+
+    pub fn is_orthographic(&self) -> bool {
+        !matches!(self.projection, Projection::Perspective)
+    }
+
     /// Builder-style: consume self and return with the new orientation.
     /// Útil al construir una cámara en una sola expresión.
     pub fn look_at(mut self, eye: Vec3, target: Vec3, _up: Vec3) -> Self {
@@ -65,8 +127,16 @@ impl Camera {
         self
     }
 
+    /// Actualiza el aspect ratio (y el alto visible en `PixelOrthographic`).
+    /// Ignora tamaños nulos (ventana minimizada).
     pub fn set_aspect_ratio(&mut self, width: f32, height: f32) {
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
         self.aspect_ratio = width / height;
+        if let Projection::PixelOrthographic { ref mut viewport_height, .. } = self.projection {
+            *viewport_height = height;
+        }
     }
 
     pub fn forward(&self) -> Vec3 {
@@ -87,15 +157,39 @@ impl Camera {
     }
 
     pub fn projection_matrix(&self) -> Mat4 {
-        let mut proj = Mat4::perspective_rh(self.fov, self.aspect_ratio, self.near, self.far);
+        let mut proj = match self.projection {
+            Projection::Perspective => Mat4::perspective_rh(self.fov, self.aspect_ratio, self.near, self.far),
+            Projection::Orthographic { .. } | Projection::PixelOrthographic { .. } => {
+                let half_h = self.ortho_height() * 0.5;
+                let half_w = half_h * self.aspect_ratio;
+                Mat4::orthographic_rh(-half_w, half_w, -half_h, half_h, self.near, self.far)
+            }
+        };
         proj.y_axis.y *= -1.0; // Vulkan Y-flip
         proj
     }
 
+    /// Alto visible (unidades de mundo) en los modos ortográficos; 0 en perspectiva.
+    pub fn ortho_height(&self) -> f32 {
+        match self.projection {
+            Projection::Perspective => 0.0,
+            Projection::Orthographic { size } => size,
+            Projection::PixelOrthographic { pixels_per_unit, viewport_height } => viewport_height / pixels_per_unit,
+        }
+    }
+
     pub fn view_projection_matrix(&self) -> Mat4 {
         self.projection_matrix() * self.view_matrix()
     }
 
+    /// Rayo desde la cámara a través del píxel `(screen_x, screen_y)` de un
+    /// viewport de `width × height` (origen arriba a la izquierda). Para
+    /// picking y gizmos; con `Reactor::set_viewport_rect` usa el tamaño y la
+    /// posición relativos al rectángulo de la escena.
+    pub fn screen_ray(&self, screen_x: f32, screen_y: f32, width: f32, height: f32) -> Ray {
+        Ray::from_screen(screen_x, screen_y, width, height, self.view_projection_matrix().inverse())
+    }
+
     // FPS-style camera controls
     pub fn rotate_yaw(&mut self, angle: f32) {
         self.rotation = Quat::from_rotation_y(angle) * self.rotation;
@@ -174,3 +268,50 @@ impl Default for Camera2D {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orthographic_flips_y_like_perspective() {
+        let camera = Camera::orthographic(10.0, 2.0, 0.1, 100.0);
+        let top = camera.projection_matrix().project_point3(Vec3::new(0.0, 5.0, -1.0));
+        let right = camera.projection_matrix().project_point3(Vec3::new(10.0, 0.0, -1.0));
+        assert!((top.y + 1.0).abs() < 1e-5, "world +Y must map to NDC -Y in Vulkan");
+        assert!((right.x - 1.0).abs() < 1e-5);
+
+        let persp = Camera::perspective(60.0, 2.0, 0.1, 100.0);
+        assert!(persp.projection_matrix().project_point3(Vec3::new(0.0, 1.0, -2.0)).y < 0.0);
+    }
+
+    #[test]
+    fn screen_ray_goes_through_the_pixel() {
+        let mut camera = Camera::perspective(60.0, 2.0, 0.1, 100.0);
+        camera.aim_at(Vec3::new(3.0, 2.0, 5.0), Vec3::ZERO);
+        let center = camera.screen_ray(400.0, 200.0, 800.0, 400.0);
+        assert!(center.direction.abs_diff_eq(camera.forward(), 1e-4));
+
+        let target = Vec3::new(1.0, -0.5, 0.25);
+        let pixel = camera.view_projection_matrix().project_point3(target);
+        let ray = camera.screen_ray((pixel.x + 1.0) * 400.0, (pixel.y + 1.0) * 200.0, 800.0, 400.0);
+        assert!(ray.direction.abs_diff_eq((target - ray.origin).normalize(), 1e-3));
+    }
+
+    #[test]
+    fn pixel_orthographic_keeps_scale_across_resizes() {
+        let mut camera = Camera::pixel_orthographic(32.0, 800.0, 600.0, -1.0, 1.0);
+        let one_unit_px = |c: &Camera, height: f32| {
+            let p = c.projection_matrix().project_point3(Vec3::new(0.0, 1.0, 0.0));
+            -p.y * height * 0.5
+        };
+        assert!((one_unit_px(&camera, 600.0) - 32.0).abs() < 1e-3);
+
+        camera.set_aspect_ratio(1920.0, 1080.0);
+        assert!((one_unit_px(&camera, 1080.0) - 32.0).abs() < 1e-3);
+        assert!((camera.aspect_ratio - 1920.0 / 1080.0).abs() < 1e-6);
+
+        camera.set_aspect_ratio(0.0, 0.0);
+        assert!((camera.aspect_ratio - 1920.0 / 1080.0).abs() < 1e-6);
+    }
+}
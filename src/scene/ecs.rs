@@ -6,10 +6,31 @@ pub type Entity = u32;
 pub trait Component: Any + Send + Sync {}
 impl<T: Any + Send + Sync> Component for T {}
 
+/// Almacén de un tipo de componente con borrado por entidad sin conocer `T`.
+trait ComponentStore: Any + Send + Sync {
+    fn remove_entity(&mut self, entity: Entity);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Component> ComponentStore for HashMap<Entity, T> {
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(&entity);
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 pub struct World {
     next_entity_id: Entity,
-    components: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    components: HashMap<TypeId, Box<dyn ComponentStore>>,
     entities: Vec<Entity>,
+    /// Posición de cada entidad viva en `entities`: `is_alive` y el borrado no recorren la lista.
+    slots: HashMap<Entity, usize>,
 }
 
 impl World {
@@ -18,26 +39,54 @@ impl World {
             next_entity_id: 0,
             components: HashMap::new(),
             entities: Vec::new(),
+            slots: HashMap::new(),
         }
     }
 
     pub fn create_entity(&mut self) -> Entity {
         let id = self.next_entity_id;
         self.next_entity_id += 1;
+        self.slots.insert(id, self.entities.len());
         self.entities.push(id);
         id
     }
 
-    pub fn destroy_entity(&mut self, entity: Entity) {
-        self.entities.retain(|&e| e != entity);
-        // Note: Components are not automatically removed
-        // A proper ECS would handle this
+    /// Destruye la entidad y todos sus componentes. Devuelve `false` si no existía.
+    pub fn destroy_entity(&mut self, entity: Entity) -> bool {
+        let Some(pos) = self.slots.remove(&entity) else {
+            return false;
+        };
+        self.entities.swap_remove(pos);
+        if let Some(&moved) = self.entities.get(pos) {
+            self.slots.insert(moved, pos);
+        }
+        for store in self.components.values_mut() {
+            store.remove_entity(entity);
+        }
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.slots.contains_key(&entity)
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
     }
 
     pub fn entity_count(&self) -> usize {
         self.entities.len()
     }
 
+    /// Id que recibirá la próxima entidad; el play mode lo guarda y lo repone.
+    pub(crate) fn next_entity_id(&self) -> Entity {
+        self.next_entity_id
+    }
+
+    pub(crate) fn set_next_entity_id(&mut self, id: Entity) {
+        self.next_entity_id = id;
+    }
+
     pub fn register_component<T: Component>(&mut self) {
         let type_id = TypeId::of::<T>();
         self.components
@@ -45,10 +94,14 @@ impl World {
             .or_insert_with(|| Box::new(HashMap::<Entity, T>::new()));
     }
 
+    /// Añade (o reemplaza) un componente. Se ignora si la entidad no está viva.
     pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        if !self.is_alive(entity) {
+            return;
+        }
         let type_id = TypeId::of::<T>();
         if let Some(store) = self.components.get_mut(&type_id) {
-            if let Some(map) = store.downcast_mut::<HashMap<Entity, T>>() {
+            if let Some(map) = store.as_any_mut().downcast_mut::<HashMap<Entity, T>>() {
                 map.insert(entity, component);
             }
         } else {
@@ -61,7 +114,7 @@ impl World {
     pub fn remove_component<T: Component>(&mut self, entity: Entity) -> Option<T> {
         let type_id = TypeId::of::<T>();
         if let Some(store) = self.components.get_mut(&type_id) {
-            if let Some(map) = store.downcast_mut::<HashMap<Entity, T>>() {
+            if let Some(map) = store.as_any_mut().downcast_mut::<HashMap<Entity, T>>() {
                 return map.remove(&entity);
             }
         }
@@ -71,7 +124,7 @@ impl World {
     pub fn get_component<T: Component>(&self, entity: Entity) -> Option<&T> {
         let type_id = TypeId::of::<T>();
         if let Some(store) = self.components.get(&type_id) {
-            if let Some(map) = store.downcast_ref::<HashMap<Entity, T>>() {
+            if let Some(map) = store.as_any().downcast_ref::<HashMap<Entity, T>>() {
                 return map.get(&entity);
             }
         }
@@ -81,7 +134,7 @@ impl World {
     pub fn get_component_mut<T: Component>(&mut self, entity: Entity) -> Option<&mut T> {
         let type_id = TypeId::of::<T>();
         if let Some(store) = self.components.get_mut(&type_id) {
-            if let Some(map) = store.downcast_mut::<HashMap<Entity, T>>() {
+            if let Some(map) = store.as_any_mut().downcast_mut::<HashMap<Entity, T>>() {
                 return map.get_mut(&entity);
             }
         }
@@ -96,7 +149,7 @@ impl World {
         let type_id = TypeId::of::<T>();
         self.components
             .get(&type_id)
-            .and_then(|store| store.downcast_ref::<HashMap<Entity, T>>())
+            .and_then(|store| store.as_any().downcast_ref::<HashMap<Entity, T>>())
             .into_iter()
             .flat_map(|map| map.iter().map(|(&e, c)| (e, c)))
     }
@@ -105,7 +158,7 @@ impl World {
         let type_id = TypeId::of::<T>();
         self.components
             .get_mut(&type_id)
-            .and_then(|store| store.downcast_mut::<HashMap<Entity, T>>())
+            .and_then(|store| store.as_any_mut().downcast_mut::<HashMap<Entity, T>>())
             .into_iter()
             .flat_map(|map| map.iter_mut().map(|(&e, c)| (e, c)))
     }
@@ -116,3 +169,53 @@ impl Default for World {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Position(i32);
+    struct Tag;
+
+    #[test]
+    fn create_and_destroy_keeps_counts_consistent() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..300).map(|_| world.create_entity()).collect();
+        for &e in &entities {
+            world.add_component(e, Position(e as i32));
+            if e % 2 == 0 {
+                world.add_component(e, Tag);
+            }
+        }
+        assert_eq!(world.entity_count(), 300);
+        assert_eq!(world.query::<Position>().count(), 300);
+        assert_eq!(world.query::<Tag>().count(), 150);
+
+        for &e in entities.iter().filter(|&&e| e % 3 == 0) {
+            assert!(world.destroy_entity(e));
+        }
+        assert_eq!(world.entity_count(), 200);
+        assert!(entities.iter().all(|&e| world.is_alive(e) == (e % 3 != 0)));
+        assert_eq!(world.query::<Position>().count(), 200);
+        assert!(world.query::<Position>().all(|(e, p)| world.is_alive(e) && p.0 == e as i32));
+        assert_eq!(world.query::<Tag>().count(), 100);
+    }
+
+    #[test]
+    fn destroyed_entity_loses_components_and_ids_are_not_reused() {
+        let mut world = World::new();
+        let a = world.create_entity();
+        world.add_component(a, Position(1));
+        assert!(world.destroy_entity(a));
+        assert!(!world.destroy_entity(a));
+        assert!(world.get_component::<Position>(a).is_none());
+
+        world.add_component(a, Position(2));
+        assert!(!world.has_component::<Position>(a));
+
+        let b = world.create_entity();
+        assert_ne!(a, b);
+        assert!(world.is_alive(b));
+    }
+}
@@ -0,0 +1,200 @@
+//! Jerarquía de entidades: `Parent` hace que el `Transform` de una entidad
+//! sea relativo al de su padre. `World::world_matrix` compone la cadena (el
+//! sync a `Scene` y a `LightingSystem` ya la usa) y `set_world_transform`
+//! escribe el local a partir de una pose en mundo, como necesita un gizmo.
+
+use crate::core::error::{ReactorError, ReactorResult};
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform::Transform;
+use glam::Mat4;
+
+/// Padre de la entidad. Usar `World::set_parent`, que rechaza ciclos; si se
+/// añade a mano y forma un ciclo, `world_matrix` corta la cadena al detectarlo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parent(pub Entity);
+
+/// Qué hacer con los hijos al destruir una entidad con `destroy_recursive`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChildPolicy {
+    /// Se destruyen también, con toda su descendencia.
+    Destroy,
+    /// Pasan al padre de la entidad (o a raíz) conservando su pose en mundo.
+    Reparent,
+}
+
+impl World {
+    pub fn parent(&self, entity: Entity) -> Option<Entity> {
+        self.get_component::<Parent>(entity).map(|p| p.0)
+    }
+
+    /// Hijos directos, ordenados por id.
+    pub fn children(&self, entity: Entity) -> Vec<Entity> {
+        let mut children: Vec<Entity> = self.query::<Parent>().filter(|(_, p)| p.0 == entity).map(|(e, _)| e).collect();
+        children.sort_unstable();
+        children
+    }
+
+    /// Cambia el padre (`None` = raíz) conservando la pose en mundo del hijo,
+    /// como al arrastrar en un panel de jerarquía. Error si alguna entidad no
+    /// existe o si `parent` desciende de `child`.
+    pub fn set_parent(&mut self, child: Entity, parent: Option<Entity>) -> ReactorResult<()> {
+        if !self.is_alive(child) {
+            return Err(ReactorError::invalid_parameter(format!("Entity {} does not exist", child)));
+        }
+        if let Some(parent) = parent {
+            if !self.is_alive(parent) {
+                return Err(ReactorError::invalid_parameter(format!("Entity {} does not exist", parent)));
+            }
+            if parent == child || self.ancestors(parent).contains(&child) {
+                return Err(ReactorError::invalid_parameter(format!(
+                    "Entity {} cannot be parented to its descendant {}", child, parent
+                )));
+            }
+        }
+        let world = self.world_matrix(child);
+        match parent {
+            Some(parent) => self.add_component(child, Parent(parent)),
+            None => { self.remove_component::<Parent>(child); }
+        }
+        self.set_world_transform(child, world);
+        Ok(())
+    }
+
+    /// Matriz en mundo: el `Transform` local (identidad si no tiene)
+    /// compuesto con el de cada ancestro.
+    pub fn world_matrix(&self, entity: Entity) -> Mat4 {
+        let local = |e: Entity| self.get_component::<Transform>(e).map(Transform::matrix).unwrap_or(Mat4::IDENTITY);
+        self.ancestors(entity).into_iter().fold(local(entity), |matrix, ancestor| local(ancestor) * matrix)
+    }
+
+    /// Escribe el `Transform` local que deja a la entidad en `world` (p. ej.
+    /// el resultado de un gizmo que trabaja en mundo).
+    pub fn set_world_transform(&mut self, entity: Entity, world: Mat4) {
+        let parent_world = self.parent(entity).map_or(Mat4::IDENTITY, |parent| self.world_matrix(parent));
+        self.add_component(entity, Transform::from(parent_world.inverse() * world));
+    }
+
+    /// Destruye la entidad y aplica `policy` a sus hijos. Devuelve `false` si
+    /// no existía.
+    pub fn destroy_recursive(&mut self, entity: Entity, policy: ChildPolicy) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+        let grandparent = self.parent(entity);
+        for child in self.children(entity) {
+            match policy {
+                ChildPolicy::Destroy => { self.destroy_recursive(child, ChildPolicy::Destroy); }
+                ChildPolicy::Reparent => {
+                    // `grandparent` es ancestro de `entity`, no de `child`: no hay ciclo.
+                    let _ = self.set_parent(child, grandparent);
+                }
+            }
+        }
+        self.destroy_entity(entity)
+    }
+
+    /// Padre, abuelo… hasta la raíz. Un ciclo (sólo posible con `Parent`
+    /// añadido a mano) corta la cadena en vez de colgarse.
+    fn ancestors(&self, entity: Entity) -> Vec<Entity> {
+        let mut chain = Vec::new();
+        let mut current = entity;
+        while let Some(parent) = self.parent(current) {
+            if parent == entity || chain.contains(&parent) {
+                log::warn!("Entity hierarchy cycle at entity {}; treating it as a root", current);
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Quat, Vec3};
+
+    fn spawn(world: &mut World, transform: Transform) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(entity, transform);
+        entity
+    }
+
+    fn world_position(world: &World, entity: Entity) -> Vec3 {
+        world.world_matrix(entity).transform_point3(Vec3::ZERO)
+    }
+
+    #[test]
+    fn children_follow_their_parent() {
+        let mut world = World::new();
+        let cube = spawn(&mut world, Transform::from_translation(10.0, 0.0, 0.0));
+        let light = spawn(&mut world, Transform::from_translation(0.0, 2.0, 0.0));
+        world.add_component(light, Parent(cube));
+        assert!(world_position(&world, light).abs_diff_eq(Vec3::new(10.0, 2.0, 0.0), 1e-5));
+
+        world.get_component_mut::<Transform>(cube).unwrap().rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        assert!(world_position(&world, light).abs_diff_eq(Vec3::new(8.0, 0.0, 0.0), 1e-5));
+        assert_eq!(world.children(cube), [light]);
+    }
+
+    #[test]
+    fn reparenting_keeps_the_world_pose() {
+        let mut world = World::new();
+        let group = spawn(&mut world, Transform::from_translation(5.0, 0.0, 0.0).with_uniform_scale(2.0));
+        let child = spawn(&mut world, Transform::from_translation(1.0, 1.0, 1.0));
+
+        world.set_parent(child, Some(group)).unwrap();
+        assert!(world_position(&world, child).abs_diff_eq(Vec3::ONE, 1e-5));
+        let local = world.get_component::<Transform>(child).unwrap();
+        assert!(local.position.abs_diff_eq(Vec3::new(-2.0, 0.5, 0.5), 1e-5));
+
+        world.set_parent(child, None).unwrap();
+        assert_eq!(world.parent(child), None);
+        assert!(world.get_component::<Transform>(child).unwrap().position.abs_diff_eq(Vec3::ONE, 1e-5));
+    }
+
+    #[test]
+    fn gizmo_writes_local_relative_to_parent() {
+        let mut world = World::new();
+        let parent = spawn(&mut world, Transform::from_translation(0.0, 3.0, 0.0));
+        let child = spawn(&mut world, Transform::new());
+        world.add_component(child, Parent(parent));
+
+        world.set_world_transform(child, Mat4::from_translation(Vec3::new(1.0, 3.0, 0.0)));
+        assert!(world.get_component::<Transform>(child).unwrap().position.abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn cycles_are_rejected_and_survived() {
+        let mut world = World::new();
+        let a = spawn(&mut world, Transform::from_translation(1.0, 0.0, 0.0));
+        let b = spawn(&mut world, Transform::from_translation(1.0, 0.0, 0.0));
+        world.set_parent(b, Some(a)).unwrap();
+        assert!(world.set_parent(a, Some(b)).is_err());
+        assert!(world.set_parent(a, Some(a)).is_err());
+
+        // A cycle built by hand must not hang the walk.
+        world.add_component(a, Parent(b));
+        let _ = world.world_matrix(a);
+        let _ = world.world_matrix(b);
+    }
+
+    #[test]
+    fn deleting_a_parent_destroys_or_reparents_children() {
+        let mut world = World::new();
+        let root = spawn(&mut world, Transform::from_translation(1.0, 0.0, 0.0));
+        let middle = spawn(&mut world, Transform::from_translation(1.0, 0.0, 0.0));
+        let leaf = spawn(&mut world, Transform::from_translation(1.0, 0.0, 0.0));
+        world.add_component(middle, Parent(root));
+        world.add_component(leaf, Parent(middle));
+
+        assert!(world.destroy_recursive(middle, ChildPolicy::Reparent));
+        assert_eq!(world.parent(leaf), Some(root));
+        assert!(world_position(&world, leaf).abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 1e-5));
+
+        assert!(world.destroy_recursive(root, ChildPolicy::Destroy));
+        assert!(!world.is_alive(leaf));
+        assert_eq!(world.entity_count(), 0);
+    }
+}
@@ -4,8 +4,12 @@
 
 pub mod camera;
 pub mod ecs;
+pub mod hierarchy;
 pub mod transform;
+pub mod transform_hierarchy;
 
-pub use camera::Camera;
+pub use camera::{Camera, Projection};
 pub use ecs::{Component, Entity, World};
+pub use hierarchy::{ChildPolicy, Parent};
 pub use transform::Transform;
+pub use transform_hierarchy::{TransformHierarchy, TransformNode};
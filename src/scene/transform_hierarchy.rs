@@ -0,0 +1,308 @@
+//! Árbol de transforms con matrices en mundo cacheadas. Cada nodo guarda su
+//! `Transform` local, su padre y sus hijos; cambiar un local (o el padre)
+//! marca sucio el nodo y toda su descendencia, y solo esos nodos se
+//! recalculan: `world_matrix` bajo demanda o `update` en una pasada de padres
+//! a hijos. Los objetos del `Scene` pueden colgar de un nodo
+//! (`SceneObject::node`); `Scene::cull` les copia la matriz cuando cambia.
+//!
+//! Es independiente de `Parent` en el ECS (`World::world_matrix`), que
+//! recorre la cadena en cada llamada: esto es para árboles grandes que se
+//! tocan poco, como los nodos de un glTF.
+
+use crate::core::error::{ReactorError, ReactorResult};
+use crate::scene::transform::Transform;
+use glam::Mat4;
+
+/// Referencia a un nodo de `TransformHierarchy`. Como `SceneHandle`, deja de
+/// resolver cuando su nodo se borra en vez de apuntar al que reutiliza el hueco.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TransformNode {
+    index: u32,
+    generation: u32,
+}
+
+struct Node {
+    generation: u32,
+    alive: bool,
+    local: Transform,
+    parent: Option<u32>,
+    children: Vec<u32>,
+    world: Mat4,
+    /// Si está sucio, toda su descendencia también.
+    dirty: bool,
+}
+
+#[derive(Default)]
+pub struct TransformHierarchy {
+    nodes: Vec<Node>,
+    free: Vec<u32>,
+    /// Nodos marcados sucios con el padre limpio: de aquí parte `update`.
+    dirty_roots: Vec<u32>,
+    /// Nodos recalculados desde el último `take_changed`.
+    changed: Vec<TransformNode>,
+    recomputed: u64,
+}
+
+impl TransformHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Añade un nodo con `local` relativo a `parent` (`None` = raíz).
+    pub fn insert(&mut self, local: Transform, parent: Option<TransformNode>) -> ReactorResult<TransformNode> {
+        let parent = parent.map(|p| self.resolve(p)).transpose()?;
+        let node = Node {
+            generation: 0,
+            alive: true,
+            local,
+            parent,
+            children: Vec::new(),
+            world: Mat4::IDENTITY,
+            dirty: false,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                let generation = self.nodes[index as usize].generation;
+                self.nodes[index as usize] = Node { generation, ..node };
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                (self.nodes.len() - 1) as u32
+            }
+        };
+        if let Some(parent) = parent {
+            self.nodes[parent as usize].children.push(index);
+        }
+        self.mark_dirty(index);
+        Ok(self.handle(index))
+    }
+
+    /// Borra el nodo y toda su descendencia. `false` si ya no existía.
+    pub fn remove(&mut self, node: TransformNode) -> bool {
+        let Ok(index) = self.resolve(node) else { return false };
+        if let Some(parent) = self.nodes[index as usize].parent {
+            self.nodes[parent as usize].children.retain(|&child| child != index);
+        }
+        let mut stack = vec![index];
+        while let Some(index) = stack.pop() {
+            let node = &mut self.nodes[index as usize];
+            stack.append(&mut node.children);
+            node.alive = false;
+            node.generation = node.generation.wrapping_add(1);
+            self.free.push(index);
+        }
+        true
+    }
+
+    pub fn contains(&self, node: TransformNode) -> bool {
+        self.resolve(node).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn local(&self, node: TransformNode) -> Option<Transform> {
+        Some(self.nodes[self.resolve(node).ok()? as usize].local)
+    }
+
+    /// Cambia el transform local y marca sucio el subárbol.
+    pub fn set_local(&mut self, node: TransformNode, local: Transform) -> ReactorResult<()> {
+        let index = self.resolve(node)?;
+        self.nodes[index as usize].local = local;
+        self.mark_dirty(index);
+        Ok(())
+    }
+
+    pub fn parent(&self, node: TransformNode) -> Option<TransformNode> {
+        let index = self.resolve(node).ok()?;
+        self.nodes[index as usize].parent.map(|parent| self.handle(parent))
+    }
+
+    pub fn children(&self, node: TransformNode) -> Vec<TransformNode> {
+        self.resolve(node)
+            .map(|index| self.nodes[index as usize].children.iter().map(|&child| self.handle(child)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Cambia el padre conservando el transform local (el nodo se mueve con
+    /// su nuevo padre). Error si `parent` desciende de `node`.
+    pub fn set_parent(&mut self, node: TransformNode, parent: Option<TransformNode>) -> ReactorResult<()> {
+        let index = self.resolve(node)?;
+        let parent = parent.map(|p| self.resolve(p)).transpose()?;
+        let mut ancestor = parent;
+        while let Some(current) = ancestor {
+            if current == index {
+                return Err(ReactorError::invalid_parameter("A transform node cannot be parented to its descendant"));
+            }
+            ancestor = self.nodes[current as usize].parent;
+        }
+        if let Some(old) = self.nodes[index as usize].parent {
+            self.nodes[old as usize].children.retain(|&child| child != index);
+        }
+        if let Some(parent) = parent {
+            self.nodes[parent as usize].children.push(index);
+        }
+        self.nodes[index as usize].parent = parent;
+        self.mark_dirty(index);
+        Ok(())
+    }
+
+    /// Matriz en mundo. Si está sucia recalcula solo la cadena de ancestros
+    /// sucios y el propio nodo; sus hijos siguen sucios hasta que se pidan.
+    pub fn world_matrix(&mut self, node: TransformNode) -> Option<Mat4> {
+        let index = self.resolve(node).ok()?;
+        let mut chain = Vec::new();
+        let mut current = Some(index);
+        while let Some(i) = current.filter(|&i| self.nodes[i as usize].dirty) {
+            chain.push(i);
+            current = self.nodes[i as usize].parent;
+        }
+        for &i in chain.iter().rev() {
+            self.recompute(i);
+            // Sus hijos siguen sucios pero ahora cuelgan de un nodo limpio.
+            let dirty_children = self.nodes[i as usize].children.iter().filter(|&&c| self.nodes[c as usize].dirty);
+            self.dirty_roots.extend(dirty_children);
+        }
+        Some(self.nodes[index as usize].world)
+    }
+
+    /// Recalcula todos los nodos sucios, de padres a hijos, sin tocar los
+    /// subárboles limpios. Devuelve cuántos recalculó.
+    pub fn update(&mut self) -> usize {
+        let before = self.recomputed;
+        for root in std::mem::take(&mut self.dirty_roots) {
+            if !self.nodes[root as usize].alive || !self.nodes[root as usize].dirty {
+                continue;
+            }
+            // Un ancestro pudo ensuciarse después; `world_matrix` lo resuelve.
+            let root_handle = self.handle(root);
+            self.world_matrix(root_handle);
+            let mut stack = self.nodes[root as usize].children.clone();
+            while let Some(index) = stack.pop() {
+                // Los hijos limpios ya están al día, y los suyos sucios en `dirty_roots`.
+                if self.nodes[index as usize].dirty {
+                    self.recompute(index);
+                    stack.extend_from_slice(&self.nodes[index as usize].children);
+                }
+            }
+        }
+        (self.recomputed - before) as usize
+    }
+
+    /// Nodos cuya matriz en mundo cambió desde la última llamada.
+    pub fn take_changed(&mut self) -> Vec<TransformNode> {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Matrices recalculadas desde que se creó el árbol (para medir).
+    pub fn recompute_count(&self) -> u64 {
+        self.recomputed
+    }
+
+    fn handle(&self, index: u32) -> TransformNode {
+        TransformNode { index, generation: self.nodes[index as usize].generation }
+    }
+
+    fn resolve(&self, node: TransformNode) -> ReactorResult<u32> {
+        match self.nodes.get(node.index as usize) {
+            Some(n) if n.alive && n.generation == node.generation => Ok(node.index),
+            _ => Err(ReactorError::invalid_parameter("Transform node no longer exists")),
+        }
+    }
+
+    /// Marca `index` y su descendencia. Para en los nodos ya sucios: los
+    /// suyos ya lo están.
+    fn mark_dirty(&mut self, index: u32) {
+        if self.nodes[index as usize].dirty {
+            return;
+        }
+        let parent_clean = self.nodes[index as usize].parent.map_or(true, |p| !self.nodes[p as usize].dirty);
+        if parent_clean {
+            self.dirty_roots.push(index);
+        }
+        let mut stack = vec![index];
+        while let Some(i) = stack.pop() {
+            let node = &mut self.nodes[i as usize];
+            if node.dirty && i != index {
+                continue;
+            }
+            node.dirty = true;
+            stack.extend_from_slice(&node.children);
+        }
+    }
+
+    /// Recalcula un nodo cuyo padre ya está limpio.
+    fn recompute(&mut self, index: u32) {
+        let node = &self.nodes[index as usize];
+        let parent_world = node.parent.map_or(Mat4::IDENTITY, |p| self.nodes[p as usize].world);
+        let world = parent_world * node.local.matrix();
+        let node = &mut self.nodes[index as usize];
+        node.world = world;
+        node.dirty = false;
+        self.recomputed += 1;
+        self.changed.push(TransformNode { index, generation: node.generation });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn world_matrices_follow_parents_lazily() {
+        let mut tree = TransformHierarchy::new();
+        let root = tree.insert(Transform::from_translation(1.0, 0.0, 0.0), None).unwrap();
+        let child = tree.insert(Transform::from_translation(0.0, 2.0, 0.0).with_uniform_scale(2.0), Some(root)).unwrap();
+        let leaf = tree.insert(Transform::from_translation(0.0, 0.0, 1.0), Some(child)).unwrap();
+
+        let origin = |tree: &mut TransformHierarchy, node| tree.world_matrix(node).unwrap().transform_point3(Vec3::ZERO);
+        assert_eq!(origin(&mut tree, leaf), Vec3::new(1.0, 2.0, 2.0));
+
+        tree.set_local(root, Transform::from_translation(5.0, 0.0, 0.0)).unwrap();
+        assert_eq!(origin(&mut tree, leaf), Vec3::new(5.0, 2.0, 2.0));
+
+        tree.set_parent(leaf, None).unwrap();
+        assert_eq!(origin(&mut tree, leaf), Vec3::new(0.0, 0.0, 1.0));
+        assert!(tree.set_parent(root, Some(child)).is_err());
+
+        assert!(tree.remove(root));
+        assert!(!tree.contains(child) && tree.contains(leaf));
+        assert!(tree.world_matrix(child).is_none());
+        let reused = tree.insert(Transform::new(), None).unwrap();
+        assert!(!tree.contains(root) && tree.contains(reused));
+    }
+
+    #[test]
+    fn update_only_touches_dirty_subtrees() {
+        let mut tree = TransformHierarchy::new();
+        let chain_root = tree.insert(Transform::new(), None).unwrap();
+        let mut tail = chain_root;
+        for _ in 1..10_000 {
+            tail = tree.insert(Transform::from_translation(0.0, 1.0, 0.0), Some(tail)).unwrap();
+        }
+        let other = tree.insert(Transform::new(), None).unwrap();
+        let other_child = tree.insert(Transform::from_translation(1.0, 0.0, 0.0), Some(other)).unwrap();
+        assert_eq!(tree.update(), 10_002);
+        assert_eq!(tree.take_changed().len(), 10_002);
+        assert_eq!(tree.update(), 0);
+
+        tree.set_local(other, Transform::from_translation(0.0, 0.0, 3.0)).unwrap();
+        assert_eq!(tree.update(), 2);
+        assert_eq!(tree.take_changed(), [other, other_child]);
+
+        tree.set_local(tail, Transform::from_translation(0.0, 2.0, 0.0)).unwrap();
+        assert_eq!(tree.update(), 1);
+
+        tree.set_local(chain_root, Transform::from_translation(1.0, 0.0, 0.0)).unwrap();
+        assert_eq!(tree.update(), 10_000);
+        let tip = tree.world_matrix(tail).unwrap().transform_point3(Vec3::ZERO);
+        assert_eq!(tip, Vec3::new(1.0, 10_000.0, 0.0));
+    }
+}
@@ -1,4 +1,9 @@
-use glam::{Quat, Vec3};
+use crate::resources::gltf_loader::GltfAnimation;
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform::Transform;
+use crate::systems::scene::Scene;
+use crate::systems::skinning::Skeleton;
+use glam::{Mat4, Quat, Vec3};
 use std::collections::HashMap;
 
 /// Keyframe for animation
@@ -160,6 +165,23 @@ impl AnimationClip {
         max_duration
     }
 
+    /// Maps playback time into `0..=duration` according to `loop_mode`.
+    /// `sample` takes clip time as-is; callers that loop map it here first.
+    pub fn local_time(&self, time: f32) -> f32 {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+        match self.loop_mode {
+            LoopMode::Once => time.clamp(0.0, duration),
+            LoopMode::Loop => time.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let t = time.rem_euclid(duration * 2.0);
+                if t <= duration { t } else { duration * 2.0 - t }
+            }
+        }
+    }
+
     pub fn sample(&self, time: f32) -> AnimationSample {
         AnimationSample {
             position: self.position_track.as_ref().and_then(|t| t.sample(time)),
@@ -178,6 +200,16 @@ pub struct AnimationSample {
 }
 
 impl AnimationSample {
+    /// Replaces the animated parts of a TRS matrix, keeping the rest.
+    pub fn apply_to_matrix(&self, matrix: &mut Mat4) {
+        let (scale, rotation, position) = matrix.to_scale_rotation_translation();
+        *matrix = Mat4::from_scale_rotation_translation(
+            self.scale.unwrap_or(scale),
+            self.rotation.unwrap_or(rotation),
+            self.position.unwrap_or(position),
+        );
+    }
+
     pub fn apply_to_transform(&self, transform: &mut crate::scene::transform::Transform) {
         if let Some(pos) = self.position {
             transform.position = pos;
@@ -284,6 +316,306 @@ impl Default for AnimationPlayer {
     }
 }
 
+/// Handle to a clip registered in an `AnimationSystem`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AnimationClipId(pub u32);
+
+/// What an `AnimationSystem` clip drives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationTarget {
+    /// A `SceneObject`, by its stable `id`.
+    Object(u64),
+    /// The `Transform` component of an ECS entity.
+    Entity(Entity),
+}
+
+/// Handle to a skinned glTF model registered in an `AnimationSystem`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SkinnedModelId(pub u32);
+
+struct SkinnedModelInstance {
+    skeleton: Skeleton,
+    animations: Vec<GltfAnimation>,
+    /// Scene objects (by stable id) drawn with this skeleton's palette.
+    objects: Vec<u64>,
+    current: Option<usize>,
+    time: f32,
+    speed: f32,
+    looping: bool,
+    playing: bool,
+}
+
+struct ClipInstance {
+    clip: AnimationClip,
+    targets: Vec<AnimationTarget>,
+    time: f32,
+    speed: f32,
+    playing: bool,
+}
+
+/// Playback position of every clip and skinned model of an
+/// `AnimationSystem`, taken by `playback` and put back by `restore_playback`
+/// (play mode uses it to rewind on stop). Keyframes and bindings are not part
+/// of it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnimationPlayback {
+    clips: Vec<(AnimationClipId, LoopMode, f32, f32, bool)>,
+    skinned: Vec<(SkinnedModelId, Option<usize>, f32, f32, bool, bool)>,
+}
+
+/// Registry of clips bound to scene objects or entities.
+///
+/// `update` advances every playing clip and writes the sampled
+/// position/rotation/scale into its targets. A `LoopMode::Once` clip stops on
+/// its last keyframe and leaves the target there.
+///
+/// Skinned models are posed the same way: `update` samples their current
+/// glTF animation and writes the joint palette into each of their objects'
+/// `SceneObject::joint_matrices`.
+#[derive(Default)]
+pub struct AnimationSystem {
+    clips: HashMap<AnimationClipId, ClipInstance>,
+    next_id: u32,
+    skinned: HashMap<SkinnedModelId, SkinnedModelInstance>,
+    next_skinned_id: u32,
+}
+
+impl AnimationSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create_clip(&mut self, name: &str) -> AnimationClipId {
+        self.add_clip(AnimationClip::new(name))
+    }
+
+    pub fn add_clip(&mut self, clip: AnimationClip) -> AnimationClipId {
+        self.next_id += 1;
+        let id = AnimationClipId(self.next_id);
+        self.clips.insert(id, ClipInstance { clip, targets: Vec::new(), time: 0.0, speed: 1.0, playing: false });
+        id
+    }
+
+    pub fn remove_clip(&mut self, id: AnimationClipId) -> Option<AnimationClip> {
+        self.clips.remove(&id).map(|instance| instance.clip)
+    }
+
+    pub fn clip(&self, id: AnimationClipId) -> Option<&AnimationClip> {
+        self.clips.get(&id).map(|instance| &instance.clip)
+    }
+
+    pub fn clip_mut(&mut self, id: AnimationClipId) -> Option<&mut AnimationClip> {
+        self.clips.get_mut(&id).map(|instance| &mut instance.clip)
+    }
+
+    pub fn add_position_keyframe(&mut self, id: AnimationClipId, time: f32, position: Vec3) -> bool {
+        let Some(clip) = self.clip_mut(id) else { return false };
+        clip.position_track.get_or_insert_with(AnimationTrack::new).add_keyframe(time, position);
+        true
+    }
+
+    pub fn add_rotation_keyframe(&mut self, id: AnimationClipId, time: f32, rotation: Quat) -> bool {
+        let Some(clip) = self.clip_mut(id) else { return false };
+        clip.rotation_track.get_or_insert_with(AnimationTrack::new).add_keyframe(time, rotation);
+        true
+    }
+
+    pub fn add_scale_keyframe(&mut self, id: AnimationClipId, time: f32, scale: Vec3) -> bool {
+        let Some(clip) = self.clip_mut(id) else { return false };
+        clip.scale_track.get_or_insert_with(AnimationTrack::new).add_keyframe(time, scale);
+        true
+    }
+
+    /// Adds a target; a clip can drive several at once.
+    pub fn bind(&mut self, id: AnimationClipId, target: AnimationTarget) -> bool {
+        let Some(instance) = self.clips.get_mut(&id) else { return false };
+        if !instance.targets.contains(&target) {
+            instance.targets.push(target);
+        }
+        true
+    }
+
+    pub fn unbind(&mut self, id: AnimationClipId, target: AnimationTarget) -> bool {
+        let Some(instance) = self.clips.get_mut(&id) else { return false };
+        let before = instance.targets.len();
+        instance.targets.retain(|t| *t != target);
+        instance.targets.len() != before
+    }
+
+    /// Restarts the clip from time 0 with the given loop mode.
+    pub fn play(&mut self, id: AnimationClipId, loop_mode: LoopMode) -> bool {
+        let Some(instance) = self.clips.get_mut(&id) else { return false };
+        instance.clip.loop_mode = loop_mode;
+        instance.time = 0.0;
+        instance.playing = true;
+        true
+    }
+
+    pub fn stop(&mut self, id: AnimationClipId) -> bool {
+        let Some(instance) = self.clips.get_mut(&id) else { return false };
+        instance.playing = false;
+        true
+    }
+
+    /// Playback rate multiplier (1.0 = authored speed).
+    pub fn set_speed(&mut self, id: AnimationClipId, speed: f32) -> bool {
+        let Some(instance) = self.clips.get_mut(&id) else { return false };
+        instance.speed = speed.max(0.0);
+        true
+    }
+
+    pub fn is_playing(&self, id: AnimationClipId) -> bool {
+        self.clips.get(&id).is_some_and(|instance| instance.playing)
+    }
+
+    /// Current playback time of the clip, in clip seconds.
+    pub fn time(&self, id: AnimationClipId) -> Option<f32> {
+        self.clips.get(&id).map(|instance| instance.clip.local_time(instance.time))
+    }
+
+    /// Registers a skinned model whose `objects` share one skeleton. They are
+    /// drawn in the rest pose until an animation plays.
+    pub fn add_skinned_model(&mut self, skeleton: Skeleton, animations: Vec<GltfAnimation>, objects: Vec<u64>) -> SkinnedModelId {
+        self.next_skinned_id += 1;
+        let id = SkinnedModelId(self.next_skinned_id);
+        self.skinned.insert(id, SkinnedModelInstance {
+            skeleton,
+            animations,
+            objects,
+            current: None,
+            time: 0.0,
+            speed: 1.0,
+            looping: false,
+            playing: false,
+        });
+        id
+    }
+
+    pub fn remove_skinned_model(&mut self, id: SkinnedModelId) -> bool {
+        self.skinned.remove(&id).is_some()
+    }
+
+    /// Skinned model that owns the scene object with the given stable id.
+    pub fn model_of_object(&self, object_id: u64) -> Option<SkinnedModelId> {
+        self.skinned.iter().find(|(_, m)| m.objects.contains(&object_id)).map(|(id, _)| *id)
+    }
+
+    pub fn model_animation_names(&self, id: SkinnedModelId) -> Vec<&str> {
+        self.skinned.get(&id).map(|m| m.animations.iter().map(|a| a.name.as_str()).collect()).unwrap_or_default()
+    }
+
+    /// Starts the named glTF animation from time 0. `false` if the model or
+    /// the animation does not exist.
+    pub fn play_model_animation(&mut self, id: SkinnedModelId, name: &str, looping: bool) -> bool {
+        let Some(model) = self.skinned.get_mut(&id) else { return false };
+        let Some(index) = model.animations.iter().position(|a| a.name == name) else { return false };
+        model.current = Some(index);
+        model.time = 0.0;
+        model.looping = looping;
+        model.playing = true;
+        true
+    }
+
+    /// Freezes the model on its current pose.
+    pub fn stop_model_animation(&mut self, id: SkinnedModelId) -> bool {
+        let Some(model) = self.skinned.get_mut(&id) else { return false };
+        model.playing = false;
+        true
+    }
+
+    pub fn set_model_speed(&mut self, id: SkinnedModelId, speed: f32) -> bool {
+        let Some(model) = self.skinned.get_mut(&id) else { return false };
+        model.speed = speed.max(0.0);
+        true
+    }
+
+    pub fn is_model_playing(&self, id: SkinnedModelId) -> bool {
+        self.skinned.get(&id).is_some_and(|m| m.playing)
+    }
+
+    pub fn playback(&self) -> AnimationPlayback {
+        AnimationPlayback {
+            clips: self.clips.iter().map(|(id, c)| (*id, c.clip.loop_mode, c.time, c.speed, c.playing)).collect(),
+            skinned: self
+                .skinned
+                .iter()
+                .map(|(id, m)| (*id, m.current, m.time, m.speed, m.looping, m.playing))
+                .collect(),
+        }
+    }
+
+    /// Rewinds clips and models to `playback`. Ones added since it was taken
+    /// are left as they are; removed ones are skipped.
+    pub fn restore_playback(&mut self, playback: &AnimationPlayback) {
+        for &(id, loop_mode, time, speed, playing) in &playback.clips {
+            if let Some(instance) = self.clips.get_mut(&id) {
+                instance.clip.loop_mode = loop_mode;
+                (instance.time, instance.speed, instance.playing) = (time, speed, playing);
+            }
+        }
+        for &(id, current, time, speed, looping, playing) in &playback.skinned {
+            if let Some(model) = self.skinned.get_mut(&id) {
+                (model.current, model.time, model.speed, model.looping, model.playing) = (current, time, speed, looping, playing);
+            }
+        }
+    }
+
+    /// Advances playing clips by `delta_time` and writes their samples into
+    /// the bound scene objects and entities. Targets that no longer exist are
+    /// skipped.
+    pub fn update(&mut self, delta_time: f32, scene: &mut Scene, world: &mut World) {
+        self.update_skinned(delta_time, scene);
+
+        for instance in self.clips.values_mut().filter(|instance| instance.playing) {
+            instance.time += delta_time * instance.speed;
+            if instance.clip.loop_mode == LoopMode::Once && instance.time >= instance.clip.duration() {
+                instance.time = instance.clip.duration();
+                instance.playing = false;
+            }
+
+            let sample = instance.clip.sample(instance.clip.local_time(instance.time));
+            for target in &instance.targets {
+                match *target {
+                    AnimationTarget::Object(id) => {
+                        if let Some(object) = scene.get_by_id_mut(id) {
+                            sample.apply_to_matrix(&mut object.transform);
+                        }
+                    }
+                    AnimationTarget::Entity(entity) => {
+                        if let Some(transform) = world.get_component_mut::<Transform>(entity) {
+                            sample.apply_to_transform(transform);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn update_skinned(&mut self, delta_time: f32, scene: &mut Scene) {
+        for model in self.skinned.values_mut() {
+            let animation = model.current.and_then(|i| model.animations.get(i));
+            if let (true, Some(animation)) = (model.playing, animation) {
+                model.time += delta_time * model.speed;
+                if model.time >= animation.duration {
+                    if model.looping && animation.duration > 0.0 {
+                        model.time %= animation.duration;
+                    } else {
+                        model.time = animation.duration;
+                        model.playing = false;
+                    }
+                }
+            }
+
+            let palette = model.skeleton.palette(animation, model.time);
+            for &id in &model.objects {
+                if let Some(object) = scene.get_by_id_mut(id) {
+                    object.joint_matrices.clone_from(&palette);
+                }
+            }
+        }
+    }
+}
+
 /// Tween for simple value animations
 pub struct Tween<T: Clone + Interpolate> {
     pub start: T,
@@ -400,3 +732,69 @@ impl EasingFunction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounce(system: &mut AnimationSystem) -> AnimationClipId {
+        let clip = system.create_clip("bounce");
+        system.add_position_keyframe(clip, 0.0, Vec3::ZERO);
+        system.add_position_keyframe(clip, 1.0, Vec3::new(0.0, 2.0, 0.0));
+        clip
+    }
+
+    #[test]
+    fn once_clip_moves_its_entity_and_stops_on_the_last_key() {
+        let mut system = AnimationSystem::new();
+        let mut scene = Scene::new();
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Transform::new());
+        let clip = bounce(&mut system);
+        system.bind(clip, AnimationTarget::Entity(entity));
+        system.play(clip, LoopMode::Once);
+
+        system.update(0.25, &mut scene, &mut world);
+        let y = world.get_component::<Transform>(entity).unwrap().position.y;
+        assert!((y - 0.5).abs() < 1e-5);
+
+        system.update(2.0, &mut scene, &mut world);
+        assert!(!system.is_playing(clip));
+        assert_eq!(world.get_component::<Transform>(entity).unwrap().position, Vec3::new(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn ping_pong_and_speed_run_the_clip_backwards_on_odd_cycles() {
+        let mut system = AnimationSystem::new();
+        let mut scene = Scene::new();
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Transform::new());
+        let clip = bounce(&mut system);
+        system.bind(clip, AnimationTarget::Entity(entity));
+        system.play(clip, LoopMode::PingPong);
+        system.set_speed(clip, 2.0);
+
+        // 0.75 s at 2x = 1.5 s of clip time: on the way back, halfway down.
+        system.update(0.75, &mut scene, &mut world);
+        assert!(system.is_playing(clip));
+        assert!((system.time(clip).unwrap() - 0.5).abs() < 1e-5);
+        let y = world.get_component::<Transform>(entity).unwrap().position.y;
+        assert!((y - 1.0).abs() < 1e-5);
+
+        // The clip itself does no wrapping: past its end it holds the last key.
+        let clip = system.clip(clip).unwrap();
+        assert_eq!(clip.sample(1.5).position, Some(Vec3::new(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn sampling_only_overrides_animated_channels() {
+        let sample = AnimationSample { position: Some(Vec3::X), rotation: None, scale: None };
+        let mut matrix = Mat4::from_scale(Vec3::splat(3.0));
+        sample.apply_to_matrix(&mut matrix);
+        let (scale, _, position) = matrix.to_scale_rotation_translation();
+        assert!((scale - Vec3::splat(3.0)).length() < 1e-5);
+        assert_eq!(position, Vec3::X);
+    }
+}
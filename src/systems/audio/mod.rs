@@ -1,3 +1,4 @@
+mod playback;
 mod system;
 mod types;
 
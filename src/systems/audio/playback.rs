@@ -0,0 +1,174 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::systems::audio::types::{AudioListener, AudioSource};
+
+/// Ganancias izquierda/derecha compartidas con el hilo de audio. Se actualizan
+/// sin bloquear mientras el clip suena (posición de fuente o de oyente).
+#[derive(Debug)]
+pub(crate) struct PanGains {
+    left: AtomicU32,
+    right: AtomicU32,
+}
+
+impl PanGains {
+    pub(crate) fn new([left, right]: [f32; 2]) -> Self {
+        Self { left: AtomicU32::new(left.to_bits()), right: AtomicU32::new(right.to_bits()) }
+    }
+
+    pub(crate) fn set(&self, [left, right]: [f32; 2]) {
+        self.left.store(left.to_bits(), Ordering::Relaxed);
+        self.right.store(right.to_bits(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> [f32; 2] {
+        [f32::from_bits(self.left.load(Ordering::Relaxed)), f32::from_bits(self.right.load(Ordering::Relaxed))]
+    }
+}
+
+/// Atenuación lineal entre `min_distance` (1.0) y `max_distance` (0.0).
+pub(crate) fn distance_attenuation(listener: &AudioListener, source: &AudioSource) -> f32 {
+    let distance = (source.position - listener.position).length();
+    if distance <= source.min_distance {
+        1.0
+    } else if distance >= source.max_distance {
+        0.0
+    } else {
+        1.0 - (distance - source.min_distance) / (source.max_distance - source.min_distance)
+    }
+}
+
+/// Ganancias `[izquierda, derecha]` de una fuente espacial: atenuación por
+/// distancia y paneo de potencia constante según el lado del oyente en que cae.
+/// Al frente (o encima del oyente) ambos canales valen la atenuación.
+pub(crate) fn spatial_gains(listener: &AudioListener, source: &AudioSource) -> [f32; 2] {
+    let attenuation = distance_attenuation(listener, source);
+    let right_axis = listener.forward.cross(listener.up).normalize_or_zero();
+    let to_source = (source.position - listener.position).normalize_or_zero();
+    let pan = right_axis.dot(to_source).clamp(-1.0, 1.0);
+    let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+    let left = (angle.cos() * std::f32::consts::SQRT_2).min(1.0);
+    let right = (angle.sin() * std::f32::consts::SQRT_2).min(1.0);
+    [left * attenuation, right * attenuation]
+}
+
+/// Reproduce las muestras de un clip. Con `pan` mezcla a mono y sale en
+/// estéreo con las ganancias compartidas; sin él, sale tal cual.
+pub(crate) struct ClipPlayback {
+    samples: Arc<Vec<f32>>,
+    channels: u16,
+    sample_rate: u32,
+    looping: bool,
+    cursor: usize,
+    pan: Option<Arc<PanGains>>,
+    pending_right: Option<f32>,
+}
+
+impl ClipPlayback {
+    pub(crate) fn new(samples: Arc<Vec<f32>>, channels: u32, sample_rate: u32, looping: bool, pan: Option<Arc<PanGains>>) -> Self {
+        Self { samples, channels: channels.max(1) as u16, sample_rate, looping, cursor: 0, pan, pending_right: None }
+    }
+
+    /// Avanza `count` muestras, volviendo al inicio si hace loop.
+    fn advance(&mut self, count: usize) -> Option<usize> {
+        if self.cursor + count > self.samples.len() {
+            if !self.looping || self.samples.len() < count {
+                return None;
+            }
+            self.cursor = 0;
+        }
+        let start = self.cursor;
+        self.cursor += count;
+        Some(start)
+    }
+}
+
+impl Iterator for ClipPlayback {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.pan.is_none() {
+            let start = self.advance(1)?;
+            return Some(self.samples[start]);
+        }
+        if let Some(right) = self.pending_right.take() {
+            return Some(right);
+        }
+        let channels = self.channels as usize;
+        let start = self.advance(channels)?;
+        let mono = self.samples[start..start + channels].iter().sum::<f32>() / channels as f32;
+        let [left, right] = self.pan.as_ref().map_or([1.0, 1.0], |pan| pan.get());
+        self.pending_right = Some(mono * right);
+        Some(mono * left)
+    }
+}
+
+impl rodio::Source for ClipPlayback {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        if self.pan.is_some() { 2 } else { self.channels }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        if self.looping {
+            return None;
+        }
+        let frames = self.samples.len() / self.channels as usize;
+        Some(Duration::from_secs_f64(frames as f64 / self.sample_rate as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    fn source_at(position: Vec3) -> AudioSource {
+        AudioSource { position, min_distance: 1.0, max_distance: 11.0, ..Default::default() }
+    }
+
+    #[test]
+    fn pans_towards_the_side_the_source_is_on() {
+        let listener = AudioListener::default(); // mira a -Z, derecha = +X
+        let [l, r] = spatial_gains(&listener, &source_at(Vec3::new(1.0, 0.0, 0.0)));
+        assert!(r > 0.99 && l < 0.01, "{l} {r}");
+        let [l, r] = spatial_gains(&listener, &source_at(Vec3::new(0.0, 0.0, -1.0)));
+        assert!((l - 1.0).abs() < 1e-4 && (r - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn attenuates_linearly_between_min_and_max_distance() {
+        let listener = AudioListener::default();
+        assert_eq!(distance_attenuation(&listener, &source_at(Vec3::new(0.0, 0.0, -0.5))), 1.0);
+        assert!((distance_attenuation(&listener, &source_at(Vec3::new(0.0, 0.0, -6.0))) - 0.5).abs() < 1e-5);
+        assert_eq!(distance_attenuation(&listener, &source_at(Vec3::new(0.0, 0.0, -20.0))), 0.0);
+    }
+
+    #[test]
+    fn spatial_playback_mixes_to_mono_and_follows_gain_updates() {
+        let gains = Arc::new(PanGains::new([1.0, 0.0]));
+        let samples = Arc::new(vec![0.2, 0.4, 0.6, 0.8]);
+        let mut playback = ClipPlayback::new(samples, 2, 48000, false, Some(gains.clone()));
+        assert_eq!(rodio::Source::channels(&playback), 2);
+        assert!((playback.next().unwrap() - 0.3).abs() < 1e-6);
+        assert_eq!(playback.next(), Some(0.0));
+        gains.set([0.0, 1.0]);
+        assert_eq!(playback.next(), Some(0.0));
+        assert!((playback.next().unwrap() - 0.7).abs() < 1e-6);
+        assert_eq!(playback.next(), None);
+    }
+
+    #[test]
+    fn looping_playback_wraps_around() {
+        let mut playback = ClipPlayback::new(Arc::new(vec![1.0, 2.0]), 1, 8000, true, None);
+        let out: Vec<f32> = playback.by_ref().take(5).collect();
+        assert_eq!(out, vec![1.0, 2.0, 1.0, 2.0, 1.0]);
+    }
+}
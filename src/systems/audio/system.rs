@@ -1,8 +1,18 @@
 use std::collections::HashMap;
-use std::io::Cursor;
+use std::sync::Arc;
 use glam::Vec3;
+use crate::systems::audio::playback::{distance_attenuation, spatial_gains, ClipPlayback, PanGains};
 use crate::systems::audio::types::*;
 
+/// Un sink de rodio sonando para una fuente.
+struct Voice {
+    sink: rodio::Sink,
+    /// Volumen de la fuente (y de su categoría), antes del master.
+    gain: f32,
+    /// Ganancias L/R si la fuente es espacial.
+    pan: Option<Arc<PanGains>>,
+}
+
 pub struct AudioSystem {
     clips: HashMap<AudioClipId, AudioClip>,
     sources: HashMap<AudioSourceId, AudioSource>,
@@ -15,7 +25,7 @@ pub struct AudioSystem {
     enabled: bool,
     _stream: Option<rodio::OutputStream>,
     stream_handle: Option<rodio::OutputStreamHandle>,
-    active_sinks: HashMap<AudioSourceId, rodio::Sink>,
+    voices: HashMap<AudioSourceId, Voice>,
 }
 
 impl AudioSystem {
@@ -43,7 +53,7 @@ impl AudioSystem {
             enabled: true,
             _stream: stream,
             stream_handle,
-            active_sinks: HashMap::new(),
+            voices: HashMap::new(),
         }
     }
 
@@ -51,8 +61,16 @@ impl AudioSystem {
         self.enabled = enabled;
     }
 
+    /// Volumen global; se aplica también a lo que ya está sonando.
     pub fn set_master_volume(&mut self, volume: f32) {
         self.master_volume = volume.clamp(0.0, 1.0);
+        for voice in self.voices.values() {
+            voice.sink.set_volume(voice.gain * self.master_volume);
+        }
+    }
+
+    pub fn master_volume(&self) -> f32 {
+        self.master_volume
     }
 
     pub fn set_music_volume(&mut self, volume: f32) {
@@ -65,6 +83,15 @@ impl AudioSystem {
 
     pub fn update_listener(&mut self, listener: AudioListener) {
         self.listener = listener;
+        self.refresh_spatial();
+    }
+
+    pub fn set_listener(&mut self, position: Vec3, forward: Vec3, up: Vec3) {
+        self.update_listener(AudioListener { position, forward, up, velocity: self.listener.velocity });
+    }
+
+    pub fn listener(&self) -> &AudioListener {
+        &self.listener
     }
 
     pub fn register_clip(&mut self, name: &str, duration: f32) -> AudioClipId {
@@ -79,7 +106,7 @@ impl AudioSystem {
                 duration,
                 channels: 2,
                 sample_rate: 44100,
-                raw_bytes: std::sync::Arc::new(Vec::new()),
+                samples: Arc::new(Vec::new()),
             },
         );
 
@@ -116,6 +143,37 @@ impl AudioSystem {
         self.sources.get_mut(&id)
     }
 
+    pub fn clip(&self, id: AudioClipId) -> Option<&AudioClip> {
+        self.clips.get(&id)
+    }
+
+    /// Mueve una fuente; si está sonando, su paneo/atenuación cambian al momento.
+    pub fn set_source_position(&mut self, id: AudioSourceId, position: Vec3) -> bool {
+        let Some(source) = self.sources.get_mut(&id) else { return false };
+        source.position = position;
+        self.refresh_spatial();
+        true
+    }
+
+    /// Ganancia propia de la fuente (0..1), multiplicada por el master.
+    pub fn set_source_volume(&mut self, id: AudioSourceId, volume: f32) -> bool {
+        let Some(source) = self.sources.get_mut(&id) else { return false };
+        source.volume = volume.clamp(0.0, 1.0);
+        if let Some(voice) = self.voices.get_mut(&id) {
+            voice.gain = source.volume;
+            voice.sink.set_volume(voice.gain * self.master_volume);
+        }
+        true
+    }
+
+    fn refresh_spatial(&self) {
+        for (id, voice) in &self.voices {
+            if let (Some(pan), Some(source)) = (&voice.pan, self.sources.get(id)) {
+                pan.set(spatial_gains(&self.listener, source));
+            }
+        }
+    }
+
     pub fn play(&mut self, id: AudioSourceId) {
         let (clip_id, volume, looping) = {
             if let Some(source) = self.sources.get_mut(&id) {
@@ -137,8 +195,8 @@ impl AudioSystem {
             source.playing = false;
             source.time = 0.0;
         }
-        if let Some(sink) = self.active_sinks.remove(&id) {
-            sink.stop();
+        if let Some(voice) = self.voices.remove(&id) {
+            voice.sink.stop();
         }
     }
 
@@ -146,8 +204,8 @@ impl AudioSystem {
         if let Some(source) = self.sources.get_mut(&id) {
             source.playing = false;
         }
-        if let Some(sink) = self.active_sinks.get(&id) {
-            sink.pause();
+        if let Some(voice) = self.voices.get(&id) {
+            voice.sink.pause();
         }
     }
 
@@ -155,8 +213,8 @@ impl AudioSystem {
         if let Some(source) = self.sources.get_mut(&id) {
             source.playing = true;
         }
-        if let Some(sink) = self.active_sinks.get(&id) {
-            sink.play();
+        if let Some(voice) = self.voices.get(&id) {
+            voice.sink.play();
         }
     }
 
@@ -180,13 +238,7 @@ impl AudioSystem {
             source.playing = true;
         }
 
-        let effective_volume = if let Some(source) = self.sources.get(&id) {
-            self.calculate_spatial_volume_for(source) * self.master_volume * self.sfx_volume
-        } else {
-            volume * self.master_volume * self.sfx_volume
-        };
-
-        self.play_clip_on_sink(id, clip, effective_volume, false);
+        self.play_clip_on_sink(id, clip, volume * self.sfx_volume, false);
         id
     }
 
@@ -194,7 +246,7 @@ impl AudioSystem {
         &mut self,
         source_id: AudioSourceId,
         clip_id: AudioClipId,
-        volume: f32,
+        gain: f32,
         looping: bool,
     ) {
         if !self.enabled {
@@ -211,7 +263,7 @@ impl AudioSystem {
             None => return,
         };
 
-        if clip.raw_bytes.is_empty() {
+        if clip.samples.is_empty() {
             return;
         }
 
@@ -223,25 +275,24 @@ impl AudioSystem {
             }
         };
 
-        sink.set_volume(volume * self.master_volume);
-
-        let cursor = Cursor::new(clip.raw_bytes.as_ref().clone());
-        match rodio::Decoder::new(cursor) {
-            Ok(decoded) => {
-                if looping {
-                    use rodio::Source;
-                    sink.append(decoded.repeat_infinite());
-                } else {
-                    sink.append(decoded);
-                }
-            }
-            Err(e) => {
-                log::warn!("Failed to decode audio clip '{}': {e}", clip.name);
-                return;
-            }
+        let pan = self
+            .sources
+            .get(&source_id)
+            .filter(|source| source.spatial)
+            .map(|source| Arc::new(PanGains::new(spatial_gains(&self.listener, source))));
+
+        sink.set_volume(gain * self.master_volume);
+        sink.append(ClipPlayback::new(
+            clip.samples.clone(),
+            clip.channels,
+            clip.sample_rate,
+            looping,
+            pan.clone(),
+        ));
+
+        if let Some(old) = self.voices.insert(source_id, Voice { sink, gain, pan }) {
+            old.sink.stop();
         }
-
-        self.active_sinks.insert(source_id, sink);
     }
 
     pub fn update(&mut self, delta_time: f32) {
@@ -273,19 +324,19 @@ impl AudioSystem {
             }
         }
 
-        for (id, sink) in &self.active_sinks {
-            if sink.empty() {
+        for (id, voice) in &self.voices {
+            if voice.sink.empty() {
                 finished_sinks.push(*id);
             }
         }
 
         for id in &to_remove {
             self.sources.remove(id);
-            self.active_sinks.remove(id);
+            self.voices.remove(id);
         }
         for id in &finished_sinks {
             if !to_remove.contains(id) {
-                self.active_sinks.remove(id);
+                self.voices.remove(id);
                 if let Some(source) = self.sources.get_mut(id) {
                     source.playing = false;
                 }
@@ -302,17 +353,7 @@ impl AudioSystem {
             return source.volume;
         }
 
-        let distance = (source.position - self.listener.position).length();
-
-        if distance <= source.min_distance {
-            source.volume
-        } else if distance >= source.max_distance {
-            0.0
-        } else {
-            let range = source.max_distance - source.min_distance;
-            let attenuation = 1.0 - (distance - source.min_distance) / range;
-            source.volume * attenuation
-        }
+        source.volume * distance_attenuation(&self.listener, source)
     }
 
     pub fn active_source_count(&self) -> usize {
@@ -320,7 +361,7 @@ impl AudioSystem {
     }
 
     pub fn active_sink_count(&self) -> usize {
-        self.active_sinks.values().filter(|s| !s.empty()).count()
+        self.voices.values().filter(|voice| !voice.sink.empty()).count()
     }
 }
 
@@ -7,6 +7,10 @@ pub struct AudioClipId(pub u32);
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct AudioSourceId(pub u32);
 
+/// Clip decodificado (WAV, OGG Vorbis, FLAC o MP3) a muestras `f32` intercaladas.
+///
+/// Se decodifica una sola vez al cargar; cada reproducción comparte las
+/// muestras vía `Arc`, sin volver a leer el archivo.
 #[derive(Clone, Debug)]
 pub struct AudioClip {
     pub id: AudioClipId,
@@ -14,7 +18,7 @@ pub struct AudioClip {
     pub duration: f32,
     pub channels: u32,
     pub sample_rate: u32,
-    pub(crate) raw_bytes: Arc<Vec<u8>>,
+    pub(crate) samples: Arc<Vec<f32>>,
 }
 
 impl AudioClip {
@@ -25,38 +29,18 @@ impl AudioClip {
         Self::from_bytes(path.as_ref().to_string_lossy().to_string(), &bytes)
     }
 
+    /// Decodifica el archivo completo. No abre ningún dispositivo de salida.
     pub fn from_bytes(
         name: String,
         bytes: &[u8],
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let mut channels = 2;
-        let mut sample_rate = 44100;
-        let mut duration = 0.0;
-
-        if bytes.len() >= 44 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
-            let mut cursor = 12;
-            while cursor + 8 < bytes.len() {
-                let chunk_id = &bytes[cursor..cursor + 4];
-                let chunk_size =
-                    u32::from_le_bytes(bytes[cursor + 4..cursor + 8].try_into()?) as usize;
-                cursor += 8;
-
-                if chunk_id == b"fmt " && cursor + 16 <= bytes.len() {
-                    channels = u16::from_le_bytes(bytes[cursor + 2..cursor + 4].try_into()?) as u32;
-                    sample_rate = u32::from_le_bytes(bytes[cursor + 4..cursor + 8].try_into()?);
-                } else if chunk_id == b"data" {
-                    let bytes_per_sample = 2;
-                    let total_samples = chunk_size / (channels as usize * bytes_per_sample);
-                    duration = total_samples as f32 / sample_rate as f32;
-                    break;
-                }
-                cursor += chunk_size;
-            }
-        }
+        use rodio::Source;
 
-        if duration == 0.0 {
-            duration = (bytes.len() as f32 / 176400.0).max(0.1);
-        }
+        let decoder = rodio::Decoder::new(std::io::Cursor::new(bytes.to_vec()))?;
+        let channels = decoder.channels().max(1) as u32;
+        let sample_rate = decoder.sample_rate().max(1);
+        let samples: Vec<f32> = decoder.convert_samples().collect();
+        let duration = (samples.len() / channels as usize) as f32 / sample_rate as f32;
 
         Ok(Self {
             id: AudioClipId(0),
@@ -64,9 +48,19 @@ impl AudioClip {
             duration,
             channels,
             sample_rate,
-            raw_bytes: Arc::new(bytes.to_vec()),
+            samples: Arc::new(samples),
         })
     }
+
+    /// Muestras por canal.
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Muestras totales (todos los canales).
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -169,3 +163,46 @@ impl AudioListener {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WAV PCM de 16 bits con `frames` muestras por canal (rampa).
+    fn wav_bytes(channels: u16, sample_rate: u32, frames: u32) -> Vec<u8> {
+        let data_len = frames * channels as u32 * 2;
+        let mut bytes = Vec::with_capacity(44 + data_len as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for i in 0..frames * channels as u32 {
+            bytes.extend_from_slice(&((i % 1000) as i16 * 16).to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn decodes_wav_without_an_output_device() {
+        let clip = AudioClip::from_bytes("ramp.wav".into(), &wav_bytes(2, 22050, 11025)).unwrap();
+        assert_eq!(clip.channels, 2);
+        assert_eq!(clip.sample_rate, 22050);
+        assert_eq!(clip.frame_count(), 11025);
+        assert_eq!(clip.sample_count(), 22050);
+        assert!((clip.duration - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_audio() {
+        assert!(AudioClip::from_bytes("junk".into(), b"definitely not audio").is_err());
+        assert!(AudioClip::from_file("does/not/exist.ogg").is_err());
+    }
+}
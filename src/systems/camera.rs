@@ -0,0 +1,408 @@
+// Camera controllers: fly (free look + movement) and orbit (focus point +
+// distance). Both own their yaw/pitch instead of reading them back from the
+// camera's quaternion, so the pitch clamp holds and nothing flips near the
+// poles. Smoothing is exponential in `dt`: one 33 ms frame ends where two
+// 16.5 ms frames would.
+
+use crate::platform::input::Input;
+use crate::scene::camera::Camera;
+use crate::systems::physics::AABB;
+use glam::{Vec2, Vec3};
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Largest pitch either controller allows. `Camera::view_matrix` uses +Y as
+/// up, which degenerates when looking straight up or down.
+pub const MAX_CAMERA_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// Factor per wheel notch in the `adjust_speed` methods.
+const SPEED_STEP: f32 = 1.2;
+
+/// `value` scaled by `SPEED_STEP` per step, clamped to `[min, max]`.
+fn step_speed(value: f32, steps: f32, min: f32, max: f32) -> f32 {
+    (value * SPEED_STEP.powf(steps)).clamp(min, max)
+}
+
+/// Extra room around the framed bounds in `OrbitCameraController::frame_bounds`.
+const FRAME_MARGIN: f32 = 1.1;
+
+/// Fraction of the remaining distance covered in `dt` seconds when easing
+/// at `rate` per second. A rate of 0 (or less) means no smoothing.
+pub fn damping_factor(rate: f32, dt: f32) -> f32 {
+    if rate <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-rate * dt.max(0.0)).exp()
+    }
+}
+
+/// Forward vector for a yaw/pitch pair, as `Camera::set_rotation` builds it.
+fn forward_from(yaw: f32, pitch: f32) -> Vec3 {
+    Vec3::new(-yaw.sin() * pitch.cos(), pitch.sin(), -yaw.cos() * pitch.cos())
+}
+
+fn clamp_pitch(pitch: f32, limit: f32) -> f32 {
+    let limit = limit.clamp(0.0, MAX_CAMERA_PITCH);
+    pitch.clamp(-limit, limit)
+}
+
+/// Input of one `FlyCameraController::update`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FlyCameraInput {
+    /// Movement axes in camera space: x = right, y = world up, z = forward.
+    /// Each in `[-1, 1]`; longer vectors are normalized.
+    pub movement: Vec3,
+    /// Look delta in pixels (mouse-style: +x right, +y down).
+    pub look: Vec2,
+    pub boost: bool,
+}
+
+impl FlyCameraInput {
+    /// WASD + Space/Ctrl to move, Shift to boost, mouse delta to look while
+    /// the right button is down (or always, with a locked cursor).
+    pub fn from_input(input: &Input) -> Self {
+        let axis = |positive: KeyCode, negative: KeyCode| {
+            input.is_key_down(positive) as i32 as f32 - input.is_key_down(negative) as i32 as f32
+        };
+        let looking = input.is_mouse_button_down(MouseButton::Right)
+            || input.cursor_mode() == crate::platform::CursorMode::Locked;
+        Self {
+            movement: Vec3::new(
+                axis(KeyCode::KeyD, KeyCode::KeyA),
+                axis(KeyCode::Space, KeyCode::ControlLeft),
+                axis(KeyCode::KeyW, KeyCode::KeyS),
+            ),
+            look: if looking { input.mouse_delta() } else { Vec2::ZERO },
+            boost: input.is_shift_down(),
+        }
+    }
+}
+
+/// Free-flying camera: mouse look plus movement along the view direction.
+#[derive(Clone, Debug)]
+pub struct FlyCameraController {
+    /// Units per second at full input.
+    pub speed: f32,
+    pub boost_multiplier: f32,
+    /// Radians per pixel of look input.
+    pub sensitivity: f32,
+    /// How fast velocity and orientation catch up with the input, per second.
+    /// 0 disables smoothing.
+    pub damping: f32,
+    /// Pitch limit in radians, capped at `MAX_CAMERA_PITCH`.
+    pub pitch_limit: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub velocity: Vec3,
+    target_yaw: f32,
+    target_pitch: f32,
+    synced: bool,
+}
+
+impl Default for FlyCameraController {
+    fn default() -> Self {
+        Self {
+            speed: 6.0,
+            boost_multiplier: 3.0,
+            sensitivity: 0.003,
+            damping: 12.0,
+            pitch_limit: MAX_CAMERA_PITCH,
+            yaw: 0.0,
+            pitch: 0.0,
+            velocity: Vec3::ZERO,
+            target_yaw: 0.0,
+            target_pitch: 0.0,
+            synced: false,
+        }
+    }
+}
+
+impl FlyCameraController {
+    pub fn new(speed: f32, sensitivity: f32) -> Self {
+        Self { speed, sensitivity, ..Self::default() }
+    }
+
+    /// Wheel-adjustable movement speed (Blender-style): each step scales
+    /// `speed` by 1.2. Returns the new speed, for an on-screen hint.
+    pub fn adjust_speed(&mut self, steps: f32) -> f32 {
+        self.speed = step_speed(self.speed, steps, 0.01, 10_000.0);
+        self.speed
+    }
+
+    /// Takes yaw and pitch from `camera`, dropping any smoothing in flight.
+    /// `update` does this on its first call.
+    pub fn sync(&mut self, camera: &Camera) {
+        self.yaw = camera.yaw();
+        self.pitch = clamp_pitch(camera.pitch(), self.pitch_limit);
+        self.target_yaw = self.yaw;
+        self.target_pitch = self.pitch;
+        self.velocity = Vec3::ZERO;
+        self.synced = true;
+    }
+
+    pub fn update(&mut self, camera: &mut Camera, dt: f32, input: FlyCameraInput) {
+        if !self.synced {
+            self.sync(camera);
+        }
+        let k = damping_factor(self.damping, dt);
+
+        self.target_yaw -= input.look.x * self.sensitivity;
+        self.target_pitch = clamp_pitch(self.target_pitch - input.look.y * self.sensitivity, self.pitch_limit);
+        self.yaw += (self.target_yaw - self.yaw) * k;
+        self.pitch = clamp_pitch(self.pitch + (self.target_pitch - self.pitch) * k, self.pitch_limit);
+        camera.set_rotation(self.yaw, self.pitch);
+
+        let movement = if input.movement.length_squared() > 1.0 { input.movement.normalize() } else { input.movement };
+        let boost = if input.boost { self.boost_multiplier } else { 1.0 };
+        let direction = camera.right() * movement.x + Vec3::Y * movement.y + camera.forward() * movement.z;
+        let target_velocity = direction * self.speed * boost;
+        self.velocity += (target_velocity - self.velocity) * k;
+        camera.position += self.velocity * dt;
+    }
+}
+
+/// Input of one `OrbitCameraController::update`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrbitCameraInput {
+    /// Orbit delta in pixels (+x drags the view right, +y down).
+    pub orbit: Vec2,
+    /// Pan delta in pixels; the focus point follows the cursor.
+    pub pan: Vec2,
+    /// Zoom steps (wheel notches); positive moves closer.
+    pub zoom: f32,
+}
+
+impl OrbitCameraInput {
+    /// Left drag orbits, middle drag (or Shift + left) pans, the wheel zooms.
+    pub fn from_input(input: &Input) -> Self {
+        let delta = input.mouse_delta();
+        let left = input.is_mouse_button_down(MouseButton::Left);
+        let panning = input.is_mouse_button_down(MouseButton::Middle) || (left && input.is_shift_down());
+        Self {
+            orbit: if left && !panning { delta } else { Vec2::ZERO },
+            pan: if panning { delta } else { Vec2::ZERO },
+            zoom: input.scroll_delta(),
+        }
+    }
+}
+
+/// Camera circling a focus point: orbit, pan and zoom as in a model viewer
+/// or editor viewport.
+#[derive(Clone, Debug)]
+pub struct OrbitCameraController {
+    pub focus: Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Radians per pixel of orbit input.
+    pub sensitivity: f32,
+    /// Focus movement per pixel of pan input, as a fraction of `distance`.
+    pub pan_speed: f32,
+    /// Distance change per zoom step, as a fraction of `distance`.
+    pub zoom_speed: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// How fast the view catches up with the input, per second. 0 disables
+    /// smoothing.
+    pub damping: f32,
+    /// Pitch limit in radians, capped at `MAX_CAMERA_PITCH`.
+    pub pitch_limit: f32,
+    /// Scales pan and zoom on top of the distance scaling; see `adjust_speed`.
+    pub speed_multiplier: f32,
+    target_focus: Vec3,
+    target_distance: f32,
+    target_yaw: f32,
+    target_pitch: f32,
+}
+
+impl Default for OrbitCameraController {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, 10.0)
+    }
+}
+
+impl OrbitCameraController {
+    pub fn new(focus: Vec3, distance: f32) -> Self {
+        let pitch = -0.4;
+        Self {
+            focus,
+            distance,
+            yaw: 0.0,
+            pitch,
+            sensitivity: 0.005,
+            pan_speed: 0.0015,
+            zoom_speed: 0.1,
+            min_distance: 0.1,
+            max_distance: 1000.0,
+            damping: 15.0,
+            pitch_limit: MAX_CAMERA_PITCH,
+            speed_multiplier: 1.0,
+            target_focus: focus,
+            target_distance: distance,
+            target_yaw: 0.0,
+            target_pitch: pitch,
+        }
+    }
+
+    /// Orbits around `focus` from the camera's current position and
+    /// direction, without smoothing.
+    pub fn from_camera(camera: &Camera, focus: Vec3) -> Self {
+        let mut controller = Self::new(focus, 1.0);
+        controller.look_from(camera.position, focus);
+        controller
+    }
+
+    /// Jumps to look at `focus` from `eye`, skipping smoothing.
+    pub fn look_from(&mut self, eye: Vec3, focus: Vec3) {
+        let offset = focus - eye;
+        let distance = offset.length().max(self.min_distance);
+        let forward = offset.normalize_or(Vec3::NEG_Z);
+        self.focus = focus;
+        self.distance = distance;
+        self.yaw = (-forward.x).atan2(-forward.z);
+        self.pitch = clamp_pitch(forward.y.clamp(-1.0, 1.0).asin(), self.pitch_limit);
+        self.snap();
+    }
+
+    /// Moves the focus point (e.g. "frame selection"); smoothed like the rest.
+    pub fn set_focus(&mut self, focus: Vec3) {
+        self.target_focus = focus;
+    }
+
+    pub fn set_distance(&mut self, distance: f32) {
+        self.target_distance = distance.clamp(self.min_distance, self.max_distance);
+    }
+
+    /// "Frame selection": eases the focus to the center of `bounds` and the
+    /// distance to where its bounding sphere fits the narrower of the
+    /// camera's two FOVs, with a 10% margin. Uses `camera.fov` and
+    /// `camera.aspect_ratio`; orthographic cameras only get the focus.
+    pub fn frame_bounds(&mut self, bounds: &AABB, camera: &Camera) {
+        self.set_focus(bounds.center());
+        let radius = bounds.extents().length().max(1e-3);
+        let half_v = camera.fov * 0.5;
+        let half_h = (half_v.tan() * camera.aspect_ratio).atan();
+        self.set_distance(radius * FRAME_MARGIN / half_v.min(half_h).sin());
+    }
+
+    /// Wheel-adjustable pan/zoom speed: each step scales `speed_multiplier`
+    /// by 1.2. Returns the new multiplier, for an on-screen hint.
+    pub fn adjust_speed(&mut self, steps: f32) -> f32 {
+        self.speed_multiplier = step_speed(self.speed_multiplier, steps, 0.05, 20.0);
+        self.speed_multiplier
+    }
+
+    /// Drops any smoothing in flight: the targets become the current view.
+    pub fn snap(&mut self) {
+        self.target_focus = self.focus;
+        self.target_distance = self.distance;
+        self.target_yaw = self.yaw;
+        self.target_pitch = self.pitch;
+    }
+
+    /// Camera position for the current focus, distance and angles.
+    pub fn eye(&self) -> Vec3 {
+        self.focus - forward_from(self.yaw, self.pitch) * self.distance
+    }
+
+    pub fn update(&mut self, camera: &mut Camera, dt: f32, input: OrbitCameraInput) {
+        self.target_yaw -= input.orbit.x * self.sensitivity;
+        self.target_pitch = clamp_pitch(self.target_pitch - input.orbit.y * self.sensitivity, self.pitch_limit);
+        self.target_distance = (self.target_distance * (-input.zoom * self.zoom_speed * self.speed_multiplier).exp())
+            .clamp(self.min_distance, self.max_distance);
+        if input.pan != Vec2::ZERO {
+            // Pan in the plane of the target view, so it does not drift while orbiting.
+            let forward = forward_from(self.target_yaw, self.target_pitch);
+            let right = forward.cross(Vec3::Y).normalize_or(Vec3::X);
+            let up = right.cross(forward);
+            let scale = self.target_distance * self.pan_speed * self.speed_multiplier;
+            self.target_focus += (-right * input.pan.x + up * input.pan.y) * scale;
+        }
+
+        let k = damping_factor(self.damping, dt);
+        self.yaw += (self.target_yaw - self.yaw) * k;
+        self.pitch = clamp_pitch(self.pitch + (self.target_pitch - self.pitch) * k, self.pitch_limit);
+        self.distance += (self.target_distance - self.distance) * k;
+        self.focus += (self.target_focus - self.focus) * k;
+
+        camera.position = self.eye();
+        camera.set_rotation(self.yaw, self.pitch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_orbit(steps: usize, total: f32) -> OrbitCameraController {
+        let mut camera = Camera::new();
+        let mut orbit = OrbitCameraController::new(Vec3::ZERO, 10.0);
+        let input = OrbitCameraInput { orbit: Vec2::new(200.0, 0.0), pan: Vec2::new(0.0, 50.0), zoom: 3.0 };
+        orbit.update(&mut camera, total / steps as f32, input);
+        for _ in 1..steps {
+            orbit.update(&mut camera, total / steps as f32, OrbitCameraInput::default());
+        }
+        orbit
+    }
+
+    #[test]
+    fn smoothing_does_not_depend_on_frame_rate() {
+        let coarse = run_orbit(2, 0.1);
+        let fine = run_orbit(20, 0.1);
+        assert!((coarse.yaw - fine.yaw).abs() < 1e-4);
+        assert!((coarse.distance - fine.distance).abs() < 1e-4);
+        assert!(coarse.focus.distance(fine.focus) < 1e-4);
+        // Not there yet, but on the way.
+        assert!(coarse.yaw < 0.0 && coarse.yaw > -1.0);
+    }
+
+    #[test]
+    fn pitch_clamp_keeps_the_view_upright() {
+        let mut camera = Camera::new();
+        let mut fly = FlyCameraController { damping: 0.0, ..Default::default() };
+        fly.update(&mut camera, 1.0 / 60.0, FlyCameraInput { look: Vec2::new(0.0, -100_000.0), ..Default::default() });
+        assert_eq!(fly.pitch, MAX_CAMERA_PITCH);
+        assert!(camera.up().y > 0.0);
+        assert!(camera.view_matrix().is_finite());
+
+        let mut orbit = OrbitCameraController { damping: 0.0, ..Default::default() };
+        for _ in 0..10 {
+            orbit.update(&mut camera, 1.0 / 60.0, OrbitCameraInput { orbit: Vec2::new(0.0, 1000.0), ..Default::default() });
+        }
+        assert!((orbit.pitch + MAX_CAMERA_PITCH).abs() < 1e-6);
+        assert!(camera.up().y > 0.0);
+        assert!(camera.view_matrix().is_finite());
+        assert!(camera.forward().abs_diff_eq(-camera.position.normalize(), 1e-4));
+    }
+
+    #[test]
+    fn framed_bounds_fit_the_view() {
+        let mut camera = Camera::perspective(60.0, 0.5, 0.1, 1000.0);
+        let mut orbit = OrbitCameraController { damping: 0.0, ..Default::default() };
+        let bounds = AABB::new(Vec3::new(4.0, -1.0, 2.0), Vec3::new(10.0, 3.0, 5.0));
+        orbit.frame_bounds(&bounds, &camera);
+        orbit.update(&mut camera, 1.0 / 60.0, OrbitCameraInput::default());
+
+        assert!(orbit.focus.abs_diff_eq(bounds.center(), 1e-4));
+        let view_proj = camera.view_projection_matrix();
+        for i in 0..8 {
+            let corner = Vec3::select(glam::BVec3::new(i & 1 != 0, i & 2 != 0, i & 4 != 0), bounds.max, bounds.min);
+            let ndc = view_proj.project_point3(corner);
+            assert!(ndc.x.abs() < 1.0 && ndc.y.abs() < 1.0, "corner {corner} outside the view: {ndc}");
+        }
+    }
+
+    #[test]
+    fn fly_moves_along_the_view_and_orbit_keeps_its_focus() {
+        let mut camera = Camera::new();
+        camera.aim_at(Vec3::ZERO, Vec3::X);
+        let mut fly = FlyCameraController { damping: 0.0, ..Default::default() };
+        fly.update(&mut camera, 0.5, FlyCameraInput { movement: Vec3::Z, ..Default::default() });
+        assert!(camera.position.abs_diff_eq(Vec3::X * fly.speed * 0.5, 1e-4));
+
+        let mut orbit = OrbitCameraController::from_camera(&camera, Vec3::new(10.0, 0.0, 0.0));
+        assert!(orbit.eye().abs_diff_eq(camera.position, 1e-4));
+        orbit.update(&mut camera, 1.0 / 60.0, OrbitCameraInput { orbit: Vec2::new(300.0, 0.0), ..Default::default() });
+        assert!((camera.position.distance(orbit.focus) - orbit.distance).abs() < 1e-4);
+    }
+}
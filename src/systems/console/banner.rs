@@ -40,7 +40,7 @@ impl ReactorBanner {
 
         println!("{}║{}{}║{}", bc, " ".repeat(inner), bc, r);
 
-        let version = format!("Vulkan Engine v{}", env!("CARGO_PKG_VERSION"));
+        let version = format!("Vulkan Engine v{}", crate::VERSION);
         let vis = super::color::visual_width(&version);
         let pad = if inner > vis + 3 { inner - vis - 3 } else { 0 };
         println!(
@@ -0,0 +1,471 @@
+//! Console command line
+//!
+//! `ConsoleRegistry` maps command names to handlers and runs lines like
+//! `setpos 1 0 -2.5` or `save "my scene.reactor"`. It also keeps the input
+//! history (up/down) and completes command names on Tab. Handlers get the
+//! host (`C`) mutably and return the text to print back, or an error
+//! message; `CommandArgs` builds the usual "missing <x>" / "must be a
+//! number" messages.
+//!
+//! `register_builtins` adds the entity commands (`spawn`, `select`,
+//! `delete`, `setpos`, `stats`, `play`, `stop`, `save`) for any
+//! `ConsoleHost`. Moves go through `TransformHistory`, so they are undoable.
+
+use crate::scene::ecs::{Entity, World};
+use crate::scene::hierarchy::ChildPolicy;
+use crate::scene::transform::Transform;
+use crate::systems::transform_history::TransformHistory;
+use glam::Vec3;
+use std::collections::BTreeMap;
+
+/// Text to print, or an error message.
+pub type CommandResult = Result<String, String>;
+
+type Handler<C> = Box<dyn FnMut(&mut C, &CommandArgs) -> CommandResult>;
+
+const MAX_HISTORY: usize = 100;
+
+/// A parsed command line: the name and its arguments, quotes removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandArgs {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl CommandArgs {
+    /// Splits on whitespace; double quotes group words and `\"` / `\\` escape
+    /// inside them. `Ok(None)` for a blank line.
+    pub fn parse(line: &str) -> Result<Option<Self>, String> {
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+        let mut in_token = false;
+        let mut quoted = false;
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    quoted = !quoted;
+                    in_token = true;
+                }
+                '\\' if quoted => match chars.next() {
+                    Some(escaped @ ('"' | '\\')) => current.push(escaped),
+                    Some(other) => {
+                        current.push('\\');
+                        current.push(other);
+                    }
+                    None => current.push('\\'),
+                },
+                c if c.is_whitespace() && !quoted => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_token = true;
+                }
+            }
+        }
+        if quoted {
+            return Err("Unterminated quote".to_string());
+        }
+        if in_token {
+            tokens.push(current);
+        }
+        let mut tokens = tokens.into_iter();
+        Ok(tokens.next().map(|name| Self { name, args: tokens.collect() }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(String::as_str)
+    }
+
+    /// Argument `index`, or "name: missing <what>".
+    pub fn string(&self, index: usize, what: &str) -> Result<&str, String> {
+        self.get(index).ok_or_else(|| format!("{}: missing <{}>", self.name, what))
+    }
+
+    pub fn number(&self, index: usize, what: &str) -> Result<f32, String> {
+        let text = self.string(index, what)?;
+        text.parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("{}: <{}> must be a number, got '{}'", self.name, what, text))
+    }
+
+    /// Three numbers from `index` on, named x, y and z in errors.
+    pub fn vec3(&self, index: usize) -> Result<Vec3, String> {
+        Ok(Vec3::new(self.number(index, "x")?, self.number(index + 1, "y")?, self.number(index + 2, "z")?))
+    }
+
+    /// Error unless there are between `min` and `max` arguments.
+    pub fn expect(&self, min: usize, max: usize, usage: &str) -> Result<(), String> {
+        if (min..=max).contains(&self.len()) {
+            Ok(())
+        } else {
+            Err(format!("usage: {} {}", self.name, usage))
+        }
+    }
+}
+
+struct Command<C> {
+    help: String,
+    handler: Handler<C>,
+}
+
+/// Named commands over a host `C`, plus input history. `help` is built in.
+pub struct ConsoleRegistry<C> {
+    commands: BTreeMap<String, Command<C>>,
+    history: Vec<String>,
+    history_cursor: Option<usize>,
+}
+
+impl<C> Default for ConsoleRegistry<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> ConsoleRegistry<C> {
+    pub fn new() -> Self {
+        Self { commands: BTreeMap::new(), history: Vec::new(), history_cursor: None }
+    }
+
+    /// Adds (or replaces) `name`. `help` is the line `help` prints, usage first.
+    pub fn register(
+        &mut self,
+        name: &str,
+        help: &str,
+        handler: impl FnMut(&mut C, &CommandArgs) -> CommandResult + 'static,
+    ) {
+        self.commands.insert(name.to_string(), Command { help: help.to_string(), handler: Box::new(handler) });
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once("help").chain(self.commands.keys().map(String::as_str).filter(|&name| name != "help"))
+    }
+
+    /// Runs one line and records it in the history. A blank line is `Ok("")`.
+    pub fn execute(&mut self, host: &mut C, line: &str) -> CommandResult {
+        let line = line.trim();
+        if !line.is_empty() && self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.history_cursor = None;
+
+        let Some(args) = CommandArgs::parse(line)? else { return Ok(String::new()) };
+        if args.name == "help" && !self.commands.contains_key("help") {
+            return self.help(args.get(0));
+        }
+        match self.commands.get_mut(&args.name) {
+            Some(command) => (command.handler)(host, &args),
+            None => {
+                let initial = args.name.chars().next();
+                let similar: Vec<&str> =
+                    self.names().filter(|name| initial.is_some_and(|c| name.starts_with(c))).collect();
+                if similar.is_empty() {
+                    Err(format!("Unknown command '{}'. Type 'help' for a list.", args.name))
+                } else {
+                    Err(format!("Unknown command '{}'. Did you mean: {}?", args.name, similar.join(", ")))
+                }
+            }
+        }
+    }
+
+    /// Every command with its help line, or the help of `name`.
+    pub fn help(&self, name: Option<&str>) -> CommandResult {
+        match name {
+            Some(name) => self
+                .commands
+                .get(name)
+                .map(|command| format!("{} {}", name, command.help))
+                .ok_or_else(|| format!("help: no command '{}'", name)),
+            None => Ok(std::iter::once("help [command]".to_string())
+                .chain(self.commands.iter().map(|(name, command)| format!("{} {}", name, command.help)))
+                .collect::<Vec<_>>()
+                .join("\n")),
+        }
+    }
+
+    /// Command names starting with the first word of `input`; empty once a
+    /// space follows the name (arguments are not completed).
+    pub fn completions(&self, input: &str) -> Vec<&str> {
+        let input = input.trim_start();
+        if input.contains(char::is_whitespace) {
+            return Vec::new();
+        }
+        self.names().filter(|name| name.starts_with(input)).collect()
+    }
+
+    /// What Tab turns `input` into: the full name plus a space when only one
+    /// command matches, otherwise the longest common prefix. `None` when
+    /// that adds nothing; show `completions` then.
+    pub fn complete(&self, input: &str) -> Option<String> {
+        let matches = self.completions(input);
+        let first = *matches.first()?;
+        if matches.len() == 1 {
+            return Some(format!("{} ", first));
+        }
+        let common = matches.iter().skip(1).fold(first.len(), |len, name| {
+            first.bytes().zip(name.bytes()).take(len).take_while(|(a, b)| a == b).count()
+        });
+        (common > input.trim_start().len()).then(|| first[..common].to_string())
+    }
+
+    /// Older entry (up arrow). Stays on the oldest one.
+    pub fn history_prev(&mut self) -> Option<&str> {
+        let index = match self.history_cursor {
+            Some(index) => index.saturating_sub(1),
+            None => self.history.len().checked_sub(1)?,
+        };
+        self.history_cursor = Some(index);
+        self.history.get(index).map(String::as_str)
+    }
+
+    /// Newer entry (down arrow); `None` past the newest, i.e. an empty line.
+    pub fn history_next(&mut self) -> Option<&str> {
+        let index = self.history_cursor? + 1;
+        if index >= self.history.len() {
+            self.history_cursor = None;
+            return None;
+        }
+        self.history_cursor = Some(index);
+        self.history.get(index).map(String::as_str)
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+}
+
+/// What the built-in commands act on. `play`, `stop`, `save` and spawning
+/// anything but an empty entity are optional.
+pub trait ConsoleHost {
+    fn world(&mut self) -> &mut World;
+    fn history(&mut self) -> &mut TransformHistory;
+    fn selection(&mut self) -> &mut Vec<Entity>;
+
+    /// Creates a `kind` object at `position`. The default only knows
+    /// "empty": an entity with a `Transform`.
+    fn spawn(&mut self, kind: &str, position: Vec3) -> Result<Entity, String> {
+        if kind != "empty" {
+            return Err(format!("spawn: unknown kind '{}' (try 'empty')", kind));
+        }
+        let world = self.world();
+        let entity = world.create_entity();
+        world.add_component(entity, Transform::from_translation(position.x, position.y, position.z));
+        Ok(entity)
+    }
+
+    fn play(&mut self) -> CommandResult {
+        Err("play: not available here".to_string())
+    }
+
+    fn stop(&mut self) -> CommandResult {
+        Err("stop: not available here".to_string())
+    }
+
+    fn save(&mut self, path: &str) -> CommandResult {
+        Err(format!("save: cannot save '{}' here", path))
+    }
+}
+
+/// Adds the entity commands to `registry`.
+pub fn register_builtins<C: ConsoleHost>(registry: &mut ConsoleRegistry<C>) {
+    registry.register("spawn", "[kind] [<x> <y> <z>] — create and select an object", |host, args| {
+        let (kind, position) = match args.len() {
+            0 => ("empty", Vec3::ZERO),
+            1 => (args.string(0, "kind")?, Vec3::ZERO),
+            3 => ("empty", args.vec3(0)?),
+            4 => (args.string(0, "kind")?, args.vec3(1)?),
+            _ => return Err("usage: spawn [kind] [<x> <y> <z>]".to_string()),
+        };
+        let entity = host.spawn(kind, position)?;
+        *host.selection() = vec![entity];
+        Ok(format!("Spawned {} entity {}", kind, entity))
+    });
+
+    registry.register("select", "<entity>... — replace the selection", |host, args| {
+        args.expect(1, usize::MAX, "<entity>...")?;
+        let mut selected = Vec::new();
+        for text in &args.args {
+            let entity = text.parse::<Entity>().map_err(|_| format!("select: <entity> must be an id, got '{}'", text))?;
+            if !host.world().is_alive(entity) {
+                return Err(format!("select: entity {} does not exist", entity));
+            }
+            selected.push(entity);
+        }
+        *host.selection() = selected;
+        Ok(format!("Selected {}", args.args.join(", ")))
+    });
+
+    registry.register("delete", "— destroy the selection and its children (cannot be undone)", |host, args| {
+        args.expect(0, 0, "")?;
+        let selection = std::mem::take(host.selection());
+        if selection.is_empty() {
+            return Err("delete: nothing selected".to_string());
+        }
+        let count = selection
+            .iter()
+            .filter(|&&entity| host.world().destroy_recursive(entity, ChildPolicy::Destroy))
+            .count();
+        Ok(format!("Deleted {} entities", count))
+    });
+
+    registry.register("setpos", "<x> <y> <z> — move the selection (undoable)", |host, args| {
+        args.expect(3, 3, "<x> <y> <z>")?;
+        let position = args.vec3(0)?;
+        let selection = host.selection().clone();
+        if selection.is_empty() {
+            return Err("setpos: nothing selected".to_string());
+        }
+        for entity in selection {
+            let before = host.world().get_component::<Transform>(entity).copied().unwrap_or_default();
+            let after = Transform { position, ..before };
+            host.world().add_component(entity, after);
+            host.history().record(entity, "Move", before, after);
+            host.history().seal();
+        }
+        Ok(format!("Moved to ({}, {}, {})", position.x, position.y, position.z))
+    });
+
+    registry.register("stats", "— entity and selection counts", |host, args| {
+        args.expect(0, 0, "")?;
+        let selected = host.selection().len();
+        let world = host.world();
+        Ok(format!(
+            "{} entities, {} with Transform, {} selected",
+            world.entity_count(),
+            world.query::<Transform>().count(),
+            selected
+        ))
+    });
+
+    registry.register("play", "— enter play mode", |host, args| {
+        args.expect(0, 0, "")?;
+        host.play()
+    });
+
+    registry.register("stop", "— leave play mode", |host, args| {
+        args.expect(0, 0, "")?;
+        host.stop()
+    });
+
+    registry.register("save", "<path> — save the scene", |host, args| {
+        args.expect(1, 1, "<path>")?;
+        host.save(args.string(0, "path")?)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Scratch {
+        world: World,
+        history: TransformHistory,
+        selection: Vec<Entity>,
+    }
+
+    impl ConsoleHost for Scratch {
+        fn world(&mut self) -> &mut World {
+            &mut self.world
+        }
+
+        fn history(&mut self) -> &mut TransformHistory {
+            &mut self.history
+        }
+
+        fn selection(&mut self) -> &mut Vec<Entity> {
+            &mut self.selection
+        }
+    }
+
+    #[test]
+    fn parser_handles_quotes_escapes_and_errors() {
+        let args = CommandArgs::parse("  save \"my scene.reactor\"  \"\" a\"b c\"\\x ").unwrap().unwrap();
+        assert_eq!(args.name, "save");
+        assert_eq!(args.args, ["my scene.reactor", "", "ab c\\x"]);
+        let escaped = CommandArgs::parse(r#"say "a \"quote\" \\ here""#).unwrap().unwrap();
+        assert_eq!(escaped.args, [r#"a "quote" \ here"#]);
+        assert_eq!(CommandArgs::parse("   ").unwrap(), None);
+        assert!(CommandArgs::parse("save \"open").is_err());
+
+        let args = CommandArgs::parse("setpos 1 x").unwrap().unwrap();
+        assert_eq!(args.number(0, "x"), Ok(1.0));
+        assert_eq!(args.number(1, "y").unwrap_err(), "setpos: <y> must be a number, got 'x'");
+        assert_eq!(args.number(2, "z").unwrap_err(), "setpos: missing <z>");
+    }
+
+    #[test]
+    fn completion_and_history() {
+        let mut registry = ConsoleRegistry::<Scratch>::new();
+        register_builtins(&mut registry);
+        assert_eq!(registry.completions("s"), ["save", "select", "setpos", "spawn", "stats", "stop"]);
+        assert_eq!(registry.complete("sp").as_deref(), Some("spawn "));
+        assert_eq!(registry.complete("se").as_deref(), None);
+        assert_eq!(registry.complete("st"), None);
+        assert!(registry.completions("spawn ").is_empty());
+
+        let mut host = Scratch::default();
+        for line in ["stats", "spawn", "stats"] {
+            registry.execute(&mut host, line).unwrap();
+        }
+        assert_eq!(registry.history_prev(), Some("stats"));
+        assert_eq!(registry.history_prev(), Some("spawn"));
+        assert_eq!(registry.history_prev(), Some("stats"));
+        assert_eq!(registry.history_prev(), Some("stats"));
+        assert_eq!(registry.history_next(), Some("spawn"));
+        assert_eq!(registry.history_next(), Some("stats"));
+        assert_eq!(registry.history_next(), None);
+    }
+
+    #[test]
+    fn builtins_edit_a_scratch_scene() {
+        let mut registry = ConsoleRegistry::new();
+        register_builtins(&mut registry);
+        registry.register("echo", "<text>... — print the arguments", |_: &mut Scratch, args| Ok(args.args.join(" ")));
+        let mut host = Scratch::default();
+
+        assert_eq!(registry.execute(&mut host, "echo \"hi there\"").unwrap(), "hi there");
+        registry.execute(&mut host, "spawn 1 2 3").unwrap();
+        let first = host.selection[0];
+        registry.execute(&mut host, "spawn empty 0 0 0").unwrap();
+        assert_eq!(host.world.entity_count(), 2);
+
+        registry.execute(&mut host, &format!("select {}", first)).unwrap();
+        registry.execute(&mut host, "setpos 4 5 6").unwrap();
+        assert_eq!(host.world.get_component::<Transform>(first).unwrap().position, Vec3::new(4.0, 5.0, 6.0));
+        host.history.undo(&mut host.world).unwrap();
+        assert_eq!(host.world.get_component::<Transform>(first).unwrap().position, Vec3::new(1.0, 2.0, 3.0));
+
+        assert_eq!(registry.execute(&mut host, "setpos 4 five 6").unwrap_err(), "setpos: <y> must be a number, got 'five'");
+        assert_eq!(registry.execute(&mut host, "setpos 1").unwrap_err(), "usage: setpos <x> <y> <z>");
+        assert!(registry.execute(&mut host, "select 99").is_err());
+        assert!(registry.execute(&mut host, "spawn cube").is_err());
+        assert!(registry.execute(&mut host, "play").is_err());
+        assert!(registry.execute(&mut host, "sapwn").unwrap_err().contains("Did you mean"));
+        assert!(registry.help(None).unwrap().contains("setpos <x> <y> <z>"));
+
+        registry.execute(&mut host, "delete").unwrap();
+        assert_eq!(host.world.entity_count(), 1);
+        assert!(registry.execute(&mut host, "delete").is_err());
+    }
+}
@@ -1,9 +1,11 @@
 mod banner;
 pub mod color;
+pub mod commands;
 mod gpu;
 mod log;
 
 pub use banner::{GameBanner, ReactorBanner};
+pub use commands::{register_builtins, CommandArgs, CommandResult, ConsoleHost, ConsoleRegistry};
 pub use gpu::gpu_name_short;
 pub use log::Log;
 
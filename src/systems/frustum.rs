@@ -195,10 +195,14 @@ pub enum FrustumTestResult {
 }
 
 /// Culling system that tracks visible objects
+///
+/// While disabled every test passes (and is still counted), so callers don't
+/// need a separate code path.
 pub struct CullingSystem {
     frustum: Frustum,
     visible_count: usize,
     total_count: usize,
+    enabled: bool,
 }
 
 impl CullingSystem {
@@ -207,9 +211,18 @@ impl CullingSystem {
             frustum: Frustum::from_view_projection(Mat4::IDENTITY),
             visible_count: 0,
             total_count: 0,
+            enabled: true,
         }
     }
 
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     pub fn update_frustum(&mut self, view_projection: Mat4) {
         self.frustum = Frustum::from_view_projection(view_projection);
         self.visible_count = 0;
@@ -218,7 +231,7 @@ impl CullingSystem {
 
     pub fn is_visible_aabb(&mut self, aabb: &AABB) -> bool {
         self.total_count += 1;
-        let visible = self.frustum.intersects_aabb(aabb);
+        let visible = !self.enabled || self.frustum.intersects_aabb(aabb);
         if visible {
             self.visible_count += 1;
         }
@@ -227,7 +240,7 @@ impl CullingSystem {
 
     pub fn is_visible_sphere(&mut self, sphere: &Sphere) -> bool {
         self.total_count += 1;
-        let visible = self.frustum.intersects_sphere(sphere);
+        let visible = !self.enabled || self.frustum.intersects_sphere(sphere);
         if visible {
             self.visible_count += 1;
         }
@@ -260,3 +273,46 @@ impl Default for CullingSystem {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube_field() -> Vec<AABB> {
+        (0..1000)
+            .map(|i| {
+                let x = (i % 10) as f32 * 20.0 - 90.0;
+                let z = (i / 10 % 10) as f32 * 20.0 - 90.0;
+                let y = (i / 100) as f32 * 20.0 - 90.0;
+                AABB::from_center_size(Vec3::new(x, y, z), Vec3::ONE)
+            })
+            .collect()
+    }
+
+    fn view_projection(target: Vec3) -> Mat4 {
+        let mut proj = Mat4::perspective_rh(60f32.to_radians(), 16.0 / 9.0, 0.1, 1000.0);
+        proj.y_axis.y *= -1.0;
+        proj * Mat4::look_at_rh(Vec3::new(0.0, 0.0, 150.0), target, Vec3::Y)
+    }
+
+    #[test]
+    fn looking_away_culls_the_field() {
+        let field = cube_field();
+        let mut culling = CullingSystem::new();
+
+        culling.update_frustum(view_projection(Vec3::ZERO));
+        field.iter().for_each(|aabb| { culling.is_visible_aabb(aabb); });
+        let facing = culling.visible_count();
+        assert!(facing > 100, "only {} visible while facing the field", facing);
+
+        culling.update_frustum(view_projection(Vec3::new(0.0, 0.0, 300.0)));
+        field.iter().for_each(|aabb| { culling.is_visible_aabb(aabb); });
+        assert_eq!(culling.visible_count(), 0);
+        assert_eq!(culling.culled_count(), 1000);
+
+        culling.set_enabled(false);
+        culling.update_frustum(view_projection(Vec3::new(0.0, 0.0, 300.0)));
+        field.iter().for_each(|aabb| { culling.is_visible_aabb(aabb); });
+        assert_eq!(culling.visible_count(), 1000);
+    }
+}
@@ -0,0 +1,319 @@
+//! Transform gizmo manipulation
+//!
+//! Handles are driven by rays from the camera through the mouse
+//! (`Camera::screen_ray`), not by screen-space deltas: `GizmoDrag::begin`
+//! intersects the ray with the handle's axis line or plane, and every
+//! `update` recomputes that intersection and applies the world-space
+//! difference to the transform captured at the start. Dragging the X arrow
+//! therefore moves along world X whatever the camera angle. Rotation rings
+//! use the angle swept on the ring's plane around the pivot.
+//!
+//! Snapping works in world space. `SnapMode::Relative` (the default) rounds
+//! the drag delta, so an object at x = 0.37 moves to 0.87, 1.37...;
+//! `Absolute` rounds the moved position components to the grid. Rings
+//! accumulate the unsnapped angle across updates (and past half a turn), so a
+//! slow drag still reaches the next increment.
+
+use crate::scene::transform::Transform;
+use crate::systems::physics::Ray;
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Below this |cos| between the ray and an axis (or plane) the intersection
+/// is too unstable to follow; `update` then returns `None` and the caller
+/// keeps the last transform.
+const MIN_GRAZING: f32 = 1e-3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GizmoAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl GizmoAxis {
+    pub fn direction(self) -> Vec3 {
+        match self {
+            GizmoAxis::X => Vec3::X,
+            GizmoAxis::Y => Vec3::Y,
+            GizmoAxis::Z => Vec3::Z,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GizmoHandle {
+    /// Arrow: moves along one world axis.
+    Translate(GizmoAxis),
+    /// Square: moves on the plane whose normal is the given axis.
+    TranslatePlane(GizmoAxis),
+    /// Center: moves on the plane facing the camera.
+    TranslateScreen,
+    /// Ring: rotates around one world axis through the pivot.
+    Rotate(GizmoAxis),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SnapMode {
+    /// Snap the drag delta.
+    #[default]
+    Relative,
+    /// Snap the resulting world position.
+    Absolute,
+}
+
+/// Snap settings; an increment of 0 disables that one.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GizmoSnap {
+    pub enabled: bool,
+    pub mode: SnapMode,
+    /// World units.
+    pub translate: f32,
+    /// Degrees.
+    pub rotate_degrees: f32,
+}
+
+impl Default for GizmoSnap {
+    fn default() -> Self {
+        Self { enabled: true, mode: SnapMode::Relative, translate: 0.0, rotate_degrees: 0.0 }
+    }
+}
+
+impl GizmoSnap {
+    /// The settings with `enabled` flipped while `invert` is held (Ctrl
+    /// during a drag).
+    pub fn inverted_if(self, invert: bool) -> Self {
+        Self { enabled: self.enabled != invert, ..self }
+    }
+
+    fn translate_step(&self) -> f32 {
+        if self.enabled { self.translate } else { 0.0 }
+    }
+
+    fn rotate_step(&self) -> f32 {
+        if self.enabled { self.rotate_degrees.to_radians() } else { 0.0 }
+    }
+}
+
+/// One drag of a gizmo handle, from mouse down to mouse up.
+#[derive(Clone, Copy, Debug)]
+pub struct GizmoDrag {
+    handle: GizmoHandle,
+    start: Transform,
+    plane_normal: Vec3,
+    /// Where the start ray hit the axis / plane (relative to the pivot for rings).
+    anchor: Vec3,
+    /// Rings: last hit (relative to the pivot) and the angle swept so far.
+    last: Vec3,
+    swept: f32,
+}
+
+impl GizmoDrag {
+    /// Starts dragging `handle` of an object at `start`. `view_forward` is
+    /// the camera forward, used by the screen handle. `None` when the ray
+    /// misses the handle's line or plane (e.g. it looks along the axis).
+    pub fn begin(handle: GizmoHandle, start: Transform, ray: &Ray, view_forward: Vec3) -> Option<Self> {
+        let plane_normal = match handle {
+            GizmoHandle::Translate(axis) | GizmoHandle::TranslatePlane(axis) | GizmoHandle::Rotate(axis) => {
+                axis.direction()
+            }
+            GizmoHandle::TranslateScreen => -view_forward.normalize(),
+        };
+        let mut drag = Self { handle, start, plane_normal, anchor: Vec3::ZERO, last: Vec3::ZERO, swept: 0.0 };
+        drag.anchor = drag.hit(ray)?;
+        drag.last = drag.anchor;
+        if matches!(handle, GizmoHandle::Rotate(_)) && drag.anchor.length_squared() < 1e-8 {
+            return None;
+        }
+        Some(drag)
+    }
+
+    pub fn handle(&self) -> GizmoHandle {
+        self.handle
+    }
+
+    /// Transform when the drag started (the `before` of the undo step).
+    pub fn start(&self) -> Transform {
+        self.start
+    }
+
+    /// Transform for the current mouse ray. `None` when the ray grazes the
+    /// axis or plane; keep the previous result then.
+    pub fn update(&mut self, ray: &Ray, snap: GizmoSnap) -> Option<Transform> {
+        let hit = self.hit(ray)?;
+        let mut transform = self.start;
+        let (delta, moved) = match self.handle {
+            GizmoHandle::Translate(axis) => (axis.direction() * (hit - self.anchor).dot(axis.direction()), axis.direction()),
+            GizmoHandle::TranslatePlane(axis) => {
+                let delta = hit - self.anchor;
+                (delta - self.plane_normal * delta.dot(self.plane_normal), Vec3::ONE - axis.direction())
+            }
+            GizmoHandle::TranslateScreen => {
+                let delta = hit - self.anchor;
+                (delta - self.plane_normal * delta.dot(self.plane_normal), Vec3::ONE)
+            }
+            GizmoHandle::Rotate(axis) => {
+                let axis = axis.direction();
+                self.swept += axis.dot(self.last.cross(hit)).atan2(self.last.dot(hit));
+                self.last = hit;
+                let angle = snap_value(self.swept, snap.rotate_step());
+                transform.rotation = (Quat::from_axis_angle(axis, angle) * self.start.rotation).normalize();
+                return Some(transform);
+            }
+        };
+        let step = snap.translate_step();
+        transform.position = match snap.mode {
+            SnapMode::Relative => self.start.position + snap_components(delta, Vec3::ZERO, moved, step),
+            SnapMode::Absolute => snap_components(self.start.position + delta, self.start.position, moved, step),
+        };
+        Some(transform)
+    }
+
+    /// Intersection of `ray` with this handle's line or plane.
+    fn hit(&self, ray: &Ray) -> Option<Vec3> {
+        let pivot = self.start.position;
+        match self.handle {
+            GizmoHandle::Translate(axis) => closest_point_on_axis(ray, pivot, axis.direction()),
+            GizmoHandle::TranslatePlane(_) | GizmoHandle::TranslateScreen => {
+                intersect_plane(ray, pivot, self.plane_normal)
+            }
+            GizmoHandle::Rotate(_) => {
+                let hit = intersect_plane(ray, pivot, self.plane_normal)? - pivot;
+                Some(hit - self.plane_normal * hit.dot(self.plane_normal))
+            }
+        }
+    }
+}
+
+/// Point of the line `origin + s * axis` closest to `ray`, or `None` when the
+/// ray is (nearly) parallel to it. `axis` must be normalized.
+pub fn closest_point_on_axis(ray: &Ray, origin: Vec3, axis: Vec3) -> Option<Vec3> {
+    let b = axis.dot(ray.direction);
+    let denom = 1.0 - b * b;
+    if denom < MIN_GRAZING {
+        return None;
+    }
+    let w = origin - ray.origin;
+    let s = (b * ray.direction.dot(w) - axis.dot(w)) / denom;
+    Some(origin + axis * s)
+}
+
+/// Where `ray` crosses the plane through `point` with `normal`, if it does in
+/// front of the ray origin.
+pub fn intersect_plane(ray: &Ray, point: Vec3, normal: Vec3) -> Option<Vec3> {
+    let denom = normal.dot(ray.direction);
+    if denom.abs() < MIN_GRAZING {
+        return None;
+    }
+    let t = normal.dot(point - ray.origin) / denom;
+    (t >= 0.0).then(|| ray.point_at(t))
+}
+
+fn snap_value(value: f32, step: f32) -> f32 {
+    if step > 0.0 {
+        (value / step).round() * step
+    } else {
+        value
+    }
+}
+
+/// Snaps the components selected by `mask` (1 = moved) and takes the rest
+/// from `rest`, so float noise never drifts the axes that were not dragged.
+fn snap_components(position: Vec3, rest: Vec3, mask: Vec3, step: f32) -> Vec3 {
+    let snapped = Vec3::new(snap_value(position.x, step), snap_value(position.y, step), snap_value(position.z, step));
+    Vec3::select(mask.cmpgt(Vec3::ZERO), snapped, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray_through(eye: Vec3, target: Vec3) -> Ray {
+        Ray::new(eye, target - eye)
+    }
+
+    #[test]
+    fn axis_drag_from_an_oblique_camera_stays_on_the_axis() {
+        let eye = Vec3::new(6.0, 5.0, 7.0);
+        let start = Transform::from_translation(1.0, 2.0, 3.0);
+        let handle = GizmoHandle::Translate(GizmoAxis::X);
+        let mut drag = GizmoDrag::begin(handle, start, &ray_through(eye, start.position), Vec3::ZERO).unwrap();
+
+        // Cursor off the axis: only X changes.
+        let moved = drag.update(&ray_through(eye, Vec3::new(3.0, 2.6, 2.2)), GizmoSnap::default()).unwrap();
+        assert!(moved.position.x > 1.0);
+        assert_eq!((moved.position.y, moved.position.z), (2.0, 3.0));
+
+        // Cursor on the axis: the object follows it exactly.
+        let moved = drag.update(&ray_through(eye, Vec3::new(4.3, 2.0, 3.0)), GizmoSnap::default()).unwrap();
+        assert!((moved.position.x - 4.3).abs() < 1e-4);
+
+        let snap = GizmoSnap { translate: 0.5, ..Default::default() };
+        let snapped = drag.update(&ray_through(eye, Vec3::new(4.3, 2.0, 3.0)), snap).unwrap();
+        assert_eq!(snapped.position, Vec3::new(4.5, 2.0, 3.0));
+    }
+
+    #[test]
+    fn looking_down_the_axis_does_not_start_a_drag() {
+        let ray = Ray::new(Vec3::new(-10.0, 0.0, 0.0), Vec3::X);
+        let handle = GizmoHandle::Translate(GizmoAxis::X);
+        assert!(GizmoDrag::begin(handle, Transform::new(), &ray, Vec3::X).is_none());
+    }
+
+    #[test]
+    fn plane_drag_keeps_the_normal_component() {
+        let eye = Vec3::new(2.0, 8.0, 4.0);
+        let handle = GizmoHandle::TranslatePlane(GizmoAxis::Y);
+        let mut drag = GizmoDrag::begin(handle, Transform::new(), &ray_through(eye, Vec3::ZERO), Vec3::ZERO).unwrap();
+        let moved = drag.update(&ray_through(eye, Vec3::new(-1.5, 0.0, 2.0)), GizmoSnap::default()).unwrap();
+        assert!(moved.position.abs_diff_eq(Vec3::new(-1.5, 0.0, 2.0), 1e-4));
+    }
+
+    #[test]
+    fn relative_snap_moves_by_increments_and_absolute_snaps_to_the_grid() {
+        let eye = Vec3::new(0.0, 4.0, 6.0);
+        let start = Transform::from_translation(0.37, 0.0, 0.0);
+        let handle = GizmoHandle::Translate(GizmoAxis::X);
+        let mut drag = GizmoDrag::begin(handle, start, &ray_through(eye, start.position), Vec3::ZERO).unwrap();
+        let nudge = ray_through(eye, Vec3::new(0.47, 0.0, 0.0));
+        let further = ray_through(eye, Vec3::new(0.67, 0.0, 0.0));
+
+        let relative = GizmoSnap { translate: 0.5, ..Default::default() };
+        assert_eq!(drag.update(&nudge, relative).unwrap().position.x, 0.37);
+        assert!((drag.update(&further, relative).unwrap().position.x - 0.87).abs() < 1e-5);
+
+        let absolute = GizmoSnap { mode: SnapMode::Absolute, ..relative };
+        assert_eq!(drag.update(&nudge, absolute).unwrap().position.x, 0.5);
+
+        // Ctrl held: snapping off for this update.
+        let free = drag.update(&nudge, relative.inverted_if(true)).unwrap();
+        assert!((free.position.x - 0.47).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ring_rotation_accumulates_the_swept_angle() {
+        let eye = Vec3::new(0.0, 10.0, 0.5);
+        let on_ring = |degrees: f32| {
+            let radians = degrees.to_radians();
+            ray_through(eye, Vec3::new(radians.cos(), 0.0, -radians.sin()) * 2.0)
+        };
+        let handle = GizmoHandle::Rotate(GizmoAxis::Y);
+        let mut drag = GizmoDrag::begin(handle, Transform::new(), &on_ring(0.0), Vec3::NEG_Y).unwrap();
+
+        // A slow drag in 5° steps still reaches the 45° increments.
+        let snap = GizmoSnap { rotate_degrees: 45.0, ..Default::default() };
+        let mut rotated = Transform::new();
+        for step in 1..=16 {
+            rotated = drag.update(&on_ring(step as f32 * 5.0), snap).unwrap();
+        }
+        assert!((rotated.rotation * Vec3::X).abs_diff_eq(Vec3::NEG_Z, 1e-4));
+        assert_eq!(rotated.position, Vec3::ZERO);
+
+        // Past half a turn the angle keeps growing instead of wrapping.
+        for step in 17..=54 {
+            rotated = drag.update(&on_ring(step as f32 * 5.0), GizmoSnap::default()).unwrap();
+        }
+        assert!((rotated.rotation * Vec3::X).abs_diff_eq(Vec3::Z, 1e-3));
+        assert!((drag.swept - 270f32.to_radians()).abs() < 1e-3);
+    }
+}
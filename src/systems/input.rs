@@ -0,0 +1,344 @@
+// =============================================================================
+// Input actions — named actions and axes over keys, mouse and gamepads
+// =============================================================================
+//
+// Game code asks for "jump" or "move_x" instead of key codes, so controls can
+// be rebound at runtime and saved. Queries read the frame's `Input`, so edges
+// (`action_pressed` / `action_released`) follow the same per-frame clearing as
+// `Input::is_key_just_pressed`: a tap shorter than a frame is still seen.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::platform::{GamepadAxis, GamepadButton, Input};
+
+/// Magnitude past which an analog input bound to an action counts as down.
+pub const ANALOG_ACTION_THRESHOLD: f32 = 0.5;
+
+/// One physical input. Gamepad bindings with `pad: None` read the active pad.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton { pad: Option<usize>, button: GamepadButton },
+    GamepadAxis { pad: Option<usize>, axis: GamepadAxis },
+}
+
+impl InputBinding {
+    /// 0.0 or 1.0 for digital inputs; the raw (deadzoned) value for axes.
+    pub fn value(&self, input: &Input) -> f32 {
+        match *self {
+            Self::GamepadAxis { pad, axis } => match pad {
+                Some(pad) => input.gamepad().pad_axis(pad, axis),
+                None => input.gamepad().axis(axis),
+            },
+            _ => {
+                if self.is_down(input) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub fn is_down(&self, input: &Input) -> bool {
+        match *self {
+            Self::Key(key) => input.is_key_down(key),
+            Self::Mouse(button) => input.is_mouse_button_down(button),
+            Self::GamepadButton { pad: Some(pad), button } => input.gamepad().pad_button_down(pad, button),
+            Self::GamepadButton { pad: None, button } => input.gamepad().is_button_down(button),
+            Self::GamepadAxis { .. } => self.value(input).abs() > ANALOG_ACTION_THRESHOLD,
+        }
+    }
+
+    /// Edges are only tracked for digital inputs; axes never report one.
+    pub fn just_pressed(&self, input: &Input) -> bool {
+        match *self {
+            Self::Key(key) => input.is_key_just_pressed(key),
+            Self::Mouse(button) => input.is_mouse_button_just_pressed(button),
+            Self::GamepadButton { pad: Some(pad), button } => input.gamepad().pad_button_just_pressed(pad, button),
+            Self::GamepadButton { pad: None, button } => input.gamepad().is_button_just_pressed(button),
+            Self::GamepadAxis { .. } => false,
+        }
+    }
+
+    pub fn just_released(&self, input: &Input) -> bool {
+        match *self {
+            Self::Key(key) => input.is_key_just_released(key),
+            Self::Mouse(button) => input.is_mouse_button_just_released(button),
+            Self::GamepadButton { pad: Some(pad), button } => input.gamepad().pad_button_just_released(pad, button),
+            Self::GamepadButton { pad: None, button } => input.gamepad().is_button_just_released(button),
+            Self::GamepadAxis { .. } => false,
+        }
+    }
+}
+
+/// An input feeding an axis, multiplied by `scale`. A digital input adds
+/// `scale` while held, so a pair of keys is `-1.0` and `+1.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub binding: InputBinding,
+    pub scale: f32,
+}
+
+/// Named actions and axes with their bindings. Unknown names read as
+/// released / `0.0`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionMap {
+    actions: BTreeMap<String, Vec<InputBinding>>,
+    axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// WASD/arrows + left stick for `move_x`/`move_y`, mouse + right stick
+    /// for `look_x`/`look_y`, and `jump`, `fire`, `pause`.
+    pub fn fps_defaults() -> Self {
+        let mut map = Self::new();
+        map.bind_axis_keys("move_x", KeyCode::KeyA, KeyCode::KeyD);
+        map.bind_axis_keys("move_x", KeyCode::ArrowLeft, KeyCode::ArrowRight);
+        map.bind_axis_gamepad("move_x", None, GamepadAxis::LeftStickX);
+        map.bind_axis_keys("move_y", KeyCode::KeyS, KeyCode::KeyW);
+        map.bind_axis_keys("move_y", KeyCode::ArrowDown, KeyCode::ArrowUp);
+        map.bind_axis_gamepad("move_y", None, GamepadAxis::LeftStickY);
+        map.bind_axis_gamepad("look_x", None, GamepadAxis::RightStickX);
+        map.bind_axis_gamepad("look_y", None, GamepadAxis::RightStickY);
+        map.bind_key("jump", KeyCode::Space);
+        map.bind_gamepad_button("jump", None, GamepadButton::South);
+        map.bind_mouse("fire", MouseButton::Left);
+        map.bind_gamepad_button("fire", None, GamepadButton::RightTrigger2);
+        map.bind_key("pause", KeyCode::Escape);
+        map.bind_gamepad_button("pause", None, GamepadButton::Start);
+        map
+    }
+
+    // ── Actions ─────────────────────────────────────────────────────────────
+
+    /// Declares an action with no bindings. Returns `false` if it already existed.
+    pub fn register_action(&mut self, name: &str) -> bool {
+        if self.actions.contains_key(name) {
+            return false;
+        }
+        self.actions.insert(name.to_string(), Vec::new());
+        true
+    }
+
+    /// Adds `binding` to `name`, registering the action if needed.
+    pub fn bind(&mut self, name: &str, binding: InputBinding) {
+        let bindings = self.actions.entry(name.to_string()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn bind_key(&mut self, name: &str, key: KeyCode) {
+        self.bind(name, InputBinding::Key(key));
+    }
+
+    pub fn bind_mouse(&mut self, name: &str, button: MouseButton) {
+        self.bind(name, InputBinding::Mouse(button));
+    }
+
+    pub fn bind_gamepad_button(&mut self, name: &str, pad: Option<usize>, button: GamepadButton) {
+        self.bind(name, InputBinding::GamepadButton { pad, button });
+    }
+
+    pub fn unbind(&mut self, name: &str, binding: InputBinding) -> bool {
+        let Some(bindings) = self.actions.get_mut(name) else { return false };
+        let before = bindings.len();
+        bindings.retain(|b| *b != binding);
+        bindings.len() != before
+    }
+
+    /// Drops every binding of `name` but keeps the action (for rebinding).
+    pub fn clear_action(&mut self, name: &str) {
+        if let Some(bindings) = self.actions.get_mut(name) {
+            bindings.clear();
+        }
+    }
+
+    pub fn remove_action(&mut self, name: &str) -> bool {
+        self.actions.remove(name).is_some()
+    }
+
+    pub fn action_bindings(&self, name: &str) -> &[InputBinding] {
+        self.actions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    /// Any binding of the action held this frame.
+    pub fn action_down(&self, input: &Input, name: &str) -> bool {
+        self.action_bindings(name).iter().any(|b| b.is_down(input))
+    }
+
+    /// The frame the action went from released to held: some binding was
+    /// pressed and none was already held.
+    pub fn action_pressed(&self, input: &Input, name: &str) -> bool {
+        let bindings = self.action_bindings(name);
+        bindings.iter().any(|b| b.just_pressed(input))
+            && !bindings.iter().any(|b| b.is_down(input) && !b.just_pressed(input))
+    }
+
+    /// The frame the action went from held to released.
+    pub fn action_released(&self, input: &Input, name: &str) -> bool {
+        let bindings = self.action_bindings(name);
+        bindings.iter().any(|b| b.just_released(input)) && !bindings.iter().any(|b| b.is_down(input))
+    }
+
+    // ── Axes ────────────────────────────────────────────────────────────────
+
+    /// Declares an axis with no bindings. Returns `false` if it already existed.
+    pub fn register_axis(&mut self, name: &str) -> bool {
+        if self.axes.contains_key(name) {
+            return false;
+        }
+        self.axes.insert(name.to_string(), Vec::new());
+        true
+    }
+
+    pub fn bind_axis(&mut self, name: &str, binding: InputBinding, scale: f32) {
+        let bindings = self.axes.entry(name.to_string()).or_default();
+        if !bindings.iter().any(|b| b.binding == binding) {
+            bindings.push(AxisBinding { binding, scale });
+        }
+    }
+
+    /// `negative` adds -1, `positive` adds +1.
+    pub fn bind_axis_keys(&mut self, name: &str, negative: KeyCode, positive: KeyCode) {
+        self.bind_axis(name, InputBinding::Key(negative), -1.0);
+        self.bind_axis(name, InputBinding::Key(positive), 1.0);
+    }
+
+    pub fn bind_axis_gamepad(&mut self, name: &str, pad: Option<usize>, axis: GamepadAxis) {
+        self.bind_axis(name, InputBinding::GamepadAxis { pad, axis }, 1.0);
+    }
+
+    pub fn clear_axis(&mut self, name: &str) {
+        if let Some(bindings) = self.axes.get_mut(name) {
+            bindings.clear();
+        }
+    }
+
+    pub fn remove_axis(&mut self, name: &str) -> bool {
+        self.axes.remove(name).is_some()
+    }
+
+    pub fn axis_bindings(&self, name: &str) -> &[AxisBinding] {
+        self.axes.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn axes(&self) -> impl Iterator<Item = &str> {
+        self.axes.keys().map(String::as_str)
+    }
+
+    /// Sum of the axis' bindings, clamped to -1..=1.
+    pub fn axis_value(&self, input: &Input, name: &str) -> f32 {
+        self.axis_bindings(name)
+            .iter()
+            .map(|b| b.binding.value(input) * b.scale)
+            .sum::<f32>()
+            .clamp(-1.0, 1.0)
+    }
+
+    // ── Persistence ─────────────────────────────────────────────────────────
+
+    pub fn to_json(&self) -> ReactorResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize input bindings: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> ReactorResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| ReactorError::invalid_format(format!("Invalid input bindings: {}", e)))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ReactorResult<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()?).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to write bindings {}", path.display()), e)
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> ReactorResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to read bindings {}", path.display()), e)
+        })?;
+        Self::from_json(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_edges_follow_the_input_frame() {
+        let mut map = ActionMap::new();
+        map.bind_key("jump", KeyCode::Space);
+        map.bind_mouse("jump", MouseButton::Right);
+        let mut input = Input::without_gamepad();
+
+        input.set_key(KeyCode::Space, true);
+        assert!(map.action_pressed(&input, "jump") && map.action_down(&input, "jump"));
+        input.begin_frame();
+        assert!(!map.action_pressed(&input, "jump") && map.action_down(&input, "jump"));
+
+        // A second binding going down while the first is held is not a new press.
+        input.set_mouse_button(MouseButton::Right, true);
+        assert!(!map.action_pressed(&input, "jump"));
+        input.begin_frame();
+        input.set_key(KeyCode::Space, false);
+        assert!(!map.action_released(&input, "jump"));
+        input.begin_frame();
+        input.set_mouse_button(MouseButton::Right, false);
+        assert!(map.action_released(&input, "jump") && !map.action_down(&input, "jump"));
+
+        // A tap within one frame still reads as pressed and released.
+        input.begin_frame();
+        input.set_key(KeyCode::Space, true);
+        input.set_key(KeyCode::Space, false);
+        assert!(map.action_pressed(&input, "jump") && map.action_released(&input, "jump"));
+        assert!(!map.action_down(&input, "unknown"));
+    }
+
+    #[test]
+    fn digital_axes_sum_and_clamp() {
+        let mut map = ActionMap::new();
+        map.bind_axis_keys("move_x", KeyCode::KeyA, KeyCode::KeyD);
+        map.bind_axis_keys("move_x", KeyCode::ArrowLeft, KeyCode::ArrowRight);
+        let mut input = Input::without_gamepad();
+
+        input.set_key(KeyCode::KeyD, true);
+        assert_eq!(map.axis_value(&input, "move_x"), 1.0);
+        input.set_key(KeyCode::ArrowRight, true);
+        assert_eq!(map.axis_value(&input, "move_x"), 1.0);
+        input.set_key(KeyCode::KeyA, true);
+        assert_eq!(map.axis_value(&input, "move_x"), 1.0);
+        input.set_key(KeyCode::ArrowRight, false);
+        assert_eq!(map.axis_value(&input, "move_x"), 0.0);
+        input.set_key(KeyCode::KeyD, false);
+        assert_eq!(map.axis_value(&input, "move_x"), -1.0);
+        assert_eq!(map.axis_value(&input, "missing"), 0.0);
+    }
+
+    #[test]
+    fn bindings_round_trip_through_json() {
+        let map = ActionMap::fps_defaults();
+        let json = map.to_json().unwrap();
+        assert_eq!(ActionMap::from_json(&json).unwrap(), map);
+        assert!(ActionMap::from_json("{ \"actions\": 3 }").is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Component editing for inspector panels
+//!
+//! The ECS components an inspector can add, show and remove, and undoable
+//! edits on them. A `ComponentCommand` keeps the value of one component kind
+//! before and after the edit for every entity it touched, so editing several
+//! selected entities at once is a single undo step. Commands go into
+//! `TransformHistory` next to transform edits; the viewport picks the change
+//! up in the same frame through `Scene::sync_from_world` and
+//! `LightingSystem::sync_from_world`.
+
+use crate::scene::camera::Camera;
+use crate::scene::ecs::{Component, Entity, World};
+use crate::systems::lighting::Light;
+use crate::systems::physics::RigidBody;
+use crate::systems::scene::MeshRenderer;
+use glam::Vec3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    MeshRenderer,
+    Light,
+    Camera,
+    RigidBody,
+}
+
+impl ComponentKind {
+    /// In "Add Component" order.
+    pub const ALL: [ComponentKind; 4] =
+        [ComponentKind::MeshRenderer, ComponentKind::Light, ComponentKind::Camera, ComponentKind::RigidBody];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MeshRenderer => "Mesh Renderer",
+            Self::Light => "Light",
+            Self::Camera => "Camera",
+            Self::RigidBody => "RigidBody",
+        }
+    }
+
+    pub fn is_on(self, world: &World, entity: Entity) -> bool {
+        match self {
+            Self::MeshRenderer => world.has_component::<MeshRenderer>(entity),
+            Self::Light => world.has_component::<Light>(entity),
+            Self::Camera => world.has_component::<Camera>(entity),
+            Self::RigidBody => world.has_component::<RigidBody>(entity),
+        }
+    }
+
+    /// What "Add Component" inserts. `None` for `MeshRenderer`, which needs a
+    /// mesh and a material picked by the user.
+    pub fn default_value(self) -> Option<ComponentValue> {
+        match self {
+            Self::MeshRenderer => None,
+            Self::Light => Some(ComponentValue::Light(Light::point(Vec3::ZERO, Vec3::ONE, 1.0, 10.0))),
+            Self::Camera => Some(ComponentValue::Camera(Camera { position: Vec3::ZERO, ..Camera::new() })),
+            Self::RigidBody => Some(ComponentValue::RigidBody(RigidBody::default())),
+        }
+    }
+
+    fn remove_from(self, world: &mut World, entity: Entity) {
+        match self {
+            Self::MeshRenderer => { world.remove_component::<MeshRenderer>(entity); }
+            Self::Light => { world.remove_component::<Light>(entity); }
+            Self::Camera => { world.remove_component::<Camera>(entity); }
+            Self::RigidBody => { world.remove_component::<RigidBody>(entity); }
+        }
+    }
+
+    fn add_label(self) -> &'static str {
+        match self {
+            Self::MeshRenderer => "Add Mesh Renderer",
+            Self::Light => "Add Light",
+            Self::Camera => "Add Camera",
+            Self::RigidBody => "Add RigidBody",
+        }
+    }
+
+    fn remove_label(self) -> &'static str {
+        match self {
+            Self::MeshRenderer => "Remove Mesh Renderer",
+            Self::Light => "Remove Light",
+            Self::Camera => "Remove Camera",
+            Self::RigidBody => "Remove RigidBody",
+        }
+    }
+}
+
+/// One inspector component, by value.
+#[derive(Clone)]
+pub enum ComponentValue {
+    MeshRenderer(MeshRenderer),
+    Light(Light),
+    Camera(Camera),
+    RigidBody(RigidBody),
+}
+
+impl ComponentValue {
+    pub fn kind(&self) -> ComponentKind {
+        match self {
+            Self::MeshRenderer(_) => ComponentKind::MeshRenderer,
+            Self::Light(_) => ComponentKind::Light,
+            Self::Camera(_) => ComponentKind::Camera,
+            Self::RigidBody(_) => ComponentKind::RigidBody,
+        }
+    }
+
+    pub fn read(world: &World, entity: Entity, kind: ComponentKind) -> Option<Self> {
+        match kind {
+            ComponentKind::MeshRenderer => world.get_component::<MeshRenderer>(entity).cloned().map(Self::MeshRenderer),
+            ComponentKind::Light => world.get_component::<Light>(entity).cloned().map(Self::Light),
+            ComponentKind::Camera => world.get_component::<Camera>(entity).copied().map(Self::Camera),
+            ComponentKind::RigidBody => world.get_component::<RigidBody>(entity).cloned().map(Self::RigidBody),
+        }
+    }
+
+    /// Adds or replaces the component on `entity`.
+    pub fn write(self, world: &mut World, entity: Entity) {
+        match self {
+            Self::MeshRenderer(c) => world.add_component(entity, c),
+            Self::Light(c) => world.add_component(entity, c),
+            Self::Camera(c) => world.add_component(entity, c),
+            Self::RigidBody(c) => world.add_component(entity, c),
+        }
+    }
+}
+
+/// Component types with a `ComponentValue` variant, for `ComponentCommand::edit`.
+pub trait InspectorComponent: Component + Clone {
+    const KIND: ComponentKind;
+}
+
+impl InspectorComponent for MeshRenderer {
+    const KIND: ComponentKind = ComponentKind::MeshRenderer;
+}
+
+impl InspectorComponent for Light {
+    const KIND: ComponentKind = ComponentKind::Light;
+}
+
+impl InspectorComponent for Camera {
+    const KIND: ComponentKind = ComponentKind::Camera;
+}
+
+impl InspectorComponent for RigidBody {
+    const KIND: ComponentKind = ComponentKind::RigidBody;
+}
+
+/// Components on `entity`, in `ComponentKind::ALL` order.
+pub fn components_of(world: &World, entity: Entity) -> Vec<ComponentKind> {
+    ComponentKind::ALL.into_iter().filter(|kind| kind.is_on(world, entity)).collect()
+}
+
+/// Components every entity of `entities` has: the sections a multi-selection
+/// inspector shows.
+pub fn common_components(world: &World, entities: &[Entity]) -> Vec<ComponentKind> {
+    if entities.is_empty() {
+        return Vec::new();
+    }
+    ComponentKind::ALL.into_iter().filter(|kind| entities.iter().all(|&e| kind.is_on(world, e))).collect()
+}
+
+#[derive(Clone)]
+pub struct ComponentChange {
+    pub entity: Entity,
+    /// `None`: the entity did not have the component.
+    pub before: Option<ComponentValue>,
+    pub after: Option<ComponentValue>,
+}
+
+/// An applied add, remove or edit of one component kind on one or more
+/// entities.
+#[derive(Clone)]
+pub struct ComponentCommand {
+    pub kind: ComponentKind,
+    pub label: &'static str,
+    pub changes: Vec<ComponentChange>,
+}
+
+impl ComponentCommand {
+    /// Adds `value` to each entity that lacks its kind. `None` if all of
+    /// them already had it.
+    pub fn add(world: &mut World, entities: &[Entity], value: ComponentValue) -> Option<Self> {
+        let kind = value.kind();
+        let changes: Vec<ComponentChange> = entities
+            .iter()
+            .filter(|&&entity| world.is_alive(entity) && !kind.is_on(world, entity))
+            .map(|&entity| ComponentChange { entity, before: None, after: Some(value.clone()) })
+            .collect();
+        Self::applied(world, kind, kind.add_label(), changes)
+    }
+
+    pub fn remove(world: &mut World, entities: &[Entity], kind: ComponentKind) -> Option<Self> {
+        let changes: Vec<ComponentChange> = entities
+            .iter()
+            .filter_map(|&entity| {
+                let before = ComponentValue::read(world, entity, kind)?;
+                Some(ComponentChange { entity, before: Some(before), after: None })
+            })
+            .collect();
+        Self::applied(world, kind, kind.remove_label(), changes)
+    }
+
+    /// Runs `edit` on the `T` of each entity that has one, e.g. an intensity
+    /// slider over a multi-selection. `None` if none of them has a `T`.
+    pub fn edit<T: InspectorComponent>(
+        world: &mut World,
+        entities: &[Entity],
+        label: &'static str,
+        mut edit: impl FnMut(&mut T),
+    ) -> Option<Self> {
+        let mut changes = Vec::new();
+        for &entity in entities {
+            let Some(before) = ComponentValue::read(world, entity, T::KIND) else { continue };
+            let Some(component) = world.get_component_mut::<T>(entity) else { continue };
+            edit(component);
+            changes.push(ComponentChange { entity, before: Some(before), after: ComponentValue::read(world, entity, T::KIND) });
+        }
+        if changes.is_empty() {
+            return None;
+        }
+        Some(Self { kind: T::KIND, label, changes })
+    }
+
+    fn applied(world: &mut World, kind: ComponentKind, label: &'static str, changes: Vec<ComponentChange>) -> Option<Self> {
+        if changes.is_empty() {
+            return None;
+        }
+        let command = Self { kind, label, changes };
+        command.redo(world);
+        Some(command)
+    }
+
+    /// "Add Light entity 3", "Edit intensity 4 entities".
+    pub fn description(&self) -> String {
+        match self.changes.as_slice() {
+            [single] => format!("{} entity {}", self.label, single.entity),
+            changes => format!("{} {} entities", self.label, changes.len()),
+        }
+    }
+
+    pub fn undo(&self, world: &mut World) {
+        for change in &self.changes {
+            self.set(world, change.entity, change.before.clone());
+        }
+    }
+
+    pub fn redo(&self, world: &mut World) {
+        for change in &self.changes {
+            self.set(world, change.entity, change.after.clone());
+        }
+    }
+
+    /// Folds a later edit of the same entities and label into this one
+    /// (a slider dragged over several frames). `false` if they differ.
+    pub(crate) fn merge(&mut self, later: &ComponentCommand) -> bool {
+        let same_entities = self.changes.len() == later.changes.len()
+            && self.changes.iter().zip(&later.changes).all(|(a, b)| a.entity == b.entity);
+        if self.kind != later.kind || self.label != later.label || !same_entities {
+            return false;
+        }
+        for (change, later) in self.changes.iter_mut().zip(&later.changes) {
+            change.after = later.after.clone();
+        }
+        true
+    }
+
+    fn set(&self, world: &mut World, entity: Entity, value: Option<ComponentValue>) {
+        match value {
+            Some(value) => value.write(world, entity),
+            None => self.kind.remove_from(world, entity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::transform_history::TransformHistory;
+
+    fn world_with(count: usize) -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        let entities = (0..count).map(|_| world.create_entity()).collect();
+        (world, entities)
+    }
+
+    fn intensity(world: &World, entity: Entity) -> Option<f32> {
+        world.get_component::<Light>(entity).map(|l| l.intensity)
+    }
+
+    #[test]
+    fn add_and_remove_are_undoable() {
+        let (mut world, entities) = world_with(1);
+        let mut history = TransformHistory::new();
+        let light = ComponentKind::Light.default_value().unwrap();
+        history.push_component(ComponentCommand::add(&mut world, &entities, light.clone()).unwrap());
+        assert_eq!(components_of(&world, 0), [ComponentKind::Light]);
+        assert!(ComponentCommand::add(&mut world, &entities, light).is_none(), "already there");
+
+        history.seal();
+        history.push_component(ComponentCommand::remove(&mut world, &entities, ComponentKind::Light).unwrap());
+        assert!(components_of(&world, 0).is_empty());
+
+        assert_eq!(history.undo(&mut world).as_deref(), Some("Remove Light entity 0"));
+        assert_eq!(intensity(&world, 0), Some(1.0));
+        assert_eq!(history.undo(&mut world).as_deref(), Some("Add Light entity 0"));
+        assert!(components_of(&world, 0).is_empty());
+        history.redo(&mut world);
+        assert!(ComponentKind::Light.is_on(&world, 0));
+    }
+
+    #[test]
+    fn multi_selection_edit_is_one_step() {
+        let (mut world, entities) = world_with(3);
+        for &entity in &entities {
+            world.add_component(entity, Light::point(Vec3::ZERO, Vec3::ONE, entity as f32, 5.0));
+        }
+        world.add_component(2, RigidBody::default());
+        assert_eq!(common_components(&world, &entities), [ComponentKind::Light]);
+
+        let mut history = TransformHistory::new();
+        for step in 1..=3 {
+            let command = ComponentCommand::edit(&mut world, &entities, "Edit intensity", |light: &mut Light| {
+                light.intensity = step as f32 * 10.0;
+            });
+            history.push_component(command.unwrap());
+        }
+        history.seal();
+        assert!(entities.iter().all(|&e| intensity(&world, e) == Some(30.0)));
+        assert_eq!(history.undo_description().as_deref(), Some("Edit intensity 3 entities"));
+
+        history.undo(&mut world);
+        assert_eq!(entities.iter().map(|&e| intensity(&world, e)).collect::<Vec<_>>(), [Some(0.0), Some(1.0), Some(2.0)]);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn edit_skips_entities_without_the_component() {
+        let (mut world, entities) = world_with(2);
+        world.add_component(1, Camera::new());
+        let command = ComponentCommand::edit(&mut world, &entities, "Edit FOV", |camera: &mut Camera| camera.fov = 1.0).unwrap();
+        assert_eq!(command.description(), "Edit FOV entity 1");
+        assert!(ComponentCommand::edit(&mut world, &[0], "Edit FOV", |camera: &mut Camera| camera.fov = 1.0).is_none());
+    }
+}
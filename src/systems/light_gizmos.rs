@@ -0,0 +1,162 @@
+//! Light gizmos
+//!
+//! Wireframes drawn into a `DebugRenderer` from the light data itself, so
+//! what the viewport shows is what the shader gets: a point light's range
+//! as a circle facing the camera (the full sphere once selected), a spot
+//! light's cone out to its range at the outer angle, and a directional
+//! light as a small sun with an arrow along its direction. Lines take the
+//! hue of the light color; selected lights are drawn brighter.
+//!
+//! ECS lights are drawn in world space (see `world_space_light`), so
+//! rotating a light entity with the rotate gizmo re-aims the drawn cone or
+//! arrow together with the light.
+
+use crate::graphics::debug_renderer::{DebugRenderer, DebugSphere};
+use crate::scene::ecs::{Entity, World};
+use crate::systems::lighting::{world_space_light, Light, LightType};
+use glam::{Vec3, Vec4};
+
+/// Segments of every circle in a light gizmo.
+pub const LIGHT_GIZMO_SEGMENTS: u32 = 32;
+/// Radius of the sun icon and length of its arrow, in world units.
+pub const SUN_GIZMO_SIZE: f32 = 0.5;
+/// Length of a spot cone whose light has no finite range.
+pub const MAX_SPOT_GIZMO_LENGTH: f32 = 100.0;
+
+/// Draws the gizmo of one light, already in world space. Disabled lights
+/// are skipped. `camera_pos` orients the range circle of point lights.
+pub fn draw_light_gizmo(debug: &mut DebugRenderer, light: &Light, selected: bool, camera_pos: Vec3) {
+    if !light.enabled {
+        return;
+    }
+    let color = gizmo_color(light.color, selected);
+    match light.light_type {
+        LightType::Point => {
+            if selected {
+                let sphere = DebugSphere { center: light.position, radius: light.range };
+                debug.sphere(&sphere, color, LIGHT_GIZMO_SEGMENTS);
+            }
+            let facing = (camera_pos - light.position).normalize_or(Vec3::Z);
+            circle(debug, light.position, facing, light.range, color);
+        }
+        LightType::Spot => {
+            let direction = light.direction.normalize_or(Vec3::NEG_Z);
+            let length = light.range.min(MAX_SPOT_GIZMO_LENGTH);
+            let half_angle = light.spot_angle.clamp(0.0, 89.0_f32.to_radians());
+            let base = light.position + direction * length;
+            let radius = length * half_angle.tan();
+            circle(debug, base, direction, radius, color);
+            let (u, v) = direction.any_orthonormal_pair();
+            for edge in [u, v, -u, -v] {
+                debug.line(light.position, base + edge * radius, color);
+            }
+            debug.line(light.position, base, color);
+        }
+        LightType::Directional => {
+            let direction = light.direction.normalize_or(Vec3::NEG_Y);
+            let center = light.position;
+            circle(debug, center, direction, SUN_GIZMO_SIZE * 0.5, color);
+            let (u, v) = direction.any_orthonormal_pair();
+            for i in 0..8 {
+                let angle = i as f32 * std::f32::consts::TAU / 8.0;
+                let ray = u * angle.cos() + v * angle.sin();
+                debug.line(center + ray * SUN_GIZMO_SIZE * 0.6, center + ray * SUN_GIZMO_SIZE, color);
+            }
+            arrow(debug, center, direction, SUN_GIZMO_SIZE * 4.0, color);
+        }
+    }
+}
+
+/// Draws a gizmo for every `Light` component in `world`, highlighting the
+/// entities in `selection`.
+pub fn draw_light_gizmos(debug: &mut DebugRenderer, world: &World, selection: &[Entity], camera_pos: Vec3) {
+    for (entity, light) in world.query::<Light>() {
+        let light = world_space_light(world, entity, light);
+        draw_light_gizmo(debug, &light, selection.contains(&entity), camera_pos);
+    }
+}
+
+/// Light color normalized to its brightest channel, so intensity and HDR
+/// colors don't wash the lines out; selected gizmos lean towards white.
+fn gizmo_color(color: Vec3, selected: bool) -> Vec4 {
+    let hue = color.max(Vec3::ZERO);
+    let hue = if hue.max_element() > 0.0 { hue / hue.max_element() } else { Vec3::ONE };
+    if selected {
+        hue.lerp(Vec3::ONE, 0.5).extend(1.0)
+    } else {
+        (hue * 0.6).extend(0.8)
+    }
+}
+
+fn circle(debug: &mut DebugRenderer, center: Vec3, normal: Vec3, radius: f32, color: Vec4) {
+    let (u, v) = normal.any_orthonormal_pair();
+    let step = std::f32::consts::TAU / LIGHT_GIZMO_SEGMENTS as f32;
+    let point = |i: u32| {
+        let angle = i as f32 * step;
+        center + (u * angle.cos() + v * angle.sin()) * radius
+    };
+    for i in 0..LIGHT_GIZMO_SEGMENTS {
+        debug.line(point(i), point(i + 1), color);
+    }
+}
+
+fn arrow(debug: &mut DebugRenderer, origin: Vec3, direction: Vec3, length: f32, color: Vec4) {
+    let tip = origin + direction * length;
+    let (u, v) = direction.any_orthonormal_pair();
+    let back = tip - direction * length * 0.2;
+    for side in [u, v, -u, -v] {
+        debug.line(tip, back + side * length * 0.08, color);
+    }
+    debug.line(origin, tip, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::transform::Transform;
+    use glam::Quat;
+
+    #[test]
+    fn spot_cone_follows_the_light_and_its_entity() {
+        let mut world = World::new();
+        let lamp = world.create_entity();
+        world.add_component(lamp, Light::spot(Vec3::ZERO, Vec3::NEG_Z, Vec3::new(2.0, 1.0, 0.0), 3.0, 10.0, 30.0));
+        world.add_component(lamp, Transform::from_translation(0.0, 1.0, 0.0));
+
+        let mut debug = DebugRenderer::new();
+        draw_light_gizmos(&mut debug, &world, &[], Vec3::new(0.0, 0.0, 10.0));
+        assert_eq!(debug.line_count(), LIGHT_GIZMO_SEGMENTS as usize + 5);
+        // Tinted by the hue of the light, not its HDR value.
+        assert!(debug.get_lines().all(|line| line.color.x <= 1.0 && line.color.z == 0.0));
+        let axis = debug.get_lines().last().unwrap();
+        assert_eq!(axis.start, Vec3::Y);
+        assert!(axis.end.abs_diff_eq(Vec3::new(0.0, 1.0, -10.0), 1e-5));
+
+        // Rotating the entity re-aims the cone.
+        world.get_component_mut::<Transform>(lamp).unwrap().rotate(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        debug.clear();
+        draw_light_gizmos(&mut debug, &world, &[lamp], Vec3::new(0.0, 0.0, 10.0));
+        let axis = debug.get_lines().last().unwrap();
+        assert!(axis.end.abs_diff_eq(Vec3::new(-10.0, 1.0, 0.0), 1e-5));
+        assert!(debug.get_lines().all(|line| line.color.w == 1.0));
+    }
+
+    #[test]
+    fn point_range_and_disabled_lights() {
+        let mut debug = DebugRenderer::new();
+        let mut light = Light::point(Vec3::ONE, Vec3::ONE, 1.0, 4.0);
+        draw_light_gizmo(&mut debug, &light, false, Vec3::new(1.0, 1.0, 20.0));
+        assert_eq!(debug.line_count(), LIGHT_GIZMO_SEGMENTS as usize);
+        assert!(debug.get_lines().all(|line| (line.start.distance(Vec3::ONE) - 4.0).abs() < 1e-4 && (line.start.z - 1.0).abs() < 1e-5));
+
+        debug.clear();
+        draw_light_gizmo(&mut debug, &light, true, Vec3::new(1.0, 1.0, 20.0));
+        assert_eq!(debug.line_count(), 4 * LIGHT_GIZMO_SEGMENTS as usize);
+
+        debug.clear();
+        light.enabled = false;
+        draw_light_gizmo(&mut debug, &light, true, Vec3::ZERO);
+        draw_light_gizmo(&mut debug, &Light::sun(), false, Vec3::ZERO);
+        assert_eq!(debug.line_count(), LIGHT_GIZMO_SEGMENTS as usize + 8 + 5);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::graphics::uniform_buffer::{LightData, LightUniformData, MAX_LIGHTS};
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform::Transform;
 use glam::Vec3;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -8,6 +10,9 @@ pub enum LightType {
     Spot,
 }
 
+/// Light source. Lives either in `LightingSystem::lights` (world space) or as an
+/// ECS component, where `position`/`direction` are local to the entity's
+/// `Transform` (see `LightingSystem::sync_from_world`).
 #[derive(Clone, Debug)]
 pub struct Light {
     pub light_type: LightType,
@@ -110,10 +115,46 @@ impl Light {
     }
 }
 
+/// `light` (a component of `entity`) in world space: position and direction
+/// go through the entity's world matrix, so rotating the entity (e.g. with
+/// the rotate gizmo) re-aims spot and directional lights.
+pub fn world_space_light(world: &World, entity: Entity, light: &Light) -> Light {
+    let mut light = light.clone();
+    // A parented light follows its parent even without a Transform of its own.
+    if world.has_component::<Transform>(entity) || world.parent(entity).is_some() {
+        let matrix = world.world_matrix(entity);
+        light.position = matrix.transform_point3(light.position);
+        let direction = matrix.transform_vector3(light.direction).normalize_or_zero();
+        if direction != Vec3::ZERO {
+            light.direction = direction;
+        }
+    }
+    light
+}
+
+/// Stable id of a light in `LightingSystem::lights`. Unlike the index, it
+/// survives other lights being removed and is never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LightId(u32);
+
+impl LightId {
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+}
+
 pub struct LightingSystem {
+    /// Global lights. Lights pushed here directly get a `LightId` on the next
+    /// `&mut self` call that needs one.
     pub lights: Vec<Light>,
     pub ambient_color: Vec3,
     pub ambient_intensity: f32,
+    /// Lights gathered from ECS entities; rebuilt on every `sync_from_world`,
+    /// so indices into `lights` are unaffected by entities coming and going.
+    entity_lights: Vec<Light>,
+    /// `LightId` of each entry of `lights`, in the same order.
+    ids: Vec<LightId>,
+    next_id: u32,
 }
 
 impl LightingSystem {
@@ -122,6 +163,9 @@ impl LightingSystem {
             lights: Vec::new(),
             ambient_color: Vec3::splat(0.1),
             ambient_intensity: 1.0,
+            entity_lights: Vec::new(),
+            ids: Vec::new(),
+            next_id: 0,
         }
     }
 
@@ -132,14 +176,79 @@ impl LightingSystem {
     }
 
     pub fn add_light(&mut self, light: Light) -> usize {
+        self.sync_ids();
         let index = self.lights.len();
         self.lights.push(light);
+        self.sync_ids();
         index
     }
 
-    pub fn remove_light(&mut self, index: usize) {
+    /// Like `add_light`, but returns the light's stable id.
+    pub fn insert_light(&mut self, light: Light) -> LightId {
+        let index = self.add_light(light);
+        self.ids[index]
+    }
+
+    /// Removes the light at `index`, shifting the ones after it down by one.
+    pub fn remove_light(&mut self, index: usize) -> Option<Light> {
+        self.sync_ids();
+        if index < self.lights.len() {
+            self.ids.remove(index);
+            Some(self.lights.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the light at `index` in O(1): the last light takes its place,
+    /// every other index stays valid. Ids are unaffected either way.
+    pub fn swap_remove_light(&mut self, index: usize) -> Option<Light> {
+        self.sync_ids();
         if index < self.lights.len() {
-            self.lights.remove(index);
+            self.ids.swap_remove(index);
+            Some(self.lights.swap_remove(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn remove_light_by_id(&mut self, id: LightId) -> Option<Light> {
+        let index = self.index_of_light(id)?;
+        self.swap_remove_light(index)
+    }
+
+    pub fn light_id(&mut self, index: usize) -> Option<LightId> {
+        self.sync_ids();
+        self.ids.get(index).copied()
+    }
+
+    /// Current index of the light with `id`, `None` once it was removed.
+    pub fn index_of_light(&self, id: LightId) -> Option<usize> {
+        self.ids.iter().take(self.lights.len()).position(|&other| other == id)
+    }
+
+    pub fn light_by_id_mut(&mut self, id: LightId) -> Option<&mut Light> {
+        let index = self.index_of_light(id)?;
+        self.lights.get_mut(index)
+    }
+
+    /// Brings `ids` back in step with `lights` after direct edits of the vector.
+    fn sync_ids(&mut self) {
+        self.ids.truncate(self.lights.len());
+        while self.ids.len() < self.lights.len() {
+            self.ids.push(LightId(self.next_id));
+            self.next_id += 1;
+        }
+    }
+
+    /// Replaces the light at `index`. Returns false if there is no such light.
+    pub fn set_light(&mut self, index: usize, light: Light) -> bool {
+        match self.lights.get_mut(index) {
+            Some(slot) => {
+                *slot = light;
+                true
+            }
+            None => false,
         }
     }
 
@@ -156,6 +265,24 @@ impl LightingSystem {
         self.ambient_intensity = intensity;
     }
 
+    /// Collects every entity with a `Light` component, moving it into world
+    /// space with the entity's `Transform` and its parents' (if any). Call
+    /// once per frame.
+    pub fn sync_from_world(&mut self, world: &World) {
+        self.entity_lights.clear();
+        for (entity, light) in world.query::<Light>() {
+            self.entity_lights.push(world_space_light(world, entity, light));
+        }
+    }
+
+    pub fn entity_lights(&self) -> &[Light] {
+        &self.entity_lights
+    }
+
+    fn active_lights(&self) -> impl Iterator<Item = &Light> {
+        self.lights.iter().chain(&self.entity_lights).filter(|l| l.enabled)
+    }
+
     pub fn to_gpu_data(&self) -> LightUniformData {
         let mut data = LightUniformData {
             ambient_color: [
@@ -167,7 +294,7 @@ impl LightingSystem {
             ..Default::default()
         };
 
-        let enabled_lights: Vec<_> = self.lights.iter().filter(|l| l.enabled).collect();
+        let enabled_lights: Vec<_> = self.active_lights().collect();
         data.light_count = enabled_lights.len().min(MAX_LIGHTS) as u32;
 
         for (i, light) in enabled_lights.iter().take(MAX_LIGHTS).enumerate() {
@@ -178,7 +305,7 @@ impl LightingSystem {
     }
 
     pub fn light_count(&self) -> usize {
-        self.lights.iter().filter(|l| l.enabled).count()
+        self.active_lights().count()
     }
 }
 
@@ -187,3 +314,61 @@ impl Default for LightingSystem {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    #[test]
+    fn entity_lights_follow_their_transform() {
+        let mut world = World::new();
+        let lamp = world.create_entity();
+        world.add_component(lamp, Light::spot(Vec3::ZERO, Vec3::NEG_Z, Vec3::ONE, 1.0, 10.0, 30.0));
+        world.add_component(lamp, Transform::from_translation(1.0, 2.0, 3.0));
+
+        let mut lighting = LightingSystem::new();
+        lighting.add_light(Light::sun());
+        lighting.sync_from_world(&world);
+        assert_eq!(lighting.light_count(), 2);
+        assert_eq!(lighting.entity_lights()[0].position, Vec3::new(1.0, 2.0, 3.0));
+
+        let transform = world.get_component_mut::<Transform>(lamp).unwrap();
+        transform.translate(Vec3::X);
+        transform.rotate(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        lighting.sync_from_world(&world);
+        let light = &lighting.entity_lights()[0];
+        assert_eq!(light.position, Vec3::new(2.0, 2.0, 3.0));
+        assert!(light.direction.abs_diff_eq(Vec3::NEG_X, 1e-5));
+        assert_eq!(lighting.to_gpu_data().light_count, 2);
+
+        world.destroy_entity(lamp);
+        lighting.sync_from_world(&world);
+        assert_eq!(lighting.light_count(), 1);
+    }
+
+    #[test]
+    fn light_ids_survive_removal_of_other_lights() {
+        let mut lighting = LightingSystem::new();
+        let point = |x: f32| Light::point(Vec3::new(x, 0.0, 0.0), Vec3::ONE, 1.0, 5.0);
+        let ids: Vec<LightId> = (0..4).map(|i| lighting.insert_light(point(i as f32))).collect();
+
+        // Swap removal moves only the last light.
+        assert_eq!(lighting.swap_remove_light(0).unwrap().position.x, 0.0);
+        assert_eq!(lighting.index_of_light(ids[3]), Some(0));
+        assert_eq!(lighting.index_of_light(ids[1]), Some(1));
+        assert_eq!(lighting.index_of_light(ids[0]), None);
+
+        // Per-frame edits through the id, without rebuilding the list.
+        lighting.light_by_id_mut(ids[2]).unwrap().position = Vec3::new(0.0, 3.0, 0.0);
+        assert_eq!(lighting.get_light(2).unwrap().position.y, 3.0);
+
+        // Lights pushed directly get fresh ids, never a removed one.
+        lighting.lights.push(point(9.0));
+        let pushed = lighting.light_id(3).unwrap();
+        assert!(!ids.contains(&pushed));
+        assert!(lighting.remove_light_by_id(ids[1]).is_some());
+        assert_eq!(lighting.index_of_light(pushed), Some(1));
+        assert_eq!(lighting.lights.len(), 3);
+    }
+}
@@ -0,0 +1,78 @@
+//! Level of detail
+//!
+//! A `SceneObject` with `lods` draws one of several meshes depending on how
+//! far the camera is from the center of its bounds. Levels are ordered from
+//! most to least detailed; each is used up to its `max_distance`, and the
+//! last one also beyond it. `Scene::update_lods` (run by `Scene::cull`)
+//! swaps the chosen level into `SceneObject::mesh`, so drawing, shadows,
+//! culling bounds and `RenderStats` all see the mesh actually drawn.
+//!
+//! Switching is delayed by a hysteresis band of `LOD_HYSTERESIS` around
+//! each threshold, so an object sitting right at the boundary does not
+//! flicker between two meshes. The scene-wide `Scene::lod_bias` scales the
+//! distance: above 1 coarser levels kick in sooner, below 1 later.
+
+use crate::resources::mesh::Mesh;
+use glam::{Mat4, Vec3, Vec4};
+use std::sync::Arc;
+
+/// Fraction of a threshold the distance must overshoot before the level
+/// changes.
+pub const LOD_HYSTERESIS: f32 = 0.1;
+
+#[derive(Clone)]
+pub struct LodLevel {
+    pub mesh: Arc<Mesh>,
+    /// Farthest (biased) distance this level is used at.
+    pub max_distance: f32,
+}
+
+/// Level for `distance` among levels with these `max_distances` (ascending),
+/// staying at `current` while the distance is within the hysteresis band of
+/// its range.
+pub fn select_lod(max_distances: &[f32], current: usize, distance: f32) -> usize {
+    let Some(last) = max_distances.len().checked_sub(1) else { return 0 };
+    let current = current.min(last);
+    let near = if current == 0 { 0.0 } else { max_distances[current - 1] * (1.0 - LOD_HYSTERESIS) };
+    let far = if current == last { f32::INFINITY } else { max_distances[current] * (1.0 + LOD_HYSTERESIS) };
+    if distance >= near && distance <= far {
+        return current;
+    }
+    max_distances.iter().position(|&max| distance <= max).unwrap_or(last)
+}
+
+/// Camera position encoded in a perspective `view_projection`. `None` for
+/// orthographic projections, which have no eye point.
+pub fn camera_position(view_projection: &Mat4) -> Option<Vec3> {
+    // El ojo es el único punto con w = 0 en clip space.
+    let eye = view_projection.inverse() * Vec4::Z;
+    (eye.w.abs() > f32::EPSILON).then(|| eye.truncate() / eye.w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_switch_past_the_hysteresis_band() {
+        let levels = [10.0, 30.0, 60.0];
+        assert_eq!(select_lod(&levels, 0, 5.0), 0);
+        assert_eq!(select_lod(&levels, 0, 10.5), 0);
+        assert_eq!(select_lod(&levels, 0, 11.5), 1);
+        assert_eq!(select_lod(&levels, 1, 9.5), 1);
+        assert_eq!(select_lod(&levels, 1, 8.5), 0);
+        assert_eq!(select_lod(&levels, 0, 45.0), 2);
+        assert_eq!(select_lod(&levels, 2, 500.0), 2);
+        assert_eq!(select_lod(&[], 3, 1.0), 0);
+    }
+
+    #[test]
+    fn camera_position_comes_back_from_the_view_projection() {
+        let eye = Vec3::new(3.0, 4.0, -5.0);
+        let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
+        let perspective = Mat4::perspective_rh(60f32.to_radians(), 1.5, 0.1, 500.0);
+        assert!(camera_position(&(perspective * view)).unwrap().abs_diff_eq(eye, 1e-3));
+        let orthographic = Mat4::orthographic_rh(-5.0, 5.0, -5.0, 5.0, 0.1, 100.0);
+        assert!(camera_position(&(orthographic * view)).is_none());
+    }
+}
@@ -4,24 +4,49 @@
 
 pub mod animation;
 pub mod audio;
+pub mod camera;
 pub mod console;
 pub mod event_bus;
 pub mod fps_controller;
 pub mod frustum;
+pub mod gizmo;
+pub mod input;
+pub mod inspector;
+pub mod light_gizmos;
 pub mod lighting;
+pub mod lod;
 pub mod particles;
 pub mod physics;
+pub mod play_session;
+pub mod prefab;
 pub mod scene;
+pub mod scene_snapshot;
+pub mod sdf;
+pub mod skinning;
+pub mod transform_history;
 
 // Re-exports for backward compatibility
 pub use animation::{
-    AnimationClip, AnimationPlayer, AnimationTrack, EasingFunction, Keyframe, LoopMode, Tween,
+    AnimationClip, AnimationClipId, AnimationPlayback, AnimationPlayer, AnimationSystem,
+    AnimationTarget, AnimationTrack, EasingFunction, Keyframe, LoopMode, SkinnedModelId, Tween,
 };
 pub use audio::{AudioClipId, AudioListener, AudioSource, AudioSourceId, AudioSystem};
-pub use console::{color, GameBanner, Log, ReactorBanner};
+pub use camera::{FlyCameraController, FlyCameraInput, OrbitCameraController, OrbitCameraInput};
+pub use console::{color, CommandArgs, ConsoleHost, ConsoleRegistry, GameBanner, Log, ReactorBanner};
 pub use event_bus::{EventBus, Observer};
 pub use frustum::{CullingSystem, Frustum, FrustumTestResult, Plane};
-pub use lighting::{Light, LightType, LightingSystem};
+pub use gizmo::{GizmoAxis, GizmoDrag, GizmoHandle, GizmoSnap, SnapMode};
+pub use input::{ActionMap, AxisBinding, InputBinding};
+pub use inspector::{ComponentCommand, ComponentKind, ComponentValue, InspectorComponent};
+pub use light_gizmos::{draw_light_gizmo, draw_light_gizmos};
+pub use lighting::{Light, LightId, LightType, LightingSystem};
+pub use lod::{LodLevel, LOD_HYSTERESIS};
 pub use particles::{EmitterShape, Particle, ParticleSystem, ParticleSystemConfig};
-pub use physics::{PhysicsWorld, Ray, RigidBody, Sphere, AABB};
-pub use scene::{Scene, SceneObject};
+pub use physics::{Capsule, Collider, ColliderId, PhysicsMaterial, PhysicsWorld, Ray, RayHit, RigidBody, Sphere, AABB};
+pub use play_session::PlaySession;
+pub use prefab::{Prefab, PrefabInstance, PREFAB_VERSION};
+pub use scene::{MeshRenderer, PickHit, Scene, SceneHandle, SceneKey, SceneObject};
+pub use scene_snapshot::{SceneSnapshot, SCENE_SNAPSHOT_VERSION};
+pub use sdf::{SdfHit, SdfOp, SdfPrimitive, SdfScene, SdfShape};
+pub use skinning::Skeleton;
+pub use transform_history::{TransformCommand, TransformHistory};
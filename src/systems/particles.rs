@@ -1,4 +1,5 @@
-use glam::{Vec3, Vec4};
+use crate::resources::vertex::InstanceData;
+use glam::{Mat4, Quat, Vec3, Vec4};
 
 /// Single particle
 #[derive(Clone, Debug)]
@@ -150,24 +151,24 @@ impl<T: Clone> RandomRange<T> {
 }
 
 impl RandomRange<f32> {
+    /// A value from the wall clock. Particle systems use `at` with their own
+    /// seeded generator instead, so their output can be replayed.
     pub fn sample(&self) -> f32 {
-        // Simple pseudo-random using time
-        let t = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .subsec_nanos() as f32
-            / 1_000_000_000.0;
+        self.at(clock_fraction())
+    }
+
+    /// Value at `t` in `[0, 1)` between `min` and `max`.
+    pub fn at(&self, t: f32) -> f32 {
         self.min + (self.max - self.min) * t
     }
 }
 
 impl RandomRange<Vec3> {
     pub fn sample(&self) -> Vec3 {
-        let t = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .subsec_nanos() as f32
-            / 1_000_000_000.0;
+        self.at(clock_fraction())
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
         self.min.lerp(self.max, t)
     }
 }
@@ -218,7 +219,15 @@ impl Default for ParticleSystemConfig {
     }
 }
 
+/// Seed of systems created without `with_seed`.
+pub const DEFAULT_PARTICLE_SEED: u64 = 0x5EED_F00D_CAFE_7012;
+
 /// Particle system
+///
+/// Random values come from a generator seeded with `seed`: the same seed and
+/// the same sequence of `update` deltas emit the same particles, and
+/// `restart` replays from the beginning (play mode relies on this).
+#[derive(Clone, Debug)]
 pub struct ParticleSystem {
     pub config: ParticleSystemConfig,
     pub position: Vec3,
@@ -227,6 +236,8 @@ pub struct ParticleSystem {
     emission_accumulator: f32,
     time: f32,
     playing: bool,
+    seed: u64,
+    rng: u64,
 }
 
 impl ParticleSystem {
@@ -240,9 +251,31 @@ impl ParticleSystem {
             emission_accumulator: 0.0,
             time: 0.0,
             playing: true,
+            seed: DEFAULT_PARTICLE_SEED,
+            rng: DEFAULT_PARTICLE_SEED,
         }
     }
 
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = seed;
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Back to the state right after construction: no particles, time 0,
+    /// playing, generator rewound to `seed`.
+    pub fn restart(&mut self) {
+        self.particles.clear();
+        self.emission_accumulator = 0.0;
+        self.time = 0.0;
+        self.playing = true;
+        self.rng = self.seed;
+    }
+
     pub fn fire() -> Self {
         Self::new(ParticleSystemConfig {
             emission_rate: 50.0,
@@ -334,38 +367,39 @@ impl ParticleSystem {
         self.particles[idx] = particle;
     }
 
-    fn create_new_particle(&self) -> Particle {
+    fn create_new_particle(&mut self) -> Particle {
+        let (lifetime, size, rotation) = (self.next_random(), self.next_random(), self.next_random());
         let mut particle = Particle {
             alive: true,
             lifetime: 0.0,
-            max_lifetime: self.config.lifetime.sample(),
-            size: self.config.start_size.sample(),
-            rotation: self.config.start_rotation.sample(),
+            max_lifetime: self.config.lifetime.at(lifetime),
+            size: self.config.start_size.at(size),
+            rotation: self.config.start_rotation.at(rotation),
             color: self.config.start_color,
             ..Default::default()
         };
 
         // Position and velocity based on shape
-        let (pos_offset, direction) = match &self.config.shape {
+        let (pos_offset, direction) = match self.config.shape.clone() {
             EmitterShape::Point => (Vec3::ZERO, Vec3::Y),
             EmitterShape::Sphere { radius } => {
-                let dir = random_unit_sphere();
-                (dir * *radius, dir)
+                let dir = self.random_unit_sphere();
+                (dir * radius, dir)
             }
             EmitterShape::Box { half_extents } => {
                 let pos = Vec3::new(
-                    random_range(-half_extents.x, half_extents.x),
-                    random_range(-half_extents.y, half_extents.y),
-                    random_range(-half_extents.z, half_extents.z),
+                    self.random_range(-half_extents.x, half_extents.x),
+                    self.random_range(-half_extents.y, half_extents.y),
+                    self.random_range(-half_extents.z, half_extents.z),
                 );
                 (pos, Vec3::Y)
             }
             EmitterShape::Cone { angle, radius } => {
                 let angle_rad = angle.to_radians();
-                let r = random_range(0.0, *radius);
-                let theta = random_range(0.0, std::f32::consts::TAU);
+                let r = self.random_range(0.0, radius);
+                let theta = self.random_range(0.0, std::f32::consts::TAU);
                 let pos = Vec3::new(r * theta.cos(), 0.0, r * theta.sin());
-                let spread = random_range(0.0, angle_rad);
+                let spread = self.random_range(0.0, angle_rad);
                 let dir = Vec3::new(
                     spread.sin() * theta.cos(),
                     spread.cos(),
@@ -374,18 +408,20 @@ impl ParticleSystem {
                 (pos, dir.normalize())
             }
             EmitterShape::Circle { radius } => {
-                let theta = random_range(0.0, std::f32::consts::TAU);
-                let r = random_range(0.0, *radius);
+                let theta = self.random_range(0.0, std::f32::consts::TAU);
+                let r = self.random_range(0.0, radius);
                 (Vec3::new(r * theta.cos(), 0.0, r * theta.sin()), Vec3::Y)
             }
         };
 
+        let speed = self.next_random();
+        let speed = self.config.start_speed.at(speed);
         if self.config.world_space {
             particle.position = self.position + self.rotation * pos_offset;
-            particle.velocity = self.rotation * direction * self.config.start_speed.sample();
+            particle.velocity = self.rotation * direction * speed;
         } else {
             particle.position = pos_offset;
-            particle.velocity = direction * self.config.start_speed.sample();
+            particle.velocity = direction * speed;
         }
 
         particle.acceleration = Vec3::new(0.0, -9.81 * self.config.gravity_modifier, 0.0);
@@ -432,12 +468,10 @@ impl ParticleSystem {
 
             // Apply over-lifetime modifiers
             let age = particle.age();
-            let _size_mult = self.config.size_over_lifetime.sample(age);
             let color = self.config.color_over_lifetime.sample(age);
 
             particle.color = color;
-            // Size is base size * multiplier
-            // (stored size is the base, we'd apply multiplier at render time)
+            // Stored size is the base; `fill_instances` applies size_over_lifetime.
         }
     }
 
@@ -452,24 +486,114 @@ impl ParticleSystem {
     pub fn is_finished(&self) -> bool {
         !self.playing && self.alive_count() == 0
     }
+
+    /// Replaces `out` with one instance per live particle: world position,
+    /// size (times `size_over_lifetime`) and roll about the view axis in the
+    /// matrix, current color in `color`. The rotation and scale live in the
+    /// billboard plane, so the vertex shader orients the quad to the camera.
+    pub fn fill_instances(&self, out: &mut Vec<InstanceData>) {
+        out.clear();
+        out.extend(self.particles().map(|particle| {
+            let position = if self.config.world_space {
+                particle.position
+            } else {
+                self.position + self.rotation * particle.position
+            };
+            let size = particle.size * self.config.size_over_lifetime.sample(particle.age());
+            let transform = Mat4::from_scale_rotation_translation(Vec3::splat(size), Quat::from_rotation_z(particle.rotation), position);
+            InstanceData::new(transform).with_color(particle.color)
+        }));
+    }
+
+    /// Uniform in `[0, 1)` (splitmix64).
+    fn next_random(&mut self) -> f32 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn random_range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_random()
+    }
+
+    fn random_unit_sphere(&mut self) -> Vec3 {
+        let y = self.random_range(-1.0, 1.0);
+        let theta = self.random_range(0.0, std::f32::consts::TAU);
+        let r = (1.0 - y * y).max(0.0).sqrt();
+        Vec3::new(r * theta.cos(), y, r * theta.sin())
+    }
 }
 
 // Helper functions
-fn random_range(min: f32, max: f32) -> f32 {
-    let t = std::time::SystemTime::now()
+fn clock_fraction() -> f32 {
+    std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .subsec_nanos() as f32
-        / 1_000_000_000.0;
-    min + (max - min) * t
+        / 1_000_000_000.0
 }
 
-fn random_unit_sphere() -> Vec3 {
-    let t = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .subsec_nanos() as f32;
-    let theta = t * 0.001 % std::f32::consts::TAU;
-    let phi = (t * 0.0001 % 1.0) * std::f32::consts::PI;
-    Vec3::new(phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(system: &ParticleSystem) -> Vec<Vec3> {
+        system.particles().map(|p| p.position).collect()
+    }
+
+    #[test]
+    fn same_seed_emits_the_same_particles() {
+        let run = |seed| {
+            let mut system = ParticleSystem::explosion().with_seed(seed);
+            for _ in 0..10 {
+                system.update(1.0 / 60.0);
+            }
+            positions(&system)
+        };
+        assert_eq!(run(7).len(), 100);
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn restart_replays_from_the_seed() {
+        let mut system = ParticleSystem::fire().with_seed(42);
+        let mut first = Vec::new();
+        for _ in 0..30 {
+            system.update(1.0 / 60.0);
+            first.push(positions(&system));
+        }
+        system.restart();
+        for frame in &first {
+            system.update(1.0 / 60.0);
+            assert_eq!(&positions(&system), frame);
+        }
+    }
+
+    #[test]
+    fn instances_carry_position_size_and_fading_color() {
+        let mut system = ParticleSystem::fire().with_seed(3);
+        system.position = Vec3::new(10.0, 0.0, 0.0);
+        for _ in 0..30 {
+            system.update(1.0 / 60.0);
+        }
+        let mut instances = vec![InstanceData::new(Mat4::IDENTITY); 3];
+        system.fill_instances(&mut instances);
+        assert_eq!(instances.len(), system.alive_count());
+
+        for (instance, particle) in instances.iter().zip(system.particles()) {
+            let (scale, _, translation) = Mat4::from_cols_array_2d(&instance.model_matrix).to_scale_rotation_translation();
+            let expected = particle.size * system.config.size_over_lifetime.sample(particle.age());
+            assert!((scale.x - expected).abs() < 1e-3);
+            assert!(translation.distance(particle.position) < 1e-4);
+            assert_eq!(instance.color, particle.color.to_array());
+        }
+        assert!(instances.iter().all(|i| i.model_matrix[3][0] > 9.0));
+        // Fire fades: older particles are more transparent.
+        let oldest = system.particles().max_by(|a, b| a.age().total_cmp(&b.age())).unwrap();
+        assert!(oldest.color.w < system.config.start_color.w);
+    }
 }
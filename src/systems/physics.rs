@@ -1,5 +1,7 @@
+use crate::scene::ecs::{Entity, World};
 use crate::scene::transform::Transform;
-use glam::{Quat, Vec3};
+use glam::{IVec3, Quat, Vec3};
+use std::collections::{BTreeMap, HashMap};
 
 /// Basic physics body component
 #[derive(Clone, Debug)]
@@ -65,6 +67,17 @@ impl AABB {
         Self { min, max }
     }
 
+    /// Tight box around `points`; `None` when there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut aabb = Self::new(first, first);
+        for point in points {
+            aabb.expand(point);
+        }
+        Some(aabb)
+    }
+
     pub fn from_center_size(center: Vec3, size: Vec3) -> Self {
         let half = size * 0.5;
         Self { min: center - half, max: center + half }
@@ -112,6 +125,17 @@ impl AABB {
         }
     }
 
+    /// World box of this local box under `matrix` (affine). Projects the
+    /// extents onto each axis instead of transforming all eight corners.
+    pub fn transformed_by(&self, matrix: &glam::Mat4) -> AABB {
+        let center = matrix.transform_point3(self.center());
+        let extents = self.extents();
+        let half = matrix.x_axis.truncate().abs() * extents.x
+            + matrix.y_axis.truncate().abs() * extents.y
+            + matrix.z_axis.truncate().abs() * extents.z;
+        AABB::new(center - half, center + half)
+    }
+
     pub fn transformed(&self, transform: &Transform) -> AABB {
         let corners = [
             Vec3::new(self.min.x, self.min.y, self.min.z),
@@ -168,6 +192,344 @@ impl Sphere {
     }
 }
 
+/// Capsule: the segment `a`–`b` swept by `radius`.
+#[derive(Clone, Copy, Debug)]
+pub struct Capsule {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(a: Vec3, b: Vec3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    /// Upright capsule of total `height` (caps included) centred on `center`.
+    pub fn vertical(center: Vec3, height: f32, radius: f32) -> Self {
+        let half = (height * 0.5 - radius).max(0.0);
+        Self::new(center - Vec3::Y * half, center + Vec3::Y * half, radius)
+    }
+
+    /// Point of the inner segment closest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        closest_on_segment(self.a, self.b, point)
+    }
+
+    pub fn aabb(&self) -> AABB {
+        let r = Vec3::splat(self.radius);
+        AABB::new(self.a.min(self.b) - r, self.a.max(self.b) + r)
+    }
+}
+
+/// Collider component. Shapes are centred on the entity's `Transform`
+/// position and scaled by its scale; rotation is ignored (axis-aligned).
+///
+/// Entities with a collider but no `RigidBody` (or a kinematic one) are static.
+#[derive(Clone, Copy, Debug)]
+pub enum Collider {
+    Box { half_extents: Vec3 },
+    Sphere { radius: f32 },
+    /// Upright capsule; `half_height` is half the inner segment (caps excluded).
+    Capsule { half_height: f32, radius: f32 },
+}
+
+impl Collider {
+    pub fn cuboid(size: Vec3) -> Self {
+        Self::Box { half_extents: size * 0.5 }
+    }
+
+    pub fn sphere(radius: f32) -> Self {
+        Self::Sphere { radius }
+    }
+
+    /// Upright capsule of total `height`, caps included.
+    pub fn capsule(height: f32, radius: f32) -> Self {
+        Self::Capsule { half_height: (height * 0.5 - radius).max(0.0), radius }
+    }
+
+    pub fn world_aabb(&self, transform: &Transform) -> AABB {
+        match *self {
+            Self::Box { half_extents } => {
+                AABB::from_center_size(transform.position, half_extents * 2.0 * transform.scale.abs())
+            }
+            Self::Sphere { radius } => {
+                let r = radius * transform.scale.abs().max_element();
+                AABB::from_center_size(transform.position, Vec3::splat(r * 2.0))
+            }
+            Self::Capsule { .. } => Shape::of(self, transform).aabb(),
+        }
+    }
+
+    /// Smallest half-size of the shape, used to bound per-substep motion.
+    fn min_half_size(&self, transform: &Transform) -> f32 {
+        match *self {
+            Self::Box { half_extents } => (half_extents * transform.scale.abs()).min_element(),
+            Self::Sphere { radius } => radius * transform.scale.abs().max_element(),
+            Self::Capsule { radius, .. } => radius * transform.scale.x.abs().max(transform.scale.z.abs()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    Box(AABB),
+    Sphere(Sphere),
+    Capsule(Capsule),
+}
+
+impl Shape {
+    fn of(collider: &Collider, transform: &Transform) -> Self {
+        match *collider {
+            Collider::Box { .. } => Shape::Box(collider.world_aabb(transform)),
+            Collider::Sphere { radius } => {
+                Shape::Sphere(Sphere::new(transform.position, radius * transform.scale.abs().max_element()))
+            }
+            Collider::Capsule { half_height, radius } => {
+                let scale = transform.scale.abs();
+                let half = Vec3::Y * half_height * scale.y;
+                let radius = radius * scale.x.max(scale.z);
+                Shape::Capsule(Capsule::new(transform.position - half, transform.position + half, radius))
+            }
+        }
+    }
+
+    fn aabb(&self) -> AABB {
+        match self {
+            Shape::Box(b) => *b,
+            Shape::Sphere(s) => AABB::from_center_size(s.center, Vec3::splat(s.radius * 2.0)),
+            Shape::Capsule(c) => c.aabb(),
+        }
+    }
+
+    /// Minimum translation that moves `self` out of `other`, if they overlap.
+    fn penetration(&self, other: &Shape) -> Option<Vec3> {
+        match (self, other) {
+            (Shape::Box(a), Shape::Box(b)) => aabb_penetration(a, b),
+            (Shape::Sphere(s), Shape::Box(b)) => sphere_aabb_penetration(s, b),
+            (Shape::Box(a), Shape::Sphere(s)) => sphere_aabb_penetration(s, a).map(|p| -p),
+            (Shape::Sphere(a), Shape::Sphere(b)) => sphere_sphere_penetration(a.center, b.center, a.radius + b.radius),
+            (Shape::Capsule(c), Shape::Box(b)) => capsule_aabb_penetration(c, b),
+            (Shape::Box(b), Shape::Capsule(c)) => capsule_aabb_penetration(c, b).map(|p| -p),
+            (Shape::Sphere(s), Shape::Capsule(c)) => {
+                sphere_sphere_penetration(s.center, c.closest_point(s.center), s.radius + c.radius)
+            }
+            (Shape::Capsule(c), Shape::Sphere(s)) => {
+                sphere_sphere_penetration(c.closest_point(s.center), s.center, s.radius + c.radius)
+            }
+            (Shape::Capsule(a), Shape::Capsule(b)) => {
+                let (pa, pb) = closest_between_segments(a.a, a.b, b.a, b.b);
+                sphere_sphere_penetration(pa, pb, a.radius + b.radius)
+            }
+        }
+    }
+
+    /// First hit of `ray` as `(t, normal)`. A ray starting inside hits at `t = 0`.
+    fn raycast(&self, ray: &Ray) -> Option<(f32, Vec3)> {
+        match self {
+            Shape::Box(b) => ray_aabb(ray, b),
+            Shape::Sphere(s) => ray_sphere(ray, s.center, s.radius),
+            Shape::Capsule(c) => ray_capsule(ray, c),
+        }
+    }
+}
+
+fn aabb_penetration(a: &AABB, b: &AABB) -> Option<Vec3> {
+    if !a.intersects(b) {
+        return None;
+    }
+    let overlap = Vec3::new(
+        (a.max.x - b.min.x).min(b.max.x - a.min.x),
+        (a.max.y - b.min.y).min(b.max.y - a.min.y),
+        (a.max.z - b.min.z).min(b.max.z - a.min.z),
+    );
+    let side = (a.center() - b.center()).signum();
+    Some(if overlap.x < overlap.y && overlap.x < overlap.z {
+        Vec3::new(overlap.x * side.x, 0.0, 0.0)
+    } else if overlap.y <= overlap.z {
+        Vec3::new(0.0, overlap.y * side.y, 0.0)
+    } else {
+        Vec3::new(0.0, 0.0, overlap.z * side.z)
+    })
+}
+
+fn sphere_aabb_penetration(s: &Sphere, b: &AABB) -> Option<Vec3> {
+    if b.contains_point(s.center) {
+        let inner = AABB::from_center_size(s.center, Vec3::splat(s.radius * 2.0));
+        return aabb_penetration(&inner, b);
+    }
+    let closest = s.center.clamp(b.min, b.max);
+    let delta = s.center - closest;
+    let dist = delta.length();
+    if dist >= s.radius {
+        return None;
+    }
+    Some(delta / dist * (s.radius - dist))
+}
+
+/// Push that separates two spheres of summed radius `radius` centred on `a` and `b`.
+fn sphere_sphere_penetration(a: Vec3, b: Vec3, radius: f32) -> Option<Vec3> {
+    let delta = a - b;
+    let dist = delta.length();
+    let depth = radius - dist;
+    if depth <= 0.0 {
+        return None;
+    }
+    let normal = if dist > 1e-6 { delta / dist } else { Vec3::Y };
+    Some(normal * depth)
+}
+
+/// The distance from a point on the segment to a box is convex along the
+/// segment, so a ternary search finds the closest point; from there it is a
+/// sphere–box test. A segment that crosses the box falls back to box–box.
+fn capsule_aabb_penetration(c: &Capsule, b: &AABB) -> Option<Vec3> {
+    let distance = |t: f32| {
+        let p = c.a.lerp(c.b, t);
+        (p - p.clamp(b.min, b.max)).length_squared()
+    };
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..24 {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if distance(m1) <= distance(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    let center = c.a.lerp(c.b, (lo + hi) * 0.5);
+    if b.contains_point(center) {
+        return aabb_penetration(&c.aabb(), b);
+    }
+    sphere_aabb_penetration(&Sphere::new(center, c.radius), b)
+}
+
+fn closest_on_segment(a: Vec3, b: Vec3, point: Vec3) -> Vec3 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq < 1e-12 {
+        return a;
+    }
+    a + ab * ((point - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+}
+
+/// Closest points between segments `p1`–`q1` and `p2`–`q2` (Ericson, RTCD 5.1.9).
+fn closest_between_segments(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> (Vec3, Vec3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+    if a < 1e-12 && e < 1e-12 {
+        return (p1, p2);
+    }
+    let (s, t) = if a < 1e-12 {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e < 1e-12 {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > 1e-12 { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+fn ray_aabb(ray: &Ray, b: &AABB) -> Option<(f32, Vec3)> {
+    if b.contains_point(ray.origin) {
+        return Some((0.0, -ray.direction));
+    }
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    let mut normal = Vec3::ZERO;
+    for axis in 0..3 {
+        let origin = ray.origin[axis];
+        let dir = ray.direction[axis];
+        if dir.abs() < 1e-12 {
+            if origin < b.min[axis] || origin > b.max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (b.min[axis] - origin) / dir;
+        let t2 = (b.max[axis] - origin) / dir;
+        let (near, far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+        if near > t_enter {
+            t_enter = near;
+            normal = Vec3::ZERO;
+            normal[axis] = -dir.signum();
+        }
+        t_exit = t_exit.min(far);
+    }
+    (t_enter <= t_exit && t_enter >= 0.0).then_some((t_enter, normal))
+}
+
+fn ray_sphere(ray: &Ray, center: Vec3, radius: f32) -> Option<(f32, Vec3)> {
+    let oc = ray.origin - center;
+    let c = oc.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some((0.0, -ray.direction));
+    }
+    let b = oc.dot(ray.direction);
+    let discriminant = b * b - c;
+    if b > 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let t = -b - discriminant.sqrt();
+    Some((t, (ray.point_at(t) - center) / radius))
+}
+
+/// Side of the cylinder first, then both end caps; the nearest wins.
+fn ray_capsule(ray: &Ray, c: &Capsule) -> Option<(f32, Vec3)> {
+    if (ray.origin - c.closest_point(ray.origin)).length_squared() <= c.radius * c.radius {
+        return Some((0.0, -ray.direction));
+    }
+    let axis = c.b - c.a;
+    let len_sq = axis.length_squared();
+    let mut best: Option<(f32, Vec3)> = None;
+    let mut keep = |hit: Option<(f32, Vec3)>| {
+        if let Some(hit) = hit {
+            if best.map_or(true, |b| hit.0 < b.0) {
+                best = Some(hit);
+            }
+        }
+    };
+    if len_sq > 1e-12 {
+        // Project out the axis: a 2D circle test in the plane normal to it.
+        let oa = ray.origin - c.a;
+        let d = ray.direction - axis * (ray.direction.dot(axis) / len_sq);
+        let o = oa - axis * (oa.dot(axis) / len_sq);
+        let qa = d.length_squared();
+        let qb = o.dot(d);
+        let qc = o.length_squared() - c.radius * c.radius;
+        let discriminant = qb * qb - qa * qc;
+        if qa > 1e-12 && discriminant >= 0.0 {
+            let t = (-qb - discriminant.sqrt()) / qa;
+            let along = (ray.point_at(t) - c.a).dot(axis) / len_sq;
+            if t >= 0.0 && (0.0..=1.0).contains(&along) {
+                let p = ray.point_at(t);
+                keep(Some((t, (p - (c.a + axis * along)) / c.radius)));
+            }
+        }
+    }
+    keep(ray_sphere(ray, c.a, c.radius));
+    keep(ray_sphere(ray, c.b, c.radius));
+    best
+}
+
 /// Ray for raycasting
 #[derive(Clone, Copy, Debug)]
 pub struct Ray {
@@ -187,8 +549,10 @@ impl Ray {
         screen_height: f32,
         inv_view_proj: glam::Mat4,
     ) -> Self {
+        // `Camera::projection_matrix` already flips Y for Vulkan, so NDC y
+        // grows downwards like screen y.
         let ndc_x = (2.0 * screen_x / screen_width) - 1.0;
-        let ndc_y = 1.0 - (2.0 * screen_y / screen_height); // Flip Y for Vulkan
+        let ndc_y = (2.0 * screen_y / screen_height) - 1.0;
 
         let near = inv_view_proj.project_point3(Vec3::new(ndc_x, ndc_y, 0.0));
         let far = inv_view_proj.project_point3(Vec3::new(ndc_x, ndc_y, 1.0));
@@ -258,11 +622,182 @@ impl Ray {
     }
 }
 
-/// Simple physics world
+/// Surface response of a static collider. Also usable as a component next
+/// to an entity's `Collider`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsMaterial {
+    /// Fraction of the normal speed kept after an impact, 0..=1.
+    pub restitution: f32,
+    /// Tangential speed lost per unit of normal speed absorbed (Coulomb-style).
+    pub friction: f32,
+}
+
+impl PhysicsMaterial {
+    pub fn new(restitution: f32, friction: f32) -> Self {
+        Self { restitution, friction }
+    }
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self { restitution: 0.0, friction: 0.5 }
+    }
+}
+
+/// Impacts slower than this (m/s) don't bounce, so resting contacts settle.
+const RESTITUTION_THRESHOLD: f32 = 1.0;
+
+/// Removes the part of `velocity` going into a surface with `normal`, then
+/// applies restitution and friction.
+fn apply_contact(velocity: &mut Vec3, normal: Vec3, material: PhysicsMaterial) {
+    let into = velocity.dot(normal);
+    if into >= 0.0 {
+        return;
+    }
+    let tangent = *velocity - normal * into;
+    let tangent_speed = tangent.length();
+    let kept = if tangent_speed > 1e-6 {
+        (1.0 - material.friction * -into / tangent_speed).max(0.0)
+    } else {
+        0.0
+    };
+    let bounce = if -into > RESTITUTION_THRESHOLD { -into * material.restitution } else { 0.0 };
+    *velocity = tangent * kept + normal * bounce;
+}
+
+/// Stable id of a collider registered in a `PhysicsWorld`; never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ColliderId(u32);
+
+impl ColliderId {
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Result of [`PhysicsWorld::raycast`].
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    pub collider_id: ColliderId,
+    /// Distance along the (normalized) ray.
+    pub t: f32,
+    pub normal: Vec3,
+}
+
+struct StaticCollider {
+    shape: Shape,
+    material: PhysicsMaterial,
+}
+
+const BROADPHASE_CELL_SIZE: f32 = 4.0;
+/// Colliders covering more cells than this skip the grid and are always tested.
+const MAX_COLLIDER_CELLS: i64 = 512;
+
+/// Uniform grid over the registered colliders. Each collider is listed in
+/// every cell its bounds touch.
+#[derive(Default)]
+struct Broadphase {
+    cells: HashMap<IVec3, Vec<ColliderId>>,
+    oversized: Vec<ColliderId>,
+}
+
+impl Broadphase {
+    fn cell_of(point: Vec3) -> IVec3 {
+        (point / BROADPHASE_CELL_SIZE).floor().as_ivec3()
+    }
+
+    /// Cell range of `aabb`, or `None` when it is too large for the grid.
+    fn cell_range(aabb: &AABB) -> Option<(IVec3, IVec3)> {
+        let lo = Self::cell_of(aabb.min);
+        let hi = Self::cell_of(aabb.max);
+        let span = (hi - lo + IVec3::ONE).as_i64vec3();
+        (span.x * span.y * span.z <= MAX_COLLIDER_CELLS).then_some((lo, hi))
+    }
+
+    fn for_each_cell(lo: IVec3, hi: IVec3, mut f: impl FnMut(IVec3)) {
+        for x in lo.x..=hi.x {
+            for y in lo.y..=hi.y {
+                for z in lo.z..=hi.z {
+                    f(IVec3::new(x, y, z));
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, id: ColliderId, aabb: &AABB) {
+        match Self::cell_range(aabb) {
+            Some((lo, hi)) => Self::for_each_cell(lo, hi, |cell| self.cells.entry(cell).or_default().push(id)),
+            None => self.oversized.push(id),
+        }
+    }
+
+    fn remove(&mut self, id: ColliderId, aabb: &AABB) {
+        match Self::cell_range(aabb) {
+            Some((lo, hi)) => Self::for_each_cell(lo, hi, |cell| {
+                if let Some(ids) = self.cells.get_mut(&cell) {
+                    ids.retain(|other| *other != id);
+                    if ids.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }),
+            None => self.oversized.retain(|other| *other != id),
+        }
+    }
+
+    /// Ids of the colliders that may overlap `aabb`, sorted and unique.
+    fn query(&self, aabb: &AABB, out: &mut Vec<ColliderId>) {
+        out.clear();
+        out.extend_from_slice(&self.oversized);
+        match Self::cell_range(aabb) {
+            Some((lo, hi)) => Self::for_each_cell(lo, hi, |cell| {
+                if let Some(ids) = self.cells.get(&cell) {
+                    out.extend_from_slice(ids);
+                }
+            }),
+            None => self.cells.values().for_each(|ids| out.extend_from_slice(ids)),
+        }
+        out.sort_unstable();
+        out.dedup();
+    }
+}
+
+/// Entry and exit distance of `ray` through `b`, clamped to the ray's start.
+fn ray_span(ray: &Ray, b: &AABB) -> Option<(f32, f32)> {
+    let mut enter = 0.0f32;
+    let mut exit = f32::INFINITY;
+    for axis in 0..3 {
+        let origin = ray.origin[axis];
+        let dir = ray.direction[axis];
+        if dir.abs() < 1e-12 {
+            if origin < b.min[axis] || origin > b.max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (b.min[axis] - origin) / dir;
+        let t2 = (b.max[axis] - origin) / dir;
+        enter = enter.max(t1.min(t2));
+        exit = exit.min(t1.max(t2));
+    }
+    (enter <= exit).then_some((enter, exit))
+}
+
+/// Simple physics world.
+///
+/// Bodies are ECS entities (`Transform` + `RigidBody` + `Collider`). Static
+/// geometry can also be registered directly with `add_static_aabb`,
+/// `add_static_sphere` and `add_capsule`; those colliders live in a uniform
+/// grid broadphase and are what [`raycast`](Self::raycast) and
+/// [`CharacterController::move_and_collide`] test against.
 pub struct PhysicsWorld {
     pub gravity: Vec3,
     pub fixed_timestep: f32,
     accumulator: f32,
+    colliders: BTreeMap<ColliderId, StaticCollider>,
+    broadphase: Broadphase,
+    bounds: Option<AABB>,
+    next_collider_id: u32,
 }
 
 impl PhysicsWorld {
@@ -271,9 +806,176 @@ impl PhysicsWorld {
             gravity: Vec3::new(0.0, -9.81, 0.0),
             fixed_timestep: 1.0 / 60.0,
             accumulator: 0.0,
+            colliders: BTreeMap::new(),
+            broadphase: Broadphase::default(),
+            bounds: None,
+            next_collider_id: 0,
         }
     }
 
+    pub fn add_static_aabb(&mut self, aabb: AABB) -> ColliderId {
+        self.add_collider(Shape::Box(aabb))
+    }
+
+    pub fn add_static_sphere(&mut self, sphere: Sphere) -> ColliderId {
+        self.add_collider(Shape::Sphere(sphere))
+    }
+
+    /// Static capsule (a pillar, a railing...). Characters are capsules too,
+    /// but move through [`CharacterController`].
+    pub fn add_capsule(&mut self, capsule: Capsule) -> ColliderId {
+        self.add_collider(Shape::Capsule(capsule))
+    }
+
+    fn add_collider(&mut self, shape: Shape) -> ColliderId {
+        let id = ColliderId(self.next_collider_id);
+        self.next_collider_id += 1;
+        let aabb = shape.aabb();
+        self.broadphase.insert(id, &aabb);
+        self.bounds = Some(self.bounds.map_or(aabb, |b| b.merge(&aabb)));
+        self.colliders.insert(id, StaticCollider { shape, material: PhysicsMaterial::default() });
+        id
+    }
+
+    pub fn remove_collider(&mut self, id: ColliderId) -> bool {
+        let Some(collider) = self.colliders.remove(&id) else { return false };
+        self.broadphase.remove(id, &collider.shape.aabb());
+        self.bounds = self.colliders.values().map(|c| c.shape.aabb()).reduce(|a, b| a.merge(&b));
+        true
+    }
+
+    pub fn set_collider_material(&mut self, id: ColliderId, material: PhysicsMaterial) -> bool {
+        let Some(collider) = self.colliders.get_mut(&id) else { return false };
+        collider.material = material;
+        true
+    }
+
+    pub fn collider_count(&self) -> usize {
+        self.colliders.len()
+    }
+
+    /// Nearest registered collider along `ray` within `max_dist`. Walks the
+    /// broadphase cells the ray crosses and stops at the first cell boundary
+    /// past the best hit. ECS `Collider` entities are not included.
+    pub fn raycast(&self, ray: &Ray, max_dist: f32) -> Option<RayHit> {
+        let mut best = None;
+        for id in &self.broadphase.oversized {
+            self.ray_test(*id, ray, max_dist, &mut best);
+        }
+        let (enter, exit) = ray_span(ray, self.bounds.as_ref()?)?;
+        let end = exit.min(max_dist);
+        if enter > end {
+            return best;
+        }
+
+        let start = ray.point_at(enter);
+        let mut cell = Broadphase::cell_of(start);
+        let mut step = IVec3::ZERO;
+        let mut t_max = Vec3::INFINITY;
+        let mut t_delta = Vec3::INFINITY;
+        for axis in 0..3 {
+            let dir = ray.direction[axis];
+            if dir.abs() < 1e-12 {
+                continue;
+            }
+            step[axis] = if dir > 0.0 { 1 } else { -1 };
+            let boundary = (cell[axis] + (dir > 0.0) as i32) as f32 * BROADPHASE_CELL_SIZE;
+            t_max[axis] = enter + (boundary - start[axis]) / dir;
+            t_delta[axis] = BROADPHASE_CELL_SIZE / dir.abs();
+        }
+
+        loop {
+            if let Some(ids) = self.broadphase.cells.get(&cell) {
+                for id in ids {
+                    self.ray_test(*id, ray, max_dist, &mut best);
+                }
+            }
+            let next = t_max.min_element();
+            if next > end || best.is_some_and(|hit: RayHit| hit.t <= next) {
+                return best;
+            }
+            let axis = if t_max.x == next { 0 } else if t_max.y == next { 1 } else { 2 };
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+        }
+    }
+
+    fn ray_test(&self, id: ColliderId, ray: &Ray, max_dist: f32, best: &mut Option<RayHit>) {
+        let Some((t, normal)) = self.colliders[&id].shape.raycast(ray) else { return };
+        if t <= max_dist && best.map_or(true, |hit| t < hit.t) {
+            *best = Some(RayHit { collider_id: id, t, normal });
+        }
+    }
+
+    /// Pushes the shape `shape_at(position)` out of every registered collider
+    /// it overlaps, moving `position`. `on_contact` gets each push, the
+    /// collider's material and the position after the push.
+    fn resolve_registered(
+        &self,
+        position: &mut Vec3,
+        shape_at: impl Fn(Vec3) -> Shape,
+        mut on_contact: impl FnMut(Vec3, PhysicsMaterial, Vec3),
+    ) {
+        if self.colliders.is_empty() {
+            return;
+        }
+        let mut candidates = Vec::new();
+        self.broadphase.query(&shape_at(*position).aabb(), &mut candidates);
+        for id in candidates {
+            let collider = &self.colliders[&id];
+            if let Some(push) = shape_at(*position).penetration(&collider.shape) {
+                *position += push;
+                on_contact(push, collider.material, *position);
+            }
+        }
+    }
+
+    /// Largest push out of the registered colliders for `shape`, if it overlaps any.
+    fn deepest_contact(&self, shape: &Shape) -> Option<Vec3> {
+        let mut candidates = Vec::new();
+        self.broadphase.query(&shape.aabb(), &mut candidates);
+        candidates
+            .iter()
+            .filter_map(|id| shape.penetration(&self.colliders[id].shape))
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+    }
+
+    /// Shape cast: moves `shape_at(from)` along the unit `dir` for up to
+    /// `max_dist` and returns how far it gets before touching a registered
+    /// collider, with the contact normal. Marches in increments of `step`
+    /// (keep it under the shape's thickness) and bisects the first overlap.
+    fn sweep(
+        &self,
+        shape_at: impl Fn(Vec3) -> Shape,
+        from: Vec3,
+        dir: Vec3,
+        max_dist: f32,
+        step: f32,
+    ) -> Option<(f32, Vec3)> {
+        if let Some(push) = self.deepest_contact(&shape_at(from)) {
+            return Some((0.0, push.normalize_or_zero()));
+        }
+        let step = step.max(1e-3);
+        let mut free = 0.0;
+        while free < max_dist {
+            let mut hit = (free + step).min(max_dist);
+            if self.deepest_contact(&shape_at(from + dir * hit)).is_some() {
+                for _ in 0..16 {
+                    let mid = (free + hit) * 0.5;
+                    if self.deepest_contact(&shape_at(from + dir * mid)).is_some() {
+                        hit = mid;
+                    } else {
+                        free = mid;
+                    }
+                }
+                let push = self.deepest_contact(&shape_at(from + dir * hit))?;
+                return Some((free, push.normalize_or_zero()));
+            }
+            free = hit;
+        }
+        None
+    }
+
     pub fn step(&mut self, delta_time: f32) -> u32 {
         self.accumulator += delta_time;
         let mut steps = 0;
@@ -287,18 +989,20 @@ impl PhysicsWorld {
     }
 
     pub fn integrate(&self, transform: &mut Transform, body: &mut RigidBody) {
+        self.integrate_dt(transform, body, self.fixed_timestep);
+    }
+
+    fn integrate_dt(&self, transform: &mut Transform, body: &mut RigidBody, dt: f32) {
         if body.is_kinematic {
             return;
         }
 
-        let dt = self.fixed_timestep;
-
         // Apply gravity
         body.velocity += self.gravity * body.gravity_scale * dt;
 
         // Apply drag
-        body.velocity *= 1.0 - body.drag * dt;
-        body.angular_velocity *= 1.0 - body.angular_drag * dt;
+        body.velocity *= (1.0 - body.drag * dt).max(0.0);
+        body.angular_velocity *= (1.0 - body.angular_drag * dt).max(0.0);
 
         // Integrate position
         transform.position += body.velocity * dt;
@@ -314,6 +1018,82 @@ impl PhysicsWorld {
     pub fn interpolation_alpha(&self) -> f32 {
         self.accumulator / self.fixed_timestep
     }
+
+    /// Accumulates `delta_time` and runs as many fixed steps of [`simulate`](Self::simulate)
+    /// as fit. Returns the number of steps taken.
+    pub fn step_world(&mut self, world: &mut World, delta_time: f32) -> u32 {
+        let steps = self.step(delta_time);
+        for _ in 0..steps {
+            self.simulate(world, self.fixed_timestep);
+        }
+        steps
+    }
+
+    /// Advances every entity with `Transform` + non-kinematic `RigidBody` by `dt`:
+    /// gravity, drag, integration, and push-out against static colliders (ECS
+    /// ones and registered ones) with their `PhysicsMaterial`.
+    ///
+    /// Fast bodies are sub-stepped so they never move more than half their
+    /// smallest extent per substep, which keeps them from tunnelling through
+    /// thin static colliders.
+    pub fn simulate(&self, world: &mut World, dt: f32) {
+        crate::profile_scope!("physics_step");
+        let statics: Vec<(Shape, PhysicsMaterial)> = world
+            .query::<Collider>()
+            .filter(|(e, _)| world.get_component::<RigidBody>(*e).map_or(true, |b| b.is_kinematic))
+            .filter_map(|(e, c)| {
+                let material = world.get_component::<PhysicsMaterial>(e).copied().unwrap_or_default();
+                world.get_component::<Transform>(e).map(|t| (Shape::of(c, t), material))
+            })
+            .collect();
+
+        let dynamic: Vec<Entity> = world
+            .query::<RigidBody>()
+            .filter(|(e, b)| !b.is_kinematic && world.has_component::<Transform>(*e))
+            .map(|(e, _)| e)
+            .collect();
+
+        for entity in dynamic {
+            let collider = world.get_component::<Collider>(entity).copied();
+            let Some(mut transform) = world.get_component::<Transform>(entity).copied() else { continue };
+            let Some(mut body) = world.get_component::<RigidBody>(entity).cloned() else { continue };
+
+            let predicted = (body.velocity + self.gravity * body.gravity_scale * dt).length() * dt;
+            let substeps = match collider {
+                Some(c) => {
+                    let limit = (c.min_half_size(&transform) * 0.5).max(1e-4);
+                    ((predicted / limit).ceil() as u32).clamp(1, 16)
+                }
+                None => 1,
+            };
+            let sub_dt = dt / substeps as f32;
+
+            for _ in 0..substeps {
+                self.integrate_dt(&mut transform, &mut body, sub_dt);
+                let Some(collider) = collider else { continue };
+                for (other, material) in &statics {
+                    let shape = Shape::of(&collider, &transform);
+                    if let Some(push) = shape.penetration(other) {
+                        transform.position += push;
+                        apply_contact(&mut body.velocity, push.normalize_or_zero(), *material);
+                    }
+                }
+                let base = transform;
+                self.resolve_registered(
+                    &mut transform.position,
+                    |position| Shape::of(&collider, &Transform { position, ..base }),
+                    |push, material, _| apply_contact(&mut body.velocity, push.normalize_or_zero(), material),
+                );
+            }
+
+            if let Some(t) = world.get_component_mut::<Transform>(entity) {
+                *t = transform;
+            }
+            if let Some(b) = world.get_component_mut::<RigidBody>(entity) {
+                *b = body;
+            }
+        }
+    }
 }
 
 impl Default for PhysicsWorld {
@@ -326,6 +1106,9 @@ impl Default for PhysicsWorld {
 // Character Controller — FPS-style movement with physics
 // =============================================================================
 
+/// Depenetration passes per `move_and_collide`; corners need more than one.
+const MAX_DEPENETRATION_PASSES: usize = 4;
+
 /// Character controller for FPS-style movement with gravity and collision
 #[derive(Clone, Debug)]
 pub struct CharacterController {
@@ -340,6 +1123,10 @@ pub struct CharacterController {
     pub air_drag: f32,
     pub is_grounded: bool,
     pub ground_check_distance: f32,
+    /// Steepest walkable surface for `move_and_collide`, in degrees.
+    pub max_slope_deg: f32,
+    /// Tallest ledge `move_and_collide` climbs without jumping.
+    pub step_height: f32,
 }
 
 impl Default for CharacterController {
@@ -356,6 +1143,8 @@ impl Default for CharacterController {
             air_drag: 0.1,
             is_grounded: false,
             ground_check_distance: 0.1,
+            max_slope_deg: 45.0,
+            step_height: 0.35,
         }
     }
 }
@@ -382,6 +1171,130 @@ impl CharacterController {
             }
         }
 
+        self.steer(dt, move_input, jump);
+
+        // Apply velocity
+        self.position += self.velocity * dt;
+    }
+
+    /// Like [`update`](Self::update), but the body is a vertical capsule
+    /// resolved against the colliders registered in `physics` instead of a
+    /// flat `ground_y`: it slides along walls, walks slopes up to
+    /// `max_slope_deg` and climbs ledges up to `step_height`.
+    pub fn move_and_collide(&mut self, dt: f32, move_input: Vec3, jump: bool, physics: &PhysicsWorld) {
+        let (height, radius) = (self.height, self.radius);
+        let shape_at = move |position| Shape::Capsule(Capsule::vertical(position, height, radius));
+        let min_ground_y = self.max_slope_deg.to_radians().cos();
+        let cast_step = radius * 0.5;
+
+        // Ground check: sweep the capsule down. While grounded it reaches a
+        // step further, so the character sticks to ramps and stairs going down.
+        let reach = self.ground_check_distance + if self.is_grounded { self.step_height } else { 0.0 };
+        let ground = physics
+            .sweep(shape_at, self.position, -Vec3::Y, reach, cast_step)
+            .map(|(distance, normal)| {
+                let resting = self.position - Vec3::Y * distance;
+                (distance, Self::support_normal(physics, resting, normal, height, radius))
+            });
+        match ground {
+            Some((distance, normal)) if self.velocity.y <= 0.0 && normal.y >= min_ground_y => {
+                self.is_grounded = true;
+                self.velocity.y = 0.0;
+                self.position.y -= distance;
+            }
+            _ => {
+                self.is_grounded = false;
+                self.velocity.y -= self.gravity * dt;
+            }
+        }
+
+        self.steer(dt, move_input, jump);
+        let start = self.position;
+        let velocity = self.velocity;
+        let motion = velocity * dt;
+        self.position += motion;
+        let blocked = self.depenetrate(physics, shape_at, min_ground_y);
+
+        // Step up: retry the horizontal move `step_height` higher and settle
+        // back down. The ledge's top is checked with a ray just past the
+        // capsule's front, since the capsule itself first lands on the edge.
+        if blocked && self.is_grounded && self.step_height > 0.0 {
+            let raised = start + Vec3::Y * self.step_height;
+            let ahead = raised + Vec3::new(motion.x, 0.0, motion.z);
+            let forward = Vec3::new(motion.x, 0.0, motion.z).normalize_or_zero();
+            let feet = ahead + forward * (radius + 0.01) - Vec3::Y * (height * 0.5);
+            let ledge = physics.raycast(&Ray::new(feet, -Vec3::Y), self.step_height);
+            let clear = physics.deepest_contact(&shape_at(raised)).is_none()
+                && physics.deepest_contact(&shape_at(ahead)).is_none();
+            if clear && ledge.is_some_and(|hit| hit.normal.y >= min_ground_y) {
+                if let Some((distance, _)) = physics.sweep(shape_at, ahead, -Vec3::Y, self.step_height, cast_step) {
+                    self.position = ahead - Vec3::Y * distance;
+                    self.velocity = Vec3::new(velocity.x, 0.0, velocity.z);
+                }
+            }
+        }
+    }
+
+    /// Iterative push-out of the capsule, removing the velocity that goes into
+    /// each wall. Returns whether a wall (too steep to walk, not a ceiling)
+    /// was hit.
+    fn depenetrate(&mut self, physics: &PhysicsWorld, shape_at: impl Fn(Vec3) -> Shape + Copy, min_ground_y: f32) -> bool {
+        let (height, radius) = (self.height, self.radius);
+        let mut blocked = false;
+        for _ in 0..MAX_DEPENETRATION_PASSES {
+            let mut touched = false;
+            let mut landed = false;
+            let mut velocity = self.velocity;
+            physics.resolve_registered(&mut self.position, shape_at, |push, _, position| {
+                touched = true;
+                let normal = push.normalize_or_zero();
+                if Self::support_normal(physics, position, normal, height, radius).y >= min_ground_y {
+                    // Ground: only stop falling, keep the walking speed on slopes.
+                    landed = true;
+                    velocity.y = velocity.y.max(0.0);
+                    return;
+                }
+                // Slide along walls horizontally: a steep slope must not be climbable.
+                let normal = if normal.y > 0.0 {
+                    blocked = true;
+                    Vec3::new(normal.x, 0.0, normal.z).normalize_or_zero()
+                } else {
+                    blocked |= normal.y > -min_ground_y;
+                    normal
+                };
+                let into = velocity.dot(normal);
+                if into < 0.0 {
+                    velocity -= normal * into;
+                }
+            });
+            self.velocity = velocity;
+            self.is_grounded |= landed;
+            if !touched {
+                break;
+            }
+        }
+        blocked
+    }
+
+    /// Normal of the surface under a contact of the capsule at `position`.
+    /// The round bottom touches a ledge's edge with a tilted normal; a short
+    /// ray just inside the contact finds the face actually underneath. Side
+    /// contacts (the normal barely points up) keep their own normal so that
+    /// ledges still block and go through the step-up.
+    fn support_normal(physics: &PhysicsWorld, position: Vec3, normal: Vec3, height: f32, radius: f32) -> Vec3 {
+        if normal.y <= 0.1 {
+            return normal;
+        }
+        let bottom = position - Vec3::Y * (height * 0.5 - radius).max(0.0);
+        let inward = -Vec3::new(normal.x, 0.0, normal.z).normalize_or_zero() * 0.01;
+        let contact = bottom - normal * radius + inward;
+        physics
+            .raycast(&Ray::new(contact + Vec3::Y * 0.05, -Vec3::Y), 0.1)
+            .map_or(normal, |hit| hit.normal)
+    }
+
+    /// Jump and horizontal acceleration, shared by both update paths.
+    fn steer(&mut self, dt: f32, move_input: Vec3, jump: bool) {
         // Jump
         if jump && self.is_grounded {
             self.velocity.y = self.jump_force;
@@ -404,9 +1317,6 @@ impl CharacterController {
             self.velocity.x = lerp(self.velocity.x, 0.0, self.ground_drag * dt);
             self.velocity.z = lerp(self.velocity.z, 0.0, self.ground_drag * dt);
         }
-
-        // Apply velocity
-        self.position += self.velocity * dt;
     }
 
     /// Get the eye position (for camera)
@@ -484,3 +1394,232 @@ impl CharacterController {
 fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t.clamp(0.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ground(world: &mut World) {
+        let floor = world.create_entity();
+        world.add_component(floor, Transform::from_position(Vec3::new(0.0, -0.5, 0.0)));
+        world.add_component(floor, Collider::cuboid(Vec3::new(100.0, 1.0, 100.0)));
+    }
+
+    #[test]
+    fn transformed_bounds_cover_all_eight_corners() {
+        let local = AABB::new(Vec3::new(-1.0, 0.0, -2.0), Vec3::new(1.0, 3.0, 2.0));
+        let matrix = glam::Mat4::from_scale_rotation_translation(
+            Vec3::new(2.0, 1.0, 0.5),
+            Quat::from_euler(glam::EulerRot::XYZ, 0.4, 1.1, -0.7),
+            Vec3::new(5.0, -3.0, 1.0),
+        );
+        let corners = (0..8).map(|i| {
+            let pick = |bit: usize, min: f32, max: f32| if i & bit == 0 { min } else { max };
+            matrix.transform_point3(Vec3::new(
+                pick(1, local.min.x, local.max.x),
+                pick(2, local.min.y, local.max.y),
+                pick(4, local.min.z, local.max.z),
+            ))
+        });
+        let expected = AABB::from_points(corners).unwrap();
+        let world = local.transformed_by(&matrix);
+        assert!(world.min.abs_diff_eq(expected.min, 1e-4), "{:?} vs {:?}", world.min, expected.min);
+        assert!(world.max.abs_diff_eq(expected.max, 1e-4), "{:?} vs {:?}", world.max, expected.max);
+    }
+
+    #[test]
+    fn falling_cubes_come_to_rest_on_ground() {
+        let mut world = World::new();
+        ground(&mut world);
+        let cubes: Vec<Entity> = (0..50)
+            .map(|i| {
+                let e = world.create_entity();
+                let x = (i % 10) as f32 * 2.0 - 9.0;
+                let z = (i / 10) as f32 * 2.0 - 5.0;
+                world.add_component(e, Transform::from_position(Vec3::new(x, 5.0 + i as f32, z)));
+                world.add_component(e, RigidBody::default());
+                world.add_component(e, Collider::cuboid(Vec3::ONE));
+                e
+            })
+            .collect();
+
+        let physics = PhysicsWorld::new();
+        for _ in 0..600 {
+            physics.simulate(&mut world, 1.0 / 60.0);
+        }
+
+        for e in cubes {
+            let t = world.get_component::<Transform>(e).unwrap();
+            let b = world.get_component::<RigidBody>(e).unwrap();
+            assert!((t.position.y - 0.5).abs() < 0.05, "cube at y={}", t.position.y);
+            assert!(b.velocity.y.abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn fast_sphere_does_not_tunnel_through_thin_floor() {
+        let mut world = World::new();
+        let floor = world.create_entity();
+        world.add_component(floor, Transform::new());
+        world.add_component(floor, Collider::cuboid(Vec3::new(10.0, 0.05, 10.0)));
+
+        let ball = world.create_entity();
+        world.add_component(ball, Transform::from_position(Vec3::new(0.0, 2.0, 0.0)));
+        world.add_component(ball, RigidBody { velocity: Vec3::new(0.0, -60.0, 0.0), ..Default::default() });
+        world.add_component(ball, Collider::sphere(0.25));
+
+        let physics = PhysicsWorld::new();
+        for _ in 0..30 {
+            physics.simulate(&mut world, 1.0 / 60.0);
+        }
+        assert!(world.get_component::<Transform>(ball).unwrap().position.y > 0.0);
+    }
+
+    #[test]
+    fn transformed_box_matches_transformed_corners() {
+        let local = AABB::new(Vec3::new(-1.0, -0.5, -2.0), Vec3::new(1.0, 0.5, 2.0));
+        let t = Transform {
+            position: Vec3::new(3.0, 1.0, -4.0),
+            rotation: Quat::from_rotation_y(0.7) * Quat::from_rotation_x(0.3),
+            scale: Vec3::new(2.0, 1.0, 0.5),
+        };
+        let fast = local.transformed_by(&t.matrix());
+        let slow = local.transformed(&t);
+        assert!((fast.min - slow.min).length() < 1e-4);
+        assert!((fast.max - slow.max).length() < 1e-4);
+        assert!(AABB::from_points(std::iter::empty()).is_none());
+    }
+
+    #[test]
+    fn screen_ray_hits_what_the_camera_shows() {
+        let camera = crate::scene::camera::Camera::perspective(60.0, 1.0, 0.1, 100.0)
+            .look_at(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let inv_vp = camera.view_projection_matrix().inverse();
+        let upper = AABB::from_center_size(Vec3::new(0.0, 3.0, 0.0), Vec3::ONE);
+
+        // Upper part of the screen (small y) looks up in the world.
+        let top = Ray::from_screen(50.0, 10.0, 100.0, 100.0, inv_vp);
+        assert!(top.direction.y > 0.0);
+        let at_box = camera.view_projection_matrix().project_point3(upper.center());
+        let px = (at_box.x + 1.0) * 50.0;
+        let py = (at_box.y + 1.0) * 50.0;
+        let ray = Ray::from_screen(px, py, 100.0, 100.0, inv_vp);
+        let t = ray.intersects_aabb(&upper).expect("ray through the box's pixel must hit it");
+        assert!(upper.contains_point(ray.point_at(t + 1e-3)), "t = {}", t);
+    }
+
+    #[test]
+    fn raycast_returns_nearest_registered_collider() {
+        let mut physics = PhysicsWorld::new();
+        let wall = physics.add_static_aabb(AABB::from_center_size(Vec3::new(0.0, 0.0, -20.0), Vec3::new(4.0, 4.0, 1.0)));
+        let ball = physics.add_static_sphere(Sphere::new(Vec3::new(0.0, 0.0, -9.0), 1.0));
+        physics.add_capsule(Capsule::vertical(Vec3::new(30.0, 0.0, 0.0), 2.0, 0.5));
+        let ray = Ray::new(Vec3::ZERO, -Vec3::Z);
+
+        let hit = physics.raycast(&ray, 100.0).unwrap();
+        assert_eq!(hit.collider_id, ball);
+        assert!((hit.t - 8.0).abs() < 1e-4, "t = {}", hit.t);
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-4));
+        assert!(physics.raycast(&ray, 5.0).is_none());
+
+        assert!(physics.remove_collider(ball));
+        let hit = physics.raycast(&ray, 100.0).unwrap();
+        assert_eq!(hit.collider_id, wall);
+        assert!((hit.t - 19.5).abs() < 1e-4);
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-4));
+
+        let side = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::X);
+        let hit = physics.raycast(&side, f32::INFINITY).unwrap();
+        assert!((hit.t - 29.5).abs() < 1e-3 && hit.normal.abs_diff_eq(-Vec3::X, 1e-3), "{:?}", hit);
+    }
+
+    #[test]
+    fn bouncy_floor_reflects_falling_sphere() {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_static_aabb(AABB::new(Vec3::new(-10.0, -1.0, -10.0), Vec3::new(10.0, 0.0, 10.0)));
+        physics.set_collider_material(floor, PhysicsMaterial::new(0.8, 0.0));
+
+        let mut world = World::new();
+        let ball = world.create_entity();
+        world.add_component(ball, Transform::from_position(Vec3::new(0.0, 3.0, 0.0)));
+        world.add_component(ball, RigidBody::default());
+        world.add_component(ball, Collider::sphere(0.25));
+
+        let mut bounced = false;
+        for _ in 0..120 {
+            physics.simulate(&mut world, 1.0 / 60.0);
+            bounced |= world.get_component::<RigidBody>(ball).unwrap().velocity.y > 3.0;
+            assert!(world.get_component::<Transform>(ball).unwrap().position.y > 0.0);
+        }
+        assert!(bounced);
+    }
+
+    #[test]
+    fn character_lands_on_colliders_and_stops_at_walls() {
+        let mut physics = PhysicsWorld::new();
+        physics.add_static_aabb(AABB::new(Vec3::new(-50.0, -1.0, -50.0), Vec3::new(50.0, 0.0, 50.0)));
+        physics.add_static_aabb(AABB::new(Vec3::new(3.0, 0.0, -5.0), Vec3::new(4.0, 3.0, 5.0)));
+
+        let mut character = CharacterController::new(Vec3::new(0.0, 3.0, 0.0));
+        for _ in 0..120 {
+            character.move_and_collide(1.0 / 60.0, Vec3::ZERO, false, &physics);
+        }
+        assert!(character.is_grounded);
+        assert!((character.position.y - character.height * 0.5).abs() < 0.05, "y = {}", character.position.y);
+
+        for _ in 0..240 {
+            character.move_and_collide(1.0 / 60.0, Vec3::X, false, &physics);
+        }
+        assert!(character.position.x <= 3.0 - character.radius + 0.01, "x = {}", character.position.x);
+        assert!(character.position.x > 2.0);
+        assert!(character.is_grounded);
+    }
+
+    fn walk(character: &mut CharacterController, physics: &PhysicsWorld, direction: Vec3, seconds: f32) {
+        for _ in 0..(seconds * 60.0) as usize {
+            character.move_and_collide(1.0 / 60.0, direction, false, physics);
+        }
+    }
+
+    #[test]
+    fn character_climbs_ramps_and_steps_but_not_steep_slopes() {
+        let floor = AABB::new(Vec3::new(-50.0, -1.0, -50.0), Vec3::new(50.0, 0.0, 50.0));
+
+        // ~17° ramp: a slanted rod the character walks along.
+        let mut physics = PhysicsWorld::new();
+        physics.add_static_aabb(floor);
+        physics.add_capsule(Capsule::new(Vec3::new(3.0, -0.8, 0.0), Vec3::new(13.0, 2.2, 0.0), 1.0));
+        let mut character = CharacterController::new(Vec3::new(0.0, 0.9, 0.0));
+        walk(&mut character, &physics, Vec3::X, 1.5);
+        assert!(character.is_grounded);
+        assert!(character.position.x > 6.0 && character.position.y > 2.0, "{:?}", character.position);
+
+        // 0.3 m step.
+        let mut physics = PhysicsWorld::new();
+        physics.add_static_aabb(floor);
+        physics.add_static_aabb(AABB::new(Vec3::new(3.0, 0.0, -5.0), Vec3::new(6.0, 0.3, 5.0)));
+        let mut character = CharacterController::new(Vec3::new(0.0, 0.9, 0.0));
+        walk(&mut character, &physics, Vec3::X, 1.0);
+        assert!(character.position.x > 3.5, "{:?}", character.position);
+        assert!((character.position.y - 1.2).abs() < 0.05, "{:?}", character.position);
+
+        // ~72° slope: a wall, not a ramp.
+        let mut physics = PhysicsWorld::new();
+        physics.add_static_aabb(floor);
+        physics.add_capsule(Capsule::new(Vec3::new(3.0, -3.0, 0.0), Vec3::new(5.0, 3.0, 0.0), 1.0));
+        let mut character = CharacterController::new(Vec3::new(0.0, 0.9, 0.0));
+        walk(&mut character, &physics, Vec3::X, 2.0);
+        assert!(character.position.y < 1.3, "{:?}", character.position);
+        assert!(character.position.x < 3.0, "{:?}", character.position);
+    }
+
+    #[test]
+    fn kinematic_bodies_are_not_integrated() {
+        let mut world = World::new();
+        let e = world.create_entity();
+        world.add_component(e, Transform::from_position(Vec3::new(0.0, 3.0, 0.0)));
+        world.add_component(e, RigidBody::kinematic());
+        PhysicsWorld::new().simulate(&mut world, 1.0);
+        assert_eq!(world.get_component::<Transform>(e).unwrap().position.y, 3.0);
+    }
+}
@@ -0,0 +1,204 @@
+//! Prefabs
+//!
+//! A prefab is an entity subtree saved as a reusable JSON asset (`.prefab`
+//! by convention): each entity with the components `SceneSnapshot` knows
+//! (`Transform`, `Parent`, `Light`, `RigidBody`, `Camera`). Ids are local to
+//! the prefab, root first, and children keep their transforms relative to
+//! their parent, so the whole tree moves with the root. The root is stored
+//! at the origin; `instantiate` places it.
+//!
+//! Instantiating deep-copies the subtree into a `World` with fresh entity
+//! ids and the parent links remapped. `instantiate_file` also tags the new
+//! root with `PrefabInstance`, the path it came from, so a later "apply to
+//! prefab" can find its source. `MeshRenderer` is not saved, like in scene
+//! snapshots.
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform::Transform;
+use crate::systems::scene_snapshot::{unknown_keys, EntitySnapshot};
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Current prefab format version.
+pub const PREFAB_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prefab {
+    pub version: u32,
+    /// Root first; ids are indices into this list.
+    pub entities: Vec<EntitySnapshot>,
+}
+
+/// Source of an entity created by `Prefab::instantiate_file`, on the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefabInstance {
+    pub source: PathBuf,
+}
+
+impl Prefab {
+    /// Captures `root` and all its descendants. Error if `root` does not exist.
+    pub fn capture(world: &World, root: Entity) -> ReactorResult<Self> {
+        if !world.is_alive(root) {
+            return Err(ReactorError::invalid_parameter(format!("Entity {} does not exist", root)));
+        }
+        let mut subtree = vec![root];
+        let mut next = 0;
+        while next < subtree.len() {
+            subtree.extend(world.children(subtree[next]));
+            next += 1;
+        }
+        let ids: HashMap<Entity, Entity> =
+            subtree.iter().enumerate().map(|(index, &entity)| (entity, index as Entity)).collect();
+
+        let entities = subtree
+            .iter()
+            .map(|&entity| {
+                let mut saved = EntitySnapshot::capture(world, entity);
+                saved.id = ids[&entity];
+                saved.parent = saved.parent.and_then(|parent| ids.get(&parent).copied());
+                saved
+            })
+            .collect::<Vec<_>>();
+        let mut prefab = Self { version: PREFAB_VERSION, entities };
+        if let Some(transform) = prefab.entities[0].transform.as_mut() {
+            transform.position = [0.0; 3];
+        }
+        prefab.entities[0].parent = None;
+        Ok(prefab)
+    }
+
+    /// Creates a copy of the subtree with its root at `position` and returns
+    /// the new root.
+    pub fn instantiate(&self, world: &mut World, position: Vec3) -> Entity {
+        let ids: HashMap<Entity, Entity> =
+            self.entities.iter().map(|saved| (saved.id, world.create_entity())).collect();
+        for saved in &self.entities {
+            saved.restore(world, ids[&saved.id], &ids);
+        }
+        let root = ids[&self.entities[0].id];
+        let mut transform = world.get_component::<Transform>(root).copied().unwrap_or_default();
+        transform.position = position;
+        world.add_component(root, transform);
+        root
+    }
+
+    /// Loads the prefab at `path` and instantiates it, tagging the root with
+    /// `PrefabInstance`.
+    pub fn instantiate_file(world: &mut World, path: impl AsRef<Path>, position: Vec3) -> ReactorResult<Entity> {
+        let path = path.as_ref();
+        let root = Self::load(path)?.instantiate(world, position);
+        world.add_component(root, PrefabInstance { source: path.to_path_buf() });
+        Ok(root)
+    }
+
+    pub fn to_bytes(&self) -> ReactorResult<Vec<u8>> {
+        serde_json::to_vec_pretty(self)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize prefab: {}", e)))
+    }
+
+    /// Parses a prefab, rejecting other versions and empty ones. Unknown
+    /// fields are ignored and logged as a warning.
+    pub fn from_bytes(data: &[u8]) -> ReactorResult<Self> {
+        let invalid = |e: serde_json::Error| ReactorError::invalid_format(format!("Invalid prefab: {}", e));
+        let value: Value = serde_json::from_slice(data).map_err(invalid)?;
+        let prefab = Self::deserialize(&value).map_err(invalid)?;
+        if prefab.version != PREFAB_VERSION {
+            return Err(ReactorError::invalid_format(format!(
+                "Unsupported prefab version {} (expected {})",
+                prefab.version, PREFAB_VERSION
+            )));
+        }
+        if prefab.entities.is_empty() {
+            return Err(ReactorError::invalid_format("Prefab has no entities"));
+        }
+        let known = serde_json::to_value(&prefab)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize prefab: {}", e)))?;
+        let mut unknown = Vec::new();
+        unknown_keys(&value, &known, "", &mut unknown);
+        if !unknown.is_empty() {
+            log::warn!("Prefab: ignoring unknown fields {}", unknown.join(", "));
+        }
+        Ok(prefab)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ReactorResult<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes()?).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to write prefab {}", path.display()), e)
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> ReactorResult<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to read prefab {}", path.display()), e)
+        })?;
+        Self::from_bytes(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::hierarchy::Parent;
+    use crate::systems::lighting::Light;
+
+    /// Lamp: root at (5, 0, 0) with a light child and a grandchild.
+    fn lamp(world: &mut World) -> Entity {
+        let root = world.create_entity();
+        let bulb = world.create_entity();
+        let glow = world.create_entity();
+        world.add_component(root, Transform::from_translation(5.0, 0.0, 0.0));
+        world.add_component(bulb, Transform::from_translation(0.0, 2.0, 0.0));
+        world.add_component(bulb, Parent(root));
+        world.add_component(bulb, Light::point(Vec3::ZERO, Vec3::ONE, 4.0, 10.0));
+        world.add_component(glow, Transform::from_translation(0.0, 0.5, 0.0));
+        world.add_component(glow, Parent(bulb));
+        root
+    }
+
+    #[test]
+    fn instances_are_independent_copies_of_the_subtree() {
+        let mut world = World::new();
+        let root = lamp(&mut world);
+        let path = std::env::temp_dir().join(format!("reactor_prefab_{}.prefab", std::process::id()));
+        Prefab::capture(&world, root).unwrap().save(&path).unwrap();
+
+        let mut world = World::new();
+        let first = Prefab::instantiate_file(&mut world, &path, Vec3::new(0.0, 0.0, 10.0));
+        let second = Prefab::instantiate_file(&mut world, &path, Vec3::new(0.0, 0.0, -10.0));
+        let _ = std::fs::remove_file(&path);
+        let (first, second) = (first.unwrap(), second.unwrap());
+
+        assert_eq!(world.entity_count(), 6);
+        for (root, z) in [(first, 10.0), (second, -10.0)] {
+            let bulb = world.children(root)[0];
+            let glow = world.children(bulb)[0];
+            assert!(world.get_component::<Light>(bulb).is_some());
+            let glow_world = world.world_matrix(glow).transform_point3(Vec3::ZERO);
+            assert!(glow_world.abs_diff_eq(Vec3::new(0.0, 2.5, z), 1e-6));
+            assert_eq!(world.get_component::<PrefabInstance>(root).unwrap().source, path);
+        }
+        let first_bulb = world.children(first)[0];
+        world.get_component_mut::<Transform>(first_bulb).unwrap().position.y = 7.0;
+        let second_bulb = world.children(second)[0];
+        assert_eq!(world.get_component::<Transform>(second_bulb).unwrap().position.y, 2.0);
+    }
+
+    #[test]
+    fn rejects_empty_and_unknown_versions() {
+        let mut world = World::new();
+        let root = lamp(&mut world);
+        let mut prefab = Prefab::capture(&world, root).unwrap();
+        prefab.version = 2;
+        assert!(Prefab::from_bytes(&prefab.to_bytes().unwrap()).is_err());
+        prefab.version = PREFAB_VERSION;
+        prefab.entities.clear();
+        assert!(Prefab::from_bytes(&prefab.to_bytes().unwrap()).is_err());
+        assert!(Prefab::capture(&world, 99).is_err());
+    }
+}
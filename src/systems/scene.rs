@@ -1,7 +1,15 @@
 use crate::resources::material::Material;
 use crate::resources::mesh::Mesh;
+use crate::resources::texture::Cubemap;
+use crate::resources::vertex::InstanceData;
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform_hierarchy::{TransformHierarchy, TransformNode};
+use crate::systems::frustum::CullingSystem;
 use crate::systems::lighting::Light;
-use glam::Mat4;
+use crate::systems::lod::{self, LodLevel};
+use crate::systems::physics::{Ray, AABB};
+use glam::{Mat4, Vec3};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 pub struct SceneObject {
@@ -15,10 +23,44 @@ pub struct SceneObject {
     pub roughness: f32,
     pub emission: glam::Vec4, // .xyz = color, .w = SSS strength/thickness
     pub anisotropy: f32,
+    /// Transforms por instancia (relativos a `transform`). Vacío = objeto normal;
+    /// si no, se dibuja con un único draw instanciado y necesita un material
+    /// instanciado (`Reactor::create_instanced_material`).
+    pub instances: Vec<InstanceData>,
+    /// Paleta de joints del skin (ver `systems::skinning`). Vacía = objeto
+    /// rígido; si no, necesita un material skinned
+    /// (`Reactor::create_skinned_material`) y un mesh `VertexSkinned`.
+    pub joint_matrices: Vec<Mat4>,
+    /// Si se dibuja en el shadow map de la luz direccional. Los objetos
+    /// skinned no proyectan sombra todavía: el pipeline de sombras sólo lee
+    /// el layout `Vertex`.
+    pub cast_shadows: bool,
+    /// Si el shader aplica la sombra de la luz direccional sobre el objeto.
+    pub receive_shadows: bool,
+    /// Resultado del frustum culling del frame (`Scene::cull`). Independiente de
+    /// `visible`, que es del usuario.
+    pub culled: bool,
+    /// Id estable asignado por `Scene::add` (0 = aún no está en una escena).
+    /// Nunca se reutiliza, así que un id de un objeto borrado no apunta a otro.
+    pub id: u64,
+    /// Nodo de `Scene::transforms` del que sale `transform`; `Scene::cull`
+    /// copia su matriz en mundo cuando cambia. `None` = matriz suelta.
+    pub node: Option<TransformNode>,
+    /// `world_aabb` cacheado. Lo refrescan `set_transform` y los setters de
+    /// `Scene`; tras escribir `transform` a mano, `refresh_bounds` (o
+    /// `Scene::cull`) lo pone al día.
+    pub world_bounds: AABB,
+    /// Niveles de detalle por distancia, del más detallado al menos (ver
+    /// `systems::lod`). Vacío = siempre `mesh`; si no, `Scene::cull` pone en
+    /// `mesh` el nivel elegido.
+    pub lods: Vec<LodLevel>,
+    /// Índice en `lods` del nivel que está en `mesh`.
+    pub lod: usize,
 }
 
 impl SceneObject {
     pub fn new(mesh: Arc<Mesh>, material: Arc<Material>, transform: Mat4) -> Self {
+        let world_bounds = mesh.world_bounds(&transform);
         Self {
             mesh,
             material,
@@ -30,9 +72,37 @@ impl SceneObject {
             roughness: 0.5,
             emission: glam::Vec4::ZERO,
             anisotropy: 0.0,
+            instances: Vec::new(),
+            joint_matrices: Vec::new(),
+            cast_shadows: true,
+            receive_shadows: true,
+            culled: false,
+            id: 0,
+            node: None,
+            world_bounds,
+            lods: Vec::new(),
+            lod: 0,
         }
     }
 
+    /// Añade un nivel de detalle usado hasta `max_distance`. Los niveles se
+    /// ordenan por distancia; el más detallado también debe estar en la lista.
+    pub fn add_lod(&mut self, mesh: Arc<Mesh>, max_distance: f32) {
+        let position = self.lods.partition_point(|level| level.max_distance <= max_distance);
+        self.lods.insert(position, LodLevel { mesh, max_distance });
+    }
+
+    pub fn with_lod(mut self, mesh: Arc<Mesh>, max_distance: f32) -> Self {
+        self.add_lod(mesh, max_distance);
+        self
+    }
+
+    /// Cuelga el objeto de `node` de `Scene::transforms` en vez de su matriz suelta.
+    pub fn with_node(mut self, node: TransformNode) -> Self {
+        self.node = Some(node);
+        self
+    }
+
     pub fn with_name(mut self, name: &str) -> Self {
         self.name = Some(name.to_string());
         self
@@ -41,14 +111,134 @@ impl SceneObject {
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
+
+    /// Cambia `transform` y refresca `world_bounds`.
+    pub fn set_transform(&mut self, transform: Mat4) {
+        self.transform = transform;
+        self.refresh_bounds();
+    }
+
+    /// Recalcula `world_bounds` desde `transform`, la malla y las instancias.
+    pub fn refresh_bounds(&mut self) {
+        self.world_bounds = self.world_aabb();
+    }
+
+    /// Caja en mundo: `mesh.bounds` por `transform` (y por cada instancia, si las hay).
+    pub fn world_aabb(&self) -> AABB {
+        let bounds = self.mesh.bounds;
+        if self.instances.is_empty() {
+            return bounds.transformed_by(&self.transform);
+        }
+        self.instances
+            .iter()
+            .map(|instance| bounds.transformed_by(&(self.transform * Mat4::from_cols_array_2d(&instance.model_matrix))))
+            .reduce(|a, b| a.merge(&b))
+            .unwrap_or(bounds.transformed_by(&self.transform))
+    }
+}
+
+/// Resultado de `Scene::pick`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickHit {
+    pub index: usize,
+    pub distance: f32,
+    pub point: Vec3,
+}
+
+/// Componente ECS: la entidad se dibuja con esta malla/material.
+///
+/// `Scene::sync_from_world` lo convierte en un `SceneObject`, con la matriz
+/// en mundo de la entidad (`World::world_matrix`: su `Transform` y el de sus
+/// padres).
+#[derive(Clone)]
+pub struct MeshRenderer {
+    pub mesh: Arc<Mesh>,
+    pub material: Arc<Material>,
+    pub visible: bool,
+    pub cast_shadows: bool,
+    pub receive_shadows: bool,
+}
+
+impl MeshRenderer {
+    pub fn new(mesh: Arc<Mesh>, material: Arc<Material>) -> Self {
+        Self { mesh, material, visible: true, cast_shadows: true, receive_shadows: true }
+    }
+
+    pub fn with_shadows(mut self, cast: bool, receive: bool) -> Self {
+        self.cast_shadows = cast;
+        self.receive_shadows = receive;
+        self
+    }
+}
+
+/// Referencia estable a un objeto de `Scene`: slot + generación. Sigue
+/// apuntando al mismo objeto aunque otros se borren o se muevan, y deja de
+/// resolver (en vez de apuntar a otro) cuando el suyo se borra.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SceneHandle {
+    slot: u32,
+    generation: u32,
+}
+
+impl SceneHandle {
+    pub fn slot(&self) -> u32 {
+        self.slot
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Lo que aceptan `Scene::get`, `get_mut`, `remove` y `swap_remove`: un
+/// índice de `objects` (válido hasta el próximo borrado) o un `SceneHandle`.
+pub trait SceneKey {
+    fn resolve(self, scene: &Scene) -> Option<usize>;
+}
+
+impl SceneKey for usize {
+    fn resolve(self, scene: &Scene) -> Option<usize> {
+        (self < scene.objects.len()).then_some(self)
+    }
+}
+
+impl SceneKey for SceneHandle {
+    fn resolve(self, scene: &Scene) -> Option<usize> {
+        scene.index_of_handle(self)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    generation: u32,
+    /// Índice en `objects`; `None` si el slot está libre.
+    index: Option<usize>,
 }
 
 pub struct Scene {
+    /// Objetos en orden de dibujo: los persistentes en orden de inserción y
+    /// después el grupo dinámico (`add_dynamic`). Se pueden modificar en
+    /// sitio; para añadir o borrar usa los métodos de `Scene`, que mantienen
+    /// los handles.
     pub objects: Vec<SceneObject>,
     pub ambient_light: glam::Vec3,
     pub sun_direction: glam::Vec3,
     pub sun_color: glam::Vec3,
     pub lights: Vec<Light>,
+    /// Jerarquía de transforms de los objetos con `node`.
+    pub transforms: TransformHierarchy,
+    /// Multiplica la distancia con la que se eligen los LODs: `> 1` pasa
+    /// antes a los niveles simples.
+    pub lod_bias: f32,
+    /// Cielo dibujado detrás de los opacos; `None` deja el color de fondo.
+    skybox: Option<Arc<Cubemap>>,
+    /// Handle de cada objeto persistente, paralelo a `objects[..handles.len()]`.
+    handles: Vec<SceneHandle>,
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
+    ecs_bindings: HashMap<Entity, SceneHandle>,
+    id_index: HashMap<u64, SceneHandle>,
+    next_id: u64,
 }
 
 impl Scene {
@@ -59,32 +249,169 @@ impl Scene {
             sun_direction: glam::Vec3::new(-0.5, -1.0, -0.5).normalize(),
             sun_color: glam::Vec3::ONE,
             lights: Vec::new(),
+            transforms: TransformHierarchy::new(),
+            lod_bias: 1.0,
+            skybox: None,
+            handles: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            ecs_bindings: HashMap::new(),
+            id_index: HashMap::new(),
+            next_id: 1,
         }
     }
 
+    /// Dibuja `cubemap` en los píxeles que ningún objeto opaco cubre. Como
+    /// con los meshes, el `Reactor` lo retiene mientras la GPU lo use.
+    pub fn set_skybox(&mut self, cubemap: Arc<Cubemap>) {
+        self.skybox = Some(cubemap);
+    }
+
+    pub fn clear_skybox(&mut self) {
+        self.skybox = None;
+    }
+
+    pub fn skybox(&self) -> Option<&Arc<Cubemap>> {
+        self.skybox.as_ref()
+    }
+
+    /// Añade un objeto que comparte `mesh` y `material` con quien los pase:
+    /// el mismo `Arc` puede usarse en muchos objetos. Soltar la referencia del
+    /// llamador mientras haya objetos que la usen es seguro; el recurso se
+    /// destruye cuando se suelta la última (el `Reactor` retiene los de cada
+    /// frame hasta que la GPU termina con él).
     pub fn add_object(
         &mut self,
         mesh: Arc<Mesh>,
         material: Arc<Material>,
         transform: Mat4,
     ) -> usize {
-        let index = self.objects.len();
-        self.objects
-            .push(SceneObject::new(mesh, material, transform));
-        index
+        self.add(SceneObject::new(mesh, material, transform))
+    }
+
+    /// Añade un objeto que se dibuja una vez por transform en un solo draw call.
+    pub fn add_instanced_object(
+        &mut self,
+        mesh: Arc<Mesh>,
+        material: Arc<Material>,
+        transforms: &[Mat4],
+    ) -> usize {
+        let mut object = SceneObject::new(mesh, material, Mat4::IDENTITY);
+        object.instances = transforms.iter().copied().map(InstanceData::new).collect();
+        self.add(object)
     }
 
+    /// Reemplaza los transforms de un objeto instanciado. `false` si el índice no existe.
+    pub fn set_instance_transforms(&mut self, key: impl SceneKey, transforms: &[Mat4]) -> bool {
+        let Some(object) = self.get_mut(key) else { return false };
+        object.instances.clear();
+        object.instances.extend(transforms.iter().copied().map(InstanceData::new));
+        object.refresh_bounds();
+        true
+    }
+
+    /// Añade el objeto y devuelve su índice actual. Los índices se desplazan
+    /// al borrar objetos anteriores; para referencias duraderas usa `insert`.
     pub fn add(&mut self, object: SceneObject) -> usize {
-        let index = self.objects.len();
-        self.objects.push(object);
-        index
+        self.insert(object);
+        self.handles.len() - 1
+    }
+
+    /// Añade el objeto tras los persistentes y devuelve su handle estable.
+    pub fn insert(&mut self, mut object: SceneObject) -> SceneHandle {
+        let index = self.handles.len();
+        if let Some(world) = object.node.and_then(|node| self.transforms.world_matrix(node)) {
+            object.transform = world;
+        }
+        object.refresh_bounds();
+        object.id = self.next_id;
+        self.next_id += 1;
+
+        let handle = match self.free_slots.pop() {
+            Some(slot) => {
+                let entry = &mut self.slots[slot as usize];
+                entry.index = Some(index);
+                SceneHandle { slot, generation: entry.generation }
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, index: Some(index) });
+                SceneHandle { slot: self.slots.len() as u32 - 1, generation: 0 }
+            }
+        };
+        self.id_index.insert(object.id, handle);
+        self.handles.push(handle);
+        // Los dinámicos van detrás y no tienen handle: basta con desplazarlos.
+        self.objects.insert(index, object);
+        handle
+    }
+
+    /// Handle del objeto que ocupa `index`; `None` fuera de rango o si es dinámico.
+    pub fn handle_of(&self, index: usize) -> Option<SceneHandle> {
+        self.handles.get(index).copied()
+    }
+
+    /// Índice actual del objeto de `handle`; `None` si ya se borró.
+    pub fn index_of_handle(&self, handle: SceneHandle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.index
+    }
+
+    /// `true` si `handle` apunta a un objeto vivo.
+    pub fn contains(&self, handle: SceneHandle) -> bool {
+        self.index_of_handle(handle).is_some()
     }
 
-    pub fn get(&self, index: usize) -> Option<&SceneObject> {
+    /// Como `add_object`, con nombre, devolviendo el id estable.
+    pub fn add_named(&mut self, mesh: Arc<Mesh>, material: Arc<Material>, transform: Mat4, name: &str) -> u64 {
+        let index = self.add(SceneObject::new(mesh, material, transform).with_name(name));
+        self.objects[index].id
+    }
+
+    /// Índice actual del objeto con `id`.
+    pub fn index_of(&self, id: u64) -> Option<usize> {
+        self.index_of_handle(*self.id_index.get(&id)?)
+    }
+
+    /// Handle del objeto con `id`.
+    pub fn handle_for_id(&self, id: u64) -> Option<SceneHandle> {
+        self.id_index.get(&id).copied()
+    }
+
+    pub fn get_by_id(&self, id: u64) -> Option<&SceneObject> {
+        self.objects.get(self.index_of(id)?)
+    }
+
+    pub fn get_by_id_mut(&mut self, id: u64) -> Option<&mut SceneObject> {
+        let index = self.index_of(id)?;
+        self.objects.get_mut(index)
+    }
+
+    /// Id del primer objeto con ese nombre.
+    pub fn find_id_by_name(&self, name: &str) -> Option<u64> {
+        self.find_by_name(name).map(|index| self.objects[index].id)
+    }
+
+    pub fn set_transform_by_id(&mut self, id: u64, transform: Mat4) -> bool {
+        let Some(object) = self.get_by_id_mut(id) else { return false };
+        object.set_transform(transform);
+        true
+    }
+
+    pub fn remove_by_id(&mut self, id: u64) -> Option<SceneObject> {
+        let index = self.index_of(id)?;
+        self.remove(index)
+    }
+
+    pub fn get(&self, key: impl SceneKey) -> Option<&SceneObject> {
+        let index = key.resolve(self)?;
         self.objects.get(index)
     }
 
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut SceneObject> {
+    pub fn get_mut(&mut self, key: impl SceneKey) -> Option<&mut SceneObject> {
+        let index = key.resolve(self)?;
         self.objects.get_mut(index)
     }
 
@@ -94,16 +421,136 @@ impl Scene {
             .position(|o| o.name.as_deref() == Some(name))
     }
 
-    pub fn remove(&mut self, index: usize) -> Option<SceneObject> {
-        if index < self.objects.len() {
-            Some(self.objects.remove(index))
-        } else {
-            None
+    /// Borra conservando el orden: los objetos posteriores bajan un índice
+    /// (O(n)). Sus handles e ids siguen valiendo.
+    pub fn remove(&mut self, key: impl SceneKey) -> Option<SceneObject> {
+        let index = key.resolve(self)?;
+        if index >= self.handles.len() {
+            return Some(self.objects.remove(index));
+        }
+        let handle = self.handles.remove(index);
+        let removed = self.objects.remove(index);
+        for moved in &self.handles[index..] {
+            if let Some(slot) = self.slots[moved.slot as usize].index.as_mut() {
+                *slot -= 1;
+            }
+        }
+        self.release(handle, &removed);
+        Some(removed)
+    }
+
+    /// Borra en O(1) moviendo el último objeto persistente al hueco. Cambia el
+    /// orden de dibujo y el índice del objeto movido, pero ningún handle.
+    pub fn swap_remove(&mut self, key: impl SceneKey) -> Option<SceneObject> {
+        let index = key.resolve(self)?;
+        if index >= self.handles.len() {
+            return Some(self.objects.remove(index));
         }
+        let last = self.handles.len() - 1;
+        self.objects.swap(index, last);
+        self.handles.swap(index, last);
+        let handle = self.handles.pop().unwrap();
+        let removed = self.objects.remove(last);
+        if index < last {
+            self.slots[self.handles[index].slot as usize].index = Some(index);
+        }
+        self.release(handle, &removed);
+        Some(removed)
+    }
+
+    /// Libera el slot de un objeto ya sacado de `objects`.
+    fn release(&mut self, handle: SceneHandle, removed: &SceneObject) {
+        let slot = &mut self.slots[handle.slot as usize];
+        slot.index = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_slots.push(handle.slot);
+        self.id_index.remove(&removed.id);
+        self.ecs_bindings.retain(|_, bound| *bound != handle);
+    }
+
+    /// Añade un objeto al grupo dinámico: vive hasta el próximo
+    /// `clear_dynamic`, no recibe handle ni id y se dibuja tras los
+    /// persistentes. Pensado para lo que se regenera cada frame (partículas,
+    /// gizmos): vaciarlo no toca los handles. Devuelve su índice.
+    pub fn add_dynamic(&mut self, mut object: SceneObject) -> usize {
+        object.refresh_bounds();
+        self.objects.push(object);
+        self.objects.len() - 1
+    }
+
+    /// Borra todo el grupo dinámico (O(objetos dinámicos)).
+    pub fn clear_dynamic(&mut self) {
+        self.objects.truncate(self.handles.len());
+    }
+
+    /// Número de objetos en el grupo dinámico.
+    pub fn dynamic_len(&self) -> usize {
+        self.objects.len() - self.handles.len()
     }
 
     pub fn clear(&mut self) {
         self.objects.clear();
+        for handle in self.handles.drain(..) {
+            let slot = &mut self.slots[handle.slot as usize];
+            slot.index = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free_slots.push(handle.slot);
+        }
+        self.ecs_bindings.clear();
+        self.id_index.clear();
+    }
+
+    /// Vuelca al scene cada entidad con `MeshRenderer`: crea su `SceneObject`
+    /// la primera vez y después actualiza transform, visibilidad y malla/material.
+    ///
+    /// El objeto de una entidad destruida o que perdió el `MeshRenderer` se
+    /// borra por su handle; los índices posteriores bajan, pero los handles
+    /// e ids de los demás siguen valiendo. Cada entidad guarda el handle de
+    /// su objeto, así que borrar otros objetos de la escena no la desvincula.
+    pub fn sync_from_world(&mut self, world: &World) {
+        crate::profile_scope!("scene_sync");
+        let mut seen = HashSet::new();
+        for (entity, renderer) in world.query::<MeshRenderer>() {
+            let transform = world.world_matrix(entity);
+            let bound = self.ecs_bindings.get(&entity).and_then(|&handle| self.index_of_handle(handle));
+            let index = match bound {
+                Some(index) => index,
+                None => {
+                    let handle = self.insert(SceneObject::new(renderer.mesh.clone(), renderer.material.clone(), transform));
+                    self.ecs_bindings.insert(entity, handle);
+                    self.index_of_handle(handle).unwrap()
+                }
+            };
+            let object = &mut self.objects[index];
+            if object.lods.is_empty() && !Arc::ptr_eq(&object.mesh, &renderer.mesh) {
+                object.mesh = renderer.mesh.clone();
+            }
+            if !Arc::ptr_eq(&object.material, &renderer.material) {
+                object.material = renderer.material.clone();
+            }
+            object.set_transform(transform);
+            object.visible = renderer.visible;
+            object.cast_shadows = renderer.cast_shadows;
+            object.receive_shadows = renderer.receive_shadows;
+            seen.insert(entity);
+        }
+
+        let stale: Vec<SceneHandle> = self
+            .ecs_bindings
+            .iter()
+            .filter(|(entity, _)| !seen.contains(*entity))
+            .map(|(_, &handle)| handle)
+            .collect();
+        for handle in stale {
+            // `remove` libera el slot y el vínculo con la entidad.
+            self.remove(handle);
+        }
+        self.ecs_bindings.retain(|entity, _| seen.contains(entity));
+    }
+
+    /// Índice del `SceneObject` asociado a una entidad tras el último sync.
+    pub fn object_for_entity(&self, entity: Entity) -> Option<usize> {
+        self.index_of_handle(*self.ecs_bindings.get(&entity)?)
     }
 
     pub fn len(&self) -> usize {
@@ -114,6 +561,105 @@ impl Scene {
         self.objects.is_empty()
     }
 
+    /// Recalcula `world_bounds` de todos los objetos (p. ej. tras escribir
+    /// `transform` directamente).
+    pub fn refresh_bounds(&mut self) {
+        for object in &mut self.objects {
+            object.refresh_bounds();
+        }
+    }
+
+    /// Elige el LOD de cada objeto con `lods` según la distancia de
+    /// `camera_position` al centro de su `world_bounds` (por `lod_bias`) y lo
+    /// pone en `mesh`.
+    pub fn update_lods(&mut self, camera_position: Vec3) {
+        let bias = self.lod_bias;
+        for object in self.objects.iter_mut().filter(|object| !object.lods.is_empty()) {
+            let distance = object.world_bounds.center().distance(camera_position) * bias;
+            let max_distances: Vec<f32> = object.lods.iter().map(|level| level.max_distance).collect();
+            let level = lod::select_lod(&max_distances, object.lod, distance);
+            if level != object.lod || !Arc::ptr_eq(&object.mesh, &object.lods[level].mesh) {
+                object.lod = level;
+                object.mesh = object.lods[level].mesh.clone();
+            }
+        }
+    }
+
+    /// Marca `culled` en cada objeto visible cuyo AABB en mundo queda fuera del
+    /// frustum de `view_projection`, refrescando antes `world_bounds` y, con
+    /// proyección perspectiva, los LODs. Los contadores quedan en `culling`.
+    pub fn cull(&mut self, culling: &mut CullingSystem, view_projection: Mat4) {
+        crate::profile_scope!("scene_cull");
+        self.sync_transforms();
+        if let Some(camera_position) = lod::camera_position(&view_projection) {
+            self.update_lods(camera_position);
+        }
+        culling.update_frustum(view_projection);
+        for object in &mut self.objects {
+            object.refresh_bounds();
+            object.culled = object.visible && !culling.is_visible_aabb(&object.world_bounds);
+        }
+    }
+
+    /// Recalcula los nodos sucios de `transforms` y copia la matriz en mundo
+    /// a los objetos que cuelgan de ellos. Los demás no se tocan.
+    pub fn sync_transforms(&mut self) {
+        self.transforms.update();
+        let changed: HashSet<TransformNode> = self.transforms.take_changed().into_iter().collect();
+        if changed.is_empty() {
+            return;
+        }
+        let Self { objects, transforms, .. } = self;
+        for object in objects.iter_mut() {
+            if let Some(world) = object.node.filter(|node| changed.contains(node)).and_then(|node| transforms.world_matrix(node)) {
+                object.set_transform(world);
+            }
+        }
+    }
+
+    /// Índices de los objetos visibles y no descartados en orden de dibujo:
+    /// primero los opacos en orden de inserción y después los transparentes
+    /// de atrás hacia delante, según el centro de su `world_bounds`.
+    pub fn draw_order(&self, view_projection: &Mat4) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.objects.len());
+        let mut transparent = Vec::new();
+        for (index, object) in self.objects.iter().enumerate() {
+            if !object.visible || object.culled {
+                continue;
+            }
+            if object.material.is_transparent() {
+                transparent.push((index, object.world_bounds.center()));
+            } else {
+                order.push(index);
+            }
+        }
+        sort_back_to_front(view_projection, &mut transparent);
+        order.extend(transparent.into_iter().map(|(index, _)| index));
+        order
+    }
+
+    /// Objeto visible más cercano cuyo AABB en mundo corta `ray`.
+    pub fn pick(&self, ray: &Ray) -> Option<PickHit> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| object.visible)
+            .filter_map(|(index, object)| {
+                let distance = ray.intersects_aabb(&object.world_aabb())?;
+                Some(PickHit { index, distance, point: ray.point_at(distance) })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Caja en mundo que contiene todos los objetos de `keys` (p. ej. la
+    /// selección, para encuadrarla). `None` si no resuelve ninguno.
+    pub fn bounds<K: SceneKey>(&self, keys: impl IntoIterator<Item = K>) -> Option<AABB> {
+        keys.into_iter()
+            .filter_map(|key| self.get(key))
+            .map(SceneObject::world_aabb)
+            .reduce(|a, b| a.merge(&b))
+    }
+
     pub fn visible_objects(&self) -> impl Iterator<Item = &SceneObject> {
         self.objects.iter().filter(|o| o.visible)
     }
@@ -137,3 +683,38 @@ impl Default for Scene {
         Self::new()
     }
 }
+
+/// Ordena `(índice, centro en mundo)` del más lejano al más cercano a la
+/// cámara. La z de clip antes de dividir por w crece con la profundidad en
+/// vista tanto en perspectiva como en ortográfica; los empates conservan el
+/// orden de entrada.
+pub fn sort_back_to_front(view_projection: &Mat4, items: &mut [(usize, Vec3)]) {
+    items.sort_by(|(_, a), (_, b)| {
+        let depth = |p: &Vec3| (*view_projection * p.extend(1.0)).z;
+        depth(b).total_cmp(&depth(a))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transparent_order_follows_the_camera() {
+        let projections = [
+            Mat4::perspective_rh(45f32.to_radians(), 1.0, 0.1, 100.0),
+            Mat4::orthographic_rh(-5.0, 5.0, -5.0, 5.0, 0.1, 100.0),
+        ];
+        for projection in projections {
+            let mut items = [(0, Vec3::new(0.0, 0.0, 1.0)), (1, Vec3::new(0.5, 0.0, -2.0)), (2, Vec3::ZERO)];
+
+            let front = projection * Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+            sort_back_to_front(&front, &mut items);
+            assert_eq!(items.map(|(index, _)| index), [1, 2, 0]);
+
+            let back = projection * Mat4::look_at_rh(Vec3::new(0.0, 0.0, -10.0), Vec3::ZERO, Vec3::Y);
+            sort_back_to_front(&back, &mut items);
+            assert_eq!(items.map(|(index, _)| index), [0, 2, 1]);
+        }
+    }
+}
@@ -0,0 +1,529 @@
+//! Scene snapshots
+//!
+//! Captures the mutable state of a running scene (object transforms and
+//! visibility, camera pose, lights) into a versioned JSON blob and restores it
+//! later. Meshes and materials are not part of the snapshot: objects are
+//! matched by index, so the snapshot is only meaningful for the scene it was
+//! taken from. This is what makes play mode non-destructive.
+//!
+//! `save` / `load` keep a snapshot in a `.reactor` file. Fields this version
+//! does not know (written by a newer build) are skipped with a warning.
+//!
+//! `with_world` adds the ECS entities with the components an inspector edits
+//! (`Transform`, `Parent`, `Light`, `RigidBody`, `Camera`), and
+//! `restore_world` writes them back. `MeshRenderer` is not saved, for the same
+//! reason meshes are not.
+
+use crate::core::error::{ErrorCode, ReactorError, ReactorResult};
+use crate::scene::camera::{Camera, Projection};
+use crate::scene::ecs::{Component, Entity, World};
+use crate::scene::hierarchy::Parent;
+use crate::scene::transform::Transform;
+use crate::systems::lighting::{Light, LightType, LightingSystem};
+use crate::systems::physics::RigidBody;
+use crate::systems::scene::Scene;
+use glam::{Mat4, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Current snapshot format version.
+pub const SCENE_SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneSnapshot {
+    pub version: u32,
+    pub objects: Vec<ObjectSnapshot>,
+    pub camera: CameraSnapshot,
+    #[serde(default)]
+    pub lights: Vec<LightSnapshot>,
+    #[serde(default)]
+    pub entities: Vec<EntitySnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectSnapshot {
+    pub transform: [f32; 16],
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraSnapshot {
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LightSnapshot {
+    pub position: [f32; 3],
+    pub direction: [f32; 3],
+    pub color: [f32; 3],
+    pub intensity: f32,
+    pub range: f32,
+    pub enabled: bool,
+}
+
+/// An ECS entity and the components an inspector edits. `None` = absent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntitySnapshot {
+    pub id: Entity,
+    #[serde(default)]
+    pub transform: Option<TransformSnapshot>,
+    #[serde(default)]
+    pub parent: Option<Entity>,
+    #[serde(default)]
+    pub light: Option<EntityLightSnapshot>,
+    #[serde(default)]
+    pub rigid_body: Option<RigidBodySnapshot>,
+    #[serde(default)]
+    pub camera: Option<EntityCameraSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformSnapshot {
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightTypeSnapshot {
+    Directional,
+    Point,
+    Spot,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityLightSnapshot {
+    pub light_type: LightTypeSnapshot,
+    pub position: [f32; 3],
+    pub direction: [f32; 3],
+    pub color: [f32; 3],
+    pub intensity: f32,
+    pub range: f32,
+    pub spot_angle: f32,
+    pub cast_shadows: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RigidBodySnapshot {
+    pub velocity: [f32; 3],
+    pub angular_velocity: [f32; 3],
+    pub mass: f32,
+    pub drag: f32,
+    pub angular_drag: f32,
+    pub gravity_scale: f32,
+    pub is_kinematic: bool,
+    pub freeze_rotation: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectionSnapshot {
+    Perspective,
+    Orthographic { size: f32 },
+    PixelOrthographic { pixels_per_unit: f32, viewport_height: f32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityCameraSnapshot {
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+    pub fov: f32,
+    pub near: f32,
+    pub far: f32,
+    pub aspect_ratio: f32,
+    pub projection: ProjectionSnapshot,
+}
+
+impl EntitySnapshot {
+    pub(crate) fn capture(world: &World, id: Entity) -> Self {
+        Self {
+            id,
+            transform: world.get_component::<Transform>(id).map(|t| TransformSnapshot {
+                position: t.position.to_array(),
+                rotation: t.rotation.to_array(),
+                scale: t.scale.to_array(),
+            }),
+            parent: world.parent(id),
+            light: world.get_component::<Light>(id).map(|l| EntityLightSnapshot {
+                light_type: match l.light_type {
+                    LightType::Directional => LightTypeSnapshot::Directional,
+                    LightType::Point => LightTypeSnapshot::Point,
+                    LightType::Spot => LightTypeSnapshot::Spot,
+                },
+                position: l.position.to_array(),
+                direction: l.direction.to_array(),
+                color: l.color.to_array(),
+                intensity: l.intensity,
+                range: l.range,
+                spot_angle: l.spot_angle,
+                cast_shadows: l.cast_shadows,
+                enabled: l.enabled,
+            }),
+            rigid_body: world.get_component::<RigidBody>(id).map(|b| RigidBodySnapshot {
+                velocity: b.velocity.to_array(),
+                angular_velocity: b.angular_velocity.to_array(),
+                mass: b.mass,
+                drag: b.drag,
+                angular_drag: b.angular_drag,
+                gravity_scale: b.gravity_scale,
+                is_kinematic: b.is_kinematic,
+                freeze_rotation: b.freeze_rotation,
+            }),
+            camera: world.get_component::<Camera>(id).map(|c| EntityCameraSnapshot {
+                position: c.position.to_array(),
+                rotation: c.rotation.to_array(),
+                fov: c.fov,
+                near: c.near,
+                far: c.far,
+                aspect_ratio: c.aspect_ratio,
+                projection: match c.projection {
+                    Projection::Perspective => ProjectionSnapshot::Perspective,
+                    Projection::Orthographic { size } => ProjectionSnapshot::Orthographic { size },
+                    Projection::PixelOrthographic { pixels_per_unit, viewport_height } => {
+                        ProjectionSnapshot::PixelOrthographic { pixels_per_unit, viewport_height }
+                    }
+                },
+            }),
+        }
+    }
+
+    pub(crate) fn restore(&self, world: &mut World, entity: Entity, ids: &HashMap<Entity, Entity>) {
+        put(world, entity, self.transform.as_ref().map(|t| Transform {
+            position: Vec3::from_array(t.position),
+            rotation: Quat::from_array(t.rotation).normalize(),
+            scale: Vec3::from_array(t.scale),
+        }));
+        put(world, entity, self.parent.and_then(|p| ids.get(&p)).map(|&p| Parent(p)));
+        put(world, entity, self.light.as_ref().map(|l| Light {
+            light_type: match l.light_type {
+                LightTypeSnapshot::Directional => LightType::Directional,
+                LightTypeSnapshot::Point => LightType::Point,
+                LightTypeSnapshot::Spot => LightType::Spot,
+            },
+            position: Vec3::from_array(l.position),
+            direction: Vec3::from_array(l.direction),
+            color: Vec3::from_array(l.color),
+            intensity: l.intensity,
+            range: l.range,
+            spot_angle: l.spot_angle,
+            cast_shadows: l.cast_shadows,
+            enabled: l.enabled,
+        }));
+        put(world, entity, self.rigid_body.as_ref().map(|b| RigidBody {
+            velocity: Vec3::from_array(b.velocity),
+            angular_velocity: Vec3::from_array(b.angular_velocity),
+            mass: b.mass,
+            drag: b.drag,
+            angular_drag: b.angular_drag,
+            gravity_scale: b.gravity_scale,
+            is_kinematic: b.is_kinematic,
+            freeze_rotation: b.freeze_rotation,
+        }));
+        put(world, entity, self.camera.as_ref().map(|c| Camera {
+            position: Vec3::from_array(c.position),
+            rotation: Quat::from_array(c.rotation).normalize(),
+            fov: c.fov,
+            near: c.near,
+            far: c.far,
+            aspect_ratio: c.aspect_ratio,
+            projection: match c.projection {
+                ProjectionSnapshot::Perspective => Projection::Perspective,
+                ProjectionSnapshot::Orthographic { size } => Projection::Orthographic { size },
+                ProjectionSnapshot::PixelOrthographic { pixels_per_unit, viewport_height } => {
+                    Projection::PixelOrthographic { pixels_per_unit, viewport_height }
+                }
+            },
+        }));
+    }
+}
+
+/// Sets or removes the `T` of `entity`.
+fn put<T: Component>(world: &mut World, entity: Entity, component: Option<T>) {
+    match component {
+        Some(component) => world.add_component(entity, component),
+        None => { world.remove_component::<T>(entity); }
+    }
+}
+
+impl SceneSnapshot {
+    pub fn capture(scene: &Scene, camera: &Camera, lighting: &LightingSystem) -> Self {
+        Self {
+            version: SCENE_SNAPSHOT_VERSION,
+            objects: scene
+                .objects
+                .iter()
+                .map(|o| ObjectSnapshot { transform: o.transform.to_cols_array(), visible: o.visible })
+                .collect(),
+            camera: CameraSnapshot {
+                position: camera.position.to_array(),
+                rotation: camera.rotation.to_array(),
+            },
+            lights: lighting
+                .lights
+                .iter()
+                .map(|l| LightSnapshot {
+                    position: l.position.to_array(),
+                    direction: l.direction.to_array(),
+                    color: l.color.to_array(),
+                    intensity: l.intensity,
+                    range: l.range,
+                    enabled: l.enabled,
+                })
+                .collect(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// Adds every ECS entity of `world`, by id.
+    pub fn with_world(mut self, world: &World) -> Self {
+        let mut ids = world.entities().to_vec();
+        ids.sort_unstable();
+        self.entities = ids.into_iter().map(|id| EntitySnapshot::capture(world, id)).collect();
+        self
+    }
+
+    /// Writes the saved entities into `world`. A saved id that is alive is
+    /// updated in place; the rest are created (with new ids, parents
+    /// remapped). Saved components replace the current ones and the ones the
+    /// snapshot lacks are removed; other components and entities are left
+    /// alone. Returns the entity each saved one ended up as, in order.
+    pub fn restore_world(&self, world: &mut World) -> Vec<Entity> {
+        let mut ids = HashMap::new();
+        for saved in self.entities.iter().filter(|saved| world.is_alive(saved.id)) {
+            ids.insert(saved.id, saved.id);
+        }
+        for saved in &self.entities {
+            ids.entry(saved.id).or_insert_with(|| world.create_entity());
+        }
+        self.entities
+            .iter()
+            .map(|saved| {
+                let entity = ids[&saved.id];
+                saved.restore(world, entity, &ids);
+                entity
+            })
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> ReactorResult<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize scene: {}", e)))
+    }
+
+    /// Parses a snapshot, rejecting anything that is not the current version.
+    /// Unknown fields are ignored and logged as a warning.
+    pub fn from_bytes(data: &[u8]) -> ReactorResult<Self> {
+        let invalid = |e: serde_json::Error| ReactorError::invalid_format(format!("Invalid scene snapshot: {}", e));
+        let value: Value = serde_json::from_slice(data).map_err(invalid)?;
+        let snapshot = Self::deserialize(&value).map_err(invalid)?;
+        if snapshot.version != SCENE_SNAPSHOT_VERSION {
+            return Err(ReactorError::invalid_format(format!(
+                "Unsupported scene snapshot version {} (expected {})",
+                snapshot.version, SCENE_SNAPSHOT_VERSION
+            )));
+        }
+        let known = serde_json::to_value(&snapshot)
+            .map_err(|e| ReactorError::internal(format!("Failed to serialize scene: {}", e)))?;
+        let mut unknown = Vec::new();
+        unknown_keys(&value, &known, "", &mut unknown);
+        if !unknown.is_empty() {
+            log::warn!("Scene snapshot: ignoring unknown fields {}", unknown.join(", "));
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes the snapshot to `path` (a `.reactor` file by convention).
+    pub fn save(&self, path: impl AsRef<Path>) -> ReactorResult<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes()?).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to write scene {}", path.display()), e)
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> ReactorResult<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            ReactorError::with_source(ErrorCode::IoError, format!("Failed to read scene {}", path.display()), e)
+        })?;
+        Self::from_bytes(&data)
+    }
+
+    /// Writes the snapshot back by index. Objects or lights that exist on only
+    /// one side are left alone; a count mismatch is logged, not an error.
+    pub fn restore(&self, scene: &mut Scene, camera: &mut Camera, lighting: &mut LightingSystem) {
+        if self.objects.len() != scene.objects.len() {
+            log::warn!(
+                "Scene snapshot has {} objects, scene has {}; restoring the first {}",
+                self.objects.len(),
+                scene.objects.len(),
+                self.objects.len().min(scene.objects.len())
+            );
+        }
+        for (object, saved) in scene.objects.iter_mut().zip(&self.objects) {
+            object.set_transform(Mat4::from_cols_array(&saved.transform));
+            object.visible = saved.visible;
+        }
+
+        camera.position = Vec3::from_array(self.camera.position);
+        camera.rotation = Quat::from_array(self.camera.rotation).normalize();
+
+        if self.lights.len() > lighting.lights.len() {
+            log::warn!(
+                "Scene snapshot has {} lights but only {} exist; missing lights are not recreated",
+                self.lights.len(),
+                lighting.lights.len()
+            );
+        }
+        for (light, saved) in lighting.lights.iter_mut().zip(&self.lights) {
+            light.position = Vec3::from_array(saved.position);
+            light.direction = Vec3::from_array(saved.direction);
+            light.color = Vec3::from_array(saved.color);
+            light.intensity = saved.intensity;
+            light.range = saved.range;
+            light.enabled = saved.enabled;
+        }
+    }
+}
+
+/// Paths of the keys in `input` that are missing from `known`, the same
+/// snapshot serialized back (so it only has the fields this version reads).
+pub(crate) fn unknown_keys(input: &Value, known: &Value, path: &str, out: &mut Vec<String>) {
+    match (input, known) {
+        (Value::Object(input), Value::Object(known)) => {
+            for (key, value) in input {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match known.get(key) {
+                    Some(known) => unknown_keys(value, known, &child, out),
+                    None => out.push(child),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(known)) => {
+            for (index, (value, known)) in input.iter().zip(known).enumerate() {
+                unknown_keys(value, known, &format!("{}[{}]", path, index), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SceneSnapshot {
+        SceneSnapshot {
+            version: SCENE_SNAPSHOT_VERSION,
+            objects: vec![ObjectSnapshot {
+                transform: Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)).to_cols_array(),
+                visible: false,
+            }],
+            camera: CameraSnapshot {
+                position: [0.0, 5.0, 10.0],
+                rotation: Quat::from_rotation_y(0.5).to_array(),
+            },
+            lights: vec![],
+            entities: vec![],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let snapshot = sample();
+        let bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(SceneSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn rejects_unknown_version_and_garbage() {
+        let mut snapshot = sample();
+        snapshot.version = 2;
+        let bytes = snapshot.to_bytes().unwrap();
+        assert!(SceneSnapshot::from_bytes(&bytes).is_err());
+        assert!(SceneSnapshot::from_bytes(b"{not json").is_err());
+    }
+
+    #[test]
+    fn skips_fields_from_newer_builds() {
+        let mut value = serde_json::to_value(sample()).unwrap();
+        value["editor_layout"] = serde_json::json!({ "panels": 3 });
+        value["objects"][0]["name"] = serde_json::json!("Cube");
+        let bytes = serde_json::to_vec(&value).unwrap();
+        assert_eq!(SceneSnapshot::from_bytes(&bytes).unwrap(), sample());
+
+        let known = serde_json::to_value(sample()).unwrap();
+        let mut unknown = Vec::new();
+        unknown_keys(&value, &known, "", &mut unknown);
+        unknown.sort();
+        assert_eq!(unknown, ["editor_layout", "objects[0].name"]);
+    }
+
+    #[test]
+    fn saves_and_loads_files() {
+        let path = std::env::temp_dir().join(format!("reactor_snapshot_{}.reactor", std::process::id()));
+        sample().save(&path).unwrap();
+        let loaded = SceneSnapshot::load(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded.unwrap(), sample());
+        assert_eq!(SceneSnapshot::load(&path).unwrap_err().code, ErrorCode::IoError);
+    }
+
+    #[test]
+    fn entities_round_trip_into_a_new_world() {
+        let mut world = World::new();
+        let root = world.create_entity();
+        let lamp = world.create_entity();
+        let body = world.create_entity();
+        world.add_component(root, Transform::from_translation(1.0, 2.0, 3.0));
+        world.add_component(lamp, Transform::new());
+        world.add_component(lamp, Parent(root));
+        world.add_component(lamp, Light::spot(Vec3::ZERO, Vec3::NEG_Y, Vec3::X, 3.0, 8.0, 30.0));
+        world.add_component(body, RigidBody { mass: 4.0, ..RigidBody::kinematic() });
+        world.add_component(body, Camera::orthographic(10.0, 1.5, 0.1, 50.0));
+        world.destroy_entity(root);
+        let root = world.create_entity();
+        world.add_component(root, Transform::from_translation(1.0, 2.0, 3.0));
+        world.add_component(lamp, Parent(root));
+
+        let bytes = sample().with_world(&world).to_bytes().unwrap();
+        let snapshot = SceneSnapshot::from_bytes(&bytes).unwrap();
+        let mut loaded = World::new();
+        let entities = snapshot.restore_world(&mut loaded);
+
+        // Saved ids 1, 2, 3 (root was recreated last) become 0, 1, 2.
+        assert_eq!(entities, [0, 1, 2]);
+        let (lamp, body, root) = (entities[0], entities[1], entities[2]);
+        assert_eq!(loaded.parent(lamp), Some(root));
+        assert!(loaded.world_matrix(lamp).transform_point3(Vec3::ZERO).abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-6));
+        let light = loaded.get_component::<Light>(lamp).unwrap();
+        assert_eq!((light.light_type, light.intensity, light.spot_angle), (LightType::Spot, 3.0, 30f32.to_radians()));
+        assert_eq!(loaded.get_component::<RigidBody>(body).unwrap().mass, 4.0);
+        assert!(loaded.get_component::<RigidBody>(body).unwrap().is_kinematic);
+        assert_eq!(loaded.get_component::<Camera>(body).unwrap().projection, Projection::Orthographic { size: 10.0 });
+    }
+
+    #[test]
+    fn restores_camera_and_clamps_light_count() {
+        let mut snapshot = sample();
+        snapshot.lights = vec![
+            LightSnapshot { position: [1.0, 0.0, 0.0], direction: [0.0, -1.0, 0.0], color: [1.0, 0.0, 0.0], intensity: 2.0, range: 5.0, enabled: true },
+            LightSnapshot { position: [9.0, 9.0, 9.0], direction: [0.0, -1.0, 0.0], color: [0.0, 1.0, 0.0], intensity: 1.0, range: 1.0, enabled: true },
+        ];
+        let mut scene = Scene::new();
+        let mut camera = Camera::new();
+        let mut lighting = LightingSystem::new();
+        lighting.add_light(crate::systems::lighting::Light::point(Vec3::ZERO, Vec3::ONE, 1.0, 10.0));
+
+        snapshot.restore(&mut scene, &mut camera, &mut lighting);
+
+        assert_eq!(camera.position, Vec3::new(0.0, 5.0, 10.0));
+        assert_eq!(lighting.lights.len(), 1);
+        assert_eq!(lighting.lights[0].color, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(lighting.lights[0].range, 5.0);
+    }
+}
@@ -0,0 +1,292 @@
+//! Signed distance field scenes
+//!
+//! An `SdfScene` is an ordered list of primitives folded left to right with
+//! their CSG op, starting from empty space. `Reactor::set_sdf_scene` uploads
+//! it and the forward pass raymarches it in a fullscreen draw that writes
+//! depth, so meshes and SDF geometry occlude each other.
+//!
+//! `distance` and `raymarch` evaluate the same field on the CPU as the
+//! shader does on the GPU, for picking and tests.
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec2, Vec3};
+
+/// Primitives uploaded per frame; the rest are ignored.
+pub const MAX_SDF_PRIMITIVES: usize = 64;
+
+/// Distance under which a ray counts as a hit, in `raymarch` and the shader.
+pub const SDF_HIT_EPSILON: f32 = 0.001;
+/// Sphere-tracing steps before a ray gives up.
+pub const SDF_MAX_STEPS: u32 = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SdfShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+    /// Ring in the local XZ plane around the Y axis.
+    Torus { major_radius: f32, minor_radius: f32 },
+}
+
+impl SdfShape {
+    /// Distance from `p`, in local space.
+    pub fn distance(&self, p: Vec3) -> f32 {
+        match *self {
+            Self::Sphere { radius } => p.length() - radius,
+            Self::Box { half_extents } => {
+                let q = p.abs() - half_extents;
+                q.max(Vec3::ZERO).length() + q.max_element().min(0.0)
+            }
+            Self::Torus { major_radius, minor_radius } => {
+                Vec2::new(Vec2::new(p.x, p.z).length() - major_radius, p.y).length() - minor_radius
+            }
+        }
+    }
+
+    fn gpu_params(&self) -> [f32; 4] {
+        match *self {
+            Self::Sphere { radius } => [radius, 0.0, 0.0, 0.0],
+            Self::Box { half_extents } => [half_extents.x, half_extents.y, half_extents.z, 1.0],
+            Self::Torus { major_radius, minor_radius } => [major_radius, minor_radius, 0.0, 2.0],
+        }
+    }
+}
+
+/// How a primitive combines with everything before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SdfOp {
+    Union,
+    /// Carves the primitive out of what came before.
+    Subtract,
+    Intersect,
+    /// Union blended over `k` world units.
+    SmoothUnion(f32),
+}
+
+impl SdfOp {
+    fn gpu_code(self) -> f32 {
+        match self {
+            Self::Union => 0.0,
+            Self::Subtract => 1.0,
+            Self::Intersect => 2.0,
+            Self::SmoothUnion(_) => 3.0,
+        }
+    }
+
+    /// Combines `(d, color)` so far with a primitive at `(b, b_color)`.
+    fn apply(self, d: f32, color: Vec3, b: f32, b_color: Vec3) -> (f32, Vec3) {
+        match self {
+            Self::Union => if b < d { (b, b_color) } else { (d, color) },
+            Self::Subtract => (d.max(-b), color),
+            Self::Intersect => if b > d { (b, b_color) } else { (d, color) },
+            Self::SmoothUnion(k) => {
+                let k = k.max(1e-4);
+                let h = (0.5 + 0.5 * (b - d) / k).clamp(0.0, 1.0);
+                (b + (d - b) * h - k * h * (1.0 - h), b_color.lerp(color, h))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfPrimitive {
+    pub shape: SdfShape,
+    /// Local to world. Scale should be uniform: distances are corrected by
+    /// the smallest axis scale.
+    pub transform: Mat4,
+    pub color: Vec3,
+    pub op: SdfOp,
+}
+
+impl SdfPrimitive {
+    pub fn new(shape: SdfShape, transform: Mat4, color: Vec3) -> Self {
+        Self { shape, transform, color, op: SdfOp::Union }
+    }
+
+    pub fn with_op(mut self, op: SdfOp) -> Self {
+        self.op = op;
+        self
+    }
+
+    fn scale(&self) -> f32 {
+        let (scale, _, _) = self.transform.to_scale_rotation_translation();
+        scale.min_element()
+    }
+
+    /// World-space distance from `p`.
+    pub fn distance(&self, p: Vec3) -> f32 {
+        self.shape.distance(self.transform.inverse().transform_point3(p)) * self.scale()
+    }
+
+    pub fn to_gpu_data(&self) -> SdfPrimitiveGpu {
+        let k = match self.op {
+            SdfOp::SmoothUnion(k) => k.max(1e-4),
+            _ => 0.0,
+        };
+        SdfPrimitiveGpu {
+            world_to_local: self.transform.inverse().to_cols_array(),
+            params: self.shape.gpu_params(),
+            color: [self.color.x, self.color.y, self.color.z, self.op.gpu_code()],
+            blend: [k, self.scale(), 0.0, 0.0],
+        }
+    }
+}
+
+/// std430 layout of one primitive in the SDF storage buffer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SdfPrimitiveGpu {
+    pub world_to_local: [f32; 16],
+    /// xyz: shape parameters, w: shape (0 sphere, 1 box, 2 torus).
+    pub params: [f32; 4],
+    /// rgb: color, a: op (0 union, 1 subtract, 2 intersect, 3 smooth union).
+    pub color: [f32; 4],
+    /// x: smooth union radius, y: distance scale.
+    pub blend: [f32; 4],
+}
+
+/// Result of `SdfScene::raymarch`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfHit {
+    pub distance: f32,
+    pub position: Vec3,
+    pub color: Vec3,
+    pub steps: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SdfScene {
+    pub primitives: Vec<SdfPrimitive>,
+}
+
+impl SdfScene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a primitive and returns its index.
+    pub fn add(&mut self, primitive: SdfPrimitive) -> u32 {
+        self.primitives.push(primitive);
+        (self.primitives.len() - 1) as u32
+    }
+
+    pub fn add_sphere(&mut self, center: Vec3, radius: f32, color: Vec3) -> u32 {
+        self.add(SdfPrimitive::new(SdfShape::Sphere { radius }, Mat4::from_translation(center), color))
+    }
+
+    pub fn add_box(&mut self, center: Vec3, half_extents: Vec3, color: Vec3) -> u32 {
+        self.add(SdfPrimitive::new(SdfShape::Box { half_extents }, Mat4::from_translation(center), color))
+    }
+
+    pub fn add_torus(&mut self, center: Vec3, major_radius: f32, minor_radius: f32, color: Vec3) -> u32 {
+        let shape = SdfShape::Torus { major_radius, minor_radius };
+        self.add(SdfPrimitive::new(shape, Mat4::from_translation(center), color))
+    }
+
+    pub fn get_mut(&mut self, index: u32) -> Option<&mut SdfPrimitive> {
+        self.primitives.get_mut(index as usize)
+    }
+
+    pub fn clear(&mut self) {
+        self.primitives.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.primitives.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.primitives.is_empty()
+    }
+
+    /// Distance and surface color of the combined field at `p`. Empty space
+    /// is `f32::MAX`.
+    pub fn sample(&self, p: Vec3) -> (f32, Vec3) {
+        self.primitives.iter().take(MAX_SDF_PRIMITIVES).fold((f32::MAX, Vec3::ZERO), |(d, color), primitive| {
+            primitive.op.apply(d, color, primitive.distance(p), primitive.color)
+        })
+    }
+
+    pub fn distance(&self, p: Vec3) -> f32 {
+        self.sample(p).0
+    }
+
+    /// Sphere-traces from `origin` along `direction` up to `max_distance`.
+    pub fn raymarch(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<SdfHit> {
+        let direction = direction.normalize();
+        let mut t = 0.0;
+        for step in 0..SDF_MAX_STEPS {
+            let position = origin + direction * t;
+            let (d, color) = self.sample(position);
+            if d < SDF_HIT_EPSILON {
+                return Some(SdfHit { distance: t, position, color, steps: step + 1 });
+            }
+            t += d;
+            if t > max_distance {
+                break;
+            }
+        }
+        None
+    }
+
+    /// Primitives as uploaded to the GPU (at most `MAX_SDF_PRIMITIVES`).
+    pub fn to_gpu_data(&self) -> Vec<SdfPrimitiveGpu> {
+        self.primitives.iter().take(MAX_SDF_PRIMITIVES).map(SdfPrimitive::to_gpu_data).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_distances() {
+        let mut scene = SdfScene::new();
+        scene.add_sphere(Vec3::new(0.0, 1.0, 0.0), 0.5, Vec3::ONE);
+        assert!((scene.distance(Vec3::new(0.0, 3.0, 0.0)) - 1.5).abs() < 1e-5);
+
+        let cube = SdfShape::Box { half_extents: Vec3::splat(1.0) };
+        assert!((cube.distance(Vec3::new(3.0, 0.0, 0.0)) - 2.0).abs() < 1e-5);
+        assert!((cube.distance(Vec3::ZERO) + 1.0).abs() < 1e-5, "negative inside");
+
+        let ring = SdfShape::Torus { major_radius: 2.0, minor_radius: 0.25 };
+        assert!((ring.distance(Vec3::new(2.0, 0.0, 0.0)) + 0.25).abs() < 1e-5);
+        assert!((ring.distance(Vec3::ZERO) - 1.75).abs() < 1e-5);
+
+        let scaled = SdfPrimitive::new(SdfShape::Sphere { radius: 1.0 }, Mat4::from_scale(Vec3::splat(2.0)), Vec3::ONE);
+        assert!((scaled.distance(Vec3::new(5.0, 0.0, 0.0)) - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn csg_ops_fold_in_order() {
+        let mut scene = SdfScene::new();
+        scene.add_box(Vec3::ZERO, Vec3::splat(1.0), Vec3::X);
+        let hole = scene.add_sphere(Vec3::new(1.0, 0.0, 0.0), 0.5, Vec3::Y);
+        scene.get_mut(hole).unwrap().op = SdfOp::Subtract;
+
+        let ray = scene.raymarch(Vec3::new(5.0, 0.0, 0.0), -Vec3::X, 100.0).unwrap();
+        assert!((ray.position.x - 0.5).abs() < 1e-2, "the ray enters the carved hole: {:?}", ray.position);
+        assert_eq!(ray.color, Vec3::X, "subtract keeps the box color");
+
+        let side = scene.raymarch(Vec3::new(0.0, 5.0, 0.0), -Vec3::Y, 100.0).unwrap();
+        assert!((side.distance - 4.0).abs() < 1e-2);
+        assert!(scene.raymarch(Vec3::new(0.0, 5.0, 0.0), Vec3::Y, 100.0).is_none());
+    }
+
+    #[test]
+    fn gpu_data_matches_the_cpu_field() {
+        let mut scene = SdfScene::new();
+        scene.add_torus(Vec3::new(1.0, 2.0, 3.0), 1.0, 0.2, Vec3::ONE);
+        scene.add(SdfPrimitive::new(SdfShape::Sphere { radius: 1.0 }, Mat4::IDENTITY, Vec3::ONE).with_op(SdfOp::SmoothUnion(0.5)));
+        for _ in 0..MAX_SDF_PRIMITIVES {
+            scene.add_sphere(Vec3::ZERO, 0.1, Vec3::ONE);
+        }
+
+        let gpu = scene.to_gpu_data();
+        assert_eq!(gpu.len(), MAX_SDF_PRIMITIVES);
+        assert_eq!(std::mem::size_of::<SdfPrimitiveGpu>(), 112);
+        assert_eq!(gpu[0].params[3], 2.0);
+        assert_eq!(Vec3::from_slice(&gpu[0].world_to_local[12..15]), Vec3::new(-1.0, -2.0, -3.0));
+        assert_eq!(gpu[1].color[3], 3.0);
+        assert_eq!(gpu[1].blend[0], 0.5);
+    }
+}
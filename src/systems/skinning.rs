@@ -0,0 +1,230 @@
+//! Skeletal animation for glTF skins.
+//!
+//! A `Skeleton` keeps the rest pose of every node in the document plus the
+//! skin's joint list, so posing it is a flat pass: sample the animation
+//! channels into local TRS, compose them up the parent chain and multiply
+//! each joint by its inverse-bind matrix. The result is the joint matrix
+//! palette a skinned vertex shader indexes with `JOINTS_0`.
+
+use crate::resources::gltf_loader::{
+    AnimationInterpolation, AnimationPath, AnimationSampler, GltfAnimation, GltfModel,
+    GltfNodeTransform, GltfSkin,
+};
+use glam::{Mat4, Quat, Vec3, Vec4};
+
+#[derive(Clone, Debug)]
+pub struct Skeleton {
+    nodes: Vec<GltfNodeTransform>,
+    joints: Vec<usize>,
+    inverse_bind: Vec<Mat4>,
+}
+
+impl Skeleton {
+    /// `nodes` must be indexed by glTF node index, as `GltfModel::nodes` is.
+    /// Joints pointing past `nodes` are posed at the identity.
+    pub fn new(nodes: Vec<GltfNodeTransform>, skin: &GltfSkin) -> Self {
+        let mut inverse_bind = skin.inverse_bind_matrices.clone();
+        inverse_bind.resize(skin.joints.len(), Mat4::IDENTITY);
+        Self { nodes, joints: skin.joints.clone(), inverse_bind }
+    }
+
+    pub fn from_model(model: &GltfModel, skin_index: usize) -> Option<Self> {
+        model.skins.get(skin_index).map(|skin| Self::new(model.nodes.clone(), skin))
+    }
+
+    pub fn joint_count(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// Palette for the rest pose (identity matrices for a well-formed skin).
+    pub fn rest_palette(&self) -> Vec<Mat4> {
+        self.palette(None, 0.0)
+    }
+
+    /// Joint matrices with `animation` sampled at `time` seconds. Channels
+    /// on nodes outside the skeleton and morph-weight channels are ignored.
+    pub fn palette(&self, animation: Option<&GltfAnimation>, time: f32) -> Vec<Mat4> {
+        let mut local: Vec<(Vec3, Quat, Vec3)> =
+            self.nodes.iter().map(|n| (n.translation, n.rotation, n.scale)).collect();
+
+        if let Some(animation) = animation {
+            for channel in &animation.channels {
+                let Some(node) = local.get_mut(channel.node_index) else { continue };
+                let Some(sampler) = animation.samplers.get(channel.sampler_index) else { continue };
+                let Some(value) = sample_channel(sampler, time, &channel.path) else { continue };
+                match channel.path {
+                    AnimationPath::Translation => node.0 = value.truncate(),
+                    AnimationPath::Rotation => node.1 = Quat::from_vec4(value).normalize(),
+                    AnimationPath::Scale => node.2 = value.truncate(),
+                    AnimationPath::Weights => {}
+                }
+            }
+        }
+
+        let mut global: Vec<Option<Mat4>> = vec![None; local.len()];
+        self.joints
+            .iter()
+            .zip(&self.inverse_bind)
+            .map(|(&joint, inverse_bind)| self.global_transform(joint, &local, &mut global, 0) * *inverse_bind)
+            .collect()
+    }
+
+    fn global_transform(&self, node: usize, local: &[(Vec3, Quat, Vec3)], cache: &mut [Option<Mat4>], depth: usize) -> Mat4 {
+        let Some(&(t, r, s)) = local.get(node) else { return Mat4::IDENTITY };
+        if let Some(m) = cache[node] {
+            return m;
+        }
+        let own = Mat4::from_scale_rotation_translation(s, r, t);
+        // glTF forbids cycles; the depth check only guards malformed files.
+        let m = match self.nodes[node].parent {
+            Some(parent) if depth < local.len() => self.global_transform(parent, local, cache, depth + 1) * own,
+            _ => own,
+        };
+        cache[node] = Some(m);
+        m
+    }
+}
+
+/// Samples a glTF sampler at `time`, clamped to its key range. Rotations are
+/// returned as `xyzw` and interpolated with `slerp`; translation and scale
+/// leave `w` at 0.
+pub fn sample_channel(sampler: &AnimationSampler, time: f32, path: &AnimationPath) -> Option<Vec4> {
+    let width = match path {
+        AnimationPath::Translation | AnimationPath::Scale => 3,
+        AnimationPath::Rotation => 4,
+        AnimationPath::Weights => return None,
+    };
+    let keys = &sampler.input;
+    let (&first, &last) = (keys.first()?, keys.last()?);
+    let cubic = sampler.interpolation == AnimationInterpolation::CubicSpline;
+    let stride = if cubic { width * 3 } else { width };
+    if sampler.output.len() < keys.len() * stride {
+        return None;
+    }
+    // CubicSpline stores (in-tangent, value, out-tangent) per key.
+    let value = |k: usize, part: usize| read_vec4(&sampler.output, k * stride + part * width, width);
+    let value_part = if cubic { 1 } else { 0 };
+
+    let time = time.clamp(first, last);
+    let k = keys.partition_point(|&t| t <= time).saturating_sub(1).min(keys.len() - 1);
+    if k + 1 >= keys.len() || sampler.interpolation == AnimationInterpolation::Step {
+        return Some(value(k, value_part));
+    }
+
+    let dt = keys[k + 1] - keys[k];
+    let t = if dt > 0.0 { (time - keys[k]) / dt } else { 0.0 };
+    let (p0, p1) = (value(k, value_part), value(k + 1, value_part));
+
+    let result = match sampler.interpolation {
+        AnimationInterpolation::CubicSpline => {
+            let m0 = value(k, 2) * dt;
+            let m1 = value(k + 1, 0) * dt;
+            let (t2, t3) = (t * t, t * t * t);
+            p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+                + m0 * (t3 - 2.0 * t2 + t)
+                + p1 * (-2.0 * t3 + 3.0 * t2)
+                + m1 * (t3 - t2)
+        }
+        _ if width == 4 => Vec4::from(Quat::from_vec4(p0).slerp(Quat::from_vec4(p1), t)),
+        _ => p0.lerp(p1, t),
+    };
+    Some(if width == 4 { Vec4::from(Quat::from_vec4(result).normalize()) } else { result })
+}
+
+fn read_vec4(data: &[f32], offset: usize, width: usize) -> Vec4 {
+    let mut v = [0.0; 4];
+    v[..width].copy_from_slice(&data[offset..offset + width]);
+    Vec4::from_array(v)
+}
+
+/// CPU reference of what the skinning vertex shader does to a position.
+pub fn skin_position(palette: &[Mat4], position: Vec3, joints: [u32; 4], weights: [f32; 4]) -> Vec3 {
+    let skin: Mat4 = joints
+        .iter()
+        .zip(weights)
+        .filter(|(_, w)| *w != 0.0)
+        .map(|(&j, w)| palette.get(j as usize).copied().unwrap_or(Mat4::IDENTITY) * w)
+        .fold(Mat4::ZERO, |acc, m| acc + m);
+    skin.transform_point3(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::gltf_loader::AnimationChannel;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn node(name: &str, parent: Option<usize>, translation: Vec3) -> GltfNodeTransform {
+        GltfNodeTransform { name: name.into(), parent, translation, rotation: Quat::IDENTITY, scale: Vec3::ONE }
+    }
+
+    /// Shoulder at the origin and an elbow one unit up, like the simple skin
+    /// sample in the glTF tutorials.
+    fn two_bone_arm() -> Skeleton {
+        let nodes = vec![node("shoulder", None, Vec3::ZERO), node("elbow", Some(0), Vec3::Y)];
+        let skin = GltfSkin {
+            name: "arm".into(),
+            joints: vec![0, 1],
+            inverse_bind_matrices: vec![Mat4::IDENTITY, Mat4::from_translation(-Vec3::Y)],
+        };
+        Skeleton::new(nodes, &skin)
+    }
+
+    fn raise_shoulder() -> GltfAnimation {
+        let quarter = Quat::from_rotation_z(FRAC_PI_2);
+        GltfAnimation {
+            name: "raise".into(),
+            duration: 1.0,
+            channels: vec![AnimationChannel { node_index: 0, sampler_index: 0, path: AnimationPath::Rotation }],
+            samplers: vec![AnimationSampler {
+                input: vec![0.0, 1.0],
+                output: [Quat::IDENTITY.to_array(), quarter.to_array()].concat(),
+                interpolation: AnimationInterpolation::Linear,
+            }],
+        }
+    }
+
+    #[test]
+    fn rest_pose_palette_is_identity() {
+        for m in two_bone_arm().rest_palette() {
+            assert!(m.abs_diff_eq(Mat4::IDENTITY, 1e-6));
+        }
+    }
+
+    #[test]
+    fn rotating_the_parent_carries_the_child_bone() {
+        let arm = two_bone_arm();
+        let palette = arm.palette(Some(&raise_shoulder()), 1.0);
+
+        let hand = skin_position(&palette, Vec3::new(0.0, 2.0, 0.0), [1, 0, 0, 0], [1.0, 0.0, 0.0, 0.0]);
+        assert!(hand.abs_diff_eq(Vec3::new(-2.0, 0.0, 0.0), 1e-5), "{hand}");
+
+        // Halfway through, a vertex split between both bones follows each by half.
+        let palette = arm.palette(Some(&raise_shoulder()), 0.5);
+        let elbow = skin_position(&palette, Vec3::Y, [0, 1, 0, 0], [0.5, 0.5, 0.0, 0.0]);
+        let expected = Quat::from_rotation_z(FRAC_PI_2 * 0.5) * Vec3::Y;
+        assert!(elbow.abs_diff_eq(expected, 1e-5), "{elbow}");
+    }
+
+    #[test]
+    fn step_and_cubic_samplers_hold_and_hit_their_keys() {
+        let step = AnimationSampler {
+            input: vec![0.0, 1.0],
+            output: vec![0.0, 0.0, 0.0, 4.0, 0.0, 0.0],
+            interpolation: AnimationInterpolation::Step,
+        };
+        assert_eq!(sample_channel(&step, 0.9, &AnimationPath::Translation), Some(Vec4::ZERO));
+        assert_eq!(sample_channel(&step, 5.0, &AnimationPath::Translation), Some(Vec4::new(4.0, 0.0, 0.0, 0.0)));
+
+        // Flat tangents: the curve eases but still passes through the midpoint.
+        let cubic = AnimationSampler {
+            input: vec![0.0, 1.0],
+            output: [[0.0; 9].as_slice(), &[0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0]].concat(),
+            interpolation: AnimationInterpolation::CubicSpline,
+        };
+        let mid = sample_channel(&cubic, 0.5, &AnimationPath::Translation).unwrap();
+        assert!((mid.x - 1.0).abs() < 1e-6);
+        let early = sample_channel(&cubic, 0.25, &AnimationPath::Translation).unwrap();
+        assert!(early.x < 0.5);
+    }
+}
@@ -0,0 +1,261 @@
+//! Undo history for entity transforms
+//!
+//! Every user action on a `Transform` becomes one `TransformCommand`
+//! (`before` / `after`). A gizmo drag is bracketed by `begin_drag` and
+//! `end_drag`, so it is a single undo step however many frames it wrote the
+//! transform. Field edits go through `record`: consecutive edits of the same
+//! entity and label merge until `seal` is called (on focus loss or Enter),
+//! so typing "12.5" is one step, not four.
+//!
+//! Inspector component edits (`ComponentCommand`) share the same stack via
+//! `push_component`, with the same merging rule.
+
+use crate::scene::ecs::{Entity, World};
+use crate::scene::transform::Transform;
+use crate::systems::inspector::ComponentCommand;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TransformCommand {
+    pub entity: Entity,
+    pub before: Transform,
+    pub after: Transform,
+    pub label: &'static str,
+}
+
+impl TransformCommand {
+    /// Text for the status bar / menu, e.g. "Move entity 3".
+    pub fn description(&self) -> String {
+        format!("{} entity {}", self.label, self.entity)
+    }
+}
+
+enum Step {
+    Transform(TransformCommand),
+    Component(ComponentCommand),
+}
+
+impl Step {
+    fn description(&self) -> String {
+        match self {
+            Step::Transform(command) => command.description(),
+            Step::Component(command) => command.description(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TransformHistory {
+    undo: Vec<Step>,
+    redo: Vec<Step>,
+    /// Drag in progress: entity and transform at press.
+    drag: Option<(Entity, Transform)>,
+    /// The top of `undo` still absorbs `record`s of the same entity and label.
+    open: bool,
+}
+
+impl TransformHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gizmo press: remembers the transform the drag starts from.
+    pub fn begin_drag(&mut self, entity: Entity, before: Transform) {
+        self.drag = Some((entity, before));
+    }
+
+    /// Gizmo release: pushes the whole drag as one "Move" step. Returns
+    /// `false` when no drag was started or the transform did not change.
+    pub fn end_drag(&mut self, after: Transform) -> bool {
+        let Some((entity, before)) = self.drag.take() else { return false };
+        if same(&before, &after) {
+            return false;
+        }
+        self.push(Step::Transform(TransformCommand { entity, before, after, label: "Move" }));
+        self.open = false;
+        true
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// A field edit. Merges into the previous step while it is still open
+    /// and targets the same entity and label.
+    pub fn record(&mut self, entity: Entity, label: &'static str, before: Transform, after: Transform) {
+        if self.open {
+            if let Some(Step::Transform(top)) = self.undo.last_mut() {
+                if top.entity == entity && top.label == label {
+                    top.after = after;
+                    return;
+                }
+            }
+        }
+        if same(&before, &after) {
+            return;
+        }
+        self.push(Step::Transform(TransformCommand { entity, before, after, label }));
+        self.open = true;
+    }
+
+    /// An already applied component add/remove/edit. Merges into the previous
+    /// step while it is still open and has the same label and entities.
+    pub fn push_component(&mut self, command: ComponentCommand) {
+        if self.open {
+            if let Some(Step::Component(top)) = self.undo.last_mut() {
+                if top.merge(&command) {
+                    return;
+                }
+            }
+        }
+        self.push(Step::Component(command));
+        self.open = true;
+    }
+
+    /// Ends the current edit: the next `record` starts a new step.
+    pub fn seal(&mut self) {
+        self.open = false;
+    }
+
+    /// Restores `before` of the last step. Returns its description.
+    pub fn undo(&mut self, world: &mut World) -> Option<String> {
+        let step = self.undo.pop()?;
+        self.open = false;
+        match &step {
+            Step::Transform(command) => apply(world, command.entity, command.before),
+            Step::Component(command) => command.undo(world),
+        }
+        let description = step.description();
+        self.redo.push(step);
+        Some(description)
+    }
+
+    pub fn redo(&mut self, world: &mut World) -> Option<String> {
+        let step = self.redo.pop()?;
+        match &step {
+            Step::Transform(command) => apply(world, command.entity, command.after),
+            Step::Component(command) => command.redo(world),
+        }
+        let description = step.description();
+        self.undo.push(step);
+        Some(description)
+    }
+
+    pub fn undo_description(&self) -> Option<String> {
+        self.undo.last().map(Step::description)
+    }
+
+    pub fn redo_description(&self) -> Option<String> {
+        self.redo.last().map(Step::description)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Drops every step, e.g. after loading another scene.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn push(&mut self, step: Step) {
+        self.undo.push(step);
+        self.redo.clear();
+    }
+}
+
+fn same(a: &Transform, b: &Transform) -> bool {
+    a.position == b.position && a.rotation == b.rotation && a.scale == b.scale
+}
+
+fn apply(world: &mut World, entity: Entity, transform: Transform) {
+    match world.get_component_mut::<Transform>(entity) {
+        Some(current) => *current = transform,
+        None => log::warn!("Undo/redo: entity {} no longer has a Transform", entity),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    fn world_with_entity() -> (World, Entity) {
+        let mut world = World::new();
+        let entity = world.create_entity();
+        world.add_component(entity, Transform::new());
+        (world, entity)
+    }
+
+    fn position(world: &World, entity: Entity) -> Vec3 {
+        world.get_component::<Transform>(entity).unwrap().position
+    }
+
+    #[test]
+    fn a_drag_is_one_undo_step() {
+        let (mut world, entity) = world_with_entity();
+        let mut history = TransformHistory::new();
+        history.begin_drag(entity, Transform::new());
+        for x in 1..=30 {
+            *world.get_component_mut::<Transform>(entity).unwrap() = Transform::from_translation(x as f32, 0.0, 0.0);
+        }
+        assert!(history.end_drag(Transform::from_translation(30.0, 0.0, 0.0)));
+        assert_eq!(history.undo_description().as_deref(), Some("Move entity 0"));
+
+        assert_eq!(history.undo(&mut world).as_deref(), Some("Move entity 0"));
+        assert_eq!(position(&world, entity), Vec3::ZERO);
+        assert!(!history.can_undo());
+
+        assert_eq!(history.redo(&mut world).as_deref(), Some("Move entity 0"));
+        assert_eq!(position(&world, entity), Vec3::new(30.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn a_click_without_movement_records_nothing() {
+        let mut history = TransformHistory::new();
+        history.begin_drag(0, Transform::new());
+        assert!(!history.end_drag(Transform::new()));
+        assert!(!history.end_drag(Transform::new()), "no drag in progress");
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn field_edits_coalesce_until_sealed() {
+        let (mut world, entity) = world_with_entity();
+        let mut history = TransformHistory::new();
+        let typed = |x: f32| Transform::from_translation(x, 0.0, 0.0);
+        history.record(entity, "Edit position", Transform::new(), typed(1.0));
+        history.record(entity, "Edit position", typed(1.0), typed(12.0));
+        history.record(entity, "Edit position", typed(12.0), typed(12.5));
+        history.seal();
+        history.record(entity, "Edit position", typed(12.5), typed(3.0));
+        history.record(entity, "Edit scale", typed(3.0), typed(3.0).with_scale(Vec3::splat(2.0)));
+
+        assert_eq!(history.undo_description().as_deref(), Some("Edit scale entity 0"));
+        history.undo(&mut world);
+        assert_eq!(history.undo(&mut world).as_deref(), Some("Edit position entity 0"));
+        assert_eq!(position(&world, entity), Vec3::new(12.5, 0.0, 0.0));
+        history.undo(&mut world);
+        assert_eq!(position(&world, entity), Vec3::ZERO);
+        assert!(!history.can_undo());
+        assert_eq!(history.redo_description().as_deref(), Some("Edit position entity 0"));
+    }
+
+    #[test]
+    fn a_new_step_clears_redo() {
+        let (mut world, entity) = world_with_entity();
+        let mut history = TransformHistory::new();
+        history.begin_drag(entity, Transform::new());
+        history.end_drag(Transform::from_translation(1.0, 0.0, 0.0));
+        history.undo(&mut world);
+        assert!(history.can_redo());
+
+        history.begin_drag(entity, Transform::new());
+        history.end_drag(Transform::from_translation(0.0, 2.0, 0.0));
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(&mut world), None);
+    }
+}
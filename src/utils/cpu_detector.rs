@@ -35,8 +35,8 @@ impl CPUDetector {
             ("Low Power: Limit background threads".to_string(), 1)
         };
 
-        println!("Detected CPU: {} Logical Cores", logical_cores);
-        println!("Recommendation: {}", recommendation);
+        log::info!("Detected CPU: {} Logical Cores", logical_cores);
+        log::info!("Recommendation: {}", recommendation);
 
         CPUInfo {
             logical_cores,
@@ -2,16 +2,87 @@ use crate::core::error::ReactorResult;
 use ash::vk;
 use ash::Instance;
 use std::ffi::CStr;
+use std::sync::Mutex;
+
+/// Overrides `set_gpu_preference`: a device index (`REACTOR_GPU=1`) or part
+/// of its name (`REACTOR_GPU=nvidia`).
+const ENV_GPU: &str = "REACTOR_GPU";
+
+/// Extensions `VulkanContext` always enables; devices without them are skipped.
+const REQUIRED_EXTENSIONS: [&CStr; 2] = [ash::khr::swapchain::NAME, ash::khr::dynamic_rendering::NAME];
+
+static GPU_PREFERENCE: Mutex<GpuPreference> = Mutex::new(GpuPreference::Auto);
 
 #[derive(Debug, Clone)]
 pub struct GPUInfo {
     pub device: vk::PhysicalDevice,
+    /// Position in `vkEnumeratePhysicalDevices`, the value `GpuPreference::Index` takes.
+    pub index: usize,
     pub name: String,
     pub device_type: vk::PhysicalDeviceType,
+    pub vendor_id: u32,
+    pub vendor: &'static str,
+    /// Driver version decoded the way the vendor numbers it.
+    pub driver_version: String,
+    pub api_version: u32,
     pub score: u32,
     pub queue_family_index: u32,
     pub vram_mb: u64,
     pub supports_ray_tracing: bool,
+    /// Has a graphics (and, with a window, present) queue and the required
+    /// extensions. Unsuitable devices are listed but never selected.
+    pub suitable: bool,
+}
+
+/// Which device `VulkanContext` should pick when there is more than one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum GpuPreference {
+    /// Highest score: discrete over integrated, then VRAM and ray tracing.
+    #[default]
+    Auto,
+    Index(usize),
+    /// Case-insensitive substring of the device name.
+    Name(String),
+}
+
+impl GpuPreference {
+    /// `"1"` is an index, anything else a name; empty or `"auto"` is `Auto`.
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        if value.is_empty() || value.eq_ignore_ascii_case("auto") {
+            Self::Auto
+        } else if let Ok(index) = value.parse() {
+            Self::Index(index)
+        } else {
+            Self::Name(value.to_string())
+        }
+    }
+}
+
+/// Device preference for contexts created from now on (the app runner sets
+/// it from `ReactorConfig::gpu`). `REACTOR_GPU` overrides it.
+pub fn set_gpu_preference(preference: GpuPreference) {
+    *GPU_PREFERENCE.lock().unwrap_or_else(|e| e.into_inner()) = preference;
+}
+
+pub fn gpu_preference() -> GpuPreference {
+    match std::env::var(ENV_GPU) {
+        Ok(value) => GpuPreference::parse(&value),
+        Err(_) => GPU_PREFERENCE.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
+/// Every device found, and the one chosen.
+#[derive(Debug, Clone)]
+pub struct GpuSelection {
+    pub gpus: Vec<GPUInfo>,
+    pub selected: usize,
+}
+
+impl GpuSelection {
+    pub fn selected(&self) -> &GPUInfo {
+        &self.gpus[self.selected]
+    }
 }
 
 pub struct GPUDetector;
@@ -21,102 +92,102 @@ impl GPUDetector {
         instance: &Instance,
         surface_loader: &ash::khr::surface::Instance,
         surface: vk::SurfaceKHR,
-    ) -> ReactorResult<GPUInfo> {
+    ) -> ReactorResult<GpuSelection> {
+        Self::detect_with(instance, |pdevice, family| unsafe {
+            surface_loader
+                .get_physical_device_surface_support(pdevice, family, surface)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Like `detect`, without requiring presentation support. CPU
+    /// implementations (lavapipe/llvmpipe) stay eligible, ranked last.
+    pub fn detect_headless(instance: &Instance) -> ReactorResult<GpuSelection> {
+        Self::detect_with(instance, |_, _| true)
+    }
+
+    fn detect_with(
+        instance: &Instance,
+        supports_present: impl Fn(vk::PhysicalDevice, u32) -> bool,
+    ) -> ReactorResult<GpuSelection> {
         let pdevices = unsafe { instance.enumerate_physical_devices()? };
 
-        let mut candidates = Vec::new();
-
-        println!("Detecting GPUs...");
-
-        for pdevice in pdevices {
-            let props = unsafe { instance.get_physical_device_properties(pdevice) };
-            let queue_families =
-                unsafe { instance.get_physical_device_queue_family_properties(pdevice) };
-
-            // Check queue support (Graphics + Present)
-            let queue_index = queue_families.iter().enumerate().position(|(i, info)| {
-                let supports_graphic = info.queue_flags.contains(vk::QueueFlags::GRAPHICS);
-                let supports_surface = unsafe {
-                    surface_loader
-                        .get_physical_device_surface_support(pdevice, i as u32, surface)
-                        .unwrap_or(false)
-                };
-                supports_graphic && supports_surface
-            });
-
-            if let Some(index) = queue_index {
-                let mut score = 0;
-
-                // Prefer Discrete GPU
-                match props.device_type {
-                    vk::PhysicalDeviceType::DISCRETE_GPU => score += 10000,
-                    vk::PhysicalDeviceType::INTEGRATED_GPU => score += 1000,
-                    vk::PhysicalDeviceType::VIRTUAL_GPU => score += 500,
-                    vk::PhysicalDeviceType::CPU => score += 100,
-                    _ => {}
-                }
-
-                // Add score for memory
-                let memory_props =
-                    unsafe { instance.get_physical_device_memory_properties(pdevice) };
-                let mut vram = 0u64;
-                for i in 0..memory_props.memory_heap_count {
-                    let heap = memory_props.memory_heaps[i as usize];
-                    if heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL) {
-                        vram += heap.size;
-                    }
-                }
-                let vram_mb = vram / (1024 * 1024);
-                score += (vram_mb / 1024) as u32 * 100; // 100 points per GB
-
-                // Check ray tracing support
-                let extensions = unsafe { instance.enumerate_device_extension_properties(pdevice) };
-                let supports_ray_tracing = extensions
-                    .map(|exts| {
-                        exts.iter().any(|ext| {
-                            let name = unsafe { CStr::from_ptr(ext.extension_name.as_ptr()) };
-                            name.to_str()
-                                .map(|s| s == "VK_KHR_ray_tracing_pipeline")
-                                .unwrap_or(false)
-                        })
-                    })
-                    .unwrap_or(false);
-
-                if supports_ray_tracing {
-                    score += 500;
-                }
-
-                let name = unsafe {
-                    CStr::from_ptr(props.device_name.as_ptr())
-                        .to_string_lossy()
-                        .into_owned()
-                };
-
-                println!(
-                    "Found GPU: {} (Score: {}, Type: {:?})",
-                    name, score, props.device_type
-                );
-
-                candidates.push(GPUInfo {
-                    device: pdevice,
-                    name,
-                    device_type: props.device_type,
-                    score,
-                    queue_family_index: index as u32,
-                    vram_mb,
-                    supports_ray_tracing,
-                });
+        log::info!("Detecting GPUs...");
+
+        let gpus: Vec<GPUInfo> = pdevices
+            .into_iter()
+            .enumerate()
+            .map(|(index, pdevice)| Self::inspect(instance, pdevice, index, &supports_present))
+            .collect();
+
+        for gpu in &gpus {
+            log::info!(
+                "Found GPU {}: {} ({}, {:?}, driver {}, {} MB, score {}{})",
+                gpu.index, gpu.name, gpu.vendor, gpu.device_type, gpu.driver_version, gpu.vram_mb, gpu.score,
+                if gpu.suitable { "" } else { ", unsuitable" }
+            );
+        }
+
+        match select(&gpus, &gpu_preference()) {
+            Some(selected) => {
+                log::info!("Selected GPU: {}", gpus[selected].name);
+                Ok(GpuSelection { gpus, selected })
             }
+            None => Err("No suitable GPU found (Must support Graphics and Presentation)".into()),
         }
+    }
+
+    fn inspect(
+        instance: &Instance,
+        pdevice: vk::PhysicalDevice,
+        index: usize,
+        supports_present: &impl Fn(vk::PhysicalDevice, u32) -> bool,
+    ) -> GPUInfo {
+        let props = unsafe { instance.get_physical_device_properties(pdevice) };
+        let queue_families = unsafe { instance.get_physical_device_queue_family_properties(pdevice) };
 
-        // Sort by score descending
-        candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.score));
+        // Check queue support (Graphics + Present)
+        let queue_index = queue_families.iter().enumerate().position(|(i, info)| {
+            let supports_graphic = info.queue_flags.contains(vk::QueueFlags::GRAPHICS);
+            supports_graphic && supports_present(pdevice, i as u32)
+        });
 
-        if let Some(best) = candidates.first() {
-            println!("Selected GPU: {}", best.name);
-            Ok(best.clone())
-        } else {
-            Err("No suitable GPU found (Must support Graphics and Presentation)".into())
+        let extensions = unsafe { instance.enumerate_device_extension_properties(pdevice) }.unwrap_or_default();
+        let has_extension = |wanted: &CStr| {
+            extensions
+                .iter()
+                .any(|ext| ext.extension_name_as_c_str().is_ok_and(|name| name == wanted))
+        };
+        let has_required = REQUIRED_EXTENSIONS.iter().all(|&name| has_extension(name));
+        let supports_ray_tracing = has_extension(ash::khr::ray_tracing_pipeline::NAME);
+
+        let memory_props = unsafe { instance.get_physical_device_memory_properties(pdevice) };
+        let vram: u64 = memory_props.memory_heaps[..memory_props.memory_heap_count as usize]
+            .iter()
+            .filter(|heap| heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+            .map(|heap| heap.size)
+            .sum();
+        let vram_mb = vram / (1024 * 1024);
+
+        let name = props
+            .device_name_as_c_str()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        GPUInfo {
+            device: pdevice,
+            index,
+            name,
+            device_type: props.device_type,
+            vendor_id: props.vendor_id,
+            vendor: vendor_name(props.vendor_id),
+            driver_version: driver_version_string(props.vendor_id, props.driver_version),
+            api_version: props.api_version,
+            score: score(props.device_type, vram_mb, supports_ray_tracing),
+            queue_family_index: queue_index.unwrap_or(0) as u32,
+            vram_mb,
+            supports_ray_tracing,
+            suitable: queue_index.is_some() && has_required,
         }
     }
 
@@ -140,3 +211,141 @@ impl GPUDetector {
             .unwrap_or_default()
     }
 }
+
+fn score(device_type: vk::PhysicalDeviceType, vram_mb: u64, supports_ray_tracing: bool) -> u32 {
+    // Prefer Discrete GPU
+    let mut score = match device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => 10000,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => 1000,
+        vk::PhysicalDeviceType::VIRTUAL_GPU => 500,
+        vk::PhysicalDeviceType::CPU => 100,
+        _ => 0,
+    };
+    score += (vram_mb / 1024) as u32 * 100; // 100 points per GB
+    if supports_ray_tracing {
+        score += 500;
+    }
+    score
+}
+
+/// Index into `gpus` of the preferred device, or of the best-scoring one when
+/// the preference is `Auto` or matches no suitable device.
+fn select(gpus: &[GPUInfo], preference: &GpuPreference) -> Option<usize> {
+    let preferred = match preference {
+        GpuPreference::Auto => None,
+        GpuPreference::Index(index) => gpus.iter().position(|gpu| gpu.index == *index),
+        GpuPreference::Name(part) => {
+            let part = part.to_lowercase();
+            gpus.iter().position(|gpu| gpu.suitable && gpu.name.to_lowercase().contains(&part))
+        }
+    };
+    match preferred {
+        Some(found) if gpus[found].suitable => return Some(found),
+        Some(found) => log::warn!("GPU {:?} ({}) is not suitable; selecting automatically", preference, gpus[found].name),
+        None if *preference != GpuPreference::Auto => {
+            log::warn!("No GPU matches {:?} ({} found); selecting automatically", preference, gpus.len());
+        }
+        None => {}
+    }
+    // The first device wins ties, as with the enumeration order before.
+    gpus.iter()
+        .enumerate()
+        .filter(|(_, gpu)| gpu.suitable)
+        .max_by_key(|(i, gpu)| (gpu.score, std::cmp::Reverse(*i)))
+        .map(|(i, _)| i)
+}
+
+fn vendor_name(vendor_id: u32) -> &'static str {
+    match vendor_id {
+        0x10DE => "NVIDIA",
+        0x1002 => "AMD",
+        0x8086 => "Intel",
+        0x13B5 => "ARM",
+        0x5143 => "Qualcomm",
+        0x106B => "Apple",
+        0x1010 => "ImgTec",
+        0x10005 => "Mesa",
+        _ => "Unknown",
+    }
+}
+
+/// NVIDIA packs 10.8.8.6 bits and Intel on Windows 18.14; everyone else uses
+/// `VK_MAKE_API_VERSION`.
+fn driver_version_string(vendor_id: u32, version: u32) -> String {
+    match vendor_id {
+        0x10DE => format!("{}.{}.{}.{}", version >> 22, (version >> 14) & 0xFF, (version >> 6) & 0xFF, version & 0x3F),
+        0x8086 if cfg!(windows) => format!("{}.{}", version >> 14, version & 0x3FFF),
+        _ => format!(
+            "{}.{}.{}",
+            vk::api_version_major(version),
+            vk::api_version_minor(version),
+            vk::api_version_patch(version)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(index: usize, name: &str, device_type: vk::PhysicalDeviceType, vram_mb: u64, suitable: bool) -> GPUInfo {
+        GPUInfo {
+            device: vk::PhysicalDevice::null(),
+            index,
+            name: name.to_string(),
+            device_type,
+            vendor_id: 0,
+            vendor: "Unknown",
+            driver_version: String::new(),
+            api_version: 0,
+            score: score(device_type, vram_mb, false),
+            queue_family_index: 0,
+            vram_mb,
+            supports_ray_tracing: false,
+            suitable,
+        }
+    }
+
+    fn laptop() -> Vec<GPUInfo> {
+        vec![
+            gpu(0, "Intel(R) UHD Graphics", vk::PhysicalDeviceType::INTEGRATED_GPU, 16384, true),
+            gpu(1, "NVIDIA GeForce RTX 4060 Laptop GPU", vk::PhysicalDeviceType::DISCRETE_GPU, 8192, true),
+            gpu(2, "llvmpipe (LLVM 17.0.6, 256 bits)", vk::PhysicalDeviceType::CPU, 0, false),
+        ]
+    }
+
+    #[test]
+    fn auto_prefers_the_discrete_gpu_over_a_bigger_integrated_one() {
+        assert_eq!(select(&laptop(), &GpuPreference::Auto), Some(1));
+    }
+
+    #[test]
+    fn preference_by_index_or_name_overrides_the_score() {
+        assert_eq!(select(&laptop(), &GpuPreference::Index(0)), Some(0));
+        assert_eq!(select(&laptop(), &GpuPreference::parse("uhd")), Some(0));
+    }
+
+    #[test]
+    fn invalid_or_unsuitable_preference_falls_back_to_auto() {
+        assert_eq!(select(&laptop(), &GpuPreference::Index(7)), Some(1));
+        assert_eq!(select(&laptop(), &GpuPreference::Index(2)), Some(1));
+        assert_eq!(select(&laptop(), &GpuPreference::Name("radeon".into())), Some(1));
+        assert_eq!(select(&laptop()[2..], &GpuPreference::Auto), None);
+    }
+
+    #[test]
+    fn preference_parses_indices_names_and_auto() {
+        assert_eq!(GpuPreference::parse(" 1 "), GpuPreference::Index(1));
+        assert_eq!(GpuPreference::parse("RTX"), GpuPreference::Name("RTX".into()));
+        assert_eq!(GpuPreference::parse("Auto"), GpuPreference::Auto);
+        assert_eq!(GpuPreference::parse(""), GpuPreference::Auto);
+    }
+
+    #[test]
+    fn nvidia_driver_versions_use_their_own_packing() {
+        // 551.86 según el panel de NVIDIA.
+        let packed = (551 << 22) | (86 << 14);
+        assert_eq!(driver_version_string(0x10DE, packed), "551.86.0.0");
+        assert_eq!(driver_version_string(0x1002, vk::make_api_version(0, 2, 0, 279)), "2.0.279");
+    }
+}
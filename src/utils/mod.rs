@@ -6,11 +6,14 @@ pub mod cpu_detector;
 pub mod gpu_detector;
 pub mod hash;
 pub mod math;
+pub mod profiling;
 pub mod resolution_detector;
+pub mod shader_watcher;
 
 pub use cpu_detector::{CPUDetector, CPUInfo};
-pub use gpu_detector::{GPUDetector, GPUInfo};
-pub use resolution_detector::ResolutionDetector;
+pub use gpu_detector::{gpu_preference, set_gpu_preference, GPUDetector, GPUInfo, GpuPreference, GpuSelection};
+pub use resolution_detector::{MonitorInfo, ResolutionDetector};
+pub use shader_watcher::ShaderWatcher;
 
 // Re-export glam for convenience
 pub use glam;
@@ -1,4 +1,5 @@
 use winit::event_loop::ActiveEventLoop;
+use winit::monitor::MonitorHandle;
 
 pub struct ResolutionDetector;
 
@@ -30,13 +31,13 @@ impl ResolutionDetector {
             let mon_width_logical = size.width as f64 / scale;
             let mon_height_logical = size.height as f64 / scale;
 
-            println!(
+            log::info!(
                 "Detected Monitor: {} ({:?})",
                 monitor.name().unwrap_or_default(),
                 size
             );
-            println!("  Scale Factor: {}", scale);
-            println!(
+            log::info!("  Scale Factor: {}", scale);
+            log::info!(
                 "  Logical Size: {}x{}",
                 mon_width_logical, mon_height_logical
             );
@@ -52,10 +53,10 @@ impl ResolutionDetector {
                 target_height as f64
             };
 
-            println!("  Selected Resolution: {}x{}", w, h);
+            log::info!("  Selected Resolution: {}x{}", w, h);
             (w, h)
         } else {
-            println!("No monitor detected, using default target.");
+            log::info!("No monitor detected, using default target.");
             (target_width as f64, target_height as f64)
         }
     }
@@ -65,10 +66,21 @@ impl ResolutionDetector {
             .primary_monitor()
             .or_else(|| event_loop.available_monitors().next())?;
 
+        Some(Self::monitor_info(&monitor))
+    }
+
+    pub fn get_all_monitors(event_loop: &ActiveEventLoop) -> Vec<MonitorInfo> {
+        event_loop
+            .available_monitors()
+            .map(|monitor| Self::monitor_info(&monitor))
+            .collect()
+    }
+
+    pub fn monitor_info(monitor: &MonitorHandle) -> MonitorInfo {
         let size = monitor.size();
         let scale = monitor.scale_factor();
 
-        Some(MonitorInfo {
+        MonitorInfo {
             name: monitor.name().unwrap_or_default(),
             physical_width: size.width,
             physical_height: size.height,
@@ -76,27 +88,7 @@ impl ResolutionDetector {
             logical_height: size.height as f64 / scale,
             scale_factor: scale,
             refresh_rate: monitor.refresh_rate_millihertz().map(|r| r / 1000),
-        })
-    }
-
-    pub fn get_all_monitors(event_loop: &ActiveEventLoop) -> Vec<MonitorInfo> {
-        event_loop
-            .available_monitors()
-            .map(|monitor| {
-                let size = monitor.size();
-                let scale = monitor.scale_factor();
-
-                MonitorInfo {
-                    name: monitor.name().unwrap_or_default(),
-                    physical_width: size.width,
-                    physical_height: size.height,
-                    logical_width: size.width as f64 / scale,
-                    logical_height: size.height as f64 / scale,
-                    scale_factor: scale,
-                    refresh_rate: monitor.refresh_rate_millihertz().map(|r| r / 1000),
-                }
-            })
-            .collect()
+        }
     }
 
     pub fn suggest_resolution(monitor: &MonitorInfo, target_aspect: f32) -> (u32, u32) {
@@ -0,0 +1,124 @@
+//! Shader Watcher
+//!
+//! Sondea la fecha de modificación de ficheros `.spv` y devuelve, en cada
+//! `poll()`, los que cambiaron desde la última llamada. Pensado para llamarse
+//! una vez por frame y alimentar `Material::reload_shaders`.
+//!
+//! A diferencia de `graphics::shader_hot_reload`, no recompila nada ni lanza
+//! hilos de `notify`: sólo informa qué binarios SPIR-V hay que recargar.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub struct ShaderWatcher {
+    files: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl ShaderWatcher {
+    pub fn new() -> Self {
+        Self { files: HashMap::new() }
+    }
+
+    /// Empieza a vigilar `path`. El estado actual se toma como referencia,
+    /// así que no se reporta como cambiado hasta que se modifique.
+    pub fn watch<P: AsRef<Path>>(&mut self, path: P) {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified_time(&path);
+        self.files.insert(path, modified);
+    }
+
+    pub fn unwatch<P: AsRef<Path>>(&mut self, path: P) {
+        self.files.remove(path.as_ref());
+    }
+
+    pub fn watched_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Ficheros cuya fecha de modificación cambió desde el último `poll()`.
+    ///
+    /// Un fichero que desaparece no se reporta (suele ser una escritura a medias
+    /// del compilador); se reporta cuando vuelve a existir.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in self.files.iter_mut() {
+            let current = modified_time(path);
+            if current.is_some() && current != *last {
+                changed.push(path.clone());
+            }
+            if current.is_some() {
+                *last = current;
+            }
+        }
+        changed.sort();
+        changed
+    }
+}
+
+impl Default for ShaderWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Reescribe `path` hasta que su mtime cambie (la resolución depende del
+    /// sistema de ficheros; en algunos es de segundos).
+    fn touch(path: &Path) {
+        let before = modified_time(path);
+        for _ in 0..300 {
+            std::thread::sleep(Duration::from_millis(10));
+            std::fs::write(path, [1u8; 4]).unwrap();
+            if modified_time(path) != before {
+                return;
+            }
+        }
+        panic!("mtime of {} never changed", path.display());
+    }
+
+    #[test]
+    fn reports_only_modified_files() {
+        let dir = std::env::temp_dir().join(format!("reactor_shader_watcher_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let a = dir.join("a.spv");
+        let b = dir.join("b.spv");
+        std::fs::write(&a, [0u8; 4]).unwrap();
+        std::fs::write(&b, [0u8; 4]).unwrap();
+
+        let mut watcher = ShaderWatcher::new();
+        watcher.watch(&a);
+        watcher.watch(&b);
+        assert!(watcher.poll().is_empty());
+
+        touch(&a);
+        assert_eq!(watcher.poll(), vec![a.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file_is_reported_when_it_appears() {
+        let dir = std::env::temp_dir().join(format!("reactor_shader_watcher_missing_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("late.spv");
+
+        let mut watcher = ShaderWatcher::new();
+        watcher.watch(&path);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::write(&path, [0u8; 4]).unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
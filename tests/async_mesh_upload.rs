@@ -0,0 +1,43 @@
+//! Uploads a batch of meshes through `create_mesh_async` and checks that
+//! every ticket yields a complete mesh. The time spent queueing is printed,
+//! not asserted. Needs a Vulkan driver (`--features gpu-tests`).
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use reactor_vulkan::resources::primitives::Primitives;
+
+const MESHES: usize = 200;
+
+#[test]
+fn async_uploads_complete_without_blocking() {
+    let reactor = common::headless(64, 64);
+    let (vertices, indices) = Primitives::sphere(64, 64);
+
+    let queued = Instant::now();
+    let mut tickets: Vec<_> = (0..MESHES).map(|_| reactor.create_mesh_async(&vertices, &indices)).collect();
+    let queue_time = queued.elapsed();
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let mut meshes = Vec::with_capacity(MESHES);
+    while !tickets.is_empty() {
+        assert!(Instant::now() < deadline, "{} uploads still pending", tickets.len());
+        tickets.retain(|&ticket| match reactor.poll_ticket(ticket) {
+            Some(result) => {
+                meshes.push(result.expect("upload succeeded"));
+                false
+            }
+            None => true,
+        });
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(meshes.len(), MESHES);
+    for mesh in &meshes {
+        assert_eq!(mesh.vertex_count as usize, vertices.len());
+        assert_eq!(mesh.index_count as usize, indices.len());
+    }
+    // Queueing only copies the vertex data on the calling thread.
+    println!("queueing {} meshes took {:?}", MESHES, queue_time);
+}
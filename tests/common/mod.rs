@@ -0,0 +1,32 @@
+//! Setup shared by the GPU integration tests.
+//!
+//! Those tests need a Vulkan driver (a software one such as lavapipe is
+//! enough) and only build with `--features gpu-tests`. Without a usable
+//! device they fail instead of reporting `ok` with nothing checked.
+
+#![allow(dead_code)]
+
+use reactor_vulkan::{Material, Reactor};
+use std::sync::Arc;
+
+/// Headless reactor rendering `width`×`height`; panics without a device.
+pub fn headless(width: u32, height: u32) -> Reactor {
+    Reactor::init_headless(width, height)
+        .unwrap_or_else(|e| panic!("gpu-tests need a usable Vulkan device: {}", e))
+}
+
+pub fn spirv(bytes: &[u8]) -> Vec<u32> {
+    ash::util::read_spv(&mut std::io::Cursor::new(bytes)).expect("valid SPIR-V")
+}
+
+/// Material built from the core `vert.spv` / `frag.spv` pair.
+pub fn default_material(reactor: &Reactor) -> Arc<Material> {
+    Arc::new(
+        reactor
+            .create_material(
+                &spirv(include_bytes!("../../shaders/vert.spv")),
+                &spirv(include_bytes!("../../shaders/frag.spv")),
+            )
+            .unwrap(),
+    )
+}
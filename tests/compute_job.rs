@@ -0,0 +1,63 @@
+//! `ComputeJob` runs a "multiply array by 2" shader over a storage buffer and
+//! `Buffer::read` returns the doubled values once `dispatch` returns. Needs a
+//! Vulkan driver (`--features gpu-tests`).
+
+mod common;
+
+use ash::vk;
+use reactor_vulkan::compute::ComputeJob;
+use reactor_vulkan::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+
+const COUNT: u32 = 1000;
+const LOCAL_SIZE: u32 = 64;
+
+const DOUBLE_COMP: &str = r#"#version 450
+layout(local_size_x = 64) in;
+
+layout(set = 0, binding = 0) buffer Values {
+    float values[];
+};
+
+layout(push_constant) uniform Params {
+    uint count;
+} params;
+
+void main() {
+    uint i = gl_GlobalInvocationID.x;
+    if (i < params.count) {
+        values[i] *= 2.0;
+    }
+}
+"#;
+
+#[test]
+fn doubles_a_storage_buffer_and_reads_it_back() {
+    let reactor = common::headless(64, 64);
+    let shader = ShaderCompiler::new()
+        .compile_source(DOUBLE_COMP, ShaderLanguage::Glsl, ShaderStage::Compute, "main")
+        .unwrap();
+    let mut job = ComputeJob::new(&reactor.context, &shader.spirv, 1, Some(4)).unwrap();
+
+    let input: Vec<f32> = (0..COUNT).map(|i| i as f32 - 100.5).collect();
+    let buffer = ComputeJob::create_buffer(
+        &reactor.context,
+        reactor.allocator.clone(),
+        std::mem::size_of_val(input.as_slice()) as u64,
+        vk::BufferUsageFlags::empty(),
+    )
+    .unwrap();
+    buffer.write(&input);
+    job.bind_buffer(0, &buffer).unwrap();
+
+    let groups = (COUNT + LOCAL_SIZE - 1) / LOCAL_SIZE;
+    job.dispatch(&reactor.context, [groups, 1, 1], &COUNT.to_ne_bytes()).unwrap();
+
+    let mut output = vec![0.0f32; COUNT as usize];
+    assert!(buffer.read(&mut output));
+    for (i, (&before, &after)) in input.iter().zip(&output).enumerate() {
+        assert_eq!(after, before * 2.0, "value {}", i);
+    }
+
+    // An empty dispatch is rejected before anything is submitted.
+    assert!(job.dispatch(&reactor.context, [0, 1, 1], &COUNT.to_ne_bytes()).is_err());
+}
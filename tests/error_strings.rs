@@ -0,0 +1,68 @@
+//! Error messages are copied into caller buffers under the error lock, so
+//! readers never see a message that another thread is replacing. Runs in its
+//! own test binary because it owns the global last-error state.
+
+use reactor_vulkan::core::error::{
+    clear_last_error, last_error_message_into, last_error_message_len, set_last_error, ErrorCode,
+};
+use reactor_vulkan::ReactorError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+const MESSAGES: [&str; 2] = [
+    "first thread: texture 'piedra_ñandú.png' not found",
+    "second thread: pipeline creation failed after a much longer explanation of why",
+];
+
+#[test]
+fn concurrent_errors_never_corrupt_copied_messages() {
+    clear_last_error();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let writers: Vec<_> = MESSAGES
+        .iter()
+        .map(|&message| {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    set_last_error(ReactorError::new(ErrorCode::InternalError, message));
+                }
+            })
+        })
+        .collect();
+
+    let readers: Vec<_> = [256usize, 24]
+        .into_iter()
+        .map(|capacity| {
+            thread::spawn(move || {
+                let mut buffer = vec![0u8; capacity];
+                for _ in 0..20_000 {
+                    let written = last_error_message_into(&mut buffer);
+                    assert_eq!(buffer[written], 0, "missing terminator");
+                    let text = std::str::from_utf8(&buffer[..written]).expect("valid UTF-8");
+                    assert!(
+                        text.is_empty() || MESSAGES.iter().any(|message| message.starts_with(text)),
+                        "torn message: {:?}",
+                        text
+                    );
+                    if capacity > MESSAGES[1].len() && !text.is_empty() {
+                        assert!(MESSAGES.contains(&text), "unexpected truncation: {:?}", text);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    stop.store(true, Ordering::Relaxed);
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    assert!(MESSAGES.iter().any(|message| message.len() == last_error_message_len()));
+    clear_last_error();
+    assert_eq!(last_error_message_len(), 0);
+}
@@ -0,0 +1,354 @@
+//! Golden-image smoke test for the headless renderer.
+//!
+//! Needs a Vulkan driver (a software one such as lavapipe is enough), so it
+//! only builds with `--features gpu-tests`.
+
+mod common;
+
+use reactor_vulkan::core::error::ErrorCode;
+use reactor_vulkan::core::RenderScalePreset;
+use reactor_vulkan::graphics::post_process::PostProcessPreset;
+use reactor_vulkan::graphics::shader_compiler::{ShaderCompiler, ShaderLanguage, ShaderStage};
+use reactor_vulkan::graphics::uniform_buffer::MaterialUniformData;
+use reactor_vulkan::resources::pbr_material::PBRMaterial;
+use reactor_vulkan::resources::primitives::Primitives;
+use reactor_vulkan::systems::particles::{EmitterShape, ParticleSystem, ParticleSystemConfig, RandomRange};
+use reactor_vulkan::systems::scene::SceneObject;
+use reactor_vulkan::systems::sdf::SdfScene;
+use reactor_vulkan::{Camera, Mat4, Reactor, Scene, Vec3, Vec4};
+use std::sync::Arc;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+
+#[test]
+fn renders_default_cube() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+    let mut scene = Scene::new();
+    scene.add_object(mesh, material, Mat4::IDENTITY);
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(2.0, 2.0, 3.0), Vec3::ZERO);
+    reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+
+    let pixels = reactor.read_pixels().unwrap();
+    assert_eq!(pixels.len(), (WIDTH * HEIGHT * 4) as usize);
+    let pixel = |x: u32, y: u32| {
+        let offset = ((y * WIDTH + x) * 4) as usize;
+        [pixels[offset], pixels[offset + 1], pixels[offset + 2], pixels[offset + 3]]
+    };
+
+    // Corners show the clear color: opaque neutral gray.
+    for (x, y) in [(0, 0), (WIDTH - 1, 0), (0, HEIGHT - 1), (WIDTH - 1, HEIGHT - 1)] {
+        let [r, g, b, a] = pixel(x, y);
+        assert_eq!(a, 255);
+        assert!(r == g && g == b, "corner ({}, {}) is {:?}, expected the gray clear color", x, y, [r, g, b, a]);
+    }
+    assert_eq!(pixel(0, 0), pixel(WIDTH - 1, HEIGHT - 1));
+
+    // The cube covers the middle of the frame.
+    assert_ne!(pixel(WIDTH / 2, HEIGHT / 2), pixel(0, 0));
+}
+
+#[test]
+fn offscreen_target_follows_viewport_size() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    let scene = Scene::new();
+    reactor.draw_scene(&scene, &Mat4::IDENTITY).unwrap();
+    assert_eq!(reactor.render_stats().frame_time, 0.0);
+
+    // An editor panel shrinking to a wide, short rect.
+    reactor.recreate_swapchain_sized(48, 20).unwrap();
+    assert!(reactor.read_pixels().is_err(), "the old frame is gone with the old images");
+    reactor.draw_scene(&scene, &Mat4::IDENTITY).unwrap();
+    assert_eq!(reactor.read_pixels().unwrap().len(), 48 * 20 * 4);
+    assert!(reactor.render_stats().frame_time > 0.0);
+    assert!(reactor.render_stats().fps() > 0.0);
+}
+
+#[test]
+fn render_scale_presets_switch_without_recreating_the_swapchain() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::Cinematic);
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+    let mut scene = Scene::new();
+    scene.add_object(mesh, material, Mat4::IDENTITY);
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(2.0, 2.0, 3.0), Vec3::ZERO);
+
+    reactor.set_render_scale_enabled(true);
+    reactor.set_render_scale_preset(RenderScalePreset::Performance);
+    reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+    let stats = reactor.render_scale_stats();
+    assert!(stats.applied, "render scale is not available on this device");
+    assert_eq!(stats.render_extent.width, 38);
+    assert!(stats.estimated_gpu_savings > 0.5);
+
+    let swapchain_images = reactor.swapchain.images.clone();
+    reactor.set_render_scale_preset(RenderScalePreset::Quality);
+    reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+    assert_eq!(reactor.swapchain.images, swapchain_images);
+    assert_eq!(reactor.render_scale_stats().render_extent.width, 54);
+
+    // The upscaled cube still covers the middle of the full-size frame.
+    let pixels = reactor.read_pixels().unwrap();
+    assert_eq!(pixels.len(), (WIDTH * HEIGHT * 4) as usize);
+    let pixel = |x: u32, y: u32| {
+        let offset = ((y * WIDTH + x) * 4) as usize;
+        &pixels[offset..offset + 4]
+    };
+    assert_ne!(pixel(WIDTH / 2, HEIGHT / 2), pixel(0, 0));
+}
+
+#[test]
+fn raymarches_an_sdf_scene() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+    assert!(reactor.sdf_pipeline.is_some(), "the raymarch shaders compile through naga");
+
+    let mut sdf = SdfScene::new();
+    sdf.add_sphere(Vec3::ZERO, 1.0, Vec3::new(1.0, 0.2, 0.2));
+    reactor.set_sdf_scene(&sdf);
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
+    reactor.draw_scene(&Scene::new(), &camera.view_projection_matrix()).unwrap();
+
+    let pixels = reactor.read_pixels().unwrap();
+    let pixel = |x: u32, y: u32| {
+        let offset = ((y * WIDTH + x) * 4) as usize;
+        [pixels[offset], pixels[offset + 1], pixels[offset + 2]]
+    };
+    let [r, g, b] = pixel(WIDTH / 2, HEIGHT / 2);
+    assert!(r > g && r > b, "the sphere is red, got {:?}", [r, g, b]);
+    assert_eq!(pixel(0, 0), [pixel(0, 0)[0]; 3], "corners keep the gray clear color");
+}
+
+#[test]
+fn rt_shadows_are_rejected_without_ray_tracing() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    // `init_headless` never enables ray tracing.
+    let err = reactor.set_rt_shadows_enabled(true).unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotSupported);
+    assert!(!reactor.rt_shadows_enabled());
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+    let mut scene = Scene::new();
+    scene.add_object(mesh, material, Mat4::IDENTITY);
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(2.0, 2.0, 3.0), Vec3::ZERO);
+    reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+    reactor.set_rt_shadows_enabled(false).unwrap();
+}
+
+/// Paints the material's base color, read from its UBO (set 0, binding 4).
+const MATERIAL_COLOR_FRAG: &str = r#"
+#version 450
+layout(set = 0, binding = 4) uniform MaterialBlock {
+    vec4 base_color;
+    float metallic;
+    float roughness;
+    float ao;
+    float emissive_strength;
+    vec4 emissive_color;
+    uint use_textures;
+} material;
+layout(location = 0) out vec4 outColor;
+void main() {
+    outColor = vec4(material.base_color.rgb, 1.0);
+}
+"#;
+
+#[test]
+fn material_params_reach_the_shader() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let vert = common::spirv(include_bytes!("../shaders/vert.spv"));
+    let frag = ShaderCompiler::new()
+        .compile_source(MATERIAL_COLOR_FRAG, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")
+        .unwrap()
+        .spirv;
+    let colored = |color: [f32; 4]| {
+        let params = MaterialUniformData { base_color: color, ..Default::default() };
+        Arc::new(reactor.create_material_with_params(&vert, &frag, params).unwrap())
+    };
+    let red = colored([1.0, 0.0, 0.0, 1.0]);
+    let blue = colored([0.0, 0.0, 1.0, 1.0]);
+    assert!(red.has_uniforms());
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let mut scene = Scene::new();
+    scene.add_object(mesh.clone(), red.clone(), Mat4::from_translation(Vec3::new(-1.2, 0.0, 0.0)));
+    scene.add_object(mesh, blue, Mat4::from_translation(Vec3::new(1.2, 0.0, 0.0)));
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(0.0, 0.0, 6.0), Vec3::ZERO);
+    let (left, right) = (WIDTH / 2 - 15, WIDTH / 2 + 15);
+    let read = |reactor: &mut Reactor| {
+        reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+        let pixels = reactor.read_pixels().unwrap();
+        let pixel = |x: u32| {
+            let offset = ((HEIGHT / 2 * WIDTH + x) * 4) as usize;
+            [pixels[offset], pixels[offset + 1], pixels[offset + 2]]
+        };
+        (pixel(left), pixel(right))
+    };
+
+    let (l, r) = read(&mut reactor);
+    assert!(l[0] > l[2], "left cube is red, got {:?}", l);
+    assert!(r[2] > r[0], "right cube is blue, got {:?}", r);
+
+    // Runtime edits reach the same pipeline.
+    red.set_color([0.0, 1.0, 0.0, 1.0]);
+    let (l, _) = read(&mut reactor);
+    assert!(l[1] > l[0] && l[1] > l[2], "left cube turned green, got {:?}", l);
+}
+
+/// Premultiplied base color with the material's alpha.
+const GLASS_FRAG: &str = r#"
+#version 450
+layout(set = 0, binding = 4) uniform MaterialBlock {
+    vec4 base_color;
+} material;
+layout(location = 0) out vec4 outColor;
+void main() {
+    outColor = vec4(material.base_color.rgb * material.base_color.a, material.base_color.a);
+}
+"#;
+
+#[test]
+fn glass_panes_blend_back_to_front_from_both_sides() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let vert = common::spirv(include_bytes!("../shaders/vert.spv"));
+    let frag = ShaderCompiler::new()
+        .compile_source(GLASS_FRAG, ShaderLanguage::Glsl, ShaderStage::Fragment, "main")
+        .unwrap()
+        .spirv;
+    let glass = |tint: Vec3| {
+        let pbr = PBRMaterial::glass(tint).with_alpha(0.5);
+        Arc::new(reactor.create_material_pbr(&vert, &frag, &pbr).unwrap())
+    };
+    let red = glass(Vec3::X);
+    let blue = glass(Vec3::Z);
+    assert!(red.is_transparent());
+
+    // Thin slabs, the red one nearer +Z and added first.
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let slab = |z: f32| Mat4::from_translation(Vec3::new(0.0, 0.0, z)) * Mat4::from_scale(Vec3::new(2.0, 2.0, 0.05));
+    let mut scene = Scene::new();
+    scene.add_object(mesh.clone(), red, slab(0.5));
+    scene.add_object(mesh, blue, slab(-0.5));
+
+    let mut center_from = |eye: Vec3| {
+        let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+        camera.aim_at(eye, Vec3::ZERO);
+        reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+        let pixels = reactor.read_pixels().unwrap();
+        let offset = ((HEIGHT / 2 * WIDTH + WIDTH / 2) * 4) as usize;
+        [pixels[offset], pixels[offset + 1], pixels[offset + 2]]
+    };
+
+    // The nearer pane dominates whichever side the camera is on.
+    let front = center_from(Vec3::new(0.0, 0.0, 6.0));
+    assert!(front[0] > front[2], "red pane is in front, got {:?}", front);
+    let back = center_from(Vec3::new(0.0, 0.0, -6.0));
+    assert!(back[2] > back[0], "blue pane is in front, got {:?}", back);
+}
+
+#[test]
+fn ten_thousand_particles_are_one_draw_call() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let (vertices, indices) = Primitives::quad();
+    let quad = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let quad_triangles = indices.len() as u64 / 3;
+    let material = Arc::new(reactor.create_particle_material().unwrap());
+    assert!(material.is_transparent());
+
+    // A still cloud of orange particles around the origin, all from one burst.
+    let mut system = ParticleSystem::new(ParticleSystemConfig {
+        max_particles: 10_000,
+        emission_rate: 0.0,
+        burst_count: 10_000,
+        shape: EmitterShape::Sphere { radius: 1.0 },
+        lifetime: RandomRange::constant(10.0),
+        start_speed: RandomRange::constant(0.0),
+        start_size: RandomRange::constant(0.2),
+        start_color: Vec4::new(1.0, 0.5, 0.0, 1.0),
+        ..Default::default()
+    });
+    system.update(1.0 / 60.0);
+    assert_eq!(system.alive_count(), 10_000);
+
+    let mut scene = Scene::new();
+    let handle = scene.insert(SceneObject::new(quad, material, Mat4::IDENTITY));
+    let object = scene.get_mut(handle).unwrap();
+    system.fill_instances(&mut object.instances);
+    object.refresh_bounds();
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
+    reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+    let stats = reactor.render_stats();
+    assert_eq!(stats.draw_calls, 1);
+    assert_eq!(stats.instances, 10_000);
+    assert_eq!(stats.triangles, quad_triangles * 10_000);
+
+    let pixels = reactor.read_pixels().unwrap();
+    let offset = ((HEIGHT / 2 * WIDTH + WIDTH / 2) * 4) as usize;
+    let center = [pixels[offset], pixels[offset + 1], pixels[offset + 2]];
+    assert!(center[0] > center[2] && center[0] > 0, "particles add orange light, got {:?}", center);
+}
+
+#[test]
+fn screenshot_writes_a_decodable_png() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+    let mut scene = Scene::new();
+    scene.add_object(mesh, material, Mat4::IDENTITY);
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(2.0, 2.0, 3.0), Vec3::ZERO);
+    let view_projection = camera.view_projection_matrix();
+
+    let dir = std::env::temp_dir().join(format!("reactor-screenshot-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("frame.png");
+    let missing = dir.join("missing").join("frame.png");
+    let error = reactor.screenshot(&missing).unwrap_err();
+    assert_eq!(error.code, ErrorCode::IoError);
+    assert!(!missing.exists());
+
+    // The copy is recorded in the next frame and written when the one after starts.
+    reactor.screenshot(&path).unwrap();
+    reactor.draw_scene(&scene, &view_projection).unwrap();
+    assert!(!path.exists(), "nothing is written before the capture resolves");
+    reactor.draw_scene(&scene, &view_projection).unwrap();
+
+    let png = image::open(&path).unwrap().to_rgba8();
+    assert_eq!(png.dimensions(), (WIDTH, HEIGHT));
+    assert_eq!(png.into_raw(), reactor.read_pixels().unwrap());
+    let leftovers: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(leftovers, ["frame.png"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,45 @@
+//! A panic inside a callback (a poisoned mutex, an unwrap on missing state)
+//! is turned into an error code plus a last-error message instead of
+//! unwinding into the caller. Runs in its own test binary because it owns the
+//! global last-error state.
+
+use reactor_vulkan::core::error::{catch_panic, clear_last_error, get_last_error_code, get_last_error_message, ErrorCode};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn panicking_callback_reports_an_error_and_the_process_survives() {
+    clear_last_error();
+
+    // Poison a lock the way a crashed worker thread would.
+    let state = Arc::new(Mutex::new(0u32));
+    let poisoner = state.clone();
+    let _ = thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("worker died holding the lock");
+    })
+    .join();
+    assert!(state.is_poisoned());
+
+    let frame_callback = || -> reactor_vulkan::ReactorResult<u32> { Ok(*state.lock().unwrap() + 1) };
+    let error = catch_panic("frame callback", frame_callback).unwrap_err();
+    assert_eq!(error.code, ErrorCode::Unknown);
+    assert!(error.message.contains("frame callback"), "{}", error.message);
+    assert!(error.message.contains("PoisonError"), "{}", error.message);
+    assert_eq!(get_last_error_code(), ErrorCode::Unknown);
+    assert_eq!(get_last_error_message().as_deref(), Some(error.message.as_str()));
+
+    let error = catch_panic("update callback", || -> reactor_vulkan::ReactorResult<u32> {
+        panic!("no active scene")
+    })
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Unknown);
+    assert_eq!(error.message, "panic in update callback: no active scene");
+    assert_eq!(get_last_error_code(), ErrorCode::Unknown);
+    assert_eq!(get_last_error_message().as_deref(), Some(error.message.as_str()));
+
+    // Callbacks that don't panic pass their result through untouched.
+    assert_eq!(catch_panic("render callback", || Ok(7)).unwrap(), 7);
+    clear_last_error();
+    assert_eq!(get_last_error_code(), ErrorCode::None);
+}
@@ -0,0 +1,37 @@
+//! Times `profile_scope!` with and without a capture running. Outside a
+//! capture a scope is one relaxed load; the timings are only printed
+//! (`--nocapture`), since wall-clock numbers are too noisy on shared CI
+//! runners to assert on. The capture itself must fill up and count the rest
+//! as dropped.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use reactor_vulkan::profile_scope;
+use reactor_vulkan::utils::profiling;
+
+const SCOPES: u32 = 1_000_000;
+
+fn time_scopes() -> Duration {
+    let start = Instant::now();
+    for i in 0..SCOPES {
+        profile_scope!("bench");
+        black_box(i);
+    }
+    start.elapsed()
+}
+
+#[test]
+fn capture_fills_up_and_counts_dropped_scopes() {
+    let idle = time_scopes();
+    profiling::start_capture();
+    let capturing = time_scopes();
+    let capture = profiling::stop_capture();
+
+    let idle_ns = idle.as_nanos() as f64 / SCOPES as f64;
+    let capturing_ns = capturing.as_nanos() as f64 / SCOPES as f64;
+    eprintln!("profile_scope!: {:.1} ns idle, {:.1} ns capturing", idle_ns, capturing_ns);
+
+    assert_eq!(capture.events.len(), profiling::CAPACITY);
+    assert_eq!(capture.dropped, SCOPES as usize - profiling::CAPACITY);
+}
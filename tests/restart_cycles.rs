@@ -0,0 +1,67 @@
+//! Opens and closes the renderer twice in one process, as a launcher would,
+//! then once more from a caller-owned loop through `PumpedApp`.
+//! Needs a display and a Vulkan driver, and the main thread (winit), so it
+//! has its own `main`:
+//!
+//!     cargo test --features display-tests --test restart_cycles
+
+use std::sync::mpsc;
+
+use reactor_vulkan::app::ReactorContext;
+use reactor_vulkan::{try_run, PumpedApp, ReactorApp, ReactorConfig};
+
+const FRAMES: u32 = 5;
+
+/// Counts frames, asks to exit after `FRAMES` and reports the count from
+/// `on_exit`, since `try_run` consumes the app.
+struct Cycle {
+    frames: u32,
+    report: mpsc::Sender<u32>,
+}
+
+impl ReactorApp for Cycle {
+    fn config(&self) -> ReactorConfig {
+        ReactorConfig::new("restart_cycles").with_size(160, 120)
+    }
+
+    fn init(&mut self, _ctx: &mut ReactorContext) {}
+
+    fn update(&mut self, ctx: &mut ReactorContext) {
+        self.frames += 1;
+        if self.frames >= FRAMES {
+            ctx.reactor.exit_requested = true;
+        }
+    }
+
+    fn on_exit(&mut self, _ctx: &mut ReactorContext) {
+        let _ = self.report.send(self.frames);
+    }
+}
+
+fn main() {
+    for cycle in 1..=2 {
+        let (report, frames) = mpsc::channel();
+        if let Err(e) = try_run(Cycle { frames: 0, report }) {
+            panic!("cycle {} failed: {}", cycle, e);
+        }
+        let frames = frames.recv().unwrap_or_else(|_| panic!("cycle {} never reached on_exit", cycle));
+        assert!(frames >= FRAMES, "cycle {}: only {} frames", cycle, frames);
+        println!("cycle {}: {} frames", cycle, frames);
+    }
+
+    // The host owns the loop and pumps REACTOR once per iteration.
+    let (report, frames) = mpsc::channel();
+    let mut pumped = PumpedApp::new(Cycle { frames: 0, report }).expect("event loop available again");
+    let mut iterations = 0;
+    while !pumped.poll_events().expect("pumped cycle failed") {
+        iterations += 1;
+        assert!(iterations < 10_000, "pumped app never closed");
+    }
+    assert!(pumped.context().is_none(), "window still alive after close");
+    drop(pumped);
+    let frames = frames.recv().expect("pumped cycle never reached on_exit");
+    assert!(frames >= FRAMES, "pumped cycle: only {} frames", frames);
+    println!("pumped cycle: {} frames over {} polls", frames, iterations);
+
+    println!("restart_cycles: ok");
+}
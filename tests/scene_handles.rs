@@ -0,0 +1,64 @@
+//! `SceneHandle` keeps pointing at the same object while others are removed,
+//! swap-removed or regenerated in the dynamic group, and stops resolving once
+//! its own object is gone. Objects need a real mesh and material, so this
+//! needs a Vulkan driver (`--features gpu-tests`).
+
+mod common;
+
+use reactor_vulkan::resources::primitives::Primitives;
+use reactor_vulkan::systems::scene::SceneObject;
+use reactor_vulkan::{Mat4, Scene};
+use std::sync::Arc;
+
+#[test]
+fn handles_survive_removal_of_other_objects() {
+    let reactor = common::headless(64, 64);
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+    let object = |name: &str| SceneObject::new(mesh.clone(), material.clone(), Mat4::IDENTITY).with_name(name);
+    let name_of = |scene: &Scene, handle| scene.get(handle).and_then(|o: &SceneObject| o.name.clone());
+
+    let mut scene = Scene::new();
+    let handles: Vec<_> = ["a", "b", "c", "d", "e"].map(|name| scene.insert(object(name))).into();
+
+    // Order-preserving removal shifts indices, not handles.
+    assert_eq!(scene.remove(handles[1]).unwrap().name.as_deref(), Some("b"));
+    assert!(!scene.contains(handles[1]));
+    assert_eq!(scene.index_of_handle(handles[2]), Some(1));
+    assert_eq!(name_of(&scene, handles[4]).as_deref(), Some("e"));
+
+    // Swap removal moves the last object into the gap.
+    scene.swap_remove(handles[0]).unwrap();
+    assert_eq!(scene.index_of_handle(handles[4]), Some(0));
+    assert_eq!(scene.handle_of(0), Some(handles[4]));
+    assert_eq!(name_of(&scene, handles[3]).as_deref(), Some("d"));
+
+    // A reused slot does not bring stale handles back to life.
+    let f = scene.insert(object("f"));
+    assert!(f.slot() == handles[0].slot() || f.slot() == handles[1].slot());
+    assert_eq!(scene.get(handles[0]).map(|o| o.id), None);
+    assert_eq!(scene.get(handles[1]).map(|o| o.id), None);
+
+    // The dynamic group is rebuilt every frame behind the persistent objects.
+    for _ in 0..3 {
+        scene.clear_dynamic();
+        for i in 0..10 {
+            scene.add_dynamic(object(&format!("particle {i}")));
+        }
+        let g = scene.insert(object("g"));
+        assert_eq!(scene.index_of_handle(g), Some(scene.len() - scene.dynamic_len() - 1));
+        scene.remove(g);
+    }
+    assert_eq!(scene.dynamic_len(), 10);
+    scene.clear_dynamic();
+    assert_eq!(scene.len(), 4);
+    for (handle, name) in [(handles[2], "c"), (handles[3], "d"), (handles[4], "e"), (f, "f")] {
+        assert_eq!(name_of(&scene, handle).as_deref(), Some(name));
+    }
+
+    // Index-based callers keep working.
+    assert_eq!(scene.get(0).and_then(|o| o.name.as_deref()), Some("e"));
+    scene.clear();
+    assert!(!scene.contains(f));
+}
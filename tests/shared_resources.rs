@@ -0,0 +1,53 @@
+//! Scene objects share meshes and materials through `Arc`: adding one to
+//! many objects clones the reference, and dropping the caller's handle while
+//! objects still use it is safe. Needs a Vulkan driver
+//! (`--features gpu-tests`).
+
+mod common;
+
+use reactor_vulkan::graphics::post_process::PostProcessPreset;
+use reactor_vulkan::resources::primitives::Primitives;
+use reactor_vulkan::{Camera, Mat4, Scene, Vec3};
+use std::sync::Arc;
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 64;
+const OBJECTS: usize = 100;
+
+#[test]
+fn one_mesh_shared_by_many_objects_outlives_its_handle() {
+    let mut reactor = common::headless(WIDTH, HEIGHT);
+    reactor.post_process.apply_preset(PostProcessPreset::None);
+
+    let (vertices, indices) = Primitives::cube();
+    let mesh = Arc::new(reactor.create_mesh(&vertices, &indices).unwrap());
+    let material = common::default_material(&reactor);
+
+    let mut scene = Scene::new();
+    for i in 0..OBJECTS {
+        let x = (i % 10) as f32 - 4.5;
+        let z = (i / 10) as f32 - 4.5;
+        scene.add_object(mesh.clone(), material.clone(), Mat4::from_translation(Vec3::new(x, 0.0, z) * 0.5));
+    }
+    assert_eq!(Arc::strong_count(&mesh), OBJECTS + 1);
+
+    // Destroy the caller's handles; the scene keeps the resources alive.
+    let weak_mesh = Arc::downgrade(&mesh);
+    drop(mesh);
+    drop(material);
+
+    let mut camera = Camera::perspective(45.0, WIDTH as f32 / HEIGHT as f32, 0.1, 100.0);
+    camera.aim_at(Vec3::new(0.0, 6.0, 6.0), Vec3::ZERO);
+    for _ in 0..3 {
+        reactor.draw_scene(&scene, &camera.view_projection_matrix()).unwrap();
+    }
+    let pixels = reactor.read_pixels().unwrap();
+    assert_eq!(pixels.len(), (WIDTH * HEIGHT * 4) as usize);
+
+    // Removing the last object releases the mesh.
+    while !scene.objects.is_empty() {
+        scene.remove(0);
+    }
+    reactor.context.wait_idle().unwrap();
+    assert_eq!(weak_mesh.strong_count(), 0);
+}